@@ -1,15 +1,43 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
+
+use chrono::{TimeDelta, Utc};
 
 use wishlist::{
-    application::{user, wishlist as wish, Service},
+    application::{
+        account, exchange, follow, group, image, item, notification, profile, user, webhook,
+        wishlist as wish, Service,
+    },
+    domain::{
+        AccountService, ItemService, NotificationService, RetryPolicy, WebhookService,
+        WishlistService,
+    },
     infrastructure::{
-        config::Config,
+        clock::SystemClock,
+        config::{self, Config},
+        events::{InMemoryItemEventBus, WebhookItemEventBus},
+        fetch::{HttpFetcher, HttpFetcherConfig},
+        imaging::RasterImageProcessor,
         logging,
+        mail::LoggingMailTransport,
+        metadata::extractors,
+        notification::{EmailNotifier, FanOutNotifier, InboxNotifier},
         persistence::in_memory::{
-            user::InMemoryUserRepository, wishlist::InMemoryWishlistRepository,
+            account::InMemoryAccountRepository, exchange::InMemoryExchangeRepository,
+            follow::InMemoryFollowRepository, group::InMemoryGroupRepository,
+            image::InMemoryImageRepository, inbox::InMemoryInboxRepository,
+            item::InMemoryItemRepository, price_history::InMemoryPriceHistoryRepository,
+            profile::InMemoryProfileRepository, reminder::InMemoryReminderRepository,
+            user::InMemoryUserRepository, webhook::InMemoryWebhookRepository,
+            wishlist::InMemoryWishlistRepository,
         },
+        scheduler,
+        storage::local::LocalBlobStore,
+        webhook::{HttpWebhookSender, HttpWebhookSenderConfig},
+    },
+    interface::http::{
+        BucketRate, GraphqlLimits, HttpServer, HttpServerConfig, LockoutPolicy, RateLimitPolicy,
+        RouteLimits,
     },
-    interface::http::{HttpServer, HttpServerConfig},
 };
 
 #[tokio::main]
@@ -26,14 +54,271 @@ async fn main() -> anyhow::Result<()> {
     let user_service = user::Service::new(user_repo.clone());
 
     let wish_repo = Arc::new(InMemoryWishlistRepository::new());
-    let wish_service = wish::Service::new(user_repo.clone(), wish_repo.clone());
+    let item_repo = Arc::new(InMemoryItemRepository::new());
+    let wish_service = wish::Service::new(user_repo.clone(), wish_repo.clone(), item_repo.clone());
+
+    let fetcher = Arc::new(HttpFetcher::new(HttpFetcherConfig {
+        timeout: Duration::from_millis(config.metadata.timeout_ms),
+        max_body_bytes: config.metadata.max_body_bytes,
+        max_redirects: config.metadata.max_redirects,
+        allow_private_networks: config.metadata.allow_private_networks,
+    }));
+
+    let image_service = image::Service::new(
+        Arc::new(LocalBlobStore::new(&config.images.storage_dir)),
+        Arc::new(InMemoryImageRepository::new()),
+        Arc::new(RasterImageProcessor::new(config.images.max_dimension)),
+        fetcher.clone(),
+        config.images.max_upload_bytes,
+    );
+
+    let price_repo = Arc::new(InMemoryPriceHistoryRepository::new());
+    // Notifications are both emailed and kept in the in-app inbox
+    let clock = Arc::new(SystemClock);
+    let inbox_repo = Arc::new(InMemoryInboxRepository::new());
+    let notifier = Arc::new(FanOutNotifier::new(
+        EmailNotifier::new(user_repo.clone(), Arc::new(LoggingMailTransport)),
+        InboxNotifier::new(inbox_repo.clone(), clock.clone()),
+    ));
+    // Wishlists shared with groups are visible to their members, e.g. to reserve their items.
+    // The changes of items are pushed live to the viewers of their wishlist, and queued for the
+    // webhooks registered on it.
+    let group_repo = Arc::new(InMemoryGroupRepository::new());
+    let webhook_repo = Arc::new(InMemoryWebhookRepository::new());
+    let item_service = item::Service::new(
+        wish_repo.clone(),
+        item_repo.clone(),
+        price_repo.clone(),
+        fetcher.clone(),
+        extractors::builtin_extractors(),
+        notifier.clone(),
+        Arc::new(image_service.clone()),
+        group_repo.clone(),
+        Arc::new(WebhookItemEventBus::new(
+            InMemoryItemEventBus::new(),
+            webhook_repo.clone(),
+            clock.clone(),
+        )),
+        clock.clone(),
+    )
+    .with_image_mirroring(config.images.mirror_remote);
+
+    // Periodically re-fetch item prices to build their history and report drops
+    let price_tracker = item_service.clone();
+    scheduler::spawn_periodic(
+        "price_tracking",
+        Duration::from_secs(config.price_tracking.interval_secs),
+        move || {
+            let price_tracker = price_tracker.clone();
+            async move {
+                if let Err(err) = price_tracker.refresh_prices().await {
+                    tracing::error!("failed to refresh prices: {}", err);
+                }
+            }
+        },
+    );
+
+    // Periodically archive the wishlists whose occasion is over, or reopen the recurring ones
+    let occasion_closer = wish_service.clone();
+    scheduler::spawn_periodic(
+        "occasions",
+        Duration::from_secs(config.occasions.interval_secs),
+        move || {
+            let occasion_closer = occasion_closer.clone();
+            async move {
+                if let Err(err) = occasion_closer.close_past_occasions(Utc::now()).await {
+                    tracing::error!("failed to close past occasions: {}", err);
+                }
+            }
+        },
+    );
+
+    let reminder_repo = Arc::new(InMemoryReminderRepository::new());
+    let notification_service = notification::Service::new(
+        user_repo.clone(),
+        wish_repo.clone(),
+        reminder_repo.clone(),
+        inbox_repo.clone(),
+        group_repo.clone(),
+        notifier.clone(),
+        clock.clone(),
+    );
+
+    // Periodically send the occasion reminders that are due
+    let reminder_sender = notification_service.clone();
+    scheduler::spawn_periodic(
+        "reminders",
+        Duration::from_secs(config.reminders.interval_secs),
+        move || {
+            let reminder_sender = reminder_sender.clone();
+            async move {
+                if let Err(err) = reminder_sender.send_due_reminders().await {
+                    tracing::error!("failed to send reminders: {}", err);
+                }
+            }
+        },
+    );
+
+    let exchange_repo = Arc::new(InMemoryExchangeRepository::new());
+    let exchange_service = exchange::Service::new(
+        user_repo.clone(),
+        wish_repo.clone(),
+        exchange_repo.clone(),
+        clock.clone(),
+    );
+
+    let group_service = group::Service::new(
+        user_repo.clone(),
+        wish_repo.clone(),
+        group_repo.clone(),
+        clock.clone(),
+    );
+
+    let follow_repo = Arc::new(InMemoryFollowRepository::new());
+    let follow_service = follow::Service::new(
+        user_repo.clone(),
+        wish_repo.clone(),
+        group_repo.clone(),
+        follow_repo.clone(),
+        clock.clone(),
+    );
 
-    let services = Service::new(user_service, wish_service);
+    // Profiles reveal the shipping address to the users holding a reservation
+    let profile_repo = Arc::new(InMemoryProfileRepository::new());
+    let profile_service = profile::Service::new(
+        user_repo.clone(),
+        wish_repo.clone(),
+        item_repo.clone(),
+        follow_repo.clone(),
+        profile_repo.clone(),
+        Arc::new(image_service.clone()),
+        clock.clone(),
+    );
+
+    // Exports and deletions of accounts are confirmed with a token emailed to the user. Deleted
+    // accounts can be restored during a grace period, then their data and images are purged
+    let account_service = account::Service::new(
+        user_repo.clone(),
+        wish_repo.clone(),
+        item_repo.clone(),
+        price_repo.clone(),
+        group_repo.clone(),
+        follow_repo.clone(),
+        exchange_repo.clone(),
+        inbox_repo.clone(),
+        reminder_repo.clone(),
+        profile_repo.clone(),
+        webhook_repo.clone(),
+        Arc::new(image_service.clone()),
+        Arc::new(InMemoryAccountRepository::new()),
+        Arc::new(LoggingMailTransport),
+        clock.clone(),
+    )
+    .with_grace_period(chrono::Duration::days(config.accounts.deletion_grace_days))
+    .with_confirmation_ttl(chrono::Duration::minutes(
+        config.accounts.confirmation_ttl_mins,
+    ));
+
+    let account_purger = account_service.clone();
+    scheduler::spawn_periodic(
+        "account_purge",
+        Duration::from_secs(config.accounts.interval_secs),
+        move || {
+            let account_purger = account_purger.clone();
+            async move {
+                if let Err(err) = account_purger.purge_deleted_accounts().await {
+                    tracing::error!("failed to purge deleted accounts: {}", err);
+                }
+            }
+        },
+    );
+
+    let webhooks = config.webhooks;
+    let webhook_service = webhook::Service::new(
+        user_repo.clone(),
+        wish_repo.clone(),
+        group_repo.clone(),
+        follow_repo.clone(),
+        webhook_repo.clone(),
+        Arc::new(HttpWebhookSender::new(HttpWebhookSenderConfig {
+            timeout: Duration::from_millis(webhooks.timeout_ms),
+            allow_private_networks: webhooks.allow_private_networks,
+        })),
+        clock.clone(),
+    )
+    .with_retry_policy(RetryPolicy::new(
+        webhooks.max_attempts,
+        TimeDelta::seconds(webhooks.backoff_base_secs),
+        TimeDelta::seconds(webhooks.backoff_max_secs),
+    ));
+
+    // Periodically deliver the queued webhook events, retrying the failed ones with backoff
+    let webhook_deliverer = webhook_service.clone();
+    scheduler::spawn_periodic(
+        "webhooks",
+        Duration::from_secs(webhooks.interval_secs),
+        move || {
+            let webhook_deliverer = webhook_deliverer.clone();
+            async move {
+                if let Err(err) = webhook_deliverer.deliver_due_webhooks().await {
+                    tracing::error!("failed to deliver webhooks: {}", err);
+                }
+            }
+        },
+    );
+
+    let services = Service::new(
+        user_service,
+        wish_service,
+        item_service,
+        image_service,
+        notification_service,
+        exchange_service,
+        group_service,
+        follow_service,
+        profile_service,
+        account_service,
+        webhook_service,
+    );
+
+    // Limit how many requests clients may send, with stricter limits on some routes
+    let bucket =
+        |bucket: config::BucketConfig| BucketRate::new(bucket.capacity, bucket.refill_per_minute);
+    let rate_limits = config.rate_limits;
+    let mut rate_limit_policy = RateLimitPolicy::new(
+        RouteLimits::new(
+            rate_limits.per_ip.map(bucket),
+            rate_limits.per_user.map(bucket),
+        ),
+        LockoutPolicy::new(
+            rate_limits.lockout.max_failures,
+            TimeDelta::seconds(rate_limits.lockout.base_secs),
+            TimeDelta::seconds(rate_limits.lockout.max_secs),
+        ),
+    );
+    for route in rate_limits.routes {
+        rate_limit_policy = rate_limit_policy.with_route(
+            &route.method,
+            &route.path,
+            RouteLimits::new(route.per_ip.map(bucket), route.per_user.map(bucket))
+                .with_lockout(route.lockout),
+        );
+    }
 
     // Initialize the HTTP server
     let server_config = HttpServerConfig {
         port: config.server.port,
         host: config.server.host,
+        max_body_bytes: config.server.max_body_bytes,
+        idempotency_ttl: Duration::from_secs(config.server.idempotency_ttl_secs),
+        idempotency_max_entries: config.server.idempotency_max_entries,
+        public_url: config.server.public_url,
+        rate_limits: rate_limit_policy,
+        graphql: GraphqlLimits {
+            max_depth: config.graphql.max_depth,
+            max_complexity: config.graphql.max_complexity,
+            max_batch: config.graphql.max_batch,
+        },
     };
     let http_server = HttpServer::new(services, server_config).await?;
     http_server.run().await
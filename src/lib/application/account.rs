@@ -0,0 +1,706 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::domain::{
+    AccountAction, AccountConfirmation, AccountConfirmationRequest, AccountDeletion, AccountExport,
+    AccountRepository, AccountService, CancelAccountDeletionError, Clock, ConfirmationToken,
+    ConfirmedAccountRequest, DeleteAccountError, DeleteItemError, Email, ExchangeRepository,
+    ExportAccountError, FindItemByIdRequest, FindUserByIdRequest, FollowRepository,
+    GroupRepository, ImageService, InboxRepository, ItemRepository, MailTransport,
+    PriceHistoryRepository, Profile, ProfileRepository, PurgeAccountsError, ReminderRepository,
+    RequestAccountConfirmationError, User, UserRepository, WebhookRepository, WishlistRepository,
+    DEFAULT_CONFIRMATION_TTL, DEFAULT_DELETION_GRACE_PERIOD,
+};
+
+pub struct Service<U, W, I, H, G, F, X, N, M, P, K, E, A, T, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    I: ItemRepository,
+    H: PriceHistoryRepository,
+    G: GroupRepository,
+    F: FollowRepository,
+    X: ExchangeRepository,
+    N: InboxRepository,
+    M: ReminderRepository,
+    P: ProfileRepository,
+    K: WebhookRepository,
+    E: ImageService,
+    A: AccountRepository,
+    T: MailTransport,
+    C: Clock,
+{
+    user_repository: Arc<U>,
+    wish_repository: Arc<W>,
+    item_repository: Arc<I>,
+    price_repository: Arc<H>,
+    group_repository: Arc<G>,
+    follow_repository: Arc<F>,
+    exchange_repository: Arc<X>,
+    inbox_repository: Arc<N>,
+    reminder_repository: Arc<M>,
+    profile_repository: Arc<P>,
+    webhook_repository: Arc<K>,
+    image_service: Arc<E>,
+    account_repository: Arc<A>,
+    mail_transport: Arc<T>,
+    clock: Arc<C>,
+    grace_period: Duration,
+    confirmation_ttl: Duration,
+}
+
+impl<U, W, I, H, G, F, X, N, M, P, K, E, A, T, C> Clone
+    for Service<U, W, I, H, G, F, X, N, M, P, K, E, A, T, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    I: ItemRepository,
+    H: PriceHistoryRepository,
+    G: GroupRepository,
+    F: FollowRepository,
+    X: ExchangeRepository,
+    N: InboxRepository,
+    M: ReminderRepository,
+    P: ProfileRepository,
+    K: WebhookRepository,
+    E: ImageService,
+    A: AccountRepository,
+    T: MailTransport,
+    C: Clock,
+{
+    fn clone(&self) -> Self {
+        Self {
+            user_repository: self.user_repository.clone(),
+            wish_repository: self.wish_repository.clone(),
+            item_repository: self.item_repository.clone(),
+            price_repository: self.price_repository.clone(),
+            group_repository: self.group_repository.clone(),
+            follow_repository: self.follow_repository.clone(),
+            exchange_repository: self.exchange_repository.clone(),
+            inbox_repository: self.inbox_repository.clone(),
+            reminder_repository: self.reminder_repository.clone(),
+            profile_repository: self.profile_repository.clone(),
+            webhook_repository: self.webhook_repository.clone(),
+            image_service: self.image_service.clone(),
+            account_repository: self.account_repository.clone(),
+            mail_transport: self.mail_transport.clone(),
+            clock: self.clock.clone(),
+            grace_period: self.grace_period,
+            confirmation_ttl: self.confirmation_ttl,
+        }
+    }
+}
+
+impl<U, W, I, H, G, F, X, N, M, P, K, E, A, T, C>
+    Service<U, W, I, H, G, F, X, N, M, P, K, E, A, T, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    I: ItemRepository,
+    H: PriceHistoryRepository,
+    G: GroupRepository,
+    F: FollowRepository,
+    X: ExchangeRepository,
+    N: InboxRepository,
+    M: ReminderRepository,
+    P: ProfileRepository,
+    K: WebhookRepository,
+    E: ImageService,
+    A: AccountRepository,
+    T: MailTransport,
+    C: Clock,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repository: Arc<U>,
+        wish_repository: Arc<W>,
+        item_repository: Arc<I>,
+        price_repository: Arc<H>,
+        group_repository: Arc<G>,
+        follow_repository: Arc<F>,
+        exchange_repository: Arc<X>,
+        inbox_repository: Arc<N>,
+        reminder_repository: Arc<M>,
+        profile_repository: Arc<P>,
+        webhook_repository: Arc<K>,
+        image_service: Arc<E>,
+        account_repository: Arc<A>,
+        mail_transport: Arc<T>,
+        clock: Arc<C>,
+    ) -> Self {
+        Self {
+            user_repository,
+            wish_repository,
+            item_repository,
+            price_repository,
+            group_repository,
+            follow_repository,
+            exchange_repository,
+            inbox_repository,
+            reminder_repository,
+            profile_repository,
+            webhook_repository,
+            image_service,
+            account_repository,
+            mail_transport,
+            clock,
+            grace_period: DEFAULT_DELETION_GRACE_PERIOD,
+            confirmation_ttl: DEFAULT_CONFIRMATION_TTL,
+        }
+    }
+
+    /// Sets how long a deleted account can still be restored before its data is purged.
+    pub fn with_grace_period(self, grace_period: Duration) -> Self {
+        Self {
+            grace_period,
+            ..self
+        }
+    }
+
+    /// Sets how long the confirmation tokens emailed to users can be used.
+    pub fn with_confirmation_ttl(self, confirmation_ttl: Duration) -> Self {
+        Self {
+            confirmation_ttl,
+            ..self
+        }
+    }
+
+    /// Uses up the confirmation of `action` the token of `req` is for, if it has not expired.
+    async fn confirm(
+        &self,
+        req: &ConfirmedAccountRequest,
+        action: AccountAction,
+    ) -> anyhow::Result<bool> {
+        let confirmation = self
+            .account_repository
+            .take_confirmation(req.user_id(), action, &req.token().digest())
+            .await?;
+        Ok(confirmation.is_some_and(|confirmation| !confirmation.is_expired(self.clock.now())))
+    }
+
+    async fn find_user(&self, id: Uuid) -> anyhow::Result<Option<User>> {
+        self.user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(id))
+            .await
+            .map_err(|err| anyhow!(err))
+    }
+
+    /// Deletes or anonymises the data of a user in every repository. Each step can be run again,
+    /// so a purge interrupted halfway is completed on the next run. The user goes last.
+    async fn purge(&self, user_id: Uuid) -> anyhow::Result<()> {
+        let wishlists = self
+            .wish_repository
+            .find_wishlists_by_owners(&[user_id])
+            .await?;
+        for entry in wishlists.iter().flat_map(|wishlist| wishlist.items()) {
+            match self.item_repository.delete(entry.item_id()).await {
+                Ok(()) | Err(DeleteItemError::ItemDoesNotExist { .. }) => {}
+                Err(err) => return Err(anyhow!(err)),
+            }
+        }
+        self.wish_repository.forget_user(user_id).await?;
+        self.item_repository.forget_user(user_id).await?;
+        self.price_repository.forget_user(user_id).await?;
+        self.group_repository.forget_user(user_id).await?;
+        self.follow_repository.forget_user(user_id).await?;
+        self.exchange_repository.forget_user(user_id).await?;
+        self.inbox_repository.forget_user(user_id).await?;
+        self.reminder_repository.forget_user(user_id).await?;
+        self.profile_repository.forget_user(user_id).await?;
+        self.webhook_repository.forget_user(user_id).await?;
+        self.image_service.forget_user(user_id).await?;
+        self.user_repository.forget_user(user_id).await?;
+        Ok(())
+    }
+}
+
+impl<U, W, I, H, G, F, X, N, M, P, K, E, A, T, C> AccountService
+    for Service<U, W, I, H, G, F, X, N, M, P, K, E, A, T, C>
+where
+    U: UserRepository + Send + Sync + 'static,
+    W: WishlistRepository + Send + Sync + 'static,
+    I: ItemRepository + Send + Sync + 'static,
+    H: PriceHistoryRepository + Send + Sync + 'static,
+    G: GroupRepository,
+    F: FollowRepository,
+    X: ExchangeRepository,
+    N: InboxRepository,
+    M: ReminderRepository,
+    P: ProfileRepository,
+    K: WebhookRepository,
+    E: ImageService,
+    A: AccountRepository,
+    T: MailTransport,
+    C: Clock,
+{
+    async fn request_account_confirmation(
+        &self,
+        req: &AccountConfirmationRequest,
+    ) -> Result<AccountConfirmation, RequestAccountConfirmationError> {
+        let user = self
+            .find_user(req.user_id())
+            .await?
+            .ok_or(RequestAccountConfirmationError::UserDoesNotExist { id: req.user_id() })?;
+        if user.anonymous() {
+            return Err(RequestAccountConfirmationError::NoEmail { id: req.user_id() });
+        }
+        let token = ConfirmationToken::generate();
+        let confirmation = AccountConfirmation::new(
+            req.user_id(),
+            req.action(),
+            &token,
+            self.clock.now() + self.confirmation_ttl,
+        );
+        self.account_repository
+            .save_confirmation(&confirmation)
+            .await?;
+        let email = Email::new(
+            user.email().clone(),
+            "Confirm your request".to_string(),
+            format!(
+                "Someone, hopefully you, asked to {}. To confirm, use the token {} before {}. \
+                 If it was not you, ignore this email.",
+                req.action().description(),
+                token,
+                confirmation.expires_at()
+            ),
+        );
+        self.mail_transport
+            .send(&email)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(confirmation)
+    }
+
+    async fn export_account(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> Result<AccountExport, ExportAccountError> {
+        if !self.confirm(req, AccountAction::Export).await? {
+            return Err(ExportAccountError::InvalidConfirmation { id: req.user_id() });
+        }
+        let user = self
+            .find_user(req.user_id())
+            .await?
+            .ok_or(ExportAccountError::UserDoesNotExist { id: req.user_id() })?;
+        let profile = self
+            .profile_repository
+            .find_profile(req.user_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .unwrap_or_else(|| Profile::new(req.user_id()));
+        let wishlists = self
+            .wish_repository
+            .find_wishlists_by_owners(&[req.user_id()])
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let mut items = Vec::new();
+        for entry in wishlists.iter().flat_map(|wishlist| wishlist.items()) {
+            let item = self
+                .item_repository
+                .find_item_by_id(&FindItemByIdRequest::new(entry.item_id()))
+                .await
+                .map_err(|err| anyhow!(err))?;
+            items.extend(item);
+        }
+        let reservations = self
+            .item_repository
+            .find_items_reserved_by(req.user_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let deletion = self
+            .account_repository
+            .find_deletion(req.user_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(AccountExport::new(
+            user,
+            profile,
+            wishlists,
+            items,
+            reservations,
+            deletion,
+            self.clock.now(),
+        ))
+    }
+
+    async fn delete_account(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> Result<AccountDeletion, DeleteAccountError> {
+        if !self.confirm(req, AccountAction::Delete).await? {
+            return Err(DeleteAccountError::InvalidConfirmation { id: req.user_id() });
+        }
+        if self.find_user(req.user_id()).await?.is_none() {
+            return Err(DeleteAccountError::UserDoesNotExist { id: req.user_id() });
+        }
+        let deletion = AccountDeletion::new(req.user_id(), self.clock.now(), self.grace_period);
+        self.account_repository.save_deletion(&deletion).await
+    }
+
+    async fn cancel_account_deletion(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> Result<AccountDeletion, CancelAccountDeletionError> {
+        if !self.confirm(req, AccountAction::CancelDeletion).await? {
+            return Err(CancelAccountDeletionError::InvalidConfirmation { id: req.user_id() });
+        }
+        self.account_repository.delete_deletion(req.user_id()).await
+    }
+
+    async fn purge_deleted_accounts(&self) -> Result<(), PurgeAccountsError> {
+        let due = self
+            .account_repository
+            .find_due_deletions(self.clock.now())
+            .await?;
+        for deletion in due {
+            self.purge(deletion.user_id()).await?;
+            // Only forget the deletion once the data is gone, so a failed purge is retried
+            match self
+                .account_repository
+                .delete_deletion(deletion.user_id())
+                .await
+            {
+                Ok(_) | Err(CancelAccountDeletionError::NotScheduled { .. }) => {}
+                Err(err) => return Err(PurgeAccountsError::Unkown(anyhow!(err))),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        application::image,
+        domain::{
+            CreateGroupRequest, CreateItemRequest, CreateUserRequest, CreateWishlistRequest,
+            FindWishlistByIdRequest, FollowStatus, ImageRepository, ImageSize, MockBlobStore,
+            MockImageFetcher, MockImageProcessor, MockImageService, SendMailError, Webhook,
+            WebhookEventKind, WebhookSecret, WebhookUrl,
+        },
+        infrastructure::{
+            clock::ManualClock,
+            persistence::in_memory::{
+                account::InMemoryAccountRepository, exchange::InMemoryExchangeRepository,
+                follow::InMemoryFollowRepository, group::InMemoryGroupRepository,
+                image::InMemoryImageRepository, inbox::InMemoryInboxRepository,
+                item::InMemoryItemRepository, price_history::InMemoryPriceHistoryRepository,
+                profile::InMemoryProfileRepository, reminder::InMemoryReminderRepository,
+                user::InMemoryUserRepository, webhook::InMemoryWebhookRepository,
+                wishlist::InMemoryWishlistRepository,
+            },
+        },
+    };
+
+    /// A [MailTransport] keeping the emails it is asked to send.
+    #[derive(Default)]
+    struct RecordingMailTransport {
+        sent: Mutex<Vec<Email>>,
+    }
+
+    impl MailTransport for RecordingMailTransport {
+        async fn send(&self, email: &Email) -> Result<(), SendMailError> {
+            self.sent.lock().unwrap().push(email.clone());
+            Ok(())
+        }
+    }
+
+    impl RecordingMailTransport {
+        /// The token in the last confirmation email sent.
+        fn last_token(&self) -> ConfirmationToken {
+            let sent = self.sent.lock().unwrap();
+            let body = sent.last().unwrap().body();
+            let token = body.split("the token ").nth(1).unwrap();
+            ConfirmationToken::from(token.split(' ').next().unwrap())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_purge_account_after_grace_period() {
+        let clock = Arc::new(ManualClock::new("2027-01-01T09:00:00Z".parse().unwrap()));
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let group_repository = Arc::new(InMemoryGroupRepository::new());
+        let follow_repository = Arc::new(InMemoryFollowRepository::new());
+        let profile_repository = Arc::new(InMemoryProfileRepository::new());
+        let webhook_repository = Arc::new(InMemoryWebhookRepository::new());
+        let image_repository = Arc::new(InMemoryImageRepository::new());
+        let deleted_blobs = Arc::new(Mutex::new(Vec::new()));
+        let mut blob_store = MockBlobStore::new();
+        let deleted = deleted_blobs.clone();
+        blob_store.expect_delete().returning(move |key| {
+            deleted.lock().unwrap().push(key.to_string());
+            Box::pin(std::future::ready(Ok(())))
+        });
+        let image_service = image::Service::new(
+            Arc::new(blob_store),
+            image_repository.clone(),
+            Arc::new(MockImageProcessor::new()),
+            Arc::new(MockImageFetcher::new()),
+            16,
+        );
+        let transport = Arc::new(RecordingMailTransport::default());
+        let service = Service::new(
+            user_repository.clone(),
+            wish_repository.clone(),
+            item_repository.clone(),
+            Arc::new(InMemoryPriceHistoryRepository::new()),
+            group_repository.clone(),
+            follow_repository.clone(),
+            Arc::new(InMemoryExchangeRepository::new()),
+            Arc::new(InMemoryInboxRepository::new()),
+            Arc::new(InMemoryReminderRepository::new()),
+            profile_repository.clone(),
+            webhook_repository.clone(),
+            Arc::new(image_service),
+            Arc::new(InMemoryAccountRepository::new()),
+            transport.clone(),
+            clock.clone(),
+        )
+        .with_grace_period(Duration::days(7));
+        let mut users = Vec::new();
+        for email in ["a@example.com", "b@example.com"] {
+            let req = CreateUserRequest::new(email.into(), "password".into());
+            users.push(*user_repository.save(&req).await.unwrap().id());
+        }
+        let (user, friend) = (users[0], users[1]);
+        let mut wishlists = Vec::new();
+        let mut items = Vec::new();
+        for owner in [user, friend] {
+            let wishlist = wish_repository
+                .save(&CreateWishlistRequest::new(owner, "Gifts".into(), false))
+                .await
+                .unwrap();
+            let item = item_repository
+                .save(&CreateItemRequest::new(
+                    wishlist.id(),
+                    wishlist.owner_id(),
+                    Some("Book".into()),
+                    "https://shop.example/book".into(),
+                    None,
+                    None,
+                ))
+                .await
+                .unwrap();
+            wish_repository
+                .add_item(wishlist.id(), item.id())
+                .await
+                .unwrap();
+            wishlists.push(wishlist.id());
+            items.push(item.id());
+        }
+        item_repository
+            .reserve_item(items[1], user, true)
+            .await
+            .unwrap();
+        profile_repository
+            .save_profile(&Profile::new(user))
+            .await
+            .unwrap();
+        let group = group_repository
+            .save(&CreateGroupRequest::new(user, "Family".into()))
+            .await
+            .unwrap();
+        follow_repository
+            .save_follow(friend, user, FollowStatus::Accepted, clock.now())
+            .await
+            .unwrap();
+        webhook_repository
+            .save_webhook(&Webhook::new(
+                Uuid::now_v7(),
+                user,
+                wishlists[0],
+                WebhookUrl::new("https://hooks.example/gifts").unwrap(),
+                WebhookSecret::generate(),
+                WebhookEventKind::ALL.to_vec(),
+                clock.now(),
+            ))
+            .await
+            .unwrap();
+        let (image, friend_image) = (Uuid::now_v7(), Uuid::now_v7());
+        image_repository.save_owner(image, user).await.unwrap();
+        image_repository
+            .save_owner(friend_image, friend)
+            .await
+            .unwrap();
+
+        let confirm = |action| {
+            let (service, transport) = (&service, &transport);
+            async move {
+                service
+                    .request_account_confirmation(&AccountConfirmationRequest::new(user, action))
+                    .await
+                    .unwrap();
+                ConfirmedAccountRequest::new(user, transport.last_token())
+            }
+        };
+        let export = service
+            .export_account(&confirm(AccountAction::Export).await)
+            .await
+            .unwrap();
+        assert_eq!(export.wishlists().len(), 1);
+        assert_eq!(export.items()[0].id(), items[0]);
+        assert_eq!(export.reservations()[0].id(), items[1]);
+
+        service
+            .delete_account(&confirm(AccountAction::Delete).await)
+            .await
+            .unwrap();
+        // Cancelling needs its own token, not the one of the deletion
+        let delete = confirm(AccountAction::Delete).await;
+        assert!(matches!(
+            service.cancel_account_deletion(&delete).await,
+            Err(CancelAccountDeletionError::InvalidConfirmation { .. })
+        ));
+        service
+            .cancel_account_deletion(&confirm(AccountAction::CancelDeletion).await)
+            .await
+            .unwrap();
+        assert!(matches!(
+            service
+                .cancel_account_deletion(&confirm(AccountAction::CancelDeletion).await)
+                .await,
+            Err(CancelAccountDeletionError::NotScheduled { .. })
+        ));
+        let deletion = service
+            .delete_account(&confirm(AccountAction::Delete).await)
+            .await
+            .unwrap();
+        assert_eq!(deletion.purge_at(), clock.now() + Duration::days(7));
+        clock.advance(Duration::days(6));
+        service.purge_deleted_accounts().await.unwrap();
+        assert!(service.find_user(user).await.unwrap().is_some());
+
+        clock.advance(Duration::days(1));
+        service.purge_deleted_accounts().await.unwrap();
+        assert!(service.find_user(user).await.unwrap().is_none());
+        assert!(service.find_user(friend).await.unwrap().is_some());
+        assert!(wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlists[0]))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlists[1]))
+            .await
+            .unwrap()
+            .is_some());
+        assert!(item_repository
+            .find_item_by_id(&FindItemByIdRequest::new(items[0]))
+            .await
+            .unwrap()
+            .is_none());
+        let reserved = item_repository
+            .find_item_by_id(&FindItemByIdRequest::new(items[1]))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reserved.reserved_by(), None);
+        assert!(group_repository
+            .find_group_by_id(group.id())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(follow_repository
+            .find_followees(friend)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(profile_repository.find_profile(user).await.unwrap(), None);
+        assert!(webhook_repository
+            .find_webhooks_by_user(user)
+            .await
+            .unwrap()
+            .is_empty());
+        let mut expected: Vec<String> = ImageSize::ALL
+            .iter()
+            .map(|size| format!("images/{}/{}", image, size))
+            .collect();
+        expected.sort();
+        let mut deleted = deleted_blobs.lock().unwrap().clone();
+        deleted.sort();
+        assert_eq!(deleted, expected);
+        assert!(image_repository
+            .find_images_by_owner(user)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            image_repository.find_images_by_owner(friend).await.unwrap(),
+            vec![friend_image]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confirm_account_actions() {
+        let clock = Arc::new(ManualClock::new("2027-01-01T09:00:00Z".parse().unwrap()));
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let transport = Arc::new(RecordingMailTransport::default());
+        let service = Service::new(
+            user_repository.clone(),
+            Arc::new(InMemoryWishlistRepository::new()),
+            Arc::new(InMemoryItemRepository::new()),
+            Arc::new(InMemoryPriceHistoryRepository::new()),
+            Arc::new(InMemoryGroupRepository::new()),
+            Arc::new(InMemoryFollowRepository::new()),
+            Arc::new(InMemoryExchangeRepository::new()),
+            Arc::new(InMemoryInboxRepository::new()),
+            Arc::new(InMemoryReminderRepository::new()),
+            Arc::new(InMemoryProfileRepository::new()),
+            Arc::new(InMemoryWebhookRepository::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(InMemoryAccountRepository::new()),
+            transport.clone(),
+            clock.clone(),
+        )
+        .with_confirmation_ttl(Duration::minutes(10));
+        let user = *user_repository
+            .save(&CreateUserRequest::new(
+                "a@example.com".into(),
+                "password".into(),
+            ))
+            .await
+            .unwrap()
+            .id();
+        let export = AccountConfirmationRequest::new(user, AccountAction::Export);
+        let guess = ConfirmedAccountRequest::new(user, ConfirmationToken::generate());
+
+        service.request_account_confirmation(&export).await.unwrap();
+        let token = transport.last_token();
+        assert_eq!(
+            transport.sent.lock().unwrap()[0].to().to_string(),
+            "a@example.com"
+        );
+        assert!(matches!(
+            service.export_account(&guess).await,
+            Err(ExportAccountError::InvalidConfirmation { .. })
+        ));
+        // A token confirms one action, once
+        let req = ConfirmedAccountRequest::new(user, token);
+        assert!(matches!(
+            service.delete_account(&req).await,
+            Err(DeleteAccountError::InvalidConfirmation { .. })
+        ));
+        service.export_account(&req).await.unwrap();
+        assert!(matches!(
+            service.export_account(&req).await,
+            Err(ExportAccountError::InvalidConfirmation { .. })
+        ));
+
+        service.request_account_confirmation(&export).await.unwrap();
+        let req = ConfirmedAccountRequest::new(user, transport.last_token());
+        clock.advance(Duration::minutes(10));
+        assert!(matches!(
+            service.export_account(&req).await,
+            Err(ExportAccountError::InvalidConfirmation { .. })
+        ));
+    }
+}
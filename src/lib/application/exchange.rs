@@ -0,0 +1,427 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use crate::domain::{
+    AddExclusionError, AddExclusionRequest, AddParticipantError, AddParticipantRequest,
+    AuditDrawError, Clock, CreateExchangeError, CreateExchangeRequest, DrawAudit,
+    DrawExchangeError, Exchange, ExchangeActionRequest, ExchangeRepository, ExchangeService,
+    FindExchangeError, FindUserByIdRequest, FindWishlistByIdRequest, Reveal, RevealRecipientError,
+    UpdateExchangeError, UserRepository, WishlistRepository,
+};
+
+pub struct Service<U, W, E, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    E: ExchangeRepository,
+    C: Clock,
+{
+    user_repository: Arc<U>,
+    wish_repository: Arc<W>,
+    exchange_repository: Arc<E>,
+    clock: Arc<C>,
+}
+
+impl<U, W, E, C> Clone for Service<U, W, E, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    E: ExchangeRepository,
+    C: Clock,
+{
+    fn clone(&self) -> Self {
+        Self {
+            user_repository: self.user_repository.clone(),
+            wish_repository: self.wish_repository.clone(),
+            exchange_repository: self.exchange_repository.clone(),
+            clock: self.clock.clone(),
+        }
+    }
+}
+
+impl<U, W, E, C> Service<U, W, E, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    E: ExchangeRepository,
+    C: Clock,
+{
+    pub fn new(
+        user_repository: Arc<U>,
+        wish_repository: Arc<W>,
+        exchange_repository: Arc<E>,
+        clock: Arc<C>,
+    ) -> Self {
+        Self {
+            user_repository,
+            wish_repository,
+            exchange_repository,
+            clock,
+        }
+    }
+
+    async fn find_exchange(&self, id: Uuid) -> Result<Option<Exchange>, FindExchangeError> {
+        self.exchange_repository.find_exchange_by_id(id).await
+    }
+}
+
+impl<U, W, E, C> ExchangeService for Service<U, W, E, C>
+where
+    U: UserRepository + Send + Sync + 'static,
+    W: WishlistRepository + Send + Sync + 'static,
+    E: ExchangeRepository,
+    C: Clock,
+{
+    async fn create_exchange(
+        &self,
+        req: &CreateExchangeRequest,
+    ) -> Result<Exchange, CreateExchangeError> {
+        let organizer = self
+            .user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(req.organizer_id()))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if organizer.is_none() {
+            return Err(CreateExchangeError::OrganizerDoesNotExist {
+                id: req.organizer_id(),
+            });
+        }
+        self.exchange_repository.save(req).await
+    }
+
+    async fn add_participant(
+        &self,
+        req: &AddParticipantRequest,
+    ) -> Result<Exchange, AddParticipantError> {
+        let participant = req.participant();
+        let exchange = self
+            .find_exchange(req.exchange_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(AddParticipantError::ExchangeDoesNotExist {
+                id: req.exchange_id(),
+            })?;
+        if req.user_id() != exchange.organizer_id() && req.user_id() != participant.user_id() {
+            return Err(AddParticipantError::NotOrganizer { id: exchange.id() });
+        }
+        let user = self
+            .user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(participant.user_id()))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if user.is_none() {
+            return Err(AddParticipantError::UserDoesNotExist {
+                id: participant.user_id(),
+            });
+        }
+        if let Some(wishlist_id) = participant.wishlist_id() {
+            let wishlist = self
+                .wish_repository
+                .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlist_id))
+                .await
+                .map_err(|err| anyhow!(err))?;
+            if wishlist.is_none_or(|wishlist| wishlist.owner_id() != participant.user_id()) {
+                return Err(AddParticipantError::WishlistDoesNotExist { id: wishlist_id });
+            }
+        }
+        self.exchange_repository
+            .add_participant(exchange.id(), participant)
+            .await
+            .map_err(|err| match err {
+                UpdateExchangeError::ExchangeDoesNotExist { id } => {
+                    AddParticipantError::ExchangeDoesNotExist { id }
+                }
+                UpdateExchangeError::AlreadyDrawn { id } => {
+                    AddParticipantError::AlreadyDrawn { id }
+                }
+                UpdateExchangeError::Unkown(err) => AddParticipantError::Unkown(err),
+            })
+    }
+
+    async fn add_exclusion(
+        &self,
+        req: &AddExclusionRequest,
+    ) -> Result<Exchange, AddExclusionError> {
+        let exclusion = req.exclusion();
+        let exchange = self
+            .find_exchange(req.exchange_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(AddExclusionError::ExchangeDoesNotExist {
+                id: req.exchange_id(),
+            })?;
+        if req.user_id() != exchange.organizer_id() {
+            return Err(AddExclusionError::NotOrganizer { id: exchange.id() });
+        }
+        if exclusion.giver_id() == exclusion.recipient_id() {
+            return Err(AddExclusionError::SameParticipant);
+        }
+        for id in [exclusion.giver_id(), exclusion.recipient_id()] {
+            if exchange.participant(id).is_none() {
+                return Err(AddExclusionError::NotParticipant { id });
+            }
+        }
+        self.exchange_repository
+            .add_exclusions(exchange.id(), req.exclusions())
+            .await
+            .map_err(|err| match err {
+                UpdateExchangeError::ExchangeDoesNotExist { id } => {
+                    AddExclusionError::ExchangeDoesNotExist { id }
+                }
+                UpdateExchangeError::AlreadyDrawn { id } => AddExclusionError::AlreadyDrawn { id },
+                UpdateExchangeError::Unkown(err) => AddExclusionError::Unkown(err),
+            })
+    }
+
+    async fn draw_exchange(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> Result<Exchange, DrawExchangeError> {
+        let exchange = self
+            .find_exchange(req.exchange_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(DrawExchangeError::ExchangeDoesNotExist {
+                id: req.exchange_id(),
+            })?;
+        if req.user_id() != exchange.organizer_id() {
+            return Err(DrawExchangeError::NotOrganizer { id: exchange.id() });
+        }
+        if exchange.draw().is_some() {
+            return Err(DrawExchangeError::AlreadyDrawn { id: exchange.id() });
+        }
+        let (seed, _) = Uuid::new_v4().as_u64_pair();
+        let draw = exchange.draw_from(seed, self.clock.now())?;
+        self.exchange_repository
+            .save_draw(exchange.id(), draw)
+            .await
+            .map_err(|err| match err {
+                UpdateExchangeError::ExchangeDoesNotExist { id } => {
+                    DrawExchangeError::ExchangeDoesNotExist { id }
+                }
+                UpdateExchangeError::AlreadyDrawn { id } => DrawExchangeError::AlreadyDrawn { id },
+                UpdateExchangeError::Unkown(err) => DrawExchangeError::Unkown(err),
+            })
+    }
+
+    async fn reveal_recipient(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> Result<Reveal, RevealRecipientError> {
+        let exchange = self
+            .find_exchange(req.exchange_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(RevealRecipientError::ExchangeDoesNotExist {
+                id: req.exchange_id(),
+            })?;
+        if exchange.participant(req.user_id()).is_none() {
+            return Err(RevealRecipientError::NotParticipant { id: req.user_id() });
+        }
+        let recipient_id = exchange
+            .draw()
+            .and_then(|draw| draw.recipient_of(req.user_id()))
+            .ok_or(RevealRecipientError::NotDrawn { id: exchange.id() })?;
+        let wishlist = match exchange
+            .participant(recipient_id)
+            .and_then(|recipient| recipient.wishlist_id())
+        {
+            Some(wishlist_id) => self
+                .wish_repository
+                .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlist_id))
+                .await
+                .map_err(|err| anyhow!(err))?,
+            None => None,
+        };
+        Ok(Reveal::new(recipient_id, wishlist))
+    }
+
+    async fn audit_draw(&self, req: &ExchangeActionRequest) -> Result<DrawAudit, AuditDrawError> {
+        let exchange = self
+            .find_exchange(req.exchange_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(AuditDrawError::ExchangeDoesNotExist {
+                id: req.exchange_id(),
+            })?;
+        if req.user_id() != exchange.organizer_id() {
+            return Err(AuditDrawError::NotOrganizer { id: exchange.id() });
+        }
+        let draw = exchange
+            .draw()
+            .ok_or(AuditDrawError::NotDrawn { id: exchange.id() })?;
+        Ok(DrawAudit::new(
+            draw.seed(),
+            draw.drawn_at(),
+            exchange.is_draw_reproducible(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+    use crate::{
+        domain::{CreateUserRequest, CreateWishlistRequest, DrawError, Exclusion, Participant},
+        infrastructure::{
+            clock::ManualClock,
+            persistence::in_memory::{
+                exchange::InMemoryExchangeRepository, user::InMemoryUserRepository,
+                wishlist::InMemoryWishlistRepository,
+            },
+        },
+    };
+
+    #[tokio::test]
+    async fn test_draw_and_reveal() {
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let service = Service::new(
+            user_repository.clone(),
+            wish_repository.clone(),
+            Arc::new(InMemoryExchangeRepository::new()),
+            Arc::new(ManualClock::new("2027-12-01T09:00:00Z".parse().unwrap())),
+        );
+        let mut users = Vec::new();
+        for email in ["a@example.com", "b@example.com", "c@example.com"] {
+            let req = CreateUserRequest::new(email.into(), "password".into());
+            users.push(*user_repository.save(&req).await.unwrap().id());
+        }
+        let organizer = users[0];
+        let exchange = service
+            .create_exchange(&CreateExchangeRequest::new(organizer, "Family".into()))
+            .await
+            .unwrap();
+        let others_wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(users[2], "Books".into(), true))
+            .await
+            .unwrap();
+        let result = service
+            .add_participant(&AddParticipantRequest::new(
+                exchange.id(),
+                users[1],
+                Participant::new(users[1], Some(others_wishlist.id())),
+            ))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AddParticipantError::WishlistDoesNotExist { .. })
+        ));
+        for user_id in &users {
+            let wishlist = wish_repository
+                .save(&CreateWishlistRequest::new(*user_id, "Gifts".into(), true))
+                .await
+                .unwrap();
+            service
+                .add_participant(&AddParticipantRequest::new(
+                    exchange.id(),
+                    *user_id,
+                    Participant::new(*user_id, Some(wishlist.id())),
+                ))
+                .await
+                .unwrap();
+        }
+        let action = |user_id| ExchangeActionRequest::new(exchange.id(), user_id);
+        let result = service.reveal_recipient(&action(users[1])).await;
+        assert!(matches!(result, Err(RevealRecipientError::NotDrawn { .. })));
+
+        // users[0] can only give to users[1], who cannot give to users[0].
+        service
+            .add_exclusion(&AddExclusionRequest::new(
+                exchange.id(),
+                organizer,
+                Exclusion::new(users[0], users[2]),
+                false,
+            ))
+            .await
+            .unwrap();
+        service
+            .add_exclusion(&AddExclusionRequest::new(
+                exchange.id(),
+                organizer,
+                Exclusion::new(users[1], users[0]),
+                false,
+            ))
+            .await
+            .unwrap();
+        let result = service.draw_exchange(&action(users[1])).await;
+        assert!(matches!(
+            result,
+            Err(DrawExchangeError::NotOrganizer { .. })
+        ));
+        service.draw_exchange(&action(organizer)).await.unwrap();
+
+        for (giver, recipient) in [(0, 1), (1, 2), (2, 0)] {
+            let reveal = service
+                .reveal_recipient(&action(users[giver]))
+                .await
+                .unwrap();
+            assert_eq!(reveal.recipient_id(), users[recipient]);
+            assert_eq!(reveal.wishlist().unwrap().owner_id(), users[recipient]);
+        }
+        let outsider = Uuid::now_v7();
+        let result = service.reveal_recipient(&action(outsider)).await;
+        assert!(matches!(
+            result,
+            Err(RevealRecipientError::NotParticipant { .. })
+        ));
+
+        let audit = service.audit_draw(&action(organizer)).await.unwrap();
+        assert!(audit.reproducible());
+        assert_eq!(
+            audit.drawn_at(),
+            "2027-12-01T09:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_draw_without_valid_derangement() {
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let service = Service::new(
+            user_repository.clone(),
+            Arc::new(InMemoryWishlistRepository::new()),
+            Arc::new(InMemoryExchangeRepository::new()),
+            Arc::new(ManualClock::new("2027-12-01T09:00:00Z".parse().unwrap())),
+        );
+        let mut users = Vec::new();
+        for email in ["a@example.com", "b@example.com"] {
+            let req = CreateUserRequest::new(email.into(), "password".into());
+            users.push(*user_repository.save(&req).await.unwrap().id());
+        }
+        let exchange = service
+            .create_exchange(&CreateExchangeRequest::new(users[0], "Couple".into()))
+            .await
+            .unwrap();
+        for user_id in &users {
+            service
+                .add_participant(&AddParticipantRequest::new(
+                    exchange.id(),
+                    users[0],
+                    Participant::new(*user_id, None),
+                ))
+                .await
+                .unwrap();
+        }
+        service
+            .add_exclusion(&AddExclusionRequest::new(
+                exchange.id(),
+                users[0],
+                Exclusion::new(users[0], users[1]),
+                true,
+            ))
+            .await
+            .unwrap();
+
+        let result = service
+            .draw_exchange(&ExchangeActionRequest::new(exchange.id(), users[0]))
+            .await;
+        assert!(matches!(
+            result,
+            Err(DrawExchangeError::Invalid(DrawError::NoValidDraw))
+        ));
+    }
+}
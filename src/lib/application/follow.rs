@@ -0,0 +1,306 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use crate::domain::{
+    AnswerFollowRequest, AnswerFollowRequestError, Block, BlockUserError, BlockUserRequest, Clock,
+    FindUserByIdRequest, Follow, FollowFeedError, FollowFeedRequest, FollowRepository,
+    FollowService, FollowSettings, FollowStatus, FollowUserError, FollowUserRequest,
+    GroupRepository, ListFollowRequestsError, ListFollowRequestsRequest, Page,
+    SetFollowSettingsError, UnblockUserError, UnfollowUserError, UserRepository, Wishlist,
+    WishlistRepository,
+};
+
+pub struct Service<U, W, G, F, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    G: GroupRepository,
+    F: FollowRepository,
+    C: Clock,
+{
+    user_repository: Arc<U>,
+    wish_repository: Arc<W>,
+    group_repository: Arc<G>,
+    follow_repository: Arc<F>,
+    clock: Arc<C>,
+}
+
+impl<U, W, G, F, C> Clone for Service<U, W, G, F, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    G: GroupRepository,
+    F: FollowRepository,
+    C: Clock,
+{
+    fn clone(&self) -> Self {
+        Self {
+            user_repository: self.user_repository.clone(),
+            wish_repository: self.wish_repository.clone(),
+            group_repository: self.group_repository.clone(),
+            follow_repository: self.follow_repository.clone(),
+            clock: self.clock.clone(),
+        }
+    }
+}
+
+impl<U, W, G, F, C> Service<U, W, G, F, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    G: GroupRepository,
+    F: FollowRepository,
+    C: Clock,
+{
+    pub fn new(
+        user_repository: Arc<U>,
+        wish_repository: Arc<W>,
+        group_repository: Arc<G>,
+        follow_repository: Arc<F>,
+        clock: Arc<C>,
+    ) -> Self {
+        Self {
+            user_repository,
+            wish_repository,
+            group_repository,
+            follow_repository,
+            clock,
+        }
+    }
+
+    async fn user_exists(&self, id: Uuid) -> anyhow::Result<bool> {
+        let user = self
+            .user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(id))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(user.is_some())
+    }
+}
+
+impl<U, W, G, F, C> FollowService for Service<U, W, G, F, C>
+where
+    U: UserRepository + Send + Sync + 'static,
+    W: WishlistRepository + Send + Sync + 'static,
+    G: GroupRepository,
+    F: FollowRepository,
+    C: Clock,
+{
+    async fn follow_user(&self, req: &FollowUserRequest) -> Result<Follow, FollowUserError> {
+        if req.user_id() == req.followee_id() {
+            return Err(FollowUserError::SelfFollow);
+        }
+        if !self.user_exists(req.followee_id()).await? {
+            return Err(FollowUserError::UserDoesNotExist {
+                id: req.followee_id(),
+            });
+        }
+        let settings = self
+            .follow_repository
+            .find_settings(req.followee_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .unwrap_or_else(|| FollowSettings::default_for(req.followee_id()));
+        let status = if settings.private_account() {
+            FollowStatus::Pending
+        } else {
+            FollowStatus::Accepted
+        };
+        self.follow_repository
+            .save_follow(req.user_id(), req.followee_id(), status, self.clock.now())
+            .await
+    }
+
+    async fn unfollow_user(&self, req: &FollowUserRequest) -> Result<Follow, UnfollowUserError> {
+        self.follow_repository
+            .delete_follow(req.user_id(), req.followee_id())
+            .await
+    }
+
+    async fn list_follow_requests(
+        &self,
+        req: &ListFollowRequestsRequest,
+    ) -> Result<Page<Follow>, ListFollowRequestsError> {
+        self.follow_repository
+            .find_pending_follows(req.user_id(), req.page())
+            .await
+    }
+
+    async fn answer_follow_request(
+        &self,
+        req: &AnswerFollowRequest,
+    ) -> Result<Follow, AnswerFollowRequestError> {
+        self.follow_repository
+            .answer_follow(req.follower_id(), req.user_id(), req.accept())
+            .await
+    }
+
+    async fn block_user(&self, req: &BlockUserRequest) -> Result<Block, BlockUserError> {
+        if req.user_id() == req.blocked_id() {
+            return Err(BlockUserError::SelfBlock);
+        }
+        if !self.user_exists(req.blocked_id()).await? {
+            return Err(BlockUserError::UserDoesNotExist {
+                id: req.blocked_id(),
+            });
+        }
+        self.follow_repository
+            .save_block(req.user_id(), req.blocked_id(), self.clock.now())
+            .await
+    }
+
+    async fn unblock_user(&self, req: &BlockUserRequest) -> Result<Block, UnblockUserError> {
+        self.follow_repository
+            .delete_block(req.user_id(), req.blocked_id())
+            .await
+    }
+
+    async fn set_follow_settings(
+        &self,
+        settings: &FollowSettings,
+    ) -> Result<FollowSettings, SetFollowSettingsError> {
+        self.follow_repository.save_settings(settings).await
+    }
+
+    async fn follow_feed(
+        &self,
+        req: &FollowFeedRequest,
+    ) -> Result<Page<Wishlist>, FollowFeedError> {
+        let followees = self
+            .follow_repository
+            .find_followees(req.user_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if followees.is_empty() {
+            return Ok(Page::empty());
+        }
+        let groups = self
+            .group_repository
+            .find_groups_by_member(req.user_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let query = req.query().clone().narrow(|filter| {
+            filter
+                .with_owners(followees)
+                .with_archived(Some(false))
+                .visible_to(req.user_id(), groups)
+        });
+        self.wish_repository
+            .list_wishlists(&query)
+            .await
+            .map_err(|err| anyhow!(err).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{
+        domain::{
+            CreateUserRequest, CreateWishlistRequest, Cursor, PageLimit, PageRequest,
+            WishlistFilter, WishlistQuery, WishlistSort,
+        },
+        infrastructure::{
+            clock::ManualClock,
+            persistence::in_memory::{
+                follow::InMemoryFollowRepository, group::InMemoryGroupRepository,
+                user::InMemoryUserRepository, wishlist::InMemoryWishlistRepository,
+            },
+        },
+    };
+
+    #[tokio::test]
+    async fn test_feed_pages_through_visible_wishlists() {
+        let clock = Arc::new(ManualClock::new("2027-12-01T09:00:00Z".parse().unwrap()));
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::with_clock(clock.clone()));
+        let service = Service::new(
+            user_repository.clone(),
+            wish_repository.clone(),
+            Arc::new(InMemoryGroupRepository::new()),
+            Arc::new(InMemoryFollowRepository::new()),
+            clock.clone(),
+        );
+        let mut users = Vec::new();
+        for email in ["a@example.com", "b@example.com", "c@example.com"] {
+            let req = CreateUserRequest::new(email.into(), "password".into());
+            users.push(*user_repository.save(&req).await.unwrap().id());
+        }
+        let (reader, public, private) = (users[0], users[1], users[2]);
+        service
+            .set_follow_settings(&FollowSettings::new(private, true))
+            .await
+            .unwrap();
+
+        let mut public_wishlists = Vec::new();
+        for (owner, is_private) in [
+            (public, false),
+            (public, true),
+            (public, false),
+            (private, false),
+        ] {
+            clock.advance(Duration::minutes(1));
+            let wishlist = wish_repository
+                .save(&CreateWishlistRequest::new(
+                    owner,
+                    "Gifts".into(),
+                    is_private,
+                ))
+                .await
+                .unwrap();
+            if owner == public && !is_private {
+                public_wishlists.push(wishlist.id());
+            }
+        }
+
+        let follow = service
+            .follow_user(&FollowUserRequest::new(reader, public))
+            .await
+            .unwrap();
+        assert_eq!(follow.status(), FollowStatus::Accepted);
+        let follow = service
+            .follow_user(&FollowUserRequest::new(reader, private))
+            .await
+            .unwrap();
+        assert_eq!(follow.status(), FollowStatus::Pending);
+
+        let page = |cursor: Option<&Cursor>| {
+            let page = PageRequest::new(cursor.cloned(), PageLimit::new(1).unwrap());
+            let query =
+                WishlistQuery::new(WishlistFilter::default(), WishlistSort::default(), page);
+            FollowFeedRequest::new(reader, query)
+        };
+        let first = service.follow_feed(&page(None)).await.unwrap();
+        assert_eq!(first.entries()[0].id(), public_wishlists[1]);
+        let second = service
+            .follow_feed(&page(first.next_cursor()))
+            .await
+            .unwrap();
+        assert_eq!(second.entries()[0].id(), public_wishlists[0]);
+        assert!(second.next_cursor().is_none());
+
+        // Once accepted, the wishlist of the private account is the most recent.
+        service
+            .answer_follow_request(&AnswerFollowRequest::new(private, reader, true))
+            .await
+            .unwrap();
+        let first = service.follow_feed(&page(None)).await.unwrap();
+        assert_eq!(first.entries()[0].owner_id(), private);
+
+        // Blocking ends the follow.
+        service
+            .block_user(&BlockUserRequest::new(private, reader))
+            .await
+            .unwrap();
+        let first = service.follow_feed(&page(None)).await.unwrap();
+        assert_eq!(first.entries()[0].id(), public_wishlists[1]);
+        let result = service
+            .follow_user(&FollowUserRequest::new(reader, private))
+            .await;
+        assert!(matches!(result, Err(FollowUserError::Blocked { .. })));
+    }
+}
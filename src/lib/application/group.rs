@@ -0,0 +1,432 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use crate::domain::{
+    Clock, CreateGroupError, CreateGroupRequest, Cursor, FindGroupError, FindUserByIdRequest,
+    FindWishlistByIdRequest, Group, GroupFeedEntry, GroupFeedError, GroupFeedRequest,
+    GroupInvitation, GroupRepository, GroupService, InviteMemberError, InviteMemberRequest,
+    ListInvitationsError, ListInvitationsRequest, ManageMemberError, ManageMemberRequest, Page,
+    RespondToInvitationError, RespondToInvitationRequest, SetMemberRoleRequest, ShareWishlistError,
+    ShareWishlistRequest, SharedWishlist, SortDirection, SortValue, UpdateGroupError,
+    UserRepository, WishlistRepository,
+};
+
+pub struct Service<U, W, G, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    G: GroupRepository,
+    C: Clock,
+{
+    user_repository: Arc<U>,
+    wish_repository: Arc<W>,
+    group_repository: Arc<G>,
+    clock: Arc<C>,
+}
+
+impl<U, W, G, C> Clone for Service<U, W, G, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    G: GroupRepository,
+    C: Clock,
+{
+    fn clone(&self) -> Self {
+        Self {
+            user_repository: self.user_repository.clone(),
+            wish_repository: self.wish_repository.clone(),
+            group_repository: self.group_repository.clone(),
+            clock: self.clock.clone(),
+        }
+    }
+}
+
+impl<U, W, G, C> Service<U, W, G, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    G: GroupRepository,
+    C: Clock,
+{
+    pub fn new(
+        user_repository: Arc<U>,
+        wish_repository: Arc<W>,
+        group_repository: Arc<G>,
+        clock: Arc<C>,
+    ) -> Self {
+        Self {
+            user_repository,
+            wish_repository,
+            group_repository,
+            clock,
+        }
+    }
+
+    async fn find_group(&self, id: Uuid) -> Result<Option<Group>, FindGroupError> {
+        self.group_repository.find_group_by_id(id).await
+    }
+}
+
+impl From<UpdateGroupError> for ManageMemberError {
+    fn from(err: UpdateGroupError) -> Self {
+        match err {
+            UpdateGroupError::GroupDoesNotExist { id } => Self::GroupDoesNotExist { id },
+            UpdateGroupError::Membership(err) => Self::Membership(err),
+            UpdateGroupError::Unkown(err) => Self::Unkown(err),
+        }
+    }
+}
+
+impl From<UpdateGroupError> for ShareWishlistError {
+    fn from(err: UpdateGroupError) -> Self {
+        match err {
+            UpdateGroupError::GroupDoesNotExist { id } => Self::GroupDoesNotExist { id },
+            UpdateGroupError::Membership(err) => Self::Unkown(anyhow!(err)),
+            UpdateGroupError::Unkown(err) => Self::Unkown(err),
+        }
+    }
+}
+
+impl<U, W, G, C> GroupService for Service<U, W, G, C>
+where
+    U: UserRepository + Send + Sync + 'static,
+    W: WishlistRepository + Send + Sync + 'static,
+    G: GroupRepository,
+    C: Clock,
+{
+    async fn create_group(&self, req: &CreateGroupRequest) -> Result<Group, CreateGroupError> {
+        let user = self
+            .user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(req.user_id()))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if user.is_none() {
+            return Err(CreateGroupError::UserDoesNotExist { id: req.user_id() });
+        }
+        self.group_repository.save(req).await
+    }
+
+    async fn invite_member(
+        &self,
+        req: &InviteMemberRequest,
+    ) -> Result<GroupInvitation, InviteMemberError> {
+        let group = self
+            .find_group(req.group_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(InviteMemberError::GroupDoesNotExist { id: req.group_id() })?;
+        if !group.is_admin(req.user_id()) {
+            return Err(InviteMemberError::NotAdmin { id: group.id() });
+        }
+        let invitee = self
+            .user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(req.invitee_id()))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if invitee.is_none() {
+            return Err(InviteMemberError::UserDoesNotExist {
+                id: req.invitee_id(),
+            });
+        }
+        self.group_repository
+            .save_invitation(
+                group.id(),
+                req.invitee_id(),
+                req.user_id(),
+                self.clock.now(),
+            )
+            .await
+    }
+
+    async fn list_invitations(
+        &self,
+        req: &ListInvitationsRequest,
+    ) -> Result<Page<GroupInvitation>, ListInvitationsError> {
+        self.group_repository
+            .find_pending_invitations(req.user_id(), req.page())
+            .await
+    }
+
+    async fn respond_to_invitation(
+        &self,
+        req: &RespondToInvitationRequest,
+    ) -> Result<GroupInvitation, RespondToInvitationError> {
+        self.group_repository
+            .answer_invitation(req.invitation_id(), req.user_id(), req.accept())
+            .await
+    }
+
+    async fn remove_member(&self, req: &ManageMemberRequest) -> Result<Group, ManageMemberError> {
+        let group = self
+            .find_group(req.group_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(ManageMemberError::GroupDoesNotExist { id: req.group_id() })?;
+        if req.user_id() != req.member_id() && !group.is_admin(req.user_id()) {
+            return Err(ManageMemberError::NotAdmin { id: group.id() });
+        }
+        Ok(self
+            .group_repository
+            .remove_member(group.id(), req.member_id())
+            .await?)
+    }
+
+    async fn set_member_role(
+        &self,
+        req: &SetMemberRoleRequest,
+    ) -> Result<Group, ManageMemberError> {
+        let group = self
+            .find_group(req.group_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(ManageMemberError::GroupDoesNotExist { id: req.group_id() })?;
+        if !group.is_admin(req.user_id()) {
+            return Err(ManageMemberError::NotAdmin { id: group.id() });
+        }
+        Ok(self
+            .group_repository
+            .set_member_role(group.id(), req.member_id(), req.role())
+            .await?)
+    }
+
+    async fn share_wishlist(
+        &self,
+        req: &ShareWishlistRequest,
+    ) -> Result<Group, ShareWishlistError> {
+        let group = self
+            .find_group(req.group_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(ShareWishlistError::GroupDoesNotExist { id: req.group_id() })?;
+        if !group.is_member(req.user_id()) {
+            return Err(ShareWishlistError::NotMember { id: req.user_id() });
+        }
+        let wishlist = self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(req.wishlist_id()))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(ShareWishlistError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            })?;
+        if wishlist.owner_id() != req.user_id() {
+            return Err(ShareWishlistError::NotWishlistOwner { id: wishlist.id() });
+        }
+        let shared = SharedWishlist::new(wishlist.id(), req.user_id(), self.clock.now());
+        Ok(self
+            .group_repository
+            .share_wishlist(group.id(), shared)
+            .await?)
+    }
+
+    async fn unshare_wishlist(
+        &self,
+        req: &ShareWishlistRequest,
+    ) -> Result<Group, ShareWishlistError> {
+        let group = self
+            .find_group(req.group_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(ShareWishlistError::GroupDoesNotExist { id: req.group_id() })?;
+        if !group.is_member(req.user_id()) {
+            return Err(ShareWishlistError::NotMember { id: req.user_id() });
+        }
+        let shared = group
+            .wishlists()
+            .iter()
+            .find(|shared| shared.wishlist_id() == req.wishlist_id())
+            .ok_or(ShareWishlistError::NotShared {
+                id: req.wishlist_id(),
+            })?;
+        if shared.shared_by() != req.user_id() && !group.is_admin(req.user_id()) {
+            return Err(ShareWishlistError::NotWishlistOwner {
+                id: req.wishlist_id(),
+            });
+        }
+        Ok(self
+            .group_repository
+            .unshare_wishlist(group.id(), req.wishlist_id())
+            .await?)
+    }
+
+    async fn group_feed(
+        &self,
+        req: &GroupFeedRequest,
+    ) -> Result<Page<GroupFeedEntry>, GroupFeedError> {
+        let group = self
+            .find_group(req.group_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(GroupFeedError::GroupDoesNotExist { id: req.group_id() })?;
+        if !group.is_member(req.user_id()) {
+            return Err(GroupFeedError::NotMember { id: req.user_id() });
+        }
+        let mut feed = Vec::new();
+        for shared in group.visible_wishlists() {
+            let wishlist = self
+                .wish_repository
+                .find_wishlist_by_id(&FindWishlistByIdRequest::new(shared.wishlist_id()))
+                .await
+                .map_err(|err| anyhow!(err))?;
+            if let Some(wishlist) = wishlist.filter(|wishlist| req.filter().matches(wishlist)) {
+                feed.push(GroupFeedEntry::new(
+                    wishlist,
+                    shared.shared_by(),
+                    shared.shared_at(),
+                ));
+            }
+        }
+        // The most recently shared first.
+        Ok(req
+            .page()
+            .paginate(feed, SortDirection::Descending, |entry| {
+                Cursor::new(
+                    Some(SortValue::Time(entry.shared_at())),
+                    entry.wishlist().id(),
+                )
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::*;
+    use crate::{
+        domain::{
+            CreateUserRequest, CreateWishlistRequest, GroupRole, PageRequest, WishlistFilter,
+        },
+        infrastructure::{
+            clock::ManualClock,
+            persistence::in_memory::{
+                group::InMemoryGroupRepository, user::InMemoryUserRepository,
+                wishlist::InMemoryWishlistRepository,
+            },
+        },
+    };
+
+    #[tokio::test]
+    async fn test_membership_grants_and_revokes_access() {
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let clock = Arc::new(ManualClock::new(Utc::now()));
+        let service = Service::new(
+            user_repository.clone(),
+            wish_repository.clone(),
+            Arc::new(InMemoryGroupRepository::new()),
+            clock.clone(),
+        );
+        let mut users = Vec::new();
+        for email in ["a@example.com", "b@example.com", "c@example.com"] {
+            let req = CreateUserRequest::new(email.into(), "password".into());
+            users.push(*user_repository.save(&req).await.unwrap().id());
+        }
+        let (admin, member, outsider) = (users[0], users[1], users[2]);
+        let group = service
+            .create_group(&CreateGroupRequest::new(admin, "Family".into()))
+            .await
+            .unwrap();
+
+        let result = service
+            .invite_member(&InviteMemberRequest::new(group.id(), member, outsider))
+            .await;
+        assert!(matches!(result, Err(InviteMemberError::NotAdmin { .. })));
+        let invitation = service
+            .invite_member(&InviteMemberRequest::new(group.id(), admin, member))
+            .await
+            .unwrap();
+        service
+            .respond_to_invitation(&RespondToInvitationRequest::new(
+                invitation.id(),
+                member,
+                true,
+            ))
+            .await
+            .unwrap();
+
+        let mut wishlists = Vec::new();
+        for owner in [admin, member] {
+            let wishlist = wish_repository
+                .save(&CreateWishlistRequest::new(owner, "Gifts".into(), true))
+                .await
+                .unwrap();
+            service
+                .share_wishlist(&ShareWishlistRequest::new(group.id(), owner, wishlist.id()))
+                .await
+                .unwrap();
+            clock.advance(Duration::minutes(1));
+            wishlists.push(wishlist.id());
+        }
+        let outsiders_wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(outsider, "Books".into(), true))
+            .await
+            .unwrap();
+        let result = service
+            .share_wishlist(&ShareWishlistRequest::new(
+                group.id(),
+                outsider,
+                outsiders_wishlist.id(),
+            ))
+            .await;
+        assert!(matches!(result, Err(ShareWishlistError::NotMember { .. })));
+        let result = service
+            .share_wishlist(&ShareWishlistRequest::new(group.id(), admin, wishlists[1]))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ShareWishlistError::NotWishlistOwner { .. })
+        ));
+
+        let feed = service
+            .group_feed(&GroupFeedRequest::new(
+                group.id(),
+                admin,
+                WishlistFilter::default(),
+                PageRequest::default(),
+            ))
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = feed
+            .entries()
+            .iter()
+            .map(|entry| entry.wishlist().id())
+            .collect();
+        assert_eq!(ids, vec![wishlists[1], wishlists[0]]);
+
+        // Leaving the group hides the member's wishlist and the group's wishlists.
+        service
+            .set_member_role(&SetMemberRoleRequest::new(
+                ManageMemberRequest::new(group.id(), admin, member),
+                GroupRole::Admin,
+            ))
+            .await
+            .unwrap();
+        let group = service
+            .remove_member(&ManageMemberRequest::new(group.id(), member, member))
+            .await
+            .unwrap();
+        assert!(!group.grants_access(member, wishlists[0]));
+        let feed = service
+            .group_feed(&GroupFeedRequest::new(
+                group.id(),
+                admin,
+                WishlistFilter::default(),
+                PageRequest::default(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(feed.entries().len(), 1);
+        let result = service
+            .group_feed(&GroupFeedRequest::new(
+                group.id(),
+                member,
+                WishlistFilter::default(),
+                PageRequest::default(),
+            ))
+            .await;
+        assert!(matches!(result, Err(GroupFeedError::NotMember { .. })));
+    }
+}
@@ -0,0 +1,288 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use crate::domain::{
+    Blob, BlobStore, FetchImageError, FindImageError, FindImageRequest, ForgetUserError,
+    ImageFetcher, ImageProcessor, ImageRepository, ImageService, ImageSize, ItemImageUrl,
+    ProcessImageError, StoreImageError, StoredImage, UploadImageRequest,
+};
+
+pub struct Service<B, R, P, F>
+where
+    B: BlobStore,
+    R: ImageRepository,
+    P: ImageProcessor,
+    F: ImageFetcher,
+{
+    blob_store: Arc<B>,
+    image_repository: Arc<R>,
+    processor: Arc<P>,
+    fetcher: Arc<F>,
+    max_bytes: usize,
+}
+
+impl<B, R, P, F> Clone for Service<B, R, P, F>
+where
+    B: BlobStore,
+    R: ImageRepository,
+    P: ImageProcessor,
+    F: ImageFetcher,
+{
+    fn clone(&self) -> Self {
+        Self {
+            blob_store: self.blob_store.clone(),
+            image_repository: self.image_repository.clone(),
+            processor: self.processor.clone(),
+            fetcher: self.fetcher.clone(),
+            max_bytes: self.max_bytes,
+        }
+    }
+}
+
+impl<B, R, P, F> Service<B, R, P, F>
+where
+    B: BlobStore,
+    R: ImageRepository,
+    P: ImageProcessor,
+    F: ImageFetcher,
+{
+    /// Creates an image service rejecting any image larger than `max_bytes`.
+    pub fn new(
+        blob_store: Arc<B>,
+        image_repository: Arc<R>,
+        processor: Arc<P>,
+        fetcher: Arc<F>,
+        max_bytes: usize,
+    ) -> Self {
+        Self {
+            blob_store,
+            image_repository,
+            processor,
+            fetcher,
+            max_bytes,
+        }
+    }
+
+    /// Validates the image, derives its thumbnails and stores every size under a new ID, owned by
+    /// `owner_id`.
+    async fn store(&self, bytes: &[u8], owner_id: Uuid) -> Result<StoredImage, StoreImageError> {
+        if bytes.len() > self.max_bytes {
+            return Err(StoreImageError::TooLarge {
+                limit: self.max_bytes,
+            });
+        }
+        // Decoding and resizing are CPU-bound, so they run off the async workers
+        let (processor, bytes) = (self.processor.clone(), bytes.to_vec());
+        let blobs = tokio::task::spawn_blocking(move || processor.process(&bytes))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .map_err(|err| match err {
+                ProcessImageError::Unsupported => StoreImageError::Unsupported,
+                ProcessImageError::TooLarge { limit } => {
+                    StoreImageError::DimensionsTooLarge { limit }
+                }
+                ProcessImageError::Unkown(err) => StoreImageError::Unkown(err),
+            })?;
+        let id = Uuid::now_v7();
+        // The owner is recorded first, so that no stored blob escapes the purge of their account
+        self.image_repository
+            .save_owner(id, owner_id)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        for (size, blob) in blobs {
+            self.blob_store
+                .put(&blob_key(id, size), &blob)
+                .await
+                .map_err(|err| anyhow!(err))?;
+        }
+        Ok(StoredImage::new(id))
+    }
+}
+
+/// Returns the key an image is stored under in the given size.
+fn blob_key(id: Uuid, size: ImageSize) -> String {
+    format!("images/{}/{}", id, size)
+}
+
+impl<B, R, P, F> ImageService for Service<B, R, P, F>
+where
+    B: BlobStore,
+    R: ImageRepository,
+    P: ImageProcessor,
+    F: ImageFetcher,
+{
+    async fn upload_image(&self, req: &UploadImageRequest) -> Result<StoredImage, StoreImageError> {
+        self.store(req.bytes(), req.user_id()).await
+    }
+
+    async fn mirror_image(
+        &self,
+        url: &ItemImageUrl,
+        owner_id: Uuid,
+    ) -> Result<StoredImage, StoreImageError> {
+        let bytes = self
+            .fetcher
+            .fetch_image(url, self.max_bytes)
+            .await
+            .map_err(|err| match err {
+                FetchImageError::TooLarge { limit } => StoreImageError::TooLarge { limit },
+                err => StoreImageError::Unkown(anyhow!(err)),
+            })?;
+        self.store(&bytes, owner_id).await
+    }
+
+    async fn find_image(&self, req: &FindImageRequest) -> Result<Option<Blob>, FindImageError> {
+        self.blob_store
+            .get(&blob_key(req.id(), req.size()))
+            .await
+            .map_err(|err| FindImageError::Unkown(anyhow!(err)))
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let images = self
+            .image_repository
+            .find_images_by_owner(user_id)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        for id in images {
+            for size in ImageSize::ALL {
+                self.blob_store
+                    .delete(&blob_key(id, size))
+                    .await
+                    .map_err(|err| anyhow!(err))?;
+            }
+        }
+        // The owners go last, so that an interrupted purge still finds the remaining blobs
+        self.image_repository.forget_user(user_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Mutex};
+
+    use super::*;
+    use crate::domain::{MockBlobStore, MockImageFetcher, MockImageProcessor, MockImageRepository};
+    use crate::infrastructure::persistence::in_memory::image::InMemoryImageRepository;
+
+    fn processor() -> MockImageProcessor {
+        let mut processor = MockImageProcessor::new();
+        processor.expect_process().returning(|bytes| {
+            Ok(ImageSize::ALL
+                .into_iter()
+                .map(|size| (size, Blob::new("image/png", bytes.to_vec())))
+                .collect())
+        });
+        processor
+    }
+
+    fn recording_store(keys: Arc<Mutex<Vec<String>>>) -> MockBlobStore {
+        let mut store = MockBlobStore::new();
+        store.expect_put().returning(move |key, _| {
+            keys.lock().unwrap().push(key.to_string());
+            Box::pin(future::ready(Ok(())))
+        });
+        store
+    }
+
+    #[tokio::test]
+    async fn test_upload_image_stores_every_size() {
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let image_repository = Arc::new(InMemoryImageRepository::new());
+        let service = Service::new(
+            Arc::new(recording_store(keys.clone())),
+            image_repository.clone(),
+            Arc::new(processor()),
+            Arc::new(MockImageFetcher::new()),
+            16,
+        );
+
+        let owner = Uuid::now_v7();
+        let image = service
+            .upload_image(&UploadImageRequest::new(owner, vec![1, 2, 3]))
+            .await
+            .unwrap();
+        assert_eq!(
+            image_repository.find_images_by_owner(owner).await.unwrap(),
+            vec![image.id()]
+        );
+        let keys = keys.lock().unwrap();
+        assert_eq!(keys.len(), ImageSize::ALL.len());
+        assert!(keys.contains(&format!("images/{}/small", image.id())));
+    }
+
+    #[tokio::test]
+    async fn test_upload_image_too_large() {
+        let mut store = MockBlobStore::new();
+        store.expect_put().never();
+        let service = Service::new(
+            Arc::new(store),
+            Arc::new(MockImageRepository::new()),
+            Arc::new(MockImageProcessor::new()),
+            Arc::new(MockImageFetcher::new()),
+            2,
+        );
+
+        let result = service
+            .upload_image(&UploadImageRequest::new(Uuid::now_v7(), vec![1, 2, 3]))
+            .await;
+        assert!(matches!(
+            result,
+            Err(StoreImageError::TooLarge { limit: 2 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_image_unsupported() {
+        let mut processor = MockImageProcessor::new();
+        processor
+            .expect_process()
+            .returning(|_| Err(ProcessImageError::Unsupported));
+        let service = Service::new(
+            Arc::new(MockBlobStore::new()),
+            Arc::new(MockImageRepository::new()),
+            Arc::new(processor),
+            Arc::new(MockImageFetcher::new()),
+            16,
+        );
+
+        let result = service
+            .upload_image(&UploadImageRequest::new(Uuid::now_v7(), b"<svg/>".to_vec()))
+            .await;
+        assert!(matches!(result, Err(StoreImageError::Unsupported)));
+    }
+
+    #[tokio::test]
+    async fn test_mirror_image() {
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let mut fetcher = MockImageFetcher::new();
+        fetcher
+            .expect_fetch_image()
+            .withf(|_, max_bytes| *max_bytes == 16)
+            .returning(|_, _| Box::pin(future::ready(Ok(vec![1, 2, 3]))));
+        let image_repository = Arc::new(InMemoryImageRepository::new());
+        let service = Service::new(
+            Arc::new(recording_store(keys.clone())),
+            image_repository.clone(),
+            Arc::new(processor()),
+            Arc::new(fetcher),
+            16,
+        );
+
+        let owner = Uuid::now_v7();
+        let image = service
+            .mirror_image(&"https://shop.example/p/1.jpg".into(), owner)
+            .await
+            .unwrap();
+        assert!(keys
+            .lock()
+            .unwrap()
+            .contains(&format!("images/{}/original", image.id())));
+        assert_eq!(
+            image_repository.find_images_by_owner(owner).await.unwrap(),
+            vec![image.id()]
+        );
+    }
+}
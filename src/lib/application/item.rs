@@ -0,0 +1,1980 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use crate::domain::{
+    Clock, CreateItemError, CreateItemRequest, ExtractorRegistry, FetchItemPageError,
+    FindImageRequest, FindItemByIdRequest, FindPriceHistoryError, FindPriceHistoryRequest,
+    FindWishlistByIdRequest, GroupRepository, ImageService, ImageSize, Item, ItemChange,
+    ItemEventAccess, ItemEventBus, ItemEventStream, ItemLinkUrl, ItemListing, ItemMetadata,
+    ItemPageFetcher, ItemPrice, ItemRepository, ItemService, ListItemsError, ListItemsRequest,
+    MarkItemReceivedError, MarkItemReceivedRequest, Notification, NotificationKind, Notifier,
+    PriceHistoryRepository, PricePoint, PriceWatch, RefreshPricesError, ReserveItemError,
+    ReserveItemRequest, ShareToken, TransferItemError, TransferItemRequest, UpdateItemError,
+    ViewSharedWishlistError, ViewSharedWishlistRequest, ViewWishlistsError, ViewWishlistsRequest,
+    WatchItemPriceError, WatchItemPriceRequest, WatchItemsError, WatchItemsRequest, Wishlist,
+    WishlistRepository, WishlistView,
+};
+
+pub struct Service<W, I, P, F, N, G, R, E, C>
+where
+    W: WishlistRepository,
+    I: ItemRepository,
+    P: PriceHistoryRepository,
+    F: ItemPageFetcher,
+    N: Notifier,
+    G: ImageService,
+    R: GroupRepository,
+    E: ItemEventBus,
+    C: Clock,
+{
+    wish_repository: Arc<W>,
+    item_repository: Arc<I>,
+    price_repository: Arc<P>,
+    page_fetcher: Arc<F>,
+    extractors: ExtractorRegistry,
+    notifier: Arc<N>,
+    image_service: Arc<G>,
+    group_repository: Arc<R>,
+    event_bus: Arc<E>,
+    clock: Arc<C>,
+    mirror_images: bool,
+}
+
+impl<W, I, P, F, N, G, R, E, C> Clone for Service<W, I, P, F, N, G, R, E, C>
+where
+    W: WishlistRepository,
+    I: ItemRepository,
+    P: PriceHistoryRepository,
+    F: ItemPageFetcher,
+    N: Notifier,
+    G: ImageService,
+    R: GroupRepository,
+    E: ItemEventBus,
+    C: Clock,
+{
+    fn clone(&self) -> Self {
+        Self {
+            wish_repository: self.wish_repository.clone(),
+            item_repository: self.item_repository.clone(),
+            price_repository: self.price_repository.clone(),
+            page_fetcher: self.page_fetcher.clone(),
+            extractors: self.extractors.clone(),
+            notifier: self.notifier.clone(),
+            image_service: self.image_service.clone(),
+            group_repository: self.group_repository.clone(),
+            event_bus: self.event_bus.clone(),
+            clock: self.clock.clone(),
+            mirror_images: self.mirror_images,
+        }
+    }
+}
+
+impl<W, I, P, F, N, G, R, E, C> Service<W, I, P, F, N, G, R, E, C>
+where
+    W: WishlistRepository,
+    I: ItemRepository,
+    P: PriceHistoryRepository,
+    F: ItemPageFetcher,
+    N: Notifier,
+    G: ImageService,
+    R: GroupRepository,
+    E: ItemEventBus,
+    C: Clock,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        wish_repository: Arc<W>,
+        item_repository: Arc<I>,
+        price_repository: Arc<P>,
+        page_fetcher: Arc<F>,
+        extractors: ExtractorRegistry,
+        notifier: Arc<N>,
+        image_service: Arc<G>,
+        group_repository: Arc<R>,
+        event_bus: Arc<E>,
+        clock: Arc<C>,
+    ) -> Self {
+        Self {
+            wish_repository,
+            item_repository,
+            price_repository,
+            page_fetcher,
+            extractors,
+            notifier,
+            image_service,
+            group_repository,
+            event_bus,
+            clock,
+            mirror_images: false,
+        }
+    }
+
+    /// Enables storing a copy of the remote image of new items, so it is served from our own
+    /// routes instead of being hotlinked.
+    pub fn with_image_mirroring(self, mirror_images: bool) -> Self {
+        Self {
+            mirror_images,
+            ..self
+        }
+    }
+
+    /// Fetches the page behind `url` and extracts its product details with the most specific
+    /// extractor registered for its host.
+    async fn fetch_metadata(&self, url: &ItemLinkUrl) -> Result<ItemMetadata, FetchItemPageError> {
+        let page = self.page_fetcher.fetch(url).await?;
+        Ok(self.extractors.extract(&page))
+    }
+
+    /// Completes the request with the details found on the item's page, if any are missing.
+    ///
+    /// A page that cannot be fetched is not an error: the item is created with what the client
+    /// provided.
+    async fn prefill(&self, req: &CreateItemRequest) -> CreateItemRequest {
+        if !req.is_incomplete() {
+            return req.clone();
+        }
+        match self.fetch_metadata(req.link_url()).await {
+            Ok(metadata) => req.prefilled_with(metadata),
+            Err(err) => {
+                tracing::warn!("failed to fetch metadata for {}: {}", req.link_url(), err);
+                req.clone()
+            }
+        }
+    }
+
+    /// Checks that the uploaded image attached to the request exists or, when mirroring is
+    /// enabled, attaches a copy of its remote image.
+    ///
+    /// An image that cannot be mirrored is not an error: the item keeps its remote image.
+    async fn attach_image(
+        &self,
+        req: CreateItemRequest,
+    ) -> Result<CreateItemRequest, CreateItemError> {
+        if let Some(id) = req.image_id() {
+            let image = self
+                .image_service
+                .find_image(&FindImageRequest::new(id, ImageSize::Original))
+                .await
+                .map_err(|err| anyhow!(err))?;
+            if image.is_none() {
+                return Err(CreateItemError::ImageDoesNotExist { id });
+            }
+            return Ok(req);
+        }
+        let Some(url) = req.image_url().filter(|_| self.mirror_images) else {
+            return Ok(req);
+        };
+        match self.image_service.mirror_image(url, req.user_id()).await {
+            Ok(image) => Ok(req.with_image_id(Some(image.id()))),
+            Err(err) => {
+                tracing::warn!("failed to mirror image {}: {}", url, err);
+                Ok(req)
+            }
+        }
+    }
+
+    /// Re-fetches the price of a single item, records it if it changed and notifies the
+    /// watchers when it dropped.
+    async fn refresh_price(&self, item: Item) -> Result<(), RefreshPricesError> {
+        let price = match self.fetch_metadata(item.link_url()).await {
+            Ok(metadata) => match metadata.price() {
+                Some(price) => price.clone(),
+                None => return Ok(()),
+            },
+            Err(err) => {
+                tracing::warn!("failed to refresh price of {}: {}", item.id(), err);
+                return Ok(());
+            }
+        };
+        let history = self
+            .price_repository
+            .find_price_history(&FindPriceHistoryRequest::new(item.id()))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let previous = history
+            .last()
+            .map(|point| point.price().clone())
+            .or_else(|| item.price().cloned());
+        if history.is_empty() || previous.as_ref() != Some(&price) {
+            self.price_repository
+                .record_price(item.id(), &PricePoint::new(price.clone(), self.clock.now()))
+                .await
+                .map_err(|err| anyhow!(err))?;
+        }
+        if let Some(updated) = self.update_price(item.id(), &price).await? {
+            let wishlist = self
+                .wish_repository
+                .find_wishlist_by_id(&FindWishlistByIdRequest::new(updated.wishlist_id()))
+                .await
+                .map_err(|err| anyhow!(err))?;
+            if let Some(wishlist) = wishlist {
+                self.publish(&wishlist, ItemChange::Edited(updated)).await;
+            }
+        }
+        let Some(previous) = previous else {
+            return Ok(());
+        };
+        let watches = self
+            .price_repository
+            .find_price_watches(item.id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        for watch in watches
+            .iter()
+            .filter(|watch| watch.is_triggered_by(&previous, &price))
+        {
+            let notification = Notification::new(
+                watch.user_id(),
+                NotificationKind::PriceDrop {
+                    item_id: item.id(),
+                    title: item.title().clone(),
+                    previous_price: previous.clone(),
+                    current_price: price.clone(),
+                },
+            );
+            if let Err(err) = self.notifier.notify(&notification).await {
+                tracing::warn!("failed to notify {}: {}", watch.user_id(), err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the refreshed price of an item, if it differs. The item is read again since it may
+    /// have been edited during the fetch; when it is edited again before the update, the update
+    /// is retried once and then skipped until the next refresh.
+    async fn update_price(&self, item_id: Uuid, price: &ItemPrice) -> anyhow::Result<Option<Item>> {
+        for _ in 0..2 {
+            let Some(mut item) = self
+                .item_repository
+                .find_item_by_id(&FindItemByIdRequest::new(item_id))
+                .await?
+            else {
+                return Ok(None);
+            };
+            if item.price() == Some(price) {
+                return Ok(None);
+            }
+            item.set_price(Some(price.clone()));
+            match self.item_repository.update(&item).await {
+                Ok(updated) => return Ok(Some(updated)),
+                Err(UpdateItemError::VersionMismatch(_)) => continue,
+                Err(UpdateItemError::ItemDoesNotExist { .. }) => return Ok(None),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        tracing::warn!("skipped the price of {}, which kept changing", item_id);
+        Ok(None)
+    }
+
+    /// Tells the viewers of `wishlist` about a change of its items. A change that cannot be
+    /// published is only missed by the viewers: it does not fail the operation.
+    async fn publish(&self, wishlist: &Wishlist, change: ItemChange) {
+        if let Err(err) = self.event_bus.publish(wishlist, change).await {
+            tracing::warn!("failed to publish a change of {}: {}", wishlist.id(), err);
+        }
+    }
+
+    /// Returns true if the item exists and `viewer_id`, or anyone holding `share_token`, may see
+    /// its wishlist.
+    async fn item_visible(
+        &self,
+        id: Uuid,
+        viewer_id: Option<Uuid>,
+        share_token: Option<&ShareToken>,
+    ) -> anyhow::Result<bool> {
+        let Some(item) = self
+            .item_repository
+            .find_item_by_id(&FindItemByIdRequest::new(id))
+            .await?
+        else {
+            return Ok(false);
+        };
+        let Some(wishlist) = self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(item.wishlist_id()))
+            .await?
+        else {
+            return Ok(false);
+        };
+        if wishlist.is_visible_with(share_token) {
+            return Ok(true);
+        }
+        let Some(viewer_id) = viewer_id else {
+            return Ok(false);
+        };
+        let groups = self
+            .group_repository
+            .find_groups_by_member(viewer_id)
+            .await?;
+        Ok(wishlist.is_visible_to(viewer_id, &groups))
+    }
+
+    /// Finds the item to move or copy, along with its wishlist and the target wishlist, making
+    /// sure the caller owns both.
+    async fn find_transferable_item(
+        &self,
+        req: &TransferItemRequest,
+    ) -> Result<(Item, Wishlist, Wishlist), TransferItemError> {
+        let item = self
+            .item_repository
+            .find_item_by_id(&FindItemByIdRequest::new(req.item_id()))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(TransferItemError::ItemDoesNotExist { id: req.item_id() })?;
+        let mut wishlists = Vec::with_capacity(2);
+        for id in [item.wishlist_id(), req.wishlist_id()] {
+            let wishlist = self
+                .wish_repository
+                .find_wishlist_by_id(&FindWishlistByIdRequest::new(id))
+                .await
+                .map_err(|err| anyhow!(err))?
+                .ok_or(TransferItemError::WishlistDoesNotExist { id })?;
+            if wishlist.owner_id() != req.user_id() {
+                return Err(TransferItemError::NotWishlistOwner { id });
+            }
+            if id == req.wishlist_id() && wishlist.archived() {
+                return Err(TransferItemError::WishlistArchived { id });
+            }
+            wishlists.push(wishlist);
+        }
+        let (Some(target), Some(source)) = (wishlists.pop(), wishlists.pop()) else {
+            return Err(TransferItemError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            });
+        };
+        Ok((item, source, target))
+    }
+}
+
+impl<W, I, P, F, N, G, R, E, C> ItemService for Service<W, I, P, F, N, G, R, E, C>
+where
+    W: WishlistRepository + Send + Sync + 'static,
+    I: ItemRepository + Send + Sync + 'static,
+    P: PriceHistoryRepository + Send + Sync + 'static,
+    F: ItemPageFetcher,
+    N: Notifier,
+    G: ImageService,
+    R: GroupRepository,
+    E: ItemEventBus,
+    C: Clock,
+{
+    async fn create_item(&self, req: &CreateItemRequest) -> Result<Item, CreateItemError> {
+        let wishlist = match self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(req.wishlist_id()))
+            .await
+        {
+            Ok(Some(wishlist)) if wishlist.owner_id() != req.user_id() => {
+                return Err(CreateItemError::NotWishlistOwner {
+                    id: req.wishlist_id(),
+                })
+            }
+            Ok(Some(wishlist)) if wishlist.archived() => {
+                return Err(CreateItemError::WishlistArchived {
+                    id: req.wishlist_id(),
+                })
+            }
+            Ok(Some(wishlist)) => wishlist,
+            Ok(None) => {
+                return Err(CreateItemError::WishlistDoesNotExist {
+                    id: req.wishlist_id(),
+                })
+            }
+            Err(err) => return Err(CreateItemError::Unkown(err.into())),
+        };
+        let req = self.prefill(req).await;
+        if req.title().is_none() {
+            return Err(CreateItemError::MissingTitle);
+        }
+        let req = self.attach_image(req).await?;
+        let item = self.item_repository.save(&req).await?;
+        self.wish_repository
+            .add_item(item.wishlist_id(), item.id())
+            .await
+            .map_err(|err| CreateItemError::Unkown(err.into()))?;
+        self.publish(&wishlist, ItemChange::Added(item.clone()))
+            .await;
+        Ok(item)
+    }
+
+    async fn list_items(&self, req: &ListItemsRequest) -> Result<ItemListing, ListItemsError> {
+        let wishlist_does_not_exist = || ListItemsError::WishlistDoesNotExist {
+            id: req.wishlist_id(),
+        };
+        let wishlist = self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(req.wishlist_id()))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or_else(wishlist_does_not_exist)?;
+        if !wishlist.is_visible_with(req.share_token()) {
+            let Some(viewer_id) = req.viewer_id() else {
+                return Err(wishlist_does_not_exist());
+            };
+            let groups = self
+                .group_repository
+                .find_groups_by_member(viewer_id)
+                .await
+                .map_err(|err| anyhow!(err))?;
+            if !wishlist.is_visible_to(viewer_id, &groups) {
+                return Err(wishlist_does_not_exist());
+            }
+        }
+        let items = self
+            .item_repository
+            .list_items(req, &wishlist)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(ItemListing::new(wishlist, items))
+    }
+
+    async fn view_wishlists(
+        &self,
+        req: &ViewWishlistsRequest,
+    ) -> Result<Vec<WishlistView>, ViewWishlistsError> {
+        let groups = self
+            .group_repository
+            .find_groups_by_member(req.user_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let wishlists: Vec<Wishlist> = self
+            .wish_repository
+            .find_wishlists_by_ids(req.wishlist_ids())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .into_iter()
+            .filter(|wishlist| wishlist.is_visible_to(req.user_id(), &groups))
+            .collect();
+        let wishlist_ids: Vec<Uuid> = wishlists.iter().map(Wishlist::id).collect();
+        let mut items: HashMap<Uuid, Vec<Item>> = HashMap::new();
+        for item in self
+            .item_repository
+            .find_items_by_wishlists(&wishlist_ids)
+            .await
+            .map_err(|err| anyhow!(err))?
+        {
+            items.entry(item.wishlist_id()).or_default().push(item);
+        }
+        Ok(wishlists
+            .into_iter()
+            .map(|wishlist| {
+                let items = items.remove(&wishlist.id()).unwrap_or_default();
+                WishlistView::new(req.user_id(), wishlist, items)
+            })
+            .collect())
+    }
+
+    async fn view_shared_wishlist(
+        &self,
+        req: &ViewSharedWishlistRequest,
+    ) -> Result<WishlistView, ViewSharedWishlistError> {
+        let wishlist = self
+            .wish_repository
+            .find_wishlist_by_slug(req.slug())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .filter(|wishlist| wishlist.is_visible_with(req.share_token()))
+            .ok_or_else(|| ViewSharedWishlistError::WishlistDoesNotExist {
+                slug: req.slug().to_string(),
+            })?;
+        let items = self
+            .item_repository
+            .find_items_by_wishlists(&[wishlist.id()])
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(WishlistView::new(req.viewer_id(), wishlist, items))
+    }
+
+    async fn refresh_prices(&self) -> Result<(), RefreshPricesError> {
+        let items = self
+            .item_repository
+            .find_items()
+            .await
+            .map_err(|err| anyhow!(err))?;
+        for item in items {
+            self.refresh_price(item).await?;
+        }
+        Ok(())
+    }
+
+    async fn find_price_history(
+        &self,
+        req: &FindPriceHistoryRequest,
+    ) -> Result<Vec<PricePoint>, FindPriceHistoryError> {
+        if !self
+            .item_visible(req.item_id(), req.viewer_id(), req.share_token())
+            .await?
+        {
+            return Err(FindPriceHistoryError::ItemDoesNotExist { id: req.item_id() });
+        }
+        self.price_repository.find_price_history(req).await
+    }
+
+    async fn watch_item_price(
+        &self,
+        req: &WatchItemPriceRequest,
+    ) -> Result<PriceWatch, WatchItemPriceError> {
+        if !self
+            .item_visible(req.item_id(), Some(req.user_id()), req.share_token())
+            .await?
+        {
+            return Err(WatchItemPriceError::ItemDoesNotExist { id: req.item_id() });
+        }
+        self.price_repository.save_price_watch(req).await
+    }
+
+    async fn move_item(&self, req: &TransferItemRequest) -> Result<Item, TransferItemError> {
+        let (item, source, target) = self.find_transferable_item(req).await?;
+        if item.wishlist_id() == req.wishlist_id() {
+            return Ok(item);
+        }
+        let moved = self
+            .item_repository
+            .move_item(item.id(), req.wishlist_id())
+            .await?;
+        // The item joins its target before it leaves its source, so a failure in between leaves
+        // it listed twice rather than in no wishlist at all.
+        if let Err(err) = self
+            .wish_repository
+            .add_item(moved.wishlist_id(), moved.id())
+            .await
+        {
+            if let Err(undo) = self
+                .item_repository
+                .move_item(item.id(), item.wishlist_id())
+                .await
+            {
+                tracing::warn!("failed to move back item {}: {}", item.id(), undo);
+            }
+            return Err(anyhow!(err).into());
+        }
+        self.wish_repository
+            .remove_item(item.wishlist_id(), item.id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        self.publish(&source, ItemChange::Removed { item_id: item.id() })
+            .await;
+        self.publish(&target, ItemChange::Added(moved.clone()))
+            .await;
+        Ok(moved)
+    }
+
+    async fn copy_item(&self, req: &TransferItemRequest) -> Result<Item, TransferItemError> {
+        let (item, _, target) = self.find_transferable_item(req).await?;
+        let copy = self
+            .item_repository
+            .save(&CreateItemRequest::copy_of(
+                &item,
+                req.wishlist_id(),
+                req.user_id(),
+            ))
+            .await
+            .map_err(|err| match err {
+                CreateItemError::Duplicate => TransferItemError::Duplicate,
+                err => TransferItemError::Unkown(err.into()),
+            })?;
+        self.wish_repository
+            .add_item(copy.wishlist_id(), copy.id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        self.publish(&target, ItemChange::Added(copy.clone())).await;
+        Ok(copy)
+    }
+
+    async fn mark_item_received(
+        &self,
+        req: &MarkItemReceivedRequest,
+    ) -> Result<Item, MarkItemReceivedError> {
+        let mut item = self
+            .item_repository
+            .find_item_by_id(&FindItemByIdRequest::new(req.item_id()))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(MarkItemReceivedError::ItemDoesNotExist { id: req.item_id() })?;
+        let wishlist = self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(item.wishlist_id()))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .filter(|wishlist| wishlist.owner_id() == req.user_id())
+            .ok_or(MarkItemReceivedError::NotWishlistOwner {
+                id: item.wishlist_id(),
+            })?;
+        item.check_version(req.expected_version())?;
+        item.set_received(req.received());
+        let item = self
+            .item_repository
+            .update(&item)
+            .await
+            .map_err(|err| match err {
+                UpdateItemError::VersionMismatch(err) => err.into(),
+                err => MarkItemReceivedError::Unkown(err.into()),
+            })?;
+        self.publish(&wishlist, ItemChange::Edited(item.clone()))
+            .await;
+        Ok(item)
+    }
+
+    async fn reserve_item(&self, req: &ReserveItemRequest) -> Result<Item, ReserveItemError> {
+        let item_does_not_exist = || ReserveItemError::ItemDoesNotExist { id: req.item_id() };
+        let item = self
+            .item_repository
+            .find_item_by_id(&FindItemByIdRequest::new(req.item_id()))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or_else(item_does_not_exist)?;
+        let wishlist = self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(item.wishlist_id()))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or_else(item_does_not_exist)?;
+        if wishlist.owner_id() == req.user_id() {
+            return Err(ReserveItemError::OwnItem);
+        }
+        // A reservation can be cancelled even after losing access to the wishlist
+        if req.reserved() {
+            let groups = self
+                .group_repository
+                .find_groups_by_member(req.user_id())
+                .await
+                .map_err(|err| anyhow!(err))?;
+            if !wishlist.is_visible_to(req.user_id(), &groups)
+                && !wishlist.is_visible_with(req.share_token())
+            {
+                return Err(item_does_not_exist());
+            }
+        }
+        if wishlist.archived() {
+            return Err(ReserveItemError::WishlistArchived { id: wishlist.id() });
+        }
+        let item = self
+            .item_repository
+            .reserve_item(req.item_id(), req.user_id(), req.reserved())
+            .await?;
+        self.publish(
+            &wishlist,
+            ItemChange::Reserved {
+                item_id: item.id(),
+                reserved: req.reserved(),
+            },
+        )
+        .await;
+        Ok(item)
+    }
+
+    async fn watch_items(
+        &self,
+        req: &WatchItemsRequest,
+    ) -> Result<ItemEventStream, WatchItemsError> {
+        let wishlist_does_not_exist = || WatchItemsError::WishlistDoesNotExist {
+            id: req.wishlist_id(),
+        };
+        let wishlist = self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(req.wishlist_id()))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or_else(wishlist_does_not_exist)?;
+        let groups = self
+            .group_repository
+            .find_groups_by_member(req.user_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if !wishlist.is_visible_to(req.user_id(), &groups) {
+            return Err(wishlist_does_not_exist());
+        }
+        let subscription = self
+            .event_bus
+            .subscribe(wishlist.id(), req.last_event_id())
+            .await?;
+        // Access may be revoked while the stream is open: the wishlist made private, or the
+        // viewer out of the groups it is shared with. Check it again before every event.
+        let (wish_repository, group_repository) =
+            (self.wish_repository.clone(), self.group_repository.clone());
+        let (wishlist_id, viewer) = (wishlist.id(), req.user_id());
+        let access: ItemEventAccess = Box::new(move || {
+            let (wish_repository, group_repository) =
+                (wish_repository.clone(), group_repository.clone());
+            Box::pin(async move {
+                still_visible(&*wish_repository, &*group_repository, wishlist_id, viewer).await
+            })
+        });
+        Ok(ItemEventStream::new(viewer, subscription).with_access(access))
+    }
+}
+
+/// Tells whether `viewer` may still see a wishlist they are watching. Errors count as a no, so
+/// that the stream ends rather than leaks.
+async fn still_visible<W, R>(
+    wish_repository: &W,
+    group_repository: &R,
+    wishlist_id: Uuid,
+    viewer: Uuid,
+) -> bool
+where
+    W: WishlistRepository,
+    R: GroupRepository,
+{
+    let wishlist = wish_repository
+        .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlist_id))
+        .await;
+    let Ok(Some(wishlist)) = wishlist else {
+        return false;
+    };
+    match group_repository.find_groups_by_member(viewer).await {
+        Ok(groups) => wishlist.is_visible_to(viewer, &groups),
+        Err(err) => {
+            tracing::warn!("failed to check who may see {}: {}", wishlist_id, err);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        domain::{
+            CreateGroupRequest, CreateWishlistRequest, HostPattern, ItemMetadataExtractor,
+            ItemNotes, ItemPage, ItemPrice, ItemPriority, ItemSort, MockGroupRepository,
+            MockImageService, MockItemPageFetcher, MockItemRepository, MockNotifier,
+            MockPriceHistoryRepository, MockWishlistRepository, ShareToken, SharedWishlist,
+            StoredImage, VersionMismatchError, Wishlist,
+        },
+        infrastructure::clock::{ManualClock, SystemClock},
+        infrastructure::events::InMemoryItemEventBus,
+        infrastructure::persistence::in_memory::{
+            group::InMemoryGroupRepository, item::InMemoryItemRepository,
+            price_history::InMemoryPriceHistoryRepository, wishlist::InMemoryWishlistRepository,
+        },
+    };
+
+    /// Reads pages made of `key: value` lines, e.g. `price: 12.50`.
+    struct LinesExtractor;
+
+    impl ItemMetadataExtractor for LinesExtractor {
+        fn hosts(&self) -> Vec<HostPattern> {
+            Vec::new()
+        }
+
+        fn extract(&self, page: &ItemPage) -> ItemMetadata {
+            let value = |key: &str| {
+                page.html()
+                    .lines()
+                    .find_map(|line| line.strip_prefix(key)?.strip_prefix(": "))
+            };
+            ItemMetadata::new(
+                value("title").map(Into::into),
+                value("image").map(Into::into),
+                value("price").map(|price| ItemPrice::parse(price).unwrap()),
+            )
+        }
+    }
+
+    /// Always reports the same title, for the hosts it is registered for.
+    struct FixedTitleExtractor(&'static str);
+
+    impl ItemMetadataExtractor for FixedTitleExtractor {
+        fn hosts(&self) -> Vec<HostPattern> {
+            vec![HostPattern::new("*.shop.example").unwrap()]
+        }
+
+        fn extract(&self, _page: &ItemPage) -> ItemMetadata {
+            ItemMetadata::new(Some(self.0.into()), None, None)
+        }
+    }
+
+    fn page_fetcher(html: &'static str) -> MockItemPageFetcher {
+        let mut fetcher = MockItemPageFetcher::new();
+        fetcher.expect_fetch().times(1).returning(move |url| {
+            let page = ItemPage::new(url.as_url().clone(), html.to_string());
+            Box::pin(future::ready(Ok(page)))
+        });
+        fetcher
+    }
+
+    fn wish_repository(id: Uuid) -> MockWishlistRepository {
+        let mut wish_mock_repo = MockWishlistRepository::new();
+        wish_mock_repo
+            .expect_find_wishlist_by_id()
+            .returning(move |_| {
+                Box::pin(future::ready(Ok(Some(Wishlist::new(
+                    id,
+                    id,
+                    "".into(),
+                    "".into(),
+                    false,
+                )))))
+            });
+        wish_mock_repo
+            .expect_add_item()
+            .returning(|_, _| Box::pin(future::ready(Ok(()))));
+        wish_mock_repo
+    }
+
+    fn item_repository() -> MockItemRepository {
+        let mut item_mock_repo = MockItemRepository::new();
+        item_mock_repo.expect_save().returning(|req| {
+            Box::pin(future::ready(Ok(Item::create(
+                Uuid::now_v7(),
+                req.wishlist_id(),
+                req.title().cloned().unwrap(),
+                req.link_url().clone(),
+                req.image_url().cloned(),
+                req.price().cloned(),
+            ))))
+        });
+        item_mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_create_item_prefilled_from_metadata() {
+        let id = Uuid::now_v7();
+        let req = CreateItemRequest::new(
+            id,
+            id,
+            Some("My title".into()),
+            "https://shop.example/p/1".into(),
+            None,
+            None,
+        );
+        let fetcher =
+            page_fetcher("title: Page title\nimage: https://shop.example/p/1.jpg\nprice: 19.99");
+        let service = Service::new(
+            Arc::new(wish_repository(id)),
+            Arc::new(item_repository()),
+            Arc::new(MockPriceHistoryRepository::new()),
+            Arc::new(fetcher),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+
+        let item = service.create_item(&req).await.unwrap();
+        assert_eq!(item.title(), &"My title".into());
+        assert_eq!(
+            item.image_url(),
+            Some(&"https://shop.example/p/1.jpg".into())
+        );
+        assert_eq!(item.price(), Some(&19.99.into()));
+    }
+
+    #[tokio::test]
+    async fn test_create_item_skips_fetch_when_complete() {
+        let id = Uuid::now_v7();
+        let req = CreateItemRequest::new(
+            id,
+            id,
+            Some("My title".into()),
+            "https://shop.example/p/1".into(),
+            Some("https://shop.example/p/1.jpg".into()),
+            Some(5.0.into()),
+        );
+        let mut fetcher = MockItemPageFetcher::new();
+        fetcher.expect_fetch().never();
+        let service = Service::new(
+            Arc::new(wish_repository(id)),
+            Arc::new(item_repository()),
+            Arc::new(MockPriceHistoryRepository::new()),
+            Arc::new(fetcher),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+
+        assert!(service.create_item(&req).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_item_in_wishlist_of_someone_else() {
+        let id = Uuid::now_v7();
+        let req = CreateItemRequest::new(
+            id,
+            Uuid::now_v7(),
+            Some("My title".into()),
+            "https://shop.example/p/1".into(),
+            None,
+            None,
+        );
+        let mut fetcher = MockItemPageFetcher::new();
+        fetcher.expect_fetch().never();
+        let service = Service::new(
+            Arc::new(wish_repository(id)),
+            Arc::new(MockItemRepository::new()),
+            Arc::new(MockPriceHistoryRepository::new()),
+            Arc::new(fetcher),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+
+        let result = service.create_item(&req).await;
+        assert!(matches!(
+            result,
+            Err(CreateItemError::NotWishlistOwner { id: wishlist_id }) if wishlist_id == id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_item_without_title() {
+        let id = Uuid::now_v7();
+        let req =
+            CreateItemRequest::new(id, id, None, "https://shop.example/p/1".into(), None, None);
+        let mut fetcher = MockItemPageFetcher::new();
+        fetcher
+            .expect_fetch()
+            .returning(|_| Box::pin(future::ready(Err(FetchItemPageError::Timeout))));
+        let service = Service::new(
+            Arc::new(wish_repository(id)),
+            Arc::new(item_repository()),
+            Arc::new(MockPriceHistoryRepository::new()),
+            Arc::new(fetcher),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+
+        let result = service.create_item(&req).await;
+        assert!(matches!(result, Err(CreateItemError::MissingTitle)));
+    }
+
+    #[tokio::test]
+    async fn test_create_item_with_registered_extractor() {
+        let id = Uuid::now_v7();
+        let fetcher = page_fetcher("title: Generic title\nprice: 7");
+        let service = Service::new(
+            Arc::new(wish_repository(id)),
+            Arc::new(item_repository()),
+            Arc::new(MockPriceHistoryRepository::new()),
+            Arc::new(fetcher),
+            ExtractorRegistry::new(LinesExtractor).register(FixedTitleExtractor("Shop title")),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+
+        let req = CreateItemRequest::new(
+            id,
+            id,
+            None,
+            "https://www.shop.example/p/1".into(),
+            None,
+            None,
+        );
+        let item = service.create_item(&req).await.unwrap();
+        assert_eq!(item.title(), &"Shop title".into());
+        assert_eq!(item.price(), Some(&7.0.into()));
+    }
+
+    #[tokio::test]
+    async fn test_create_item_mirrors_remote_image() {
+        let id = Uuid::now_v7();
+        let image_id = Uuid::now_v7();
+        let mut item_mock_repo = MockItemRepository::new();
+        item_mock_repo.expect_save().returning(|req| {
+            let mut item = Item::create(
+                Uuid::now_v7(),
+                req.wishlist_id(),
+                req.title().cloned().unwrap(),
+                req.link_url().clone(),
+                req.image_url().cloned(),
+                req.price().cloned(),
+            );
+            item.set_image_id(req.image_id());
+            Box::pin(future::ready(Ok(item)))
+        });
+        let mut image_service = MockImageService::new();
+        image_service
+            .expect_mirror_image()
+            .withf(|url, _| url.to_string() == "https://shop.example/p/1.jpg")
+            .return_once(move |_, _| Box::pin(future::ready(Ok(StoredImage::new(image_id)))));
+        let service = Service::new(
+            Arc::new(wish_repository(id)),
+            Arc::new(item_mock_repo),
+            Arc::new(MockPriceHistoryRepository::new()),
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(image_service),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        )
+        .with_image_mirroring(true);
+
+        let req = CreateItemRequest::new(
+            id,
+            id,
+            Some("My title".into()),
+            "https://shop.example/p/1".into(),
+            Some("https://shop.example/p/1.jpg".into()),
+            Some(5.0.into()),
+        );
+        let item = service.create_item(&req).await.unwrap();
+        assert_eq!(item.image_id(), Some(image_id));
+        assert_eq!(
+            item.image_url(),
+            Some(&"https://shop.example/p/1.jpg".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_item_with_unknown_image() {
+        let id = Uuid::now_v7();
+        let mut image_service = MockImageService::new();
+        image_service
+            .expect_find_image()
+            .return_once(|_| Box::pin(future::ready(Ok(None))));
+        let mut item_mock_repo = MockItemRepository::new();
+        item_mock_repo.expect_save().never();
+        let mut fetcher = MockItemPageFetcher::new();
+        fetcher
+            .expect_fetch()
+            .returning(|_| Box::pin(future::ready(Err(FetchItemPageError::Timeout))));
+        let service = Service::new(
+            Arc::new(wish_repository(id)),
+            Arc::new(item_mock_repo),
+            Arc::new(MockPriceHistoryRepository::new()),
+            Arc::new(fetcher),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(image_service),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+
+        let image_id = Uuid::now_v7();
+        let req = CreateItemRequest::new(
+            id,
+            id,
+            Some("My title".into()),
+            "https://shop.example/p/1".into(),
+            None,
+            Some(5.0.into()),
+        )
+        .with_image_id(Some(image_id));
+        let result = service.create_item(&req).await;
+        assert!(matches!(
+            result,
+            Err(CreateItemError::ImageDoesNotExist { id }) if id == image_id
+        ));
+    }
+
+    /// Returns a fetcher that reports the given prices, one per call, in order.
+    fn scripted_fetcher(prices: Vec<&'static str>) -> MockItemPageFetcher {
+        let prices = std::sync::Mutex::new(prices.into_iter());
+        let mut fetcher = MockItemPageFetcher::new();
+        fetcher.expect_fetch().returning(move |url| {
+            let html = format!("price: {}", prices.lock().unwrap().next().unwrap());
+            let page = ItemPage::new(url.as_url().clone(), html);
+            Box::pin(future::ready(Ok(page)))
+        });
+        fetcher
+    }
+
+    #[tokio::test]
+    async fn test_refresh_prices_records_history_and_notifies() {
+        let item_repo = Arc::new(InMemoryItemRepository::new());
+        let price_repo = Arc::new(InMemoryPriceHistoryRepository::new());
+        let item = item_repo
+            .save(&CreateItemRequest::new(
+                Uuid::now_v7(),
+                Uuid::now_v7(),
+                Some("Camera".into()),
+                "https://shop.example/camera".into(),
+                None,
+                Some(100.0.into()),
+            ))
+            .await
+            .unwrap();
+        let any_drop = Uuid::now_v7();
+        let big_drop = Uuid::now_v7();
+        let target = Uuid::now_v7();
+        for req in [
+            WatchItemPriceRequest::new(item.id(), any_drop, None, None),
+            WatchItemPriceRequest::new(item.id(), big_drop, Some(rust_decimal::Decimal::TEN), None),
+            WatchItemPriceRequest::new(item.id(), target, None, Some(70.0.into())),
+        ] {
+            price_repo.save_price_watch(&req).await.unwrap();
+        }
+        let notified = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut notifier = MockNotifier::new();
+        let sink = notified.clone();
+        notifier.expect_notify().returning(move |notification| {
+            sink.lock().unwrap().push(notification.clone());
+            Box::pin(future::ready(Ok(())))
+        });
+        let service = Service::new(
+            Arc::new(InMemoryWishlistRepository::new()),
+            item_repo.clone(),
+            price_repo.clone(),
+            Arc::new(scripted_fetcher(vec!["100", "95", "95", "80", "65"])),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(notifier),
+            Arc::new(MockImageService::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+
+        for _ in 0..5 {
+            service.refresh_prices().await.unwrap();
+        }
+
+        let history = price_repo
+            .find_price_history(&FindPriceHistoryRequest::new(item.id()))
+            .await
+            .unwrap();
+        let prices: Vec<_> = history.iter().map(|p| p.price().to_string()).collect();
+        assert_eq!(prices, vec!["100", "95", "80", "65"]);
+        let item = item_repo
+            .find_item_by_id(&FindItemByIdRequest::new(item.id()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(item.price(), Some(&65.0.into()));
+
+        let recipients: Vec<_> = notified
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|n| n.recipient_id())
+            .collect();
+        // 100 -> 95: any drop. 95 -> 80: any drop and more than 10%.
+        // 80 -> 65: any drop, more than 10% and crossing the 70 target.
+        assert_eq!(recipients.len(), 6);
+        assert_eq!(recipients.iter().filter(|id| **id == any_drop).count(), 3);
+        assert_eq!(recipients.iter().filter(|id| **id == big_drop).count(), 2);
+        assert_eq!(recipients.iter().filter(|id| **id == target).count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_price_of_item_edited_during_fetch() {
+        let item_repo = Arc::new(InMemoryItemRepository::new());
+        let item = item_repo
+            .save(&CreateItemRequest::new(
+                Uuid::now_v7(),
+                Uuid::now_v7(),
+                Some("Camera".into()),
+                "https://shop.example/camera".into(),
+                None,
+                Some(100.0.into()),
+            ))
+            .await
+            .unwrap();
+        // The owner edits the item while its page is being fetched
+        let mut fetcher = MockItemPageFetcher::new();
+        let (editor, item_id) = (item_repo.clone(), item.id());
+        fetcher.expect_fetch().times(1).returning(move |url| {
+            let page = ItemPage::new(url.as_url().clone(), "price: 80".to_string());
+            let editor = editor.clone();
+            Box::pin(async move {
+                let mut item = editor
+                    .find_item_by_id(&FindItemByIdRequest::new(item_id))
+                    .await
+                    .unwrap()
+                    .unwrap();
+                item.set_notes(Some("Black, not silver".into()));
+                editor.update(&item).await.unwrap();
+                Ok(page)
+            })
+        });
+        let now = "2027-03-14T08:00:00Z".parse().unwrap();
+        let price_repo = Arc::new(InMemoryPriceHistoryRepository::new());
+        let service = Service::new(
+            Arc::new(InMemoryWishlistRepository::new()),
+            item_repo.clone(),
+            price_repo.clone(),
+            Arc::new(fetcher),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(ManualClock::new(now)),
+        );
+
+        service.refresh_prices().await.unwrap();
+
+        let item = item_repo
+            .find_item_by_id(&FindItemByIdRequest::new(item.id()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(item.price(), Some(&80.0.into()));
+        assert_eq!(item.notes(), Some(&"Black, not silver".into()));
+        let history = price_repo
+            .find_price_history(&FindPriceHistoryRequest::new(item.id()))
+            .await
+            .unwrap();
+        assert_eq!(history, vec![PricePoint::new(80.0.into(), now)]);
+    }
+
+    #[tokio::test]
+    async fn test_find_price_history_of_unknown_item() {
+        let mut item_mock_repo = MockItemRepository::new();
+        item_mock_repo
+            .expect_find_item_by_id()
+            .returning(|_| Box::pin(future::ready(Ok(None))));
+        let service = Service::new(
+            Arc::new(MockWishlistRepository::new()),
+            Arc::new(item_mock_repo),
+            Arc::new(MockPriceHistoryRepository::new()),
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+
+        let result = service
+            .find_price_history(&FindPriceHistoryRequest::new(Uuid::now_v7()))
+            .await;
+        assert!(matches!(
+            result,
+            Err(FindPriceHistoryError::ItemDoesNotExist { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_price_history_and_watches_of_visible_wishlists() {
+        let (owner, member, stranger) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let group_repository = Arc::new(InMemoryGroupRepository::new());
+        let service = Service::new(
+            wish_repository.clone(),
+            item_repository.clone(),
+            Arc::new(InMemoryPriceHistoryRepository::new()),
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            group_repository.clone(),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+        let wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(owner, "Secret".into(), true))
+            .await
+            .unwrap();
+        let item = item_repository
+            .save(&CreateItemRequest::new(
+                wishlist.id(),
+                owner,
+                Some("Camera".into()),
+                "https://shop.example/camera".into(),
+                None,
+                Some(100.0.into()),
+            ))
+            .await
+            .unwrap();
+        let history = |viewer: Option<Uuid>, token: Option<ShareToken>| {
+            let service = service.clone();
+            let req = FindPriceHistoryRequest::new(item.id())
+                .with_viewer(viewer)
+                .with_share_token(token);
+            async move { service.find_price_history(&req).await }
+        };
+        let watch = |user_id: Uuid, token: Option<ShareToken>| {
+            let service = service.clone();
+            let req =
+                WatchItemPriceRequest::new(item.id(), user_id, None, None).with_share_token(token);
+            async move { service.watch_item_price(&req).await }
+        };
+
+        // A private wishlist is only open to its owner
+        assert!(history(Some(owner), None).await.is_ok());
+        assert!(watch(owner, None).await.is_ok());
+        assert!(matches!(
+            history(Some(stranger), None).await,
+            Err(FindPriceHistoryError::ItemDoesNotExist { id }) if id == item.id()
+        ));
+        assert!(matches!(
+            history(None, None).await,
+            Err(FindPriceHistoryError::ItemDoesNotExist { .. })
+        ));
+        assert!(matches!(
+            watch(stranger, None).await,
+            Err(WatchItemPriceError::ItemDoesNotExist { id }) if id == item.id()
+        ));
+
+        // ...or to whoever holds the token of its share link
+        let token = ShareToken::generate();
+        wish_repository
+            .set_share_token(wishlist.id(), Some(token.clone()))
+            .await
+            .unwrap();
+        assert!(history(None, Some(token.clone())).await.is_ok());
+        assert!(watch(stranger, Some(token)).await.is_ok());
+        assert!(matches!(
+            history(None, Some(ShareToken::generate())).await,
+            Err(FindPriceHistoryError::ItemDoesNotExist { .. })
+        ));
+
+        // ...or to the members of a group it is shared with
+        assert!(matches!(
+            watch(member, None).await,
+            Err(WatchItemPriceError::ItemDoesNotExist { .. })
+        ));
+        let group = group_repository
+            .save(&CreateGroupRequest::new(owner, "Family".into()))
+            .await
+            .unwrap();
+        let invitation = group_repository
+            .save_invitation(group.id(), member, owner, Utc::now())
+            .await
+            .unwrap();
+        group_repository
+            .answer_invitation(invitation.id(), member, true)
+            .await
+            .unwrap();
+        group_repository
+            .share_wishlist(
+                group.id(),
+                SharedWishlist::new(wishlist.id(), owner, Utc::now()),
+            )
+            .await
+            .unwrap();
+        assert!(history(Some(member), None).await.is_ok());
+        assert!(watch(member, None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_move_and_copy_items() {
+        let owner = Uuid::now_v7();
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let price_repository = Arc::new(InMemoryPriceHistoryRepository::new());
+        let service = Service::new(
+            wish_repository.clone(),
+            item_repository.clone(),
+            price_repository,
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(InMemoryGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+        let wishlist = |name: &'static str, owner: Uuid| {
+            let wish_repository = wish_repository.clone();
+            async move {
+                wish_repository
+                    .save(&CreateWishlistRequest::new(owner, name.into(), false))
+                    .await
+                    .unwrap()
+                    .id()
+            }
+        };
+        let ideas = wishlist("Ideas", owner).await;
+        let birthday = wishlist("Birthday", owner).await;
+        let someone_else = wishlist("Theirs", Uuid::now_v7()).await;
+        let req = CreateItemRequest::new(
+            ideas,
+            owner,
+            Some("Book".into()),
+            "https://shop.example/book".into(),
+            None,
+            Some(12.5.into()),
+        )
+        .with_image_id(Some(Uuid::now_v7()))
+        .with_priority(ItemPriority::MustHave)
+        .with_notes(Some(ItemNotes::from("Hardcover")));
+        let item = item_repository.save(&req).await.unwrap();
+        wish_repository.add_item(ideas, item.id()).await.unwrap();
+        let find_wishlist = |id: Uuid| {
+            let wish_repository = wish_repository.clone();
+            async move {
+                wish_repository
+                    .find_wishlist_by_id(&FindWishlistByIdRequest::new(id))
+                    .await
+                    .unwrap()
+                    .unwrap()
+            }
+        };
+
+        let copy = service
+            .copy_item(&TransferItemRequest::new(item.id(), birthday, owner))
+            .await
+            .unwrap();
+        assert_ne!(copy.id(), item.id());
+        assert_eq!(copy.wishlist_id(), birthday);
+        assert_eq!(copy.image_id(), item.image_id());
+        assert_eq!(copy.priority(), item.priority());
+        assert_eq!(copy.notes(), item.notes());
+        assert!(find_wishlist(ideas).await.contains_item(item.id()));
+        assert!(find_wishlist(birthday).await.contains_item(copy.id()));
+
+        let result = service
+            .move_item(&TransferItemRequest::new(item.id(), birthday, owner))
+            .await;
+        assert!(matches!(result, Err(TransferItemError::Duplicate)));
+        let result = service
+            .move_item(&TransferItemRequest::new(item.id(), someone_else, owner))
+            .await;
+        assert!(matches!(
+            result,
+            Err(TransferItemError::NotWishlistOwner { id }) if id == someone_else
+        ));
+
+        let moved = service
+            .move_item(&TransferItemRequest::new(copy.id(), ideas, owner))
+            .await;
+        assert!(matches!(moved, Err(TransferItemError::Duplicate)));
+        let other = wishlist("Christmas", owner).await;
+        let mut source_stream = service
+            .watch_items(&WatchItemsRequest::new(ideas, owner))
+            .await
+            .unwrap();
+        let moved = service
+            .move_item(&TransferItemRequest::new(item.id(), other, owner))
+            .await
+            .unwrap();
+        assert_eq!(moved.id(), item.id());
+        assert_eq!(moved.wishlist_id(), other);
+        assert!(!find_wishlist(ideas).await.contains_item(item.id()));
+        assert!(find_wishlist(other).await.contains_item(item.id()));
+        let removed = source_stream.next().await.unwrap();
+        assert_eq!(removed.wishlist_id(), ideas);
+        assert_eq!(
+            removed.change(),
+            &ItemChange::Removed { item_id: item.id() }
+        );
+
+        wish_repository.set_archived(birthday, true).await.unwrap();
+        let result = service
+            .move_item(&TransferItemRequest::new(item.id(), birthday, owner))
+            .await;
+        assert!(matches!(
+            result,
+            Err(TransferItemError::WishlistArchived { id }) if id == birthday
+        ));
+        let result = service
+            .copy_item(&TransferItemRequest::new(item.id(), birthday, owner))
+            .await;
+        assert!(matches!(
+            result,
+            Err(TransferItemError::WishlistArchived { id }) if id == birthday
+        ));
+        assert!(find_wishlist(other).await.contains_item(item.id()));
+    }
+
+    #[tokio::test]
+    async fn test_move_item_back_when_target_fails() {
+        let (owner, source, target) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let item = Item::create(
+            Uuid::now_v7(),
+            source,
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        let mut wish_mock_repo = MockWishlistRepository::new();
+        wish_mock_repo
+            .expect_find_wishlist_by_id()
+            .returning(move |req| {
+                let wishlist = Wishlist::new(*req.id(), owner, "".into(), "".into(), false);
+                Box::pin(future::ready(Ok(Some(wishlist))))
+            });
+        wish_mock_repo
+            .expect_add_item()
+            .times(1)
+            .returning(|_, _| Box::pin(future::ready(Err(anyhow!("unavailable").into()))));
+        wish_mock_repo.expect_remove_item().never();
+        let mut item_mock_repo = MockItemRepository::new();
+        let found = item.clone();
+        item_mock_repo
+            .expect_find_item_by_id()
+            .returning(move |_| Box::pin(future::ready(Ok(Some(found.clone())))));
+        let moved = item.clone();
+        item_mock_repo
+            .expect_move_item()
+            .withf(move |_, wishlist_id| *wishlist_id == target)
+            .times(1)
+            .returning(move |_, wishlist_id| {
+                let mut moved = moved.clone();
+                moved.set_wishlist_id(wishlist_id);
+                Box::pin(future::ready(Ok(moved)))
+            });
+        let back = item.clone();
+        item_mock_repo
+            .expect_move_item()
+            .withf(move |_, wishlist_id| *wishlist_id == source)
+            .times(1)
+            .returning(move |_, _| Box::pin(future::ready(Ok(back.clone()))));
+        let service = Service::new(
+            Arc::new(wish_mock_repo),
+            Arc::new(item_mock_repo),
+            Arc::new(MockPriceHistoryRepository::new()),
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+
+        let result = service
+            .move_item(&TransferItemRequest::new(item.id(), target, owner))
+            .await;
+        assert!(matches!(result, Err(TransferItemError::Unkown(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reserve_items_of_visible_wishlists() {
+        let (owner, giver) = (Uuid::now_v7(), Uuid::now_v7());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let service = Service::new(
+            wish_repository.clone(),
+            item_repository.clone(),
+            Arc::new(InMemoryPriceHistoryRepository::new()),
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(InMemoryGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+        let (mut wishlists, mut items) = (Vec::new(), Vec::new());
+        for private in [false, true] {
+            let wishlist = wish_repository
+                .save(&CreateWishlistRequest::new(owner, "Gifts".into(), private))
+                .await
+                .unwrap();
+            let req = CreateItemRequest::new(
+                wishlist.id(),
+                wishlist.owner_id(),
+                Some("Book".into()),
+                "https://shop.example/book".into(),
+                None,
+                None,
+            );
+            items.push(item_repository.save(&req).await.unwrap().id());
+            wishlists.push(wishlist.id());
+        }
+        let (public_item, private_item) = (items[0], items[1]);
+
+        let result = service
+            .reserve_item(&ReserveItemRequest::new(public_item, owner, true))
+            .await;
+        assert!(matches!(result, Err(ReserveItemError::OwnItem)));
+        let result = service
+            .reserve_item(&ReserveItemRequest::new(private_item, giver, true))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ReserveItemError::ItemDoesNotExist { id }) if id == private_item
+        ));
+        let reserved = service
+            .reserve_item(&ReserveItemRequest::new(public_item, giver, true))
+            .await
+            .unwrap();
+        assert_eq!(reserved.reserved_by(), Some(giver));
+        let cancelled = service
+            .reserve_item(&ReserveItemRequest::new(public_item, giver, false))
+            .await
+            .unwrap();
+        assert_eq!(cancelled.reserved_by(), None);
+
+        wish_repository
+            .set_archived(wishlists[0], true)
+            .await
+            .unwrap();
+        let result = service
+            .reserve_item(&ReserveItemRequest::new(public_item, giver, true))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ReserveItemError::WishlistArchived { id }) if id == wishlists[0]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_view_visible_wishlists() {
+        let (owner, giver) = (Uuid::now_v7(), Uuid::now_v7());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let service = Service::new(
+            wish_repository.clone(),
+            item_repository.clone(),
+            Arc::new(InMemoryPriceHistoryRepository::new()),
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(InMemoryGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+        let mut wishlists = Vec::new();
+        for private in [false, true] {
+            let wishlist = wish_repository
+                .save(&CreateWishlistRequest::new(owner, "Gifts".into(), private))
+                .await
+                .unwrap();
+            for title in ["Book", "Pen"] {
+                let req = CreateItemRequest::new(
+                    wishlist.id(),
+                    wishlist.owner_id(),
+                    Some(title.into()),
+                    format!("https://shop.example/{}", title).as_str().into(),
+                    None,
+                    None,
+                );
+                let item = item_repository.save(&req).await.unwrap();
+                wish_repository
+                    .add_item(wishlist.id(), item.id())
+                    .await
+                    .unwrap();
+            }
+            wishlists.push(wishlist.id());
+        }
+        let (public, private) = (wishlists[0], wishlists[1]);
+        let list_req = ListItemsRequest::new(
+            private,
+            Default::default(),
+            ItemSort::default(),
+            Default::default(),
+        );
+        assert!(matches!(
+            service.list_items(&list_req).await,
+            Err(ListItemsError::WishlistDoesNotExist { .. })
+        ));
+        assert!(matches!(
+            service
+                .list_items(&list_req.clone().with_viewer(Some(giver)))
+                .await,
+            Err(ListItemsError::WishlistDoesNotExist { .. })
+        ));
+        let listing = service
+            .list_items(&list_req.clone().with_viewer(Some(owner)))
+            .await
+            .unwrap();
+        assert_eq!(listing.items().len(), 2);
+        let listing = service
+            .list_items(&ListItemsRequest::new(
+                public,
+                Default::default(),
+                ItemSort::default(),
+                Default::default(),
+            ))
+            .await
+            .unwrap();
+        service
+            .reserve_item(&ReserveItemRequest::new(
+                listing.items()[0].id(),
+                giver,
+                true,
+            ))
+            .await
+            .unwrap();
+        let req = |user_id| ViewWishlistsRequest::new(user_id, vec![public, private, Uuid::nil()]);
+
+        let seen = service.view_wishlists(&req(giver)).await.unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].wishlist().id(), public);
+        assert_eq!(seen[0].items().len(), 2);
+        assert_eq!(seen[0].reservation_count(), Some(1));
+
+        let seen = service.view_wishlists(&req(owner)).await.unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].reservation_count(), None);
+        let titles: Vec<String> = seen[1]
+            .items()
+            .iter()
+            .map(|item| item.title().to_string())
+            .collect();
+        assert_eq!(titles, ["Book", "Pen"]);
+
+        let shared = wish_repository
+            .set_share_token(private, Some(ShareToken::generate()))
+            .await
+            .unwrap();
+        let guest = Uuid::now_v7();
+        let view_req = ViewSharedWishlistRequest::new(shared.slug().to_string(), guest);
+        assert!(matches!(
+            service.view_shared_wishlist(&view_req).await,
+            Err(ViewSharedWishlistError::WishlistDoesNotExist { .. })
+        ));
+        let reserve_req = ReserveItemRequest::new(seen[1].items()[0].id(), guest, true);
+        assert!(matches!(
+            service.reserve_item(&reserve_req).await,
+            Err(ReserveItemError::ItemDoesNotExist { .. })
+        ));
+        service
+            .reserve_item(&reserve_req.with_share_token(shared.share_token().cloned()))
+            .await
+            .unwrap();
+        let view = service
+            .view_shared_wishlist(&view_req.with_share_token(shared.share_token().cloned()))
+            .await
+            .unwrap();
+        let listing = service
+            .list_items(&list_req.with_share_token(shared.share_token().cloned()))
+            .await
+            .unwrap();
+        assert_eq!(listing.items().len(), 2);
+        assert_eq!(view.reservation_count(), Some(1));
+        assert_eq!(view.items()[0].reserved_by(), Some(guest));
+    }
+
+    #[tokio::test]
+    async fn test_mark_item_received_at_expected_version() {
+        let (owner, giver) = (Uuid::now_v7(), Uuid::now_v7());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let service = Service::new(
+            wish_repository.clone(),
+            item_repository.clone(),
+            Arc::new(InMemoryPriceHistoryRepository::new()),
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(InMemoryGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+        let wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(owner, "Gifts".into(), false))
+            .await
+            .unwrap();
+        let item = item_repository
+            .save(&CreateItemRequest::new(
+                wishlist.id(),
+                wishlist.owner_id(),
+                Some("Book".into()),
+                "https://shop.example/book".into(),
+                None,
+                None,
+            ))
+            .await
+            .unwrap();
+        service
+            .reserve_item(&ReserveItemRequest::new(item.id(), giver, true))
+            .await
+            .unwrap();
+
+        let req = MarkItemReceivedRequest::new(item.id(), owner, true)
+            .with_expected_version(Some(item.version()));
+        let received = service.mark_item_received(&req).await.unwrap();
+        assert!(received.received());
+        assert_eq!(received.version(), item.version() + 1);
+        assert_eq!(received.reserved_by(), Some(giver));
+
+        let result = service.mark_item_received(&req).await;
+        assert!(matches!(
+            result,
+            Err(MarkItemReceivedError::VersionMismatch(VersionMismatchError { expected, actual, .. }))
+                if expected == item.version() && actual == received.version()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_watch_items_of_visible_wishlists() {
+        let (owner, giver) = (Uuid::now_v7(), Uuid::now_v7());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let service = Service::new(
+            wish_repository.clone(),
+            item_repository.clone(),
+            Arc::new(InMemoryPriceHistoryRepository::new()),
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            Arc::new(InMemoryGroupRepository::new()),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+        let wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(owner, "Gifts".into(), false))
+            .await
+            .unwrap();
+        let private = wish_repository
+            .save(&CreateWishlistRequest::new(owner, "Secret".into(), true))
+            .await
+            .unwrap();
+        let result = service
+            .watch_items(&WatchItemsRequest::new(private.id(), giver))
+            .await;
+        assert!(matches!(
+            result,
+            Err(WatchItemsError::WishlistDoesNotExist { id }) if id == private.id()
+        ));
+
+        let mut owner_stream = service
+            .watch_items(&WatchItemsRequest::new(wishlist.id(), owner))
+            .await
+            .unwrap();
+        let mut giver_stream = service
+            .watch_items(&WatchItemsRequest::new(wishlist.id(), giver))
+            .await
+            .unwrap();
+        let item = service
+            .create_item(&CreateItemRequest::new(
+                wishlist.id(),
+                wishlist.owner_id(),
+                Some("Book".into()),
+                "https://shop.example/book".into(),
+                Some("https://shop.example/book.jpg".into()),
+                Some(12.5.into()),
+            ))
+            .await
+            .unwrap();
+        service
+            .reserve_item(&ReserveItemRequest::new(item.id(), giver, true))
+            .await
+            .unwrap();
+        service
+            .mark_item_received(&MarkItemReceivedRequest::new(item.id(), owner, true))
+            .await
+            .unwrap();
+
+        let added = giver_stream.next().await.unwrap();
+        assert!(matches!(added.change(), ItemChange::Added(added) if added.id() == item.id()));
+        let reserved = giver_stream.next().await.unwrap();
+        assert_eq!(
+            reserved.change(),
+            &ItemChange::Reserved {
+                item_id: item.id(),
+                reserved: true
+            }
+        );
+        let edited = giver_stream.next().await.unwrap();
+        assert!(matches!(edited.change(), ItemChange::Edited(edited) if edited.received()));
+
+        // The owner never hears of the reservation
+        assert_eq!(owner_stream.next().await.unwrap().id(), added.id());
+        let ItemChange::Edited(seen) = owner_stream.next().await.unwrap().change().clone() else {
+            panic!("expected the owner to see the item received");
+        };
+        assert_eq!(seen.reserved_by(), None);
+
+        // A viewer coming back resumes after the last event they received
+        let mut resumed = service
+            .watch_items(
+                &WatchItemsRequest::new(wishlist.id(), giver).with_last_event_id(Some(added.id())),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resumed.next().await, Some(reserved));
+        assert_eq!(resumed.next().await, Some(edited));
+    }
+
+    #[tokio::test]
+    async fn test_watch_items_ends_when_access_is_revoked() {
+        let (owner, giver) = (Uuid::now_v7(), Uuid::now_v7());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let group_repository = Arc::new(InMemoryGroupRepository::new());
+        let service = Service::new(
+            wish_repository.clone(),
+            Arc::new(InMemoryItemRepository::new()),
+            Arc::new(InMemoryPriceHistoryRepository::new()),
+            Arc::new(MockItemPageFetcher::new()),
+            ExtractorRegistry::new(LinesExtractor),
+            Arc::new(MockNotifier::new()),
+            Arc::new(MockImageService::new()),
+            group_repository.clone(),
+            Arc::new(InMemoryItemEventBus::new()),
+            Arc::new(SystemClock),
+        );
+        let wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(owner, "Secret".into(), true))
+            .await
+            .unwrap();
+        let group = group_repository
+            .save(&CreateGroupRequest::new(owner, "Family".into()))
+            .await
+            .unwrap();
+        let invitation = group_repository
+            .save_invitation(group.id(), giver, owner, Utc::now())
+            .await
+            .unwrap();
+        group_repository
+            .answer_invitation(invitation.id(), giver, true)
+            .await
+            .unwrap();
+        group_repository
+            .share_wishlist(
+                group.id(),
+                SharedWishlist::new(wishlist.id(), owner, Utc::now()),
+            )
+            .await
+            .unwrap();
+        let wishlist_id = wishlist.id();
+        let add_item = |link: &'static str| {
+            let service = service.clone();
+            async move {
+                service
+                    .create_item(&CreateItemRequest::new(
+                        wishlist_id,
+                        owner,
+                        Some("Book".into()),
+                        link.into(),
+                        Some("https://shop.example/book.jpg".into()),
+                        Some(12.5.into()),
+                    ))
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let mut owner_stream = service
+            .watch_items(&WatchItemsRequest::new(wishlist.id(), owner))
+            .await
+            .unwrap();
+        let mut giver_stream = service
+            .watch_items(&WatchItemsRequest::new(wishlist.id(), giver))
+            .await
+            .unwrap();
+        let first = add_item("https://shop.example/book").await;
+        assert!(matches!(
+            giver_stream.next().await.unwrap().change(),
+            ItemChange::Added(added) if added.id() == first.id()
+        ));
+
+        group_repository
+            .remove_member(group.id(), giver)
+            .await
+            .unwrap();
+        let second = add_item("https://shop.example/pen").await;
+        assert_eq!(giver_stream.next().await, None);
+        owner_stream.next().await.unwrap();
+        assert!(matches!(
+            owner_stream.next().await.unwrap().change(),
+            ItemChange::Added(added) if added.id() == second.id()
+        ));
+    }
+}
@@ -1,11 +1,53 @@
 use std::{future::Future, sync::Arc};
 
 use crate::domain::{
-    CreateUserError, CreateUserRequest, CreateWishlistError, CreateWishlistRequest, User,
-    UserService, Wishlist, WishlistService,
+    AccountConfirmation, AccountConfirmationRequest, AccountDeletion, AccountExport,
+    AccountService, AddExclusionError, AddExclusionRequest, AddParticipantError,
+    AddParticipantRequest, AnswerFollowRequest, AnswerFollowRequestError, AuditDrawError, Blob,
+    Block, BlockUserError, BlockUserRequest, CancelAccountDeletionError, ConfirmedAccountRequest,
+    CreateExchangeError, CreateExchangeRequest, CreateGroupError, CreateGroupRequest,
+    CreateItemError, CreateItemRequest, CreateSectionError, CreateSectionRequest, CreateUserError,
+    CreateUserRequest, CreateWishlistError, CreateWishlistRequest, DeleteAccountError,
+    DeleteSectionError, DeleteSectionRequest, DeleteWebhookError, DrawAudit, DrawExchangeError,
+    DuplicateWishlistError, DuplicateWishlistRequest, Exchange, ExchangeActionRequest,
+    ExchangeService, ExportAccountError, FindImageError, FindImageRequest, FindPriceHistoryError,
+    FindPriceHistoryRequest, FindProfileError, FindProfileRequest, FindProfilesError,
+    FindProfilesRequest, FindWishlistsError, Follow, FollowFeedError, FollowFeedRequest,
+    FollowService, FollowSettings, FollowUserError, FollowUserRequest, Group, GroupFeedEntry,
+    GroupFeedError, GroupFeedRequest, GroupInvitation, GroupService, ImageService, InboxMessage,
+    InviteMemberError, InviteMemberRequest, Item, ItemEventStream, ItemListing, ItemService,
+    ListFollowRequestsError, ListFollowRequestsRequest, ListInboxError, ListInboxRequest,
+    ListInvitationsError, ListInvitationsRequest, ListItemsError, ListItemsRequest,
+    ListWebhookDeliveriesError, ListWebhookDeliveriesRequest, ListWebhooksError,
+    ListWebhooksRequest, ManageMemberError, ManageMemberRequest, MarkItemReceivedError,
+    MarkItemReceivedRequest, MoveItemToSectionError, MoveItemToSectionRequest, NotificationService,
+    Occasion, Page, PricePoint, PriceWatch, Profile, ProfileService, RegisterWebhookError,
+    RegisterWebhookRequest, ReminderPreferences, ReminderSubscription, ReorderWishlistError,
+    ReorderWishlistRequest, RequestAccountConfirmationError, ReserveItemError, ReserveItemRequest,
+    RespondToInvitationError, RespondToInvitationRequest, Reveal, RevealRecipientError,
+    SetFollowSettingsError, SetMemberRoleRequest, SetProfileError, SetReminderPreferencesError,
+    SetWishlistOccasionError, SetWishlistOccasionRequest, SetWishlistTemplateError,
+    SetWishlistTemplateRequest, ShareLinkError, ShareLinkRequest, ShareWishlistError,
+    ShareWishlistRequest, StoreImageError, StoredImage, SubscribeReminderError,
+    SubscribeReminderRequest, SuggestOccasionsError, SuggestOccasionsRequest, TransferItemError,
+    TransferItemRequest, UnblockUserError, UnfollowUserError, UnsubscribeReminderError,
+    UnsubscribeReminderRequest, UploadImageRequest, User, UserService, ViewSharedWishlistError,
+    ViewSharedWishlistRequest, ViewWishlistsError, ViewWishlistsRequest, WatchItemPriceError,
+    WatchItemPriceRequest, WatchItemsError, WatchItemsRequest, Webhook, WebhookDelivery,
+    WebhookRequest, WebhookService, Wishlist, WishlistQuery, WishlistSection, WishlistService,
+    WishlistView,
 };
 
+pub mod account;
+pub mod exchange;
+pub mod follow;
+pub mod group;
+pub mod image;
+pub mod item;
+pub mod notification;
+pub mod profile;
 pub mod user;
+pub mod webhook;
 pub mod wishlist;
 
 pub trait UseCases: Clone + Send + Sync + 'static {
@@ -17,47 +59,371 @@ pub trait UseCases: Clone + Send + Sync + 'static {
         &self,
         req: &CreateWishlistRequest,
     ) -> impl Future<Output = Result<Wishlist, CreateWishlistError>> + Send;
+    fn create_section(
+        &self,
+        req: &CreateSectionRequest,
+    ) -> impl Future<Output = Result<WishlistSection, CreateSectionError>> + Send;
+    fn delete_section(
+        &self,
+        req: &DeleteSectionRequest,
+    ) -> impl Future<Output = Result<Wishlist, DeleteSectionError>> + Send;
+    fn move_item_to_section(
+        &self,
+        req: &MoveItemToSectionRequest,
+    ) -> impl Future<Output = Result<Wishlist, MoveItemToSectionError>> + Send;
+    fn reorder_wishlist(
+        &self,
+        req: &ReorderWishlistRequest,
+    ) -> impl Future<Output = Result<Wishlist, ReorderWishlistError>> + Send;
+    fn duplicate_wishlist(
+        &self,
+        req: &DuplicateWishlistRequest,
+    ) -> impl Future<Output = Result<Wishlist, DuplicateWishlistError>> + Send;
+    fn set_wishlist_template(
+        &self,
+        req: &SetWishlistTemplateRequest,
+    ) -> impl Future<Output = Result<Wishlist, SetWishlistTemplateError>> + Send;
+    fn create_share_link(
+        &self,
+        req: &ShareLinkRequest,
+    ) -> impl Future<Output = Result<Wishlist, ShareLinkError>> + Send;
+    fn revoke_share_link(
+        &self,
+        req: &ShareLinkRequest,
+    ) -> impl Future<Output = Result<Wishlist, ShareLinkError>> + Send;
+    fn list_templates(
+        &self,
+        query: &WishlistQuery,
+    ) -> impl Future<Output = Result<Page<Wishlist>, FindWishlistsError>> + Send;
+    fn set_wishlist_occasion(
+        &self,
+        req: &SetWishlistOccasionRequest,
+    ) -> impl Future<Output = Result<Wishlist, SetWishlistOccasionError>> + Send;
+    fn create_item(
+        &self,
+        req: &CreateItemRequest,
+    ) -> impl Future<Output = Result<Item, CreateItemError>> + Send;
+    fn list_items(
+        &self,
+        req: &ListItemsRequest,
+    ) -> impl Future<Output = Result<ItemListing, ListItemsError>> + Send;
+    fn view_wishlists(
+        &self,
+        req: &ViewWishlistsRequest,
+    ) -> impl Future<Output = Result<Vec<WishlistView>, ViewWishlistsError>> + Send;
+    fn view_shared_wishlist(
+        &self,
+        req: &ViewSharedWishlistRequest,
+    ) -> impl Future<Output = Result<WishlistView, ViewSharedWishlistError>> + Send;
+    fn move_item(
+        &self,
+        req: &TransferItemRequest,
+    ) -> impl Future<Output = Result<Item, TransferItemError>> + Send;
+    fn copy_item(
+        &self,
+        req: &TransferItemRequest,
+    ) -> impl Future<Output = Result<Item, TransferItemError>> + Send;
+    fn mark_item_received(
+        &self,
+        req: &MarkItemReceivedRequest,
+    ) -> impl Future<Output = Result<Item, MarkItemReceivedError>> + Send;
+    fn reserve_item(
+        &self,
+        req: &ReserveItemRequest,
+    ) -> impl Future<Output = Result<Item, ReserveItemError>> + Send;
+    fn watch_items(
+        &self,
+        req: &WatchItemsRequest,
+    ) -> impl Future<Output = Result<ItemEventStream, WatchItemsError>> + Send;
+    fn find_price_history(
+        &self,
+        req: &FindPriceHistoryRequest,
+    ) -> impl Future<Output = Result<Vec<PricePoint>, FindPriceHistoryError>> + Send;
+    fn watch_item_price(
+        &self,
+        req: &WatchItemPriceRequest,
+    ) -> impl Future<Output = Result<PriceWatch, WatchItemPriceError>> + Send;
+    fn upload_image(
+        &self,
+        req: &UploadImageRequest,
+    ) -> impl Future<Output = Result<StoredImage, StoreImageError>> + Send;
+    fn find_image(
+        &self,
+        req: &FindImageRequest,
+    ) -> impl Future<Output = Result<Option<Blob>, FindImageError>> + Send;
+    fn subscribe_reminder(
+        &self,
+        req: &SubscribeReminderRequest,
+    ) -> impl Future<Output = Result<ReminderSubscription, SubscribeReminderError>> + Send;
+    fn unsubscribe_reminder(
+        &self,
+        req: &UnsubscribeReminderRequest,
+    ) -> impl Future<Output = Result<ReminderSubscription, UnsubscribeReminderError>> + Send;
+    fn set_reminder_preferences(
+        &self,
+        preferences: &ReminderPreferences,
+    ) -> impl Future<Output = Result<ReminderPreferences, SetReminderPreferencesError>> + Send;
+    fn list_inbox(
+        &self,
+        req: &ListInboxRequest,
+    ) -> impl Future<Output = Result<Page<InboxMessage>, ListInboxError>> + Send;
+    fn create_exchange(
+        &self,
+        req: &CreateExchangeRequest,
+    ) -> impl Future<Output = Result<Exchange, CreateExchangeError>> + Send;
+    fn add_participant(
+        &self,
+        req: &AddParticipantRequest,
+    ) -> impl Future<Output = Result<Exchange, AddParticipantError>> + Send;
+    fn add_exclusion(
+        &self,
+        req: &AddExclusionRequest,
+    ) -> impl Future<Output = Result<Exchange, AddExclusionError>> + Send;
+    fn draw_exchange(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> impl Future<Output = Result<Exchange, DrawExchangeError>> + Send;
+    fn reveal_recipient(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> impl Future<Output = Result<Reveal, RevealRecipientError>> + Send;
+    fn audit_draw(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> impl Future<Output = Result<DrawAudit, AuditDrawError>> + Send;
+    fn create_group(
+        &self,
+        req: &CreateGroupRequest,
+    ) -> impl Future<Output = Result<Group, CreateGroupError>> + Send;
+    fn invite_member(
+        &self,
+        req: &InviteMemberRequest,
+    ) -> impl Future<Output = Result<GroupInvitation, InviteMemberError>> + Send;
+    fn list_invitations(
+        &self,
+        req: &ListInvitationsRequest,
+    ) -> impl Future<Output = Result<Page<GroupInvitation>, ListInvitationsError>> + Send;
+    fn respond_to_invitation(
+        &self,
+        req: &RespondToInvitationRequest,
+    ) -> impl Future<Output = Result<GroupInvitation, RespondToInvitationError>> + Send;
+    fn remove_member(
+        &self,
+        req: &ManageMemberRequest,
+    ) -> impl Future<Output = Result<Group, ManageMemberError>> + Send;
+    fn set_member_role(
+        &self,
+        req: &SetMemberRoleRequest,
+    ) -> impl Future<Output = Result<Group, ManageMemberError>> + Send;
+    fn share_wishlist(
+        &self,
+        req: &ShareWishlistRequest,
+    ) -> impl Future<Output = Result<Group, ShareWishlistError>> + Send;
+    fn unshare_wishlist(
+        &self,
+        req: &ShareWishlistRequest,
+    ) -> impl Future<Output = Result<Group, ShareWishlistError>> + Send;
+    fn group_feed(
+        &self,
+        req: &GroupFeedRequest,
+    ) -> impl Future<Output = Result<Page<GroupFeedEntry>, GroupFeedError>> + Send;
+    fn follow_user(
+        &self,
+        req: &FollowUserRequest,
+    ) -> impl Future<Output = Result<Follow, FollowUserError>> + Send;
+    fn unfollow_user(
+        &self,
+        req: &FollowUserRequest,
+    ) -> impl Future<Output = Result<Follow, UnfollowUserError>> + Send;
+    fn list_follow_requests(
+        &self,
+        req: &ListFollowRequestsRequest,
+    ) -> impl Future<Output = Result<Page<Follow>, ListFollowRequestsError>> + Send;
+    fn answer_follow_request(
+        &self,
+        req: &AnswerFollowRequest,
+    ) -> impl Future<Output = Result<Follow, AnswerFollowRequestError>> + Send;
+    fn block_user(
+        &self,
+        req: &BlockUserRequest,
+    ) -> impl Future<Output = Result<Block, BlockUserError>> + Send;
+    fn unblock_user(
+        &self,
+        req: &BlockUserRequest,
+    ) -> impl Future<Output = Result<Block, UnblockUserError>> + Send;
+    fn set_follow_settings(
+        &self,
+        settings: &FollowSettings,
+    ) -> impl Future<Output = Result<FollowSettings, SetFollowSettingsError>> + Send;
+    fn follow_feed(
+        &self,
+        req: &FollowFeedRequest,
+    ) -> impl Future<Output = Result<Page<Wishlist>, FollowFeedError>> + Send;
+    fn set_profile(
+        &self,
+        profile: &Profile,
+    ) -> impl Future<Output = Result<Profile, SetProfileError>> + Send;
+    fn find_profile(
+        &self,
+        req: &FindProfileRequest,
+    ) -> impl Future<Output = Result<Profile, FindProfileError>> + Send;
+    fn find_profiles(
+        &self,
+        req: &FindProfilesRequest,
+    ) -> impl Future<Output = Result<Vec<Profile>, FindProfilesError>> + Send;
+    fn suggest_occasions(
+        &self,
+        req: &SuggestOccasionsRequest,
+    ) -> impl Future<Output = Result<Vec<Occasion>, SuggestOccasionsError>> + Send;
+    fn request_account_confirmation(
+        &self,
+        req: &AccountConfirmationRequest,
+    ) -> impl Future<Output = Result<AccountConfirmation, RequestAccountConfirmationError>> + Send;
+    fn export_account(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> impl Future<Output = Result<AccountExport, ExportAccountError>> + Send;
+    fn delete_account(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> impl Future<Output = Result<AccountDeletion, DeleteAccountError>> + Send;
+    fn cancel_account_deletion(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> impl Future<Output = Result<AccountDeletion, CancelAccountDeletionError>> + Send;
+    fn register_webhook(
+        &self,
+        req: &RegisterWebhookRequest,
+    ) -> impl Future<Output = Result<Webhook, RegisterWebhookError>> + Send;
+    fn list_webhooks(
+        &self,
+        req: &ListWebhooksRequest,
+    ) -> impl Future<Output = Result<Page<Webhook>, ListWebhooksError>> + Send;
+    fn delete_webhook(
+        &self,
+        req: &WebhookRequest,
+    ) -> impl Future<Output = Result<Webhook, DeleteWebhookError>> + Send;
+    fn list_webhook_deliveries(
+        &self,
+        req: &ListWebhookDeliveriesRequest,
+    ) -> impl Future<Output = Result<Page<WebhookDelivery>, ListWebhookDeliveriesError>> + Send;
 }
 
-pub struct Service<U, W>
+pub struct Service<U, W, I, G, N, E, P, F, R, A, K>
 where
     U: UserService,
     W: WishlistService,
+    I: ItemService,
+    G: ImageService,
+    N: NotificationService,
+    E: ExchangeService,
+    P: GroupService,
+    F: FollowService,
+    R: ProfileService,
+    A: AccountService,
+    K: WebhookService,
 {
     user_service: Arc<U>,
     wish_service: Arc<W>,
+    item_service: Arc<I>,
+    image_service: Arc<G>,
+    notification_service: Arc<N>,
+    exchange_service: Arc<E>,
+    group_service: Arc<P>,
+    follow_service: Arc<F>,
+    profile_service: Arc<R>,
+    account_service: Arc<A>,
+    webhook_service: Arc<K>,
 }
 
-impl<U, W> Service<U, W>
+impl<U, W, I, G, N, E, P, F, R, A, K> Service<U, W, I, G, N, E, P, F, R, A, K>
 where
     U: UserService,
     W: WishlistService,
+    I: ItemService,
+    G: ImageService,
+    N: NotificationService,
+    E: ExchangeService,
+    P: GroupService,
+    F: FollowService,
+    R: ProfileService,
+    A: AccountService,
+    K: WebhookService,
 {
-    pub fn new(user_service: U, wish_service: W) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_service: U,
+        wish_service: W,
+        item_service: I,
+        image_service: G,
+        notification_service: N,
+        exchange_service: E,
+        group_service: P,
+        follow_service: F,
+        profile_service: R,
+        account_service: A,
+        webhook_service: K,
+    ) -> Self {
         Self {
             user_service: Arc::new(user_service),
             wish_service: Arc::new(wish_service),
+            item_service: Arc::new(item_service),
+            image_service: Arc::new(image_service),
+            notification_service: Arc::new(notification_service),
+            exchange_service: Arc::new(exchange_service),
+            group_service: Arc::new(group_service),
+            follow_service: Arc::new(follow_service),
+            profile_service: Arc::new(profile_service),
+            account_service: Arc::new(account_service),
+            webhook_service: Arc::new(webhook_service),
         }
     }
 }
 
-impl<U, W> Clone for Service<U, W>
+impl<U, W, I, G, N, E, P, F, R, A, K> Clone for Service<U, W, I, G, N, E, P, F, R, A, K>
 where
     U: UserService,
     W: WishlistService,
+    I: ItemService,
+    G: ImageService,
+    N: NotificationService,
+    E: ExchangeService,
+    P: GroupService,
+    F: FollowService,
+    R: ProfileService,
+    A: AccountService,
+    K: WebhookService,
 {
     fn clone(&self) -> Self {
         Self {
             user_service: self.user_service.clone(),
             wish_service: self.wish_service.clone(),
+            item_service: self.item_service.clone(),
+            image_service: self.image_service.clone(),
+            notification_service: self.notification_service.clone(),
+            exchange_service: self.exchange_service.clone(),
+            group_service: self.group_service.clone(),
+            follow_service: self.follow_service.clone(),
+            profile_service: self.profile_service.clone(),
+            account_service: self.account_service.clone(),
+            webhook_service: self.webhook_service.clone(),
         }
     }
 }
 
-impl<U, W> UseCases for Service<U, W>
+impl<U, W, I, G, N, E, P, F, R, A, K> UseCases for Service<U, W, I, G, N, E, P, F, R, A, K>
 where
     U: UserService,
     W: WishlistService,
+    I: ItemService,
+    G: ImageService,
+    N: NotificationService,
+    E: ExchangeService,
+    P: GroupService,
+    F: FollowService,
+    R: ProfileService,
+    A: AccountService,
+    K: WebhookService,
 {
     async fn create_user(&self, req: &CreateUserRequest) -> Result<User, CreateUserError> {
         let result = self.user_service.create_user(req).await;
@@ -71,4 +437,381 @@ where
         let result = self.wish_service.create_wishlist(req).await;
         result
     }
+
+    async fn create_section(
+        &self,
+        req: &CreateSectionRequest,
+    ) -> Result<WishlistSection, CreateSectionError> {
+        self.wish_service.create_section(req).await
+    }
+
+    async fn delete_section(
+        &self,
+        req: &DeleteSectionRequest,
+    ) -> Result<Wishlist, DeleteSectionError> {
+        self.wish_service.delete_section(req).await
+    }
+
+    async fn move_item_to_section(
+        &self,
+        req: &MoveItemToSectionRequest,
+    ) -> Result<Wishlist, MoveItemToSectionError> {
+        self.wish_service.move_item_to_section(req).await
+    }
+
+    async fn reorder_wishlist(
+        &self,
+        req: &ReorderWishlistRequest,
+    ) -> Result<Wishlist, ReorderWishlistError> {
+        self.wish_service.reorder_wishlist(req).await
+    }
+
+    async fn duplicate_wishlist(
+        &self,
+        req: &DuplicateWishlistRequest,
+    ) -> Result<Wishlist, DuplicateWishlistError> {
+        self.wish_service.duplicate_wishlist(req).await
+    }
+
+    async fn set_wishlist_template(
+        &self,
+        req: &SetWishlistTemplateRequest,
+    ) -> Result<Wishlist, SetWishlistTemplateError> {
+        self.wish_service.set_wishlist_template(req).await
+    }
+
+    async fn create_share_link(&self, req: &ShareLinkRequest) -> Result<Wishlist, ShareLinkError> {
+        self.wish_service.create_share_link(req).await
+    }
+
+    async fn revoke_share_link(&self, req: &ShareLinkRequest) -> Result<Wishlist, ShareLinkError> {
+        self.wish_service.revoke_share_link(req).await
+    }
+
+    async fn list_templates(
+        &self,
+        query: &WishlistQuery,
+    ) -> Result<Page<Wishlist>, FindWishlistsError> {
+        self.wish_service.list_templates(query).await
+    }
+
+    async fn set_wishlist_occasion(
+        &self,
+        req: &SetWishlistOccasionRequest,
+    ) -> Result<Wishlist, SetWishlistOccasionError> {
+        self.wish_service.set_wishlist_occasion(req).await
+    }
+
+    async fn create_item(&self, req: &CreateItemRequest) -> Result<Item, CreateItemError> {
+        self.item_service.create_item(req).await
+    }
+
+    async fn list_items(&self, req: &ListItemsRequest) -> Result<ItemListing, ListItemsError> {
+        self.item_service.list_items(req).await
+    }
+
+    async fn view_wishlists(
+        &self,
+        req: &ViewWishlistsRequest,
+    ) -> Result<Vec<WishlistView>, ViewWishlistsError> {
+        self.item_service.view_wishlists(req).await
+    }
+
+    async fn view_shared_wishlist(
+        &self,
+        req: &ViewSharedWishlistRequest,
+    ) -> Result<WishlistView, ViewSharedWishlistError> {
+        self.item_service.view_shared_wishlist(req).await
+    }
+
+    async fn move_item(&self, req: &TransferItemRequest) -> Result<Item, TransferItemError> {
+        self.item_service.move_item(req).await
+    }
+
+    async fn copy_item(&self, req: &TransferItemRequest) -> Result<Item, TransferItemError> {
+        self.item_service.copy_item(req).await
+    }
+
+    async fn mark_item_received(
+        &self,
+        req: &MarkItemReceivedRequest,
+    ) -> Result<Item, MarkItemReceivedError> {
+        self.item_service.mark_item_received(req).await
+    }
+
+    async fn reserve_item(&self, req: &ReserveItemRequest) -> Result<Item, ReserveItemError> {
+        self.item_service.reserve_item(req).await
+    }
+
+    async fn watch_items(
+        &self,
+        req: &WatchItemsRequest,
+    ) -> Result<ItemEventStream, WatchItemsError> {
+        self.item_service.watch_items(req).await
+    }
+
+    async fn find_price_history(
+        &self,
+        req: &FindPriceHistoryRequest,
+    ) -> Result<Vec<PricePoint>, FindPriceHistoryError> {
+        self.item_service.find_price_history(req).await
+    }
+
+    async fn watch_item_price(
+        &self,
+        req: &WatchItemPriceRequest,
+    ) -> Result<PriceWatch, WatchItemPriceError> {
+        self.item_service.watch_item_price(req).await
+    }
+
+    async fn upload_image(&self, req: &UploadImageRequest) -> Result<StoredImage, StoreImageError> {
+        self.image_service.upload_image(req).await
+    }
+
+    async fn find_image(&self, req: &FindImageRequest) -> Result<Option<Blob>, FindImageError> {
+        self.image_service.find_image(req).await
+    }
+
+    async fn subscribe_reminder(
+        &self,
+        req: &SubscribeReminderRequest,
+    ) -> Result<ReminderSubscription, SubscribeReminderError> {
+        self.notification_service.subscribe_reminder(req).await
+    }
+
+    async fn unsubscribe_reminder(
+        &self,
+        req: &UnsubscribeReminderRequest,
+    ) -> Result<ReminderSubscription, UnsubscribeReminderError> {
+        self.notification_service.unsubscribe_reminder(req).await
+    }
+
+    async fn set_reminder_preferences(
+        &self,
+        preferences: &ReminderPreferences,
+    ) -> Result<ReminderPreferences, SetReminderPreferencesError> {
+        self.notification_service
+            .set_reminder_preferences(preferences)
+            .await
+    }
+
+    async fn list_inbox(
+        &self,
+        req: &ListInboxRequest,
+    ) -> Result<Page<InboxMessage>, ListInboxError> {
+        self.notification_service.list_inbox(req).await
+    }
+
+    async fn create_exchange(
+        &self,
+        req: &CreateExchangeRequest,
+    ) -> Result<Exchange, CreateExchangeError> {
+        self.exchange_service.create_exchange(req).await
+    }
+
+    async fn add_participant(
+        &self,
+        req: &AddParticipantRequest,
+    ) -> Result<Exchange, AddParticipantError> {
+        self.exchange_service.add_participant(req).await
+    }
+
+    async fn add_exclusion(
+        &self,
+        req: &AddExclusionRequest,
+    ) -> Result<Exchange, AddExclusionError> {
+        self.exchange_service.add_exclusion(req).await
+    }
+
+    async fn draw_exchange(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> Result<Exchange, DrawExchangeError> {
+        self.exchange_service.draw_exchange(req).await
+    }
+
+    async fn reveal_recipient(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> Result<Reveal, RevealRecipientError> {
+        self.exchange_service.reveal_recipient(req).await
+    }
+
+    async fn audit_draw(&self, req: &ExchangeActionRequest) -> Result<DrawAudit, AuditDrawError> {
+        self.exchange_service.audit_draw(req).await
+    }
+    async fn create_group(&self, req: &CreateGroupRequest) -> Result<Group, CreateGroupError> {
+        self.group_service.create_group(req).await
+    }
+
+    async fn invite_member(
+        &self,
+        req: &InviteMemberRequest,
+    ) -> Result<GroupInvitation, InviteMemberError> {
+        self.group_service.invite_member(req).await
+    }
+
+    async fn list_invitations(
+        &self,
+        req: &ListInvitationsRequest,
+    ) -> Result<Page<GroupInvitation>, ListInvitationsError> {
+        self.group_service.list_invitations(req).await
+    }
+
+    async fn respond_to_invitation(
+        &self,
+        req: &RespondToInvitationRequest,
+    ) -> Result<GroupInvitation, RespondToInvitationError> {
+        self.group_service.respond_to_invitation(req).await
+    }
+
+    async fn remove_member(&self, req: &ManageMemberRequest) -> Result<Group, ManageMemberError> {
+        self.group_service.remove_member(req).await
+    }
+
+    async fn set_member_role(
+        &self,
+        req: &SetMemberRoleRequest,
+    ) -> Result<Group, ManageMemberError> {
+        self.group_service.set_member_role(req).await
+    }
+
+    async fn share_wishlist(
+        &self,
+        req: &ShareWishlistRequest,
+    ) -> Result<Group, ShareWishlistError> {
+        self.group_service.share_wishlist(req).await
+    }
+
+    async fn unshare_wishlist(
+        &self,
+        req: &ShareWishlistRequest,
+    ) -> Result<Group, ShareWishlistError> {
+        self.group_service.unshare_wishlist(req).await
+    }
+
+    async fn group_feed(
+        &self,
+        req: &GroupFeedRequest,
+    ) -> Result<Page<GroupFeedEntry>, GroupFeedError> {
+        self.group_service.group_feed(req).await
+    }
+    async fn follow_user(&self, req: &FollowUserRequest) -> Result<Follow, FollowUserError> {
+        self.follow_service.follow_user(req).await
+    }
+
+    async fn unfollow_user(&self, req: &FollowUserRequest) -> Result<Follow, UnfollowUserError> {
+        self.follow_service.unfollow_user(req).await
+    }
+
+    async fn list_follow_requests(
+        &self,
+        req: &ListFollowRequestsRequest,
+    ) -> Result<Page<Follow>, ListFollowRequestsError> {
+        self.follow_service.list_follow_requests(req).await
+    }
+
+    async fn answer_follow_request(
+        &self,
+        req: &AnswerFollowRequest,
+    ) -> Result<Follow, AnswerFollowRequestError> {
+        self.follow_service.answer_follow_request(req).await
+    }
+
+    async fn block_user(&self, req: &BlockUserRequest) -> Result<Block, BlockUserError> {
+        self.follow_service.block_user(req).await
+    }
+
+    async fn unblock_user(&self, req: &BlockUserRequest) -> Result<Block, UnblockUserError> {
+        self.follow_service.unblock_user(req).await
+    }
+
+    async fn set_follow_settings(
+        &self,
+        settings: &FollowSettings,
+    ) -> Result<FollowSettings, SetFollowSettingsError> {
+        self.follow_service.set_follow_settings(settings).await
+    }
+
+    async fn follow_feed(
+        &self,
+        req: &FollowFeedRequest,
+    ) -> Result<Page<Wishlist>, FollowFeedError> {
+        self.follow_service.follow_feed(req).await
+    }
+
+    async fn set_profile(&self, profile: &Profile) -> Result<Profile, SetProfileError> {
+        self.profile_service.set_profile(profile).await
+    }
+
+    async fn find_profile(&self, req: &FindProfileRequest) -> Result<Profile, FindProfileError> {
+        self.profile_service.find_profile(req).await
+    }
+
+    async fn find_profiles(
+        &self,
+        req: &FindProfilesRequest,
+    ) -> Result<Vec<Profile>, FindProfilesError> {
+        self.profile_service.find_profiles(req).await
+    }
+
+    async fn suggest_occasions(
+        &self,
+        req: &SuggestOccasionsRequest,
+    ) -> Result<Vec<Occasion>, SuggestOccasionsError> {
+        self.profile_service.suggest_occasions(req).await
+    }
+
+    async fn request_account_confirmation(
+        &self,
+        req: &AccountConfirmationRequest,
+    ) -> Result<AccountConfirmation, RequestAccountConfirmationError> {
+        self.account_service.request_account_confirmation(req).await
+    }
+
+    async fn export_account(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> Result<AccountExport, ExportAccountError> {
+        self.account_service.export_account(req).await
+    }
+
+    async fn delete_account(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> Result<AccountDeletion, DeleteAccountError> {
+        self.account_service.delete_account(req).await
+    }
+
+    async fn cancel_account_deletion(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> Result<AccountDeletion, CancelAccountDeletionError> {
+        self.account_service.cancel_account_deletion(req).await
+    }
+
+    async fn register_webhook(
+        &self,
+        req: &RegisterWebhookRequest,
+    ) -> Result<Webhook, RegisterWebhookError> {
+        self.webhook_service.register_webhook(req).await
+    }
+
+    async fn list_webhooks(
+        &self,
+        req: &ListWebhooksRequest,
+    ) -> Result<Page<Webhook>, ListWebhooksError> {
+        self.webhook_service.list_webhooks(req).await
+    }
+
+    async fn delete_webhook(&self, req: &WebhookRequest) -> Result<Webhook, DeleteWebhookError> {
+        self.webhook_service.delete_webhook(req).await
+    }
+
+    async fn list_webhook_deliveries(
+        &self,
+        req: &ListWebhookDeliveriesRequest,
+    ) -> Result<Page<WebhookDelivery>, ListWebhookDeliveriesError> {
+        self.webhook_service.list_webhook_deliveries(req).await
+    }
 }
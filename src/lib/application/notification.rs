@@ -0,0 +1,346 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use crate::domain::{
+    Clock, FindUserByIdRequest, FindWishlistByIdRequest, GroupRepository, InboxMessage,
+    InboxRepository, ListInboxError, ListInboxRequest, Notification, NotificationKind,
+    NotificationService, Notifier, Page, ReminderPreferences, ReminderRepository,
+    ReminderSubscription, SendRemindersError, SetReminderPreferencesError, SubscribeReminderError,
+    SubscribeReminderRequest, UnsubscribeReminderError, UnsubscribeReminderRequest, UserRepository,
+    Wishlist, WishlistRepository,
+};
+
+pub struct Service<U, W, R, B, P, N, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    R: ReminderRepository,
+    B: InboxRepository,
+    P: GroupRepository,
+    N: Notifier,
+    C: Clock,
+{
+    user_repository: Arc<U>,
+    wish_repository: Arc<W>,
+    reminder_repository: Arc<R>,
+    inbox_repository: Arc<B>,
+    group_repository: Arc<P>,
+    notifier: Arc<N>,
+    clock: Arc<C>,
+}
+
+impl<U, W, R, B, P, N, C> Clone for Service<U, W, R, B, P, N, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    R: ReminderRepository,
+    B: InboxRepository,
+    P: GroupRepository,
+    N: Notifier,
+    C: Clock,
+{
+    fn clone(&self) -> Self {
+        Self {
+            user_repository: self.user_repository.clone(),
+            wish_repository: self.wish_repository.clone(),
+            reminder_repository: self.reminder_repository.clone(),
+            inbox_repository: self.inbox_repository.clone(),
+            group_repository: self.group_repository.clone(),
+            notifier: self.notifier.clone(),
+            clock: self.clock.clone(),
+        }
+    }
+}
+
+impl<U, W, R, B, P, N, C> Service<U, W, R, B, P, N, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    R: ReminderRepository,
+    B: InboxRepository,
+    P: GroupRepository,
+    N: Notifier,
+    C: Clock,
+{
+    pub fn new(
+        user_repository: Arc<U>,
+        wish_repository: Arc<W>,
+        reminder_repository: Arc<R>,
+        inbox_repository: Arc<B>,
+        group_repository: Arc<P>,
+        notifier: Arc<N>,
+        clock: Arc<C>,
+    ) -> Self {
+        Self {
+            user_repository,
+            wish_repository,
+            reminder_repository,
+            inbox_repository,
+            group_repository,
+            notifier,
+            clock,
+        }
+    }
+
+    /// Returns true if the user may see the wishlist, see [Wishlist::is_visible_to].
+    async fn can_view(&self, user_id: Uuid, wishlist: &Wishlist) -> anyhow::Result<bool> {
+        let groups = self
+            .group_repository
+            .find_groups_by_member(user_id)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(wishlist.is_visible_to(user_id, &groups))
+    }
+
+    /// Sends the reminder of a subscription if it is due, and records it as sent.
+    async fn send_reminder(
+        &self,
+        subscription: &ReminderSubscription,
+    ) -> Result<(), SendRemindersError> {
+        let wishlist = self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(subscription.wishlist_id()))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let Some(wishlist) = wishlist.filter(|wishlist| !wishlist.archived()) else {
+            return Ok(());
+        };
+        if !self.can_view(subscription.user_id(), &wishlist).await? {
+            return Ok(());
+        }
+        let Some(occasion) = wishlist.occasion() else {
+            return Ok(());
+        };
+        let preferences = self
+            .reminder_repository
+            .find_preferences(subscription.user_id())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .unwrap_or_else(|| ReminderPreferences::default_for(subscription.user_id()));
+        let now = self.clock.now();
+        if !subscription.is_due(occasion, &preferences, now) {
+            return Ok(());
+        }
+        let notification = Notification::new(
+            subscription.user_id(),
+            NotificationKind::OccasionReminder {
+                wishlist_id: wishlist.id(),
+                wishlist_name: wishlist.name().clone(),
+                occasion: occasion.kind(),
+                date: occasion.date(),
+                days_left: occasion.days_until(now),
+            },
+        );
+        if let Err(err) = self.notifier.notify(&notification).await {
+            tracing::warn!("failed to remind {}: {}", subscription.user_id(), err);
+            return Ok(());
+        }
+        self.reminder_repository
+            .mark_sent(subscription.id(), occasion.date())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(())
+    }
+}
+
+impl<U, W, R, B, P, N, C> NotificationService for Service<U, W, R, B, P, N, C>
+where
+    U: UserRepository + Send + Sync + 'static,
+    W: WishlistRepository + Send + Sync + 'static,
+    R: ReminderRepository,
+    B: InboxRepository,
+    P: GroupRepository,
+    N: Notifier,
+    C: Clock,
+{
+    async fn subscribe_reminder(
+        &self,
+        req: &SubscribeReminderRequest,
+    ) -> Result<ReminderSubscription, SubscribeReminderError> {
+        let user = self
+            .user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(req.user_id()))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if user.is_none() {
+            return Err(SubscribeReminderError::UserDoesNotExist { id: req.user_id() });
+        }
+        let wishlist = self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(req.wishlist_id()))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let visible = match wishlist {
+            Some(wishlist) => self.can_view(req.user_id(), &wishlist).await?,
+            None => false,
+        };
+        if !visible {
+            return Err(SubscribeReminderError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            });
+        }
+        self.reminder_repository.save_subscription(req).await
+    }
+
+    async fn unsubscribe_reminder(
+        &self,
+        req: &UnsubscribeReminderRequest,
+    ) -> Result<ReminderSubscription, UnsubscribeReminderError> {
+        self.reminder_repository.delete_subscription(req).await
+    }
+
+    async fn set_reminder_preferences(
+        &self,
+        preferences: &ReminderPreferences,
+    ) -> Result<ReminderPreferences, SetReminderPreferencesError> {
+        self.reminder_repository.save_preferences(preferences).await
+    }
+
+    async fn list_inbox(
+        &self,
+        req: &ListInboxRequest,
+    ) -> Result<Page<InboxMessage>, ListInboxError> {
+        self.inbox_repository.find_messages(req).await
+    }
+
+    async fn send_due_reminders(&self) -> Result<(), SendRemindersError> {
+        let subscriptions = self
+            .reminder_repository
+            .find_subscriptions()
+            .await
+            .map_err(|err| anyhow!(err))?;
+        for subscription in subscriptions {
+            self.send_reminder(&subscription).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+    use crate::{
+        domain::{
+            CreateUserRequest, CreateWishlistRequest, Occasion, OccasionKind, PageRequest,
+            QuietHours, Recurrence,
+        },
+        infrastructure::{
+            clock::ManualClock,
+            notification::InboxNotifier,
+            persistence::in_memory::{
+                group::InMemoryGroupRepository, inbox::InMemoryInboxRepository,
+                reminder::InMemoryReminderRepository, user::InMemoryUserRepository,
+                wishlist::InMemoryWishlistRepository,
+            },
+        },
+    };
+
+    #[tokio::test]
+    async fn test_send_due_reminders_on_schedule() {
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let inbox_repository = Arc::new(InMemoryInboxRepository::new());
+        let clock = Arc::new(ManualClock::new("2027-03-01T12:00:00Z".parse().unwrap()));
+        let service = Service::new(
+            user_repository.clone(),
+            wish_repository.clone(),
+            Arc::new(InMemoryReminderRepository::new()),
+            inbox_repository.clone(),
+            Arc::new(InMemoryGroupRepository::new()),
+            Arc::new(InboxNotifier::new(inbox_repository.clone(), clock.clone())),
+            clock.clone(),
+        );
+        let mut users = Vec::new();
+        for email in ["alice@example.com", "bob@example.com"] {
+            let req = CreateUserRequest::new(email.into(), "password".into());
+            users.push(*user_repository.save(&req).await.unwrap().id());
+        }
+        let (owner, giver) = (users[0], users[1]);
+        let wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(
+                owner,
+                "Alice's birthday".into(),
+                true,
+            ))
+            .await
+            .unwrap();
+        let result = service
+            .subscribe_reminder(&SubscribeReminderRequest::new(giver, wishlist.id(), 3))
+            .await;
+        assert!(matches!(
+            result,
+            Err(SubscribeReminderError::WishlistDoesNotExist { .. })
+        ));
+        let wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(
+                owner,
+                "Alice's birthday".into(),
+                false,
+            ))
+            .await
+            .unwrap();
+        wish_repository
+            .set_occasion(
+                wishlist.id(),
+                Some(Occasion::new(
+                    OccasionKind::Birthday,
+                    "2027-03-14".parse().unwrap(),
+                    chrono_tz::Europe::Paris,
+                    Some(Recurrence::Yearly),
+                )),
+                None,
+            )
+            .await
+            .unwrap();
+        service
+            .subscribe_reminder(&SubscribeReminderRequest::new(giver, wishlist.id(), 3))
+            .await
+            .unwrap();
+        let quiet_hours =
+            QuietHours::new("22:00:00".parse().unwrap(), "08:00:00".parse().unwrap()).unwrap();
+        service
+            .set_reminder_preferences(&ReminderPreferences::new(
+                giver,
+                chrono_tz::Asia::Tokyo,
+                Some(quiet_hours),
+            ))
+            .await
+            .unwrap();
+        let giver_inbox = ListInboxRequest::new(giver, PageRequest::default());
+        let inbox = || service.list_inbox(&giver_inbox);
+
+        service.send_due_reminders().await.unwrap();
+        assert!(inbox().await.unwrap().entries().is_empty());
+
+        // Midnight of March 11 in Tokyo, within the quiet hours.
+        clock.set("2027-03-10T15:00:00Z".parse().unwrap());
+        service.send_due_reminders().await.unwrap();
+        assert!(inbox().await.unwrap().entries().is_empty());
+
+        // 08:00 in Tokyo.
+        clock.advance(TimeDelta::hours(8));
+        service.send_due_reminders().await.unwrap();
+        let messages = inbox().await.unwrap();
+        let messages = messages.entries();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(
+            messages[0].kind(),
+            NotificationKind::OccasionReminder { days_left: 3, .. }
+        ));
+        assert_eq!(messages[0].created_at(), clock.now());
+
+        clock.advance(TimeDelta::hours(4));
+        service.send_due_reminders().await.unwrap();
+        assert_eq!(inbox().await.unwrap().entries().len(), 1);
+        assert!(service
+            .list_inbox(&ListInboxRequest::new(owner, PageRequest::default()))
+            .await
+            .unwrap()
+            .entries()
+            .is_empty());
+    }
+}
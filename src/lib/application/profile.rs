@@ -0,0 +1,343 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use anyhow::anyhow;
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::domain::{
+    Clock, FindImageRequest, FindProfileError, FindProfileRequest, FindProfilesError,
+    FindProfilesRequest, FindUserByIdRequest, FollowRepository, ImageService, ImageSize,
+    ItemRepository, Occasion, Profile, ProfileRepository, ProfileService, ProfileViewer,
+    SetProfileError, SuggestOccasionsError, SuggestOccasionsRequest, UserRepository,
+    WishlistRepository,
+};
+
+pub struct Service<U, W, I, F, P, G, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    I: ItemRepository,
+    F: FollowRepository,
+    P: ProfileRepository,
+    G: ImageService,
+    C: Clock,
+{
+    user_repository: Arc<U>,
+    wish_repository: Arc<W>,
+    item_repository: Arc<I>,
+    follow_repository: Arc<F>,
+    profile_repository: Arc<P>,
+    image_service: Arc<G>,
+    clock: Arc<C>,
+}
+
+impl<U, W, I, F, P, G, C> Clone for Service<U, W, I, F, P, G, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    I: ItemRepository,
+    F: FollowRepository,
+    P: ProfileRepository,
+    G: ImageService,
+    C: Clock,
+{
+    fn clone(&self) -> Self {
+        Self {
+            user_repository: self.user_repository.clone(),
+            wish_repository: self.wish_repository.clone(),
+            item_repository: self.item_repository.clone(),
+            follow_repository: self.follow_repository.clone(),
+            profile_repository: self.profile_repository.clone(),
+            image_service: self.image_service.clone(),
+            clock: self.clock.clone(),
+        }
+    }
+}
+
+impl<U, W, I, F, P, G, C> Service<U, W, I, F, P, G, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    I: ItemRepository,
+    F: FollowRepository,
+    P: ProfileRepository,
+    G: ImageService,
+    C: Clock,
+{
+    pub fn new(
+        user_repository: Arc<U>,
+        wish_repository: Arc<W>,
+        item_repository: Arc<I>,
+        follow_repository: Arc<F>,
+        profile_repository: Arc<P>,
+        image_service: Arc<G>,
+        clock: Arc<C>,
+    ) -> Self {
+        Self {
+            user_repository,
+            wish_repository,
+            item_repository,
+            follow_repository,
+            profile_repository,
+            image_service,
+            clock,
+        }
+    }
+
+    async fn user_exists(&self, id: Uuid) -> anyhow::Result<bool> {
+        let user = self
+            .user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(id))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(user.is_some())
+    }
+
+    /// Returns the saved profile of the user, or an empty one.
+    async fn profile_of(&self, user_id: Uuid) -> anyhow::Result<Profile> {
+        let profile = self
+            .profile_repository
+            .find_profile(user_id)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(profile.unwrap_or_else(|| Profile::new(user_id)))
+    }
+
+    /// Works out what `viewer_id` is to every owner: the owners they follow, and those they hold
+    /// a reservation for on an item not received yet.
+    async fn relations_of(&self, viewer_id: Uuid) -> anyhow::Result<ViewerRelations> {
+        let followees = self
+            .follow_repository
+            .find_followees(viewer_id)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let reserved_wishlists: Vec<Uuid> = self
+            .item_repository
+            .find_items_reserved_by(viewer_id)
+            .await
+            .map_err(|err| anyhow!(err))?
+            .iter()
+            .filter(|item| !item.received())
+            .map(|item| item.wishlist_id())
+            .collect();
+        let reserved_owners = self
+            .wish_repository
+            .find_wishlists_by_ids(&reserved_wishlists)
+            .await
+            .map_err(|err| anyhow!(err))?
+            .iter()
+            .map(|wishlist| wishlist.owner_id())
+            .collect();
+        Ok(ViewerRelations {
+            viewer_id,
+            followees: followees.into_iter().collect(),
+            reserved_owners,
+        })
+    }
+}
+
+/// What a viewer is to the owners of profiles, see [Service::relations_of].
+struct ViewerRelations {
+    viewer_id: Uuid,
+    followees: HashSet<Uuid>,
+    reserved_owners: HashSet<Uuid>,
+}
+
+impl ViewerRelations {
+    /// What the viewer is to `owner_id`: a follower, and a reserver as long as they hold a
+    /// reservation on an item the owner has not received yet.
+    fn viewer_of(&self, owner_id: Uuid) -> ProfileViewer {
+        if self.viewer_id == owner_id {
+            return ProfileViewer::owner();
+        }
+        ProfileViewer::other(
+            self.followees.contains(&owner_id),
+            self.reserved_owners.contains(&owner_id),
+        )
+    }
+}
+
+impl<U, W, I, F, P, G, C> ProfileService for Service<U, W, I, F, P, G, C>
+where
+    U: UserRepository + Send + Sync + 'static,
+    W: WishlistRepository + Send + Sync + 'static,
+    I: ItemRepository + Send + Sync + 'static,
+    F: FollowRepository,
+    P: ProfileRepository,
+    G: ImageService,
+    C: Clock,
+{
+    async fn set_profile(&self, profile: &Profile) -> Result<Profile, SetProfileError> {
+        if !self.user_exists(profile.user_id()).await? {
+            return Err(SetProfileError::UserDoesNotExist {
+                id: profile.user_id(),
+            });
+        }
+        if let Some(avatar_id) = profile.avatar_id() {
+            let avatar = self
+                .image_service
+                .find_image(&FindImageRequest::new(avatar_id, ImageSize::Small))
+                .await
+                .map_err(|err| anyhow!(err))?;
+            if avatar.is_none() {
+                return Err(SetProfileError::AvatarDoesNotExist { id: avatar_id });
+            }
+        }
+        // The latest date anywhere on Earth, 14 hours ahead of UTC
+        let today = (self.clock.now() + Duration::hours(14)).date_naive();
+        if let Some(birthday) = profile.birthday().filter(|birthday| *birthday > today) {
+            return Err(SetProfileError::BirthdayInFuture { date: birthday });
+        }
+        self.profile_repository.save_profile(profile).await
+    }
+
+    async fn find_profile(&self, req: &FindProfileRequest) -> Result<Profile, FindProfileError> {
+        if !self.user_exists(req.profile_id()).await? {
+            return Err(FindProfileError::UserDoesNotExist {
+                id: req.profile_id(),
+            });
+        }
+        let profile = self.profile_of(req.profile_id()).await?;
+        let relations = self.relations_of(req.user_id()).await?;
+        Ok(profile.visible_to(relations.viewer_of(req.profile_id())))
+    }
+
+    async fn find_profiles(
+        &self,
+        req: &FindProfilesRequest,
+    ) -> Result<Vec<Profile>, FindProfilesError> {
+        let profiles: HashMap<Uuid, Profile> = self
+            .profile_repository
+            .find_profiles(req.profile_ids())
+            .await
+            .map_err(|err| anyhow!(err))?
+            .into_iter()
+            .map(|profile| (profile.user_id(), profile))
+            .collect();
+        let relations = self.relations_of(req.user_id()).await?;
+        Ok(req
+            .profile_ids()
+            .iter()
+            .map(|&profile_id| {
+                profiles
+                    .get(&profile_id)
+                    .cloned()
+                    .unwrap_or_else(|| Profile::new(profile_id))
+                    .visible_to(relations.viewer_of(profile_id))
+            })
+            .collect())
+    }
+
+    async fn suggest_occasions(
+        &self,
+        req: &SuggestOccasionsRequest,
+    ) -> Result<Vec<Occasion>, SuggestOccasionsError> {
+        let profile = self.profile_of(req.user_id()).await?;
+        Ok(profile
+            .birthday_occasion(req.time_zone(), self.clock.now())
+            .into_iter()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{
+            CreateItemRequest, CreateUserRequest, CreateWishlistRequest, MockImageService,
+            ShippingAddress,
+        },
+        infrastructure::{
+            clock::ManualClock,
+            persistence::in_memory::{
+                follow::InMemoryFollowRepository, item::InMemoryItemRepository,
+                profile::InMemoryProfileRepository, user::InMemoryUserRepository,
+                wishlist::InMemoryWishlistRepository,
+            },
+        },
+    };
+
+    #[tokio::test]
+    async fn test_reveal_address_to_reservers() {
+        let clock = Arc::new(ManualClock::new("2027-12-01T09:00:00Z".parse().unwrap()));
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let service = Service::new(
+            user_repository.clone(),
+            wish_repository.clone(),
+            item_repository.clone(),
+            Arc::new(InMemoryFollowRepository::new()),
+            Arc::new(InMemoryProfileRepository::new()),
+            Arc::new(MockImageService::new()),
+            clock.clone(),
+        );
+        let mut users = Vec::new();
+        for email in ["a@example.com", "b@example.com"] {
+            let req = CreateUserRequest::new(email.into(), "password".into());
+            users.push(*user_repository.save(&req).await.unwrap().id());
+        }
+        let (owner, giver) = (users[0], users[1]);
+        let address =
+            ShippingAddress::new("Alice", "1 Main St", None, "Paris", "75001", "FR").unwrap();
+        let profile = Profile::new(owner)
+            .with_birthday(Some("1990-03-14".parse().unwrap()))
+            .with_shipping_address(Some(address.clone()));
+        service.set_profile(&profile).await.unwrap();
+        let future_birthday =
+            Profile::new(owner).with_birthday(Some("2027-12-03".parse().unwrap()));
+        assert!(matches!(
+            service.set_profile(&future_birthday).await,
+            Err(SetProfileError::BirthdayInFuture { .. })
+        ));
+
+        let req = FindProfileRequest::new(giver, owner);
+        let seen = service.find_profile(&req).await.unwrap();
+        assert_eq!(seen.shipping_address(), None);
+        assert_eq!(seen.birthday(), None);
+
+        let wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(owner, "Gifts".into(), false))
+            .await
+            .unwrap();
+        let item = item_repository
+            .save(&CreateItemRequest::new(
+                wishlist.id(),
+                wishlist.owner_id(),
+                Some("Book".into()),
+                "https://shop.example/book".into(),
+                None,
+                None,
+            ))
+            .await
+            .unwrap();
+        item_repository
+            .reserve_item(item.id(), giver, true)
+            .await
+            .unwrap();
+        let seen = service.find_profile(&req).await.unwrap();
+        assert_eq!(seen.shipping_address(), Some(&address));
+        assert_eq!(seen.birthday(), None);
+        let batch = service
+            .find_profiles(&FindProfilesRequest::new(giver, vec![owner, giver]))
+            .await
+            .unwrap();
+        assert_eq!(batch, vec![seen, Profile::new(giver)]);
+        let own = service
+            .find_profile(&FindProfileRequest::new(owner, owner))
+            .await
+            .unwrap();
+        assert_eq!(own, profile);
+
+        let suggestions = service
+            .suggest_occasions(&SuggestOccasionsRequest::new(owner, chrono_tz::UTC))
+            .await
+            .unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].date(), "2028-03-14".parse().unwrap());
+    }
+}
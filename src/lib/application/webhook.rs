@@ -0,0 +1,709 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use crate::domain::{
+    AttemptOutcome, Clock, Cursor, DeleteWebhookError, DeliverWebhooksError, DeliveryAttempt,
+    FindUserByIdRequest, FindWishlistByIdRequest, FollowRepository, GroupRepository,
+    ListWebhookDeliveriesError, ListWebhookDeliveriesRequest, ListWebhooksError,
+    ListWebhooksRequest, Page, RegisterWebhookError, RegisterWebhookRequest, RetryPolicy,
+    SortDirection, SortValue, UserRepository, Webhook, WebhookDelivery, WebhookRepository,
+    WebhookRequest, WebhookSecret, WebhookSender, WebhookService, WishlistRepository,
+};
+
+/// How many due deliveries are attempted per run, so that a backlog is worked through in
+/// bounded batches.
+const DELIVERY_BATCH_SIZE: usize = 100;
+
+pub struct Service<U, W, G, F, R, S, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    G: GroupRepository,
+    F: FollowRepository,
+    R: WebhookRepository,
+    S: WebhookSender,
+    C: Clock,
+{
+    user_repository: Arc<U>,
+    wish_repository: Arc<W>,
+    group_repository: Arc<G>,
+    follow_repository: Arc<F>,
+    webhook_repository: Arc<R>,
+    sender: Arc<S>,
+    clock: Arc<C>,
+    retry_policy: RetryPolicy,
+}
+
+impl<U, W, G, F, R, S, C> Clone for Service<U, W, G, F, R, S, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    G: GroupRepository,
+    F: FollowRepository,
+    R: WebhookRepository,
+    S: WebhookSender,
+    C: Clock,
+{
+    fn clone(&self) -> Self {
+        Self {
+            user_repository: self.user_repository.clone(),
+            wish_repository: self.wish_repository.clone(),
+            group_repository: self.group_repository.clone(),
+            follow_repository: self.follow_repository.clone(),
+            webhook_repository: self.webhook_repository.clone(),
+            sender: self.sender.clone(),
+            clock: self.clock.clone(),
+            retry_policy: self.retry_policy,
+        }
+    }
+}
+
+impl<U, W, G, F, R, S, C> Service<U, W, G, F, R, S, C>
+where
+    U: UserRepository,
+    W: WishlistRepository,
+    G: GroupRepository,
+    F: FollowRepository,
+    R: WebhookRepository,
+    S: WebhookSender,
+    C: Clock,
+{
+    pub fn new(
+        user_repository: Arc<U>,
+        wish_repository: Arc<W>,
+        group_repository: Arc<G>,
+        follow_repository: Arc<F>,
+        webhook_repository: Arc<R>,
+        sender: Arc<S>,
+        clock: Arc<C>,
+    ) -> Self {
+        Self {
+            user_repository,
+            wish_repository,
+            group_repository,
+            follow_repository,
+            webhook_repository,
+            sender,
+            clock,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Sets when failed deliveries are retried, and when they are dead-lettered.
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy,
+            ..self
+        }
+    }
+
+    /// Finds a webhook of the user.
+    async fn find_own_webhook(&self, req: &WebhookRequest) -> anyhow::Result<Option<Webhook>> {
+        let webhook = self
+            .webhook_repository
+            .find_webhook_by_id(req.webhook_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(webhook.filter(|webhook| webhook.user_id() == req.user_id()))
+    }
+
+    /// Returns true if `user_id` may see the wishlist: it exists, is visible to them, and its
+    /// owner has not blocked them.
+    async fn may_see(&self, user_id: Uuid, wishlist_id: Uuid) -> anyhow::Result<bool> {
+        let wishlist = self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlist_id))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let Some(wishlist) = wishlist else {
+            return Ok(false);
+        };
+        let groups = self
+            .group_repository
+            .find_groups_by_member(user_id)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if !wishlist.is_visible_to(user_id, &groups) {
+            return Ok(false);
+        }
+        let block = self
+            .follow_repository
+            .find_block(wishlist.owner_id(), user_id)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(block.is_none())
+    }
+
+    /// Attempts a delivery once and records the outcome.
+    async fn deliver(&self, mut delivery: WebhookDelivery) -> Result<(), DeliverWebhooksError> {
+        let webhook = self
+            .webhook_repository
+            .find_webhook_by_id(delivery.webhook_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        // Deliveries of deleted webhooks are dropped with them
+        let Some(webhook) = webhook else {
+            return Ok(());
+        };
+        // Access may have been revoked since the webhook was registered: the list made private or
+        // unshared, the user removed from the group or blocked. The webhook goes with it.
+        if !self
+            .may_see(webhook.user_id(), webhook.wishlist_id())
+            .await?
+        {
+            tracing::info!(
+                "dropping webhook {}: user {} may no longer see wishlist {}",
+                webhook.id(),
+                webhook.user_id(),
+                webhook.wishlist_id()
+            );
+            let req = WebhookRequest::new(webhook.id(), webhook.user_id());
+            match self.webhook_repository.delete_webhook(&req).await {
+                Ok(_) | Err(DeleteWebhookError::WebhookDoesNotExist { .. }) => {}
+                Err(err) => return Err(DeliverWebhooksError::Unkown(anyhow!(err))),
+            }
+            return Ok(());
+        }
+        let now = self.clock.now();
+        let outcome = match self.sender.send(&webhook, &delivery, now).await {
+            Ok(status) => AttemptOutcome::Responded { status },
+            Err(err) => AttemptOutcome::Failed {
+                reason: err.to_string(),
+            },
+        };
+        if !outcome.is_success() {
+            tracing::warn!(
+                "failed to deliver {} to webhook {}: {:?}",
+                delivery.id(),
+                webhook.id(),
+                outcome
+            );
+        }
+        delivery.record_attempt(DeliveryAttempt::new(now, outcome), &self.retry_policy);
+        self.webhook_repository
+            .save_delivery(&delivery)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(())
+    }
+}
+
+impl<U, W, G, F, R, S, C> WebhookService for Service<U, W, G, F, R, S, C>
+where
+    U: UserRepository + Send + Sync + 'static,
+    W: WishlistRepository + Send + Sync + 'static,
+    G: GroupRepository,
+    F: FollowRepository,
+    R: WebhookRepository,
+    S: WebhookSender,
+    C: Clock,
+{
+    async fn register_webhook(
+        &self,
+        req: &RegisterWebhookRequest,
+    ) -> Result<Webhook, RegisterWebhookError> {
+        let user = self
+            .user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(req.user_id()))
+            .await
+            .map_err(|err| anyhow!(err))?;
+        if user.is_none() {
+            return Err(RegisterWebhookError::UserDoesNotExist { id: req.user_id() });
+        }
+        if !self.may_see(req.user_id(), req.wishlist_id()).await? {
+            return Err(RegisterWebhookError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            });
+        }
+        let webhook = Webhook::new(
+            Uuid::now_v7(),
+            req.user_id(),
+            req.wishlist_id(),
+            req.url().clone(),
+            WebhookSecret::generate(),
+            req.events().to_vec(),
+            self.clock.now(),
+        );
+        self.webhook_repository.save_webhook(&webhook).await
+    }
+
+    async fn list_webhooks(
+        &self,
+        req: &ListWebhooksRequest,
+    ) -> Result<Page<Webhook>, ListWebhooksError> {
+        let webhooks = self
+            .webhook_repository
+            .find_webhooks_by_user(req.user_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(req
+            .page()
+            .paginate(webhooks, SortDirection::Ascending, |webhook| {
+                Cursor::new(Some(SortValue::Time(webhook.created_at())), webhook.id())
+            }))
+    }
+
+    async fn delete_webhook(&self, req: &WebhookRequest) -> Result<Webhook, DeleteWebhookError> {
+        self.webhook_repository.delete_webhook(req).await
+    }
+
+    async fn list_webhook_deliveries(
+        &self,
+        req: &ListWebhookDeliveriesRequest,
+    ) -> Result<Page<WebhookDelivery>, ListWebhookDeliveriesError> {
+        let own = WebhookRequest::new(req.webhook_id(), req.user_id());
+        if self.find_own_webhook(&own).await?.is_none() {
+            return Err(ListWebhookDeliveriesError::WebhookDoesNotExist {
+                id: req.webhook_id(),
+            });
+        }
+        self.webhook_repository.find_deliveries(req).await
+    }
+
+    async fn deliver_due_webhooks(&self) -> Result<(), DeliverWebhooksError> {
+        let due = self
+            .webhook_repository
+            .find_due_deliveries(self.clock.now(), DELIVERY_BATCH_SIZE)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        for delivery in due {
+            self.deliver(delivery).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use axum::{http::StatusCode, routing::post, Router};
+    use chrono::{TimeDelta, Utc};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::{
+        domain::{
+            CreateGroupRequest, CreateUserRequest, CreateWishlistRequest, DeliveryStatus, Item,
+            ItemChange, ItemEventBus, MockWebhookSender, MockWishlistRepository, PageLimit,
+            PageRequest, SharedWishlist, WebhookEventKind, WebhookUrl, Wishlist,
+        },
+        infrastructure::{
+            clock::ManualClock,
+            events::{InMemoryItemEventBus, WebhookItemEventBus},
+            persistence::in_memory::{
+                follow::InMemoryFollowRepository, group::InMemoryGroupRepository,
+                user::InMemoryUserRepository, webhook::InMemoryWebhookRepository,
+                wishlist::InMemoryWishlistRepository,
+            },
+            webhook::{HttpWebhookSender, HttpWebhookSenderConfig},
+        },
+    };
+
+    /// A local receiver that fails the first `failures` requests, then accepts the others.
+    async fn spawn_flaky_receiver(failures: usize) -> (String, Arc<Mutex<usize>>) {
+        let received = Arc::new(Mutex::new(0));
+        let counter = received.clone();
+        let router = Router::new().route(
+            "/hooks",
+            post(move || {
+                let counter = counter.clone();
+                async move {
+                    let mut received = counter.lock().unwrap();
+                    *received += 1;
+                    if *received <= failures {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    } else {
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        (format!("http://{}/hooks", address), received)
+    }
+
+    #[tokio::test]
+    async fn test_deliveries_retried_with_backoff_then_dead_lettered() {
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let webhook_repository = Arc::new(InMemoryWebhookRepository::new());
+        let clock = Arc::new(ManualClock::new("2027-03-01T12:00:00Z".parse().unwrap()));
+        let service = Service::new(
+            user_repository.clone(),
+            wish_repository.clone(),
+            Arc::new(InMemoryGroupRepository::new()),
+            Arc::new(InMemoryFollowRepository::new()),
+            webhook_repository.clone(),
+            Arc::new(HttpWebhookSender::new(HttpWebhookSenderConfig {
+                timeout: Duration::from_millis(500),
+                allow_private_networks: true,
+            })),
+            clock.clone(),
+        )
+        .with_retry_policy(RetryPolicy::new(
+            3,
+            TimeDelta::minutes(1),
+            TimeDelta::hours(1),
+        ));
+        let bus = WebhookItemEventBus::new(
+            InMemoryItemEventBus::new(),
+            webhook_repository.clone(),
+            clock.clone(),
+        );
+        let mut users = Vec::new();
+        for email in ["alice@example.com", "bob@example.com"] {
+            let req = CreateUserRequest::new(email.into(), "password".into());
+            users.push(*user_repository.save(&req).await.unwrap().id());
+        }
+        let (owner, stranger) = (users[0], users[1]);
+        let wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(owner, "Birthday".into(), true))
+            .await
+            .unwrap();
+
+        // A private wishlist is only the owner's
+        let (url, received) = spawn_flaky_receiver(1).await;
+        let register = |user_id, url: &str| {
+            RegisterWebhookRequest::new(
+                user_id,
+                wishlist.id(),
+                WebhookUrl::new(url).unwrap(),
+                vec![WebhookEventKind::ItemAdded],
+            )
+        };
+        let result = service.register_webhook(&register(stranger, &url)).await;
+        assert!(matches!(
+            result,
+            Err(RegisterWebhookError::WishlistDoesNotExist { .. })
+        ));
+        let webhook = service
+            .register_webhook(&register(owner, &url))
+            .await
+            .unwrap();
+        assert!(webhook.secret().as_str().starts_with("whsec_"));
+        let (dead_url, _) = spawn_flaky_receiver(usize::MAX).await;
+        let dead = service
+            .register_webhook(&register(owner, &dead_url))
+            .await
+            .unwrap();
+
+        let item = Item::create(
+            Uuid::now_v7(),
+            wishlist.id(),
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        bus.publish(&wishlist, ItemChange::Added(item))
+            .await
+            .unwrap();
+        let log = |webhook: &Webhook| {
+            let req =
+                ListWebhookDeliveriesRequest::new(webhook.id(), owner, PageRequest::default());
+            let service = &service;
+            async move { service.list_webhook_deliveries(&req).await }
+        };
+
+        service.deliver_due_webhooks().await.unwrap();
+        let deliveries = log(&webhook).await.unwrap();
+        let delivery = &deliveries.entries()[0];
+        assert_eq!(delivery.status(), DeliveryStatus::Pending);
+        assert_eq!(
+            delivery.attempts()[0].outcome(),
+            &AttemptOutcome::Responded { status: 503 }
+        );
+        assert_eq!(
+            delivery.next_attempt_at(),
+            Some(clock.now() + TimeDelta::minutes(1))
+        );
+
+        // Nothing is retried before the backoff is over
+        service.deliver_due_webhooks().await.unwrap();
+        assert_eq!(*received.lock().unwrap(), 1);
+        clock.advance(TimeDelta::minutes(1));
+        service.deliver_due_webhooks().await.unwrap();
+        let deliveries = log(&webhook).await.unwrap();
+        assert_eq!(deliveries.entries()[0].status(), DeliveryStatus::Delivered);
+        assert_eq!(*received.lock().unwrap(), 2);
+
+        clock.advance(TimeDelta::minutes(2));
+        service.deliver_due_webhooks().await.unwrap();
+        let deliveries = log(&dead).await.unwrap();
+        assert_eq!(
+            deliveries.entries()[0].status(),
+            DeliveryStatus::DeadLettered
+        );
+        assert_eq!(deliveries.entries()[0].attempts().len(), 3);
+
+        let result = service
+            .list_webhook_deliveries(&ListWebhookDeliveriesRequest::new(
+                webhook.id(),
+                stranger,
+                PageRequest::default(),
+            ))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ListWebhookDeliveriesError::WebhookDoesNotExist { .. })
+        ));
+        let limit = PageLimit::new(1).unwrap();
+        let first = service
+            .list_webhooks(&ListWebhooksRequest::new(
+                owner,
+                PageRequest::new(None, limit),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(first.entries(), std::slice::from_ref(&webhook));
+        let second = service
+            .list_webhooks(&ListWebhooksRequest::new(
+                owner,
+                PageRequest::new(first.next_cursor().cloned(), limit),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(second.entries(), std::slice::from_ref(&dead));
+        assert_eq!(second.next_cursor(), None);
+        service
+            .delete_webhook(&WebhookRequest::new(dead.id(), owner))
+            .await
+            .unwrap();
+        assert_eq!(
+            service
+                .list_webhooks(&ListWebhooksRequest::new(owner, PageRequest::default()))
+                .await
+                .unwrap()
+                .entries(),
+            &[webhook]
+        );
+    }
+
+    /// A webhook of `member` on the wishlist of `owner`, both members of a group.
+    struct SharedWebhook<W: WishlistRepository> {
+        service: Service<
+            InMemoryUserRepository,
+            W,
+            InMemoryGroupRepository,
+            InMemoryFollowRepository,
+            InMemoryWebhookRepository,
+            MockWebhookSender,
+            ManualClock,
+        >,
+        bus: WebhookItemEventBus<InMemoryItemEventBus, InMemoryWebhookRepository, ManualClock>,
+        group_repository: Arc<InMemoryGroupRepository>,
+        follow_repository: Arc<InMemoryFollowRepository>,
+        webhook_repository: Arc<InMemoryWebhookRepository>,
+        wishlist: Wishlist,
+        group_id: Uuid,
+        owner: Uuid,
+        member: Uuid,
+    }
+
+    impl<W: WishlistRepository + Send + Sync + 'static> SharedWebhook<W> {
+        /// Registers the webhook, whose receiver expects exactly one event. The wishlist is
+        /// shared with the group if `share`.
+        async fn register(wish_repository: Arc<W>, wishlist: Wishlist, share: bool) -> Self {
+            let owner = wishlist.owner_id();
+            let user_repository = Arc::new(InMemoryUserRepository::new());
+            let member = *user_repository
+                .save(&CreateUserRequest::new(
+                    "member@example.com".into(),
+                    "password".into(),
+                ))
+                .await
+                .unwrap()
+                .id();
+            let group_repository = Arc::new(InMemoryGroupRepository::new());
+            let group = group_repository
+                .save(&CreateGroupRequest::new(owner, "Family".into()))
+                .await
+                .unwrap();
+            let invitation = group_repository
+                .save_invitation(group.id(), member, owner, Utc::now())
+                .await
+                .unwrap();
+            group_repository
+                .answer_invitation(invitation.id(), member, true)
+                .await
+                .unwrap();
+            if share {
+                group_repository
+                    .share_wishlist(
+                        group.id(),
+                        SharedWishlist::new(wishlist.id(), owner, Utc::now()),
+                    )
+                    .await
+                    .unwrap();
+            }
+            let mut sender = MockWebhookSender::new();
+            sender
+                .expect_send()
+                .times(1)
+                .returning(|_, _, _| Box::pin(std::future::ready(Ok(200))));
+            let follow_repository = Arc::new(InMemoryFollowRepository::new());
+            let webhook_repository = Arc::new(InMemoryWebhookRepository::new());
+            let clock = Arc::new(ManualClock::new("2027-03-01T12:00:00Z".parse().unwrap()));
+            let service = Service::new(
+                user_repository,
+                wish_repository,
+                group_repository.clone(),
+                follow_repository.clone(),
+                webhook_repository.clone(),
+                Arc::new(sender),
+                clock.clone(),
+            );
+            let bus = WebhookItemEventBus::new(
+                InMemoryItemEventBus::new(),
+                webhook_repository.clone(),
+                clock,
+            );
+            service
+                .register_webhook(&RegisterWebhookRequest::new(
+                    member,
+                    wishlist.id(),
+                    WebhookUrl::new("https://hooks.example/gifts").unwrap(),
+                    WebhookEventKind::ALL.to_vec(),
+                ))
+                .await
+                .unwrap();
+            Self {
+                service,
+                bus,
+                group_repository,
+                follow_repository,
+                webhook_repository,
+                wishlist,
+                group_id: group.id(),
+                owner,
+                member,
+            }
+        }
+
+        /// Publishes an item event and delivers it.
+        async fn publish_and_deliver(&self) {
+            let item = Item::create(
+                Uuid::now_v7(),
+                self.wishlist.id(),
+                "Book".into(),
+                "https://shop.example/book".into(),
+                None,
+                None,
+            );
+            self.bus
+                .publish(&self.wishlist, ItemChange::Added(item))
+                .await
+                .unwrap();
+            self.service.deliver_due_webhooks().await.unwrap();
+        }
+
+        /// Checks the event published before the revocation was delivered, and the webhook
+        /// dropped with the one published after.
+        async fn assert_dropped_after(&self, revoke: impl Future<Output = ()>) {
+            self.publish_and_deliver().await;
+            revoke.await;
+            self.publish_and_deliver().await;
+            assert!(self
+                .webhook_repository
+                .find_webhooks_by_user(self.member)
+                .await
+                .unwrap()
+                .is_empty());
+        }
+    }
+
+    async fn shared_webhook() -> SharedWebhook<InMemoryWishlistRepository> {
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let wishlist = wish_repository
+            .save(&CreateWishlistRequest::new(
+                Uuid::now_v7(),
+                "Birthday".into(),
+                true,
+            ))
+            .await
+            .unwrap();
+        SharedWebhook::register(wish_repository, wishlist, true).await
+    }
+
+    #[tokio::test]
+    async fn test_webhook_dropped_when_wishlist_unshared() {
+        let shared = shared_webhook().await;
+        shared
+            .assert_dropped_after(async {
+                shared
+                    .group_repository
+                    .unshare_wishlist(shared.group_id, shared.wishlist.id())
+                    .await
+                    .unwrap();
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_webhook_dropped_when_owner_leaves_group() {
+        let shared = shared_webhook().await;
+        shared
+            .assert_dropped_after(async {
+                shared
+                    .group_repository
+                    .remove_member(shared.group_id, shared.member)
+                    .await
+                    .unwrap();
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_webhook_dropped_when_owner_blocked() {
+        let shared = shared_webhook().await;
+        shared
+            .assert_dropped_after(async {
+                shared
+                    .follow_repository
+                    .save_block(shared.owner, shared.member, Utc::now())
+                    .await
+                    .unwrap();
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_webhook_dropped_when_wishlist_made_private() {
+        // Wishlists cannot be made private through the repository, so the mock flips it
+        let private = Arc::new(Mutex::new(false));
+        let wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        );
+        let mut wish_repository = MockWishlistRepository::new();
+        let (found, flag) = (wishlist.clone(), private.clone());
+        wish_repository
+            .expect_find_wishlist_by_id()
+            .returning(move |_| {
+                let wishlist = Wishlist::new(
+                    found.id(),
+                    found.owner_id(),
+                    found.name().clone(),
+                    found.slug().clone(),
+                    *flag.lock().unwrap(),
+                );
+                Box::pin(std::future::ready(Ok(Some(wishlist))))
+            });
+        let shared = SharedWebhook::register(Arc::new(wish_repository), wishlist, false).await;
+        shared
+            .assert_dropped_after(async {
+                *private.lock().unwrap() = true;
+            })
+            .await;
+    }
+}
@@ -1,49 +1,152 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
 
 use crate::domain::{
-    CreateWishlistError, CreateWishlistRequest, FindUserByIdRequest, UserRepository, Wishlist,
-    WishlistRepository, WishlistService,
+    CloseOccasionsError, CreateItemRequest, CreateSectionError, CreateSectionRequest,
+    CreateWishlistError, CreateWishlistRequest, DeleteSectionError, DeleteSectionRequest,
+    DuplicateWishlistError, DuplicateWishlistRequest, FindItemByIdRequest, FindUserByIdRequest,
+    FindWishlistByIdRequest, FindWishlistsError, ItemRepository, MoveItemToSectionError,
+    MoveItemToSectionRequest, Occasion, Page, ReorderWishlistError, ReorderWishlistRequest,
+    SetWishlistOccasionError, SetWishlistOccasionRequest, SetWishlistTemplateError,
+    SetWishlistTemplateRequest, ShareLinkError, ShareLinkRequest, ShareToken, UserRepository,
+    Wishlist, WishlistQuery, WishlistRepository, WishlistSection, WishlistService,
 };
 
-pub struct Service<U, W>
+pub struct Service<U, W, I>
 where
     U: UserRepository,
     W: WishlistRepository,
+    I: ItemRepository,
 {
     user_repository: Arc<U>,
     wish_repository: Arc<W>,
+    item_repository: Arc<I>,
 }
 
-impl<U, W> Clone for Service<U, W>
+impl<U, W, I> Clone for Service<U, W, I>
 where
     U: UserRepository,
     W: WishlistRepository,
+    I: ItemRepository,
 {
     fn clone(&self) -> Self {
         Self {
             user_repository: self.user_repository.clone(),
             wish_repository: self.wish_repository.clone(),
+            item_repository: self.item_repository.clone(),
         }
     }
 }
 
-impl<U, W> Service<U, W>
+impl<U, W, I> Service<U, W, I>
 where
     U: UserRepository,
     W: WishlistRepository,
+    I: ItemRepository,
 {
-    pub fn new(user_repository: Arc<U>, wish_repository: Arc<W>) -> Self {
+    pub fn new(user_repository: Arc<U>, wish_repository: Arc<W>, item_repository: Arc<I>) -> Self {
         Self {
             user_repository,
             wish_repository,
+            item_repository,
+        }
+    }
+
+    async fn find_wishlist(&self, id: Uuid) -> anyhow::Result<Option<Wishlist>> {
+        Ok(self
+            .wish_repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(id))
+            .await?)
+    }
+
+    /// Checks that the wishlist of a share link request exists and belongs to the user.
+    async fn check_owner(&self, req: &ShareLinkRequest) -> Result<(), ShareLinkError> {
+        let wishlist = self.find_wishlist(req.wishlist_id()).await?.ok_or(
+            ShareLinkError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        if wishlist.owner_id() != req.user_id() {
+            return Err(ShareLinkError::NotWishlistOwner { id: wishlist.id() });
+        }
+        Ok(())
+    }
+
+    /// Copies the sections and items of `source` into the empty wishlist `copy`.
+    async fn copy_contents(&self, source: &Wishlist, copy: &Wishlist) -> anyhow::Result<()> {
+        let mut sections = HashMap::new();
+        for section in source.sections() {
+            let created = self
+                .wish_repository
+                .create_section(&CreateSectionRequest::new(
+                    copy.id(),
+                    copy.owner_id(),
+                    section.name().clone(),
+                ))
+                .await?;
+            sections.insert(section.id(), created.id());
+        }
+        for entry in source.items() {
+            let Some(item) = self
+                .item_repository
+                .find_item_by_id(&FindItemByIdRequest::new(entry.item_id()))
+                .await?
+            else {
+                continue;
+            };
+            let item = self
+                .item_repository
+                .save(&CreateItemRequest::copy_of(
+                    &item,
+                    copy.id(),
+                    copy.owner_id(),
+                ))
+                .await?;
+            self.wish_repository.add_item(copy.id(), item.id()).await?;
+            if let Some(section_id) = entry.section_id() {
+                self.wish_repository
+                    .move_item_to_section(&MoveItemToSectionRequest::new(
+                        copy.id(),
+                        copy.owner_id(),
+                        item.id(),
+                        sections.get(&section_id).copied(),
+                    ))
+                    .await?;
+            }
         }
+        Ok(())
+    }
+
+    /// Reopens a wishlist for the next occurrence of its occasion, after deleting the items the
+    /// owner received.
+    async fn reopen(&self, wishlist: &Wishlist, next: Occasion) -> anyhow::Result<()> {
+        for entry in wishlist.items() {
+            let item = self
+                .item_repository
+                .find_item_by_id(&FindItemByIdRequest::new(entry.item_id()))
+                .await?;
+            if item.is_some_and(|item| item.received()) {
+                self.wish_repository
+                    .remove_item(wishlist.id(), entry.item_id())
+                    .await?;
+                self.item_repository.delete(entry.item_id()).await?;
+            }
+        }
+        self.wish_repository
+            .set_occasion(wishlist.id(), Some(next), None)
+            .await?;
+        Ok(())
     }
 }
 
-impl<U, W> WishlistService for Service<U, W>
+impl<U, W, I> WishlistService for Service<U, W, I>
 where
     U: UserRepository + Send + Sync + 'static,
     W: WishlistRepository + Send + Sync + 'static,
+    I: ItemRepository + Send + Sync + 'static,
 {
     async fn create_wishlist(
         &self,
@@ -55,13 +158,203 @@ where
             .await
         {
             Ok(Some(_)) => self.wish_repository.save(req).await,
-            Ok(None) => {
-                return Err(CreateWishlistError::OwnerIdDoesNotExist { id: req.owner_id() });
+            Ok(None) => Err(CreateWishlistError::OwnerIdDoesNotExist { id: req.owner_id() }),
+            Err(err) => Err(CreateWishlistError::Unknown(err.into())),
+        }
+    }
+
+    async fn create_section(
+        &self,
+        req: &CreateSectionRequest,
+    ) -> Result<WishlistSection, CreateSectionError> {
+        let wishlist = self.find_wishlist(req.wishlist_id()).await?.ok_or(
+            CreateSectionError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        if wishlist.owner_id() != req.user_id() {
+            return Err(CreateSectionError::NotWishlistOwner { id: wishlist.id() });
+        }
+        self.wish_repository.create_section(req).await
+    }
+
+    async fn delete_section(
+        &self,
+        req: &DeleteSectionRequest,
+    ) -> Result<Wishlist, DeleteSectionError> {
+        let wishlist = self.find_wishlist(req.wishlist_id()).await?.ok_or(
+            DeleteSectionError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        if wishlist.owner_id() != req.user_id() {
+            return Err(DeleteSectionError::NotWishlistOwner { id: wishlist.id() });
+        }
+        self.wish_repository.delete_section(req).await
+    }
+
+    async fn move_item_to_section(
+        &self,
+        req: &MoveItemToSectionRequest,
+    ) -> Result<Wishlist, MoveItemToSectionError> {
+        let wishlist = self.find_wishlist(req.wishlist_id()).await?.ok_or(
+            MoveItemToSectionError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        if wishlist.owner_id() != req.user_id() {
+            return Err(MoveItemToSectionError::NotWishlistOwner { id: wishlist.id() });
+        }
+        self.wish_repository.move_item_to_section(req).await
+    }
+
+    async fn reorder_wishlist(
+        &self,
+        req: &ReorderWishlistRequest,
+    ) -> Result<Wishlist, ReorderWishlistError> {
+        let wishlist = self.find_wishlist(req.wishlist_id()).await?.ok_or(
+            ReorderWishlistError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        if wishlist.owner_id() != req.user_id() {
+            return Err(ReorderWishlistError::NotWishlistOwner { id: wishlist.id() });
+        }
+        self.wish_repository.reorder_wishlist(req).await
+    }
+
+    async fn duplicate_wishlist(
+        &self,
+        req: &DuplicateWishlistRequest,
+    ) -> Result<Wishlist, DuplicateWishlistError> {
+        let source = self.find_wishlist(req.wishlist_id()).await?.ok_or(
+            DuplicateWishlistError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        if !source.can_be_duplicated_by(req.user_id()) {
+            return Err(DuplicateWishlistError::NotAllowed { id: source.id() });
+        }
+        let copy = CreateWishlistRequest::new(
+            req.user_id(),
+            req.name().unwrap_or(source.name()).clone(),
+            req.private().unwrap_or(source.private()),
+        );
+        let copy = self.create_wishlist(&copy).await.map_err(|err| match err {
+            CreateWishlistError::OwnerIdDoesNotExist { id } => {
+                DuplicateWishlistError::UserDoesNotExist { id }
             }
-            Err(err) => {
-                return Err(CreateWishlistError::Unknown(err.into()));
+            err => DuplicateWishlistError::Unkown(err.into()),
+        })?;
+        self.copy_contents(&source, &copy).await?;
+        self.find_wishlist(copy.id())
+            .await?
+            .ok_or(DuplicateWishlistError::Unkown(anyhow!(
+                "wishlist {} disappeared while being copied",
+                copy.id()
+            )))
+    }
+
+    async fn set_wishlist_template(
+        &self,
+        req: &SetWishlistTemplateRequest,
+    ) -> Result<Wishlist, SetWishlistTemplateError> {
+        let wishlist = self.find_wishlist(req.wishlist_id()).await?.ok_or(
+            SetWishlistTemplateError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        if wishlist.owner_id() != req.user_id() {
+            return Err(SetWishlistTemplateError::NotWishlistOwner { id: wishlist.id() });
+        }
+        self.wish_repository
+            .set_template(req.wishlist_id(), req.template(), req.expected_version())
+            .await
+    }
+
+    async fn create_share_link(&self, req: &ShareLinkRequest) -> Result<Wishlist, ShareLinkError> {
+        self.check_owner(req).await?;
+        self.wish_repository
+            .set_share_token(req.wishlist_id(), Some(ShareToken::generate()))
+            .await
+    }
+
+    async fn revoke_share_link(&self, req: &ShareLinkRequest) -> Result<Wishlist, ShareLinkError> {
+        self.check_owner(req).await?;
+        self.wish_repository
+            .set_share_token(req.wishlist_id(), None)
+            .await
+    }
+
+    async fn list_templates(
+        &self,
+        query: &WishlistQuery,
+    ) -> Result<Page<Wishlist>, FindWishlistsError> {
+        self.wish_repository
+            .list_wishlists(
+                &query
+                    .clone()
+                    .narrow(|filter| filter.with_template(Some(true))),
+            )
+            .await
+    }
+
+    async fn set_wishlist_occasion(
+        &self,
+        req: &SetWishlistOccasionRequest,
+    ) -> Result<Wishlist, SetWishlistOccasionError> {
+        let wishlist = self.find_wishlist(req.wishlist_id()).await?.ok_or(
+            SetWishlistOccasionError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        if wishlist.owner_id() != req.user_id() {
+            return Err(SetWishlistOccasionError::NotWishlistOwner { id: wishlist.id() });
+        }
+        let updated = self
+            .wish_repository
+            .set_occasion(
+                wishlist.id(),
+                req.occasion().cloned(),
+                req.expected_version(),
+            )
+            .await?;
+        // A new occasion reopens an archived wishlist; it is archived again if it is already over.
+        if updated.archived() && req.occasion().is_some() {
+            return Ok(self
+                .wish_repository
+                .set_archived(wishlist.id(), false)
+                .await
+                .map_err(|err| anyhow!(err))?);
+        }
+        Ok(updated)
+    }
+
+    async fn close_past_occasions(&self, now: DateTime<Utc>) -> Result<(), CloseOccasionsError> {
+        let wishlists = self
+            .wish_repository
+            .find_past_occasions(now)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        for wishlist in wishlists {
+            match wishlist
+                .occasion()
+                .and_then(|occasion| occasion.next_after(now))
+            {
+                Some(next) => {
+                    tracing::info!(wishlist_id = %wishlist.id(), "reopening wishlist for {}", next.date());
+                    self.reopen(&wishlist, next).await?;
+                }
+                None => {
+                    tracing::info!(wishlist_id = %wishlist.id(), "archiving wishlist");
+                    self.wish_repository
+                        .set_archived(wishlist.id(), true)
+                        .await
+                        .map_err(|err| anyhow!(err))?;
+                }
             }
         }
+        Ok(())
     }
 }
 
@@ -69,10 +362,17 @@ where
 mod tests {
     use std::future;
 
-    use uuid::Uuid;
-
     use super::*;
-    use crate::domain::{MockUserRepository, MockWishlistRepository, User, WishlistService};
+    use crate::{
+        domain::{
+            CreateUserRequest, Item, ItemPriority, MockItemRepository, MockUserRepository,
+            MockWishlistRepository, OccasionKind, Recurrence, User, WishlistService,
+        },
+        infrastructure::persistence::in_memory::{
+            item::InMemoryItemRepository, user::InMemoryUserRepository,
+            wishlist::InMemoryWishlistRepository,
+        },
+    };
 
     #[tokio::test]
     async fn test_create_wishlist() {
@@ -92,8 +392,268 @@ mod tests {
                 true,
             ))))
         });
-        let wish_service = Service::new(Arc::new(user_mock_repo), Arc::new(wish_mock_repo));
+        let wish_service = Service::new(
+            Arc::new(user_mock_repo),
+            Arc::new(wish_mock_repo),
+            Arc::new(MockItemRepository::new()),
+        );
         let result = wish_service.create_wishlist(&req).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_duplicate_wishlist() {
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let service = Service::new(
+            user_repository.clone(),
+            wish_repository.clone(),
+            item_repository.clone(),
+        );
+        let mut users = Vec::new();
+        for email in ["alice@example.com", "bob@example.com"] {
+            let req = CreateUserRequest::new(email.into(), "password".into());
+            users.push(*user_repository.save(&req).await.unwrap().id());
+        }
+        let (owner, other) = (users[0], users[1]);
+        let source = service
+            .create_wishlist(&CreateWishlistRequest::new(owner, "Baby".into(), false))
+            .await
+            .unwrap();
+        let section = service
+            .create_section(&CreateSectionRequest::new(
+                source.id(),
+                owner,
+                "Sleep".into(),
+            ))
+            .await
+            .unwrap();
+        let mut item_ids = Vec::new();
+        for path in ["cot", "bottle"] {
+            let req = CreateItemRequest::new(
+                source.id(),
+                owner,
+                Some(path.into()),
+                format!("https://shop.example/{}", path).as_str().into(),
+                None,
+                None,
+            )
+            .with_priority(ItemPriority::MustHave);
+            let item = item_repository.save(&req).await.unwrap();
+            wish_repository
+                .add_item(source.id(), item.id())
+                .await
+                .unwrap();
+            item_ids.push(item.id());
+        }
+        service
+            .reorder_wishlist(&ReorderWishlistRequest::new(
+                source.id(),
+                owner,
+                vec![item_ids[1], item_ids[0]],
+                vec![],
+            ))
+            .await
+            .unwrap();
+        service
+            .move_item_to_section(&MoveItemToSectionRequest::new(
+                source.id(),
+                owner,
+                item_ids[0],
+                Some(section.id()),
+            ))
+            .await
+            .unwrap();
+
+        let result = service
+            .duplicate_wishlist(&DuplicateWishlistRequest::new(source.id(), other))
+            .await;
+        assert!(matches!(
+            result,
+            Err(DuplicateWishlistError::NotAllowed { .. })
+        ));
+        let result = service
+            .reorder_wishlist(&ReorderWishlistRequest::new(
+                source.id(),
+                other,
+                vec![item_ids[0]],
+                vec![],
+            ))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ReorderWishlistError::NotWishlistOwner { .. })
+        ));
+
+        let result = service
+            .create_share_link(&ShareLinkRequest::new(source.id(), other))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ShareLinkError::NotWishlistOwner { .. })
+        ));
+        let shared = service
+            .create_share_link(&ShareLinkRequest::new(source.id(), owner))
+            .await
+            .unwrap();
+        let token = shared.share_token().unwrap().clone();
+        let reshared = service
+            .create_share_link(&ShareLinkRequest::new(source.id(), owner))
+            .await
+            .unwrap();
+        assert_ne!(reshared.share_token(), Some(&token));
+        let revoked = service
+            .revoke_share_link(&ShareLinkRequest::new(source.id(), owner))
+            .await
+            .unwrap();
+        assert_eq!(revoked.share_token(), None);
+
+        service
+            .set_wishlist_template(&SetWishlistTemplateRequest::new(source.id(), owner, true))
+            .await
+            .unwrap();
+        let copy = service
+            .duplicate_wishlist(
+                &DuplicateWishlistRequest::new(source.id(), other).with_name(Some("Ours".into())),
+            )
+            .await
+            .unwrap();
+        assert_eq!(copy.owner_id(), other);
+        assert_eq!(copy.name(), &"Ours".into());
+        assert_ne!(copy.slug(), source.slug());
+        assert!(!copy.template());
+        assert_eq!(copy.sections().len(), 1);
+        assert_ne!(copy.sections()[0].id(), section.id());
+        let copied: Vec<Item> = {
+            let mut copied = Vec::new();
+            for entry in copy.items() {
+                let req = FindItemByIdRequest::new(entry.item_id());
+                copied.push(
+                    item_repository
+                        .find_item_by_id(&req)
+                        .await
+                        .unwrap()
+                        .unwrap(),
+                );
+            }
+            copied
+        };
+        let titles: Vec<String> = copied.iter().map(|item| item.title().to_string()).collect();
+        assert_eq!(titles, ["bottle", "cot"]);
+        assert!(copied.iter().all(|item| !item_ids.contains(&item.id())));
+        assert!(copied
+            .iter()
+            .all(|item| item.priority() == ItemPriority::MustHave));
+        assert_eq!(copy.section_of(copied[1].id()), Some(&copy.sections()[0]));
+    }
+
+    #[tokio::test]
+    async fn test_close_past_occasions() {
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let wish_repository = Arc::new(InMemoryWishlistRepository::new());
+        let item_repository = Arc::new(InMemoryItemRepository::new());
+        let service = Service::new(
+            user_repository.clone(),
+            wish_repository.clone(),
+            item_repository.clone(),
+        );
+        let req = CreateUserRequest::new("alice@example.com".into(), "password".into());
+        let owner = *user_repository.save(&req).await.unwrap().id();
+        let occasion = |kind, date: &str, recurrence| {
+            Some(Occasion::new(
+                kind,
+                date.parse().unwrap(),
+                chrono_tz::Europe::Paris,
+                recurrence,
+            ))
+        };
+        let mut wishlists = Vec::new();
+        for (name, occasion) in [
+            (
+                "Wedding",
+                occasion(OccasionKind::Wedding, "2027-06-12", None),
+            ),
+            (
+                "Birthday",
+                occasion(
+                    OccasionKind::Birthday,
+                    "2027-06-10",
+                    Some(Recurrence::Yearly),
+                ),
+            ),
+            (
+                "Christmas",
+                occasion(
+                    OccasionKind::Christmas,
+                    "2027-12-25",
+                    Some(Recurrence::Yearly),
+                ),
+            ),
+        ] {
+            let wishlist = service
+                .create_wishlist(&CreateWishlistRequest::new(owner, name.into(), false))
+                .await
+                .unwrap();
+            service
+                .set_wishlist_occasion(&SetWishlistOccasionRequest::new(
+                    wishlist.id(),
+                    owner,
+                    occasion,
+                ))
+                .await
+                .unwrap();
+            wishlists.push(wishlist.id());
+        }
+        let (wedding, birthday, christmas) = (wishlists[0], wishlists[1], wishlists[2]);
+        let mut item_ids = Vec::new();
+        for path in ["book", "game"] {
+            let req = CreateItemRequest::new(
+                birthday,
+                owner,
+                Some(path.into()),
+                format!("https://shop.example/{}", path).as_str().into(),
+                None,
+                None,
+            );
+            let item = item_repository.save(&req).await.unwrap();
+            wish_repository.add_item(birthday, item.id()).await.unwrap();
+            item_ids.push(item.id());
+        }
+        let mut received = item_repository
+            .find_item_by_id(&FindItemByIdRequest::new(item_ids[0]))
+            .await
+            .unwrap()
+            .unwrap();
+        received.set_received(true);
+        item_repository.update(&received).await.unwrap();
+
+        service
+            .close_past_occasions("2027-06-12T22:00:00Z".parse().unwrap())
+            .await
+            .unwrap();
+
+        let find = |id| service.find_wishlist(id);
+        let wedding = find(wedding).await.unwrap().unwrap();
+        assert!(wedding.archived());
+        let birthday = find(birthday).await.unwrap().unwrap();
+        assert!(!birthday.archived());
+        assert_eq!(
+            birthday.occasion().unwrap().date(),
+            "2028-06-10".parse().unwrap()
+        );
+        assert!(!birthday.contains_item(item_ids[0]));
+        assert!(birthday.contains_item(item_ids[1]));
+        let deleted = item_repository
+            .find_item_by_id(&FindItemByIdRequest::new(item_ids[0]))
+            .await
+            .unwrap();
+        assert!(deleted.is_none());
+        let christmas = find(christmas).await.unwrap().unwrap();
+        assert!(!christmas.archived());
+        assert_eq!(
+            christmas.occasion().unwrap().date(),
+            "2027-12-25".parse().unwrap()
+        );
+    }
 }
@@ -0,0 +1,380 @@
+mod repository;
+mod service;
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use uuid::Uuid;
+
+pub use repository::*;
+pub use service::*;
+
+use super::{Item, Profile, User, Wishlist};
+
+/// The grace period of an [AccountDeletion] when none is configured.
+pub const DEFAULT_DELETION_GRACE_PERIOD: Duration = Duration::days(30);
+
+/// How long an [AccountConfirmation] can be used when no lifetime is configured.
+pub const DEFAULT_CONFIRMATION_TTL: Duration = Duration::minutes(30);
+
+/// A request of a user to delete their account. Their data is only purged at `purge_at`, and the
+/// deletion can be cancelled until then.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountDeletion {
+    user_id: Uuid,
+    requested_at: DateTime<Utc>,
+    purge_at: DateTime<Utc>,
+}
+
+impl AccountDeletion {
+    pub fn new(user_id: Uuid, requested_at: DateTime<Utc>, grace_period: Duration) -> Self {
+        Self {
+            user_id,
+            requested_at,
+            purge_at: requested_at + grace_period,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn requested_at(&self) -> DateTime<Utc> {
+        self.requested_at
+    }
+
+    pub fn purge_at(&self) -> DateTime<Utc> {
+        self.purge_at
+    }
+
+    /// Whether the grace period is over at `now`.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.purge_at <= now
+    }
+}
+
+/// The personal data of a user: their account and profile, their wishlists and the items in
+/// them, and the items they reserved on the wishlists of others.
+#[derive(Debug, Clone)]
+pub struct AccountExport {
+    user: User,
+    profile: Profile,
+    wishlists: Vec<Wishlist>,
+    items: Vec<Item>,
+    reservations: Vec<Item>,
+    deletion: Option<AccountDeletion>,
+    exported_at: DateTime<Utc>,
+}
+
+impl AccountExport {
+    pub fn new(
+        user: User,
+        profile: Profile,
+        wishlists: Vec<Wishlist>,
+        items: Vec<Item>,
+        reservations: Vec<Item>,
+        deletion: Option<AccountDeletion>,
+        exported_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            user,
+            profile,
+            wishlists,
+            items,
+            reservations,
+            deletion,
+            exported_at,
+        }
+    }
+
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    pub fn wishlists(&self) -> &[Wishlist] {
+        &self.wishlists
+    }
+
+    /// The items of the user's wishlists.
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// The items the user reserved on the wishlists of others.
+    pub fn reservations(&self) -> &[Item] {
+        &self.reservations
+    }
+
+    /// The pending deletion of the account, if any.
+    pub fn deletion(&self) -> Option<&AccountDeletion> {
+        self.deletion.as_ref()
+    }
+
+    pub fn exported_at(&self) -> DateTime<Utc> {
+        self.exported_at
+    }
+}
+
+/// The actions on an account that only its owner may take, after confirming them with a token
+/// emailed to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccountAction {
+    Export,
+    Delete,
+    CancelDeletion,
+}
+
+impl AccountAction {
+    /// What the action does, as shown in the confirmation email.
+    pub fn description(&self) -> &'static str {
+        match self {
+            AccountAction::Export => "export the data of your account",
+            AccountAction::Delete => "delete your account",
+            AccountAction::CancelDeletion => "cancel the deletion of your account",
+        }
+    }
+}
+
+impl Display for AccountAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AccountAction::Export => "export",
+            AccountAction::Delete => "delete",
+            AccountAction::CancelDeletion => "cancel_deletion",
+        })
+    }
+}
+
+impl FromStr for AccountAction {
+    type Err = AccountActionInvalidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "export" => Ok(AccountAction::Export),
+            "delete" => Ok(AccountAction::Delete),
+            "cancel_deletion" => Ok(AccountAction::CancelDeletion),
+            _ => Err(AccountActionInvalidError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Action {0} is invalid")]
+pub struct AccountActionInvalidError(pub String);
+
+/// The [ConfirmationToken] struct holds the secret emailed to a user to confirm an
+/// [AccountAction]. Only its digest is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmationToken(String);
+
+impl ConfirmationToken {
+    /// Generates a new random token.
+    pub fn generate() -> Self {
+        Self(format!(
+            "{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        ))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The SHA-256 digest of the token, in hexadecimal.
+    pub fn digest(&self) -> String {
+        format!("{:x}", Sha256::digest(self.0.as_bytes()))
+    }
+}
+
+impl From<&str> for ConfirmationToken {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl Display for ConfirmationToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A pending confirmation of an [AccountAction]: the digest of the token emailed to the user,
+/// which can be used once until `expires_at`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountConfirmation {
+    user_id: Uuid,
+    action: AccountAction,
+    digest: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl AccountConfirmation {
+    pub fn new(
+        user_id: Uuid,
+        action: AccountAction,
+        token: &ConfirmationToken,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            user_id,
+            action,
+            digest: token.digest(),
+            expires_at,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn action(&self) -> AccountAction {
+        self.action
+    }
+
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    /// Whether the confirmation has expired at `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// The [AccountConfirmationRequest] struct represents a request of a user to be emailed a token
+/// confirming an [AccountAction].
+#[derive(Debug, Clone)]
+pub struct AccountConfirmationRequest {
+    user_id: Uuid,
+    action: AccountAction,
+}
+
+impl AccountConfirmationRequest {
+    pub fn new(user_id: Uuid, action: AccountAction) -> Self {
+        Self { user_id, action }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn action(&self) -> AccountAction {
+        self.action
+    }
+}
+
+/// The [ConfirmedAccountRequest] struct represents a request of a user about their own account,
+/// with the token they were emailed to confirm it.
+#[derive(Debug, Clone)]
+pub struct ConfirmedAccountRequest {
+    user_id: Uuid,
+    token: ConfirmationToken,
+}
+
+impl ConfirmedAccountRequest {
+    pub fn new(user_id: Uuid, token: ConfirmationToken) -> Self {
+        Self { user_id, token }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn token(&self) -> &ConfirmationToken {
+        &self.token
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RequestAccountConfirmationError {
+    #[error("User ID {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("User ID {id} has no email address to send a confirmation to")]
+    NoEmail { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum TakeAccountConfirmationError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ExportAccountError {
+    #[error("User ID {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("The confirmation token of user ID {id} is invalid or expired")]
+    InvalidConfirmation { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum DeleteAccountError {
+    #[error("User ID {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("The confirmation token of user ID {id} is invalid or expired")]
+    InvalidConfirmation { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum CancelAccountDeletionError {
+    #[error("The confirmation token of user ID {id} is invalid or expired")]
+    InvalidConfirmation { id: Uuid },
+    #[error("The account of user ID {id} is not being deleted")]
+    NotScheduled { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FindAccountDeletionError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum PurgeAccountsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The error of erasing the data of a user from a repository.
+#[derive(Debug, Error)]
+pub enum ForgetUserError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deletion_is_due_after_grace_period() {
+        let requested_at: DateTime<Utc> = "2027-01-01T12:00:00Z".parse().unwrap();
+        let deletion = AccountDeletion::new(Uuid::now_v7(), requested_at, Duration::days(30));
+        assert_eq!(
+            deletion.purge_at(),
+            "2027-01-31T12:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert!(!deletion.is_due(requested_at + Duration::days(29)));
+        assert!(deletion.is_due(requested_at + Duration::days(30)));
+    }
+}
@@ -0,0 +1,72 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    AccountAction, AccountConfirmation, AccountDeletion, CancelAccountDeletionError,
+    DeleteAccountError, FindAccountDeletionError, PurgeAccountsError,
+    RequestAccountConfirmationError, TakeAccountConfirmationError,
+};
+
+#[cfg(test)]
+use mockall::automock;
+
+/// The [AccountRepository] trait defines the contract for the pending account deletions, and the
+/// confirmations of the actions on accounts.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait AccountRepository: Send + Sync + 'static {
+    /// Saves a deletion. Requesting it again keeps the existing one, and its grace period.
+    ///
+    /// # Errors
+    /// - [DeleteAccountError::Unkown] for any errors that may occur.
+    fn save_deletion(
+        &self,
+        deletion: &AccountDeletion,
+    ) -> impl Future<Output = Result<AccountDeletion, DeleteAccountError>> + Send;
+    /// Finds the pending deletion of the account of a user.
+    ///
+    /// # Errors
+    /// - [FindAccountDeletionError::Unkown] for any errors that may occur.
+    fn find_deletion(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<AccountDeletion>, FindAccountDeletionError>> + Send;
+    /// Deletes the pending deletion of the account of a user.
+    ///
+    /// # Errors
+    /// - [CancelAccountDeletionError::NotScheduled] if the account is not being deleted.
+    /// - [CancelAccountDeletionError::Unkown] for any other errors that may occur.
+    fn delete_deletion(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<AccountDeletion, CancelAccountDeletionError>> + Send;
+    /// Finds the deletions whose grace period is over at `now`.
+    ///
+    /// # Errors
+    /// - [PurgeAccountsError::Unkown] for any errors that may occur.
+    fn find_due_deletions(
+        &self,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<AccountDeletion>, PurgeAccountsError>> + Send;
+    /// Saves a confirmation, replacing the one of the same user and action.
+    ///
+    /// # Errors
+    /// - [RequestAccountConfirmationError::Unkown] for any errors that may occur.
+    fn save_confirmation(
+        &self,
+        confirmation: &AccountConfirmation,
+    ) -> impl Future<Output = Result<(), RequestAccountConfirmationError>> + Send;
+    /// Removes and returns the confirmation of `action` by a user if its digest is `digest`, so
+    /// that it can only be used once. A confirmation with another digest is kept.
+    ///
+    /// # Errors
+    /// - [TakeAccountConfirmationError::Unkown] for any errors that may occur.
+    fn take_confirmation(
+        &self,
+        user_id: Uuid,
+        action: AccountAction,
+        digest: &str,
+    ) -> impl Future<Output = Result<Option<AccountConfirmation>, TakeAccountConfirmationError>> + Send;
+}
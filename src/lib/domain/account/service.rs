@@ -0,0 +1,72 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+
+use super::{
+    AccountConfirmation, AccountConfirmationRequest, AccountDeletion, AccountExport,
+    CancelAccountDeletionError, ConfirmedAccountRequest, DeleteAccountError, ExportAccountError,
+    PurgeAccountsError, RequestAccountConfirmationError,
+};
+
+/// The [AccountService] trait defines the contract for the personal data of users: exporting it,
+/// and deleting it after a grace period. Each step must be confirmed with a token emailed to the
+/// user, including cancelling a deletion.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait AccountService: Send + Sync + 'static {
+    /// Emails a user a token confirming an action on their account, valid once for a limited
+    /// time. Asking again replaces the previous token of the action.
+    ///
+    /// # Errors
+    /// - [RequestAccountConfirmationError::UserDoesNotExist] if the user does not exist.
+    /// - [RequestAccountConfirmationError::NoEmail] if the user is anonymous.
+    /// - [RequestAccountConfirmationError::Unkown] for any other errors that may occur.
+    fn request_account_confirmation(
+        &self,
+        req: &AccountConfirmationRequest,
+    ) -> impl Future<Output = Result<AccountConfirmation, RequestAccountConfirmationError>> + Send;
+    /// Exports all the personal data of a user, which uses up their export confirmation.
+    ///
+    /// # Errors
+    /// - [ExportAccountError::InvalidConfirmation] if the token does not confirm an export, or
+    ///   expired.
+    /// - [ExportAccountError::UserDoesNotExist] if the user does not exist.
+    /// - [ExportAccountError::Unkown] for any other errors that may occur.
+    fn export_account(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> impl Future<Output = Result<AccountExport, ExportAccountError>> + Send;
+    /// Schedules the deletion of the account of a user at the end of the grace period, which uses
+    /// up their deletion confirmation. Asking again returns the already scheduled deletion.
+    ///
+    /// # Errors
+    /// - [DeleteAccountError::InvalidConfirmation] if the token does not confirm a deletion, or
+    ///   expired.
+    /// - [DeleteAccountError::UserDoesNotExist] if the user does not exist.
+    /// - [DeleteAccountError::Unkown] for any other errors that may occur.
+    fn delete_account(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> impl Future<Output = Result<AccountDeletion, DeleteAccountError>> + Send;
+    /// Cancels the deletion of the account of a user during its grace period, which uses up their
+    /// cancellation confirmation.
+    ///
+    /// # Errors
+    /// - [CancelAccountDeletionError::InvalidConfirmation] if the token does not confirm a
+    ///   cancellation, or expired.
+    /// - [CancelAccountDeletionError::NotScheduled] if the account is not being deleted.
+    /// - [CancelAccountDeletionError::Unkown] for any other errors that may occur.
+    fn cancel_account_deletion(
+        &self,
+        req: &ConfirmedAccountRequest,
+    ) -> impl Future<Output = Result<AccountDeletion, CancelAccountDeletionError>> + Send;
+    /// Deletes or anonymises the data of every account whose grace period is over, across all
+    /// the repositories. Run periodically.
+    ///
+    /// # Errors
+    /// - [PurgeAccountsError::Unkown] if the data cannot be read or erased; the accounts not
+    ///   purged yet are purged on the next run.
+    fn purge_deleted_accounts(&self)
+        -> impl Future<Output = Result<(), PurgeAccountsError>> + Send;
+}
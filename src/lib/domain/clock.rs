@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+
+/// The [Clock] trait is the source of the current time for time-based schedules, so they can be
+/// run against a clock controlled by tests.
+#[cfg_attr(test, automock)]
+pub trait Clock: Send + Sync + 'static {
+    /// Returns the current instant.
+    fn now(&self) -> DateTime<Utc>;
+}
@@ -0,0 +1,605 @@
+mod draw;
+mod repository;
+mod service;
+
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+pub use draw::*;
+pub use repository::*;
+pub use service::*;
+
+use super::Wishlist;
+
+/// A gift exchange, such as a Secret Santa: every participant gives a gift to exactly one other
+/// participant, drawn at random while respecting the exclusions.
+#[derive(Debug, Clone)]
+pub struct Exchange {
+    id: Uuid,
+    organizer_id: Uuid,
+    name: ExchangeName,
+    participants: Vec<Participant>,
+    exclusions: Vec<Exclusion>,
+    draw: Option<Draw>,
+}
+
+impl Exchange {
+    pub fn new(id: Uuid, organizer_id: Uuid, name: ExchangeName) -> Self {
+        Self {
+            id,
+            organizer_id,
+            name,
+            participants: Vec::new(),
+            exclusions: Vec::new(),
+            draw: None,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn organizer_id(&self) -> Uuid {
+        self.organizer_id
+    }
+
+    pub fn name(&self) -> &ExchangeName {
+        &self.name
+    }
+
+    pub fn participants(&self) -> &[Participant] {
+        &self.participants
+    }
+
+    pub fn exclusions(&self) -> &[Exclusion] {
+        &self.exclusions
+    }
+
+    pub fn draw(&self) -> Option<&Draw> {
+        self.draw.as_ref()
+    }
+
+    pub fn participant(&self, user_id: Uuid) -> Option<&Participant> {
+        self.participants
+            .iter()
+            .find(|participant| participant.user_id == user_id)
+    }
+
+    /// Adds a participant, or replaces the wishlist of an existing one.
+    pub fn add_participant(&mut self, participant: Participant) {
+        match self
+            .participants
+            .iter_mut()
+            .find(|existing| existing.user_id == participant.user_id)
+        {
+            Some(existing) => *existing = participant,
+            None => self.participants.push(participant),
+        }
+    }
+
+    /// Adds an exclusion unless the exchange already has it.
+    pub fn add_exclusion(&mut self, exclusion: Exclusion) {
+        if !self.exclusions.contains(&exclusion) {
+            self.exclusions.push(exclusion);
+        }
+    }
+
+    pub fn set_draw(&mut self, draw: Draw) {
+        self.draw = Some(draw);
+    }
+
+    /// Replaces `user_id` by `alias` wherever the exchange refers to them, and drops their
+    /// wishlist. The draw stays reproducible, as the order of the participants is kept.
+    pub fn anonymise(&mut self, user_id: Uuid, alias: Uuid) {
+        let replace = |id: &mut Uuid| {
+            if *id == user_id {
+                *id = alias;
+            }
+        };
+        replace(&mut self.organizer_id);
+        for participant in self.participants.iter_mut() {
+            if participant.user_id == user_id {
+                *participant = Participant::new(alias, None);
+            }
+        }
+        for exclusion in self.exclusions.iter_mut() {
+            replace(&mut exclusion.giver_id);
+            replace(&mut exclusion.recipient_id);
+        }
+        if let Some(draw) = self.draw.as_mut() {
+            for assignment in draw.assignments.iter_mut() {
+                replace(&mut assignment.giver_id);
+                replace(&mut assignment.recipient_id);
+            }
+        }
+    }
+
+    /// Draws the recipients of the participants from `seed`. The same participants, exclusions
+    /// and seed always give the same draw.
+    ///
+    /// # Errors
+    /// - [DrawError::TooFewParticipants] if the exchange has less than two participants.
+    /// - [DrawError::NoValidDraw] if the exclusions leave no way for everyone to give and receive
+    ///   exactly one gift.
+    pub fn draw_from(&self, seed: u64, drawn_at: DateTime<Utc>) -> Result<Draw, DrawError> {
+        let givers: Vec<Uuid> = self.participants.iter().map(Participant::user_id).collect();
+        let assignments = draw_assignments(&givers, &self.exclusions, seed)?;
+        Ok(Draw::new(seed, assignments, drawn_at))
+    }
+
+    /// Whether drawing again from the stored seed gives the stored assignments.
+    pub fn is_draw_reproducible(&self) -> bool {
+        let Some(draw) = &self.draw else {
+            return false;
+        };
+        self.draw_from(draw.seed(), draw.drawn_at())
+            .is_ok_and(|redrawn| redrawn.assignments() == draw.assignments())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExchangeName(String);
+
+impl From<&str> for ExchangeName {
+    fn from(value: &str) -> Self {
+        ExchangeName(value.to_string())
+    }
+}
+
+impl Display for ExchangeName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl ExchangeName {
+    pub fn new(name: &str) -> Result<Self, ExchangeNameInvalidError> {
+        if name.trim().is_empty() {
+            return Err(ExchangeNameInvalidError {
+                invalid_name: ExchangeName(name.to_string()),
+            });
+        }
+        Ok(ExchangeName(name.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Name is invalid")]
+pub struct ExchangeNameInvalidError {
+    pub invalid_name: ExchangeName,
+}
+
+/// A participant of an [Exchange], with the wishlist their giver gets to see.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Participant {
+    user_id: Uuid,
+    wishlist_id: Option<Uuid>,
+}
+
+impl Participant {
+    pub fn new(user_id: Uuid, wishlist_id: Option<Uuid>) -> Self {
+        Self {
+            user_id,
+            wishlist_id,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn wishlist_id(&self) -> Option<Uuid> {
+        self.wishlist_id
+    }
+}
+
+/// A pair that must not be drawn: `giver_id` never gives to `recipient_id`. Couples exclude
+/// each other both ways, while last year's pairs only need to be excluded one way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Exclusion {
+    giver_id: Uuid,
+    recipient_id: Uuid,
+}
+
+impl Exclusion {
+    pub fn new(giver_id: Uuid, recipient_id: Uuid) -> Self {
+        Self {
+            giver_id,
+            recipient_id,
+        }
+    }
+
+    pub fn giver_id(&self) -> Uuid {
+        self.giver_id
+    }
+
+    pub fn recipient_id(&self) -> Uuid {
+        self.recipient_id
+    }
+}
+
+/// The outcome of drawing an [Exchange], along with the seed it was drawn from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draw {
+    seed: u64,
+    assignments: Vec<Assignment>,
+    drawn_at: DateTime<Utc>,
+}
+
+impl Draw {
+    pub fn new(seed: u64, assignments: Vec<Assignment>, drawn_at: DateTime<Utc>) -> Self {
+        Self {
+            seed,
+            assignments,
+            drawn_at,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    pub fn drawn_at(&self) -> DateTime<Utc> {
+        self.drawn_at
+    }
+
+    /// The participant `giver_id` gives a gift to.
+    pub fn recipient_of(&self, giver_id: Uuid) -> Option<Uuid> {
+        self.assignments
+            .iter()
+            .find(|assignment| assignment.giver_id == giver_id)
+            .map(Assignment::recipient_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment {
+    giver_id: Uuid,
+    recipient_id: Uuid,
+}
+
+impl Assignment {
+    pub fn new(giver_id: Uuid, recipient_id: Uuid) -> Self {
+        Self {
+            giver_id,
+            recipient_id,
+        }
+    }
+
+    pub fn giver_id(&self) -> Uuid {
+        self.giver_id
+    }
+
+    pub fn recipient_id(&self) -> Uuid {
+        self.recipient_id
+    }
+}
+
+/// What a participant learns of a drawn [Exchange]: who they give to, and what that person
+/// wishes for.
+#[derive(Debug, Clone)]
+pub struct Reveal {
+    recipient_id: Uuid,
+    wishlist: Option<Wishlist>,
+}
+
+impl Reveal {
+    pub fn new(recipient_id: Uuid, wishlist: Option<Wishlist>) -> Self {
+        Self {
+            recipient_id,
+            wishlist,
+        }
+    }
+
+    pub fn recipient_id(&self) -> Uuid {
+        self.recipient_id
+    }
+
+    pub fn wishlist(&self) -> Option<&Wishlist> {
+        self.wishlist.as_ref()
+    }
+}
+
+/// The result of auditing the draw of an [Exchange]. It never includes the assignments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawAudit {
+    seed: u64,
+    drawn_at: DateTime<Utc>,
+    reproducible: bool,
+}
+
+impl DrawAudit {
+    pub fn new(seed: u64, drawn_at: DateTime<Utc>, reproducible: bool) -> Self {
+        Self {
+            seed,
+            drawn_at,
+            reproducible,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn drawn_at(&self) -> DateTime<Utc> {
+        self.drawn_at
+    }
+
+    pub fn reproducible(&self) -> bool {
+        self.reproducible
+    }
+}
+
+/// The [CreateExchangeRequest] struct represents a request to create a new [Exchange].
+#[derive(Debug, Clone)]
+pub struct CreateExchangeRequest {
+    organizer_id: Uuid,
+    name: ExchangeName,
+}
+
+impl CreateExchangeRequest {
+    pub fn new(organizer_id: Uuid, name: ExchangeName) -> Self {
+        Self { organizer_id, name }
+    }
+
+    pub fn organizer_id(&self) -> Uuid {
+        self.organizer_id
+    }
+
+    pub fn name(&self) -> &ExchangeName {
+        &self.name
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CreateExchangeError {
+    #[error("User with id {id} does not exist")]
+    OrganizerDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [AddParticipantRequest] struct represents a request by `user_id` to add `participant` to
+/// an [Exchange]. Organizers add anyone, other users only add themselves.
+#[derive(Debug, Clone)]
+pub struct AddParticipantRequest {
+    exchange_id: Uuid,
+    user_id: Uuid,
+    participant: Participant,
+}
+
+impl AddParticipantRequest {
+    pub fn new(exchange_id: Uuid, user_id: Uuid, participant: Participant) -> Self {
+        Self {
+            exchange_id,
+            user_id,
+            participant,
+        }
+    }
+
+    pub fn exchange_id(&self) -> Uuid {
+        self.exchange_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn participant(&self) -> Participant {
+        self.participant
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AddParticipantError {
+    #[error("Exchange with id {id} does not exist")]
+    ExchangeDoesNotExist { id: Uuid },
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Only the organizer of exchange {id} can add other participants")]
+    NotOrganizer { id: Uuid },
+    #[error("Exchange with id {id} has already been drawn")]
+    AlreadyDrawn { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [AddExclusionRequest] struct represents a request by the organizer of an [Exchange] to
+/// exclude a pair, in both directions when `mutual`.
+#[derive(Debug, Clone)]
+pub struct AddExclusionRequest {
+    exchange_id: Uuid,
+    user_id: Uuid,
+    exclusion: Exclusion,
+    mutual: bool,
+}
+
+impl AddExclusionRequest {
+    pub fn new(exchange_id: Uuid, user_id: Uuid, exclusion: Exclusion, mutual: bool) -> Self {
+        Self {
+            exchange_id,
+            user_id,
+            exclusion,
+            mutual,
+        }
+    }
+
+    pub fn exchange_id(&self) -> Uuid {
+        self.exchange_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn exclusion(&self) -> Exclusion {
+        self.exclusion
+    }
+
+    pub fn mutual(&self) -> bool {
+        self.mutual
+    }
+
+    /// The exclusions to add to the [Exchange].
+    pub fn exclusions(&self) -> Vec<Exclusion> {
+        let mut exclusions = vec![self.exclusion];
+        if self.mutual {
+            exclusions.push(Exclusion::new(
+                self.exclusion.recipient_id,
+                self.exclusion.giver_id,
+            ));
+        }
+        exclusions
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AddExclusionError {
+    #[error("Exchange with id {id} does not exist")]
+    ExchangeDoesNotExist { id: Uuid },
+    #[error("Only the organizer of exchange {id} can add exclusions")]
+    NotOrganizer { id: Uuid },
+    #[error("User with id {id} does not participate in the exchange")]
+    NotParticipant { id: Uuid },
+    #[error("A participant cannot be excluded from themselves")]
+    SameParticipant,
+    #[error("Exchange with id {id} has already been drawn")]
+    AlreadyDrawn { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [ExchangeActionRequest] struct represents a request by `user_id` about an [Exchange]:
+/// drawing it, revealing their recipient or auditing the draw.
+#[derive(Debug, Clone)]
+pub struct ExchangeActionRequest {
+    exchange_id: Uuid,
+    user_id: Uuid,
+}
+
+impl ExchangeActionRequest {
+    pub fn new(exchange_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            exchange_id,
+            user_id,
+        }
+    }
+
+    pub fn exchange_id(&self) -> Uuid {
+        self.exchange_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DrawExchangeError {
+    #[error("Exchange with id {id} does not exist")]
+    ExchangeDoesNotExist { id: Uuid },
+    #[error("Only the organizer of exchange {id} can draw it")]
+    NotOrganizer { id: Uuid },
+    #[error("Exchange with id {id} has already been drawn")]
+    AlreadyDrawn { id: Uuid },
+    #[error(transparent)]
+    Invalid(#[from] DrawError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RevealRecipientError {
+    #[error("Exchange with id {id} does not exist")]
+    ExchangeDoesNotExist { id: Uuid },
+    #[error("User with id {id} does not participate in the exchange")]
+    NotParticipant { id: Uuid },
+    #[error("Exchange with id {id} has not been drawn yet")]
+    NotDrawn { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum AuditDrawError {
+    #[error("Exchange with id {id} does not exist")]
+    ExchangeDoesNotExist { id: Uuid },
+    #[error("Only the organizer of exchange {id} can audit its draw")]
+    NotOrganizer { id: Uuid },
+    #[error("Exchange with id {id} has not been drawn yet")]
+    NotDrawn { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draw_is_reproducible_from_seed() {
+        let mut exchange = Exchange::new(Uuid::now_v7(), Uuid::now_v7(), "Office".into());
+        for _ in 0..6 {
+            exchange.add_participant(Participant::new(Uuid::now_v7(), None));
+        }
+        let draw = exchange.draw_from(42, Utc::now()).unwrap();
+        assert_eq!(exchange.draw_from(42, draw.drawn_at()).unwrap(), draw);
+        assert!(!exchange.is_draw_reproducible());
+
+        exchange.set_draw(draw.clone());
+        assert!(exchange.is_draw_reproducible());
+
+        let mut tampered = exchange.clone();
+        let mut assignments = draw.assignments().to_vec();
+        assignments.swap(0, 1);
+        tampered.set_draw(Draw::new(draw.seed(), assignments, draw.drawn_at()));
+        assert!(!tampered.is_draw_reproducible());
+    }
+
+    #[test]
+    fn add_participant_replaces_wishlist() {
+        let mut exchange = Exchange::new(Uuid::now_v7(), Uuid::now_v7(), "Family".into());
+        let user_id = Uuid::now_v7();
+        let wishlist_id = Uuid::now_v7();
+        exchange.add_participant(Participant::new(user_id, None));
+        exchange.add_participant(Participant::new(user_id, Some(wishlist_id)));
+        assert_eq!(
+            exchange.participants(),
+            [Participant::new(user_id, Some(wishlist_id))]
+        );
+    }
+
+    #[test]
+    fn anonymised_draw_stays_reproducible() {
+        let organizer_id = Uuid::now_v7();
+        let mut exchange = Exchange::new(Uuid::now_v7(), organizer_id, "Office".into());
+        for _ in 0..4 {
+            exchange.add_participant(Participant::new(Uuid::now_v7(), Some(Uuid::now_v7())));
+        }
+        exchange.add_participant(Participant::new(organizer_id, Some(Uuid::now_v7())));
+        let giver_id = exchange.participants()[0].user_id();
+        exchange.add_exclusion(Exclusion::new(giver_id, organizer_id));
+        exchange.set_draw(exchange.draw_from(7, Utc::now()).unwrap());
+
+        let alias = Uuid::now_v7();
+        exchange.anonymise(organizer_id, alias);
+        assert_eq!(exchange.organizer_id(), alias);
+        assert_eq!(
+            exchange.participant(alias),
+            Some(&Participant::new(alias, None))
+        );
+        assert_eq!(exchange.participant(organizer_id), None);
+        assert_eq!(exchange.exclusions(), [Exclusion::new(giver_id, alias)]);
+        assert_eq!(exchange.draw().unwrap().recipient_of(organizer_id), None);
+        assert!(exchange.is_draw_reproducible());
+    }
+}
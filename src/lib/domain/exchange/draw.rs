@@ -0,0 +1,212 @@
+use std::collections::HashSet;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{Assignment, Exclusion};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DrawError {
+    #[error("An exchange needs at least two participants")]
+    TooFewParticipants,
+    #[error("No draw satisfies the exclusions")]
+    NoValidDraw,
+}
+
+/// A small seeded pseudo-random generator (SplitMix64). It is part of the domain rather than a
+/// dependency so that stored seeds keep reproducing the same draws across upgrades.
+#[derive(Debug, Clone)]
+pub struct SeededRng(u64);
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Shuffles `values` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            values.swap(i, j);
+        }
+    }
+}
+
+/// Assigns a recipient to every giver so that nobody gives to themselves, everybody receives
+/// exactly once and no excluded pair is drawn.
+///
+/// The givers and their candidates are shuffled from `seed`, then matched with augmenting paths,
+/// which finds a valid draw whenever one exists. The assignments come in the order of `givers`.
+///
+/// # Errors
+/// - [DrawError::TooFewParticipants] if there are less than two givers.
+/// - [DrawError::NoValidDraw] if the exclusions rule out every draw.
+pub fn draw_assignments(
+    givers: &[Uuid],
+    exclusions: &[Exclusion],
+    seed: u64,
+) -> Result<Vec<Assignment>, DrawError> {
+    if givers.len() < 2 {
+        return Err(DrawError::TooFewParticipants);
+    }
+    let excluded: HashSet<(Uuid, Uuid)> = exclusions
+        .iter()
+        .map(|exclusion| (exclusion.giver_id(), exclusion.recipient_id()))
+        .collect();
+    let mut rng = SeededRng::new(seed);
+    let mut order: Vec<usize> = (0..givers.len()).collect();
+    rng.shuffle(&mut order);
+    let candidates: Vec<Vec<usize>> = givers
+        .iter()
+        .map(|giver| {
+            let mut candidates: Vec<usize> = givers
+                .iter()
+                .enumerate()
+                .filter(|(_, recipient)| {
+                    *recipient != giver && !excluded.contains(&(*giver, **recipient))
+                })
+                .map(|(index, _)| index)
+                .collect();
+            rng.shuffle(&mut candidates);
+            candidates
+        })
+        .collect();
+
+    // giver_of[recipient] is the giver currently matched to the recipient.
+    let mut giver_of: Vec<Option<usize>> = vec![None; givers.len()];
+    for giver in order {
+        let mut visited = vec![false; givers.len()];
+        if !augment(giver, &candidates, &mut visited, &mut giver_of) {
+            return Err(DrawError::NoValidDraw);
+        }
+    }
+
+    let mut recipient_of = vec![0; givers.len()];
+    for (recipient, giver) in giver_of.iter().enumerate() {
+        recipient_of[giver.expect("every recipient is matched")] = recipient;
+    }
+    Ok(givers
+        .iter()
+        .zip(recipient_of)
+        .map(|(giver, recipient)| Assignment::new(*giver, givers[recipient]))
+        .collect())
+}
+
+/// Looks for an augmenting path from `giver`, re-matching other givers along the way.
+fn augment(
+    giver: usize,
+    candidates: &[Vec<usize>],
+    visited: &mut [bool],
+    giver_of: &mut [Option<usize>],
+) -> bool {
+    for &recipient in &candidates[giver] {
+        if visited[recipient] {
+            continue;
+        }
+        visited[recipient] = true;
+        let free = match giver_of[recipient] {
+            None => true,
+            Some(other) => augment(other, candidates, visited, giver_of),
+        };
+        if free {
+            giver_of[recipient] = Some(giver);
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn users(count: usize) -> Vec<Uuid> {
+        (0..count).map(|_| Uuid::now_v7()).collect()
+    }
+
+    fn assert_valid(givers: &[Uuid], exclusions: &[Exclusion], assignments: &[Assignment]) {
+        assert_eq!(assignments.len(), givers.len());
+        let recipients: HashSet<Uuid> = assignments.iter().map(|a| a.recipient_id()).collect();
+        assert_eq!(recipients.len(), givers.len());
+        for (giver, assignment) in givers.iter().zip(assignments) {
+            assert_eq!(assignment.giver_id(), *giver);
+            assert_ne!(assignment.recipient_id(), *giver);
+            assert!(!exclusions.contains(&Exclusion::new(
+                assignment.giver_id(),
+                assignment.recipient_id()
+            )));
+        }
+    }
+
+    #[test]
+    fn draws_respect_exclusions() {
+        let people = users(4);
+        // Two couples, and last year's pair people[0] -> people[2].
+        let exclusions = [
+            Exclusion::new(people[0], people[1]),
+            Exclusion::new(people[1], people[0]),
+            Exclusion::new(people[2], people[3]),
+            Exclusion::new(people[3], people[2]),
+            Exclusion::new(people[0], people[2]),
+        ];
+        let mut draws = HashSet::new();
+        for seed in 0..50 {
+            let assignments = draw_assignments(&people, &exclusions, seed).unwrap();
+            assert_valid(&people, &exclusions, &assignments);
+            draws.insert(
+                assignments
+                    .iter()
+                    .map(|a| a.recipient_id())
+                    .collect::<Vec<_>>(),
+            );
+        }
+        assert!(draws.len() > 1);
+    }
+
+    #[test]
+    fn same_seed_same_draw() {
+        let people = users(10);
+        assert_eq!(
+            draw_assignments(&people, &[], 7),
+            draw_assignments(&people, &[], 7)
+        );
+        assert_ne!(
+            draw_assignments(&people, &[], 7),
+            draw_assignments(&people, &[], 8)
+        );
+    }
+
+    #[test]
+    fn reports_impossible_draws() {
+        assert_eq!(
+            draw_assignments(&users(1), &[], 0),
+            Err(DrawError::TooFewParticipants)
+        );
+        let people = users(3);
+        let exclusions = [
+            Exclusion::new(people[0], people[1]),
+            Exclusion::new(people[0], people[2]),
+        ];
+        assert_eq!(
+            draw_assignments(&people, &exclusions, 0),
+            Err(DrawError::NoValidDraw)
+        );
+        // Nobody can give to people[2].
+        let exclusions = [
+            Exclusion::new(people[0], people[2]),
+            Exclusion::new(people[1], people[2]),
+        ];
+        assert_eq!(
+            draw_assignments(&people, &exclusions, 0),
+            Err(DrawError::NoValidDraw)
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::future::Future;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::ForgetUserError;
+
+use super::{CreateExchangeError, CreateExchangeRequest, Draw, Exchange, Exclusion, Participant};
+
+#[cfg(test)]
+use mockall::automock;
+
+/// The [ExchangeRepository] trait defines the contract for gift exchange data operations.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ExchangeRepository: Send + Sync + 'static {
+    /// Saves a new exchange, without participants.
+    ///
+    /// # Errors
+    /// - [CreateExchangeError::Unkown] for any errors that may occur.
+    fn save(
+        &self,
+        req: &CreateExchangeRequest,
+    ) -> impl Future<Output = Result<Exchange, CreateExchangeError>> + Send;
+    /// Finds an exchange by its ID.
+    ///
+    /// # Errors
+    /// - [FindExchangeError::Unkown] for any errors that may occur during the search.
+    fn find_exchange_by_id(
+        &self,
+        exchange_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Exchange>, FindExchangeError>> + Send;
+    /// Applies [Exchange::add_participant] atomically.
+    ///
+    /// # Errors
+    /// - [UpdateExchangeError::ExchangeDoesNotExist] if the exchange does not exist.
+    /// - [UpdateExchangeError::AlreadyDrawn] if the exchange has been drawn.
+    /// - [UpdateExchangeError::Unkown] for any other errors that may occur.
+    fn add_participant(
+        &self,
+        exchange_id: Uuid,
+        participant: Participant,
+    ) -> impl Future<Output = Result<Exchange, UpdateExchangeError>> + Send;
+    /// Applies [Exchange::add_exclusion] atomically for each exclusion.
+    ///
+    /// # Errors
+    /// - [UpdateExchangeError::ExchangeDoesNotExist] if the exchange does not exist.
+    /// - [UpdateExchangeError::AlreadyDrawn] if the exchange has been drawn.
+    /// - [UpdateExchangeError::Unkown] for any other errors that may occur.
+    fn add_exclusions(
+        &self,
+        exchange_id: Uuid,
+        exclusions: Vec<Exclusion>,
+    ) -> impl Future<Output = Result<Exchange, UpdateExchangeError>> + Send;
+    /// Stores the draw of an exchange. An exchange is drawn once, and only with a draw of its
+    /// current participants.
+    ///
+    /// # Errors
+    /// - [UpdateExchangeError::ExchangeDoesNotExist] if the exchange does not exist.
+    /// - [UpdateExchangeError::AlreadyDrawn] if the exchange has been drawn.
+    /// - [UpdateExchangeError::Unkown] if the participants changed since the draw, or for any
+    ///   other errors that may occur.
+    fn save_draw(
+        &self,
+        exchange_id: Uuid,
+        draw: Draw,
+    ) -> impl Future<Output = Result<Exchange, UpdateExchangeError>> + Send;
+    /// Replaces a user by an anonymous participant in the exchanges they took part in, so that
+    /// the draws of the other participants still hold.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum FindExchangeError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UpdateExchangeError {
+    #[error("Exchange with id {id} does not exist")]
+    ExchangeDoesNotExist { id: Uuid },
+    #[error("Exchange with id {id} has already been drawn")]
+    AlreadyDrawn { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
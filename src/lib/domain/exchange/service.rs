@@ -0,0 +1,87 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+
+use super::{
+    AddExclusionError, AddExclusionRequest, AddParticipantError, AddParticipantRequest,
+    AuditDrawError, CreateExchangeError, CreateExchangeRequest, DrawAudit, DrawExchangeError,
+    Exchange, ExchangeActionRequest, Reveal, RevealRecipientError,
+};
+
+/// The [ExchangeService] trait defines the contract for gift exchanges such as Secret Santa.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ExchangeService: Send + Sync + 'static {
+    /// Creates an exchange organized by a user.
+    ///
+    /// # Errors
+    /// - [CreateExchangeError::OrganizerDoesNotExist] if the organizer does not exist.
+    /// - [CreateExchangeError::Unkown] for any other errors that may occur.
+    fn create_exchange(
+        &self,
+        req: &CreateExchangeRequest,
+    ) -> impl Future<Output = Result<Exchange, CreateExchangeError>> + Send;
+    /// Adds a participant to an exchange, with the wishlist their giver gets to see.
+    ///
+    /// # Errors
+    /// - [AddParticipantError::ExchangeDoesNotExist] if the exchange does not exist.
+    /// - [AddParticipantError::NotOrganizer] if a user other than the organizer adds someone
+    ///   else.
+    /// - [AddParticipantError::UserDoesNotExist] if the participant does not exist.
+    /// - [AddParticipantError::WishlistDoesNotExist] if the wishlist does not exist or is not
+    ///   the participant's.
+    /// - [AddParticipantError::AlreadyDrawn] if the exchange has been drawn.
+    /// - [AddParticipantError::Unkown] for any other errors that may occur.
+    fn add_participant(
+        &self,
+        req: &AddParticipantRequest,
+    ) -> impl Future<Output = Result<Exchange, AddParticipantError>> + Send;
+    /// Excludes a pair of participants from being drawn.
+    ///
+    /// # Errors
+    /// - [AddExclusionError::ExchangeDoesNotExist] if the exchange does not exist.
+    /// - [AddExclusionError::NotOrganizer] if the user is not the organizer.
+    /// - [AddExclusionError::NotParticipant] if either user does not participate.
+    /// - [AddExclusionError::SameParticipant] if both users are the same.
+    /// - [AddExclusionError::AlreadyDrawn] if the exchange has been drawn.
+    /// - [AddExclusionError::Unkown] for any other errors that may occur.
+    fn add_exclusion(
+        &self,
+        req: &AddExclusionRequest,
+    ) -> impl Future<Output = Result<Exchange, AddExclusionError>> + Send;
+    /// Draws the exchange from a fresh seed, which is stored with the draw.
+    ///
+    /// # Errors
+    /// - [DrawExchangeError::ExchangeDoesNotExist] if the exchange does not exist.
+    /// - [DrawExchangeError::NotOrganizer] if the user is not the organizer.
+    /// - [DrawExchangeError::AlreadyDrawn] if the exchange has been drawn.
+    /// - [DrawExchangeError::Invalid] if there are too few participants or no valid draw.
+    /// - [DrawExchangeError::Unkown] for any other errors that may occur.
+    fn draw_exchange(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> impl Future<Output = Result<Exchange, DrawExchangeError>> + Send;
+    /// Reveals to a participant who they give to, and only that.
+    ///
+    /// # Errors
+    /// - [RevealRecipientError::ExchangeDoesNotExist] if the exchange does not exist.
+    /// - [RevealRecipientError::NotParticipant] if the user does not participate.
+    /// - [RevealRecipientError::NotDrawn] if the exchange has not been drawn.
+    /// - [RevealRecipientError::Unkown] for any other errors that may occur.
+    fn reveal_recipient(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> impl Future<Output = Result<Reveal, RevealRecipientError>> + Send;
+    /// Checks that the stored draw is reproduced by its seed, without revealing it.
+    ///
+    /// # Errors
+    /// - [AuditDrawError::ExchangeDoesNotExist] if the exchange does not exist.
+    /// - [AuditDrawError::NotOrganizer] if the user is not the organizer.
+    /// - [AuditDrawError::NotDrawn] if the exchange has not been drawn.
+    /// - [AuditDrawError::Unkown] for any other errors that may occur.
+    fn audit_draw(
+        &self,
+        req: &ExchangeActionRequest,
+    ) -> impl Future<Output = Result<DrawAudit, AuditDrawError>> + Send;
+}
@@ -0,0 +1,327 @@
+mod repository;
+mod service;
+
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+pub use repository::*;
+pub use service::*;
+
+use super::{PageRequest, WishlistQuery};
+
+/// A follow of `followee_id` by `follower_id`. Following a private account takes the approval of
+/// its owner, so the follow is pending until then.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Follow {
+    follower_id: Uuid,
+    followee_id: Uuid,
+    status: FollowStatus,
+    created_at: DateTime<Utc>,
+}
+
+impl Follow {
+    pub fn new(
+        follower_id: Uuid,
+        followee_id: Uuid,
+        status: FollowStatus,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            follower_id,
+            followee_id,
+            status,
+            created_at,
+        }
+    }
+
+    pub fn follower_id(&self) -> Uuid {
+        self.follower_id
+    }
+
+    pub fn followee_id(&self) -> Uuid {
+        self.followee_id
+    }
+
+    pub fn status(&self) -> FollowStatus {
+        self.status
+    }
+
+    pub fn set_status(&mut self, status: FollowStatus) {
+        self.status = status;
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowStatus {
+    /// Waiting for the approval of the followee.
+    Pending,
+    Accepted,
+    /// Refused by the followee. Declined follows are not kept.
+    Declined,
+}
+
+impl Display for FollowStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FollowStatus::Pending => "pending",
+            FollowStatus::Accepted => "accepted",
+            FollowStatus::Declined => "declined",
+        })
+    }
+}
+
+/// A block of `blocked_id` by `blocker_id`: neither can follow the other while it lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block {
+    blocker_id: Uuid,
+    blocked_id: Uuid,
+    created_at: DateTime<Utc>,
+}
+
+impl Block {
+    pub fn new(blocker_id: Uuid, blocked_id: Uuid, created_at: DateTime<Utc>) -> Self {
+        Self {
+            blocker_id,
+            blocked_id,
+            created_at,
+        }
+    }
+
+    pub fn blocker_id(&self) -> Uuid {
+        self.blocker_id
+    }
+
+    pub fn blocked_id(&self) -> Uuid {
+        self.blocked_id
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+/// The follow settings of a user. Accounts are public unless made private.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FollowSettings {
+    user_id: Uuid,
+    private_account: bool,
+}
+
+impl FollowSettings {
+    pub fn new(user_id: Uuid, private_account: bool) -> Self {
+        Self {
+            user_id,
+            private_account,
+        }
+    }
+
+    /// The settings of a user who has not set any.
+    pub fn default_for(user_id: Uuid) -> Self {
+        Self::new(user_id, false)
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    /// Returns true if following the user takes their approval.
+    pub fn private_account(&self) -> bool {
+        self.private_account
+    }
+}
+
+/// The [FollowUserRequest] struct represents a request by `user_id` to follow `followee_id`, or
+/// to stop following them.
+#[derive(Debug, Clone)]
+pub struct FollowUserRequest {
+    user_id: Uuid,
+    followee_id: Uuid,
+}
+
+impl FollowUserRequest {
+    pub fn new(user_id: Uuid, followee_id: Uuid) -> Self {
+        Self {
+            user_id,
+            followee_id,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn followee_id(&self) -> Uuid {
+        self.followee_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FollowUserError {
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("Users cannot follow themselves")]
+    SelfFollow,
+    #[error("User with id {id} cannot be followed")]
+    Blocked { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UnfollowUserError {
+    #[error("User does not follow user with id {id}")]
+    NotFollowing { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [AnswerFollowRequest] struct represents the answer of `user_id` to the request of
+/// `follower_id` to follow them.
+#[derive(Debug, Clone)]
+pub struct AnswerFollowRequest {
+    user_id: Uuid,
+    follower_id: Uuid,
+    accept: bool,
+}
+
+impl AnswerFollowRequest {
+    pub fn new(user_id: Uuid, follower_id: Uuid, accept: bool) -> Self {
+        Self {
+            user_id,
+            follower_id,
+            accept,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn follower_id(&self) -> Uuid {
+        self.follower_id
+    }
+
+    pub fn accept(&self) -> bool {
+        self.accept
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AnswerFollowRequestError {
+    #[error("User with id {id} has no pending request to follow the user")]
+    RequestDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [ListFollowRequestsRequest] struct represents a request by a user for a page of the
+/// pending requests to follow them, oldest first.
+#[derive(Debug, Clone)]
+pub struct ListFollowRequestsRequest {
+    user_id: Uuid,
+    page: PageRequest,
+}
+
+impl ListFollowRequestsRequest {
+    pub fn new(user_id: Uuid, page: PageRequest) -> Self {
+        Self { user_id, page }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn page(&self) -> &PageRequest {
+        &self.page
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ListFollowRequestsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [BlockUserRequest] struct represents a request by `user_id` to block `blocked_id`, or to
+/// unblock them.
+#[derive(Debug, Clone)]
+pub struct BlockUserRequest {
+    user_id: Uuid,
+    blocked_id: Uuid,
+}
+
+impl BlockUserRequest {
+    pub fn new(user_id: Uuid, blocked_id: Uuid) -> Self {
+        Self {
+            user_id,
+            blocked_id,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn blocked_id(&self) -> Uuid {
+        self.blocked_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum BlockUserError {
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("Users cannot block themselves")]
+    SelfBlock,
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UnblockUserError {
+    #[error("User has not blocked user with id {id}")]
+    NotBlocked { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SetFollowSettingsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [FollowFeedRequest] struct represents a request by a user for a page of the wishlists of
+/// the people they follow.
+#[derive(Debug, Clone)]
+pub struct FollowFeedRequest {
+    user_id: Uuid,
+    query: WishlistQuery,
+}
+
+impl FollowFeedRequest {
+    pub fn new(user_id: Uuid, query: WishlistQuery) -> Self {
+        Self { user_id, query }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn query(&self) -> &WishlistQuery {
+        &self.query
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FollowFeedError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
@@ -0,0 +1,132 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::{ForgetUserError, Page, PageRequest};
+
+use super::{
+    AnswerFollowRequestError, Block, BlockUserError, Follow, FollowSettings, FollowStatus,
+    FollowUserError, ListFollowRequestsError, SetFollowSettingsError, UnblockUserError,
+    UnfollowUserError,
+};
+
+#[cfg(test)]
+use mockall::automock;
+
+/// The [FollowRepository] trait defines the contract for follow graph data operations.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait FollowRepository: Send + Sync + 'static {
+    /// Saves a follow with the given status. Following a user again returns the existing follow.
+    ///
+    /// # Errors
+    /// - [FollowUserError::Blocked] if either user has blocked the other.
+    /// - [FollowUserError::Unkown] for any other errors that may occur.
+    fn save_follow(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+        status: FollowStatus,
+        created_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Follow, FollowUserError>> + Send;
+    /// Deletes a follow, pending or not.
+    ///
+    /// # Errors
+    /// - [UnfollowUserError::NotFollowing] if there is no such follow.
+    /// - [UnfollowUserError::Unkown] for any other errors that may occur.
+    fn delete_follow(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> impl Future<Output = Result<Follow, UnfollowUserError>> + Send;
+    /// Accepts a pending follow, or deletes it when declined.
+    ///
+    /// # Errors
+    /// - [AnswerFollowRequestError::RequestDoesNotExist] if there is no such pending follow.
+    /// - [AnswerFollowRequestError::Unkown] for any other errors that may occur.
+    fn answer_follow(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+        accept: bool,
+    ) -> impl Future<Output = Result<Follow, AnswerFollowRequestError>> + Send;
+    /// Finds a page of the pending requests to follow a user, oldest first.
+    ///
+    /// # Errors
+    /// - [ListFollowRequestsError::Unkown] for any errors that may occur during the search.
+    fn find_pending_follows(
+        &self,
+        followee_id: Uuid,
+        page: &PageRequest,
+    ) -> impl Future<Output = Result<Page<Follow>, ListFollowRequestsError>> + Send;
+    /// Finds the users a user follows, once accepted.
+    ///
+    /// # Errors
+    /// - [FindFollowError::Unkown] for any errors that may occur during the search.
+    fn find_followees(
+        &self,
+        follower_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Uuid>, FindFollowError>> + Send;
+    /// Saves a block and deletes the follows between both users, either way. Blocking a user
+    /// again returns the existing block.
+    ///
+    /// # Errors
+    /// - [BlockUserError::Unkown] for any errors that may occur.
+    fn save_block(
+        &self,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+        created_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Block, BlockUserError>> + Send;
+    /// Deletes a block.
+    ///
+    /// # Errors
+    /// - [UnblockUserError::NotBlocked] if there is no such block.
+    /// - [UnblockUserError::Unkown] for any other errors that may occur.
+    fn delete_block(
+        &self,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+    ) -> impl Future<Output = Result<Block, UnblockUserError>> + Send;
+    /// Finds the block of `blocked_id` by `blocker_id`, if there is one.
+    ///
+    /// # Errors
+    /// - [FindFollowError::Unkown] for any errors that may occur during the search.
+    fn find_block(
+        &self,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Block>, FindFollowError>> + Send;
+    /// Finds the follow settings of a user, if they set any.
+    ///
+    /// # Errors
+    /// - [FindFollowError::Unkown] for any errors that may occur during the search.
+    fn find_settings(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<FollowSettings>, FindFollowError>> + Send;
+    /// Saves the follow settings of a user, replacing previous ones.
+    ///
+    /// # Errors
+    /// - [SetFollowSettingsError::Unkown] for any errors that may occur.
+    fn save_settings(
+        &self,
+        settings: &FollowSettings,
+    ) -> impl Future<Output = Result<FollowSettings, SetFollowSettingsError>> + Send;
+    /// Deletes the follows and blocks from or of a user, and their follow settings.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum FindFollowError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
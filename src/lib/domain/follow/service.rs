@@ -0,0 +1,93 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::domain::{Page, Wishlist};
+
+use super::{
+    AnswerFollowRequest, AnswerFollowRequestError, Block, BlockUserError, BlockUserRequest, Follow,
+    FollowFeedError, FollowFeedRequest, FollowSettings, FollowUserError, FollowUserRequest,
+    ListFollowRequestsError, ListFollowRequestsRequest, SetFollowSettingsError, UnblockUserError,
+    UnfollowUserError,
+};
+
+/// The [FollowService] trait defines the contract for the follow graph between users and the
+/// feed of the wishlists of followed users.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait FollowService: Send + Sync + 'static {
+    /// Follows a user. Following a private account is pending until its owner accepts.
+    ///
+    /// # Errors
+    /// - [FollowUserError::UserDoesNotExist] if the followee does not exist.
+    /// - [FollowUserError::SelfFollow] if both users are the same.
+    /// - [FollowUserError::Blocked] if either user has blocked the other.
+    /// - [FollowUserError::Unkown] for any other errors that may occur.
+    fn follow_user(
+        &self,
+        req: &FollowUserRequest,
+    ) -> impl Future<Output = Result<Follow, FollowUserError>> + Send;
+    /// Stops following a user, or cancels a pending request to.
+    ///
+    /// # Errors
+    /// - [UnfollowUserError::NotFollowing] if the user does not follow the followee.
+    /// - [UnfollowUserError::Unkown] for any other errors that may occur.
+    fn unfollow_user(
+        &self,
+        req: &FollowUserRequest,
+    ) -> impl Future<Output = Result<Follow, UnfollowUserError>> + Send;
+    /// Lists a page of the pending requests to follow a user, oldest first.
+    ///
+    /// # Errors
+    /// - [ListFollowRequestsError::Unkown] for any errors that may occur.
+    fn list_follow_requests(
+        &self,
+        req: &ListFollowRequestsRequest,
+    ) -> impl Future<Output = Result<Page<Follow>, ListFollowRequestsError>> + Send;
+    /// Accepts or declines a request to follow the user.
+    ///
+    /// # Errors
+    /// - [AnswerFollowRequestError::RequestDoesNotExist] if there is no such pending request.
+    /// - [AnswerFollowRequestError::Unkown] for any other errors that may occur.
+    fn answer_follow_request(
+        &self,
+        req: &AnswerFollowRequest,
+    ) -> impl Future<Output = Result<Follow, AnswerFollowRequestError>> + Send;
+    /// Blocks a user, ending the follows between them either way.
+    ///
+    /// # Errors
+    /// - [BlockUserError::UserDoesNotExist] if the blocked user does not exist.
+    /// - [BlockUserError::SelfBlock] if both users are the same.
+    /// - [BlockUserError::Unkown] for any other errors that may occur.
+    fn block_user(
+        &self,
+        req: &BlockUserRequest,
+    ) -> impl Future<Output = Result<Block, BlockUserError>> + Send;
+    /// Unblocks a user. Previous follows are not restored.
+    ///
+    /// # Errors
+    /// - [UnblockUserError::NotBlocked] if the user has not blocked them.
+    /// - [UnblockUserError::Unkown] for any other errors that may occur.
+    fn unblock_user(
+        &self,
+        req: &BlockUserRequest,
+    ) -> impl Future<Output = Result<Block, UnblockUserError>> + Send;
+    /// Sets the follow settings of a user.
+    ///
+    /// # Errors
+    /// - [SetFollowSettingsError::Unkown] for any errors that may occur.
+    fn set_follow_settings(
+        &self,
+        settings: &FollowSettings,
+    ) -> impl Future<Output = Result<FollowSettings, SetFollowSettingsError>> + Send;
+    /// Lists a page of the wishlists of the users the user follows that they may see, most
+    /// recently updated first.
+    ///
+    /// # Errors
+    /// - [FollowFeedError::Unkown] for any errors that may occur.
+    fn follow_feed(
+        &self,
+        req: &FollowFeedRequest,
+    ) -> impl Future<Output = Result<Page<Wishlist>, FollowFeedError>> + Send;
+}
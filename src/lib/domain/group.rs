@@ -0,0 +1,740 @@
+mod repository;
+mod service;
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+pub use repository::*;
+pub use service::*;
+
+use super::{PageRequest, Wishlist, WishlistFilter};
+
+/// A group of users, such as a family, that wishlists are shared with as a whole. Access follows
+/// membership: members see the wishlists shared with the group for as long as they, and the
+/// owner of the wishlist, belong to it.
+#[derive(Debug, Clone)]
+pub struct Group {
+    id: Uuid,
+    name: GroupName,
+    members: Vec<GroupMember>,
+    wishlists: Vec<SharedWishlist>,
+}
+
+impl Group {
+    /// Creates a group with `creator_id` as its first admin.
+    pub fn new(id: Uuid, name: GroupName, creator_id: Uuid) -> Self {
+        Self {
+            id,
+            name,
+            members: vec![GroupMember::new(creator_id, GroupRole::Admin)],
+            wishlists: Vec::new(),
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &GroupName {
+        &self.name
+    }
+
+    pub fn members(&self) -> &[GroupMember] {
+        &self.members
+    }
+
+    /// The wishlists shared with the group, oldest share first.
+    pub fn wishlists(&self) -> &[SharedWishlist] {
+        &self.wishlists
+    }
+
+    pub fn role_of(&self, user_id: Uuid) -> Option<GroupRole> {
+        self.members
+            .iter()
+            .find(|member| member.user_id == user_id)
+            .map(GroupMember::role)
+    }
+
+    pub fn is_member(&self, user_id: Uuid) -> bool {
+        self.role_of(user_id).is_some()
+    }
+
+    pub fn is_admin(&self, user_id: Uuid) -> bool {
+        self.role_of(user_id) == Some(GroupRole::Admin)
+    }
+
+    /// Adds a member, leaving existing members unchanged.
+    pub fn add_member(&mut self, member: GroupMember) {
+        if !self.is_member(member.user_id) {
+            self.members.push(member);
+        }
+    }
+
+    /// Removes a member. The last admin cannot leave a group that has other members.
+    pub fn remove_member(&mut self, user_id: Uuid) -> Result<(), GroupMembershipError> {
+        let role = self
+            .role_of(user_id)
+            .ok_or(GroupMembershipError::NotMember { id: user_id })?;
+        if role == GroupRole::Admin && self.admin_count() == 1 && self.members.len() > 1 {
+            return Err(GroupMembershipError::LastAdmin);
+        }
+        self.members.retain(|member| member.user_id != user_id);
+        Ok(())
+    }
+
+    /// Changes the role of a member. The last admin cannot step down.
+    pub fn set_role(&mut self, user_id: Uuid, role: GroupRole) -> Result<(), GroupMembershipError> {
+        let current = self
+            .role_of(user_id)
+            .ok_or(GroupMembershipError::NotMember { id: user_id })?;
+        if current == GroupRole::Admin && role != GroupRole::Admin && self.admin_count() == 1 {
+            return Err(GroupMembershipError::LastAdmin);
+        }
+        for member in self.members.iter_mut() {
+            if member.user_id == user_id {
+                member.role = role;
+            }
+        }
+        Ok(())
+    }
+
+    /// Shares a wishlist with the group. Sharing it again keeps the original share.
+    pub fn share(&mut self, shared: SharedWishlist) {
+        if !self.is_shared(shared.wishlist_id) {
+            self.wishlists.push(shared);
+        }
+    }
+
+    /// Stops sharing a wishlist. Returns false if it was not shared.
+    pub fn unshare(&mut self, wishlist_id: Uuid) -> bool {
+        let length = self.wishlists.len();
+        self.wishlists
+            .retain(|shared| shared.wishlist_id != wishlist_id);
+        self.wishlists.len() != length
+    }
+
+    pub fn is_shared(&self, wishlist_id: Uuid) -> bool {
+        self.wishlists
+            .iter()
+            .any(|shared| shared.wishlist_id == wishlist_id)
+    }
+
+    /// The shares that are visible to members: those of people who still belong to the group.
+    pub fn visible_wishlists(&self) -> impl Iterator<Item = &SharedWishlist> {
+        self.wishlists
+            .iter()
+            .filter(|shared| self.is_member(shared.shared_by))
+    }
+
+    /// Returns true if the group lets `user_id` see the wishlist.
+    pub fn grants_access(&self, user_id: Uuid, wishlist_id: Uuid) -> bool {
+        self.is_member(user_id)
+            && self
+                .visible_wishlists()
+                .any(|shared| shared.wishlist_id == wishlist_id)
+    }
+
+    /// Removes a user and the wishlists they shared, whatever their role. When they were the last
+    /// admin, the oldest remaining member becomes admin.
+    pub fn forget(&mut self, user_id: Uuid) {
+        self.members.retain(|member| member.user_id != user_id);
+        self.wishlists.retain(|shared| shared.shared_by != user_id);
+        if self.admin_count() == 0 {
+            if let Some(member) = self.members.first_mut() {
+                member.role = GroupRole::Admin;
+            }
+        }
+    }
+
+    fn admin_count(&self) -> usize {
+        self.members
+            .iter()
+            .filter(|member| member.role == GroupRole::Admin)
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupName(String);
+
+impl From<&str> for GroupName {
+    fn from(value: &str) -> Self {
+        GroupName(value.to_string())
+    }
+}
+
+impl Display for GroupName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl GroupName {
+    pub fn new(name: &str) -> Result<Self, GroupNameInvalidError> {
+        if name.trim().is_empty() {
+            return Err(GroupNameInvalidError {
+                invalid_name: GroupName(name.to_string()),
+            });
+        }
+        Ok(GroupName(name.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Name is invalid")]
+pub struct GroupNameInvalidError {
+    pub invalid_name: GroupName,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupMember {
+    user_id: Uuid,
+    role: GroupRole,
+}
+
+impl GroupMember {
+    pub fn new(user_id: Uuid, role: GroupRole) -> Self {
+        Self { user_id, role }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn role(&self) -> GroupRole {
+        self.role
+    }
+}
+
+/// Admins manage the members of a group; members share and see wishlists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupRole {
+    Admin,
+    Member,
+}
+
+impl Display for GroupRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GroupRole::Admin => "admin",
+            GroupRole::Member => "member",
+        })
+    }
+}
+
+impl FromStr for GroupRole {
+    type Err = GroupRoleInvalidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "admin" => Ok(GroupRole::Admin),
+            "member" => Ok(GroupRole::Member),
+            _ => Err(GroupRoleInvalidError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Role {0} is invalid")]
+pub struct GroupRoleInvalidError(pub String);
+
+/// A wishlist shared with a [Group] by its owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedWishlist {
+    wishlist_id: Uuid,
+    shared_by: Uuid,
+    shared_at: DateTime<Utc>,
+}
+
+impl SharedWishlist {
+    pub fn new(wishlist_id: Uuid, shared_by: Uuid, shared_at: DateTime<Utc>) -> Self {
+        Self {
+            wishlist_id,
+            shared_by,
+            shared_at,
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn shared_by(&self) -> Uuid {
+        self.shared_by
+    }
+
+    pub fn shared_at(&self) -> DateTime<Utc> {
+        self.shared_at
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GroupMembershipError {
+    #[error("User with id {id} is not a member of the group")]
+    NotMember { id: Uuid },
+    #[error("A group needs at least one admin")]
+    LastAdmin,
+}
+
+/// An invitation for a user to join a [Group].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupInvitation {
+    id: Uuid,
+    group_id: Uuid,
+    invitee_id: Uuid,
+    invited_by: Uuid,
+    status: InvitationStatus,
+    created_at: DateTime<Utc>,
+}
+
+impl GroupInvitation {
+    pub fn new(
+        id: Uuid,
+        group_id: Uuid,
+        invitee_id: Uuid,
+        invited_by: Uuid,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            group_id,
+            invitee_id,
+            invited_by,
+            status: InvitationStatus::Pending,
+            created_at,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn group_id(&self) -> Uuid {
+        self.group_id
+    }
+
+    pub fn invitee_id(&self) -> Uuid {
+        self.invitee_id
+    }
+
+    pub fn invited_by(&self) -> Uuid {
+        self.invited_by
+    }
+
+    pub fn status(&self) -> InvitationStatus {
+        self.status
+    }
+
+    pub fn set_status(&mut self, status: InvitationStatus) {
+        self.status = status;
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvitationStatus {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+impl Display for InvitationStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            InvitationStatus::Pending => "pending",
+            InvitationStatus::Accepted => "accepted",
+            InvitationStatus::Declined => "declined",
+        })
+    }
+}
+
+/// An entry of the feed of a [Group]: a wishlist shared with it.
+#[derive(Debug, Clone)]
+pub struct GroupFeedEntry {
+    wishlist: Wishlist,
+    shared_by: Uuid,
+    shared_at: DateTime<Utc>,
+}
+
+impl GroupFeedEntry {
+    pub fn new(wishlist: Wishlist, shared_by: Uuid, shared_at: DateTime<Utc>) -> Self {
+        Self {
+            wishlist,
+            shared_by,
+            shared_at,
+        }
+    }
+
+    pub fn wishlist(&self) -> &Wishlist {
+        &self.wishlist
+    }
+
+    pub fn shared_by(&self) -> Uuid {
+        self.shared_by
+    }
+
+    pub fn shared_at(&self) -> DateTime<Utc> {
+        self.shared_at
+    }
+}
+
+/// The [CreateGroupRequest] struct represents a request by a user to create a [Group].
+#[derive(Debug, Clone)]
+pub struct CreateGroupRequest {
+    user_id: Uuid,
+    name: GroupName,
+}
+
+impl CreateGroupRequest {
+    pub fn new(user_id: Uuid, name: GroupName) -> Self {
+        Self { user_id, name }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn name(&self) -> &GroupName {
+        &self.name
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CreateGroupError {
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [InviteMemberRequest] struct represents a request by an admin of a [Group] to invite a
+/// user into it.
+#[derive(Debug, Clone)]
+pub struct InviteMemberRequest {
+    group_id: Uuid,
+    user_id: Uuid,
+    invitee_id: Uuid,
+}
+
+impl InviteMemberRequest {
+    pub fn new(group_id: Uuid, user_id: Uuid, invitee_id: Uuid) -> Self {
+        Self {
+            group_id,
+            user_id,
+            invitee_id,
+        }
+    }
+
+    pub fn group_id(&self) -> Uuid {
+        self.group_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn invitee_id(&self) -> Uuid {
+        self.invitee_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum InviteMemberError {
+    #[error("Group with id {id} does not exist")]
+    GroupDoesNotExist { id: Uuid },
+    #[error("Only admins of group {id} can invite members")]
+    NotAdmin { id: Uuid },
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("User with id {id} is already a member of the group")]
+    AlreadyMember { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [ListInvitationsRequest] struct represents a request by a user for a page of their
+/// pending invitations, oldest first.
+#[derive(Debug, Clone)]
+pub struct ListInvitationsRequest {
+    user_id: Uuid,
+    page: PageRequest,
+}
+
+impl ListInvitationsRequest {
+    pub fn new(user_id: Uuid, page: PageRequest) -> Self {
+        Self { user_id, page }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn page(&self) -> &PageRequest {
+        &self.page
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ListInvitationsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [RespondToInvitationRequest] struct represents the answer of a user to an invitation.
+#[derive(Debug, Clone)]
+pub struct RespondToInvitationRequest {
+    invitation_id: Uuid,
+    user_id: Uuid,
+    accept: bool,
+}
+
+impl RespondToInvitationRequest {
+    pub fn new(invitation_id: Uuid, user_id: Uuid, accept: bool) -> Self {
+        Self {
+            invitation_id,
+            user_id,
+            accept,
+        }
+    }
+
+    pub fn invitation_id(&self) -> Uuid {
+        self.invitation_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn accept(&self) -> bool {
+        self.accept
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RespondToInvitationError {
+    #[error("Invitation with id {id} does not exist")]
+    InvitationDoesNotExist { id: Uuid },
+    #[error("Invitation with id {id} has already been answered")]
+    AlreadyAnswered { id: Uuid },
+    #[error("Group with id {id} does not exist")]
+    GroupDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [ManageMemberRequest] struct represents a request by `user_id` about `member_id`, a
+/// member of a [Group]: removing them, or changing their role.
+#[derive(Debug, Clone)]
+pub struct ManageMemberRequest {
+    group_id: Uuid,
+    user_id: Uuid,
+    member_id: Uuid,
+}
+
+impl ManageMemberRequest {
+    pub fn new(group_id: Uuid, user_id: Uuid, member_id: Uuid) -> Self {
+        Self {
+            group_id,
+            user_id,
+            member_id,
+        }
+    }
+
+    pub fn group_id(&self) -> Uuid {
+        self.group_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn member_id(&self) -> Uuid {
+        self.member_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ManageMemberError {
+    #[error("Group with id {id} does not exist")]
+    GroupDoesNotExist { id: Uuid },
+    #[error("Only admins of group {id} can manage other members")]
+    NotAdmin { id: Uuid },
+    #[error(transparent)]
+    Membership(#[from] GroupMembershipError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [SetMemberRoleRequest] struct represents a request by an admin of a [Group] to change
+/// the role of a member.
+#[derive(Debug, Clone)]
+pub struct SetMemberRoleRequest {
+    member: ManageMemberRequest,
+    role: GroupRole,
+}
+
+impl SetMemberRoleRequest {
+    pub fn new(member: ManageMemberRequest, role: GroupRole) -> Self {
+        Self { member, role }
+    }
+
+    pub fn group_id(&self) -> Uuid {
+        self.member.group_id()
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.member.user_id()
+    }
+
+    pub fn member_id(&self) -> Uuid {
+        self.member.member_id()
+    }
+
+    pub fn role(&self) -> GroupRole {
+        self.role
+    }
+}
+
+/// The [ShareWishlistRequest] struct represents a request by `user_id` to share a wishlist with a
+/// [Group], or to stop sharing it.
+#[derive(Debug, Clone)]
+pub struct ShareWishlistRequest {
+    group_id: Uuid,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+}
+
+impl ShareWishlistRequest {
+    pub fn new(group_id: Uuid, user_id: Uuid, wishlist_id: Uuid) -> Self {
+        Self {
+            group_id,
+            user_id,
+            wishlist_id,
+        }
+    }
+
+    pub fn group_id(&self) -> Uuid {
+        self.group_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ShareWishlistError {
+    #[error("Group with id {id} does not exist")]
+    GroupDoesNotExist { id: Uuid },
+    #[error("User with id {id} is not a member of the group")]
+    NotMember { id: Uuid },
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} is not the user's")]
+    NotWishlistOwner { id: Uuid },
+    #[error("Wishlist with id {id} is not shared with the group")]
+    NotShared { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [GroupFeedRequest] struct represents a request by a member for a page of the feed of a
+/// [Group], most recently shared first.
+#[derive(Debug, Clone)]
+pub struct GroupFeedRequest {
+    group_id: Uuid,
+    user_id: Uuid,
+    filter: WishlistFilter,
+    page: PageRequest,
+}
+
+impl GroupFeedRequest {
+    pub fn new(group_id: Uuid, user_id: Uuid, filter: WishlistFilter, page: PageRequest) -> Self {
+        Self {
+            group_id,
+            user_id,
+            filter,
+            page,
+        }
+    }
+
+    pub fn group_id(&self) -> Uuid {
+        self.group_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn filter(&self) -> &WishlistFilter {
+        &self.filter
+    }
+
+    pub fn page(&self) -> &PageRequest {
+        &self.page
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum GroupFeedError {
+    #[error("Group with id {id} does not exist")]
+    GroupDoesNotExist { id: Uuid },
+    #[error("User with id {id} is not a member of the group")]
+    NotMember { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access_follows_membership() {
+        let (admin, member, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let mut group = Group::new(Uuid::now_v7(), "Family".into(), admin);
+        group.share(SharedWishlist::new(wishlist_id, admin, Utc::now()));
+        assert!(!group.grants_access(member, wishlist_id));
+
+        group.add_member(GroupMember::new(member, GroupRole::Member));
+        assert!(group.grants_access(member, wishlist_id));
+
+        group.set_role(member, GroupRole::Admin).unwrap();
+        group.remove_member(admin).unwrap();
+        assert!(!group.grants_access(member, wishlist_id));
+        assert_eq!(group.visible_wishlists().count(), 0);
+    }
+
+    #[test]
+    fn keeps_an_admin() {
+        let (admin, member) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut group = Group::new(Uuid::now_v7(), "Family".into(), admin);
+        group.add_member(GroupMember::new(member, GroupRole::Member));
+        assert_eq!(
+            group.set_role(admin, GroupRole::Member),
+            Err(GroupMembershipError::LastAdmin)
+        );
+        assert_eq!(
+            group.remove_member(admin),
+            Err(GroupMembershipError::LastAdmin)
+        );
+        assert_eq!(
+            group.remove_member(Uuid::nil()),
+            Err(GroupMembershipError::NotMember { id: Uuid::nil() })
+        );
+        group.remove_member(member).unwrap();
+        group.remove_member(admin).unwrap();
+        assert!(group.members().is_empty());
+    }
+}
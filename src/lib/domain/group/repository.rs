@@ -0,0 +1,150 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::{ForgetUserError, Page, PageRequest};
+
+use super::{
+    CreateGroupError, CreateGroupRequest, Group, GroupInvitation, GroupMembershipError, GroupRole,
+    InviteMemberError, ListInvitationsError, RespondToInvitationError, SharedWishlist,
+};
+
+#[cfg(test)]
+use mockall::automock;
+
+/// The [GroupRepository] trait defines the contract for group and invitation data operations.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait GroupRepository: Send + Sync + 'static {
+    /// Saves a new group, with its creator as admin.
+    ///
+    /// # Errors
+    /// - [CreateGroupError::Unkown] for any errors that may occur.
+    fn save(
+        &self,
+        req: &CreateGroupRequest,
+    ) -> impl Future<Output = Result<Group, CreateGroupError>> + Send;
+    /// Finds a group by its ID.
+    ///
+    /// # Errors
+    /// - [FindGroupError::Unkown] for any errors that may occur during the search.
+    fn find_group_by_id(
+        &self,
+        group_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Group>, FindGroupError>> + Send;
+    /// Finds the groups a user is a member of.
+    ///
+    /// # Errors
+    /// - [FindGroupError::Unkown] for any errors that may occur during the search.
+    fn find_groups_by_member(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Group>, FindGroupError>> + Send;
+    /// Applies [Group::remove_member] atomically.
+    ///
+    /// # Errors
+    /// - [UpdateGroupError::GroupDoesNotExist] if the group does not exist.
+    /// - [UpdateGroupError::Membership] if the user is not a member or is the last admin.
+    /// - [UpdateGroupError::Unkown] for any other errors that may occur.
+    fn remove_member(
+        &self,
+        group_id: Uuid,
+        member_id: Uuid,
+    ) -> impl Future<Output = Result<Group, UpdateGroupError>> + Send;
+    /// Applies [Group::set_role] atomically.
+    ///
+    /// # Errors
+    /// - [UpdateGroupError::GroupDoesNotExist] if the group does not exist.
+    /// - [UpdateGroupError::Membership] if the user is not a member or is the last admin.
+    /// - [UpdateGroupError::Unkown] for any other errors that may occur.
+    fn set_member_role(
+        &self,
+        group_id: Uuid,
+        member_id: Uuid,
+        role: GroupRole,
+    ) -> impl Future<Output = Result<Group, UpdateGroupError>> + Send;
+    /// Applies [Group::share] atomically.
+    ///
+    /// # Errors
+    /// - [UpdateGroupError::GroupDoesNotExist] if the group does not exist.
+    /// - [UpdateGroupError::Unkown] for any other errors that may occur.
+    fn share_wishlist(
+        &self,
+        group_id: Uuid,
+        shared: SharedWishlist,
+    ) -> impl Future<Output = Result<Group, UpdateGroupError>> + Send;
+    /// Applies [Group::unshare] atomically.
+    ///
+    /// # Errors
+    /// - [UpdateGroupError::GroupDoesNotExist] if the group does not exist.
+    /// - [UpdateGroupError::Unkown] for any other errors that may occur.
+    fn unshare_wishlist(
+        &self,
+        group_id: Uuid,
+        wishlist_id: Uuid,
+    ) -> impl Future<Output = Result<Group, UpdateGroupError>> + Send;
+    /// Saves an invitation of `invitee_id` into a group. Inviting a user again while an
+    /// invitation is pending returns that invitation.
+    ///
+    /// # Errors
+    /// - [InviteMemberError::GroupDoesNotExist] if the group does not exist.
+    /// - [InviteMemberError::AlreadyMember] if the invitee is already a member.
+    /// - [InviteMemberError::Unkown] for any other errors that may occur.
+    fn save_invitation(
+        &self,
+        group_id: Uuid,
+        invitee_id: Uuid,
+        invited_by: Uuid,
+        created_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<GroupInvitation, InviteMemberError>> + Send;
+    /// Finds a page of the pending invitations of a user, oldest first.
+    ///
+    /// # Errors
+    /// - [ListInvitationsError::Unkown] for any errors that may occur during the search.
+    fn find_pending_invitations(
+        &self,
+        user_id: Uuid,
+        page: &PageRequest,
+    ) -> impl Future<Output = Result<Page<GroupInvitation>, ListInvitationsError>> + Send;
+    /// Answers an invitation of `user_id`, adding them to the group when they accept.
+    ///
+    /// # Errors
+    /// - [RespondToInvitationError::InvitationDoesNotExist] if the invitation does not exist or
+    ///   is not for the user.
+    /// - [RespondToInvitationError::AlreadyAnswered] if the invitation is not pending.
+    /// - [RespondToInvitationError::GroupDoesNotExist] if the group no longer exists.
+    /// - [RespondToInvitationError::Unkown] for any other errors that may occur.
+    fn answer_invitation(
+        &self,
+        invitation_id: Uuid,
+        user_id: Uuid,
+        accept: bool,
+    ) -> impl Future<Output = Result<GroupInvitation, RespondToInvitationError>> + Send;
+    /// Removes a user from their groups, along with their shares and invitations. Groups left
+    /// without an admin get their oldest member as admin, and groups left empty are deleted.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum FindGroupError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UpdateGroupError {
+    #[error("Group with id {id} does not exist")]
+    GroupDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Membership(#[from] GroupMembershipError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
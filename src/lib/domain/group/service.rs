@@ -0,0 +1,120 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::domain::Page;
+
+use super::{
+    CreateGroupError, CreateGroupRequest, Group, GroupFeedEntry, GroupFeedError, GroupFeedRequest,
+    GroupInvitation, InviteMemberError, InviteMemberRequest, ListInvitationsError,
+    ListInvitationsRequest, ManageMemberError, ManageMemberRequest, RespondToInvitationError,
+    RespondToInvitationRequest, SetMemberRoleRequest, ShareWishlistError, ShareWishlistRequest,
+};
+
+/// The [GroupService] trait defines the contract for groups, such as families, that wishlists
+/// are shared with.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait GroupService: Send + Sync + 'static {
+    /// Creates a group with the user as its admin.
+    ///
+    /// # Errors
+    /// - [CreateGroupError::UserDoesNotExist] if the user does not exist.
+    /// - [CreateGroupError::Unkown] for any other errors that may occur.
+    fn create_group(
+        &self,
+        req: &CreateGroupRequest,
+    ) -> impl Future<Output = Result<Group, CreateGroupError>> + Send;
+    /// Invites a user into a group.
+    ///
+    /// # Errors
+    /// - [InviteMemberError::GroupDoesNotExist] if the group does not exist.
+    /// - [InviteMemberError::NotAdmin] if the user is not an admin of the group.
+    /// - [InviteMemberError::UserDoesNotExist] if the invitee does not exist.
+    /// - [InviteMemberError::AlreadyMember] if the invitee is already a member.
+    /// - [InviteMemberError::Unkown] for any other errors that may occur.
+    fn invite_member(
+        &self,
+        req: &InviteMemberRequest,
+    ) -> impl Future<Output = Result<GroupInvitation, InviteMemberError>> + Send;
+    /// Lists a page of the pending invitations of a user, oldest first.
+    ///
+    /// # Errors
+    /// - [ListInvitationsError::Unkown] for any errors that may occur.
+    fn list_invitations(
+        &self,
+        req: &ListInvitationsRequest,
+    ) -> impl Future<Output = Result<Page<GroupInvitation>, ListInvitationsError>> + Send;
+    /// Accepts or declines an invitation. Accepting it makes the user a member.
+    ///
+    /// # Errors
+    /// - [RespondToInvitationError::InvitationDoesNotExist] if the invitation does not exist or
+    ///   is not for the user.
+    /// - [RespondToInvitationError::AlreadyAnswered] if the invitation is not pending.
+    /// - [RespondToInvitationError::GroupDoesNotExist] if the group no longer exists.
+    /// - [RespondToInvitationError::Unkown] for any other errors that may occur.
+    fn respond_to_invitation(
+        &self,
+        req: &RespondToInvitationRequest,
+    ) -> impl Future<Output = Result<GroupInvitation, RespondToInvitationError>> + Send;
+    /// Removes a member from a group. Admins remove anyone, members only leave.
+    ///
+    /// # Errors
+    /// - [ManageMemberError::GroupDoesNotExist] if the group does not exist.
+    /// - [ManageMemberError::NotAdmin] if a user other than an admin removes someone else.
+    /// - [ManageMemberError::Membership] if the member does not belong to the group or is its
+    ///   last admin.
+    /// - [ManageMemberError::Unkown] for any other errors that may occur.
+    fn remove_member(
+        &self,
+        req: &ManageMemberRequest,
+    ) -> impl Future<Output = Result<Group, ManageMemberError>> + Send;
+    /// Changes the role of a member.
+    ///
+    /// # Errors
+    /// - [ManageMemberError::GroupDoesNotExist] if the group does not exist.
+    /// - [ManageMemberError::NotAdmin] if the user is not an admin of the group.
+    /// - [ManageMemberError::Membership] if the member does not belong to the group or is its
+    ///   last admin.
+    /// - [ManageMemberError::Unkown] for any other errors that may occur.
+    fn set_member_role(
+        &self,
+        req: &SetMemberRoleRequest,
+    ) -> impl Future<Output = Result<Group, ManageMemberError>> + Send;
+    /// Shares one of the user's wishlists with a group they are a member of.
+    ///
+    /// # Errors
+    /// - [ShareWishlistError::GroupDoesNotExist] if the group does not exist.
+    /// - [ShareWishlistError::NotMember] if the user is not a member of the group.
+    /// - [ShareWishlistError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [ShareWishlistError::NotWishlistOwner] if the wishlist is not the user's.
+    /// - [ShareWishlistError::Unkown] for any other errors that may occur.
+    fn share_wishlist(
+        &self,
+        req: &ShareWishlistRequest,
+    ) -> impl Future<Output = Result<Group, ShareWishlistError>> + Send;
+    /// Stops sharing a wishlist with a group. Its owner and the admins of the group can do so.
+    ///
+    /// # Errors
+    /// - [ShareWishlistError::GroupDoesNotExist] if the group does not exist.
+    /// - [ShareWishlistError::NotMember] if the user is not a member of the group.
+    /// - [ShareWishlistError::NotShared] if the wishlist is not shared with the group.
+    /// - [ShareWishlistError::NotWishlistOwner] if the user is neither its owner nor an admin.
+    /// - [ShareWishlistError::Unkown] for any other errors that may occur.
+    fn unshare_wishlist(
+        &self,
+        req: &ShareWishlistRequest,
+    ) -> impl Future<Output = Result<Group, ShareWishlistError>> + Send;
+    /// Lists a page of the wishlists shared with a group by its current members that match the
+    /// request filter, most recently shared first.
+    ///
+    /// # Errors
+    /// - [GroupFeedError::GroupDoesNotExist] if the group does not exist.
+    /// - [GroupFeedError::NotMember] if the user is not a member of the group.
+    /// - [GroupFeedError::Unkown] for any other errors that may occur.
+    fn group_feed(
+        &self,
+        req: &GroupFeedRequest,
+    ) -> impl Future<Output = Result<Page<GroupFeedEntry>, GroupFeedError>> + Send;
+}
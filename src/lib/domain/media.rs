@@ -0,0 +1,7 @@
+mod blob;
+mod image_service;
+mod repository;
+
+pub use blob::*;
+pub use image_service::*;
+pub use repository::*;
@@ -0,0 +1,73 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+use thiserror::Error;
+
+/// The [BlobStore] trait defines the contract for storing opaque binary content by key.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait BlobStore: Send + Sync + 'static {
+    /// Stores a blob under `key`, replacing any previous content.
+    ///
+    /// # Errors
+    /// - [BlobStoreError::InvalidKey] if the key cannot be used by the store.
+    /// - [BlobStoreError::Unkown] for any other errors that may occur while writing.
+    fn put(
+        &self,
+        key: &str,
+        blob: &Blob,
+    ) -> impl Future<Output = Result<(), BlobStoreError>> + Send;
+    /// Reads the blob stored under `key`.
+    ///
+    /// # Returns
+    /// - `Ok(Some(blob))` if a blob is stored under the key.
+    /// - `Ok(None)` if no blob is stored under the key.
+    ///
+    /// # Errors
+    /// - [BlobStoreError::InvalidKey] if the key cannot be used by the store.
+    /// - [BlobStoreError::Unkown] for any other errors that may occur while reading.
+    fn get(&self, key: &str) -> impl Future<Output = Result<Option<Blob>, BlobStoreError>> + Send;
+    /// Deletes the blob stored under `key`. Deleting a key without a blob does nothing.
+    ///
+    /// # Errors
+    /// - [BlobStoreError::InvalidKey] if the key cannot be used by the store.
+    /// - [BlobStoreError::Unkown] for any other errors that may occur while deleting.
+    fn delete(&self, key: &str) -> impl Future<Output = Result<(), BlobStoreError>> + Send;
+}
+
+/// The [Blob] struct is binary content along with its media type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blob {
+    content_type: String,
+    bytes: Vec<u8>,
+}
+
+impl Blob {
+    pub fn new(content_type: &str, bytes: Vec<u8>) -> Self {
+        Self {
+            content_type: content_type.to_string(),
+            bytes,
+        }
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum BlobStoreError {
+    #[error("Blob key {key} is invalid")]
+    InvalidKey { key: String },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
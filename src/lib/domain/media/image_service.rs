@@ -0,0 +1,265 @@
+use std::{
+    fmt::{Display, Formatter},
+    future::Future,
+    str::FromStr,
+};
+
+#[cfg(test)]
+use mockall::automock;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::Blob;
+use crate::domain::{ForgetUserError, ItemImageUrl};
+
+/// The [ImageService] trait defines the contract for storing images on our side, so they are
+/// served from our own routes instead of being hotlinked.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ImageService: Send + Sync + 'static {
+    /// Stores an image uploaded by a user, along with its thumbnails, and records them as its
+    /// owner.
+    ///
+    /// # Errors
+    /// - [StoreImageError::TooLarge] if the image exceeds the upload size limit.
+    /// - [StoreImageError::DimensionsTooLarge] if the image exceeds the dimension limit.
+    /// - [StoreImageError::Unsupported] if the content is not an image in a supported format.
+    /// - [StoreImageError::Unkown] for any other errors that may occur while storing.
+    fn upload_image(
+        &self,
+        req: &UploadImageRequest,
+    ) -> impl Future<Output = Result<StoredImage, StoreImageError>> + Send;
+    /// Downloads a remote image and stores a copy of it, along with its thumbnails, on behalf of
+    /// `owner_id`.
+    ///
+    /// # Errors
+    /// - [StoreImageError::TooLarge] if the image exceeds the upload size limit.
+    /// - [StoreImageError::DimensionsTooLarge] if the image exceeds the dimension limit.
+    /// - [StoreImageError::Unsupported] if the content is not an image in a supported format.
+    /// - [StoreImageError::Unkown] if the image cannot be fetched or stored.
+    fn mirror_image(
+        &self,
+        url: &ItemImageUrl,
+        owner_id: Uuid,
+    ) -> impl Future<Output = Result<StoredImage, StoreImageError>> + Send;
+    /// Finds a stored image in the given size.
+    ///
+    /// # Returns
+    /// - `Ok(Some(blob))` if the image exists.
+    /// - `Ok(None)` if no image with the given ID exists.
+    ///
+    /// # Errors
+    /// - [FindImageError::Unkown] for any errors that may occur during the search.
+    fn find_image(
+        &self,
+        req: &FindImageRequest,
+    ) -> impl Future<Output = Result<Option<Blob>, FindImageError>> + Send;
+    /// Deletes every size of the images stored by a user. Can be run again if interrupted.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur while deleting.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+/// The [ImageProcessor] trait defines the contract for validating images and deriving their
+/// thumbnails.
+#[cfg_attr(test, automock)]
+pub trait ImageProcessor: Send + Sync + 'static {
+    /// Sniffs the format of `bytes` and re-encodes the image in every [ImageSize].
+    ///
+    /// # Errors
+    /// - [ProcessImageError::Unsupported] if the content is not an image in a supported format.
+    /// - [ProcessImageError::TooLarge] if the image dimensions exceed the configured limit.
+    /// - [ProcessImageError::Unkown] for any other errors that may occur while processing.
+    fn process(&self, bytes: &[u8]) -> Result<Vec<(ImageSize, Blob)>, ProcessImageError>;
+}
+
+/// The [ImageFetcher] trait defines the contract for downloading a remote image.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ImageFetcher: Send + Sync + 'static {
+    /// Downloads the image behind `url`, reading at most `max_bytes`.
+    ///
+    /// # Errors
+    /// - [FetchImageError::Forbidden] if the URL targets a host that may not be fetched.
+    /// - [FetchImageError::TooLarge] if the image is larger than `max_bytes`.
+    /// - [FetchImageError::Timeout] if the image could not be fetched in time.
+    /// - [FetchImageError::Unknown] for any other errors that may occur while fetching.
+    fn fetch_image(
+        &self,
+        url: &ItemImageUrl,
+        max_bytes: usize,
+    ) -> impl Future<Output = Result<Vec<u8>, FetchImageError>> + Send;
+}
+
+/// The sizes every stored image is available in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageSize {
+    Original,
+    Large,
+    Medium,
+    Small,
+}
+
+impl ImageSize {
+    pub const ALL: [ImageSize; 4] = [
+        ImageSize::Original,
+        ImageSize::Large,
+        ImageSize::Medium,
+        ImageSize::Small,
+    ];
+
+    /// The maximum width and height of the size, if it is a thumbnail.
+    pub fn max_dimension(&self) -> Option<u32> {
+        match self {
+            ImageSize::Original => None,
+            ImageSize::Large => Some(1024),
+            ImageSize::Medium => Some(512),
+            ImageSize::Small => Some(128),
+        }
+    }
+}
+
+impl Display for ImageSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ImageSize::Original => "original",
+            ImageSize::Large => "large",
+            ImageSize::Medium => "medium",
+            ImageSize::Small => "small",
+        })
+    }
+}
+
+impl FromStr for ImageSize {
+    type Err = ImageSizeInvalidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ImageSize::ALL
+            .into_iter()
+            .find(|size| size.to_string() == value)
+            .ok_or_else(|| ImageSizeInvalidError {
+                invalid_size: value.to_string(),
+            })
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Image size is invalid")]
+pub struct ImageSizeInvalidError {
+    pub invalid_size: String,
+}
+
+/// The [StoredImage] struct identifies an image stored on our side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredImage {
+    id: Uuid,
+}
+
+impl StoredImage {
+    pub fn new(id: Uuid) -> Self {
+        Self { id }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+/// The [UploadImageRequest] struct represents a request to store an image uploaded by a user.
+#[derive(Debug, Clone)]
+pub struct UploadImageRequest {
+    user_id: Uuid,
+    bytes: Vec<u8>,
+}
+
+impl UploadImageRequest {
+    pub fn new(user_id: Uuid, bytes: Vec<u8>) -> Self {
+        Self { user_id, bytes }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum StoreImageError {
+    #[error("Image is larger than {limit} bytes")]
+    TooLarge { limit: usize },
+    #[error("Image is larger than {limit} pixels wide or high")]
+    DimensionsTooLarge { limit: u32 },
+    #[error("Image format is not supported")]
+    Unsupported,
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [FindImageRequest] struct represents a request to read a stored image in a given size.
+#[derive(Debug, Clone)]
+pub struct FindImageRequest {
+    id: Uuid,
+    size: ImageSize,
+}
+
+impl FindImageRequest {
+    pub fn new(id: Uuid, size: ImageSize) -> Self {
+        Self { id, size }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn size(&self) -> ImageSize {
+        self.size
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FindImageError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ProcessImageError {
+    #[error("Image format is not supported")]
+    Unsupported,
+    #[error("Image is larger than {limit} pixels wide or high")]
+    TooLarge { limit: u32 },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FetchImageError {
+    #[error("Fetching {url} is not allowed")]
+    Forbidden { url: String },
+    #[error("Image is larger than {limit} bytes")]
+    TooLarge { limit: usize },
+    #[error("Fetching the image timed out")]
+    Timeout,
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImageSize;
+
+    #[test]
+    fn image_size_round_trip() {
+        for size in ImageSize::ALL {
+            assert_eq!(size.to_string().parse::<ImageSize>().unwrap(), size);
+        }
+        assert!("huge".parse::<ImageSize>().is_err());
+    }
+}
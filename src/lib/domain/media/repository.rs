@@ -0,0 +1,53 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::ForgetUserError;
+
+/// The [ImageRepository] trait defines the contract for recording who stored each image, so that
+/// their images can be found again when their account is purged.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ImageRepository: Send + Sync + 'static {
+    /// Records that the image `image_id` was stored by `owner_id`.
+    ///
+    /// # Errors
+    /// - [SaveImageOwnerError::Unkown] for any errors that may occur while saving.
+    fn save_owner(
+        &self,
+        image_id: Uuid,
+        owner_id: Uuid,
+    ) -> impl Future<Output = Result<(), SaveImageOwnerError>> + Send;
+    /// Finds the IDs of the images stored by a user.
+    ///
+    /// # Errors
+    /// - [FindImageOwnerError::Unkown] for any errors that may occur during the search.
+    fn find_images_by_owner(
+        &self,
+        owner_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Uuid>, FindImageOwnerError>> + Send;
+    /// Forgets the images stored by a user. Their blobs are left to the
+    /// [BlobStore](crate::domain::BlobStore).
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum SaveImageOwnerError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FindImageOwnerError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
@@ -1,5 +1,27 @@
+mod account;
+mod clock;
+mod exchange;
+mod follow;
+mod group;
+mod media;
+mod notification;
+mod pagination;
+mod profile;
 mod user;
+mod version;
+mod webhook;
 mod wishlist;
 
+pub use account::*;
+pub use clock::*;
+pub use exchange::*;
+pub use follow::*;
+pub use group::*;
+pub use media::*;
+pub use notification::*;
+pub use pagination::*;
+pub use profile::*;
 pub use user::*;
+pub use version::*;
+pub use webhook::*;
 pub use wishlist::*;
@@ -0,0 +1,123 @@
+mod inbox;
+mod mail;
+mod reminder;
+mod service;
+
+use std::future::Future;
+
+use chrono::NaiveDate;
+pub use inbox::*;
+pub use mail::*;
+#[cfg(test)]
+use mockall::automock;
+pub use reminder::*;
+pub use service::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::{ItemPrice, ItemTitle, OccasionKind, WishlistName};
+
+/// The [Notifier] trait defines the contract for delivering [Notification]s to users.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait Notifier: Send + Sync + 'static {
+    /// Delivers a notification to its recipient.
+    ///
+    /// # Arguments
+    /// * `notification` - A reference to the `Notification` to deliver.
+    ///
+    /// # Errors
+    /// - [NotifyError::Unkown] for any errors that may occur during delivery.
+    fn notify(
+        &self,
+        notification: &Notification,
+    ) -> impl Future<Output = Result<(), NotifyError>> + Send;
+}
+
+/// The [Notification] struct represents a message addressed to a single user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    recipient_id: Uuid,
+    kind: NotificationKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationKind {
+    PriceDrop {
+        item_id: Uuid,
+        title: ItemTitle,
+        previous_price: ItemPrice,
+        current_price: ItemPrice,
+    },
+    OccasionReminder {
+        wishlist_id: Uuid,
+        wishlist_name: WishlistName,
+        occasion: OccasionKind,
+        date: NaiveDate,
+        days_left: i64,
+    },
+}
+
+impl NotificationKind {
+    /// A one-line summary, used as the subject of emails.
+    pub fn subject(&self) -> String {
+        match self {
+            NotificationKind::PriceDrop { title, .. } => format!("Price drop on {}", title),
+            NotificationKind::OccasionReminder {
+                wishlist_name,
+                days_left,
+                ..
+            } => match days_left {
+                0 => format!("{} is today", wishlist_name),
+                1 => format!("{} is tomorrow", wishlist_name),
+                days => format!("{} is in {} days", wishlist_name, days),
+            },
+        }
+    }
+
+    /// The full text of the notification.
+    pub fn message(&self) -> String {
+        match self {
+            NotificationKind::PriceDrop {
+                title,
+                previous_price,
+                current_price,
+                ..
+            } => format!(
+                "The price of {} dropped from {} to {}.",
+                title, previous_price, current_price
+            ),
+            NotificationKind::OccasionReminder {
+                wishlist_name,
+                occasion,
+                date,
+                ..
+            } => format!(
+                "Reminder: the wishlist {} is for a {} on {}.",
+                wishlist_name,
+                occasion.to_string().replace('_', " "),
+                date
+            ),
+        }
+    }
+}
+
+impl Notification {
+    pub fn new(recipient_id: Uuid, kind: NotificationKind) -> Self {
+        Self { recipient_id, kind }
+    }
+
+    pub fn recipient_id(&self) -> Uuid {
+        self.recipient_id
+    }
+
+    pub fn kind(&self) -> &NotificationKind {
+        &self.kind
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum NotifyError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
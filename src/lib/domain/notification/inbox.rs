@@ -0,0 +1,111 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::{ForgetUserError, Page, PageRequest};
+
+use super::{Notification, NotificationKind};
+
+/// The [InboxRepository] trait defines the contract for storing the notifications users read in
+/// the app.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait InboxRepository: Send + Sync + 'static {
+    /// Stores a notification in the inbox of its recipient.
+    ///
+    /// # Errors
+    /// - [SaveInboxMessageError::Unkown] for any errors that may occur while saving.
+    fn save(
+        &self,
+        notification: &Notification,
+        created_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<InboxMessage, SaveInboxMessageError>> + Send;
+    /// Finds a page of the messages in the inbox of a user, newest first.
+    ///
+    /// # Errors
+    /// - [ListInboxError::Unkown] for any errors that may occur during the search.
+    fn find_messages(
+        &self,
+        req: &ListInboxRequest,
+    ) -> impl Future<Output = Result<Page<InboxMessage>, ListInboxError>> + Send;
+    /// Deletes the inbox of a user.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+/// The [InboxMessage] struct is a [Notification] delivered to the in-app inbox of its recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboxMessage {
+    id: Uuid,
+    notification: Notification,
+    created_at: DateTime<Utc>,
+}
+
+impl InboxMessage {
+    pub fn new(id: Uuid, notification: Notification, created_at: DateTime<Utc>) -> Self {
+        Self {
+            id,
+            notification,
+            created_at,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn recipient_id(&self) -> Uuid {
+        self.notification.recipient_id()
+    }
+
+    pub fn kind(&self) -> &NotificationKind {
+        self.notification.kind()
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+/// The [ListInboxRequest] struct represents a request by a user to read a page of their inbox,
+/// newest first.
+#[derive(Debug, Clone)]
+pub struct ListInboxRequest {
+    user_id: Uuid,
+    page: PageRequest,
+}
+
+impl ListInboxRequest {
+    pub fn new(user_id: Uuid, page: PageRequest) -> Self {
+        Self { user_id, page }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn page(&self) -> &PageRequest {
+        &self.page
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SaveInboxMessageError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ListInboxError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
@@ -0,0 +1,50 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+use thiserror::Error;
+
+use crate::domain::UserEmail;
+
+/// The [MailTransport] trait defines the contract for sending emails.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait MailTransport: Send + Sync + 'static {
+    /// Sends an email to its recipient.
+    ///
+    /// # Errors
+    /// - [SendMailError::Unkown] for any errors that may occur while sending.
+    fn send(&self, email: &Email) -> impl Future<Output = Result<(), SendMailError>> + Send;
+}
+
+/// The [Email] struct is a plain-text email addressed to a single recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email {
+    to: UserEmail,
+    subject: String,
+    body: String,
+}
+
+impl Email {
+    pub fn new(to: UserEmail, subject: String, body: String) -> Self {
+        Self { to, subject, body }
+    }
+
+    pub fn to(&self) -> &UserEmail {
+        &self.to
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SendMailError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
@@ -0,0 +1,425 @@
+use std::future::Future;
+
+use chrono::{DateTime, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+#[cfg(test)]
+use mockall::automock;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::ForgetUserError;
+
+use crate::domain::Occasion;
+
+/// The [ReminderRepository] trait defines the contract for storing reminder subscriptions and
+/// the reminder preferences of users.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ReminderRepository: Send + Sync + 'static {
+    /// Saves a subscription, or returns the existing one if the user already subscribed to the
+    /// same wishlist with the same lead time.
+    ///
+    /// # Errors
+    /// - [SubscribeReminderError::Unkown] for any errors that may occur while saving.
+    fn save_subscription(
+        &self,
+        req: &SubscribeReminderRequest,
+    ) -> impl Future<Output = Result<ReminderSubscription, SubscribeReminderError>> + Send;
+    /// Deletes a subscription of a user and returns it.
+    ///
+    /// # Errors
+    /// - [UnsubscribeReminderError::ReminderDoesNotExist] if the user has no such subscription.
+    /// - [UnsubscribeReminderError::Unkown] for any other errors that may occur.
+    fn delete_subscription(
+        &self,
+        req: &UnsubscribeReminderRequest,
+    ) -> impl Future<Output = Result<ReminderSubscription, UnsubscribeReminderError>> + Send;
+    /// Finds every subscription.
+    ///
+    /// # Errors
+    /// - [FindRemindersError::Unkown] for any errors that may occur during the search.
+    fn find_subscriptions(
+        &self,
+    ) -> impl Future<Output = Result<Vec<ReminderSubscription>, FindRemindersError>> + Send;
+    /// Records that the reminder of a subscription was sent for the occasion on `date`.
+    ///
+    /// # Errors
+    /// - [MarkReminderSentError::ReminderDoesNotExist] if the subscription does not exist.
+    /// - [MarkReminderSentError::Unkown] for any other errors that may occur.
+    fn mark_sent(
+        &self,
+        subscription_id: Uuid,
+        date: NaiveDate,
+    ) -> impl Future<Output = Result<(), MarkReminderSentError>> + Send;
+    /// Saves the reminder preferences of a user, replacing the previous ones.
+    ///
+    /// # Errors
+    /// - [SetReminderPreferencesError::Unkown] for any errors that may occur while saving.
+    fn save_preferences(
+        &self,
+        preferences: &ReminderPreferences,
+    ) -> impl Future<Output = Result<ReminderPreferences, SetReminderPreferencesError>> + Send;
+    /// Finds the reminder preferences of a user, if they set any.
+    ///
+    /// # Errors
+    /// - [FindReminderPreferencesError::Unkown] for any errors that may occur during the search.
+    fn find_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<ReminderPreferences>, FindReminderPreferencesError>> + Send;
+    /// Deletes the reminder subscriptions and preferences of a user.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+/// The [ReminderSubscription] struct is a user's subscription to be reminded of the occasion of a
+/// wishlist `days_before` days ahead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderSubscription {
+    id: Uuid,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+    days_before: u32,
+    last_sent_for: Option<NaiveDate>,
+}
+
+impl ReminderSubscription {
+    pub fn new(id: Uuid, user_id: Uuid, wishlist_id: Uuid, days_before: u32) -> Self {
+        Self {
+            id,
+            user_id,
+            wishlist_id,
+            days_before,
+            last_sent_for: None,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn days_before(&self) -> u32 {
+        self.days_before
+    }
+
+    /// The date of the last occasion a reminder was sent for, if any.
+    pub fn last_sent_for(&self) -> Option<NaiveDate> {
+        self.last_sent_for
+    }
+
+    pub fn set_last_sent_for(&mut self, date: Option<NaiveDate>) {
+        self.last_sent_for = date;
+    }
+
+    /// The instant the reminder of `occasion` becomes due: the start of the day `days_before`
+    /// days ahead of it in the user's time zone, or the end of the user's quiet hours if it
+    /// starts within them.
+    pub fn due_at(&self, occasion: &Occasion, preferences: &ReminderPreferences) -> DateTime<Utc> {
+        let day = occasion
+            .date()
+            .checked_sub_days(Days::new(self.days_before.into()))
+            .unwrap_or(NaiveDate::MIN);
+        preferences.deliverable_from(day.and_time(NaiveTime::MIN))
+    }
+
+    /// Returns true if the reminder of `occasion` should be sent at `now`: it is due, was not
+    /// sent yet, the occasion is not over and `now` is outside the user's quiet hours.
+    pub fn is_due(
+        &self,
+        occasion: &Occasion,
+        preferences: &ReminderPreferences,
+        now: DateTime<Utc>,
+    ) -> bool {
+        self.last_sent_for != Some(occasion.date())
+            && !occasion.is_over(now)
+            && self.due_at(occasion, preferences) <= now
+            && !preferences.is_quiet(now)
+    }
+}
+
+/// The [ReminderPreferences] struct is how a user wants to receive reminders: the time zone their
+/// days are counted in, and the hours they do not want to be disturbed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderPreferences {
+    user_id: Uuid,
+    time_zone: Tz,
+    quiet_hours: Option<QuietHours>,
+}
+
+impl ReminderPreferences {
+    pub fn new(user_id: Uuid, time_zone: Tz, quiet_hours: Option<QuietHours>) -> Self {
+        Self {
+            user_id,
+            time_zone,
+            quiet_hours,
+        }
+    }
+
+    /// The preferences of users who did not set any: UTC, without quiet hours.
+    pub fn default_for(user_id: Uuid) -> Self {
+        Self::new(user_id, Tz::UTC, None)
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn time_zone(&self) -> Tz {
+        self.time_zone
+    }
+
+    pub fn quiet_hours(&self) -> Option<QuietHours> {
+        self.quiet_hours
+    }
+
+    /// Returns true if `now` falls within the user's quiet hours.
+    pub fn is_quiet(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.time_zone).time();
+        self.quiet_hours
+            .is_some_and(|quiet_hours| quiet_hours.contains(local))
+    }
+
+    /// The first instant from the local time `local` that is outside the user's quiet hours.
+    pub fn deliverable_from(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        let local = match self.quiet_hours {
+            Some(quiet_hours) if quiet_hours.contains(local.time()) => {
+                let end = local.date().and_time(quiet_hours.end());
+                if end < local {
+                    end + Days::new(1)
+                } else {
+                    end
+                }
+            }
+            _ => local,
+        };
+        self.time_zone
+            .from_local_datetime(&local)
+            .earliest()
+            .unwrap_or_else(|| self.time_zone.from_utc_datetime(&local))
+            .with_timezone(&Utc)
+    }
+}
+
+/// The [QuietHours] struct is a daily period without reminders, from `start` until `end`. It
+/// spans midnight when `end` is before `start`, e.g. 22:00 to 08:00.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Result<Self, QuietHoursInvalidError> {
+        if start == end {
+            return Err(QuietHoursInvalidError { start, end });
+        }
+        Ok(Self { start, end })
+    }
+
+    pub fn start(&self) -> NaiveTime {
+        self.start
+    }
+
+    pub fn end(&self) -> NaiveTime {
+        self.end
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Quiet hours from {start} to {end} are invalid")]
+pub struct QuietHoursInvalidError {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+/// The [SubscribeReminderRequest] struct represents a request by a user to be reminded of the
+/// occasion of a wishlist.
+#[derive(Debug, Clone)]
+pub struct SubscribeReminderRequest {
+    user_id: Uuid,
+    wishlist_id: Uuid,
+    days_before: u32,
+}
+
+impl SubscribeReminderRequest {
+    pub fn new(user_id: Uuid, wishlist_id: Uuid, days_before: u32) -> Self {
+        Self {
+            user_id,
+            wishlist_id,
+            days_before,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn days_before(&self) -> u32 {
+        self.days_before
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SubscribeReminderError {
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [UnsubscribeReminderRequest] struct represents a request by a user to cancel one of their
+/// reminder subscriptions.
+#[derive(Debug, Clone)]
+pub struct UnsubscribeReminderRequest {
+    reminder_id: Uuid,
+    user_id: Uuid,
+}
+
+impl UnsubscribeReminderRequest {
+    pub fn new(reminder_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            reminder_id,
+            user_id,
+        }
+    }
+
+    pub fn reminder_id(&self) -> Uuid {
+        self.reminder_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum UnsubscribeReminderError {
+    #[error("Reminder with id {id} does not exist")]
+    ReminderDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FindRemindersError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum MarkReminderSentError {
+    #[error("Reminder with id {id} does not exist")]
+    ReminderDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SetReminderPreferencesError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FindReminderPreferencesError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SendRemindersError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::OccasionKind;
+
+    use super::*;
+
+    fn time(value: &str) -> NaiveTime {
+        value.parse().unwrap()
+    }
+
+    fn utc(instant: &str) -> DateTime<Utc> {
+        instant.parse().unwrap()
+    }
+
+    #[test]
+    fn quiet_hours_across_midnight() {
+        assert!(QuietHours::new(time("08:00:00"), time("08:00:00")).is_err());
+        let night = QuietHours::new(time("22:00:00"), time("08:00:00")).unwrap();
+        assert!(night.contains(time("23:30:00")));
+        assert!(night.contains(time("07:59:59")));
+        assert!(!night.contains(time("08:00:00")));
+        let lunch = QuietHours::new(time("12:00:00"), time("14:00:00")).unwrap();
+        assert!(lunch.contains(time("13:00:00")));
+        assert!(!lunch.contains(time("23:00:00")));
+    }
+
+    #[test]
+    fn due_after_quiet_hours_in_time_zone() {
+        let occasion = Occasion::new(
+            OccasionKind::Birthday,
+            "2027-03-14".parse().unwrap(),
+            chrono_tz::Europe::Paris,
+            None,
+        );
+        let reminder = ReminderSubscription::new(Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7(), 3);
+
+        let utc_prefs = ReminderPreferences::default_for(reminder.user_id());
+        assert_eq!(
+            reminder.due_at(&occasion, &utc_prefs),
+            utc("2027-03-11T00:00:00Z")
+        );
+
+        let quiet = QuietHours::new(time("22:00:00"), time("08:00:00")).unwrap();
+        let prefs = ReminderPreferences::new(
+            reminder.user_id(),
+            chrono_tz::America::New_York,
+            Some(quiet),
+        );
+        // 08:00 in New York, after the night.
+        assert_eq!(
+            reminder.due_at(&occasion, &prefs),
+            utc("2027-03-11T13:00:00Z")
+        );
+        assert!(!reminder.is_due(&occasion, &prefs, utc("2027-03-11T12:59:59Z")));
+        assert!(reminder.is_due(&occasion, &prefs, utc("2027-03-11T13:00:00Z")));
+        // 22:00 in New York, the next night.
+        assert!(!reminder.is_due(&occasion, &prefs, utc("2027-03-12T03:00:00Z")));
+
+        let mut sent = reminder.clone();
+        sent.set_last_sent_for(Some(occasion.date()));
+        assert!(!sent.is_due(&occasion, &prefs, utc("2027-03-12T15:00:00Z")));
+        assert!(!reminder.is_due(&occasion, &prefs, utc("2027-03-15T15:00:00Z")));
+    }
+}
@@ -0,0 +1,63 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::domain::Page;
+
+use super::{
+    InboxMessage, ListInboxError, ListInboxRequest, ReminderPreferences, ReminderSubscription,
+    SendRemindersError, SetReminderPreferencesError, SubscribeReminderError,
+    SubscribeReminderRequest, UnsubscribeReminderError, UnsubscribeReminderRequest,
+};
+
+/// The [NotificationService] trait defines the contract for reminders and the in-app inbox.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait NotificationService: Send + Sync + 'static {
+    /// Subscribes a user to reminders of the occasion of a wishlist.
+    ///
+    /// # Errors
+    /// - [SubscribeReminderError::UserDoesNotExist] if the user does not exist.
+    /// - [SubscribeReminderError::WishlistDoesNotExist] if the wishlist does not exist, or is
+    ///   private and not the user's.
+    /// - [SubscribeReminderError::Unkown] for any other errors that may occur.
+    fn subscribe_reminder(
+        &self,
+        req: &SubscribeReminderRequest,
+    ) -> impl Future<Output = Result<ReminderSubscription, SubscribeReminderError>> + Send;
+    /// Cancels a reminder subscription of a user.
+    ///
+    /// # Errors
+    /// - [UnsubscribeReminderError::ReminderDoesNotExist] if the user has no such subscription.
+    /// - [UnsubscribeReminderError::Unkown] for any other errors that may occur.
+    fn unsubscribe_reminder(
+        &self,
+        req: &UnsubscribeReminderRequest,
+    ) -> impl Future<Output = Result<ReminderSubscription, UnsubscribeReminderError>> + Send;
+    /// Sets the time zone and quiet hours of a user's reminders.
+    ///
+    /// # Errors
+    /// - [SetReminderPreferencesError::Unkown] for any errors that may occur.
+    fn set_reminder_preferences(
+        &self,
+        preferences: &ReminderPreferences,
+    ) -> impl Future<Output = Result<ReminderPreferences, SetReminderPreferencesError>> + Send;
+    /// Lists a page of the messages in the inbox of a user, newest first.
+    ///
+    /// # Errors
+    /// - [ListInboxError::Unkown] for any errors that may occur during the search.
+    fn list_inbox(
+        &self,
+        req: &ListInboxRequest,
+    ) -> impl Future<Output = Result<Page<InboxMessage>, ListInboxError>> + Send;
+    /// Sends the reminders that are due, once per occasion, and skips the others until the next
+    /// run. See [ReminderSubscription::is_due].
+    ///
+    /// Reminders that cannot be delivered are retried on the next run.
+    ///
+    /// # Errors
+    /// - [SendRemindersError::Unkown] if the subscriptions or wishlists cannot be read or
+    ///   written.
+    fn send_due_reminders(&self) -> impl Future<Output = Result<(), SendRemindersError>> + Send;
+}
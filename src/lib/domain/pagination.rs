@@ -0,0 +1,317 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    pub fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    }
+}
+
+/// A value entries of a listing are sorted on. A listing sorts every entry on the same kind of
+/// value.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SortValue {
+    Integer(i64),
+    Decimal(Decimal),
+    Text(String),
+    Time(DateTime<Utc>),
+    Id(Uuid),
+}
+
+/// The position of an entry in a sorted listing: the value it is sorted on, if it has one, and
+/// its ID to break ties. The cursor of a page is the position of its last entry, which clients
+/// get as an opaque string to pass back for the next page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    value: Option<SortValue>,
+    id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(value: Option<SortValue>, id: Uuid) -> Self {
+        Self { value, id }
+    }
+
+    pub fn value(&self) -> Option<&SortValue> {
+        self.value.as_ref()
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Orders two positions on their value in `direction`, positions without a value last
+    /// whatever the direction, then on their ID.
+    pub fn compare(&self, other: &Self, direction: SortDirection) -> Ordering {
+        let ordering = match (&self.value, &other.value) {
+            (Some(a), Some(b)) => direction.apply(a.cmp(b)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        ordering.then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match &self.value {
+            None => "n".to_string(),
+            Some(SortValue::Integer(value)) => format!("i{}", value),
+            Some(SortValue::Decimal(value)) => format!("d{}", value),
+            Some(SortValue::Text(value)) => format!("t{}", value),
+            Some(SortValue::Time(value)) => format!("m{}", value.timestamp_micros()),
+            Some(SortValue::Id(value)) => format!("u{}", value.simple()),
+        };
+        let position = format!("{}:{}", self.id.simple(), value);
+        f.write_str(&URL_SAFE_NO_PAD.encode(position))
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = CursorInvalidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CursorInvalidError(s.to_string());
+        let position = URL_SAFE_NO_PAD
+            .decode(s)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(invalid)?;
+        let (id, value) = position.split_once(':').ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+        let mut chars = value.chars();
+        let tag = chars.next().ok_or_else(invalid)?;
+        let value = chars.as_str();
+        let value = match tag {
+            'n' if value.is_empty() => None,
+            'i' => Some(SortValue::Integer(value.parse().map_err(|_| invalid())?)),
+            'd' => Some(SortValue::Decimal(value.parse().map_err(|_| invalid())?)),
+            't' => Some(SortValue::Text(value.to_string())),
+            'm' => Some(SortValue::Time(
+                value
+                    .parse()
+                    .ok()
+                    .and_then(DateTime::from_timestamp_micros)
+                    .ok_or_else(invalid)?,
+            )),
+            'u' => Some(SortValue::Id(
+                Uuid::parse_str(value).map_err(|_| invalid())?,
+            )),
+            _ => return Err(invalid()),
+        };
+        Ok(Self::new(value, id))
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Cursor {0} is invalid")]
+pub struct CursorInvalidError(pub String);
+
+/// The number of entries in a page, between 1 and [PageLimit::MAX].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageLimit(usize);
+
+impl PageLimit {
+    pub const DEFAULT: usize = 20;
+    pub const MAX: usize = 100;
+
+    pub fn new(limit: usize) -> Result<Self, PageLimitInvalidError> {
+        if limit == 0 || limit > Self::MAX {
+            return Err(PageLimitInvalidError(limit));
+        }
+        Ok(Self(limit))
+    }
+
+    pub fn get(&self) -> usize {
+        self.0
+    }
+}
+
+impl Default for PageLimit {
+    fn default() -> Self {
+        Self(Self::DEFAULT)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Limit {0} is not between 1 and 100")]
+pub struct PageLimitInvalidError(pub usize);
+
+/// Which page of a listing to read: the one after `cursor`, or the first one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageRequest {
+    cursor: Option<Cursor>,
+    limit: PageLimit,
+}
+
+impl PageRequest {
+    pub fn new(cursor: Option<Cursor>, limit: PageLimit) -> Self {
+        Self { cursor, limit }
+    }
+
+    pub fn cursor(&self) -> Option<&Cursor> {
+        self.cursor.as_ref()
+    }
+
+    pub fn limit(&self) -> PageLimit {
+        self.limit
+    }
+
+    /// Returns true if the entry at `position` comes after the cursor of the request.
+    pub fn follows(&self, position: &Cursor, direction: SortDirection) -> bool {
+        self.cursor
+            .as_ref()
+            .is_none_or(|cursor| position.compare(cursor, direction).is_gt())
+    }
+
+    /// Takes the requested page out of unsorted `entries`, ordered on their `position` in
+    /// `direction`. Only the entries of the page are sorted, so adapters without an index to
+    /// seek with still read a page in linear time.
+    pub fn paginate<T>(
+        &self,
+        entries: impl IntoIterator<Item = T>,
+        direction: SortDirection,
+        position: impl Fn(&T) -> Cursor,
+    ) -> Page<T> {
+        let limit = self.limit.get();
+        let compare = |(a, _): &(Cursor, T), (b, _): &(Cursor, T)| a.compare(b, direction);
+        let mut entries: Vec<(Cursor, T)> = entries
+            .into_iter()
+            .map(|entry| (position(&entry), entry))
+            .filter(|(position, _)| self.follows(position, direction))
+            .collect();
+        // One entry past the page tells whether there is a next page.
+        if entries.len() > limit + 1 {
+            entries.select_nth_unstable_by(limit, compare);
+            entries.truncate(limit + 1);
+        }
+        entries.sort_by(compare);
+        let next_cursor = if entries.len() > limit {
+            entries.truncate(limit);
+            entries.last().map(|(position, _)| position.clone())
+        } else {
+            None
+        };
+        Page::new(
+            entries.into_iter().map(|(_, entry)| entry).collect(),
+            next_cursor,
+        )
+    }
+}
+
+/// A page of a listing, with the cursor of the next page if there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    entries: Vec<T>,
+    next_cursor: Option<Cursor>,
+}
+
+impl<T> Page<T> {
+    pub fn new(entries: Vec<T>, next_cursor: Option<Cursor>) -> Self {
+        Self {
+            entries,
+            next_cursor,
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(Vec::new(), None)
+    }
+
+    pub fn entries(&self) -> &[T] {
+        &self.entries
+    }
+
+    pub fn next_cursor(&self) -> Option<&Cursor> {
+        self.next_cursor.as_ref()
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page::new(self.entries.into_iter().map(f).collect(), self.next_cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_round_trip() {
+        let id = Uuid::now_v7();
+        let values = [
+            None,
+            Some(SortValue::Integer(-3)),
+            Some(SortValue::Decimal(Decimal::new(1299, 2))),
+            Some(SortValue::Text("size: m".to_string())),
+            Some(SortValue::Time(
+                "2027-12-01T09:00:00.123456Z".parse().unwrap(),
+            )),
+            Some(SortValue::Id(Uuid::now_v7())),
+        ];
+        for value in values {
+            let cursor = Cursor::new(value, id);
+            assert_eq!(cursor.to_string().parse::<Cursor>().unwrap(), cursor);
+        }
+        assert!("12345".parse::<Cursor>().is_err());
+        assert!(URL_SAFE_NO_PAD
+            .encode(format!("{}:x1", id.simple()))
+            .parse::<Cursor>()
+            .is_err());
+    }
+
+    #[test]
+    fn limit_bounds() {
+        assert!(PageLimit::new(0).is_err());
+        assert!(PageLimit::new(101).is_err());
+        assert_eq!(PageLimit::new(100).unwrap().get(), 100);
+        assert_eq!(PageLimit::default().get(), PageLimit::DEFAULT);
+    }
+
+    #[test]
+    fn paginate_through_every_entry() {
+        // Prices, with ties and missing ones, which come last in either direction.
+        let entries: Vec<(Uuid, Option<i64>)> = [Some(3), None, Some(1), Some(3), Some(2), None]
+            .into_iter()
+            .map(|price| (Uuid::now_v7(), price))
+            .collect();
+        let position =
+            |(id, price): &(Uuid, Option<i64>)| Cursor::new(price.map(SortValue::Integer), *id);
+        for direction in [SortDirection::Ascending, SortDirection::Descending] {
+            let mut expected = entries.clone();
+            expected.sort_by(|a, b| position(a).compare(&position(b), direction));
+
+            let mut listed = Vec::new();
+            let mut req = PageRequest::new(None, PageLimit::new(4).unwrap());
+            loop {
+                let page = req.paginate(entries.clone(), direction, position);
+                assert!(page.entries().len() <= 4);
+                listed.extend_from_slice(page.entries());
+                match page.next_cursor() {
+                    Some(cursor) => req = PageRequest::new(Some(cursor.clone()), req.limit()),
+                    None => break,
+                }
+            }
+            assert_eq!(listed, expected);
+            assert!(listed[4..].iter().all(|(_, price)| price.is_none()));
+        }
+    }
+}
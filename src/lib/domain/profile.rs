@@ -0,0 +1,569 @@
+mod repository;
+mod service;
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use thiserror::Error;
+use uuid::Uuid;
+
+pub use repository::*;
+pub use service::*;
+
+use super::{Occasion, OccasionKind, Recurrence};
+
+/// The public face of a user: how they are named and pictured on shared lists, their birthday,
+/// and where gifts are shipped. Every field is optional and shown according to
+/// [ProfileVisibility].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    user_id: Uuid,
+    display_name: Option<DisplayName>,
+    avatar_id: Option<Uuid>,
+    birthday: Option<NaiveDate>,
+    shipping_address: Option<ShippingAddress>,
+    visibility: ProfileVisibility,
+}
+
+impl Profile {
+    /// Creates an empty profile, with the default visibility.
+    pub fn new(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            display_name: None,
+            avatar_id: None,
+            birthday: None,
+            shipping_address: None,
+            visibility: ProfileVisibility::default(),
+        }
+    }
+
+    pub fn with_display_name(self, display_name: Option<DisplayName>) -> Self {
+        Self {
+            display_name,
+            ..self
+        }
+    }
+
+    pub fn with_avatar_id(self, avatar_id: Option<Uuid>) -> Self {
+        Self { avatar_id, ..self }
+    }
+
+    pub fn with_birthday(self, birthday: Option<NaiveDate>) -> Self {
+        Self { birthday, ..self }
+    }
+
+    pub fn with_shipping_address(self, shipping_address: Option<ShippingAddress>) -> Self {
+        Self {
+            shipping_address,
+            ..self
+        }
+    }
+
+    pub fn with_visibility(self, visibility: ProfileVisibility) -> Self {
+        Self { visibility, ..self }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn display_name(&self) -> Option<&DisplayName> {
+        self.display_name.as_ref()
+    }
+
+    /// The ID of the avatar, stored like any other uploaded image.
+    pub fn avatar_id(&self) -> Option<Uuid> {
+        self.avatar_id
+    }
+
+    pub fn birthday(&self) -> Option<NaiveDate> {
+        self.birthday
+    }
+
+    pub fn shipping_address(&self) -> Option<&ShippingAddress> {
+        self.shipping_address.as_ref()
+    }
+
+    pub fn visibility(&self) -> ProfileVisibility {
+        self.visibility
+    }
+
+    /// The profile as `viewer` sees it: the fields they may not see are cleared.
+    pub fn visible_to(&self, viewer: ProfileViewer) -> Profile {
+        let visibility = self.visibility;
+        Self {
+            user_id: self.user_id,
+            display_name: self
+                .display_name
+                .clone()
+                .filter(|_| visibility.display_name.allows(viewer)),
+            avatar_id: self.avatar_id.filter(|_| visibility.avatar.allows(viewer)),
+            birthday: self.birthday.filter(|_| visibility.birthday.allows(viewer)),
+            shipping_address: self
+                .shipping_address
+                .clone()
+                .filter(|_| visibility.shipping_address.allows(viewer)),
+            visibility,
+        }
+    }
+
+    /// The next birthday of the user that is not over at `now`, as a yearly [Occasion] in
+    /// `time_zone`, if they gave their birthday.
+    pub fn birthday_occasion(&self, time_zone: Tz, now: DateTime<Utc>) -> Option<Occasion> {
+        Occasion::new(
+            OccasionKind::Birthday,
+            self.birthday?,
+            time_zone,
+            Some(Recurrence::Yearly),
+        )
+        .next_after(now)
+    }
+}
+
+/// The name shown for a user instead of their ID, e.g. `Alice`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayName(String);
+
+impl DisplayName {
+    pub const MAX_LENGTH: usize = 64;
+
+    /// Trims the name, which must then be between 1 and [DisplayName::MAX_LENGTH] characters.
+    pub fn new(name: &str) -> Result<Self, DisplayNameInvalidError> {
+        let trimmed = name.trim();
+        if trimmed.is_empty() || trimmed.chars().count() > Self::MAX_LENGTH {
+            return Err(DisplayNameInvalidError(name.to_string()));
+        }
+        Ok(Self(trimmed.to_string()))
+    }
+}
+
+impl Display for DisplayName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Display name {0:?} is not between 1 and 64 characters")]
+pub struct DisplayNameInvalidError(pub String);
+
+/// The postal address gifts are shipped to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShippingAddress {
+    recipient: String,
+    line1: String,
+    line2: Option<String>,
+    city: String,
+    postal_code: String,
+    country: String,
+}
+
+impl ShippingAddress {
+    /// Creates an address, trimming every line. Only `line2` may be blank.
+    pub fn new(
+        recipient: &str,
+        line1: &str,
+        line2: Option<&str>,
+        city: &str,
+        postal_code: &str,
+        country: &str,
+    ) -> Result<Self, ShippingAddressInvalidError> {
+        let required = |field: &'static str, value: &str| {
+            let value = value.trim();
+            if value.is_empty() {
+                return Err(ShippingAddressInvalidError(field));
+            }
+            Ok(value.to_string())
+        };
+        Ok(Self {
+            recipient: required("recipient", recipient)?,
+            line1: required("line1", line1)?,
+            line2: line2
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(ToString::to_string),
+            city: required("city", city)?,
+            postal_code: required("postal_code", postal_code)?,
+            country: required("country", country)?,
+        })
+    }
+
+    pub fn recipient(&self) -> &str {
+        &self.recipient
+    }
+
+    pub fn line1(&self) -> &str {
+        &self.line1
+    }
+
+    pub fn line2(&self) -> Option<&str> {
+        self.line2.as_deref()
+    }
+
+    pub fn city(&self) -> &str {
+        &self.city
+    }
+
+    pub fn postal_code(&self) -> &str {
+        &self.postal_code
+    }
+
+    pub fn country(&self) -> &str {
+        &self.country
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Shipping address {0} is missing")]
+pub struct ShippingAddressInvalidError(pub &'static str);
+
+/// Who may see a field of a [Profile], besides its owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldVisibility {
+    /// Every user.
+    Everyone,
+    /// The users whose request to follow the owner was accepted.
+    Followers,
+    /// The users who reserved an item from one of the owner's wishlists.
+    Reservers,
+    /// No one but the owner.
+    Nobody,
+}
+
+impl FieldVisibility {
+    pub const ALL: [FieldVisibility; 4] = [
+        FieldVisibility::Everyone,
+        FieldVisibility::Followers,
+        FieldVisibility::Reservers,
+        FieldVisibility::Nobody,
+    ];
+
+    /// Returns true if `viewer` may see a field with this visibility.
+    pub fn allows(&self, viewer: ProfileViewer) -> bool {
+        match self {
+            FieldVisibility::Everyone => true,
+            FieldVisibility::Followers => viewer.owner || viewer.follower,
+            FieldVisibility::Reservers => viewer.owner || viewer.reserver,
+            FieldVisibility::Nobody => viewer.owner,
+        }
+    }
+}
+
+impl Display for FieldVisibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FieldVisibility::Everyone => "everyone",
+            FieldVisibility::Followers => "followers",
+            FieldVisibility::Reservers => "reservers",
+            FieldVisibility::Nobody => "nobody",
+        })
+    }
+}
+
+impl FromStr for FieldVisibility {
+    type Err = ProfileVisibilityInvalidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|visibility| visibility.to_string() == value)
+            .ok_or(ProfileVisibilityInvalidError::Visibility(value.to_string()))
+    }
+}
+
+/// The visibility of each field of a [Profile]. The shipping address is never shown beyond the
+/// users who reserved an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileVisibility {
+    display_name: FieldVisibility,
+    avatar: FieldVisibility,
+    birthday: FieldVisibility,
+    shipping_address: FieldVisibility,
+}
+
+impl ProfileVisibility {
+    pub fn new(
+        display_name: FieldVisibility,
+        avatar: FieldVisibility,
+        birthday: FieldVisibility,
+        shipping_address: FieldVisibility,
+    ) -> Result<Self, ProfileVisibilityInvalidError> {
+        if !matches!(
+            shipping_address,
+            FieldVisibility::Reservers | FieldVisibility::Nobody
+        ) {
+            return Err(ProfileVisibilityInvalidError::ShippingAddress(
+                shipping_address,
+            ));
+        }
+        Ok(Self {
+            display_name,
+            avatar,
+            birthday,
+            shipping_address,
+        })
+    }
+
+    pub fn display_name(&self) -> FieldVisibility {
+        self.display_name
+    }
+
+    pub fn avatar(&self) -> FieldVisibility {
+        self.avatar
+    }
+
+    pub fn birthday(&self) -> FieldVisibility {
+        self.birthday
+    }
+
+    pub fn shipping_address(&self) -> FieldVisibility {
+        self.shipping_address
+    }
+}
+
+impl Default for ProfileVisibility {
+    fn default() -> Self {
+        Self {
+            display_name: FieldVisibility::Everyone,
+            avatar: FieldVisibility::Everyone,
+            birthday: FieldVisibility::Followers,
+            shipping_address: FieldVisibility::Reservers,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum ProfileVisibilityInvalidError {
+    #[error("Visibility {0} is invalid")]
+    Visibility(String),
+    #[error("The shipping address cannot be visible to {0}")]
+    ShippingAddress(FieldVisibility),
+}
+
+/// What the viewer of a [Profile] is to its owner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProfileViewer {
+    owner: bool,
+    follower: bool,
+    reserver: bool,
+}
+
+impl ProfileViewer {
+    /// The owner of the profile, who sees every field.
+    pub fn owner() -> Self {
+        Self {
+            owner: true,
+            ..Self::default()
+        }
+    }
+
+    /// Any other user, following the owner or not, and holding a reservation on one of their
+    /// items or not.
+    pub fn other(follower: bool, reserver: bool) -> Self {
+        Self {
+            owner: false,
+            follower,
+            reserver,
+        }
+    }
+
+    pub fn is_owner(&self) -> bool {
+        self.owner
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SetProfileError {
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("Image with id {id} does not exist")]
+    AvatarDoesNotExist { id: Uuid },
+    #[error("Birthday {date} is in the future")]
+    BirthdayInFuture { date: NaiveDate },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [FindProfileRequest] struct represents a request by `user_id` to see the profile of
+/// `profile_id`.
+#[derive(Debug, Clone)]
+pub struct FindProfileRequest {
+    user_id: Uuid,
+    profile_id: Uuid,
+}
+
+impl FindProfileRequest {
+    pub fn new(user_id: Uuid, profile_id: Uuid) -> Self {
+        Self {
+            user_id,
+            profile_id,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn profile_id(&self) -> Uuid {
+        self.profile_id
+    }
+}
+
+/// The [FindProfilesRequest] struct represents a request by `user_id` to see the profiles of
+/// several users at once, e.g. the owners of the wishlists they browse.
+#[derive(Debug, Clone)]
+pub struct FindProfilesRequest {
+    user_id: Uuid,
+    profile_ids: Vec<Uuid>,
+}
+
+impl FindProfilesRequest {
+    pub fn new(user_id: Uuid, profile_ids: Vec<Uuid>) -> Self {
+        Self {
+            user_id,
+            profile_ids,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn profile_ids(&self) -> &[Uuid] {
+        &self.profile_ids
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FindProfilesError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FindProfileError {
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [SuggestOccasionsRequest] struct represents a request for the occasions a user could make
+/// a wishlist for, with dates taken in `time_zone`.
+#[derive(Debug, Clone)]
+pub struct SuggestOccasionsRequest {
+    user_id: Uuid,
+    time_zone: Tz,
+}
+
+impl SuggestOccasionsRequest {
+    pub fn new(user_id: Uuid, time_zone: Tz) -> Self {
+        Self { user_id, time_zone }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn time_zone(&self) -> Tz {
+        self.time_zone
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SuggestOccasionsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> Profile {
+        Profile::new(Uuid::now_v7())
+            .with_display_name(Some(DisplayName::new(" Alice ").unwrap()))
+            .with_avatar_id(Some(Uuid::now_v7()))
+            .with_birthday(Some("1990-02-28".parse().unwrap()))
+            .with_shipping_address(Some(
+                ShippingAddress::new("Alice", "1 Main St", None, "Paris", "75001", "FR").unwrap(),
+            ))
+    }
+
+    #[test]
+    fn validate_fields() {
+        assert_eq!(DisplayName::new(" Alice ").unwrap().to_string(), "Alice");
+        assert!(DisplayName::new("  ").is_err());
+        assert!(DisplayName::new(&"a".repeat(65)).is_err());
+        assert!(ShippingAddress::new("Alice", " ", None, "Paris", "75001", "FR").is_err());
+        assert_eq!(
+            ShippingAddress::new("Alice", "1 Main St", Some(" "), "Paris", "75001", "FR")
+                .unwrap()
+                .line2(),
+            None
+        );
+        assert!(ProfileVisibility::new(
+            FieldVisibility::Everyone,
+            FieldVisibility::Everyone,
+            FieldVisibility::Everyone,
+            FieldVisibility::Followers,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn hide_fields_by_visibility() {
+        let profile = profile();
+        assert_eq!(profile.visible_to(ProfileViewer::owner()), profile);
+
+        let stranger = profile.visible_to(ProfileViewer::other(false, false));
+        assert_eq!(stranger.display_name(), profile.display_name());
+        assert_eq!(stranger.avatar_id(), profile.avatar_id());
+        assert_eq!(stranger.birthday(), None);
+        assert_eq!(stranger.shipping_address(), None);
+
+        let follower = profile.visible_to(ProfileViewer::other(true, false));
+        assert_eq!(follower.birthday(), profile.birthday());
+        assert_eq!(follower.shipping_address(), None);
+
+        let reserver = profile.visible_to(ProfileViewer::other(false, true));
+        assert_eq!(reserver.birthday(), None);
+        assert_eq!(reserver.shipping_address(), profile.shipping_address());
+
+        let hidden = profile
+            .clone()
+            .with_visibility(
+                ProfileVisibility::new(
+                    FieldVisibility::Nobody,
+                    FieldVisibility::Followers,
+                    FieldVisibility::Nobody,
+                    FieldVisibility::Nobody,
+                )
+                .unwrap(),
+            )
+            .visible_to(ProfileViewer::other(true, true));
+        assert_eq!(hidden.display_name(), None);
+        assert_eq!(hidden.avatar_id(), profile.avatar_id());
+        assert_eq!(hidden.birthday(), None);
+        assert_eq!(hidden.shipping_address(), None);
+    }
+
+    #[test]
+    fn suggest_next_birthday() {
+        let occasion = profile()
+            .birthday_occasion(
+                chrono_tz::Europe::Paris,
+                "2027-03-01T00:00:00Z".parse().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(occasion.kind(), OccasionKind::Birthday);
+        assert_eq!(occasion.date(), "2028-02-28".parse().unwrap());
+        assert_eq!(occasion.recurrence(), Some(Recurrence::Yearly));
+        assert!(Profile::new(Uuid::now_v7())
+            .birthday_occasion(chrono_tz::UTC, Utc::now())
+            .is_none());
+    }
+}
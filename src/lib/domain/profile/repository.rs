@@ -0,0 +1,60 @@
+use std::future::Future;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::ForgetUserError;
+
+use super::{Profile, SetProfileError};
+
+#[cfg(test)]
+use mockall::automock;
+
+/// The [ProfileRepository] trait defines the contract for profile data operations.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ProfileRepository: Send + Sync + 'static {
+    /// Saves the profile of a user, replacing the previous one.
+    ///
+    /// # Errors
+    /// - [SetProfileError::Unkown] for any errors that may occur.
+    fn save_profile(
+        &self,
+        profile: &Profile,
+    ) -> impl Future<Output = Result<Profile, SetProfileError>> + Send;
+    /// Finds the profile of a user.
+    ///
+    /// # Returns
+    /// - `Ok(Some(profile))` if the user saved a profile.
+    /// - `Ok(None)` if they never did.
+    ///
+    /// # Errors
+    /// - [FindProfileRepositoryError::Unkown] for any errors that may occur during the search.
+    fn find_profile(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Profile>, FindProfileRepositoryError>> + Send;
+    /// Finds the saved profiles of any of the users, in no particular order. Users who never
+    /// saved one are skipped.
+    ///
+    /// # Errors
+    /// - [FindProfileRepositoryError::Unkown] for any errors that may occur during the search.
+    fn find_profiles(
+        &self,
+        user_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<Profile>, FindProfileRepositoryError>> + Send;
+    /// Deletes the profile of a user.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum FindProfileRepositoryError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
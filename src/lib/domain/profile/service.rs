@@ -0,0 +1,56 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::domain::Occasion;
+
+use super::{
+    FindProfileError, FindProfileRequest, FindProfilesError, FindProfilesRequest, Profile,
+    SetProfileError, SuggestOccasionsError, SuggestOccasionsRequest,
+};
+
+/// The [ProfileService] trait defines the contract for the profiles of users.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ProfileService: Send + Sync + 'static {
+    /// Sets the profile of a user, replacing the previous one.
+    ///
+    /// # Errors
+    /// - [SetProfileError::UserDoesNotExist] if the user does not exist.
+    /// - [SetProfileError::AvatarDoesNotExist] if the avatar is not a stored image.
+    /// - [SetProfileError::BirthdayInFuture] if the birthday is after today.
+    /// - [SetProfileError::Unkown] for any other errors that may occur.
+    fn set_profile(
+        &self,
+        profile: &Profile,
+    ) -> impl Future<Output = Result<Profile, SetProfileError>> + Send;
+    /// Finds the profile of a user, without the fields the caller may not see. A user who never
+    /// set their profile has an empty one.
+    ///
+    /// # Errors
+    /// - [FindProfileError::UserDoesNotExist] if the user does not exist.
+    /// - [FindProfileError::Unkown] for any other errors that may occur.
+    fn find_profile(
+        &self,
+        req: &FindProfileRequest,
+    ) -> impl Future<Output = Result<Profile, FindProfileError>> + Send;
+    /// Finds the profiles of several users at once, like [ProfileService::find_profile], in the
+    /// order of the request. Unlike it, the users are not checked to exist: they are expected to
+    /// be known, e.g. as the owners of wishlists.
+    ///
+    /// # Errors
+    /// - [FindProfilesError::Unkown] for any errors that may occur.
+    fn find_profiles(
+        &self,
+        req: &FindProfilesRequest,
+    ) -> impl Future<Output = Result<Vec<Profile>, FindProfilesError>> + Send;
+    /// Suggests the occasions the user could make a wishlist for, from their profile.
+    ///
+    /// # Errors
+    /// - [SuggestOccasionsError::Unkown] for any errors that may occur.
+    fn suggest_occasions(
+        &self,
+        req: &SuggestOccasionsRequest,
+    ) -> impl Future<Output = Result<Vec<Occasion>, SuggestOccasionsError>> + Send;
+}
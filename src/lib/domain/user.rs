@@ -31,6 +31,10 @@ impl User {
         &self.id
     }
 
+    pub fn anonymous(&self) -> bool {
+        self.anonymous
+    }
+
     pub fn email(&self) -> &UserEmail {
         &self.email
     }
@@ -51,7 +55,7 @@ mod user_tests {
         let id = Uuid::now_v7();
         let user = User::new(id, UserEmail::from(""), UserPassword::from(""));
         assert_eq!(user.id, id);
-        assert_eq!(user.anonymous, true);
+        assert!(user.anonymous);
         assert_eq!(user.email, UserEmail::from(""));
         assert_eq!(user.password, UserPassword::from(""));
     }
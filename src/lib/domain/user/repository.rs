@@ -1,5 +1,9 @@
 use std::future::Future;
 
+use uuid::Uuid;
+
+use crate::domain::ForgetUserError;
+
 #[cfg(test)]
 use mockall::automock;
 
@@ -57,4 +61,12 @@ pub trait UserRepository {
         &self,
         id: &FindUserByIdRequest,
     ) -> impl Future<Output = Result<Option<User>, FindUserByIdError>> + Send;
+    /// Deletes a user and their credentials.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
 }
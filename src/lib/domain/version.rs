@@ -0,0 +1,33 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+/// The version of an entity, which moves to the next one on every change its owner can see.
+/// Updates may expect an entity to still be at the version a client last read, so that
+/// concurrent changes are detected rather than silently overwritten.
+pub type Version = u64;
+
+/// The version of a new entity.
+pub const FIRST_VERSION: Version = 1;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("{id} is at version {actual}, not {expected}")]
+pub struct VersionMismatchError {
+    pub id: Uuid,
+    pub expected: Version,
+    pub actual: Version,
+}
+
+impl VersionMismatchError {
+    /// Checks that the entity `id`, at version `actual`, is at the `expected` version if an
+    /// update expects one.
+    pub fn check(id: Uuid, actual: Version, expected: Option<Version>) -> Result<(), Self> {
+        match expected {
+            Some(expected) if expected != actual => Err(Self {
+                id,
+                expected,
+                actual,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
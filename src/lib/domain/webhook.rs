@@ -0,0 +1,714 @@
+mod repository;
+mod service;
+
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::str::FromStr;
+
+use chrono::{DateTime, TimeDelta, Utc};
+#[cfg(test)]
+use mockall::automock;
+pub use repository::*;
+pub use service::*;
+use thiserror::Error;
+use url::Url;
+use uuid::Uuid;
+
+use super::{ItemChange, ItemEvent, PageRequest};
+
+/// The [WebhookSender] trait defines the contract for posting the event of a
+/// [WebhookDelivery] to the URL of its [Webhook].
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait WebhookSender: Send + Sync + 'static {
+    /// Posts the event of `delivery` to the URL of `webhook`, signed with its secret and stamped
+    /// with `sent_at`, and returns the HTTP status of the response, whatever it is.
+    ///
+    /// # Errors
+    /// - [SendWebhookError::Forbidden] if the URL targets a host that may not be reached.
+    /// - [SendWebhookError::Timeout] if the receiver did not respond in time.
+    /// - [SendWebhookError::Unknown] for any other errors that may occur while sending.
+    fn send(
+        &self,
+        webhook: &Webhook,
+        delivery: &WebhookDelivery,
+        sent_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<u16, SendWebhookError>> + Send;
+}
+
+/// How many times a delivery is attempted when no [RetryPolicy] is configured.
+pub const DEFAULT_MAX_DELIVERY_ATTEMPTS: u32 = 8;
+
+/// When the failed attempts of a [WebhookDelivery] are retried, and when it is given up on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base: TimeDelta,
+    max: TimeDelta,
+}
+
+impl RetryPolicy {
+    /// Retries a delivery `base` after its first failed attempt, then twice as long after every
+    /// further one, up to `max`, and dead-letters it after `max_attempts` attempts.
+    pub fn new(max_attempts: u32, base: TimeDelta, max: TimeDelta) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base,
+            max,
+        }
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns how long to wait after `attempts` failed attempts before the next one.
+    pub fn backoff(&self, attempts: u32) -> TimeDelta {
+        let doublings = attempts.saturating_sub(1).min(30);
+        (self.base * (1 << doublings)).min(self.max)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_MAX_DELIVERY_ATTEMPTS,
+            TimeDelta::seconds(30),
+            TimeDelta::hours(6),
+        )
+    }
+}
+
+/// The URL events are posted to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookUrl(Url);
+
+impl WebhookUrl {
+    pub fn new(url: &str) -> Result<Self, WebhookUrlInvalidError> {
+        match Url::parse(url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(Self(url)),
+            _ => Err(WebhookUrlInvalidError {
+                invalid_url: url.to_string(),
+            }),
+        }
+    }
+
+    pub fn as_url(&self) -> &Url {
+        &self.0
+    }
+}
+
+impl Display for WebhookUrl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Webhook URL {invalid_url} is invalid")]
+pub struct WebhookUrlInvalidError {
+    pub invalid_url: String,
+}
+
+/// The key the payloads of a [Webhook] are signed with, so that the receiver can tell they come
+/// from us. It is only shown to its owner when the webhook is registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookSecret(String);
+
+impl WebhookSecret {
+    /// Generates a new secret from 244 random bits.
+    pub fn generate() -> Self {
+        Self(format!(
+            "whsec_{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        ))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for WebhookSecret {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+/// The kinds of [ItemEvent]s a [Webhook] can be notified of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WebhookEventKind {
+    ItemAdded,
+    ItemEdited,
+    ItemReserved,
+    ItemRemoved,
+}
+
+impl WebhookEventKind {
+    pub const ALL: [Self; 4] = [
+        Self::ItemAdded,
+        Self::ItemEdited,
+        Self::ItemReserved,
+        Self::ItemRemoved,
+    ];
+
+    pub fn of(change: &ItemChange) -> Self {
+        match change {
+            ItemChange::Added(_) => Self::ItemAdded,
+            ItemChange::Edited(_) => Self::ItemEdited,
+            ItemChange::Reserved { .. } => Self::ItemReserved,
+            ItemChange::Removed { .. } => Self::ItemRemoved,
+        }
+    }
+}
+
+impl Display for WebhookEventKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::ItemAdded => "item_added",
+            Self::ItemEdited => "item_edited",
+            Self::ItemReserved => "item_reserved",
+            Self::ItemRemoved => "item_removed",
+        })
+    }
+}
+
+impl FromStr for WebhookEventKind {
+    type Err = WebhookEventKindInvalidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.to_string() == s)
+            .ok_or_else(|| WebhookEventKindInvalidError(s.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Webhook event {0} is invalid")]
+pub struct WebhookEventKindInvalidError(pub String);
+
+/// The [Webhook] struct is an endpoint of a user, notified of the events of a wishlist they
+/// may see. It lasts as long as they may.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    id: Uuid,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+    url: WebhookUrl,
+    secret: WebhookSecret,
+    events: Vec<WebhookEventKind>,
+    created_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn new(
+        id: Uuid,
+        user_id: Uuid,
+        wishlist_id: Uuid,
+        url: WebhookUrl,
+        secret: WebhookSecret,
+        events: Vec<WebhookEventKind>,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            wishlist_id,
+            url,
+            secret,
+            events,
+            created_at,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn url(&self) -> &WebhookUrl {
+        &self.url
+    }
+
+    pub fn secret(&self) -> &WebhookSecret {
+        &self.secret
+    }
+
+    /// The kinds of events the webhook is notified of.
+    pub fn events(&self) -> &[WebhookEventKind] {
+        &self.events
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Returns the delivery of `event` to the webhook, if it is notified of it. Like any other
+    /// viewer, the webhooks of the owner of the wishlist are never told about reservations, see
+    /// [ItemEvent::seen_by].
+    pub fn delivery_for(&self, event: &ItemEvent, now: DateTime<Utc>) -> Option<WebhookDelivery> {
+        if event.wishlist_id() != self.wishlist_id {
+            return None;
+        }
+        let event = event.seen_by(self.user_id)?;
+        if !self.events.contains(&WebhookEventKind::of(event.change())) {
+            return None;
+        }
+        Some(WebhookDelivery::new(Uuid::now_v7(), self, event, now))
+    }
+}
+
+/// Where a [WebhookDelivery] stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// Waiting for its first attempt, or to be retried.
+    Pending,
+    /// The receiver acknowledged the event with a 2xx response.
+    Delivered,
+    /// Every attempt failed: the delivery is given up on and kept for inspection.
+    DeadLettered,
+}
+
+impl Display for DeliveryStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::DeadLettered => "dead_lettered",
+        })
+    }
+}
+
+/// What came out of an attempt to deliver an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    /// The receiver responded, successfully or not.
+    Responded { status: u16 },
+    /// The receiver could not be reached.
+    Failed { reason: String },
+}
+
+impl AttemptOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Responded { status } if (200..300).contains(status))
+    }
+}
+
+/// The [DeliveryAttempt] struct is an attempt to deliver an event, as shown in the delivery log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryAttempt {
+    attempted_at: DateTime<Utc>,
+    outcome: AttemptOutcome,
+}
+
+impl DeliveryAttempt {
+    pub fn new(attempted_at: DateTime<Utc>, outcome: AttemptOutcome) -> Self {
+        Self {
+            attempted_at,
+            outcome,
+        }
+    }
+
+    pub fn attempted_at(&self) -> DateTime<Utc> {
+        self.attempted_at
+    }
+
+    pub fn outcome(&self) -> &AttemptOutcome {
+        &self.outcome
+    }
+}
+
+/// The [WebhookDelivery] struct is an event queued for a [Webhook], with the attempts made to
+/// deliver it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    id: Uuid,
+    webhook_id: Uuid,
+    user_id: Uuid,
+    event: ItemEvent,
+    status: DeliveryStatus,
+    attempts: Vec<DeliveryAttempt>,
+    next_attempt_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    /// Queues `event` for `webhook`, to be attempted right away.
+    pub fn new(id: Uuid, webhook: &Webhook, event: ItemEvent, created_at: DateTime<Utc>) -> Self {
+        Self {
+            id,
+            webhook_id: webhook.id(),
+            user_id: webhook.user_id(),
+            event,
+            status: DeliveryStatus::Pending,
+            attempts: Vec::new(),
+            next_attempt_at: Some(created_at),
+            created_at,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn webhook_id(&self) -> Uuid {
+        self.webhook_id
+    }
+
+    /// The owner of the webhook.
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn event(&self) -> &ItemEvent {
+        &self.event
+    }
+
+    pub fn status(&self) -> DeliveryStatus {
+        self.status
+    }
+
+    pub fn attempts(&self) -> &[DeliveryAttempt] {
+        &self.attempts
+    }
+
+    /// When the delivery is attempted next, unless it is over.
+    pub fn next_attempt_at(&self) -> Option<DateTime<Utc>> {
+        self.next_attempt_at
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    /// Returns true if the delivery should be attempted at `now`.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_attempt_at.is_some_and(|at| at <= now)
+    }
+
+    /// Records an attempt, then schedules the next one with the backoff of `policy`, or ends the
+    /// delivery if it succeeded or was attempted too many times.
+    pub fn record_attempt(&mut self, attempt: DeliveryAttempt, policy: &RetryPolicy) {
+        let attempted_at = attempt.attempted_at();
+        let success = attempt.outcome().is_success();
+        self.attempts.push(attempt);
+        let attempts = self.attempts.len() as u32;
+        (self.status, self.next_attempt_at) = if success {
+            (DeliveryStatus::Delivered, None)
+        } else if attempts >= policy.max_attempts() {
+            (DeliveryStatus::DeadLettered, None)
+        } else {
+            (
+                DeliveryStatus::Pending,
+                Some(attempted_at + policy.backoff(attempts)),
+            )
+        };
+    }
+}
+
+/// The [RegisterWebhookRequest] struct represents a request by a user to be notified of the
+/// events of a wishlist at a URL.
+#[derive(Debug, Clone)]
+pub struct RegisterWebhookRequest {
+    user_id: Uuid,
+    wishlist_id: Uuid,
+    url: WebhookUrl,
+    events: Vec<WebhookEventKind>,
+}
+
+impl RegisterWebhookRequest {
+    /// Notifies the webhook of `events`, or of every kind of event if there are none.
+    pub fn new(
+        user_id: Uuid,
+        wishlist_id: Uuid,
+        url: WebhookUrl,
+        events: Vec<WebhookEventKind>,
+    ) -> Self {
+        let mut events = if events.is_empty() {
+            WebhookEventKind::ALL.to_vec()
+        } else {
+            events
+        };
+        events.sort_by_key(|kind| kind.to_string());
+        events.dedup();
+        Self {
+            user_id,
+            wishlist_id,
+            url,
+            events,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn url(&self) -> &WebhookUrl {
+        &self.url
+    }
+
+    pub fn events(&self) -> &[WebhookEventKind] {
+        &self.events
+    }
+}
+
+/// The [ListWebhooksRequest] struct represents a request by a user to list a page of their
+/// webhooks, oldest first.
+#[derive(Debug, Clone)]
+pub struct ListWebhooksRequest {
+    user_id: Uuid,
+    page: PageRequest,
+}
+
+impl ListWebhooksRequest {
+    pub fn new(user_id: Uuid, page: PageRequest) -> Self {
+        Self { user_id, page }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn page(&self) -> &PageRequest {
+        &self.page
+    }
+}
+
+/// The [WebhookRequest] struct represents a request by a user about one of their webhooks.
+#[derive(Debug, Clone)]
+pub struct WebhookRequest {
+    webhook_id: Uuid,
+    user_id: Uuid,
+}
+
+impl WebhookRequest {
+    pub fn new(webhook_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            webhook_id,
+            user_id,
+        }
+    }
+
+    pub fn webhook_id(&self) -> Uuid {
+        self.webhook_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+}
+
+/// The [ListWebhookDeliveriesRequest] struct represents a request by a user to read a page of
+/// the delivery log of one of their webhooks, newest first.
+#[derive(Debug, Clone)]
+pub struct ListWebhookDeliveriesRequest {
+    webhook_id: Uuid,
+    user_id: Uuid,
+    page: PageRequest,
+}
+
+impl ListWebhookDeliveriesRequest {
+    pub fn new(webhook_id: Uuid, user_id: Uuid, page: PageRequest) -> Self {
+        Self {
+            webhook_id,
+            user_id,
+            page,
+        }
+    }
+
+    pub fn webhook_id(&self) -> Uuid {
+        self.webhook_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn page(&self) -> &PageRequest {
+        &self.page
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RegisterWebhookError {
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ListWebhooksError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum DeleteWebhookError {
+    #[error("Webhook with id {id} does not exist")]
+    WebhookDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ListWebhookDeliveriesError {
+    #[error("Webhook with id {id} does not exist")]
+    WebhookDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum DeliverWebhooksError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SendWebhookError {
+    #[error("Sending to {url} is not allowed")]
+    Forbidden { url: String },
+    #[error("Webhook timed out")]
+    Timeout,
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Item, Wishlist};
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::new(4, TimeDelta::seconds(10), TimeDelta::seconds(30));
+        assert_eq!(policy.backoff(1), TimeDelta::seconds(10));
+        assert_eq!(policy.backoff(2), TimeDelta::seconds(20));
+        assert_eq!(policy.backoff(3), TimeDelta::seconds(30));
+        assert_eq!(policy.backoff(64), TimeDelta::seconds(30));
+    }
+
+    #[test]
+    fn test_deliveries_retried_then_dead_lettered() {
+        let now: DateTime<Utc> = "2027-03-01T12:00:00Z".parse().unwrap();
+        let (owner, viewer) = (Uuid::now_v7(), Uuid::now_v7());
+        let wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            owner,
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        );
+        let item = Item::create(
+            Uuid::now_v7(),
+            wishlist.id(),
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        let reserved = ItemEvent::new(
+            1,
+            &wishlist,
+            ItemChange::Reserved {
+                item_id: item.id(),
+                reserved: true,
+            },
+        );
+        let webhook = |user_id, events: Vec<WebhookEventKind>| {
+            Webhook::new(
+                Uuid::now_v7(),
+                user_id,
+                wishlist.id(),
+                WebhookUrl::new("https://hooks.example/wishlist").unwrap(),
+                WebhookSecret::generate(),
+                events,
+                now,
+            )
+        };
+
+        // The owner is not told about reservations, and webhooks only get the events they want
+        assert!(webhook(owner, WebhookEventKind::ALL.to_vec())
+            .delivery_for(&reserved, now)
+            .is_none());
+        let added = ItemEvent::new(2, &wishlist, ItemChange::Added(item));
+        assert!(webhook(viewer, vec![WebhookEventKind::ItemReserved])
+            .delivery_for(&added, now)
+            .is_none());
+        let mut delivery = webhook(viewer, vec![WebhookEventKind::ItemReserved])
+            .delivery_for(&reserved, now)
+            .unwrap();
+        assert!(delivery.is_due(now));
+
+        let policy = RetryPolicy::new(3, TimeDelta::minutes(1), TimeDelta::hours(1));
+        let failed = |at| {
+            DeliveryAttempt::new(
+                at,
+                AttemptOutcome::Failed {
+                    reason: "connection refused".into(),
+                },
+            )
+        };
+        delivery.record_attempt(failed(now), &policy);
+        assert_eq!(delivery.status(), DeliveryStatus::Pending);
+        assert_eq!(
+            delivery.next_attempt_at(),
+            Some(now + TimeDelta::minutes(1))
+        );
+        assert!(!delivery.is_due(now));
+        let later = now + TimeDelta::minutes(1);
+        delivery.record_attempt(
+            DeliveryAttempt::new(later, AttemptOutcome::Responded { status: 500 }),
+            &policy,
+        );
+        assert_eq!(
+            delivery.next_attempt_at(),
+            Some(later + TimeDelta::minutes(2))
+        );
+        delivery.record_attempt(failed(later + TimeDelta::minutes(2)), &policy);
+        assert_eq!(delivery.status(), DeliveryStatus::DeadLettered);
+        assert_eq!(delivery.next_attempt_at(), None);
+        assert_eq!(delivery.attempts().len(), 3);
+    }
+
+    #[test]
+    fn test_register_webhook_request_events() {
+        let url = WebhookUrl::new("https://hooks.example/wishlist").unwrap();
+        let req = RegisterWebhookRequest::new(Uuid::now_v7(), Uuid::now_v7(), url.clone(), vec![]);
+        assert_eq!(req.events().len(), 4);
+        let req = RegisterWebhookRequest::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            url,
+            vec![WebhookEventKind::ItemAdded, WebhookEventKind::ItemAdded],
+        );
+        assert_eq!(req.events(), &[WebhookEventKind::ItemAdded]);
+
+        assert!(WebhookUrl::new("ftp://hooks.example").is_err());
+        assert_eq!(
+            "item_reserved".parse::<WebhookEventKind>().unwrap(),
+            WebhookEventKind::ItemReserved
+        );
+        assert_eq!(
+            "item_removed".parse::<WebhookEventKind>().unwrap(),
+            WebhookEventKind::ItemRemoved
+        );
+        assert!("item_deleted".parse::<WebhookEventKind>().is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::{ForgetUserError, Page};
+
+use super::{
+    DeleteWebhookError, ListWebhookDeliveriesError, ListWebhookDeliveriesRequest,
+    RegisterWebhookError, Webhook, WebhookDelivery, WebhookRequest,
+};
+
+/// The [WebhookRepository] trait defines the contract for storing webhooks and the queue of
+/// their deliveries, which is also their delivery log.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait WebhookRepository: Send + Sync + 'static {
+    /// Saves a new webhook.
+    ///
+    /// # Errors
+    /// - [RegisterWebhookError::Unkown] for any errors that may occur while saving.
+    fn save_webhook(
+        &self,
+        webhook: &Webhook,
+    ) -> impl Future<Output = Result<Webhook, RegisterWebhookError>> + Send;
+    /// Finds a webhook by its ID.
+    ///
+    /// # Errors
+    /// - [FindWebhooksError::Unkown] for any errors that may occur during the search.
+    fn find_webhook_by_id(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<Webhook>, FindWebhooksError>> + Send;
+    /// Finds the webhooks of a user, oldest first.
+    ///
+    /// # Errors
+    /// - [FindWebhooksError::Unkown] for any errors that may occur during the search.
+    fn find_webhooks_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Webhook>, FindWebhooksError>> + Send;
+    /// Finds the webhooks notified of the events of a wishlist.
+    ///
+    /// # Errors
+    /// - [FindWebhooksError::Unkown] for any errors that may occur during the search.
+    fn find_webhooks_by_wishlist(
+        &self,
+        wishlist_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Webhook>, FindWebhooksError>> + Send;
+    /// Deletes a webhook of a user with its deliveries, and returns it.
+    ///
+    /// # Errors
+    /// - [DeleteWebhookError::WebhookDoesNotExist] if the user has no such webhook.
+    /// - [DeleteWebhookError::Unkown] for any other errors that may occur.
+    fn delete_webhook(
+        &self,
+        req: &WebhookRequest,
+    ) -> impl Future<Output = Result<Webhook, DeleteWebhookError>> + Send;
+    /// Saves a delivery, queuing it if it is new and replacing it otherwise.
+    ///
+    /// # Errors
+    /// - [SaveWebhookDeliveryError::Unkown] for any errors that may occur while saving.
+    fn save_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> impl Future<Output = Result<WebhookDelivery, SaveWebhookDeliveryError>> + Send;
+    /// Finds at most `limit` deliveries due at `now`, the longest waiting first. See
+    /// [WebhookDelivery::is_due].
+    ///
+    /// # Errors
+    /// - [FindWebhookDeliveriesError::Unkown] for any errors that may occur during the search.
+    fn find_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, FindWebhookDeliveriesError>> + Send;
+    /// Finds a page of the deliveries of a webhook, newest first.
+    ///
+    /// # Errors
+    /// - [ListWebhookDeliveriesError::Unkown] for any errors that may occur during the search.
+    fn find_deliveries(
+        &self,
+        req: &ListWebhookDeliveriesRequest,
+    ) -> impl Future<Output = Result<Page<WebhookDelivery>, ListWebhookDeliveriesError>> + Send;
+    /// Deletes the webhooks of a user and their deliveries.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+#[derive(Debug, Error)]
+pub enum FindWebhooksError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SaveWebhookDeliveryError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FindWebhookDeliveriesError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
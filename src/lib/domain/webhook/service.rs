@@ -0,0 +1,66 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::domain::Page;
+
+use super::{
+    DeleteWebhookError, DeliverWebhooksError, ListWebhookDeliveriesError,
+    ListWebhookDeliveriesRequest, ListWebhooksError, ListWebhooksRequest, RegisterWebhookError,
+    RegisterWebhookRequest, Webhook, WebhookDelivery, WebhookRequest,
+};
+
+/// The [WebhookService] trait defines the contract for the webhooks of users and the delivery of
+/// their events.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait WebhookService: Send + Sync + 'static {
+    /// Registers a webhook notified of the events of a wishlist the user may see, with a new
+    /// secret to sign its payloads.
+    ///
+    /// # Errors
+    /// - [RegisterWebhookError::UserDoesNotExist] if the user does not exist.
+    /// - [RegisterWebhookError::WishlistDoesNotExist] if the wishlist does not exist, the user
+    ///   may not see it, or its owner blocked them.
+    /// - [RegisterWebhookError::Unkown] for any other errors that may occur.
+    fn register_webhook(
+        &self,
+        req: &RegisterWebhookRequest,
+    ) -> impl Future<Output = Result<Webhook, RegisterWebhookError>> + Send;
+    /// Lists a page of the webhooks of a user, oldest first.
+    ///
+    /// # Errors
+    /// - [ListWebhooksError::Unkown] for any errors that may occur during the search.
+    fn list_webhooks(
+        &self,
+        req: &ListWebhooksRequest,
+    ) -> impl Future<Output = Result<Page<Webhook>, ListWebhooksError>> + Send;
+    /// Deletes a webhook of a user. Its pending deliveries are dropped.
+    ///
+    /// # Errors
+    /// - [DeleteWebhookError::WebhookDoesNotExist] if the user has no such webhook.
+    /// - [DeleteWebhookError::Unkown] for any other errors that may occur.
+    fn delete_webhook(
+        &self,
+        req: &WebhookRequest,
+    ) -> impl Future<Output = Result<Webhook, DeleteWebhookError>> + Send;
+    /// Lists a page of the deliveries of a webhook of the user with their attempts, newest
+    /// first.
+    ///
+    /// # Errors
+    /// - [ListWebhookDeliveriesError::WebhookDoesNotExist] if the user has no such webhook.
+    /// - [ListWebhookDeliveriesError::Unkown] for any other errors that may occur.
+    fn list_webhook_deliveries(
+        &self,
+        req: &ListWebhookDeliveriesRequest,
+    ) -> impl Future<Output = Result<Page<WebhookDelivery>, ListWebhookDeliveriesError>> + Send;
+    /// Attempts the deliveries that are due, and schedules the failed ones to be retried or
+    /// dead-letters them. See [WebhookDelivery::record_attempt]. Webhooks whose user may no
+    /// longer see the wishlist are deleted along with their deliveries, instead of notified.
+    ///
+    /// # Errors
+    /// - [DeliverWebhooksError::Unkown] if the queue cannot be read or written.
+    fn deliver_due_webhooks(&self)
+        -> impl Future<Output = Result<(), DeliverWebhooksError>> + Send;
+}
@@ -1,17 +1,29 @@
 mod item;
+mod listing;
 mod name;
+mod occasion;
 mod repository;
+mod section;
 mod service;
+mod share;
 mod slug;
 
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
 pub use item::*;
+pub use listing::*;
 pub use name::*;
+pub use occasion::*;
 pub use repository::*;
+pub use section::*;
 pub use service::*;
+pub use share::ShareToken;
 pub use slug::WishlistSlug;
-use std::collections::HashSet;
 use uuid::Uuid;
 
+use super::{Group, Version, VersionMismatchError, FIRST_VERSION};
+
 #[derive(Debug, Clone)]
 pub struct Wishlist {
     id: Uuid,
@@ -19,7 +31,41 @@ pub struct Wishlist {
     name: WishlistName,
     slug: WishlistSlug,
     private: bool,
-    items: HashSet<Uuid>,
+    share_token: Option<ShareToken>,
+    template: bool,
+    occasion: Option<Occasion>,
+    archived: bool,
+    sections: Vec<WishlistSection>,
+    items: Vec<WishlistEntry>,
+    updated_at: DateTime<Utc>,
+    version: Version,
+}
+
+/// The place of an item in a [Wishlist]: its position is its index in [Wishlist::items].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WishlistEntry {
+    item_id: Uuid,
+    section_id: Option<Uuid>,
+}
+
+impl WishlistEntry {
+    pub fn item_id(&self) -> Uuid {
+        self.item_id
+    }
+
+    pub fn section_id(&self) -> Option<Uuid> {
+        self.section_id
+    }
+}
+
+/// The creation time of a wishlist, from its time-ordered ID.
+fn created_at(id: Uuid) -> DateTime<Utc> {
+    id.get_timestamp()
+        .and_then(|timestamp| {
+            let (seconds, nanos) = timestamp.to_unix();
+            DateTime::from_timestamp(seconds as i64, nanos)
+        })
+        .unwrap_or(DateTime::UNIX_EPOCH)
 }
 
 impl Wishlist {
@@ -36,7 +82,14 @@ impl Wishlist {
             name,
             slug,
             private,
-            items: HashSet::new(),
+            share_token: None,
+            template: false,
+            occasion: None,
+            archived: false,
+            sections: Vec::new(),
+            items: Vec::new(),
+            updated_at: created_at(id),
+            version: FIRST_VERSION,
         }
     }
 
@@ -60,15 +113,298 @@ impl Wishlist {
         self.private
     }
 
-    pub fn items(&self) -> &HashSet<Uuid> {
+    /// The secret of the link that shows the wishlist to anyone, if the owner created one.
+    pub fn share_token(&self) -> Option<&ShareToken> {
+        self.share_token.as_ref()
+    }
+
+    pub fn set_share_token(&mut self, share_token: Option<ShareToken>) {
+        self.share_token = share_token;
+    }
+
+    /// Returns true if anyone holding `share_token` may see the wishlist: it is public, or the
+    /// token is the one of its share link.
+    pub fn is_visible_with(&self, share_token: Option<&ShareToken>) -> bool {
+        !self.private || (share_token.is_some() && share_token == self.share_token.as_ref())
+    }
+
+    /// Returns true if anyone may start a wishlist of their own from a copy of this one.
+    pub fn template(&self) -> bool {
+        self.template
+    }
+
+    pub fn set_template(&mut self, template: bool) {
+        self.template = template;
+    }
+
+    /// The event the wishlist is for, if any.
+    pub fn occasion(&self) -> Option<&Occasion> {
+        self.occasion.as_ref()
+    }
+
+    pub fn set_occasion(&mut self, occasion: Option<Occasion>) {
+        self.occasion = occasion;
+    }
+
+    /// Returns true once the wishlist was closed after its occasion.
+    pub fn archived(&self) -> bool {
+        self.archived
+    }
+
+    pub fn set_archived(&mut self, archived: bool) {
+        self.archived = archived;
+    }
+
+    /// The last time the wishlist or its layout changed.
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    pub fn touch(&mut self, at: DateTime<Utc>) {
+        self.updated_at = at;
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Moves the wishlist to its next version, once it changed.
+    pub fn next_version(&mut self) {
+        self.version += 1;
+    }
+
+    /// Checks that the wishlist is at the version an update expects, if any.
+    pub fn check_version(&self, expected: Option<Version>) -> Result<(), VersionMismatchError> {
+        VersionMismatchError::check(self.id, self.version, expected)
+    }
+
+    /// Returns true if `user_id` may see the wishlist: it is public, theirs, or shared with one
+    /// of `groups` by a member.
+    pub fn is_visible_to(&self, user_id: Uuid, groups: &[Group]) -> bool {
+        !self.private
+            || self.owner_id == user_id
+            || groups
+                .iter()
+                .any(|group| group.grants_access(user_id, self.id))
+    }
+
+    /// Returns true if `user_id` may copy the wishlist: they own it or it is a template.
+    pub fn can_be_duplicated_by(&self, user_id: Uuid) -> bool {
+        self.owner_id == user_id || self.template
+    }
+
+    /// The sections of the wishlist, in the owner's order.
+    pub fn sections(&self) -> &[WishlistSection] {
+        &self.sections
+    }
+
+    /// The items of the wishlist, in the owner's order.
+    pub fn items(&self) -> &[WishlistEntry] {
         &self.items
     }
+
+    pub fn contains_item(&self, item_id: Uuid) -> bool {
+        self.position_of(item_id).is_some()
+    }
+
+    /// Returns the zero-based position of an item in the owner's order.
+    pub fn position_of(&self, item_id: Uuid) -> Option<usize> {
+        self.items.iter().position(|entry| entry.item_id == item_id)
+    }
+
+    /// Returns the section an item is in, if any.
+    pub fn section_of(&self, item_id: Uuid) -> Option<&WishlistSection> {
+        let entry = self.items.iter().find(|entry| entry.item_id == item_id)?;
+        let section_id = entry.section_id?;
+        self.sections
+            .iter()
+            .find(|section| section.id() == section_id)
+    }
+
+    /// Appends an item outside any section. Returns false if it already is in the wishlist.
+    pub fn add_item(&mut self, item_id: Uuid) -> bool {
+        if self.contains_item(item_id) {
+            return false;
+        }
+        self.items.push(WishlistEntry {
+            item_id,
+            section_id: None,
+        });
+        true
+    }
+
+    /// Removes an item. Returns false if it was not in the wishlist.
+    pub fn remove_item(&mut self, item_id: Uuid) -> bool {
+        let length = self.items.len();
+        self.items.retain(|entry| entry.item_id != item_id);
+        self.items.len() != length
+    }
+
+    /// Appends a section, rejecting it if another section has the same name.
+    pub fn add_section(&mut self, section: WishlistSection) -> Result<(), CreateSectionError> {
+        if self
+            .sections
+            .iter()
+            .any(|existing| existing.name().same_as(section.name()))
+        {
+            return Err(CreateSectionError::DuplicateName {
+                name: section.name().clone(),
+            });
+        }
+        self.sections.push(section);
+        Ok(())
+    }
+
+    /// Removes a section. Its items keep their position, outside any section.
+    pub fn remove_section(&mut self, section_id: Uuid) -> Result<(), DeleteSectionError> {
+        let index = self
+            .sections
+            .iter()
+            .position(|section| section.id() == section_id)
+            .ok_or(DeleteSectionError::SectionDoesNotExist { id: section_id })?;
+        self.sections.remove(index);
+        for entry in self.items.iter_mut() {
+            if entry.section_id == Some(section_id) {
+                entry.section_id = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves an item into a section, or out of any section when `section_id` is `None`.
+    pub fn move_item_to_section(
+        &mut self,
+        item_id: Uuid,
+        section_id: Option<Uuid>,
+    ) -> Result<(), MoveItemToSectionError> {
+        if let Some(id) = section_id {
+            if !self.sections.iter().any(|section| section.id() == id) {
+                return Err(MoveItemToSectionError::SectionDoesNotExist { id });
+            }
+        }
+        let entry = self
+            .items
+            .iter_mut()
+            .find(|entry| entry.item_id == item_id)
+            .ok_or(MoveItemToSectionError::ItemNotInWishlist { id: item_id })?;
+        entry.section_id = section_id;
+        Ok(())
+    }
+
+    /// Reorders the items and the sections of the wishlist.
+    ///
+    /// An ordering listing every item (or section) replaces the current order. A partial ordering
+    /// only rearranges the listed ones among the positions they already occupy, leaving the others
+    /// where they are: reordering `[a, b, c, d]` with `[d, b]` gives `[a, d, c, b]`. Nothing
+    /// changes if either ordering is invalid.
+    pub fn reorder(
+        &mut self,
+        item_ids: &[Uuid],
+        section_ids: &[Uuid],
+    ) -> Result<(), ReorderWishlistError> {
+        let items = reorder_slots(&self.items, item_ids, WishlistEntry::item_id)
+            .map_err(|err| err.into_error(|id| ReorderWishlistError::ItemNotInWishlist { id }))?;
+        let sections = reorder_slots(&self.sections, section_ids, WishlistSection::id)
+            .map_err(|err| err.into_error(|id| ReorderWishlistError::SectionDoesNotExist { id }))?;
+        self.items = items;
+        self.sections = sections;
+        Ok(())
+    }
+}
+
+enum ReorderSlotsError {
+    Missing(Uuid),
+    Duplicate(Uuid),
+}
+
+impl ReorderSlotsError {
+    fn into_error(
+        self,
+        missing: impl FnOnce(Uuid) -> ReorderWishlistError,
+    ) -> ReorderWishlistError {
+        match self {
+            ReorderSlotsError::Missing(id) => missing(id),
+            ReorderSlotsError::Duplicate(id) => ReorderWishlistError::Duplicate { id },
+        }
+    }
+}
+
+/// Returns `values` with the ones listed in `ids` moved, in that order, into the slots the listed
+/// ones occupy.
+fn reorder_slots<T: Clone>(
+    values: &[T],
+    ids: &[Uuid],
+    id_of: impl Fn(&T) -> Uuid,
+) -> Result<Vec<T>, ReorderSlotsError> {
+    let mut listed = HashSet::new();
+    let mut reordered = Vec::with_capacity(ids.len());
+    for &id in ids {
+        if !listed.insert(id) {
+            return Err(ReorderSlotsError::Duplicate(id));
+        }
+        let value = values
+            .iter()
+            .find(|value| id_of(value) == id)
+            .ok_or(ReorderSlotsError::Missing(id))?;
+        reordered.push(value.clone());
+    }
+    let mut reordered = reordered.into_iter();
+    Ok(values
+        .iter()
+        .map(|value| {
+            if listed.contains(&id_of(value)) {
+                reordered.next().unwrap_or_else(|| value.clone())
+            } else {
+                value.clone()
+            }
+        })
+        .collect())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Wishlist, WishlistName, WishlistSlug};
-    use uuid::Uuid;
+    use super::*;
+
+    fn wishlist_with_items(count: usize) -> (Wishlist, Vec<Uuid>) {
+        let mut wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            "Birthday".into(),
+            "Birthday".into(),
+            false,
+        );
+        let ids: Vec<Uuid> = (0..count).map(|_| Uuid::now_v7()).collect();
+        for id in &ids {
+            wishlist.add_item(*id);
+        }
+        (wishlist, ids)
+    }
+
+    fn item_ids(wishlist: &Wishlist) -> Vec<Uuid> {
+        wishlist
+            .items()
+            .iter()
+            .map(WishlistEntry::item_id)
+            .collect()
+    }
+
+    #[test]
+    fn share_link_shows_private_wishlist() {
+        let mut wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            "Birthday".into(),
+            "Birthday".into(),
+            true,
+        );
+        let token = ShareToken::generate();
+        assert!(!wishlist.is_visible_with(None));
+        assert!(!wishlist.is_visible_with(Some(&token)));
+        wishlist.set_share_token(Some(token.clone()));
+        assert!(wishlist.is_visible_with(Some(&token)));
+        assert!(!wishlist.is_visible_with(Some(&ShareToken::generate())));
+        assert!(!wishlist.is_visible_with(None));
+    }
 
     #[test]
     fn create_wishlist() {
@@ -82,7 +418,94 @@ mod tests {
         assert_eq!(wishlist.owner_id, owner_id);
         assert_eq!(wishlist.name, "Test wishlist".into());
         assert!(wishlist.slug.to_string().contains("test-wishlist-"));
-        assert_eq!(wishlist.private, true);
+        assert!(wishlist.private);
+        assert!(!wishlist.template);
         assert!(wishlist.items.is_empty());
     }
+
+    #[test]
+    fn duplicate_templates_only() {
+        let (mut wishlist, _) = wishlist_with_items(0);
+        let someone = Uuid::now_v7();
+        assert!(wishlist.can_be_duplicated_by(wishlist.owner_id()));
+        assert!(!wishlist.can_be_duplicated_by(someone));
+        wishlist.set_template(true);
+        assert!(wishlist.can_be_duplicated_by(someone));
+    }
+
+    #[test]
+    fn add_items_in_order() {
+        let (mut wishlist, ids) = wishlist_with_items(3);
+        assert!(!wishlist.add_item(ids[0]));
+        assert_eq!(item_ids(&wishlist), ids);
+        assert_eq!(wishlist.position_of(ids[2]), Some(2));
+
+        assert!(wishlist.remove_item(ids[1]));
+        assert!(!wishlist.remove_item(ids[1]));
+        assert_eq!(item_ids(&wishlist), [ids[0], ids[2]]);
+    }
+
+    #[test]
+    fn reorder_items() {
+        let (mut wishlist, ids) = wishlist_with_items(4);
+        let [a, b, c, d] = [ids[0], ids[1], ids[2], ids[3]];
+
+        wishlist.reorder(&[d, b], &[]).unwrap();
+        assert_eq!(item_ids(&wishlist), [a, d, c, b]);
+        wishlist.reorder(&[c, b, a, d], &[]).unwrap();
+        assert_eq!(item_ids(&wishlist), [c, b, a, d]);
+
+        let unknown = Uuid::now_v7();
+        assert!(matches!(
+            wishlist.reorder(&[a, unknown], &[]),
+            Err(ReorderWishlistError::ItemNotInWishlist { id }) if id == unknown
+        ));
+        assert!(matches!(
+            wishlist.reorder(&[a, b, a], &[]),
+            Err(ReorderWishlistError::Duplicate { id }) if id == a
+        ));
+        assert!(matches!(
+            wishlist.reorder(&[d, c, b, a], &[unknown]),
+            Err(ReorderWishlistError::SectionDoesNotExist { .. })
+        ));
+        assert_eq!(item_ids(&wishlist), [c, b, a, d]);
+    }
+
+    #[test]
+    fn manage_sections() {
+        let (mut wishlist, ids) = wishlist_with_items(2);
+        let books = WishlistSection::new(Uuid::now_v7(), "Books".into());
+        let kitchen = WishlistSection::new(Uuid::now_v7(), "Kitchen".into());
+        wishlist.add_section(books.clone()).unwrap();
+        wishlist.add_section(kitchen.clone()).unwrap();
+        assert!(matches!(
+            wishlist.add_section(WishlistSection::new(Uuid::now_v7(), "BOOKS".into())),
+            Err(CreateSectionError::DuplicateName { .. })
+        ));
+
+        wishlist
+            .move_item_to_section(ids[1], Some(books.id()))
+            .unwrap();
+        assert_eq!(wishlist.section_of(ids[1]), Some(&books));
+        assert!(matches!(
+            wishlist.move_item_to_section(ids[0], Some(Uuid::now_v7())),
+            Err(MoveItemToSectionError::SectionDoesNotExist { .. })
+        ));
+        assert!(matches!(
+            wishlist.move_item_to_section(Uuid::now_v7(), None),
+            Err(MoveItemToSectionError::ItemNotInWishlist { .. })
+        ));
+
+        wishlist.reorder(&[], &[kitchen.id(), books.id()]).unwrap();
+        assert_eq!(wishlist.sections(), [kitchen.clone(), books.clone()]);
+
+        wishlist.remove_section(books.id()).unwrap();
+        assert_eq!(wishlist.sections(), [kitchen]);
+        assert_eq!(wishlist.section_of(ids[1]), None);
+        assert_eq!(item_ids(&wishlist), ids);
+        assert!(matches!(
+            wishlist.remove_section(books.id()),
+            Err(DeleteSectionError::SectionDoesNotExist { .. })
+        ));
+    }
 }
@@ -1,43 +1,180 @@
+mod attribute;
+mod event;
+mod extractor;
 mod image_url;
 mod link_url;
+mod listing;
+mod metadata;
+mod notes;
+mod page;
 mod price;
+mod price_history;
+mod priority;
 mod repository;
 mod service;
 mod title;
 
-pub use image_url::ItemImageUrl;
-pub use link_url::ItemLinkUrl;
-pub use price::ItemPrice;
+pub use attribute::*;
+pub use event::*;
+pub use extractor::*;
+pub use image_url::*;
+pub use link_url::*;
+pub use listing::*;
+pub use metadata::*;
+pub use notes::*;
+pub use page::*;
+pub use price::*;
+pub use price_history::*;
+pub use priority::*;
 pub use repository::*;
 pub use service::*;
-pub use title::ItemTitle;
+pub use title::*;
 use uuid::Uuid;
 
+use crate::domain::{Version, VersionMismatchError, FIRST_VERSION};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Item {
     id: Uuid,
+    wishlist_id: Uuid,
     title: ItemTitle,
     link_url: ItemLinkUrl,
-    image_url: ItemImageUrl,
-    price: ItemPrice,
+    image_url: Option<ItemImageUrl>,
+    price: Option<ItemPrice>,
+    image_id: Option<Uuid>,
+    priority: ItemPriority,
+    notes: Option<ItemNotes>,
+    attributes: ItemAttributes,
+    received: bool,
+    reserved_by: Option<Uuid>,
+    version: Version,
 }
 
 impl Item {
     pub fn create(
         id: Uuid,
+        wishlist_id: Uuid,
         title: ItemTitle,
         link_url: ItemLinkUrl,
-        image_url: ItemImageUrl,
-        price: ItemPrice,
+        image_url: Option<ItemImageUrl>,
+        price: Option<ItemPrice>,
     ) -> Self {
         Self {
             id,
+            wishlist_id,
             title,
             link_url,
             image_url,
             price,
+            image_id: None,
+            priority: ItemPriority::default(),
+            notes: None,
+            attributes: ItemAttributes::default(),
+            received: false,
+            reserved_by: None,
+            version: FIRST_VERSION,
         }
     }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn set_wishlist_id(&mut self, wishlist_id: Uuid) {
+        self.wishlist_id = wishlist_id;
+    }
+
+    pub fn title(&self) -> &ItemTitle {
+        &self.title
+    }
+
+    pub fn link_url(&self) -> &ItemLinkUrl {
+        &self.link_url
+    }
+
+    pub fn image_url(&self) -> Option<&ItemImageUrl> {
+        self.image_url.as_ref()
+    }
+
+    pub fn price(&self) -> Option<&ItemPrice> {
+        self.price.as_ref()
+    }
+
+    pub fn set_price(&mut self, price: Option<ItemPrice>) {
+        self.price = price;
+    }
+
+    /// The ID of the copy of the item's image stored on our side, if any.
+    pub fn image_id(&self) -> Option<Uuid> {
+        self.image_id
+    }
+
+    pub fn set_image_id(&mut self, image_id: Option<Uuid>) {
+        self.image_id = image_id;
+    }
+
+    pub fn priority(&self) -> ItemPriority {
+        self.priority
+    }
+
+    pub fn set_priority(&mut self, priority: ItemPriority) {
+        self.priority = priority;
+    }
+
+    pub fn notes(&self) -> Option<&ItemNotes> {
+        self.notes.as_ref()
+    }
+
+    pub fn set_notes(&mut self, notes: Option<ItemNotes>) {
+        self.notes = notes;
+    }
+
+    pub fn attributes(&self) -> &ItemAttributes {
+        &self.attributes
+    }
+
+    pub fn set_attributes(&mut self, attributes: ItemAttributes) {
+        self.attributes = attributes;
+    }
+
+    /// Returns true once the owner got the item, which then leaves recurring wishlists when they
+    /// reopen.
+    pub fn received(&self) -> bool {
+        self.received
+    }
+
+    pub fn set_received(&mut self, received: bool) {
+        self.received = received;
+    }
+
+    /// The user who reserved the item to offer it, kept from the owner of its wishlist.
+    pub fn reserved_by(&self) -> Option<Uuid> {
+        self.reserved_by
+    }
+
+    pub fn set_reserved_by(&mut self, reserved_by: Option<Uuid>) {
+        self.reserved_by = reserved_by;
+    }
+
+    /// The version of the item as its owner sees it: reservations, which are kept from the
+    /// owner, leave it unchanged.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Moves the item to its next version, once it changed.
+    pub fn next_version(&mut self) {
+        self.version += 1;
+    }
+
+    /// Checks that the item is at the version an update expects, if any.
+    pub fn check_version(&self, expected: Option<Version>) -> Result<(), VersionMismatchError> {
+        VersionMismatchError::check(self.id, self.version, expected)
+    }
 }
 
 #[cfg(test)]
@@ -45,23 +182,40 @@ mod tests {
     use uuid::Uuid;
 
     use crate::domain::wishlist::{
-        item::{ItemImageUrl, ItemLinkUrl, ItemPrice, ItemTitle},
+        item::{ItemImageUrl, ItemLinkUrl, ItemPrice, ItemPriority, ItemTitle},
         Item,
     };
 
     #[test]
     fn create_item() {
         let id = Uuid::now_v7();
+        let wishlist_id = Uuid::now_v7();
         let title = ItemTitle::from("Title");
         let link_url = ItemLinkUrl::from("https://www.test_link_url.com");
         let image_url = ItemImageUrl::from("https://www.test_image_url.com");
         let price = ItemPrice::from(10.10);
-        let item = Item::create(id, title, link_url, image_url, price);
+        let item = Item::create(
+            id,
+            wishlist_id,
+            title,
+            link_url,
+            Some(image_url),
+            Some(price),
+        );
 
         assert_eq!(item.id, id);
+        assert_eq!(item.wishlist_id, wishlist_id);
         assert_eq!(item.title, "Title".into());
         assert_eq!(item.link_url, "https://www.test_link_url.com".into());
-        assert_eq!(item.image_url, "https://www.test_image_url.com".into());
-        assert_eq!(item.price, 10.10.into());
+        assert_eq!(
+            item.image_url,
+            Some("https://www.test_image_url.com".into())
+        );
+        assert_eq!(item.price, Some(10.10.into()));
+        assert_eq!(item.priority, ItemPriority::Normal);
+        assert!(item.notes.is_none());
+        assert!(item.attributes.is_empty());
+        assert!(!item.received);
+        assert!(item.reserved_by.is_none());
     }
 }
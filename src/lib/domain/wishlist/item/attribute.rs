@@ -0,0 +1,135 @@
+use std::fmt::{Display, Formatter};
+
+use thiserror::Error;
+
+/// A variant attribute of an item, e.g. `size: M` or `color: navy blue`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemAttribute {
+    key: String,
+    value: String,
+}
+
+impl ItemAttribute {
+    pub const MAX_KEY_LENGTH: usize = 64;
+    pub const MAX_VALUE_LENGTH: usize = 256;
+
+    /// Trims the key and value and rejects them if either is empty or too long.
+    pub fn new(key: &str, value: &str) -> Result<Self, ItemAttributeInvalidError> {
+        let (key, value) = (key.trim(), value.trim());
+        let key_length = key.chars().count();
+        let value_length = value.chars().count();
+        if !(1..=Self::MAX_KEY_LENGTH).contains(&key_length)
+            || !(1..=Self::MAX_VALUE_LENGTH).contains(&value_length)
+        {
+            return Err(ItemAttributeInvalidError::Invalid {
+                key: key.to_string(),
+            });
+        }
+        Ok(Self {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns true if the attribute has the given key and value, ignoring case.
+    pub fn matches(&self, key: &str, value: &str) -> bool {
+        self.key.eq_ignore_ascii_case(key.trim()) && self.value.eq_ignore_ascii_case(value.trim())
+    }
+}
+
+impl Display for ItemAttribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.key, self.value)
+    }
+}
+
+/// The variant attributes of an item. Keys are unique, ignoring case, and kept in the order
+/// they were given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ItemAttributes(Vec<ItemAttribute>);
+
+impl ItemAttributes {
+    pub const MAX_COUNT: usize = 20;
+
+    pub fn new(attributes: Vec<ItemAttribute>) -> Result<Self, ItemAttributeInvalidError> {
+        if attributes.len() > Self::MAX_COUNT {
+            return Err(ItemAttributeInvalidError::TooMany {
+                count: attributes.len(),
+            });
+        }
+        for (i, attribute) in attributes.iter().enumerate() {
+            if attributes[..i]
+                .iter()
+                .any(|other| other.key.eq_ignore_ascii_case(&attribute.key))
+            {
+                return Err(ItemAttributeInvalidError::DuplicateKey {
+                    key: attribute.key.clone(),
+                });
+            }
+        }
+        Ok(Self(attributes))
+    }
+
+    /// Returns the value of the attribute with the given key, ignoring case.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|attribute| attribute.key.eq_ignore_ascii_case(key.trim()))
+            .map(ItemAttribute::value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ItemAttribute> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum ItemAttributeInvalidError {
+    #[error("Attribute {key} must have a key of 1 to 64 and a value of 1 to 256 characters")]
+    Invalid { key: String },
+    #[error("Attribute {key} is given more than once")]
+    DuplicateKey { key: String },
+    #[error("Items can have at most 20 attributes, {count} were given")]
+    TooMany { count: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_attributes() {
+        let size = ItemAttribute::new(" size ", " M ").unwrap();
+        assert_eq!(size.to_string(), "size: M");
+        assert!(size.matches("SIZE", "m"));
+        assert!(ItemAttribute::new("", "M").is_err());
+        assert!(ItemAttribute::new("size", " ").is_err());
+
+        let color = ItemAttribute::new("color", "navy blue").unwrap();
+        let attributes = ItemAttributes::new(vec![size.clone(), color]).unwrap();
+        assert_eq!(attributes.get("Color"), Some("navy blue"));
+        assert_eq!(attributes.get("material"), None);
+
+        let duplicate = ItemAttribute::new("Size", "L").unwrap();
+        assert!(matches!(
+            ItemAttributes::new(vec![size.clone(), duplicate]),
+            Err(ItemAttributeInvalidError::DuplicateKey { .. })
+        ));
+        assert!(matches!(
+            ItemAttributes::new(vec![size; ItemAttributes::MAX_COUNT + 1]),
+            Err(ItemAttributeInvalidError::TooMany { .. })
+        ));
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+
+#[cfg(test)]
+use mockall::automock;
+use thiserror::Error;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::Item;
+use crate::domain::Wishlist;
+
+/// The ID of an [ItemEvent]. IDs grow with every event published, so that a viewer who lost
+/// their connection can resume after the last event they received.
+pub type EventId = u64;
+
+/// The [ItemEventBus] trait defines the contract for publishing what happens to the items of a
+/// wishlist to the viewers watching it live.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ItemEventBus: Send + Sync + 'static {
+    /// Publishes an event about the items of `wishlist`, giving it the next [EventId].
+    ///
+    /// # Errors
+    /// - [PublishItemEventError::Unkown] for any errors that may occur while publishing.
+    fn publish(
+        &self,
+        wishlist: &Wishlist,
+        change: ItemChange,
+    ) -> impl Future<Output = Result<ItemEvent, PublishItemEventError>> + Send;
+    /// Subscribes to the events about the items of a wishlist. With `after`, the recent events
+    /// following it are delivered first, so that nothing is missed between two subscriptions.
+    ///
+    /// # Errors
+    /// - [SubscribeItemEventsError::Unkown] for any errors that may occur while subscribing.
+    fn subscribe(
+        &self,
+        wishlist_id: Uuid,
+        after: Option<EventId>,
+    ) -> impl Future<Output = Result<ItemEventSubscription, SubscribeItemEventsError>> + Send;
+}
+
+/// What happened to an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemChange {
+    Added(Item),
+    Edited(Item),
+    /// The item was reserved by someone, or their reservation was cancelled. Who reserved it is
+    /// not told.
+    Reserved {
+        item_id: Uuid,
+        reserved: bool,
+    },
+    /// The item left the wishlist, moved to another one.
+    Removed {
+        item_id: Uuid,
+    },
+}
+
+/// The [ItemEvent] struct is a change of an item, published to the viewers of its wishlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemEvent {
+    id: EventId,
+    wishlist_id: Uuid,
+    owner_id: Uuid,
+    change: ItemChange,
+}
+
+impl ItemEvent {
+    pub fn new(id: EventId, wishlist: &Wishlist, change: ItemChange) -> Self {
+        Self {
+            id,
+            wishlist_id: wishlist.id(),
+            owner_id: wishlist.owner_id(),
+            change,
+        }
+    }
+
+    pub fn id(&self) -> EventId {
+        self.id
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn change(&self) -> &ItemChange {
+        &self.change
+    }
+
+    /// Returns the event as `viewer` may see it, if at all. Reservations are kept from the owner
+    /// of the wishlist, so they never get reservation events nor the reserver of an item.
+    pub fn seen_by(&self, viewer: Uuid) -> Option<Self> {
+        if viewer != self.owner_id {
+            return Some(self.clone());
+        }
+        let change = match &self.change {
+            ItemChange::Reserved { .. } => return None,
+            ItemChange::Added(item) => ItemChange::Added(hide_reservation(item)),
+            ItemChange::Edited(item) => ItemChange::Edited(hide_reservation(item)),
+            ItemChange::Removed { item_id } => ItemChange::Removed { item_id: *item_id },
+        };
+        Some(Self {
+            change,
+            ..self.clone()
+        })
+    }
+}
+
+fn hide_reservation(item: &Item) -> Item {
+    let mut item = item.clone();
+    item.set_reserved_by(None);
+    item
+}
+
+/// The [ItemEventSubscription] struct receives the events about the items of a wishlist: first
+/// the missed ones, then the live ones.
+#[derive(Debug)]
+pub struct ItemEventSubscription {
+    missed: VecDeque<ItemEvent>,
+    live: broadcast::Receiver<ItemEvent>,
+}
+
+impl ItemEventSubscription {
+    pub fn new(missed: Vec<ItemEvent>, live: broadcast::Receiver<ItemEvent>) -> Self {
+        Self {
+            missed: missed.into(),
+            live,
+        }
+    }
+
+    /// Waits for the next event. The subscription ends when the bus closes, or when the
+    /// subscriber falls so far behind that events were dropped: they must subscribe again after
+    /// the last event they received.
+    pub async fn next(&mut self) -> Option<ItemEvent> {
+        if let Some(event) = self.missed.pop_front() {
+            return Some(event);
+        }
+        self.live.recv().await.ok()
+    }
+}
+
+/// Tells whether the viewer of an [ItemEventStream] may still see the wishlist.
+pub type ItemEventAccess =
+    Box<dyn Fn() -> Pin<Box<dyn Future<Output = bool> + Send>> + Send + Sync>;
+
+/// The [ItemEventStream] struct is an [ItemEventSubscription] as seen by a viewer of the
+/// wishlist.
+pub struct ItemEventStream {
+    viewer: Uuid,
+    subscription: ItemEventSubscription,
+    access: Option<ItemEventAccess>,
+}
+
+impl ItemEventStream {
+    pub fn new(viewer: Uuid, subscription: ItemEventSubscription) -> Self {
+        Self {
+            viewer,
+            subscription,
+            access: None,
+        }
+    }
+
+    /// Checks before every event that the viewer may still see the wishlist, and ends the stream
+    /// as soon as they may not.
+    pub fn with_access(self, access: ItemEventAccess) -> Self {
+        Self {
+            access: Some(access),
+            ..self
+        }
+    }
+
+    /// Waits for the next event the viewer may see, see [ItemEvent::seen_by].
+    pub async fn next(&mut self) -> Option<ItemEvent> {
+        loop {
+            let event = self.subscription.next().await?;
+            if let Some(access) = &self.access {
+                if !access().await {
+                    return None;
+                }
+            }
+            if let Some(event) = event.seen_by(self.viewer) {
+                return Some(event);
+            }
+        }
+    }
+}
+
+impl Debug for ItemEventStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemEventStream")
+            .field("viewer", &self.viewer)
+            .field("subscription", &self.subscription)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The [WatchItemsRequest] struct represents a request to follow the changes of the items of a
+/// wishlist live.
+#[derive(Debug, Clone)]
+pub struct WatchItemsRequest {
+    wishlist_id: Uuid,
+    user_id: Uuid,
+    last_event_id: Option<EventId>,
+}
+
+impl WatchItemsRequest {
+    pub fn new(wishlist_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+            last_event_id: None,
+        }
+    }
+
+    /// Resumes after the last event the viewer received.
+    pub fn with_last_event_id(self, last_event_id: Option<EventId>) -> Self {
+        Self {
+            last_event_id,
+            ..self
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn last_event_id(&self) -> Option<EventId> {
+        self.last_event_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WatchItemsError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum PublishItemEventError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SubscribeItemEventsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+impl From<SubscribeItemEventsError> for WatchItemsError {
+    fn from(e: SubscribeItemEventsError) -> Self {
+        match e {
+            SubscribeItemEventsError::Unkown(err) => Self::Unkown(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reservations_kept_from_owner() {
+        let (owner, viewer) = (Uuid::now_v7(), Uuid::now_v7());
+        let wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            owner,
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        );
+        let mut item = Item::create(
+            Uuid::now_v7(),
+            wishlist.id(),
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        item.set_reserved_by(Some(viewer));
+        let edited = ItemEvent::new(1, &wishlist, ItemChange::Edited(item.clone()));
+        let reserved = ItemEvent::new(
+            2,
+            &wishlist,
+            ItemChange::Reserved {
+                item_id: item.id(),
+                reserved: true,
+            },
+        );
+
+        assert_eq!(edited.seen_by(viewer), Some(edited.clone()));
+        assert_eq!(reserved.seen_by(viewer), Some(reserved.clone()));
+        assert_eq!(reserved.seen_by(owner), None);
+        let ItemChange::Edited(seen) = edited.seen_by(owner).unwrap().change().clone() else {
+            panic!("expected an edition");
+        };
+        assert_eq!(seen.reserved_by(), None);
+
+        let (sender, live) = broadcast::channel(4);
+        let mut stream = ItemEventStream::new(
+            owner,
+            ItemEventSubscription::new(vec![reserved.clone()], live),
+        );
+        sender.send(edited).unwrap();
+        drop(sender);
+        assert_eq!(stream.next().await.map(|event| event.id()), Some(1));
+        assert_eq!(stream.next().await, None);
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+use thiserror::Error;
+use url::Url;
+
+use super::{ItemMetadata, ItemPage};
+
+/// The [ItemMetadataExtractor] trait defines the contract for reading product details out of a
+/// fetched [ItemPage].
+///
+/// Extractors declare the hosts they understand; the [ExtractorRegistry] picks the most specific
+/// one for a page.
+pub trait ItemMetadataExtractor: Send + Sync + 'static {
+    /// Returns the patterns of the hosts this extractor is written for.
+    fn hosts(&self) -> Vec<HostPattern>;
+    /// Extracts whatever product details the page exposes.
+    fn extract(&self, page: &ItemPage) -> ItemMetadata;
+}
+
+/// The [HostPattern] struct matches the host of a URL.
+///
+/// A pattern is a dot-separated list of labels where a leading `*` matches the domain itself and
+/// any of its subdomains, and a trailing `*` matches any public suffix:
+/// - `www.ikea.com` only matches that host.
+/// - `*.etsy.com` matches `etsy.com` and `www.etsy.com`.
+/// - `*.amazon.*` matches `amazon.de` and `www.amazon.co.uk`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostPattern {
+    any_subdomain: bool,
+    labels: Vec<String>,
+    any_suffix: bool,
+}
+
+impl HostPattern {
+    pub fn new(pattern: &str) -> Result<Self, HostPatternInvalidError> {
+        let invalid = || HostPatternInvalidError {
+            invalid_pattern: pattern.to_string(),
+        };
+        let pattern = pattern.trim().to_ascii_lowercase();
+        let mut labels: Vec<String> = pattern.split('.').map(str::to_string).collect();
+        let any_subdomain = labels.first().is_some_and(|label| label == "*");
+        if any_subdomain {
+            labels.remove(0);
+        }
+        let any_suffix = labels.last().is_some_and(|label| label == "*");
+        if any_suffix {
+            labels.pop();
+        }
+        if labels.is_empty()
+            || labels.iter().any(|label| {
+                label.is_empty() || !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            })
+        {
+            return Err(invalid());
+        }
+        Ok(Self {
+            any_subdomain,
+            labels,
+            any_suffix,
+        })
+    }
+
+    /// Returns true if `host` is matched by the pattern.
+    pub fn matches(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        let host: Vec<&str> = host.split('.').collect();
+        let len = self.labels.len();
+        let starts = if self.any_subdomain {
+            (0..host.len()).collect::<Vec<_>>()
+        } else {
+            vec![0]
+        };
+        starts.into_iter().any(|start| {
+            let Some(candidate) = host.get(start..start + len) else {
+                return false;
+            };
+            let rest = host.len() - start - len;
+            candidate
+                .iter()
+                .zip(&self.labels)
+                .all(|(label, expected)| label == expected)
+                && if self.any_suffix { rest > 0 } else { rest == 0 }
+        })
+    }
+
+    /// Returns how specific the pattern is: literal labels count the most, and a pattern without
+    /// wildcards beats one with the same labels and wildcards.
+    pub fn specificity(&self) -> usize {
+        self.labels.len() * 4 + usize::from(!self.any_subdomain) * 2 + usize::from(!self.any_suffix)
+    }
+}
+
+impl Display for HostPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.any_subdomain {
+            f.write_str("*.")?;
+        }
+        f.write_str(&self.labels.join("."))?;
+        if self.any_suffix {
+            f.write_str(".*")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Host pattern is invalid")]
+pub struct HostPatternInvalidError {
+    pub invalid_pattern: String,
+}
+
+/// The [ExtractorRegistry] struct holds the [ItemMetadataExtractor]s available to read product
+/// pages.
+///
+/// The most specific extractor matching the page's host runs first, and the fallback extractor
+/// fills in whatever it missed.
+#[derive(Clone)]
+pub struct ExtractorRegistry {
+    fallback: Arc<dyn ItemMetadataExtractor>,
+    extractors: Vec<Arc<dyn ItemMetadataExtractor>>,
+}
+
+impl ExtractorRegistry {
+    /// Creates a registry with the extractor used for every page, e.g. a generic OpenGraph one.
+    pub fn new(fallback: impl ItemMetadataExtractor) -> Self {
+        Self {
+            fallback: Arc::new(fallback),
+            extractors: Vec::new(),
+        }
+    }
+
+    /// Registers an extractor. On equally specific patterns, the last registered one wins so
+    /// built-in extractors can be overridden.
+    pub fn register(mut self, extractor: impl ItemMetadataExtractor) -> Self {
+        self.extractors.push(Arc::new(extractor));
+        self
+    }
+
+    /// Returns the most specific extractor registered for the host of `url`, if any.
+    pub fn select(&self, url: &Url) -> Option<&dyn ItemMetadataExtractor> {
+        let host = url.host_str()?;
+        self.extractors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, extractor)| {
+                extractor
+                    .hosts()
+                    .iter()
+                    .filter(|pattern| pattern.matches(host))
+                    .map(HostPattern::specificity)
+                    .max()
+                    .map(|specificity| ((specificity, index), extractor.as_ref()))
+            })
+            .max_by_key(|(rank, _)| *rank)
+            .map(|(_, extractor)| extractor)
+    }
+
+    /// Extracts the product details of a page with the most specific extractor, completed by the
+    /// fallback one.
+    pub fn extract(&self, page: &ItemPage) -> ItemMetadata {
+        let fallback = self.fallback.extract(page);
+        match self.select(page.url()) {
+            Some(extractor) => extractor.extract(page).or(fallback),
+            None => fallback,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(Vec<&'static str>, &'static str);
+
+    impl ItemMetadataExtractor for Fixed {
+        fn hosts(&self) -> Vec<HostPattern> {
+            self.0
+                .iter()
+                .map(|p| HostPattern::new(p).unwrap())
+                .collect()
+        }
+
+        fn extract(&self, _page: &ItemPage) -> ItemMetadata {
+            ItemMetadata::new(Some(self.1.into()), None, None)
+        }
+    }
+
+    fn title_for(registry: &ExtractorRegistry, url: &str) -> String {
+        let page = ItemPage::new(Url::parse(url).unwrap(), String::new());
+        registry.extract(&page).title().unwrap().to_string()
+    }
+
+    #[test]
+    fn host_pattern_matches() {
+        let exact = HostPattern::new("www.ikea.com").unwrap();
+        assert!(exact.matches("www.ikea.com"));
+        assert!(!exact.matches("ikea.com"));
+
+        let subdomains = HostPattern::new("*.etsy.com").unwrap();
+        assert!(subdomains.matches("etsy.com"));
+        assert!(subdomains.matches("www.etsy.com"));
+        assert!(!subdomains.matches("notetsy.com"));
+        assert!(!subdomains.matches("etsy.com.evil.example"));
+
+        let suffixes = HostPattern::new("*.amazon.*").unwrap();
+        assert!(suffixes.matches("amazon.de"));
+        assert!(suffixes.matches("www.amazon.co.uk"));
+        assert!(!suffixes.matches("amazon"));
+
+        assert!(HostPattern::new("").is_err());
+        assert!(HostPattern::new("*.*").is_err());
+        assert!(HostPattern::new("shop..example").is_err());
+    }
+
+    #[test]
+    fn registry_selects_most_specific_extractor() {
+        let registry = ExtractorRegistry::new(Fixed(vec![], "generic"))
+            .register(Fixed(vec!["*.amazon.*"], "amazon"))
+            .register(Fixed(vec!["*.amazon.co.uk"], "amazon uk"))
+            .register(Fixed(vec!["smile.amazon.co.uk"], "smile"));
+
+        assert_eq!(title_for(&registry, "https://www.amazon.de/dp/1"), "amazon");
+        assert_eq!(
+            title_for(&registry, "https://www.amazon.co.uk/dp/1"),
+            "amazon uk"
+        );
+        assert_eq!(
+            title_for(&registry, "https://smile.amazon.co.uk/dp/1"),
+            "smile"
+        );
+        assert_eq!(title_for(&registry, "https://shop.example/p/1"), "generic");
+    }
+
+    #[test]
+    fn registry_prefers_last_registered_on_ties() {
+        let registry = ExtractorRegistry::new(Fixed(vec![], "generic"))
+            .register(Fixed(vec!["*.etsy.com"], "built-in"))
+            .register(Fixed(vec!["*.etsy.com"], "custom"));
+
+        assert_eq!(
+            title_for(&registry, "https://www.etsy.com/listing/1"),
+            "custom"
+        );
+    }
+}
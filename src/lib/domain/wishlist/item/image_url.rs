@@ -1,11 +1,43 @@
+use std::fmt::{Display, Formatter};
+
+use thiserror::Error;
 use url::Url;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ItemImageUrl(url::Url);
 
+impl ItemImageUrl {
+    pub fn new(url: &str) -> Result<Self, ItemImageUrlInvalidError> {
+        match Url::parse(url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {
+                Ok(ItemImageUrl(url))
+            }
+            _ => Err(ItemImageUrlInvalidError {
+                invalid_url: url.to_string(),
+            }),
+        }
+    }
+
+    pub fn as_url(&self) -> &Url {
+        &self.0
+    }
+}
+
 impl From<&str> for ItemImageUrl {
     fn from(value: &str) -> Self {
         let url = Url::parse(value).expect("Parse error");
         ItemImageUrl(url)
     }
 }
+
+impl Display for ItemImageUrl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Image URL is invalid")]
+pub struct ItemImageUrlInvalidError {
+    pub invalid_url: String,
+}
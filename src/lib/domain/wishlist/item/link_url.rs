@@ -1,11 +1,43 @@
+use std::fmt::{Display, Formatter};
+
+use thiserror::Error;
 use url::Url;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ItemLinkUrl(url::Url);
 
+impl ItemLinkUrl {
+    pub fn new(url: &str) -> Result<Self, ItemLinkUrlInvalidError> {
+        match Url::parse(url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {
+                Ok(ItemLinkUrl(url))
+            }
+            _ => Err(ItemLinkUrlInvalidError {
+                invalid_url: url.to_string(),
+            }),
+        }
+    }
+
+    pub fn as_url(&self) -> &Url {
+        &self.0
+    }
+}
+
 impl From<&str> for ItemLinkUrl {
     fn from(value: &str) -> Self {
         let url = Url::parse(value).expect("Parse error");
         ItemLinkUrl(url)
     }
 }
+
+impl Display for ItemLinkUrl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Link URL is invalid")]
+pub struct ItemLinkUrlInvalidError {
+    pub invalid_url: String,
+}
@@ -0,0 +1,591 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use rust_decimal::Decimal;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{Item, ItemPriority};
+use crate::domain::{Cursor, Page, PageRequest, ShareToken, SortDirection, SortValue, Wishlist};
+
+/// The [ListItemsRequest] struct represents a request to list a page of the items of a wishlist,
+/// filtered and sorted. Private wishlists are only listed for a viewer who may see them or who
+/// has their share token.
+#[derive(Debug, Clone)]
+pub struct ListItemsRequest {
+    wishlist_id: Uuid,
+    viewer_id: Option<Uuid>,
+    share_token: Option<ShareToken>,
+    filter: ItemFilter,
+    sort: ItemSort,
+    page: PageRequest,
+}
+
+impl ListItemsRequest {
+    pub fn new(wishlist_id: Uuid, filter: ItemFilter, sort: ItemSort, page: PageRequest) -> Self {
+        Self {
+            wishlist_id,
+            viewer_id: None,
+            share_token: None,
+            filter,
+            sort,
+            page,
+        }
+    }
+
+    /// Lists the items as `viewer_id` sees them, which opens the wishlists they own or were
+    /// granted.
+    pub fn with_viewer(self, viewer_id: Option<Uuid>) -> Self {
+        Self { viewer_id, ..self }
+    }
+
+    /// Opens a private wishlist, when the token is the one of its share link.
+    pub fn with_share_token(self, share_token: Option<ShareToken>) -> Self {
+        Self {
+            share_token,
+            ..self
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn viewer_id(&self) -> Option<Uuid> {
+        self.viewer_id
+    }
+
+    pub fn share_token(&self) -> Option<&ShareToken> {
+        self.share_token.as_ref()
+    }
+
+    pub fn filter(&self) -> &ItemFilter {
+        &self.filter
+    }
+
+    pub fn sort(&self) -> &ItemSort {
+        &self.sort
+    }
+
+    pub fn page(&self) -> &PageRequest {
+        &self.page
+    }
+
+    /// Returns true if `item` belongs to the listed wishlist and passes the filter.
+    pub fn matches(&self, item: &Item) -> bool {
+        item.wishlist_id() == self.wishlist_id && self.filter.matches(item)
+    }
+}
+
+/// The [ItemListing] struct holds a page of the listed items of a wishlist, in the requested
+/// order, along with the wishlist itself for the sections the items are in.
+#[derive(Debug, Clone)]
+pub struct ItemListing {
+    wishlist: Wishlist,
+    items: Page<Item>,
+}
+
+impl ItemListing {
+    pub fn new(wishlist: Wishlist, items: Page<Item>) -> Self {
+        Self { wishlist, items }
+    }
+
+    pub fn wishlist(&self) -> &Wishlist {
+        &self.wishlist
+    }
+
+    pub fn items(&self) -> &[Item] {
+        self.items.entries()
+    }
+
+    pub fn next_cursor(&self) -> Option<&Cursor> {
+        self.items.next_cursor()
+    }
+}
+
+/// The [ViewWishlistsRequest] struct represents a request by a user to see several wishlists at
+/// once, along with all their items.
+#[derive(Debug, Clone)]
+pub struct ViewWishlistsRequest {
+    user_id: Uuid,
+    wishlist_ids: Vec<Uuid>,
+}
+
+impl ViewWishlistsRequest {
+    pub fn new(user_id: Uuid, wishlist_ids: Vec<Uuid>) -> Self {
+        Self {
+            user_id,
+            wishlist_ids,
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn wishlist_ids(&self) -> &[Uuid] {
+        &self.wishlist_ids
+    }
+}
+
+/// The [WishlistView] struct holds a wishlist and all its items, in the owner's order, as a user
+/// sees them: reservations are kept from the owner.
+#[derive(Debug, Clone)]
+pub struct WishlistView {
+    wishlist: Wishlist,
+    items: Vec<Item>,
+    reservation_count: Option<usize>,
+}
+
+impl WishlistView {
+    /// The view of `viewer_id` on `wishlist` and its `items`. Items missing from the owner's order
+    /// come last.
+    pub fn new(viewer_id: Uuid, wishlist: Wishlist, mut items: Vec<Item>) -> Self {
+        items.sort_by_key(|item| {
+            (
+                wishlist.position_of(item.id()).unwrap_or(usize::MAX),
+                item.id(),
+            )
+        });
+        let reservation_count = if wishlist.owner_id() == viewer_id {
+            for item in items.iter_mut() {
+                item.set_reserved_by(None);
+            }
+            None
+        } else {
+            Some(
+                items
+                    .iter()
+                    .filter(|item| item.reserved_by().is_some())
+                    .count(),
+            )
+        };
+        Self {
+            wishlist,
+            items,
+            reservation_count,
+        }
+    }
+
+    pub fn wishlist(&self) -> &Wishlist {
+        &self.wishlist
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// How many items are reserved, unless the viewer owns the wishlist.
+    pub fn reservation_count(&self) -> Option<usize> {
+        self.reservation_count
+    }
+}
+
+/// Criteria an item must meet to be listed. Unset criteria match every item.
+#[derive(Debug, Clone, Default)]
+pub struct ItemFilter {
+    priority: Option<ItemPriority>,
+    min_priority: Option<ItemPriority>,
+    attribute: Option<(String, String)>,
+    price_range: PriceRange,
+}
+
+impl ItemFilter {
+    /// Only lists items with exactly this priority.
+    pub fn with_priority(self, priority: Option<ItemPriority>) -> Self {
+        Self { priority, ..self }
+    }
+
+    /// Only lists items with at least this priority.
+    pub fn with_min_priority(self, min_priority: Option<ItemPriority>) -> Self {
+        Self {
+            min_priority,
+            ..self
+        }
+    }
+
+    /// Only lists items having the attribute `key` set to `value`, ignoring case.
+    pub fn with_attribute(self, attribute: Option<(String, String)>) -> Self {
+        Self { attribute, ..self }
+    }
+
+    /// Only lists items whose price is in the range.
+    pub fn with_price_range(self, price_range: PriceRange) -> Self {
+        Self {
+            price_range,
+            ..self
+        }
+    }
+
+    pub fn matches(&self, item: &Item) -> bool {
+        self.priority
+            .is_none_or(|priority| item.priority() == priority)
+            && self
+                .min_priority
+                .is_none_or(|priority| item.priority() >= priority)
+            && self.attribute.as_ref().is_none_or(|(key, value)| {
+                item.attributes()
+                    .iter()
+                    .any(|attribute| attribute.matches(key, value))
+            })
+            && self.price_range.contains(item)
+    }
+}
+
+/// Bounds on the price of items, both included. Items without a price are out of any bounded
+/// range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PriceRange {
+    min: Option<Decimal>,
+    max: Option<Decimal>,
+}
+
+impl PriceRange {
+    pub fn new(min: Option<Decimal>, max: Option<Decimal>) -> Result<Self, PriceRangeInvalidError> {
+        match (min, max) {
+            (Some(min), Some(max)) if min > max => Err(PriceRangeInvalidError { min, max }),
+            _ => Ok(Self { min, max }),
+        }
+    }
+
+    pub fn min(&self) -> Option<Decimal> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<Decimal> {
+        self.max
+    }
+
+    pub fn contains(&self, item: &Item) -> bool {
+        if self.min.is_none() && self.max.is_none() {
+            return true;
+        }
+        item.price().is_some_and(|price| {
+            self.min.is_none_or(|min| price.value() >= min)
+                && self.max.is_none_or(|max| price.value() <= max)
+        })
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Minimum price {min} is above maximum price {max}")]
+pub struct PriceRangeInvalidError {
+    pub min: Decimal,
+    pub max: Decimal,
+}
+
+/// The keys items can be sorted by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemSortKey {
+    /// The order the owner arranged the items in.
+    Position,
+    /// The order items were added in.
+    Created,
+    Title,
+    Price,
+    Priority,
+    /// The value of a variant attribute, e.g. `size`.
+    Attribute(String),
+}
+
+impl Display for ItemSortKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemSortKey::Position => f.write_str("position"),
+            ItemSortKey::Created => f.write_str("created"),
+            ItemSortKey::Title => f.write_str("title"),
+            ItemSortKey::Price => f.write_str("price"),
+            ItemSortKey::Priority => f.write_str("priority"),
+            ItemSortKey::Attribute(key) => write!(f, "attribute:{}", key),
+        }
+    }
+}
+
+impl FromStr for ItemSortKey {
+    type Err = ItemSortInvalidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "position" => Ok(ItemSortKey::Position),
+            "created" => Ok(ItemSortKey::Created),
+            "title" => Ok(ItemSortKey::Title),
+            "price" => Ok(ItemSortKey::Price),
+            "priority" => Ok(ItemSortKey::Priority),
+            _ => match value.strip_prefix("attribute:").map(str::trim) {
+                Some(key) if !key.is_empty() => Ok(ItemSortKey::Attribute(key.to_string())),
+                _ => Err(ItemSortInvalidError {
+                    invalid_sort: value.to_string(),
+                }),
+            },
+        }
+    }
+}
+
+/// How listed items are ordered. Items that are equal on the key keep the order they were added
+/// in; items without a value for the key, e.g. without a price, always come last.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemSort {
+    key: ItemSortKey,
+    direction: SortDirection,
+}
+
+impl ItemSort {
+    pub fn new(key: ItemSortKey, direction: SortDirection) -> Self {
+        Self { key, direction }
+    }
+
+    pub fn key(&self) -> &ItemSortKey {
+        &self.key
+    }
+
+    pub fn direction(&self) -> SortDirection {
+        self.direction
+    }
+
+    /// The position of an item of `wishlist` in the listing. Positions are kept by the wishlist,
+    /// not the items, hence the wishlist for [ItemSortKey::Position].
+    pub fn position(&self, item: &Item, wishlist: &Wishlist) -> Cursor {
+        let value = match &self.key {
+            ItemSortKey::Position => wishlist
+                .position_of(item.id())
+                .map(|position| SortValue::Integer(position as i64)),
+            ItemSortKey::Created => Some(SortValue::Id(item.id())),
+            ItemSortKey::Title => Some(SortValue::Text(item.title().to_string().to_lowercase())),
+            ItemSortKey::Price => item.price().map(|price| SortValue::Decimal(price.value())),
+            ItemSortKey::Priority => Some(SortValue::Integer(item.priority() as i64)),
+            ItemSortKey::Attribute(key) => item
+                .attributes()
+                .get(key)
+                .map(|value| SortValue::Text(value.to_lowercase())),
+        };
+        Cursor::new(value, item.id())
+    }
+
+    /// Compares two items of `wishlist` on the sort key.
+    pub fn compare(&self, a: &Item, b: &Item, wishlist: &Wishlist) -> Ordering {
+        self.position(a, wishlist)
+            .compare(&self.position(b, wishlist), self.direction)
+    }
+}
+
+impl Default for ItemSort {
+    fn default() -> Self {
+        Self::new(ItemSortKey::Position, SortDirection::Ascending)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Sort is invalid")]
+pub struct ItemSortInvalidError {
+    pub invalid_sort: String,
+}
+
+#[derive(Debug, Error)]
+pub enum ListItemsError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ViewWishlistsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [ViewSharedWishlistRequest] struct represents a request by someone following a link to a
+/// wishlist, by its slug, to see it with all its items. `viewer_id` tells the reservations they
+/// made apart; they need not be a user.
+#[derive(Debug, Clone)]
+pub struct ViewSharedWishlistRequest {
+    slug: String,
+    viewer_id: Uuid,
+    share_token: Option<ShareToken>,
+}
+
+impl ViewSharedWishlistRequest {
+    pub fn new(slug: String, viewer_id: Uuid) -> Self {
+        Self {
+            slug,
+            viewer_id,
+            share_token: None,
+        }
+    }
+
+    /// Shows the wishlist even if it is private, when the token is the one of its share link.
+    pub fn with_share_token(self, share_token: Option<ShareToken>) -> Self {
+        Self {
+            share_token,
+            ..self
+        }
+    }
+
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    pub fn viewer_id(&self) -> Uuid {
+        self.viewer_id
+    }
+
+    pub fn share_token(&self) -> Option<&ShareToken> {
+        self.share_token.as_ref()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ViewSharedWishlistError {
+    #[error("Wishlist {slug} does not exist")]
+    WishlistDoesNotExist { slug: String },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ItemAttribute, ItemAttributes};
+
+    fn item(title: &str, price: Option<f32>, priority: ItemPriority, size: Option<&str>) -> Item {
+        let mut item = Item::create(
+            Uuid::now_v7(),
+            Uuid::nil(),
+            title.into(),
+            "https://shop.example/p".into(),
+            None,
+            price.map(Into::into),
+        );
+        item.set_priority(priority);
+        let attributes = size
+            .map(|size| vec![ItemAttribute::new("size", size).unwrap()])
+            .unwrap_or_default();
+        item.set_attributes(ItemAttributes::new(attributes).unwrap());
+        item
+    }
+
+    fn wishlist() -> Wishlist {
+        Wishlist::new(
+            Uuid::nil(),
+            Uuid::nil(),
+            "Birthday".into(),
+            "Birthday".into(),
+            false,
+        )
+    }
+
+    fn sorted_titles(items: &[Item], sort: &str, direction: SortDirection) -> Vec<String> {
+        let sort = ItemSort::new(sort.parse().unwrap(), direction);
+        let wishlist = wishlist();
+        let mut items = items.to_vec();
+        items.sort_by(|a, b| sort.compare(a, b, &wishlist));
+        items.iter().map(|item| item.title().to_string()).collect()
+    }
+
+    #[test]
+    fn sort_items() {
+        let items = vec![
+            item("b", Some(20.0), ItemPriority::Normal, Some("M")),
+            item("C", None, ItemPriority::MustHave, None),
+            item("a", Some(10.0), ItemPriority::NiceToHave, Some("L")),
+        ];
+        let asc = SortDirection::Ascending;
+        let desc = SortDirection::Descending;
+
+        assert_eq!(sorted_titles(&items, "created", desc), ["a", "C", "b"]);
+        assert_eq!(sorted_titles(&items, "title", asc), ["a", "b", "C"]);
+        assert_eq!(sorted_titles(&items, "price", asc), ["a", "b", "C"]);
+        assert_eq!(sorted_titles(&items, "price", desc), ["b", "a", "C"]);
+        assert_eq!(sorted_titles(&items, "priority", desc), ["C", "b", "a"]);
+        assert_eq!(
+            sorted_titles(&items, "attribute:size", asc),
+            ["a", "b", "C"]
+        );
+        assert!("attribute:".parse::<ItemSortKey>().is_err());
+        assert!("colour".parse::<ItemSortKey>().is_err());
+    }
+
+    #[test]
+    fn list_items_in_position_order() {
+        let items = vec![
+            item("a", None, ItemPriority::Normal, None),
+            item("b", None, ItemPriority::Normal, None),
+            item("c", None, ItemPriority::Normal, None),
+        ];
+        let mut wishlist = wishlist();
+        for id in [items[2].id(), items[0].id()] {
+            wishlist.add_item(id);
+        }
+        let titles = |sort: ItemSort| -> Vec<String> {
+            let mut items = items.clone();
+            items.sort_by(|a, b| sort.compare(a, b, &wishlist));
+            items.iter().map(|item| item.title().to_string()).collect()
+        };
+
+        assert_eq!(titles(ItemSort::default()), ["c", "a", "b"]);
+        let sort = ItemSort::new(ItemSortKey::Position, SortDirection::Descending);
+        assert_eq!(titles(sort), ["a", "c", "b"]);
+    }
+
+    #[test]
+    fn filter_items() {
+        let must_have = item("a", None, ItemPriority::MustHave, Some("M"));
+        let normal = item("b", None, ItemPriority::Normal, Some("L"));
+
+        let filter = ItemFilter::default().with_min_priority(Some(ItemPriority::Normal));
+        assert!(filter.matches(&must_have) && filter.matches(&normal));
+        let filter = ItemFilter::default().with_priority(Some(ItemPriority::Normal));
+        assert!(!filter.matches(&must_have) && filter.matches(&normal));
+        let filter =
+            ItemFilter::default().with_attribute(Some(("Size".to_string(), "m".to_string())));
+        assert!(filter.matches(&must_have) && !filter.matches(&normal));
+    }
+
+    #[test]
+    fn filter_items_by_price() {
+        let cheap = item("a", Some(10.0), ItemPriority::Normal, None);
+        let pricey = item("b", Some(20.0), ItemPriority::Normal, None);
+        let unpriced = item("c", None, ItemPriority::Normal, None);
+
+        let range = PriceRange::new(Some(Decimal::from(15)), None).unwrap();
+        let filter = ItemFilter::default().with_price_range(range);
+        assert!(!filter.matches(&cheap) && filter.matches(&pricey) && !filter.matches(&unpriced));
+        let range = PriceRange::new(None, Some(Decimal::from(10))).unwrap();
+        let filter = ItemFilter::default().with_price_range(range);
+        assert!(filter.matches(&cheap) && !filter.matches(&pricey));
+        assert!(ItemFilter::default().matches(&unpriced));
+        assert!(PriceRange::new(Some(Decimal::from(2)), Some(Decimal::from(1))).is_err());
+    }
+
+    #[test]
+    fn test_wishlist_view_keeps_reservations_from_owner() {
+        let (owner, friend) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            owner,
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        );
+        let (first, mut second) = (
+            item("a", None, ItemPriority::Normal, None),
+            item("b", None, ItemPriority::Normal, None),
+        );
+        second.set_reserved_by(Some(friend));
+        wishlist.add_item(second.id());
+        wishlist.add_item(first.id());
+        let items = vec![first.clone(), second.clone()];
+
+        let seen = WishlistView::new(friend, wishlist.clone(), items.clone());
+        assert_eq!(seen.reservation_count(), Some(1));
+        assert_eq!(seen.items()[0].id(), second.id());
+        assert_eq!(seen.items()[0].reserved_by(), Some(friend));
+
+        let seen = WishlistView::new(owner, wishlist, items);
+        assert_eq!(seen.reservation_count(), None);
+        assert!(seen.items().iter().all(|item| item.reserved_by().is_none()));
+    }
+}
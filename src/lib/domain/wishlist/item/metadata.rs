@@ -0,0 +1,69 @@
+use super::{ItemImageUrl, ItemPrice, ItemTitle};
+
+/// The [ItemMetadata] struct holds the product details extracted from a product page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ItemMetadata {
+    title: Option<ItemTitle>,
+    image_url: Option<ItemImageUrl>,
+    price: Option<ItemPrice>,
+}
+
+impl ItemMetadata {
+    pub fn new(
+        title: Option<ItemTitle>,
+        image_url: Option<ItemImageUrl>,
+        price: Option<ItemPrice>,
+    ) -> Self {
+        Self {
+            title,
+            image_url,
+            price,
+        }
+    }
+
+    pub fn title(&self) -> Option<&ItemTitle> {
+        self.title.as_ref()
+    }
+
+    pub fn image_url(&self) -> Option<&ItemImageUrl> {
+        self.image_url.as_ref()
+    }
+
+    pub fn price(&self) -> Option<&ItemPrice> {
+        self.price.as_ref()
+    }
+
+    /// Fills the details missing from `self` with the ones found in `other`.
+    pub fn or(self, other: ItemMetadata) -> Self {
+        Self {
+            title: self.title.or(other.title),
+            image_url: self.image_url.or(other.image_url),
+            price: self.price.or(other.price),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.title.is_some() && self.image_url.is_some() && self.price.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ItemMetadata;
+
+    #[test]
+    fn merge_metadata() {
+        let first = ItemMetadata::new(Some("First".into()), None, None);
+        let second = ItemMetadata::new(
+            Some("Second".into()),
+            Some("https://a.b/c.png".into()),
+            None,
+        );
+        let merged = first.or(second);
+
+        assert_eq!(merged.title(), Some(&"First".into()));
+        assert_eq!(merged.image_url(), Some(&"https://a.b/c.png".into()));
+        assert!(merged.price().is_none());
+        assert!(!merged.is_complete());
+    }
+}
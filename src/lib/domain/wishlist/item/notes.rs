@@ -0,0 +1,57 @@
+use std::fmt::{Display, Formatter};
+
+use thiserror::Error;
+
+/// Free-form notes about an item, e.g. "the blue one, not the black one".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemNotes(String);
+
+impl ItemNotes {
+    pub const MAX_LENGTH: usize = 2000;
+
+    /// Trims the notes and rejects them if they are empty or longer than [Self::MAX_LENGTH]
+    /// characters.
+    pub fn new(notes: &str) -> Result<Self, ItemNotesInvalidError> {
+        let notes = notes.trim();
+        let length = notes.chars().count();
+        if length == 0 || length > Self::MAX_LENGTH {
+            return Err(ItemNotesInvalidError { length });
+        }
+        Ok(ItemNotes(notes.to_string()))
+    }
+}
+
+impl From<&str> for ItemNotes {
+    fn from(value: &str) -> Self {
+        ItemNotes(value.to_string())
+    }
+}
+
+impl Display for ItemNotes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error(
+    "Notes must be between 1 and {} characters long",
+    ItemNotes::MAX_LENGTH
+)]
+pub struct ItemNotesInvalidError {
+    pub length: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ItemNotes;
+
+    #[test]
+    fn create_notes() {
+        assert_eq!(ItemNotes::new("  size M ").unwrap().to_string(), "size M");
+        assert_eq!(ItemNotes::new("   ").unwrap_err().length, 0);
+        let too_long = "é".repeat(ItemNotes::MAX_LENGTH + 1);
+        assert!(ItemNotes::new(&too_long).is_err());
+        assert!(ItemNotes::new(&too_long[2..]).is_ok());
+    }
+}
@@ -0,0 +1,66 @@
+use std::future::Future;
+
+#[cfg(test)]
+use mockall::automock;
+use thiserror::Error;
+use url::Url;
+
+use super::ItemLinkUrl;
+
+/// The [ItemPageFetcher] trait defines the contract for downloading the page an [ItemLinkUrl]
+/// points to.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait ItemPageFetcher: Send + Sync + 'static {
+    /// Fetches the page behind `url`.
+    ///
+    /// # Arguments
+    /// * `url` - A reference to the `ItemLinkUrl` of the product page.
+    ///
+    /// # Returns
+    /// - `Ok(page)` with the final URL, after redirects, and the HTML of the page.
+    ///
+    /// # Errors
+    /// - [FetchItemPageError::Forbidden] if the URL targets a host that may not be fetched.
+    /// - [FetchItemPageError::TooLarge] if the page exceeds the configured size limit.
+    /// - [FetchItemPageError::Timeout] if the page could not be fetched in time.
+    /// - [FetchItemPageError::Unknown] for any other errors that may occur while fetching.
+    fn fetch(
+        &self,
+        url: &ItemLinkUrl,
+    ) -> impl Future<Output = Result<ItemPage, FetchItemPageError>> + Send;
+}
+
+/// The [ItemPage] struct is a downloaded product page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemPage {
+    url: Url,
+    html: String,
+}
+
+impl ItemPage {
+    pub fn new(url: Url, html: String) -> Self {
+        Self { url, html }
+    }
+
+    /// The URL the page was served from, after redirects.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FetchItemPageError {
+    #[error("Fetching {url} is not allowed")]
+    Forbidden { url: String },
+    #[error("Page is larger than {limit} bytes")]
+    TooLarge { limit: usize },
+    #[error("Fetching the page timed out")]
+    Timeout,
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
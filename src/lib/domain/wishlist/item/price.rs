@@ -1,12 +1,89 @@
 use core::f32;
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 
 use rust_decimal::{prelude::FromPrimitive, Decimal};
+use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ItemPrice(rust_decimal::Decimal);
 
+impl ItemPrice {
+    pub fn new(price: Decimal) -> Result<Self, ItemPriceInvalidError> {
+        if price.is_sign_negative() {
+            return Err(ItemPriceInvalidError {
+                invalid_price: price.to_string(),
+            });
+        }
+        Ok(ItemPrice(price.normalize()))
+    }
+
+    /// Parses a price as written on a product page, e.g. `"1 299,99"` or `"$1,299.99"`.
+    pub fn parse(price: &str) -> Result<Self, ItemPriceInvalidError> {
+        let invalid = || ItemPriceInvalidError {
+            invalid_price: price.to_string(),
+        };
+        let digits: String = price
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+            .collect();
+        if digits.is_empty() {
+            return Err(invalid());
+        }
+        // The last separator followed by one or two digits is the decimal separator, every other
+        // separator groups thousands.
+        let normalized = match digits.rfind(['.', ',']) {
+            Some(pos) if (1..=2).contains(&(digits.len() - pos - 1)) => {
+                let (int, frac) = digits.split_at(pos);
+                format!("{}.{}", int.replace(['.', ','], ""), &frac[1..])
+            }
+            _ => digits.replace(['.', ','], ""),
+        };
+        let value = Decimal::from_str(&normalized).map_err(|_| invalid())?;
+        Self::new(value)
+    }
+
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+}
+
 impl From<f32> for ItemPrice {
     fn from(value: f32) -> Self {
         Self(Decimal::from_f32(value).unwrap())
     }
 }
+
+impl Display for ItemPrice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Price is invalid")]
+pub struct ItemPriceInvalidError {
+    pub invalid_price: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ItemPrice;
+
+    #[test]
+    fn parse_price() {
+        assert_eq!(ItemPrice::parse("12").unwrap().to_string(), "12");
+        assert_eq!(
+            ItemPrice::parse("$1,299.99").unwrap().to_string(),
+            "1299.99"
+        );
+        assert_eq!(
+            ItemPrice::parse("1.299,90 €").unwrap().to_string(),
+            "1299.9"
+        );
+        assert_eq!(ItemPrice::parse("1 000").unwrap().to_string(), "1000");
+        assert!(ItemPrice::parse("free").is_err());
+    }
+}
@@ -0,0 +1,330 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use rust_decimal::Decimal;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::{ForgetUserError, ShareToken};
+
+use super::ItemPrice;
+
+/// The [PriceHistoryRepository] trait defines the contract for storing the prices observed for
+/// items over time, and who wants to hear about them.
+#[cfg_attr(test, automock)]
+#[allow(refining_impl_trait)]
+pub trait PriceHistoryRepository {
+    /// Appends a price observation to an item's history.
+    ///
+    /// # Errors
+    /// - [RecordPriceError::Unkown] for any errors that may occur while recording.
+    fn record_price(
+        &self,
+        item_id: Uuid,
+        point: &PricePoint,
+    ) -> impl Future<Output = Result<(), RecordPriceError>> + Send;
+    /// Finds the price history of an item, oldest observation first.
+    ///
+    /// # Errors
+    /// - [FindPriceHistoryError::Unkown] for any errors that may occur during the search.
+    fn find_price_history(
+        &self,
+        req: &FindPriceHistoryRequest,
+    ) -> impl Future<Output = Result<Vec<PricePoint>, FindPriceHistoryError>> + Send;
+    /// Saves a new price watch.
+    ///
+    /// # Errors
+    /// - [WatchItemPriceError::Unkown] for any errors that may occur while saving.
+    fn save_price_watch(
+        &self,
+        req: &WatchItemPriceRequest,
+    ) -> impl Future<Output = Result<PriceWatch, WatchItemPriceError>> + Send;
+    /// Finds every price watch registered on an item.
+    ///
+    /// # Errors
+    /// - [FindPriceWatchesError::Unkown] for any errors that may occur during the search.
+    fn find_price_watches(
+        &self,
+        item_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<PriceWatch>, FindPriceWatchesError>> + Send;
+    /// Deletes the price watches of a user.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
+}
+
+/// The [PricePoint] struct is a price observed for an item at a given time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PricePoint {
+    price: ItemPrice,
+    recorded_at: DateTime<Utc>,
+}
+
+impl PricePoint {
+    pub fn new(price: ItemPrice, recorded_at: DateTime<Utc>) -> Self {
+        Self { price, recorded_at }
+    }
+
+    pub fn price(&self) -> &ItemPrice {
+        &self.price
+    }
+
+    pub fn recorded_at(&self) -> DateTime<Utc> {
+        self.recorded_at
+    }
+}
+
+/// The [PriceWatch] struct is a user's subscription to price drops of an item.
+///
+/// Without a `drop_percent` nor a `target_price`, any drop is reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceWatch {
+    id: Uuid,
+    item_id: Uuid,
+    user_id: Uuid,
+    drop_percent: Option<Decimal>,
+    target_price: Option<ItemPrice>,
+}
+
+impl PriceWatch {
+    pub fn new(
+        id: Uuid,
+        item_id: Uuid,
+        user_id: Uuid,
+        drop_percent: Option<Decimal>,
+        target_price: Option<ItemPrice>,
+    ) -> Self {
+        Self {
+            id,
+            item_id,
+            user_id,
+            drop_percent,
+            target_price,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn item_id(&self) -> Uuid {
+        self.item_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn drop_percent(&self) -> Option<Decimal> {
+        self.drop_percent
+    }
+
+    pub fn target_price(&self) -> Option<&ItemPrice> {
+        self.target_price.as_ref()
+    }
+
+    /// Returns true if going from `previous` to `current` should be reported to the watcher.
+    ///
+    /// A drop is reported when it is at least `drop_percent` of the previous price, or when it
+    /// crosses `target_price`.
+    pub fn is_triggered_by(&self, previous: &ItemPrice, current: &ItemPrice) -> bool {
+        let (previous, current) = (previous.value(), current.value());
+        if current >= previous {
+            return false;
+        }
+        let crosses_target = self
+            .target_price
+            .as_ref()
+            .is_some_and(|target| current <= target.value() && previous > target.value());
+        let drops_enough = match self.drop_percent {
+            Some(percent) => {
+                !previous.is_zero()
+                    && (previous - current) * Decimal::ONE_HUNDRED / previous >= percent
+            }
+            None => self.target_price.is_none(),
+        };
+        crosses_target || drops_enough
+    }
+}
+
+/// The [FindPriceHistoryRequest] struct represents a request to find an item's price history.
+#[derive(Debug, Clone)]
+pub struct FindPriceHistoryRequest {
+    item_id: Uuid,
+    viewer_id: Option<Uuid>,
+    share_token: Option<ShareToken>,
+}
+
+impl FindPriceHistoryRequest {
+    pub fn new(item_id: Uuid) -> Self {
+        Self {
+            item_id,
+            viewer_id: None,
+            share_token: None,
+        }
+    }
+
+    /// Finds the history as `viewer_id` sees it, which opens the items of the wishlists they own
+    /// or were shared with them.
+    pub fn with_viewer(self, viewer_id: Option<Uuid>) -> Self {
+        Self { viewer_id, ..self }
+    }
+
+    /// Opens the items of a private wishlist, when the token is the one of its share link.
+    pub fn with_share_token(self, share_token: Option<ShareToken>) -> Self {
+        Self {
+            share_token,
+            ..self
+        }
+    }
+
+    pub fn item_id(&self) -> Uuid {
+        self.item_id
+    }
+
+    pub fn viewer_id(&self) -> Option<Uuid> {
+        self.viewer_id
+    }
+
+    pub fn share_token(&self) -> Option<&ShareToken> {
+        self.share_token.as_ref()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FindPriceHistoryError {
+    #[error("Item with id {id} does not exist")]
+    ItemDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [WatchItemPriceRequest] struct represents a request to be notified of an item's price
+/// drops.
+#[derive(Debug, Clone)]
+pub struct WatchItemPriceRequest {
+    item_id: Uuid,
+    user_id: Uuid,
+    drop_percent: Option<Decimal>,
+    target_price: Option<ItemPrice>,
+    share_token: Option<ShareToken>,
+}
+
+impl WatchItemPriceRequest {
+    pub fn new(
+        item_id: Uuid,
+        user_id: Uuid,
+        drop_percent: Option<Decimal>,
+        target_price: Option<ItemPrice>,
+    ) -> Self {
+        Self {
+            item_id,
+            user_id,
+            drop_percent,
+            target_price,
+            share_token: None,
+        }
+    }
+
+    /// Lets the user watch the items of a private wishlist, when the token is the one of its
+    /// share link.
+    pub fn with_share_token(self, share_token: Option<ShareToken>) -> Self {
+        Self {
+            share_token,
+            ..self
+        }
+    }
+
+    pub fn item_id(&self) -> Uuid {
+        self.item_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn drop_percent(&self) -> Option<Decimal> {
+        self.drop_percent
+    }
+
+    pub fn target_price(&self) -> Option<&ItemPrice> {
+        self.target_price.as_ref()
+    }
+
+    pub fn share_token(&self) -> Option<&ShareToken> {
+        self.share_token.as_ref()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WatchItemPriceError {
+    #[error("Item with id {id} does not exist")]
+    ItemDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RecordPriceError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FindPriceWatchesError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RefreshPricesError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    use super::PriceWatch;
+
+    fn watch(drop_percent: Option<Decimal>, target_price: Option<f32>) -> PriceWatch {
+        PriceWatch::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            drop_percent,
+            target_price.map(Into::into),
+        )
+    }
+
+    #[test]
+    fn any_drop_triggers_watch_without_thresholds() {
+        let watch = watch(None, None);
+        assert!(watch.is_triggered_by(&100.0.into(), &99.0.into()));
+        assert!(!watch.is_triggered_by(&100.0.into(), &100.0.into()));
+        assert!(!watch.is_triggered_by(&100.0.into(), &120.0.into()));
+    }
+
+    #[test]
+    fn drop_percent_triggers_watch() {
+        let watch = watch(Some(Decimal::TEN), None);
+        assert!(!watch.is_triggered_by(&100.0.into(), &95.0.into()));
+        assert!(watch.is_triggered_by(&100.0.into(), &90.0.into()));
+    }
+
+    #[test]
+    fn crossing_target_triggers_watch() {
+        let watch = watch(None, Some(50.0));
+        assert!(!watch.is_triggered_by(&100.0.into(), &60.0.into()));
+        assert!(watch.is_triggered_by(&60.0.into(), &50.0.into()));
+        assert!(!watch.is_triggered_by(&50.0.into(), &45.0.into()));
+    }
+}
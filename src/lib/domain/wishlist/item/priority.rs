@@ -0,0 +1,70 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use thiserror::Error;
+
+/// How much the owner of a wishlist wants an item, from the least to the most wanted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ItemPriority {
+    NiceToHave,
+    #[default]
+    Normal,
+    MustHave,
+}
+
+impl ItemPriority {
+    pub const ALL: [ItemPriority; 3] = [
+        ItemPriority::NiceToHave,
+        ItemPriority::Normal,
+        ItemPriority::MustHave,
+    ];
+}
+
+impl Display for ItemPriority {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ItemPriority::NiceToHave => "nice_to_have",
+            ItemPriority::Normal => "normal",
+            ItemPriority::MustHave => "must_have",
+        })
+    }
+}
+
+impl FromStr for ItemPriority {
+    type Err = ItemPriorityInvalidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ItemPriority::ALL
+            .into_iter()
+            .find(|priority| priority.to_string() == value)
+            .ok_or_else(|| ItemPriorityInvalidError {
+                invalid_priority: value.to_string(),
+            })
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Priority is invalid")]
+pub struct ItemPriorityInvalidError {
+    pub invalid_priority: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ItemPriority;
+
+    #[test]
+    fn priority_round_trip_and_order() {
+        for priority in ItemPriority::ALL {
+            assert_eq!(
+                priority.to_string().parse::<ItemPriority>().unwrap(),
+                priority
+            );
+        }
+        assert!("urgent".parse::<ItemPriority>().is_err());
+        assert!(ItemPriority::MustHave > ItemPriority::Normal);
+        assert!(ItemPriority::Normal > ItemPriority::NiceToHave);
+    }
+}
@@ -1,9 +1,16 @@
 use std::future::Future;
 
+use uuid::Uuid;
+
+use crate::domain::{ForgetUserError, Page, Wishlist};
+
 #[cfg(test)]
 use mockall::automock;
 
-use super::{CreateItemError, CreateItemRequest, FindItemByIdError, FindItemByIdRequest, Item};
+use super::{
+    CreateItemError, CreateItemRequest, DeleteItemError, FindItemByIdError, FindItemByIdRequest,
+    FindItemsError, Item, ListItemsRequest, ReserveItemError, TransferItemError, UpdateItemError,
+};
 
 /// The [ItemRepoisitory] trait defines the contract for item-related data operations.
 #[cfg_attr(test, automock)]
@@ -19,6 +26,7 @@ pub trait ItemRepository {
     ///
     /// # Errors
     /// - [CreateItemError::Duplicate] if a item with the same url already exists.
+    /// - [CreateItemError::MissingTitle] if the request has no title.
     /// - [CreateItemError::Unkown] for any other errors that may occur during item creation.
     fn save(
         &self,
@@ -39,4 +47,93 @@ pub trait ItemRepository {
         &self,
         req: &FindItemByIdRequest,
     ) -> impl Future<Output = Result<Option<Item>, FindItemByIdError>> + Send;
+    /// Finds every item.
+    ///
+    /// # Errors
+    /// - [FindItemsError::Unkown] for any errors that may occur during the search.
+    fn find_items(&self) -> impl Future<Output = Result<Vec<Item>, FindItemsError>> + Send;
+    /// Finds a page of the items of `wishlist` matching the request filter, in the requested
+    /// order.
+    ///
+    /// Implementations must follow [ItemFilter::matches](super::ItemFilter::matches) and
+    /// [ItemSort::position](super::ItemSort::position).
+    ///
+    /// # Errors
+    /// - [FindItemsError::Unkown] for any errors that may occur during the search.
+    fn list_items(
+        &self,
+        req: &ListItemsRequest,
+        wishlist: &Wishlist,
+    ) -> impl Future<Output = Result<Page<Item>, FindItemsError>> + Send;
+    /// Finds the items reserved by a user.
+    ///
+    /// # Errors
+    /// - [FindItemsError::Unkown] for any errors that may occur during the search.
+    fn find_items_reserved_by(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Item>, FindItemsError>> + Send;
+    /// Finds the items of any of the wishlists, in no particular order.
+    ///
+    /// # Errors
+    /// - [FindItemsError::Unkown] for any errors that may occur during the search.
+    fn find_items_by_wishlists(
+        &self,
+        wishlist_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<Item>, FindItemsError>> + Send;
+    /// Replaces a stored item with the given one, unless it changed since the given one was read.
+    /// The reservation of the item is left as stored: only [ItemRepository::reserve_item]
+    /// changes it.
+    ///
+    /// # Arguments
+    /// * `item` - A reference to the updated `Item`, at the version it was read at.
+    ///
+    /// # Returns
+    /// - `Ok(item)` if the item is updated successfully, at its next version.
+    ///
+    /// # Errors
+    /// - [UpdateItemError::ItemDoesNotExist] if the item does not exist.
+    /// - [UpdateItemError::VersionMismatch] if the stored item is at another version.
+    /// - [UpdateItemError::Unkown] for any other errors that may occur during the update.
+    fn update(&self, item: &Item) -> impl Future<Output = Result<Item, UpdateItemError>> + Send;
+    /// Moves an item to another wishlist, unless that wishlist already has an item with the same
+    /// link.
+    ///
+    /// # Errors
+    /// - [TransferItemError::ItemDoesNotExist] if the item does not exist.
+    /// - [TransferItemError::Duplicate] if an item with the same url exists in the wishlist.
+    /// - [TransferItemError::Unkown] for any other errors that may occur during the move.
+    fn move_item(
+        &self,
+        item_id: Uuid,
+        wishlist_id: Uuid,
+    ) -> impl Future<Output = Result<Item, TransferItemError>> + Send;
+    /// Reserves an item for a user, or cancels their reservation, unless someone else holds it.
+    /// Reservations are kept from the owner of the item, so they leave its version unchanged.
+    ///
+    /// # Errors
+    /// - [ReserveItemError::ItemDoesNotExist] if the item does not exist.
+    /// - [ReserveItemError::AlreadyReserved] if someone else reserved the item.
+    /// - [ReserveItemError::NotReserver] if the user cancels a reservation that is not theirs.
+    /// - [ReserveItemError::Unkown] for any other errors that may occur.
+    fn reserve_item(
+        &self,
+        item_id: Uuid,
+        user_id: Uuid,
+        reserved: bool,
+    ) -> impl Future<Output = Result<Item, ReserveItemError>> + Send;
+    /// Deletes an item.
+    ///
+    /// # Errors
+    /// - [DeleteItemError::ItemDoesNotExist] if the item does not exist.
+    /// - [DeleteItemError::Unkown] for any other errors that may occur during the deletion.
+    fn delete(&self, item_id: Uuid) -> impl Future<Output = Result<(), DeleteItemError>> + Send;
+    /// Cancels the reservations a user holds on items.
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
 }
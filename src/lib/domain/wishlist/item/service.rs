@@ -5,14 +5,23 @@ use mockall::automock;
 use thiserror::Error;
 use uuid::Uuid;
 
-use super::{Item, ItemImageUrl, ItemLinkUrl, ItemPrice, ItemTitle};
+use super::{
+    FindPriceHistoryError, FindPriceHistoryRequest, Item, ItemAttributes, ItemEventStream,
+    ItemImageUrl, ItemLinkUrl, ItemListing, ItemMetadata, ItemNotes, ItemPrice, ItemPriority,
+    ItemTitle, ListItemsError, ListItemsRequest, PricePoint, PriceWatch, RefreshPricesError,
+    ViewSharedWishlistError, ViewSharedWishlistRequest, ViewWishlistsError, ViewWishlistsRequest,
+    WatchItemPriceError, WatchItemPriceRequest, WatchItemsError, WatchItemsRequest, WishlistView,
+};
+use crate::domain::{ShareToken, Version, VersionMismatchError};
 
 /// The [ItemService] trait defines the contract for item-related operations.
 #[cfg_attr(test, automock)]
 #[allow(refining_impl_trait)]
-pub trait ItemService {
+pub trait ItemService: Send + Sync + 'static {
     /// Creates a new item with the provided request.
     ///
+    /// Details missing from the request are pre-filled from the page behind its link.
+    ///
     /// # Arguments
     /// * `req` - A reference to a `CreateItemRequest` containing the item's details.
     ///
@@ -22,51 +31,274 @@ pub trait ItemService {
     ///
     /// # Errors
     /// - [CreateItemError::Duplicate] if an item with the same URL already exists.
+    /// - [CreateItemError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [CreateItemError::WishlistArchived] if the wishlist was archived after its occasion.
+    /// - [CreateItemError::MissingTitle] if no title was given nor found on the page.
+    /// - [CreateItemError::ImageDoesNotExist] if the uploaded image does not exist.
     /// - [CreateItemError::Unkown] for any other errors that may occur during item creation.
     fn create_item(
         &self,
         req: &CreateItemRequest,
     ) -> impl Future<Output = Result<Item, CreateItemError>> + Send;
+    /// Lists the items of a wishlist matching the request filter, in the requested order, which
+    /// defaults to the owner's order.
+    ///
+    /// # Errors
+    /// - [ListItemsError::WishlistDoesNotExist] if the wishlist does not exist or the viewer may
+    ///   not see it.
+    /// - [ListItemsError::Unkown] for any other errors that may occur during the search.
+    fn list_items(
+        &self,
+        req: &ListItemsRequest,
+    ) -> impl Future<Output = Result<ItemListing, ListItemsError>> + Send;
+    /// Finds several wishlists at once with all their items, as the caller sees them. Wishlists
+    /// that do not exist or that the caller may not see are left out.
+    ///
+    /// # Errors
+    /// - [ViewWishlistsError::Unkown] for any errors that may occur during the search.
+    fn view_wishlists(
+        &self,
+        req: &ViewWishlistsRequest,
+    ) -> impl Future<Output = Result<Vec<WishlistView>, ViewWishlistsError>> + Send;
+    /// Finds a wishlist by its slug with all its items, for someone following a link to it.
+    ///
+    /// # Errors
+    /// - [ViewSharedWishlistError::WishlistDoesNotExist] if no wishlist has the slug, or if it is
+    ///   private and the request does not hold the token of its share link.
+    /// - [ViewSharedWishlistError::Unkown] for any other errors that may occur during the search.
+    fn view_shared_wishlist(
+        &self,
+        req: &ViewSharedWishlistRequest,
+    ) -> impl Future<Output = Result<WishlistView, ViewSharedWishlistError>> + Send;
+    /// Re-fetches the price of every item, records the changes in their price history and
+    /// notifies the watchers of the items whose price dropped.
+    ///
+    /// Items whose page cannot be fetched are skipped until the next refresh.
+    ///
+    /// # Errors
+    /// - [RefreshPricesError::Unkown] if the items or their history cannot be read or written.
+    fn refresh_prices(&self) -> impl Future<Output = Result<(), RefreshPricesError>> + Send;
+    /// Finds the price history of an item of a wishlist the viewer may see, oldest observation
+    /// first.
+    ///
+    /// # Errors
+    /// - [FindPriceHistoryError::ItemDoesNotExist] if the item does not exist or the viewer may
+    ///   not see its wishlist.
+    /// - [FindPriceHistoryError::Unkown] for any other errors that may occur during the search.
+    fn find_price_history(
+        &self,
+        req: &FindPriceHistoryRequest,
+    ) -> impl Future<Output = Result<Vec<PricePoint>, FindPriceHistoryError>> + Send;
+    /// Subscribes a user to the price drops of an item of a wishlist they may see.
+    ///
+    /// # Errors
+    /// - [WatchItemPriceError::ItemDoesNotExist] if the item does not exist or the user may not
+    ///   see its wishlist.
+    /// - [WatchItemPriceError::Unkown] for any other errors that may occur.
+    fn watch_item_price(
+        &self,
+        req: &WatchItemPriceRequest,
+    ) -> impl Future<Output = Result<PriceWatch, WatchItemPriceError>> + Send;
+    /// Moves an item to another wishlist of the same owner. The item keeps its ID, so everything
+    /// attached to it, such as its price history and watches, follows it.
+    ///
+    /// # Errors
+    /// - [TransferItemError::ItemDoesNotExist] if the item does not exist.
+    /// - [TransferItemError::WishlistDoesNotExist] if the target wishlist does not exist.
+    /// - [TransferItemError::NotWishlistOwner] if the caller does not own both wishlists.
+    /// - [TransferItemError::WishlistArchived] if the target wishlist is archived.
+    /// - [TransferItemError::Duplicate] if the target already has an item with the same link.
+    /// - [TransferItemError::Unkown] for any other errors that may occur.
+    fn move_item(
+        &self,
+        req: &TransferItemRequest,
+    ) -> impl Future<Output = Result<Item, TransferItemError>> + Send;
+    /// Copies an item, with its details and image, into another wishlist of the same owner. The
+    /// copy is a new item: nothing attached to the original follows it.
+    ///
+    /// # Errors
+    /// Same as [ItemService::move_item].
+    fn copy_item(
+        &self,
+        req: &TransferItemRequest,
+    ) -> impl Future<Output = Result<Item, TransferItemError>> + Send;
+    /// Marks an item as received by the owner of its wishlist, or not.
+    ///
+    /// # Errors
+    /// - [MarkItemReceivedError::ItemDoesNotExist] if the item does not exist.
+    /// - [MarkItemReceivedError::NotWishlistOwner] if the caller does not own its wishlist.
+    /// - [MarkItemReceivedError::Unkown] for any other errors that may occur.
+    fn mark_item_received(
+        &self,
+        req: &MarkItemReceivedRequest,
+    ) -> impl Future<Output = Result<Item, MarkItemReceivedError>> + Send;
+    /// Reserves an item of a wishlist the caller may see, so no one else offers it, or cancels
+    /// the caller's reservation. Reserving an item again keeps the reservation. Holding the token
+    /// of the share link of a wishlist lets anyone reserve its items.
+    ///
+    /// # Errors
+    /// - [ReserveItemError::ItemDoesNotExist] if the item does not exist or the caller may not
+    ///   see its wishlist.
+    /// - [ReserveItemError::OwnItem] if the caller owns its wishlist.
+    /// - [ReserveItemError::WishlistArchived] if its wishlist is archived.
+    /// - [ReserveItemError::AlreadyReserved] if someone else reserved the item.
+    /// - [ReserveItemError::NotReserver] if the caller cancels a reservation that is not theirs.
+    /// - [ReserveItemError::Unkown] for any other errors that may occur.
+    fn reserve_item(
+        &self,
+        req: &ReserveItemRequest,
+    ) -> impl Future<Output = Result<Item, ReserveItemError>> + Send;
+    /// Follows the changes of the items of a wishlist the caller may see, live. Reservations are
+    /// kept from the owner of the wishlist, see
+    /// [ItemEvent::seen_by](super::ItemEvent::seen_by).
+    ///
+    /// # Errors
+    /// - [WatchItemsError::WishlistDoesNotExist] if the wishlist does not exist or the caller may
+    ///   not see it.
+    /// - [WatchItemsError::Unkown] for any other errors that may occur.
+    fn watch_items(
+        &self,
+        req: &WatchItemsRequest,
+    ) -> impl Future<Output = Result<ItemEventStream, WatchItemsError>> + Send;
 }
 
 /// The [CreateItemRequest] struct represents a request to create a new [Item].
 #[derive(Debug, Clone)]
 pub struct CreateItemRequest {
-    title: ItemTitle,
+    wishlist_id: Uuid,
+    user_id: Uuid,
+    title: Option<ItemTitle>,
     link_url: ItemLinkUrl,
-    image_url: ItemImageUrl,
-    price: ItemPrice,
+    image_url: Option<ItemImageUrl>,
+    price: Option<ItemPrice>,
+    image_id: Option<Uuid>,
+    priority: ItemPriority,
+    notes: Option<ItemNotes>,
+    attributes: ItemAttributes,
 }
 
 impl CreateItemRequest {
     pub fn new(
-        title: ItemTitle,
+        wishlist_id: Uuid,
+        user_id: Uuid,
+        title: Option<ItemTitle>,
         link_url: ItemLinkUrl,
-        image_url: ItemImageUrl,
-        price: ItemPrice,
+        image_url: Option<ItemImageUrl>,
+        price: Option<ItemPrice>,
     ) -> Self {
         Self {
+            wishlist_id,
+            user_id,
             title,
             link_url,
             image_url,
             price,
+            image_id: None,
+            priority: ItemPriority::default(),
+            notes: None,
+            attributes: ItemAttributes::default(),
         }
     }
 
-    pub fn title(&self) -> &ItemTitle {
-        &self.title
+    /// Attaches an image stored on our side, either uploaded by the user or mirrored from
+    /// `image_url`.
+    pub fn with_image_id(self, image_id: Option<Uuid>) -> Self {
+        Self { image_id, ..self }
+    }
+
+    pub fn with_priority(self, priority: ItemPriority) -> Self {
+        Self { priority, ..self }
+    }
+
+    pub fn with_notes(self, notes: Option<ItemNotes>) -> Self {
+        Self { notes, ..self }
+    }
+
+    pub fn with_attributes(self, attributes: ItemAttributes) -> Self {
+        Self { attributes, ..self }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn title(&self) -> Option<&ItemTitle> {
+        self.title.as_ref()
     }
 
     pub fn link_url(&self) -> &ItemLinkUrl {
         &self.link_url
     }
 
-    pub fn image_url(&self) -> &ItemImageUrl {
-        &self.image_url
+    pub fn image_url(&self) -> Option<&ItemImageUrl> {
+        self.image_url.as_ref()
+    }
+
+    pub fn price(&self) -> Option<&ItemPrice> {
+        self.price.as_ref()
+    }
+
+    pub fn image_id(&self) -> Option<Uuid> {
+        self.image_id
+    }
+
+    pub fn priority(&self) -> ItemPriority {
+        self.priority
+    }
+
+    pub fn notes(&self) -> Option<&ItemNotes> {
+        self.notes.as_ref()
+    }
+
+    pub fn attributes(&self) -> &ItemAttributes {
+        &self.attributes
+    }
+
+    /// Returns a request creating a copy of `item` in the wishlist `wishlist_id`.
+    pub fn copy_of(item: &Item, wishlist_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+            title: Some(item.title().clone()),
+            link_url: item.link_url().clone(),
+            image_url: item.image_url().cloned(),
+            price: item.price().cloned(),
+            image_id: item.image_id(),
+            priority: item.priority(),
+            notes: item.notes().cloned(),
+            attributes: item.attributes().clone(),
+        }
+    }
+
+    /// Returns true if any of the title, image or price is missing.
+    pub fn is_incomplete(&self) -> bool {
+        self.title.is_none() || self.image_url.is_none() || self.price.is_none()
     }
 
-    pub fn price(&self) -> &ItemPrice {
-        &self.price
+    /// Returns a copy of the request where missing details are taken from `metadata`.
+    pub fn prefilled_with(&self, metadata: ItemMetadata) -> Self {
+        let metadata = ItemMetadata::new(
+            self.title.clone(),
+            self.image_url.clone(),
+            self.price.clone(),
+        )
+        .or(metadata);
+        Self {
+            wishlist_id: self.wishlist_id,
+            user_id: self.user_id,
+            title: metadata.title().cloned(),
+            link_url: self.link_url.clone(),
+            image_url: metadata.image_url().cloned(),
+            price: metadata.price().cloned(),
+            image_id: self.image_id,
+            priority: self.priority,
+            notes: self.notes.clone(),
+            attributes: self.attributes.clone(),
+        }
     }
 }
 
@@ -74,6 +306,184 @@ impl CreateItemRequest {
 pub enum CreateItemError {
     #[error("Item with already exist")]
     Duplicate,
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not belong to the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error("Wishlist with id {id} is archived")]
+    WishlistArchived { id: Uuid },
+    #[error("Item has no title")]
+    MissingTitle,
+    #[error("Image with id {id} does not exist")]
+    ImageDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [TransferItemRequest] struct represents a request by a user to move or copy an [Item]
+/// into another wishlist.
+#[derive(Debug, Clone)]
+pub struct TransferItemRequest {
+    item_id: Uuid,
+    wishlist_id: Uuid,
+    user_id: Uuid,
+}
+
+impl TransferItemRequest {
+    pub fn new(item_id: Uuid, wishlist_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            item_id,
+            wishlist_id,
+            user_id,
+        }
+    }
+
+    pub fn item_id(&self) -> Uuid {
+        self.item_id
+    }
+
+    /// The ID of the target wishlist.
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    /// The ID of the user asking for the transfer, who must own both wishlists.
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TransferItemError {
+    #[error("Item with id {id} does not exist")]
+    ItemDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} is not owned by the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error("Wishlist with id {id} is archived")]
+    WishlistArchived { id: Uuid },
+    #[error("Item with the same link already exists in the wishlist")]
+    Duplicate,
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [MarkItemReceivedRequest] struct represents a request by the owner of an [Item] to mark
+/// it as received, or not.
+#[derive(Debug, Clone)]
+pub struct MarkItemReceivedRequest {
+    item_id: Uuid,
+    user_id: Uuid,
+    received: bool,
+    expected_version: Option<Version>,
+}
+
+impl MarkItemReceivedRequest {
+    pub fn new(item_id: Uuid, user_id: Uuid, received: bool) -> Self {
+        Self {
+            item_id,
+            user_id,
+            received,
+            expected_version: None,
+        }
+    }
+
+    pub fn item_id(&self) -> Uuid {
+        self.item_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn received(&self) -> bool {
+        self.received
+    }
+
+    /// Only applies the change if the item is still at this version.
+    pub fn with_expected_version(self, expected_version: Option<Version>) -> Self {
+        Self {
+            expected_version,
+            ..self
+        }
+    }
+
+    pub fn expected_version(&self) -> Option<Version> {
+        self.expected_version
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum MarkItemReceivedError {
+    #[error("Item with id {id} does not exist")]
+    ItemDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} is not owned by the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error(transparent)]
+    VersionMismatch(#[from] VersionMismatchError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [ReserveItemRequest] struct represents a request by a user to reserve an [Item], or to
+/// cancel their reservation.
+#[derive(Debug, Clone)]
+pub struct ReserveItemRequest {
+    item_id: Uuid,
+    user_id: Uuid,
+    reserved: bool,
+    share_token: Option<ShareToken>,
+}
+
+impl ReserveItemRequest {
+    pub fn new(item_id: Uuid, user_id: Uuid, reserved: bool) -> Self {
+        Self {
+            item_id,
+            user_id,
+            reserved,
+            share_token: None,
+        }
+    }
+
+    /// Lets the user reserve items of a private wishlist, when the token is the one of its share
+    /// link.
+    pub fn with_share_token(self, share_token: Option<ShareToken>) -> Self {
+        Self {
+            share_token,
+            ..self
+        }
+    }
+
+    pub fn share_token(&self) -> Option<&ShareToken> {
+        self.share_token.as_ref()
+    }
+
+    pub fn item_id(&self) -> Uuid {
+        self.item_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn reserved(&self) -> bool {
+        self.reserved
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ReserveItemError {
+    #[error("Item with id {id} does not exist")]
+    ItemDoesNotExist { id: Uuid },
+    #[error("Owners cannot reserve their own items")]
+    OwnItem,
+    #[error("Wishlist with id {id} is archived")]
+    WishlistArchived { id: Uuid },
+    #[error("Item with id {id} is already reserved")]
+    AlreadyReserved { id: Uuid },
+    #[error("Item with id {id} is not reserved by the user")]
+    NotReserver { id: Uuid },
     #[error(transparent)]
     Unkown(#[from] anyhow::Error),
 }
@@ -99,3 +509,27 @@ pub enum FindItemByIdError {
     #[error(transparent)]
     Unkown(#[from] anyhow::Error),
 }
+
+#[derive(Debug, Error)]
+pub enum FindItemsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum UpdateItemError {
+    #[error("Item with id {id} does not exist")]
+    ItemDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    VersionMismatch(#[from] VersionMismatchError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum DeleteItemError {
+    #[error("Item with id {id} does not exist")]
+    ItemDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
@@ -1,8 +1,36 @@
+use std::fmt::{Display, Formatter};
+
+use thiserror::Error;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ItemTitle(String);
 
+impl ItemTitle {
+    pub fn new(title: &str) -> Result<Self, ItemTitleInvalidError> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(ItemTitleInvalidError {
+                invalid_title: ItemTitle(title.to_string()),
+            });
+        }
+        Ok(ItemTitle(title.to_string()))
+    }
+}
+
 impl From<&str> for ItemTitle {
     fn from(value: &str) -> Self {
         ItemTitle(value.to_string())
     }
 }
+
+impl Display for ItemTitle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Title is invalid")]
+pub struct ItemTitleInvalidError {
+    pub invalid_title: ItemTitle,
+}
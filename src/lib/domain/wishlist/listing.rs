@@ -0,0 +1,312 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{OccasionKind, Wishlist};
+use crate::domain::{Cursor, Group, PageRequest, SortDirection, SortValue};
+
+/// The [WishlistQuery] struct selects a page of wishlists, filtered and sorted.
+#[derive(Debug, Clone, Default)]
+pub struct WishlistQuery {
+    filter: WishlistFilter,
+    sort: WishlistSort,
+    page: PageRequest,
+}
+
+impl WishlistQuery {
+    pub fn new(filter: WishlistFilter, sort: WishlistSort, page: PageRequest) -> Self {
+        Self { filter, sort, page }
+    }
+
+    pub fn filter(&self) -> &WishlistFilter {
+        &self.filter
+    }
+
+    pub fn sort(&self) -> &WishlistSort {
+        &self.sort
+    }
+
+    pub fn page(&self) -> &PageRequest {
+        &self.page
+    }
+
+    /// Narrows the filter of the query, e.g. to the wishlists a use case may list.
+    pub fn narrow(self, narrow: impl FnOnce(WishlistFilter) -> WishlistFilter) -> Self {
+        Self {
+            filter: narrow(self.filter),
+            ..self
+        }
+    }
+}
+
+/// Whether a wishlist is listed to everyone or only to its owner and the groups it is shared
+/// with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WishlistVisibility {
+    Public,
+    Private,
+}
+
+impl Display for WishlistVisibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WishlistVisibility::Public => "public",
+            WishlistVisibility::Private => "private",
+        })
+    }
+}
+
+impl FromStr for WishlistVisibility {
+    type Err = WishlistVisibilityInvalidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "public" => Ok(WishlistVisibility::Public),
+            "private" => Ok(WishlistVisibility::Private),
+            _ => Err(WishlistVisibilityInvalidError(value.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Visibility {0} is invalid")]
+pub struct WishlistVisibilityInvalidError(pub String);
+
+/// Criteria a wishlist must meet to be listed. Unset criteria match every wishlist.
+#[derive(Debug, Clone, Default)]
+pub struct WishlistFilter {
+    owner_ids: Option<Vec<Uuid>>,
+    template: Option<bool>,
+    archived: Option<bool>,
+    visibility: Option<WishlistVisibility>,
+    occasion: Option<OccasionKind>,
+    viewer: Option<(Uuid, Vec<Group>)>,
+}
+
+impl WishlistFilter {
+    /// Only lists the wishlists of these owners.
+    pub fn with_owners(self, owner_ids: Vec<Uuid>) -> Self {
+        Self {
+            owner_ids: Some(owner_ids),
+            ..self
+        }
+    }
+
+    /// Only lists templates, or only wishlists that are not.
+    pub fn with_template(self, template: Option<bool>) -> Self {
+        Self { template, ..self }
+    }
+
+    /// Only lists archived wishlists, or only wishlists that are not.
+    pub fn with_archived(self, archived: Option<bool>) -> Self {
+        Self { archived, ..self }
+    }
+
+    pub fn with_visibility(self, visibility: Option<WishlistVisibility>) -> Self {
+        Self { visibility, ..self }
+    }
+
+    /// Only lists wishlists for this kind of occasion.
+    pub fn with_occasion(self, occasion: Option<OccasionKind>) -> Self {
+        Self { occasion, ..self }
+    }
+
+    /// Only lists the wishlists `user_id` may see, given the groups they are in.
+    pub fn visible_to(self, user_id: Uuid, groups: Vec<Group>) -> Self {
+        Self {
+            viewer: Some((user_id, groups)),
+            ..self
+        }
+    }
+
+    pub fn owner_ids(&self) -> Option<&[Uuid]> {
+        self.owner_ids.as_deref()
+    }
+
+    pub fn matches(&self, wishlist: &Wishlist) -> bool {
+        self.owner_ids
+            .as_ref()
+            .is_none_or(|owner_ids| owner_ids.contains(&wishlist.owner_id()))
+            && self
+                .template
+                .is_none_or(|template| wishlist.template() == template)
+            && self
+                .archived
+                .is_none_or(|archived| wishlist.archived() == archived)
+            && self.visibility.is_none_or(|visibility| {
+                wishlist.private() == (visibility == WishlistVisibility::Private)
+            })
+            && self.occasion.is_none_or(|kind| {
+                wishlist
+                    .occasion()
+                    .is_some_and(|occasion| occasion.kind() == kind)
+            })
+            && self
+                .viewer
+                .as_ref()
+                .is_none_or(|(user_id, groups)| wishlist.is_visible_to(*user_id, groups))
+    }
+}
+
+/// The keys wishlists can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WishlistSortKey {
+    /// The last time the wishlist or its items changed.
+    Updated,
+    /// The order wishlists were created in.
+    Created,
+    Name,
+}
+
+impl WishlistSortKey {
+    /// The direction the key sorts in when none is asked for: the most recently updated first,
+    /// ascending otherwise.
+    pub fn default_direction(&self) -> SortDirection {
+        match self {
+            WishlistSortKey::Updated => SortDirection::Descending,
+            WishlistSortKey::Created | WishlistSortKey::Name => SortDirection::Ascending,
+        }
+    }
+}
+
+impl Display for WishlistSortKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WishlistSortKey::Updated => "updated",
+            WishlistSortKey::Created => "created",
+            WishlistSortKey::Name => "name",
+        })
+    }
+}
+
+impl FromStr for WishlistSortKey {
+    type Err = WishlistSortInvalidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "updated" => Ok(WishlistSortKey::Updated),
+            "created" => Ok(WishlistSortKey::Created),
+            "name" => Ok(WishlistSortKey::Name),
+            _ => Err(WishlistSortInvalidError {
+                invalid_sort: value.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("Sort is invalid")]
+pub struct WishlistSortInvalidError {
+    pub invalid_sort: String,
+}
+
+/// How listed wishlists are ordered. Wishlists that are equal on the key keep the order they
+/// were created in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WishlistSort {
+    key: WishlistSortKey,
+    direction: SortDirection,
+}
+
+impl WishlistSort {
+    pub fn new(key: WishlistSortKey, direction: SortDirection) -> Self {
+        Self { key, direction }
+    }
+
+    pub fn key(&self) -> WishlistSortKey {
+        self.key
+    }
+
+    pub fn direction(&self) -> SortDirection {
+        self.direction
+    }
+
+    /// The position of a wishlist in the listing.
+    pub fn position(&self, wishlist: &Wishlist) -> Cursor {
+        let value = match self.key {
+            WishlistSortKey::Updated => SortValue::Time(wishlist.updated_at()),
+            WishlistSortKey::Created => SortValue::Id(wishlist.id()),
+            WishlistSortKey::Name => SortValue::Text(wishlist.name().to_string().to_lowercase()),
+        };
+        Cursor::new(Some(value), wishlist.id())
+    }
+}
+
+impl Default for WishlistSort {
+    fn default() -> Self {
+        let key = WishlistSortKey::Updated;
+        Self::new(key, key.default_direction())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+
+    use super::*;
+    use crate::domain::{Occasion, PageLimit};
+
+    fn wishlist(name: &str, private: bool, updated_minutes: i64) -> Wishlist {
+        let mut wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            Uuid::nil(),
+            name.into(),
+            name.into(),
+            private,
+        );
+        wishlist.touch(
+            "2027-12-01T09:00:00Z".parse::<DateTime<Utc>>().unwrap()
+                + Duration::minutes(updated_minutes),
+        );
+        wishlist
+    }
+
+    #[test]
+    fn filter_wishlists() {
+        let public = wishlist("Birthday", false, 0);
+        let mut private = wishlist("Wedding", true, 0);
+        private.set_occasion(Some(Occasion::new(
+            OccasionKind::Wedding,
+            "2027-06-12".parse().unwrap(),
+            chrono_tz::Europe::Paris,
+            None,
+        )));
+
+        let filter = WishlistFilter::default().with_visibility(Some(WishlistVisibility::Public));
+        assert!(filter.matches(&public) && !filter.matches(&private));
+        let filter = WishlistFilter::default().with_occasion(Some(OccasionKind::Wedding));
+        assert!(!filter.matches(&public) && filter.matches(&private));
+        let filter = WishlistFilter::default().visible_to(Uuid::now_v7(), Vec::new());
+        assert!(filter.matches(&public) && !filter.matches(&private));
+        let filter = WishlistFilter::default().with_owners(vec![Uuid::now_v7()]);
+        assert!(!filter.matches(&public));
+    }
+
+    #[test]
+    fn sort_wishlists() {
+        let wishlists = vec![
+            wishlist("b", false, 2),
+            wishlist("C", false, 0),
+            wishlist("a", false, 1),
+        ];
+        let names = |sort: WishlistSort| -> Vec<String> {
+            PageRequest::new(None, PageLimit::default())
+                .paginate(wishlists.clone(), sort.direction(), |wishlist| {
+                    sort.position(wishlist)
+                })
+                .entries()
+                .iter()
+                .map(|wishlist| wishlist.name().to_string())
+                .collect()
+        };
+
+        assert_eq!(names(WishlistSort::default()), ["b", "a", "C"]);
+        let sort = WishlistSort::new(WishlistSortKey::Name, SortDirection::Ascending);
+        assert_eq!(names(sort), ["a", "b", "C"]);
+        let sort = WishlistSort::new(WishlistSortKey::Created, SortDirection::Descending);
+        assert_eq!(names(sort), ["a", "C", "b"]);
+        assert!("colour".parse::<WishlistSortKey>().is_err());
+    }
+}
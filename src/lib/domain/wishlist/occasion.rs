@@ -0,0 +1,252 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use thiserror::Error;
+
+/// The event a wishlist is for, e.g. a birthday on 2027-03-14 in Europe/Paris, every year.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occasion {
+    kind: OccasionKind,
+    date: NaiveDate,
+    time_zone: Tz,
+    recurrence: Option<Recurrence>,
+    /// The date the recurrence was set from, which [Occasion::next] follows rather than the
+    /// current date, e.g. so a yearly event on February 29 stays on that day in leap years.
+    anchor: NaiveDate,
+}
+
+impl Occasion {
+    pub fn new(
+        kind: OccasionKind,
+        date: NaiveDate,
+        time_zone: Tz,
+        recurrence: Option<Recurrence>,
+    ) -> Self {
+        Self {
+            kind,
+            date,
+            time_zone,
+            recurrence,
+            anchor: date,
+        }
+    }
+
+    pub fn kind(&self) -> OccasionKind {
+        self.kind
+    }
+
+    /// The day of the event, in its time zone.
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn time_zone(&self) -> Tz {
+        self.time_zone
+    }
+
+    pub fn recurrence(&self) -> Option<Recurrence> {
+        self.recurrence
+    }
+
+    /// The instant the event is over: midnight at the end of its day, in its time zone.
+    pub fn ends_at(&self) -> DateTime<Utc> {
+        let end = self
+            .date
+            .succ_opt()
+            .unwrap_or(self.date)
+            .and_time(Default::default());
+        self.time_zone
+            .from_local_datetime(&end)
+            .earliest()
+            .unwrap_or_else(|| self.time_zone.from_utc_datetime(&end))
+            .with_timezone(&Utc)
+    }
+
+    /// Returns true if the event is over at `now`.
+    pub fn is_over(&self, now: DateTime<Utc>) -> bool {
+        self.ends_at() <= now
+    }
+
+    /// The number of days from `now` to the event, both taken in the time zone of the event:
+    /// 0 on the day itself, negative once it is past.
+    pub fn days_until(&self, now: DateTime<Utc>) -> i64 {
+        let today = now.with_timezone(&self.time_zone).date_naive();
+        (self.date - today).num_days()
+    }
+
+    /// The next occurrence of a recurring event, or `None` if it does not recur. A yearly event
+    /// on February 29 falls on February 28 in the years that are not leap years.
+    pub fn next(&self) -> Option<Occasion> {
+        let date = match self.recurrence? {
+            Recurrence::Yearly => {
+                let year = self.date.year() + 1;
+                self.anchor
+                    .with_year(year)
+                    .or_else(|| NaiveDate::from_ymd_opt(year, self.anchor.month(), 28))?
+            }
+        };
+        Some(Occasion {
+            date,
+            ..self.clone()
+        })
+    }
+
+    /// The first occurrence of the event that is not over at `now`, or `None` if it does not
+    /// recur.
+    pub fn next_after(&self, now: DateTime<Utc>) -> Option<Occasion> {
+        let mut occasion = self.next()?;
+        while occasion.is_over(now) {
+            occasion = occasion.next()?;
+        }
+        Some(occasion)
+    }
+}
+
+/// The kinds of events a wishlist can be for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OccasionKind {
+    Birthday,
+    Wedding,
+    Christmas,
+    Holiday,
+    BabyShower,
+    Anniversary,
+    Graduation,
+    Other,
+}
+
+impl OccasionKind {
+    pub const ALL: [OccasionKind; 8] = [
+        OccasionKind::Birthday,
+        OccasionKind::Wedding,
+        OccasionKind::Christmas,
+        OccasionKind::Holiday,
+        OccasionKind::BabyShower,
+        OccasionKind::Anniversary,
+        OccasionKind::Graduation,
+        OccasionKind::Other,
+    ];
+}
+
+impl Display for OccasionKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            OccasionKind::Birthday => "birthday",
+            OccasionKind::Wedding => "wedding",
+            OccasionKind::Christmas => "christmas",
+            OccasionKind::Holiday => "holiday",
+            OccasionKind::BabyShower => "baby_shower",
+            OccasionKind::Anniversary => "anniversary",
+            OccasionKind::Graduation => "graduation",
+            OccasionKind::Other => "other",
+        })
+    }
+}
+
+impl FromStr for OccasionKind {
+    type Err = OccasionInvalidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.to_string() == value)
+            .ok_or(OccasionInvalidError::Kind(value.to_string()))
+    }
+}
+
+/// How often an event comes back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recurrence {
+    Yearly,
+}
+
+impl Display for Recurrence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Recurrence::Yearly => f.write_str("yearly"),
+        }
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = OccasionInvalidError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "yearly" => Ok(Recurrence::Yearly),
+            _ => Err(OccasionInvalidError::Recurrence(value.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum OccasionInvalidError {
+    #[error("Occasion kind {0} is invalid")]
+    Kind(String),
+    #[error("Date {0} is invalid")]
+    Date(String),
+    #[error("Time zone {0} is invalid")]
+    TimeZone(String),
+    #[error("Recurrence {0} is invalid")]
+    Recurrence(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occasion(date: &str, time_zone: Tz, recurrence: Option<Recurrence>) -> Occasion {
+        Occasion::new(
+            OccasionKind::Birthday,
+            date.parse().unwrap(),
+            time_zone,
+            recurrence,
+        )
+    }
+
+    fn utc(instant: &str) -> DateTime<Utc> {
+        instant.parse().unwrap()
+    }
+
+    #[test]
+    fn end_in_time_zone() {
+        let birthday = occasion("2027-03-14", chrono_tz::Asia::Tokyo, None);
+        assert_eq!(birthday.ends_at(), utc("2027-03-14T15:00:00Z"));
+        assert!(!birthday.is_over(utc("2027-03-14T14:59:59Z")));
+        assert!(birthday.is_over(utc("2027-03-14T15:00:00Z")));
+    }
+
+    #[test]
+    fn count_days_in_time_zone() {
+        let birthday = occasion("2027-03-14", chrono_tz::America::New_York, None);
+        assert_eq!(birthday.days_until(utc("2027-03-01T12:00:00Z")), 13);
+        // Still the 13th in New York.
+        assert_eq!(birthday.days_until(utc("2027-03-14T03:00:00Z")), 1);
+        assert_eq!(birthday.days_until(utc("2027-03-14T12:00:00Z")), 0);
+        assert_eq!(birthday.days_until(utc("2027-03-16T12:00:00Z")), -2);
+    }
+
+    #[test]
+    fn recur_yearly() {
+        let once = occasion("2028-02-29", Tz::UTC, None);
+        assert_eq!(once.next(), None);
+
+        let leap = occasion("2028-02-29", Tz::UTC, Some(Recurrence::Yearly));
+        assert_eq!(leap.next().unwrap().date(), "2029-02-28".parse().unwrap());
+        let missed = leap.next_after(utc("2031-06-01T00:00:00Z")).unwrap();
+        assert_eq!(missed.date(), "2032-02-29".parse().unwrap());
+    }
+
+    #[test]
+    fn parse_kinds() {
+        for kind in OccasionKind::ALL {
+            assert_eq!(kind.to_string().parse::<OccasionKind>().unwrap(), kind);
+        }
+        assert!("party".parse::<OccasionKind>().is_err());
+        assert!("weekly".parse::<Recurrence>().is_err());
+    }
+}
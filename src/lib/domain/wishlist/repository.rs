@@ -1,11 +1,26 @@
 use std::future::Future;
 
-use crate::domain::wishlist::{CreateWishlistError, CreateWishlistRequest, Wishlist};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{ForgetUserError, Page, Version};
+
+use crate::domain::wishlist::{
+    AddWishlistItemError, ArchiveWishlistError, CreateSectionError, CreateSectionRequest,
+    CreateWishlistError, CreateWishlistRequest, DeleteSectionError, DeleteSectionRequest,
+    FindWishlistByIdError, FindWishlistByIdRequest, FindWishlistsError, MoveItemToSectionError,
+    MoveItemToSectionRequest, Occasion, RemoveWishlistItemError, ReorderWishlistError,
+    ReorderWishlistRequest, SetWishlistOccasionError, SetWishlistTemplateError, ShareLinkError,
+    ShareToken, Wishlist, WishlistQuery, WishlistSection,
+};
 
 #[cfg(test)]
 use mockall::automock;
 
 /// The [WishlistRepository] trait defines the contract for wishlist-related data operations.
+///
+/// Every change to a wishlist moves it to its [next version](Wishlist::next_version). Changes
+/// that expect a version are applied atomically with the check that the wishlist is still at it.
 #[cfg_attr(test, automock)]
 #[allow(refining_impl_trait)]
 pub trait WishlistRepository {
@@ -24,4 +39,201 @@ pub trait WishlistRepository {
         &self,
         req: &CreateWishlistRequest,
     ) -> impl Future<Output = Result<Wishlist, CreateWishlistError>> + Send;
+    /// Finds a wishlist by its ID.
+    ///
+    /// # Arguments
+    /// * `req` - A reference to a `FindWishlistByIdRequest` containing the wishlist's ID.
+    ///
+    /// # Returns
+    /// - `Ok(Some(wishlist))` if a wishlist with the given ID exists.
+    /// - `Ok(None)` if no wishlist with the given ID exists.
+    ///
+    /// # Errors
+    /// - [FindWishlistByIdError::Unkown] for any other errors that may occur during the search.
+    fn find_wishlist_by_id(
+        &self,
+        req: &FindWishlistByIdRequest,
+    ) -> impl Future<Output = Result<Option<Wishlist>, FindWishlistByIdError>> + Send;
+    /// Finds the wishlist with the given slug.
+    ///
+    /// # Returns
+    /// - `Ok(Some(wishlist))` if a wishlist has the slug.
+    /// - `Ok(None)` if none has.
+    ///
+    /// # Errors
+    /// - [FindWishlistByIdError::Unkown] for any errors that may occur during the search.
+    fn find_wishlist_by_slug(
+        &self,
+        slug: &str,
+    ) -> impl Future<Output = Result<Option<Wishlist>, FindWishlistByIdError>> + Send;
+    /// Finds the wishlists with any of the IDs, in no particular order. IDs without a wishlist
+    /// are skipped.
+    ///
+    /// # Errors
+    /// - [FindWishlistsError::Unkown] for any errors that may occur during the search.
+    fn find_wishlists_by_ids(
+        &self,
+        ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<Wishlist>, FindWishlistsError>> + Send;
+    /// Adds an item to a wishlist.
+    ///
+    /// # Arguments
+    /// * `wishlist_id` - The ID of the wishlist.
+    /// * `item_id` - The ID of the item to add.
+    ///
+    /// # Errors
+    /// - [AddWishlistItemError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [AddWishlistItemError::Unkown] for any other errors that may occur.
+    fn add_item(
+        &self,
+        wishlist_id: Uuid,
+        item_id: Uuid,
+    ) -> impl Future<Output = Result<(), AddWishlistItemError>> + Send;
+    /// Removes an item from a wishlist. Removing an item that is not in the wishlist does
+    /// nothing.
+    ///
+    /// # Arguments
+    /// * `wishlist_id` - The ID of the wishlist.
+    /// * `item_id` - The ID of the item to remove.
+    ///
+    /// # Errors
+    /// - [RemoveWishlistItemError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [RemoveWishlistItemError::Unkown] for any other errors that may occur.
+    fn remove_item(
+        &self,
+        wishlist_id: Uuid,
+        item_id: Uuid,
+    ) -> impl Future<Output = Result<(), RemoveWishlistItemError>> + Send;
+    /// Adds a new section to a wishlist, after its existing sections.
+    ///
+    /// # Errors
+    /// - [CreateSectionError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [CreateSectionError::DuplicateName] if the wishlist already has a section with that
+    ///   name.
+    /// - [CreateSectionError::Unkown] for any other errors that may occur.
+    fn create_section(
+        &self,
+        req: &CreateSectionRequest,
+    ) -> impl Future<Output = Result<WishlistSection, CreateSectionError>> + Send;
+    /// Removes a section from a wishlist and moves its items out of it, atomically.
+    ///
+    /// # Errors
+    /// - [DeleteSectionError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [DeleteSectionError::SectionDoesNotExist] if the section is not in the wishlist.
+    /// - [DeleteSectionError::VersionMismatch] if the wishlist is not at the expected version.
+    /// - [DeleteSectionError::Unkown] for any other errors that may occur.
+    fn delete_section(
+        &self,
+        req: &DeleteSectionRequest,
+    ) -> impl Future<Output = Result<Wishlist, DeleteSectionError>> + Send;
+    /// Moves an item of a wishlist into one of its sections, or out of any section.
+    ///
+    /// # Errors
+    /// - [MoveItemToSectionError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [MoveItemToSectionError::ItemNotInWishlist] if the item is not in the wishlist.
+    /// - [MoveItemToSectionError::SectionDoesNotExist] if the section is not in the wishlist.
+    /// - [MoveItemToSectionError::VersionMismatch] if the wishlist is not at the expected
+    ///   version.
+    /// - [MoveItemToSectionError::Unkown] for any other errors that may occur.
+    fn move_item_to_section(
+        &self,
+        req: &MoveItemToSectionRequest,
+    ) -> impl Future<Output = Result<Wishlist, MoveItemToSectionError>> + Send;
+    /// Applies [Wishlist::reorder] atomically: concurrent changes to the wishlist are never lost
+    /// and an invalid ordering leaves it unchanged.
+    ///
+    /// # Errors
+    /// - [ReorderWishlistError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [ReorderWishlistError::ItemNotInWishlist], [ReorderWishlistError::SectionDoesNotExist]
+    ///   or [ReorderWishlistError::Duplicate] if the ordering is invalid.
+    /// - [ReorderWishlistError::VersionMismatch] if the wishlist is not at the expected version.
+    /// - [ReorderWishlistError::Unkown] for any other errors that may occur.
+    fn reorder_wishlist(
+        &self,
+        req: &ReorderWishlistRequest,
+    ) -> impl Future<Output = Result<Wishlist, ReorderWishlistError>> + Send;
+    /// Marks a wishlist as a template, or unmarks it, if it is at `expected_version` when one is
+    /// given.
+    ///
+    /// # Errors
+    /// - [SetWishlistTemplateError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [SetWishlistTemplateError::VersionMismatch] if the wishlist is not at the expected
+    ///   version.
+    /// - [SetWishlistTemplateError::Unkown] for any other errors that may occur.
+    fn set_template(
+        &self,
+        wishlist_id: Uuid,
+        template: bool,
+        expected_version: Option<Version>,
+    ) -> impl Future<Output = Result<Wishlist, SetWishlistTemplateError>> + Send;
+    /// Sets the share token of a wishlist, or clears it.
+    ///
+    /// # Errors
+    /// - [ShareLinkError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [ShareLinkError::Unkown] for any other errors that may occur.
+    fn set_share_token(
+        &self,
+        wishlist_id: Uuid,
+        share_token: Option<ShareToken>,
+    ) -> impl Future<Output = Result<Wishlist, ShareLinkError>> + Send;
+    /// Finds a page of the wishlists matching the query filter, in the query order.
+    ///
+    /// Implementations must follow [WishlistFilter::matches](super::WishlistFilter::matches) and
+    /// [WishlistSort::position](super::WishlistSort::position).
+    ///
+    /// # Errors
+    /// - [FindWishlistsError::Unkown] for any errors that may occur during the search.
+    fn list_wishlists(
+        &self,
+        query: &WishlistQuery,
+    ) -> impl Future<Output = Result<Page<Wishlist>, FindWishlistsError>> + Send;
+    /// Sets the occasion of a wishlist, or clears it, if it is at `expected_version` when one is
+    /// given.
+    ///
+    /// # Errors
+    /// - [SetWishlistOccasionError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [SetWishlistOccasionError::VersionMismatch] if the wishlist is not at the expected
+    ///   version.
+    /// - [SetWishlistOccasionError::Unkown] for any other errors that may occur.
+    fn set_occasion(
+        &self,
+        wishlist_id: Uuid,
+        occasion: Option<Occasion>,
+        expected_version: Option<Version>,
+    ) -> impl Future<Output = Result<Wishlist, SetWishlistOccasionError>> + Send;
+    /// Archives a wishlist, or brings it back.
+    ///
+    /// # Errors
+    /// - [ArchiveWishlistError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [ArchiveWishlistError::Unkown] for any other errors that may occur.
+    fn set_archived(
+        &self,
+        wishlist_id: Uuid,
+        archived: bool,
+    ) -> impl Future<Output = Result<Wishlist, ArchiveWishlistError>> + Send;
+    /// Finds the wishlists that are not archived and whose occasion is over at `now`.
+    ///
+    /// # Errors
+    /// - [FindWishlistsError::Unkown] for any errors that may occur during the search.
+    fn find_past_occasions(
+        &self,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<Wishlist>, FindWishlistsError>> + Send;
+    /// Finds the wishlists of any of the owners, most recently updated first.
+    ///
+    /// # Errors
+    /// - [FindWishlistsError::Unkown] for any errors that may occur during the search.
+    fn find_wishlists_by_owners(
+        &self,
+        owner_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<Wishlist>, FindWishlistsError>> + Send;
+    /// Deletes the wishlists owned by a user. Their items are left to the
+    /// [ItemRepository](crate::domain::ItemRepository).
+    ///
+    /// # Errors
+    /// - [ForgetUserError::Unkown] for any errors that may occur.
+    fn forget_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), ForgetUserError>> + Send;
 }
@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+
+use thiserror::Error;
+use uuid::Uuid;
+
+/// A named group of items inside a wishlist, e.g. "Books" or "Kitchen".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WishlistSection {
+    id: Uuid,
+    name: SectionName,
+}
+
+impl WishlistSection {
+    pub fn new(id: Uuid, name: SectionName) -> Self {
+        Self { id, name }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &SectionName {
+        &self.name
+    }
+}
+
+/// The name of a [WishlistSection]. Names are unique within a wishlist, ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionName(String);
+
+impl SectionName {
+    pub const MAX_LENGTH: usize = 100;
+
+    /// Trims the name and rejects it if it is empty or longer than [Self::MAX_LENGTH]
+    /// characters.
+    pub fn new(name: &str) -> Result<Self, SectionNameInvalidError> {
+        let name = name.trim();
+        let length = name.chars().count();
+        if length == 0 || length > Self::MAX_LENGTH {
+            return Err(SectionNameInvalidError { length });
+        }
+        Ok(SectionName(name.to_string()))
+    }
+
+    /// Returns true if both names are the same, ignoring case.
+    pub fn same_as(&self, other: &SectionName) -> bool {
+        self.0.to_lowercase() == other.0.to_lowercase()
+    }
+}
+
+impl From<&str> for SectionName {
+    fn from(value: &str) -> Self {
+        SectionName(value.to_string())
+    }
+}
+
+impl Display for SectionName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error(
+    "Section name must be between 1 and {} characters long",
+    SectionName::MAX_LENGTH
+)]
+pub struct SectionNameInvalidError {
+    pub length: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SectionName;
+
+    #[test]
+    fn create_section_name() {
+        assert_eq!(SectionName::new(" Books ").unwrap().to_string(), "Books");
+        assert_eq!(SectionName::new("  ").unwrap_err().length, 0);
+        assert!(SectionName::new(&"a".repeat(SectionName::MAX_LENGTH + 1)).is_err());
+        assert!(SectionName::from("Books").same_as(&"bOOKS".into()));
+    }
+}
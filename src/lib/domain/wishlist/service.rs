@@ -1,12 +1,14 @@
 use std::future::Future;
 
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 use uuid::Uuid;
 
 #[cfg(test)]
 use mockall::automock;
 
-use super::{Wishlist, WishlistName};
+use super::{Occasion, SectionName, Wishlist, WishlistName, WishlistQuery, WishlistSection};
+use crate::domain::{Page, Version, VersionMismatchError};
 
 /// The [WishlistService] trait defines the contract for wishlist-related operations.
 #[cfg_attr(test, automock)]
@@ -23,11 +25,136 @@ pub trait WishlistService: Send + Sync + 'static {
     /// # Errors
     /// - [CreateWishlistError::OwnerIdDoesNotExist] if the owner ID does not exist.
     /// - [CreateWishlistError::Unknown] for any other errors that may occur during wishlist
-    /// creation.
+    ///   creation.
     fn create_wishlist(
         &self,
         req: &CreateWishlistRequest,
     ) -> impl Future<Output = Result<Wishlist, CreateWishlistError>> + Send;
+    /// Adds a named section to a wishlist, after its existing sections.
+    ///
+    /// # Errors
+    /// - [CreateSectionError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [CreateSectionError::NotWishlistOwner] if the user does not own the wishlist.
+    /// - [CreateSectionError::DuplicateName] if the wishlist already has a section with that
+    ///   name.
+    /// - [CreateSectionError::Unkown] for any other errors that may occur.
+    fn create_section(
+        &self,
+        req: &CreateSectionRequest,
+    ) -> impl Future<Output = Result<WishlistSection, CreateSectionError>> + Send;
+    /// Removes a section from a wishlist. Its items stay in the wishlist, outside any section.
+    ///
+    /// # Errors
+    /// - [DeleteSectionError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [DeleteSectionError::NotWishlistOwner] if the user does not own the wishlist.
+    /// - [DeleteSectionError::SectionDoesNotExist] if the section is not in the wishlist.
+    /// - [DeleteSectionError::Unkown] for any other errors that may occur.
+    fn delete_section(
+        &self,
+        req: &DeleteSectionRequest,
+    ) -> impl Future<Output = Result<Wishlist, DeleteSectionError>> + Send;
+    /// Moves an item into a section of its wishlist, or out of any section. The item keeps its
+    /// position.
+    ///
+    /// # Errors
+    /// - [MoveItemToSectionError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [MoveItemToSectionError::NotWishlistOwner] if the user does not own the wishlist.
+    /// - [MoveItemToSectionError::ItemNotInWishlist] if the item is not in the wishlist.
+    /// - [MoveItemToSectionError::SectionDoesNotExist] if the section is not in the wishlist.
+    /// - [MoveItemToSectionError::Unkown] for any other errors that may occur.
+    fn move_item_to_section(
+        &self,
+        req: &MoveItemToSectionRequest,
+    ) -> impl Future<Output = Result<Wishlist, MoveItemToSectionError>> + Send;
+    /// Reorders the items and sections of a wishlist at once: either both orderings apply or
+    /// neither does. See [Wishlist::reorder] for how partial orderings apply.
+    ///
+    /// # Errors
+    /// - [ReorderWishlistError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [ReorderWishlistError::NotWishlistOwner] if the user does not own the wishlist.
+    /// - [ReorderWishlistError::ItemNotInWishlist] if an item is not in the wishlist.
+    /// - [ReorderWishlistError::SectionDoesNotExist] if a section is not in the wishlist.
+    /// - [ReorderWishlistError::Duplicate] if an ID is listed twice.
+    /// - [ReorderWishlistError::Unkown] for any other errors that may occur.
+    fn reorder_wishlist(
+        &self,
+        req: &ReorderWishlistRequest,
+    ) -> impl Future<Output = Result<Wishlist, ReorderWishlistError>> + Send;
+    /// Creates a new wishlist for the user, with a fresh slug and copies of the sections and
+    /// items of an existing one, in the same order. Users may duplicate their own wishlists and
+    /// templates.
+    ///
+    /// # Errors
+    /// - [DuplicateWishlistError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [DuplicateWishlistError::UserDoesNotExist] if the user does not exist.
+    /// - [DuplicateWishlistError::NotAllowed] if the wishlist is neither the user's nor a
+    ///   template.
+    /// - [DuplicateWishlistError::Unkown] for any other errors that may occur; the copy may then
+    ///   be incomplete.
+    fn duplicate_wishlist(
+        &self,
+        req: &DuplicateWishlistRequest,
+    ) -> impl Future<Output = Result<Wishlist, DuplicateWishlistError>> + Send;
+    /// Marks a wishlist as a template others can duplicate, or unmarks it.
+    ///
+    /// # Errors
+    /// - [SetWishlistTemplateError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [SetWishlistTemplateError::NotWishlistOwner] if the user does not own the wishlist.
+    /// - [SetWishlistTemplateError::Unkown] for any other errors that may occur.
+    fn set_wishlist_template(
+        &self,
+        req: &SetWishlistTemplateRequest,
+    ) -> impl Future<Output = Result<Wishlist, SetWishlistTemplateError>> + Send;
+    /// Creates a share link for a wishlist, replacing the previous one: anyone with the link may
+    /// see the wishlist and reserve its items, even when it is private.
+    ///
+    /// # Errors
+    /// - [ShareLinkError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [ShareLinkError::NotWishlistOwner] if the user does not own the wishlist.
+    /// - [ShareLinkError::Unkown] for any other errors that may occur.
+    fn create_share_link(
+        &self,
+        req: &ShareLinkRequest,
+    ) -> impl Future<Output = Result<Wishlist, ShareLinkError>> + Send;
+    /// Revokes the share link of a wishlist, if it has one.
+    ///
+    /// # Errors
+    /// - [ShareLinkError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [ShareLinkError::NotWishlistOwner] if the user does not own the wishlist.
+    /// - [ShareLinkError::Unkown] for any other errors that may occur.
+    fn revoke_share_link(
+        &self,
+        req: &ShareLinkRequest,
+    ) -> impl Future<Output = Result<Wishlist, ShareLinkError>> + Send;
+    /// Lists a page of the wishlists marked as templates that match the query.
+    ///
+    /// # Errors
+    /// - [FindWishlistsError::Unkown] for any errors that may occur during the search.
+    fn list_templates(
+        &self,
+        query: &WishlistQuery,
+    ) -> impl Future<Output = Result<Page<Wishlist>, FindWishlistsError>> + Send;
+    /// Sets the occasion a wishlist is for, or clears it.
+    ///
+    /// # Errors
+    /// - [SetWishlistOccasionError::WishlistDoesNotExist] if the wishlist does not exist.
+    /// - [SetWishlistOccasionError::NotWishlistOwner] if the user does not own the wishlist.
+    /// - [SetWishlistOccasionError::Unkown] for any other errors that may occur.
+    fn set_wishlist_occasion(
+        &self,
+        req: &SetWishlistOccasionRequest,
+    ) -> impl Future<Output = Result<Wishlist, SetWishlistOccasionError>> + Send;
+    /// Closes the wishlists whose occasion is over at `now`. A wishlist for a one-off occasion
+    /// is archived; a wishlist for a recurring one reopens for its next occurrence, without the
+    /// items the owner received.
+    ///
+    /// # Errors
+    /// - [CloseOccasionsError::Unkown] if the wishlists or their items cannot be read or
+    ///   written; the wishlists not closed yet are closed on the next run.
+    fn close_past_occasions(
+        &self,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), CloseOccasionsError>> + Send;
 }
 
 /// The [CreateWishlistRequest] struct represents a request to create a new [Wishlist].
@@ -73,3 +200,513 @@ pub enum CreateWishlistError {
 pub struct OwnerIdInvalidError {
     pub invalid_owner_id: Uuid,
 }
+
+/// The [FindWishlistByIdRequest] struct represents a request to find a wishlist by its ID.
+#[derive(Debug, Clone)]
+pub struct FindWishlistByIdRequest {
+    id: Uuid,
+}
+
+impl FindWishlistByIdRequest {
+    pub fn new(id: Uuid) -> Self {
+        Self { id }
+    }
+
+    pub fn id(&self) -> &Uuid {
+        &self.id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FindWishlistByIdError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum FindWishlistsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum AddWishlistItemError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RemoveWishlistItemError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [CreateSectionRequest] struct represents a request to add a [WishlistSection] to a
+/// [Wishlist].
+#[derive(Debug, Clone)]
+pub struct CreateSectionRequest {
+    wishlist_id: Uuid,
+    user_id: Uuid,
+    name: SectionName,
+}
+
+impl CreateSectionRequest {
+    pub fn new(wishlist_id: Uuid, user_id: Uuid, name: SectionName) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+            name,
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn name(&self) -> &SectionName {
+        &self.name
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CreateSectionError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not belong to the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error("Section {name} already exists")]
+    DuplicateName { name: SectionName },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [DeleteSectionRequest] struct represents a request to remove a [WishlistSection] from a
+/// [Wishlist].
+#[derive(Debug, Clone)]
+pub struct DeleteSectionRequest {
+    wishlist_id: Uuid,
+    user_id: Uuid,
+    section_id: Uuid,
+    expected_version: Option<Version>,
+}
+
+impl DeleteSectionRequest {
+    pub fn new(wishlist_id: Uuid, user_id: Uuid, section_id: Uuid) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+            section_id,
+            expected_version: None,
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn section_id(&self) -> Uuid {
+        self.section_id
+    }
+
+    /// Only applies the change if the wishlist is still at this version.
+    pub fn with_expected_version(self, expected_version: Option<Version>) -> Self {
+        Self {
+            expected_version,
+            ..self
+        }
+    }
+
+    pub fn expected_version(&self) -> Option<Version> {
+        self.expected_version
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DeleteSectionError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not belong to the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error("Section with id {id} does not exist")]
+    SectionDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    VersionMismatch(#[from] VersionMismatchError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [MoveItemToSectionRequest] struct represents a request to move an item into a
+/// [WishlistSection], or out of any section when `section_id` is `None`.
+#[derive(Debug, Clone)]
+pub struct MoveItemToSectionRequest {
+    wishlist_id: Uuid,
+    user_id: Uuid,
+    item_id: Uuid,
+    section_id: Option<Uuid>,
+    expected_version: Option<Version>,
+}
+
+impl MoveItemToSectionRequest {
+    pub fn new(wishlist_id: Uuid, user_id: Uuid, item_id: Uuid, section_id: Option<Uuid>) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+            item_id,
+            section_id,
+            expected_version: None,
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn item_id(&self) -> Uuid {
+        self.item_id
+    }
+
+    pub fn section_id(&self) -> Option<Uuid> {
+        self.section_id
+    }
+
+    /// Only applies the change if the wishlist is still at this version.
+    pub fn with_expected_version(self, expected_version: Option<Version>) -> Self {
+        Self {
+            expected_version,
+            ..self
+        }
+    }
+
+    pub fn expected_version(&self) -> Option<Version> {
+        self.expected_version
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum MoveItemToSectionError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not belong to the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error("Item with id {id} is not in the wishlist")]
+    ItemNotInWishlist { id: Uuid },
+    #[error("Section with id {id} does not exist")]
+    SectionDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    VersionMismatch(#[from] VersionMismatchError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [ReorderWishlistRequest] struct represents a request to reorder the items and sections
+/// of a [Wishlist]. Either ordering may be partial or empty.
+#[derive(Debug, Clone)]
+pub struct ReorderWishlistRequest {
+    wishlist_id: Uuid,
+    user_id: Uuid,
+    item_ids: Vec<Uuid>,
+    section_ids: Vec<Uuid>,
+    expected_version: Option<Version>,
+}
+
+impl ReorderWishlistRequest {
+    pub fn new(
+        wishlist_id: Uuid,
+        user_id: Uuid,
+        item_ids: Vec<Uuid>,
+        section_ids: Vec<Uuid>,
+    ) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+            item_ids,
+            section_ids,
+            expected_version: None,
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn item_ids(&self) -> &[Uuid] {
+        &self.item_ids
+    }
+
+    pub fn section_ids(&self) -> &[Uuid] {
+        &self.section_ids
+    }
+
+    /// Only applies the change if the wishlist is still at this version.
+    pub fn with_expected_version(self, expected_version: Option<Version>) -> Self {
+        Self {
+            expected_version,
+            ..self
+        }
+    }
+
+    pub fn expected_version(&self) -> Option<Version> {
+        self.expected_version
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ReorderWishlistError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not belong to the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error("Item with id {id} is not in the wishlist")]
+    ItemNotInWishlist { id: Uuid },
+    #[error("Section with id {id} does not exist")]
+    SectionDoesNotExist { id: Uuid },
+    #[error("{id} is listed more than once")]
+    Duplicate { id: Uuid },
+    #[error(transparent)]
+    VersionMismatch(#[from] VersionMismatchError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [DuplicateWishlistRequest] struct represents a request by a user to start a new
+/// [Wishlist] from a copy of an existing one.
+#[derive(Debug, Clone)]
+pub struct DuplicateWishlistRequest {
+    wishlist_id: Uuid,
+    user_id: Uuid,
+    name: Option<WishlistName>,
+    private: Option<bool>,
+}
+
+impl DuplicateWishlistRequest {
+    pub fn new(wishlist_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+            name: None,
+            private: None,
+        }
+    }
+
+    /// Names the copy, instead of reusing the name of the original.
+    pub fn with_name(self, name: Option<WishlistName>) -> Self {
+        Self { name, ..self }
+    }
+
+    /// Sets the visibility of the copy, instead of reusing the one of the original.
+    pub fn with_private(self, private: Option<bool>) -> Self {
+        Self { private, ..self }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    /// The ID of the user asking for the copy, who will own it.
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn name(&self) -> Option<&WishlistName> {
+        self.name.as_ref()
+    }
+
+    pub fn private(&self) -> Option<bool> {
+        self.private
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DuplicateWishlistError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("User with id {id} does not exist")]
+    UserDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} is neither owned by the user nor a template")]
+    NotAllowed { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [ShareLinkRequest] struct represents a request by a user to create or revoke the share
+/// link of their [Wishlist].
+#[derive(Debug, Clone)]
+pub struct ShareLinkRequest {
+    wishlist_id: Uuid,
+    user_id: Uuid,
+}
+
+impl ShareLinkRequest {
+    pub fn new(wishlist_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ShareLinkError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} does not belong to the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [SetWishlistTemplateRequest] struct represents a request by a user to mark their
+/// [Wishlist] as a template, or to unmark it.
+#[derive(Debug, Clone)]
+pub struct SetWishlistTemplateRequest {
+    wishlist_id: Uuid,
+    user_id: Uuid,
+    template: bool,
+    expected_version: Option<Version>,
+}
+
+impl SetWishlistTemplateRequest {
+    pub fn new(wishlist_id: Uuid, user_id: Uuid, template: bool) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+            template,
+            expected_version: None,
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn template(&self) -> bool {
+        self.template
+    }
+
+    /// Only applies the change if the wishlist is still at this version.
+    pub fn with_expected_version(self, expected_version: Option<Version>) -> Self {
+        Self {
+            expected_version,
+            ..self
+        }
+    }
+
+    pub fn expected_version(&self) -> Option<Version> {
+        self.expected_version
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SetWishlistTemplateError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} is not owned by the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error(transparent)]
+    VersionMismatch(#[from] VersionMismatchError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+/// The [SetWishlistOccasionRequest] struct represents a request by the owner of a [Wishlist] to
+/// set the occasion it is for, or to clear it.
+#[derive(Debug, Clone)]
+pub struct SetWishlistOccasionRequest {
+    wishlist_id: Uuid,
+    user_id: Uuid,
+    occasion: Option<Occasion>,
+    expected_version: Option<Version>,
+}
+
+impl SetWishlistOccasionRequest {
+    pub fn new(wishlist_id: Uuid, user_id: Uuid, occasion: Option<Occasion>) -> Self {
+        Self {
+            wishlist_id,
+            user_id,
+            occasion,
+            expected_version: None,
+        }
+    }
+
+    pub fn wishlist_id(&self) -> Uuid {
+        self.wishlist_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn occasion(&self) -> Option<&Occasion> {
+        self.occasion.as_ref()
+    }
+
+    /// Only applies the change if the wishlist is still at this version.
+    pub fn with_expected_version(self, expected_version: Option<Version>) -> Self {
+        Self {
+            expected_version,
+            ..self
+        }
+    }
+
+    pub fn expected_version(&self) -> Option<Version> {
+        self.expected_version
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SetWishlistOccasionError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error("Wishlist with id {id} is not owned by the user")]
+    NotWishlistOwner { id: Uuid },
+    #[error(transparent)]
+    VersionMismatch(#[from] VersionMismatchError),
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ArchiveWishlistError {
+    #[error("Wishlist with id {id} does not exist")]
+    WishlistDoesNotExist { id: Uuid },
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum CloseOccasionsError {
+    #[error(transparent)]
+    Unkown(#[from] anyhow::Error),
+}
@@ -0,0 +1,35 @@
+use std::fmt::{Display, Formatter};
+
+use uuid::Uuid;
+
+/// The [ShareToken] struct holds the secret of a share link: whoever has the link may see the
+/// [Wishlist](super::Wishlist), even a private one, and reserve its items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareToken(String);
+
+impl ShareToken {
+    /// Generates a new random token.
+    pub fn generate() -> Self {
+        Self(format!(
+            "{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        ))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for ShareToken {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl Display for ShareToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
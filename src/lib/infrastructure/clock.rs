@@ -0,0 +1,62 @@
+use std::sync::Mutex;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::domain::Clock;
+
+/// The [SystemClock] struct is a [Clock] reading the system time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// The [ManualClock] struct is a [Clock] that only moves when told to, so schedules can be
+/// checked deterministically.
+pub struct ManualClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, delta: TimeDelta) {
+        *self.now.lock().unwrap() += delta;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock() {
+        let clock = ManualClock::new("2027-03-14T08:00:00Z".parse().unwrap());
+        clock.advance(TimeDelta::hours(2));
+        assert_eq!(
+            clock.now(),
+            "2027-03-14T10:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        clock.set("2027-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(
+            clock.now(),
+            "2027-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+}
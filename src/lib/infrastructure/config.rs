@@ -12,12 +12,112 @@ pub struct DatabaseConfig {
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    pub max_body_bytes: usize,
+    pub idempotency_ttl_secs: u64,
+    pub idempotency_max_entries: usize,
+    pub public_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MetadataConfig {
+    pub timeout_ms: u64,
+    pub max_body_bytes: usize,
+    pub max_redirects: usize,
+    pub allow_private_networks: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PriceTrackingConfig {
+    pub interval_secs: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OccasionsConfig {
+    pub interval_secs: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemindersConfig {
+    pub interval_secs: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountsConfig {
+    pub deletion_grace_days: i64,
+    pub confirmation_ttl_mins: i64,
+    pub interval_secs: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhooksConfig {
+    pub interval_secs: u64,
+    pub timeout_ms: u64,
+    pub max_attempts: u32,
+    pub backoff_base_secs: i64,
+    pub backoff_max_secs: i64,
+    pub allow_private_networks: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphqlConfig {
+    pub max_depth: usize,
+    pub max_complexity: usize,
+    pub max_batch: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImagesConfig {
+    pub storage_dir: String,
+    pub max_upload_bytes: usize,
+    pub max_dimension: u32,
+    pub mirror_remote: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct BucketConfig {
+    pub capacity: u32,
+    pub refill_per_minute: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RouteRateLimitConfig {
+    pub method: String,
+    pub path: String,
+    pub per_ip: Option<BucketConfig>,
+    pub per_user: Option<BucketConfig>,
+    #[serde(default)]
+    pub lockout: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LockoutConfig {
+    pub max_failures: u32,
+    pub base_secs: i64,
+    pub max_secs: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RateLimitsConfig {
+    pub per_ip: Option<BucketConfig>,
+    pub per_user: Option<BucketConfig>,
+    #[serde(default)]
+    pub routes: Vec<RouteRateLimitConfig>,
+    pub lockout: LockoutConfig,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub database: DatabaseConfig,
     pub server: ServerConfig,
+    pub metadata: MetadataConfig,
+    pub price_tracking: PriceTrackingConfig,
+    pub occasions: OccasionsConfig,
+    pub reminders: RemindersConfig,
+    pub images: ImagesConfig,
+    pub accounts: AccountsConfig,
+    pub webhooks: WebhooksConfig,
+    pub graphql: GraphqlConfig,
+    pub rate_limits: RateLimitsConfig,
 }
 
 impl Config {
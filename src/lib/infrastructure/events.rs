@@ -0,0 +1,265 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::domain::{
+    Clock, EventId, ItemChange, ItemEvent, ItemEventBus, ItemEventSubscription,
+    PublishItemEventError, SubscribeItemEventsError, WebhookRepository, Wishlist,
+};
+
+/// How many recent events of each wishlist are kept to be replayed to resuming viewers, and
+/// how far behind a live viewer may fall before their subscription ends.
+const DEFAULT_HISTORY: usize = 256;
+
+/// The events of a wishlist: the recent ones, and the live viewers.
+struct Topic {
+    history: VecDeque<ItemEvent>,
+    sender: broadcast::Sender<ItemEvent>,
+}
+
+struct Topics {
+    last_id: EventId,
+    topics: HashMap<Uuid, Topic>,
+}
+
+/// The [InMemoryItemEventBus] struct is an in-memory implementation of the [ItemEventBus]
+/// trait, for the viewers connected to a single server.
+pub struct InMemoryItemEventBus {
+    topics: Mutex<Topics>,
+    history: usize,
+}
+
+impl InMemoryItemEventBus {
+    pub fn new() -> Self {
+        Self::with_history(DEFAULT_HISTORY)
+    }
+
+    /// Keeps the last `history` events of each wishlist, at least one.
+    pub fn with_history(history: usize) -> Self {
+        Self {
+            topics: Mutex::new(Topics {
+                last_id: 0,
+                topics: HashMap::new(),
+            }),
+            history: history.max(1),
+        }
+    }
+}
+
+impl Default for InMemoryItemEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Topics {
+    fn topic(&mut self, wishlist_id: Uuid, history: usize) -> &mut Topic {
+        self.topics.entry(wishlist_id).or_insert_with(|| Topic {
+            history: VecDeque::with_capacity(history),
+            sender: broadcast::channel(history).0,
+        })
+    }
+}
+
+impl ItemEventBus for InMemoryItemEventBus {
+    async fn publish(
+        &self,
+        wishlist: &Wishlist,
+        change: ItemChange,
+    ) -> Result<ItemEvent, PublishItemEventError> {
+        // Events are numbered and sent under the lock, so subscribers never miss nor repeat one
+        let mut topics = self.topics.lock().unwrap();
+        topics.last_id += 1;
+        let event = ItemEvent::new(topics.last_id, wishlist, change);
+        let topic = topics.topic(wishlist.id(), self.history);
+        if topic.history.len() == self.history {
+            topic.history.pop_front();
+        }
+        topic.history.push_back(event.clone());
+        // No one may be watching
+        let _ = topic.sender.send(event.clone());
+        Ok(event)
+    }
+
+    async fn subscribe(
+        &self,
+        wishlist_id: Uuid,
+        after: Option<EventId>,
+    ) -> Result<ItemEventSubscription, SubscribeItemEventsError> {
+        let mut topics = self.topics.lock().unwrap();
+        let topic = topics.topic(wishlist_id, self.history);
+        let missed = match after {
+            Some(after) => topic
+                .history
+                .iter()
+                .filter(|event| event.id() > after)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        Ok(ItemEventSubscription::new(missed, topic.sender.subscribe()))
+    }
+}
+
+/// The [WebhookItemEventBus] struct publishes events on another [ItemEventBus], then queues
+/// their deliveries to the webhooks of the wishlist, see [Webhook::delivery_for].
+///
+/// [Webhook::delivery_for]: crate::domain::Webhook::delivery_for
+pub struct WebhookItemEventBus<B, R, C>
+where
+    B: ItemEventBus,
+    R: WebhookRepository,
+    C: Clock,
+{
+    bus: B,
+    webhook_repository: Arc<R>,
+    clock: Arc<C>,
+}
+
+impl<B, R, C> WebhookItemEventBus<B, R, C>
+where
+    B: ItemEventBus,
+    R: WebhookRepository,
+    C: Clock,
+{
+    pub fn new(bus: B, webhook_repository: Arc<R>, clock: Arc<C>) -> Self {
+        Self {
+            bus,
+            webhook_repository,
+            clock,
+        }
+    }
+
+    async fn enqueue(&self, event: &ItemEvent) -> anyhow::Result<()> {
+        let webhooks = self
+            .webhook_repository
+            .find_webhooks_by_wishlist(event.wishlist_id())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        let now = self.clock.now();
+        for delivery in webhooks
+            .iter()
+            .filter_map(|webhook| webhook.delivery_for(event, now))
+        {
+            self.webhook_repository
+                .save_delivery(&delivery)
+                .await
+                .map_err(|err| anyhow!(err))?;
+        }
+        Ok(())
+    }
+}
+
+impl<B, R, C> ItemEventBus for WebhookItemEventBus<B, R, C>
+where
+    B: ItemEventBus,
+    R: WebhookRepository,
+    C: Clock,
+{
+    async fn publish(
+        &self,
+        wishlist: &Wishlist,
+        change: ItemChange,
+    ) -> Result<ItemEvent, PublishItemEventError> {
+        let event = self.bus.publish(wishlist, change).await?;
+        // Live viewers already got the event, whatever happens to the webhooks
+        if let Err(err) = self.enqueue(&event).await {
+            tracing::warn!(
+                "failed to queue the webhooks of event {}: {}",
+                event.id(),
+                err
+            );
+        }
+        Ok(event)
+    }
+
+    async fn subscribe(
+        &self,
+        wishlist_id: Uuid,
+        after: Option<EventId>,
+    ) -> Result<ItemEventSubscription, SubscribeItemEventsError> {
+        self.bus.subscribe(wishlist_id, after).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Item;
+
+    fn wishlist() -> Wishlist {
+        Wishlist::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        )
+    }
+
+    fn added(wishlist: &Wishlist, title: &str) -> ItemChange {
+        ItemChange::Added(Item::create(
+            Uuid::now_v7(),
+            wishlist.id(),
+            title.into(),
+            "https://shop.example/p/1".into(),
+            None,
+            None,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_live_and_resumed_subscriptions() {
+        let bus = InMemoryItemEventBus::with_history(2);
+        let (birthday, christmas) = (wishlist(), wishlist());
+
+        let first = bus
+            .publish(&birthday, added(&birthday, "Book"))
+            .await
+            .unwrap();
+        let mut live = bus.subscribe(birthday.id(), None).await.unwrap();
+        bus.publish(&christmas, added(&christmas, "Scarf"))
+            .await
+            .unwrap();
+        let second = bus
+            .publish(&birthday, added(&birthday, "Pen"))
+            .await
+            .unwrap();
+        assert_eq!(live.next().await, Some(second.clone()));
+
+        let mut resumed = bus
+            .subscribe(birthday.id(), Some(first.id()))
+            .await
+            .unwrap();
+        assert_eq!(resumed.next().await, Some(second.clone()));
+        let third = bus
+            .publish(&birthday, added(&birthday, "Mug"))
+            .await
+            .unwrap();
+        assert_eq!(resumed.next().await, Some(third.clone()));
+        assert!(third.id() > second.id() && second.id() > first.id());
+
+        // Only the last two events are kept
+        let mut late = bus.subscribe(birthday.id(), Some(0)).await.unwrap();
+        assert_eq!(late.next().await, Some(second));
+        assert_eq!(late.next().await, Some(third));
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscription_ends() {
+        let bus = InMemoryItemEventBus::with_history(1);
+        let birthday = wishlist();
+        let mut live = bus.subscribe(birthday.id(), None).await.unwrap();
+        bus.publish(&birthday, added(&birthday, "Book"))
+            .await
+            .unwrap();
+        bus.publish(&birthday, added(&birthday, "Pen"))
+            .await
+            .unwrap();
+
+        assert_eq!(live.next().await, None);
+    }
+}
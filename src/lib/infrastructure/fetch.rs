@@ -0,0 +1,484 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE, LOCATION},
+    redirect::Policy,
+    Client, Response,
+};
+use thiserror::Error;
+use url::Url;
+
+use crate::domain::{
+    FetchImageError, FetchItemPageError, ImageFetcher, ItemImageUrl, ItemLinkUrl, ItemPage,
+    ItemPageFetcher,
+};
+
+const USER_AGENT: &str = concat!("wishlist/", env!("CARGO_PKG_VERSION"));
+const ACCEPT_HTML: &str = "text/html,application/xhtml+xml;q=0.9,*/*;q=0.1";
+const ACCEPT_IMAGE: &str = "image/png,image/jpeg,image/gif,image/webp;q=0.9,*/*;q=0.1";
+
+pub struct HttpFetcherConfig {
+    /// Upper bound for the whole fetch, redirects included.
+    pub timeout: Duration,
+    /// Pages larger than this are rejected without being read further.
+    pub max_body_bytes: usize,
+    pub max_redirects: usize,
+    /// Allows fetching loopback and private addresses. Only meant for tests and local setups.
+    pub allow_private_networks: bool,
+}
+
+/// The [HttpFetcher] struct downloads remote content over HTTP(S) on behalf of users. It
+/// implements [ItemPageFetcher] for product pages and [ImageFetcher] for mirrored images.
+///
+/// Every hop of a redirect chain is resolved up front and rejected if it points to a
+/// non-public address; the connection is then pinned to the checked addresses so a second DNS
+/// answer cannot be used to reach an internal host.
+pub struct HttpFetcher {
+    config: HttpFetcherConfig,
+}
+
+/// Failures shared by every kind of fetch, mapped onto the domain error of each port.
+#[derive(Debug, Error)]
+pub(super) enum FetchError {
+    #[error("Fetching {url} is not allowed")]
+    Forbidden { url: String },
+    #[error("Response is larger than {limit} bytes")]
+    TooLarge { limit: usize },
+    #[error("Fetch timed out")]
+    Timeout,
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            FetchError::Timeout
+        } else {
+            FetchError::Unknown(err.into())
+        }
+    }
+}
+
+impl From<FetchError> for FetchItemPageError {
+    fn from(err: FetchError) -> Self {
+        match err {
+            FetchError::Forbidden { url } => FetchItemPageError::Forbidden { url },
+            FetchError::TooLarge { limit } => FetchItemPageError::TooLarge { limit },
+            FetchError::Timeout => FetchItemPageError::Timeout,
+            FetchError::Unknown(err) => FetchItemPageError::Unknown(err),
+        }
+    }
+}
+
+impl From<FetchError> for FetchImageError {
+    fn from(err: FetchError) -> Self {
+        match err {
+            FetchError::Forbidden { url } => FetchImageError::Forbidden { url },
+            FetchError::TooLarge { limit } => FetchImageError::TooLarge { limit },
+            FetchError::Timeout => FetchImageError::Timeout,
+            FetchError::Unknown(err) => FetchImageError::Unknown(err),
+        }
+    }
+}
+
+impl HttpFetcher {
+    pub fn new(config: HttpFetcherConfig) -> Self {
+        Self { config }
+    }
+
+    async fn fetch_page(&self, url: &Url) -> Result<ItemPage, FetchError> {
+        let (url, response) = self.follow(url, ACCEPT_HTML).await?;
+        let is_html =
+            content_type(&response).is_none_or(|content_type| content_type.contains("html"));
+        if !is_html {
+            return Ok(ItemPage::new(url, String::new()));
+        }
+        let body = read_body(response, self.config.max_body_bytes).await?;
+        Ok(ItemPage::new(
+            url,
+            String::from_utf8_lossy(&body).into_owned(),
+        ))
+    }
+
+    async fn fetch_bytes(&self, url: &Url, max_bytes: usize) -> Result<Vec<u8>, FetchError> {
+        let (_, response) = self.follow(url, ACCEPT_IMAGE).await?;
+        read_body(response, max_bytes).await
+    }
+
+    /// Requests `url`, following redirects, and returns the final URL with its successful
+    /// response.
+    async fn follow(&self, url: &Url, accept: &str) -> Result<(Url, Response), FetchError> {
+        let mut url = url.clone();
+        for _ in 0..=self.config.max_redirects {
+            let response = self.get(&url, accept).await?;
+            if response.status().is_redirection() {
+                let location = response
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .ok_or_else(|| anyhow!("redirect from {} has no location", url))?;
+                url = url.join(location).context("invalid redirect location")?;
+                continue;
+            }
+            if !response.status().is_success() {
+                return Err(anyhow!("{} responded with {}", url, response.status()).into());
+            }
+            return Ok((url, response));
+        }
+        Err(anyhow!("too many redirects").into())
+    }
+
+    async fn get(&self, url: &Url, accept: &str) -> Result<Response, FetchError> {
+        let client = pinned_public_client(
+            url,
+            self.config.timeout,
+            self.config.allow_private_networks,
+            USER_AGENT,
+        )
+        .await?;
+        Ok(client
+            .get(url.clone())
+            .header(ACCEPT, accept)
+            .send()
+            .await?)
+    }
+
+    async fn with_timeout<T>(
+        &self,
+        fetch: impl std::future::Future<Output = Result<T, FetchError>>,
+    ) -> Result<T, FetchError> {
+        tokio::time::timeout(self.config.timeout, fetch)
+            .await
+            .unwrap_or(Err(FetchError::Timeout))
+    }
+}
+
+impl ItemPageFetcher for HttpFetcher {
+    async fn fetch(&self, url: &ItemLinkUrl) -> Result<ItemPage, FetchItemPageError> {
+        Ok(self.with_timeout(self.fetch_page(url.as_url())).await?)
+    }
+}
+
+impl ImageFetcher for HttpFetcher {
+    async fn fetch_image(
+        &self,
+        url: &ItemImageUrl,
+        max_bytes: usize,
+    ) -> Result<Vec<u8>, FetchImageError> {
+        Ok(self
+            .with_timeout(self.fetch_bytes(url.as_url(), max_bytes))
+            .await?)
+    }
+}
+
+/// Builds a client for a single request to `url`. The host of `url` is resolved now, and rejected
+/// unless every address is public or `allow_private` is set; the client is then pinned to the
+/// checked addresses, so a second DNS answer cannot be used to reach an internal host. It does
+/// not follow redirects: callers check each hop with a new client.
+pub(super) async fn pinned_public_client(
+    url: &Url,
+    timeout: Duration,
+    allow_private: bool,
+    user_agent: &str,
+) -> Result<Client, FetchError> {
+    let forbidden = || FetchError::Forbidden {
+        url: url.to_string(),
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(forbidden());
+    }
+    let host = url.host_str().ok_or_else(forbidden)?;
+    let port = url.port_or_known_default().ok_or_else(forbidden)?;
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .with_context(|| format!("failed to resolve {}", host))?
+        .collect();
+    if addrs.is_empty() || (!allow_private && addrs.iter().any(|addr| !is_public(addr.ip()))) {
+        return Err(forbidden());
+    }
+    Ok(Client::builder()
+        .redirect(Policy::none())
+        .connect_timeout(timeout)
+        .timeout(timeout)
+        .user_agent(user_agent)
+        .resolve_to_addrs(host, &addrs)
+        .build()
+        .context("failed to build HTTP client")?)
+}
+
+fn content_type(response: &Response) -> Option<&str> {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+}
+
+/// Reads the whole body of `response`, giving up as soon as it grows past `limit` bytes.
+async fn read_body(mut response: Response, limit: usize) -> Result<Vec<u8>, FetchError> {
+    if response
+        .content_length()
+        .is_some_and(|length| length as usize > limit)
+    {
+        return Err(FetchError::TooLarge { limit });
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(FetchError::TooLarge { limit });
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Returns true if `ip` is a globally routable unicast address.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match embedded_v4(ip) {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+/// Returns the IPv4 address an IPv6 address reaches, if it embeds one: IPv4-mapped
+/// (`::ffff:a.b.c.d`), IPv4-compatible (`::a.b.c.d`), NAT64 (`64:ff9b::/96`) and 6to4
+/// (`2002::/16`) addresses are routed to it, so they are only as public as it is.
+fn embedded_v4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = ip.segments();
+    let v4 = |high: u16, low: u16| Ipv4Addr::from((u32::from(high) << 16) | u32::from(low));
+    if let Some(ip) = ip.to_ipv4_mapped() {
+        return Some(ip);
+    }
+    match segments {
+        [0, 0, 0, 0, 0, 0, high, low] => Some(v4(high, low)),
+        [0x64, 0xff9b, 0, 0, 0, 0, high, low] => Some(v4(high, low)),
+        [0x2002, high, low, ..] => Some(v4(high, low)),
+        _ => None,
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // Shared address space (RFC 6598) and reserved blocks.
+        || (a == 100 && (64..128).contains(&b))
+        || a == 0
+        || a >= 240
+        // Benchmarking (RFC 2544).
+        || (a == 198 && (18..20).contains(&b)))
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local (fc00::/7) and link-local (fe80::/10) addresses.
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        // Documentation (2001:db8::/32).
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8)
+        // Local-use NAT64 (64:ff9b:1::/48), whose embedding of IPv4 addresses varies.
+        || (first == 0x64 && ip.segments()[1] == 0xff9b && ip.segments()[2] == 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        http::{header, StatusCode},
+        response::{IntoResponse, Redirect},
+        routing::get,
+        Router,
+    };
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        let path = format!("{}/fixtures/html/{}", env!("CARGO_MANIFEST_DIR"), name);
+        std::fs::read_to_string(path).expect("fixture exists")
+    }
+
+    /// Serves the HTML fixtures from a local stub server and returns its base URL.
+    async fn spawn_stub() -> String {
+        let router = Router::new()
+            .route(
+                "/product",
+                get(|| async {
+                    (
+                        [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+                        fixture("json_ld.html"),
+                    )
+                }),
+            )
+            .route("/moved", get(|| async { Redirect::temporary("/product") }))
+            .route("/loop", get(|| async { Redirect::temporary("/loop") }))
+            .route(
+                "/large",
+                get(|| async { ([(header::CONTENT_TYPE, "text/html")], "a".repeat(4096)) }),
+            )
+            .route(
+                "/image",
+                get(|| async { ([(header::CONTENT_TYPE, "image/png")], vec![0u8; 16]) }),
+            )
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "too late"
+                }),
+            )
+            .route(
+                "/missing",
+                get(|| async { StatusCode::NOT_FOUND.into_response() }),
+            );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://{}", address)
+    }
+
+    fn fetcher(allow_private_networks: bool) -> HttpFetcher {
+        HttpFetcher::new(HttpFetcherConfig {
+            timeout: Duration::from_millis(500),
+            max_body_bytes: 2048,
+            max_redirects: 3,
+            allow_private_networks,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page() {
+        let address = spawn_stub().await;
+        let url = ItemLinkUrl::from(format!("{}/product", address).as_str());
+
+        let page = fetcher(true).fetch(&url).await.unwrap();
+        assert_eq!(page.url(), url.as_url());
+        assert_eq!(page.html(), fixture("json_ld.html"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_follows_redirects() {
+        let address = spawn_stub().await;
+        let url = ItemLinkUrl::from(format!("{}/moved", address).as_str());
+
+        let page = fetcher(true).fetch(&url).await.unwrap();
+        assert_eq!(page.url().path(), "/product");
+        assert_eq!(page.html(), fixture("json_ld.html"));
+
+        let url = ItemLinkUrl::from(format!("{}/loop", address).as_str());
+        let result = fetcher(true).fetch(&url).await;
+        assert!(matches!(result, Err(FetchItemPageError::Unknown(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_private_networks() {
+        let address = spawn_stub().await;
+        let url = ItemLinkUrl::from(format!("{}/product", address).as_str());
+
+        let result = fetcher(false).fetch(&url).await;
+        assert!(matches!(result, Err(FetchItemPageError::Forbidden { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_enforces_size_limit() {
+        let address = spawn_stub().await;
+        let url = ItemLinkUrl::from(format!("{}/large", address).as_str());
+
+        let result = fetcher(true).fetch(&url).await;
+        assert!(matches!(
+            result,
+            Err(FetchItemPageError::TooLarge { limit: 2048 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_times_out() {
+        let address = spawn_stub().await;
+        let url = ItemLinkUrl::from(format!("{}/slow", address).as_str());
+
+        let result = fetcher(true).fetch(&url).await;
+        assert!(matches!(result, Err(FetchItemPageError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_ignores_non_html_and_errors() {
+        let address = spawn_stub().await;
+        let url = ItemLinkUrl::from(format!("{}/image", address).as_str());
+        let page = fetcher(true).fetch(&url).await.unwrap();
+        assert!(page.html().is_empty());
+
+        let url = ItemLinkUrl::from(format!("{}/missing", address).as_str());
+        let result = fetcher(true).fetch(&url).await;
+        assert!(matches!(result, Err(FetchItemPageError::Unknown(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_image() {
+        let address = spawn_stub().await;
+        let url = ItemImageUrl::from(format!("{}/image", address).as_str());
+        let bytes = fetcher(true).fetch_image(&url, 1024).await.unwrap();
+        assert_eq!(bytes, vec![0u8; 16]);
+
+        let result = fetcher(true).fetch_image(&url, 8).await;
+        assert!(matches!(
+            result,
+            Err(FetchImageError::TooLarge { limit: 8 })
+        ));
+
+        let result = fetcher(false).fetch_image(&url, 1024).await;
+        assert!(matches!(result, Err(FetchImageError::Forbidden { .. })));
+    }
+
+    #[test]
+    fn test_is_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "192.168.0.1",
+            "169.254.169.254",
+            "100.64.0.1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{} should be private", ip);
+        }
+        for ip in [
+            "::",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::127.0.0.1",
+            "::10.0.0.1",
+            "64:ff9b::127.0.0.1",
+            "64:ff9b::a00:1",
+            "64:ff9b:1::1",
+            "2002:7f00:1::",
+            "2002:a00:1::1",
+            "2002:c0a8:1::",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{} should be private", ip);
+        }
+        for ip in [
+            "93.184.216.34",
+            "2606:2800:220:1:248:1893:25c8:1946",
+            "::ffff:93.184.216.34",
+            "64:ff9b::93.184.216.34",
+            "2002:5db8:d822::1",
+        ] {
+            assert!(is_public(ip.parse().unwrap()), "{} should be public", ip);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::io::Cursor;
+
+use anyhow::Context;
+use image::{
+    codecs::{jpeg::JpegEncoder, png::PngEncoder},
+    DynamicImage, ImageError, ImageFormat, ImageReader, Limits,
+};
+
+use crate::domain::{Blob, ImageProcessor, ImageSize, ProcessImageError};
+
+const JPEG_QUALITY: u8 = 85;
+
+/// The [RasterImageProcessor] struct implements [ImageProcessor] for PNG, JPEG, GIF and WebP
+/// images.
+///
+/// The format is sniffed from the content rather than trusted from the client. Every size,
+/// the original included, is re-encoded so that metadata such as EXIF locations is dropped:
+/// images with transparency are stored as PNG, the others as JPEG. Thumbnails keep the aspect
+/// ratio and are never upscaled.
+pub struct RasterImageProcessor {
+    max_dimension: u32,
+}
+
+impl RasterImageProcessor {
+    /// Creates a processor rejecting images wider or higher than `max_dimension` pixels.
+    pub fn new(max_dimension: u32) -> Self {
+        Self { max_dimension }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<DynamicImage, ProcessImageError> {
+        let format = image::guess_format(bytes).map_err(|_| ProcessImageError::Unsupported)?;
+        if !matches!(
+            format,
+            ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::Gif | ImageFormat::WebP
+        ) {
+            return Err(ProcessImageError::Unsupported);
+        }
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(self.max_dimension);
+        limits.max_image_height = Some(self.max_dimension);
+        let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+        reader.limits(limits);
+        reader.decode().map_err(|err| match err {
+            ImageError::Limits(_) => ProcessImageError::TooLarge {
+                limit: self.max_dimension,
+            },
+            // Reading from memory only fails on truncated content.
+            ImageError::Unsupported(_) | ImageError::Decoding(_) | ImageError::IoError(_) => {
+                ProcessImageError::Unsupported
+            }
+            err => ProcessImageError::Unkown(err.into()),
+        })
+    }
+}
+
+impl ImageProcessor for RasterImageProcessor {
+    fn process(&self, bytes: &[u8]) -> Result<Vec<(ImageSize, Blob)>, ProcessImageError> {
+        let image = self.decode(bytes)?;
+        ImageSize::ALL
+            .into_iter()
+            .map(|size| {
+                let resized = match size.max_dimension() {
+                    Some(max) if image.width() > max || image.height() > max => {
+                        image.thumbnail(max, max)
+                    }
+                    _ => image.clone(),
+                };
+                Ok((size, encode(&resized)?))
+            })
+            .collect()
+    }
+}
+
+fn encode(image: &DynamicImage) -> Result<Blob, ProcessImageError> {
+    let mut bytes = Vec::new();
+    if image.color().has_alpha() {
+        DynamicImage::ImageRgba8(image.to_rgba8())
+            .write_with_encoder(PngEncoder::new(&mut bytes))
+            .context("failed to encode PNG")?;
+        Ok(Blob::new("image/png", bytes))
+    } else {
+        DynamicImage::ImageRgb8(image.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY))
+            .context("failed to encode JPEG")?;
+        Ok(Blob::new("image/jpeg", bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{GenericImageView, ImageBuffer, Rgb, Rgba};
+
+    use super::*;
+
+    /// Encodes a plain image of the given size in `format`.
+    fn sample_image(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let image = if format == ImageFormat::Png {
+            DynamicImage::ImageRgba8(ImageBuffer::from_pixel(width, height, Rgba([1, 2, 3, 128])))
+        } else {
+            DynamicImage::ImageRgb8(ImageBuffer::from_pixel(width, height, Rgb([1, 2, 3])))
+        };
+        image
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    fn dimensions(blob: &Blob) -> (u32, u32) {
+        image::load_from_memory(blob.bytes()).unwrap().dimensions()
+    }
+
+    #[test]
+    fn test_process_creates_thumbnails() {
+        let bytes = sample_image(2000, 1000, ImageFormat::Jpeg);
+        let blobs = RasterImageProcessor::new(4096).process(&bytes).unwrap();
+
+        let sizes: Vec<_> = blobs
+            .iter()
+            .map(|(size, blob)| {
+                assert_eq!(blob.content_type(), "image/jpeg");
+                (*size, dimensions(blob))
+            })
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                (ImageSize::Original, (2000, 1000)),
+                (ImageSize::Large, (1024, 512)),
+                (ImageSize::Medium, (512, 256)),
+                (ImageSize::Small, (128, 64)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_process_never_upscales() {
+        let bytes = sample_image(100, 50, ImageFormat::Png);
+        let blobs = RasterImageProcessor::new(4096).process(&bytes).unwrap();
+
+        for (_, blob) in blobs {
+            assert_eq!(blob.content_type(), "image/png");
+            assert_eq!(dimensions(&blob), (100, 50));
+        }
+    }
+
+    #[test]
+    fn test_process_rejects_unsupported_content() {
+        let processor = RasterImageProcessor::new(4096);
+        let svg = b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>";
+        assert!(matches!(
+            processor.process(svg),
+            Err(ProcessImageError::Unsupported)
+        ));
+        let mut truncated = sample_image(64, 64, ImageFormat::Png);
+        truncated.truncate(32);
+        assert!(matches!(
+            processor.process(&truncated),
+            Err(ProcessImageError::Unsupported)
+        ));
+        // Sniffed as BMP, which is deliberately not accepted.
+        assert!(matches!(
+            processor.process(b"BM\0\0\0\0\0\0\0\0"),
+            Err(ProcessImageError::Unsupported)
+        ));
+    }
+
+    #[test]
+    fn test_process_enforces_dimension_limit() {
+        let bytes = sample_image(300, 10, ImageFormat::Png);
+        let result = RasterImageProcessor::new(256).process(&bytes);
+        assert!(matches!(
+            result,
+            Err(ProcessImageError::TooLarge { limit: 256 })
+        ));
+    }
+}
@@ -0,0 +1,12 @@
+use crate::domain::{Email, MailTransport, SendMailError};
+
+/// The [LoggingMailTransport] struct is a [MailTransport] that writes emails to the logs instead
+/// of sending them.
+pub struct LoggingMailTransport;
+
+impl MailTransport for LoggingMailTransport {
+    async fn send(&self, email: &Email) -> Result<(), SendMailError> {
+        tracing::info!(to = %email.to(), "email {:?}: {}", email.subject(), email.body());
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+mod amazon;
+mod etsy;
+mod generic;
+mod ikea;
+
+pub use amazon::AmazonExtractor;
+pub use etsy::EtsyExtractor;
+pub use generic::GenericExtractor;
+pub use ikea::IkeaExtractor;
+
+use scraper::{Html, Selector};
+use url::Url;
+
+use crate::domain::{ExtractorRegistry, ItemImageUrl, ItemPrice, ItemTitle};
+
+/// Returns a registry with the [GenericExtractor] as fallback and every built-in retailer
+/// extractor registered.
+pub fn builtin_extractors() -> ExtractorRegistry {
+    ExtractorRegistry::new(GenericExtractor)
+        .register(AmazonExtractor)
+        .register(EtsyExtractor)
+        .register(IkeaExtractor)
+}
+
+fn selector(selectors: &str) -> Selector {
+    Selector::parse(selectors).expect("selector is valid")
+}
+
+fn title(value: &str) -> Option<ItemTitle> {
+    ItemTitle::new(value).ok()
+}
+
+fn image_url(value: &str, base: &Url) -> Option<ItemImageUrl> {
+    let url = base.join(value.trim()).ok()?;
+    ItemImageUrl::new(url.as_str()).ok()
+}
+
+fn price(value: &str) -> Option<ItemPrice> {
+    ItemPrice::parse(value).ok()
+}
+
+/// Returns the text of the first element matching `selectors`.
+fn text(document: &Html, selectors: &str) -> Option<String> {
+    document
+        .select(&selector(selectors))
+        .map(|element| element.text().collect::<String>().trim().to_string())
+        .find(|text| !text.is_empty())
+}
+
+/// Returns the first value of one of `attributes` on an element matching `selectors`.
+fn attr(document: &Html, selectors: &str, attributes: &[&str]) -> Option<String> {
+    document.select(&selector(selectors)).find_map(|element| {
+        attributes
+            .iter()
+            .find_map(|attribute| element.value().attr(attribute))
+            .map(str::to_string)
+    })
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use url::Url;
+
+    use super::builtin_extractors;
+    use crate::domain::ItemPage;
+
+    /// Loads a saved HTML page from `fixtures/html` as if it was served from `url`.
+    pub fn fixture_page(name: &str, url: &str) -> ItemPage {
+        let path = format!("{}/fixtures/html/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let html = std::fs::read_to_string(path).expect("fixture exists");
+        ItemPage::new(Url::parse(url).unwrap(), html)
+    }
+
+    #[test]
+    fn builtin_extractors_are_selected_by_host() {
+        let registry = builtin_extractors();
+        let cases = [
+            (
+                "https://www.amazon.co.uk/dp/B0TEST",
+                "Kindle Paperwhite (16 GB)",
+            ),
+            (
+                "https://www.etsy.com/listing/1234/wool",
+                "Hand knitted wool scarf",
+            ),
+            (
+                "https://www.ikea.com/fr/fr/p/billy-1",
+                "BILLY Bookcase, white",
+            ),
+        ];
+        for (url, expected) in cases {
+            let fixture = match Url::parse(url).unwrap().host_str().unwrap() {
+                host if host.contains("amazon") => "amazon.html",
+                host if host.contains("etsy") => "etsy.html",
+                _ => "ikea.html",
+            };
+            let metadata = registry.extract(&fixture_page(fixture, url));
+            assert_eq!(metadata.title(), Some(&expected.into()), "{}", url);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use scraper::Html;
+
+use super::{attr, image_url, price, text, title};
+use crate::domain::{HostPattern, ItemMetadata, ItemMetadataExtractor, ItemPage};
+
+/// The [AmazonExtractor] struct reads Amazon product pages, which expose neither OpenGraph nor
+/// JSON-LD product data.
+pub struct AmazonExtractor;
+
+impl ItemMetadataExtractor for AmazonExtractor {
+    fn hosts(&self) -> Vec<HostPattern> {
+        vec![HostPattern::new("*.amazon.*").expect("pattern is valid")]
+    }
+
+    fn extract(&self, page: &ItemPage) -> ItemMetadata {
+        let document = Html::parse_document(page.html());
+        ItemMetadata::new(
+            text(&document, "#productTitle").as_deref().and_then(title),
+            attr(
+                &document,
+                "#landingImage, #imgBlkFront",
+                &["data-old-hires", "src"],
+            )
+            .filter(|src| !src.is_empty())
+            .and_then(|src| image_url(&src, page.url())),
+            text(
+                &document,
+                "#corePrice_feature_div .a-price .a-offscreen, \
+                 #corePriceDisplay_desktop_feature_div .a-price .a-offscreen, \
+                 #priceblock_ourprice, #price_inside_buybox",
+            )
+            .as_deref()
+            .and_then(price),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::metadata::extractors::tests::fixture_page;
+
+    #[test]
+    fn extract_amazon_product() {
+        let page = fixture_page("amazon.html", "https://www.amazon.co.uk/dp/B0TEST");
+        let metadata = AmazonExtractor.extract(&page);
+
+        assert_eq!(metadata.title(), Some(&"Kindle Paperwhite (16 GB)".into()));
+        assert_eq!(
+            metadata.image_url(),
+            Some(&"https://m.media-amazon.com/images/I/kindle-large.jpg".into())
+        );
+        assert_eq!(metadata.price().unwrap().to_string(), "149.99");
+    }
+}
@@ -0,0 +1,54 @@
+use scraper::Html;
+
+use super::{attr, image_url, price, text, title};
+use crate::domain::{HostPattern, ItemMetadata, ItemMetadataExtractor, ItemPage};
+
+/// The [EtsyExtractor] struct reads Etsy listings, whose structured data only carries the
+/// lowest price across variations rather than the one shown in the buy box.
+pub struct EtsyExtractor;
+
+impl ItemMetadataExtractor for EtsyExtractor {
+    fn hosts(&self) -> Vec<HostPattern> {
+        vec![HostPattern::new("*.etsy.com").expect("pattern is valid")]
+    }
+
+    fn extract(&self, page: &ItemPage) -> ItemMetadata {
+        let document = Html::parse_document(page.html());
+        ItemMetadata::new(
+            text(&document, "h1[data-buy-box-listing-title]")
+                .as_deref()
+                .and_then(title),
+            attr(
+                &document,
+                "img[data-src-zoom-image], .listing-page-image-carousel-component img",
+                &["data-src-zoom-image", "src"],
+            )
+            .and_then(|src| image_url(&src, page.url())),
+            text(
+                &document,
+                r#"[data-buy-box-region="price"] .wt-text-title-larger"#,
+            )
+            .as_deref()
+            .and_then(price),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::metadata::extractors::tests::fixture_page;
+
+    #[test]
+    fn extract_etsy_listing() {
+        let page = fixture_page("etsy.html", "https://www.etsy.com/listing/1234/wool");
+        let metadata = EtsyExtractor.extract(&page);
+
+        assert_eq!(metadata.title(), Some(&"Hand knitted wool scarf".into()));
+        assert_eq!(
+            metadata.image_url(),
+            Some(&"https://i.etsystatic.com/1234/il_fullxfull.jpg".into())
+        );
+        assert_eq!(metadata.price().unwrap().to_string(), "42");
+    }
+}
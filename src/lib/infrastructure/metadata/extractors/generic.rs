@@ -0,0 +1,266 @@
+use scraper::{ElementRef, Html};
+use serde_json::Value;
+use url::Url;
+
+use super::{image_url, price, selector, title};
+use crate::domain::{HostPattern, ItemMetadata, ItemMetadataExtractor, ItemPage, ItemPrice};
+
+/// The [GenericExtractor] struct reads the product details any well-behaved page exposes.
+///
+/// Sources are tried from the most to the least structured: JSON-LD `Product`/`Offer`, schema.org
+/// microdata, OpenGraph, Twitter cards and finally the document `<title>`.
+pub struct GenericExtractor;
+
+impl ItemMetadataExtractor for GenericExtractor {
+    fn hosts(&self) -> Vec<HostPattern> {
+        Vec::new()
+    }
+
+    fn extract(&self, page: &ItemPage) -> ItemMetadata {
+        let document = Html::parse_document(page.html());
+        let base = page.url();
+        json_ld(&document, base)
+            .or(microdata(&document, base))
+            .or(open_graph(&document, base))
+            .or(twitter_card(&document, base))
+            .or(document_title(&document))
+    }
+}
+
+/// Returns the `content` of the first `<meta>` tag whose `property` or `name` is one of `keys`.
+fn meta_content<'a>(document: &'a Html, keys: &[&str]) -> Option<&'a str> {
+    let metas = selector("meta[content]");
+    keys.iter().find_map(|key| {
+        document.select(&metas).find_map(|meta| {
+            let element = meta.value();
+            let name = element.attr("property").or(element.attr("name"))?;
+            name.eq_ignore_ascii_case(key)
+                .then(|| element.attr("content"))
+                .flatten()
+        })
+    })
+}
+
+fn open_graph(document: &Html, base: &Url) -> ItemMetadata {
+    ItemMetadata::new(
+        meta_content(document, &["og:title"]).and_then(title),
+        meta_content(
+            document,
+            &["og:image:secure_url", "og:image", "og:image:url"],
+        )
+        .and_then(|value| image_url(value, base)),
+        meta_content(document, &["product:price:amount", "og:price:amount"]).and_then(price),
+    )
+}
+
+fn twitter_card(document: &Html, base: &Url) -> ItemMetadata {
+    let price_label = meta_content(document, &["twitter:label1"]).unwrap_or_default();
+    ItemMetadata::new(
+        meta_content(document, &["twitter:title"]).and_then(title),
+        meta_content(document, &["twitter:image", "twitter:image:src"])
+            .and_then(|value| image_url(value, base)),
+        price_label
+            .eq_ignore_ascii_case("price")
+            .then(|| meta_content(document, &["twitter:data1"]))
+            .flatten()
+            .and_then(price),
+    )
+}
+
+fn document_title(document: &Html) -> ItemMetadata {
+    let title_selector = selector("title");
+    let text = document
+        .select(&title_selector)
+        .next()
+        .map(|element| element.text().collect::<String>());
+    ItemMetadata::new(text.as_deref().and_then(title), None, None)
+}
+
+fn json_ld(document: &Html, base: &Url) -> ItemMetadata {
+    let scripts = selector(r#"script[type="application/ld+json"]"#);
+    document
+        .select(&scripts)
+        .filter_map(|script| serde_json::from_str::<Value>(&script.text().collect::<String>()).ok())
+        .flat_map(|value| {
+            let mut products = Vec::new();
+            collect_products(&value, &mut products);
+            products
+                .into_iter()
+                .map(|product| json_ld_product(product, base))
+                .collect::<Vec<_>>()
+        })
+        .fold(ItemMetadata::default(), ItemMetadata::or)
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match value.get("@type") {
+        Some(Value::String(kind)) => kind == expected,
+        Some(Value::Array(kinds)) => kinds.iter().any(|kind| kind == expected),
+        _ => false,
+    }
+}
+
+/// Walks a JSON-LD document, including `@graph` containers, looking for `Product` nodes.
+fn collect_products<'a>(value: &'a Value, products: &mut Vec<&'a Value>) {
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_products(value, products)),
+        Value::Object(object) => {
+            if has_type(value, "Product") {
+                products.push(value);
+            }
+            if let Some(graph) = object.get("@graph") {
+                collect_products(graph, products);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn json_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::Array(values) => values.iter().find_map(json_string),
+        Value::Object(object) => object
+            .get("url")
+            .or(object.get("contentUrl"))
+            .and_then(json_string),
+        _ => None,
+    }
+}
+
+fn json_ld_price(offers: &Value) -> Option<ItemPrice> {
+    match offers {
+        Value::Array(offers) => offers.iter().find_map(json_ld_price),
+        Value::Object(offer) => ["price", "lowPrice"]
+            .iter()
+            .find_map(|key| offer.get(*key).and_then(json_string))
+            .and_then(|value| price(&value))
+            .or_else(|| offer.get("priceSpecification").and_then(json_ld_price))
+            .or_else(|| offer.get("offers").and_then(json_ld_price)),
+        _ => None,
+    }
+}
+
+fn json_ld_product(product: &Value, base: &Url) -> ItemMetadata {
+    ItemMetadata::new(
+        product
+            .get("name")
+            .and_then(json_string)
+            .and_then(|value| title(&value)),
+        product
+            .get("image")
+            .and_then(json_string)
+            .and_then(|value| image_url(&value, base)),
+        product.get("offers").and_then(json_ld_price),
+    )
+}
+
+/// Returns the value of a microdata property, following the attribute the HTML spec reads for
+/// the element carrying it.
+fn itemprop_value(element: ElementRef) -> Option<String> {
+    let value = element.value();
+    value
+        .attr("content")
+        .or_else(|| match value.name() {
+            "img" | "source" => value.attr("src"),
+            "a" | "link" => value.attr("href"),
+            "meta" => value.attr("content"),
+            _ => None,
+        })
+        .map(str::to_string)
+        .or_else(|| Some(element.text().collect::<String>()))
+}
+
+fn microdata(document: &Html, base: &Url) -> ItemMetadata {
+    let products = selector(r#"[itemscope][itemtype*="schema.org/Product"]"#);
+    let Some(product) = document.select(&products).next() else {
+        return ItemMetadata::default();
+    };
+    let property = |name: &str| {
+        product
+            .select(&selector(&format!(r#"[itemprop="{}"]"#, name)))
+            .next()
+            .and_then(itemprop_value)
+    };
+    ItemMetadata::new(
+        property("name").as_deref().and_then(title),
+        property("image")
+            .as_deref()
+            .and_then(|value| image_url(value, base)),
+        property("price").as_deref().and_then(price),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::infrastructure::metadata::extractors::tests::fixture_page;
+
+    fn extract_metadata(name: &str) -> ItemMetadata {
+        GenericExtractor.extract(&fixture_page(name, "https://shop.example/products/42"))
+    }
+
+    #[test]
+    fn extract_open_graph() {
+        let metadata = extract_metadata("open_graph.html");
+        assert_eq!(metadata.title(), Some(&"Espresso machine".into()));
+        assert_eq!(
+            metadata.image_url(),
+            Some(&"https://shop.example/images/espresso.jpg".into())
+        );
+        assert_eq!(metadata.price().unwrap().to_string(), "249.9");
+    }
+
+    #[test]
+    fn extract_twitter_card() {
+        let metadata = extract_metadata("twitter_card.html");
+        assert_eq!(metadata.title(), Some(&"Wool scarf".into()));
+        assert_eq!(
+            metadata.image_url(),
+            Some(&"https://cdn.shop.example/scarf.png".into())
+        );
+        assert_eq!(metadata.price().unwrap().to_string(), "35");
+    }
+
+    #[test]
+    fn extract_json_ld() {
+        let metadata = extract_metadata("json_ld.html");
+        assert_eq!(
+            metadata.title(),
+            Some(&"Noise cancelling headphones".into())
+        );
+        assert_eq!(
+            metadata.image_url(),
+            Some(&"https://cdn.shop.example/headphones-1.jpg".into())
+        );
+        assert_eq!(metadata.price().unwrap().to_string(), "299.99");
+    }
+
+    #[test]
+    fn extract_microdata() {
+        let metadata = extract_metadata("microdata.html");
+        assert_eq!(metadata.title(), Some(&"Cast iron skillet".into()));
+        assert_eq!(
+            metadata.image_url(),
+            Some(&"https://shop.example/img/skillet.jpg".into())
+        );
+        assert_eq!(metadata.price().unwrap().to_string(), "39.5");
+    }
+
+    #[test]
+    fn extract_document_title_only() {
+        let html = "<html><head><title> Plain page </title></head><body></body></html>";
+        let page = ItemPage::new(
+            Url::parse("https://shop.example/products/42").unwrap(),
+            html.to_string(),
+        );
+        let metadata = GenericExtractor.extract(&page);
+        assert_eq!(metadata.title(), Some(&"Plain page".into()));
+        assert!(metadata.image_url().is_none());
+        assert!(metadata.price().is_none());
+    }
+}
@@ -0,0 +1,59 @@
+use scraper::Html;
+
+use super::{attr, image_url, price, text, title};
+use crate::domain::{HostPattern, ItemMetadata, ItemMetadataExtractor, ItemPage};
+
+/// The [IkeaExtractor] struct reads IKEA product pages, which split the product name from its
+/// description and the integer part of the price from its decimals.
+pub struct IkeaExtractor;
+
+impl ItemMetadataExtractor for IkeaExtractor {
+    fn hosts(&self) -> Vec<HostPattern> {
+        vec![HostPattern::new("*.ikea.com").expect("pattern is valid")]
+    }
+
+    fn extract(&self, page: &ItemPage) -> ItemMetadata {
+        let document = Html::parse_document(page.html());
+        let name = text(&document, ".pip-header-section__title--big");
+        let description = text(&document, ".pip-header-section__description-text");
+        let full_title = match (name, description) {
+            (Some(name), Some(description)) => Some(format!("{} {}", name, description)),
+            (name, _) => name,
+        };
+        let integer = text(
+            &document,
+            ".pip-price-module__current-price .pip-price__integer",
+        );
+        let decimal = text(
+            &document,
+            ".pip-price-module__current-price .pip-price__decimal",
+        );
+        let full_price =
+            integer.map(|integer| format!("{}{}", integer, decimal.unwrap_or_default()));
+        ItemMetadata::new(
+            full_title.as_deref().and_then(title),
+            attr(&document, ".pip-product__left-top img.pip-image", &["src"])
+                .and_then(|src| image_url(&src, page.url())),
+            full_price.as_deref().and_then(price),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::metadata::extractors::tests::fixture_page;
+
+    #[test]
+    fn extract_ikea_product() {
+        let page = fixture_page("ikea.html", "https://www.ikea.com/fr/fr/p/billy-1");
+        let metadata = IkeaExtractor.extract(&page);
+
+        assert_eq!(metadata.title(), Some(&"BILLY Bookcase, white".into()));
+        assert_eq!(
+            metadata.image_url(),
+            Some(&"https://www.ikea.com/fr/fr/images/products/billy-bookcase-white.jpg".into())
+        );
+        assert_eq!(metadata.price().unwrap().to_string(), "1299.95");
+    }
+}
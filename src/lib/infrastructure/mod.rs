@@ -1,3 +1,13 @@
+pub mod clock;
 pub mod config;
+pub mod events;
+pub mod fetch;
+pub mod imaging;
 pub mod logging;
+pub mod mail;
+pub mod metadata;
+pub mod notification;
 pub mod persistence;
+pub mod scheduler;
+pub mod storage;
+pub mod webhook;
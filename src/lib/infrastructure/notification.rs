@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use crate::domain::{
+    Clock, Email, FindUserByIdRequest, InboxRepository, MailTransport, Notification,
+    NotificationKind, Notifier, NotifyError, UserRepository,
+};
+
+/// The [LoggingNotifier] struct is a [Notifier] that only writes notifications to the logs.
+pub struct LoggingNotifier;
+
+impl Notifier for LoggingNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotifyError> {
+        match notification.kind() {
+            NotificationKind::PriceDrop {
+                item_id,
+                title,
+                previous_price,
+                current_price,
+            } => tracing::info!(
+                recipient_id = %notification.recipient_id(),
+                %item_id,
+                "price of {} dropped from {} to {}",
+                title,
+                previous_price,
+                current_price
+            ),
+            NotificationKind::OccasionReminder {
+                wishlist_id, date, ..
+            } => tracing::info!(
+                recipient_id = %notification.recipient_id(),
+                %wishlist_id,
+                "reminder of the occasion on {}",
+                date
+            ),
+        }
+        Ok(())
+    }
+}
+
+/// The [EmailNotifier] struct is a [Notifier] that emails notifications to their recipient
+/// through a [MailTransport]. Anonymous users, who have no email address, are skipped.
+pub struct EmailNotifier<U, M>
+where
+    U: UserRepository,
+    M: MailTransport,
+{
+    user_repository: Arc<U>,
+    transport: Arc<M>,
+}
+
+impl<U, M> EmailNotifier<U, M>
+where
+    U: UserRepository,
+    M: MailTransport,
+{
+    pub fn new(user_repository: Arc<U>, transport: Arc<M>) -> Self {
+        Self {
+            user_repository,
+            transport,
+        }
+    }
+}
+
+impl<U, M> Notifier for EmailNotifier<U, M>
+where
+    U: UserRepository + Send + Sync + 'static,
+    M: MailTransport,
+{
+    async fn notify(&self, notification: &Notification) -> Result<(), NotifyError> {
+        let recipient_id = notification.recipient_id();
+        let user = self
+            .user_repository
+            .find_user_by_id(&FindUserByIdRequest::new(recipient_id))
+            .await
+            .map_err(|err| anyhow!(err))?
+            .ok_or(anyhow!("user {} does not exist", recipient_id))?;
+        if user.anonymous() {
+            return Ok(());
+        }
+        let email = Email::new(
+            user.email().clone(),
+            notification.kind().subject(),
+            notification.kind().message(),
+        );
+        self.transport
+            .send(&email)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(())
+    }
+}
+
+/// The [InboxNotifier] struct is a [Notifier] that stores notifications in the in-app inbox of
+/// their recipient.
+pub struct InboxNotifier<B, C>
+where
+    B: InboxRepository,
+    C: Clock,
+{
+    inbox_repository: Arc<B>,
+    clock: Arc<C>,
+}
+
+impl<B, C> InboxNotifier<B, C>
+where
+    B: InboxRepository,
+    C: Clock,
+{
+    pub fn new(inbox_repository: Arc<B>, clock: Arc<C>) -> Self {
+        Self {
+            inbox_repository,
+            clock,
+        }
+    }
+}
+
+impl<B, C> Notifier for InboxNotifier<B, C>
+where
+    B: InboxRepository,
+    C: Clock,
+{
+    async fn notify(&self, notification: &Notification) -> Result<(), NotifyError> {
+        self.inbox_repository
+            .save(notification, self.clock.now())
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(())
+    }
+}
+
+/// The [FanOutNotifier] struct is a [Notifier] that delivers every notification through two
+/// other notifiers. A failure of one does not prevent delivery through the other, but is
+/// reported.
+pub struct FanOutNotifier<A, B>
+where
+    A: Notifier,
+    B: Notifier,
+{
+    first: A,
+    second: B,
+}
+
+impl<A, B> FanOutNotifier<A, B>
+where
+    A: Notifier,
+    B: Notifier,
+{
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A, B> Notifier for FanOutNotifier<A, B>
+where
+    A: Notifier,
+    B: Notifier,
+{
+    async fn notify(&self, notification: &Notification) -> Result<(), NotifyError> {
+        let first = self.first.notify(notification).await;
+        let second = self.second.notify(notification).await;
+        first.and(second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        domain::{CreateUserRequest, MockMailTransport, MockNotifier, OccasionKind},
+        infrastructure::{
+            clock::ManualClock,
+            persistence::in_memory::{
+                inbox::InMemoryInboxRepository, user::InMemoryUserRepository,
+            },
+        },
+    };
+
+    fn reminder(recipient_id: Uuid) -> Notification {
+        Notification::new(
+            recipient_id,
+            NotificationKind::OccasionReminder {
+                wishlist_id: Uuid::now_v7(),
+                wishlist_name: "Alice's birthday".into(),
+                occasion: OccasionKind::Birthday,
+                date: "2027-03-14".parse().unwrap(),
+                days_left: 3,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_email_and_inbox_notifiers() {
+        let user_repository = Arc::new(InMemoryUserRepository::new());
+        let user = user_repository
+            .save(&CreateUserRequest::new(
+                "bob@example.com".into(),
+                "password".into(),
+            ))
+            .await
+            .unwrap();
+        let mut transport = MockMailTransport::new();
+        transport
+            .expect_send()
+            .withf(|email| {
+                email.to().to_string() == "bob@example.com"
+                    && email.subject() == "Alice's birthday is in 3 days"
+            })
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        let inbox_repository = Arc::new(InMemoryInboxRepository::new());
+        let now = "2027-03-11T09:00:00Z".parse().unwrap();
+        let notifier = FanOutNotifier::new(
+            EmailNotifier::new(user_repository, Arc::new(transport)),
+            InboxNotifier::new(inbox_repository.clone(), Arc::new(ManualClock::new(now))),
+        );
+
+        notifier.notify(&reminder(*user.id())).await.unwrap();
+        let messages = inbox_repository
+            .find_messages(&crate::domain::ListInboxRequest::new(
+                *user.id(),
+                crate::domain::PageRequest::default(),
+            ))
+            .await
+            .unwrap();
+        let messages = messages.entries();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].created_at(), now);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_reports_failures() {
+        let mut failing = MockNotifier::new();
+        failing
+            .expect_notify()
+            .returning(|_| Box::pin(future::ready(Err(anyhow!("down").into()))));
+        let mut working = MockNotifier::new();
+        working
+            .expect_notify()
+            .times(1)
+            .returning(|_| Box::pin(future::ready(Ok(()))));
+        let notifier = FanOutNotifier::new(failing, working);
+
+        let result = notifier.notify(&reminder(Uuid::now_v7())).await;
+        assert!(result.is_err());
+    }
+}
@@ -1,3 +1,13 @@
+pub mod account;
+pub mod exchange;
+pub mod follow;
+pub mod group;
+pub mod image;
+pub mod inbox;
 pub mod item;
+pub mod price_history;
+pub mod profile;
+pub mod reminder;
 pub mod user;
+pub mod webhook;
 pub mod wishlist;
@@ -0,0 +1,138 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    AccountAction, AccountConfirmation, AccountDeletion, AccountRepository,
+    CancelAccountDeletionError, DeleteAccountError, FindAccountDeletionError, PurgeAccountsError,
+    RequestAccountConfirmationError, TakeAccountConfirmationError,
+};
+
+/// The [InMemoryAccountRepository] struct is an in-memory implementation of the
+/// [AccountRepository] trait.
+pub struct InMemoryAccountRepository {
+    deletions: Mutex<HashMap<Uuid, AccountDeletion>>,
+    confirmations: Mutex<HashMap<(Uuid, AccountAction), AccountConfirmation>>,
+}
+
+impl InMemoryAccountRepository {
+    pub fn new() -> Self {
+        Self {
+            deletions: Mutex::new(HashMap::new()),
+            confirmations: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryAccountRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccountRepository for InMemoryAccountRepository {
+    async fn save_deletion(
+        &self,
+        deletion: &AccountDeletion,
+    ) -> Result<AccountDeletion, DeleteAccountError> {
+        let mut deletions = self.deletions.lock().unwrap();
+        Ok(*deletions.entry(deletion.user_id()).or_insert(*deletion))
+    }
+
+    async fn find_deletion(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<AccountDeletion>, FindAccountDeletionError> {
+        let deletions = self.deletions.lock().unwrap();
+        Ok(deletions.get(&user_id).copied())
+    }
+
+    async fn delete_deletion(
+        &self,
+        user_id: Uuid,
+    ) -> Result<AccountDeletion, CancelAccountDeletionError> {
+        let mut deletions = self.deletions.lock().unwrap();
+        deletions
+            .remove(&user_id)
+            .ok_or(CancelAccountDeletionError::NotScheduled { id: user_id })
+    }
+
+    async fn find_due_deletions(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<AccountDeletion>, PurgeAccountsError> {
+        let deletions = self.deletions.lock().unwrap();
+        Ok(deletions
+            .values()
+            .filter(|deletion| deletion.is_due(now))
+            .copied()
+            .collect())
+    }
+
+    async fn save_confirmation(
+        &self,
+        confirmation: &AccountConfirmation,
+    ) -> Result<(), RequestAccountConfirmationError> {
+        let mut confirmations = self.confirmations.lock().unwrap();
+        confirmations.insert(
+            (confirmation.user_id(), confirmation.action()),
+            confirmation.clone(),
+        );
+        Ok(())
+    }
+
+    async fn take_confirmation(
+        &self,
+        user_id: Uuid,
+        action: AccountAction,
+        digest: &str,
+    ) -> Result<Option<AccountConfirmation>, TakeAccountConfirmationError> {
+        let mut confirmations = self.confirmations.lock().unwrap();
+        let key = (user_id, action);
+        if confirmations
+            .get(&key)
+            .is_some_and(|confirmation| confirmation.digest() == digest)
+        {
+            return Ok(confirmations.remove(&key));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_schedule_and_cancel_deletion() {
+        let repository = InMemoryAccountRepository::new();
+        let user_id = Uuid::now_v7();
+        let now: DateTime<Utc> = "2027-01-01T00:00:00Z".parse().unwrap();
+        let deletion = AccountDeletion::new(user_id, now, Duration::days(30));
+        repository.save_deletion(&deletion).await.unwrap();
+        let again = AccountDeletion::new(user_id, now + Duration::days(1), Duration::days(30));
+        assert_eq!(repository.save_deletion(&again).await.unwrap(), deletion);
+        assert!(repository
+            .find_due_deletions(now + Duration::days(29))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            repository
+                .find_due_deletions(now + Duration::days(30))
+                .await
+                .unwrap(),
+            vec![deletion]
+        );
+
+        repository.delete_deletion(user_id).await.unwrap();
+        assert_eq!(repository.find_deletion(user_id).await.unwrap(), None);
+        assert!(matches!(
+            repository.delete_deletion(user_id).await,
+            Err(CancelAccountDeletionError::NotScheduled { .. })
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use crate::domain::{
+    CreateExchangeError, CreateExchangeRequest, Draw, Exchange, ExchangeRepository, Exclusion,
+    FindExchangeError, ForgetUserError, Participant, UpdateExchangeError,
+};
+
+/// The [InMemoryExchangeRepository] struct is an in-memory implementation of the
+/// [ExchangeRepository] trait.
+pub struct InMemoryExchangeRepository {
+    exchanges: Mutex<HashMap<Uuid, Exchange>>,
+}
+
+impl InMemoryExchangeRepository {
+    pub fn new() -> Self {
+        Self {
+            exchanges: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Applies `update` to an exchange that has not been drawn yet.
+    fn update(
+        &self,
+        exchange_id: Uuid,
+        update: impl FnOnce(&mut Exchange) -> Result<(), UpdateExchangeError>,
+    ) -> Result<Exchange, UpdateExchangeError> {
+        let mut exchanges = self.exchanges.lock().unwrap();
+        let exchange = exchanges
+            .get_mut(&exchange_id)
+            .ok_or(UpdateExchangeError::ExchangeDoesNotExist { id: exchange_id })?;
+        if exchange.draw().is_some() {
+            return Err(UpdateExchangeError::AlreadyDrawn { id: exchange_id });
+        }
+        update(exchange)?;
+        Ok(exchange.clone())
+    }
+}
+
+impl Default for InMemoryExchangeRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExchangeRepository for InMemoryExchangeRepository {
+    async fn save(&self, req: &CreateExchangeRequest) -> Result<Exchange, CreateExchangeError> {
+        let exchange = Exchange::new(Uuid::now_v7(), req.organizer_id(), req.name().clone());
+        self.exchanges
+            .lock()
+            .unwrap()
+            .insert(exchange.id(), exchange.clone());
+        Ok(exchange)
+    }
+
+    async fn find_exchange_by_id(
+        &self,
+        exchange_id: Uuid,
+    ) -> Result<Option<Exchange>, FindExchangeError> {
+        Ok(self.exchanges.lock().unwrap().get(&exchange_id).cloned())
+    }
+
+    async fn add_participant(
+        &self,
+        exchange_id: Uuid,
+        participant: Participant,
+    ) -> Result<Exchange, UpdateExchangeError> {
+        self.update(exchange_id, |exchange| {
+            exchange.add_participant(participant);
+            Ok(())
+        })
+    }
+
+    async fn add_exclusions(
+        &self,
+        exchange_id: Uuid,
+        exclusions: Vec<Exclusion>,
+    ) -> Result<Exchange, UpdateExchangeError> {
+        self.update(exchange_id, |exchange| {
+            for exclusion in exclusions {
+                exchange.add_exclusion(exclusion);
+            }
+            Ok(())
+        })
+    }
+
+    async fn save_draw(
+        &self,
+        exchange_id: Uuid,
+        draw: Draw,
+    ) -> Result<Exchange, UpdateExchangeError> {
+        self.update(exchange_id, |exchange| {
+            let participants: HashSet<Uuid> = exchange
+                .participants()
+                .iter()
+                .map(Participant::user_id)
+                .collect();
+            let givers: HashSet<Uuid> = draw
+                .assignments()
+                .iter()
+                .map(|assignment| assignment.giver_id())
+                .collect();
+            if participants != givers {
+                return Err(anyhow!("participants of exchange {} changed", exchange_id).into());
+            }
+            exchange.set_draw(draw);
+            Ok(())
+        })
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let mut exchanges = self.exchanges.lock().unwrap();
+        // A random alias, so that nothing links the participant back to the user
+        let alias = Uuid::new_v4();
+        for exchange in exchanges.values_mut() {
+            exchange.anonymise(user_id, alias);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_no_changes_after_draw() {
+        let repository = InMemoryExchangeRepository::new();
+        let exchange = repository
+            .save(&CreateExchangeRequest::new(Uuid::now_v7(), "Office".into()))
+            .await
+            .unwrap();
+        let mut exchange = exchange;
+        for _ in 0..3 {
+            exchange = repository
+                .add_participant(exchange.id(), Participant::new(Uuid::now_v7(), None))
+                .await
+                .unwrap();
+        }
+        let stale = exchange.draw_from(1, Utc::now()).unwrap();
+        repository
+            .add_participant(exchange.id(), Participant::new(Uuid::now_v7(), None))
+            .await
+            .unwrap();
+        assert!(matches!(
+            repository.save_draw(exchange.id(), stale).await,
+            Err(UpdateExchangeError::Unkown(_))
+        ));
+
+        let exchange = repository
+            .find_exchange_by_id(exchange.id())
+            .await
+            .unwrap()
+            .unwrap();
+        let draw = exchange.draw_from(1, Utc::now()).unwrap();
+        repository
+            .save_draw(exchange.id(), draw.clone())
+            .await
+            .unwrap();
+        assert!(matches!(
+            repository.save_draw(exchange.id(), draw).await,
+            Err(UpdateExchangeError::AlreadyDrawn { .. })
+        ));
+        assert!(matches!(
+            repository
+                .add_participant(exchange.id(), Participant::new(Uuid::now_v7(), None))
+                .await,
+            Err(UpdateExchangeError::AlreadyDrawn { .. })
+        ));
+    }
+}
@@ -0,0 +1,241 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    AnswerFollowRequestError, Block, BlockUserError, Cursor, FindFollowError, Follow,
+    FollowRepository, FollowSettings, FollowStatus, FollowUserError, ForgetUserError,
+    ListFollowRequestsError, Page, PageRequest, SetFollowSettingsError, SortDirection, SortValue,
+    UnblockUserError, UnfollowUserError,
+};
+
+/// The [InMemoryFollowRepository] struct is an in-memory implementation of the
+/// [FollowRepository] trait. Follows and blocks are keyed by `(follower, followee)` and
+/// `(blocker, blocked)`, and the follows are always locked before the blocks.
+pub struct InMemoryFollowRepository {
+    follows: Mutex<HashMap<(Uuid, Uuid), Follow>>,
+    blocks: Mutex<HashMap<(Uuid, Uuid), Block>>,
+    settings: Mutex<HashMap<Uuid, FollowSettings>>,
+}
+
+impl InMemoryFollowRepository {
+    pub fn new() -> Self {
+        Self {
+            follows: Mutex::new(HashMap::new()),
+            blocks: Mutex::new(HashMap::new()),
+            settings: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryFollowRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FollowRepository for InMemoryFollowRepository {
+    async fn save_follow(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+        status: FollowStatus,
+        created_at: DateTime<Utc>,
+    ) -> Result<Follow, FollowUserError> {
+        let mut follows = self.follows.lock().unwrap();
+        let blocks = self.blocks.lock().unwrap();
+        if blocks.contains_key(&(follower_id, followee_id))
+            || blocks.contains_key(&(followee_id, follower_id))
+        {
+            return Err(FollowUserError::Blocked { id: followee_id });
+        }
+        let follow = follows
+            .entry((follower_id, followee_id))
+            .or_insert_with(|| Follow::new(follower_id, followee_id, status, created_at));
+        Ok(*follow)
+    }
+
+    async fn delete_follow(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> Result<Follow, UnfollowUserError> {
+        self.follows
+            .lock()
+            .unwrap()
+            .remove(&(follower_id, followee_id))
+            .ok_or(UnfollowUserError::NotFollowing { id: followee_id })
+    }
+
+    async fn answer_follow(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+        accept: bool,
+    ) -> Result<Follow, AnswerFollowRequestError> {
+        let mut follows = self.follows.lock().unwrap();
+        let key = (follower_id, followee_id);
+        let follow = follows
+            .get_mut(&key)
+            .filter(|follow| follow.status() == FollowStatus::Pending)
+            .ok_or(AnswerFollowRequestError::RequestDoesNotExist { id: follower_id })?;
+        if accept {
+            follow.set_status(FollowStatus::Accepted);
+            return Ok(*follow);
+        }
+        let mut declined = follows.remove(&key).unwrap();
+        declined.set_status(FollowStatus::Declined);
+        Ok(declined)
+    }
+
+    async fn find_pending_follows(
+        &self,
+        followee_id: Uuid,
+        page: &PageRequest,
+    ) -> Result<Page<Follow>, ListFollowRequestsError> {
+        let pending: Vec<Follow> = self
+            .follows
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|follow| {
+                follow.followee_id() == followee_id && follow.status() == FollowStatus::Pending
+            })
+            .copied()
+            .collect();
+        Ok(page.paginate(pending, SortDirection::Ascending, |follow| {
+            Cursor::new(
+                Some(SortValue::Time(follow.created_at())),
+                follow.follower_id(),
+            )
+        }))
+    }
+
+    async fn find_followees(&self, follower_id: Uuid) -> Result<Vec<Uuid>, FindFollowError> {
+        let mut followees: Vec<Uuid> = self
+            .follows
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|follow| {
+                follow.follower_id() == follower_id && follow.status() == FollowStatus::Accepted
+            })
+            .map(Follow::followee_id)
+            .collect();
+        followees.sort();
+        Ok(followees)
+    }
+
+    async fn save_block(
+        &self,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+        created_at: DateTime<Utc>,
+    ) -> Result<Block, BlockUserError> {
+        let mut follows = self.follows.lock().unwrap();
+        let mut blocks = self.blocks.lock().unwrap();
+        follows.remove(&(blocker_id, blocked_id));
+        follows.remove(&(blocked_id, blocker_id));
+        let block = blocks
+            .entry((blocker_id, blocked_id))
+            .or_insert_with(|| Block::new(blocker_id, blocked_id, created_at));
+        Ok(*block)
+    }
+
+    async fn delete_block(
+        &self,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+    ) -> Result<Block, UnblockUserError> {
+        self.blocks
+            .lock()
+            .unwrap()
+            .remove(&(blocker_id, blocked_id))
+            .ok_or(UnblockUserError::NotBlocked { id: blocked_id })
+    }
+
+    async fn find_block(
+        &self,
+        blocker_id: Uuid,
+        blocked_id: Uuid,
+    ) -> Result<Option<Block>, FindFollowError> {
+        Ok(self
+            .blocks
+            .lock()
+            .unwrap()
+            .get(&(blocker_id, blocked_id))
+            .copied())
+    }
+
+    async fn find_settings(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<FollowSettings>, FindFollowError> {
+        Ok(self.settings.lock().unwrap().get(&user_id).copied())
+    }
+
+    async fn save_settings(
+        &self,
+        settings: &FollowSettings,
+    ) -> Result<FollowSettings, SetFollowSettingsError> {
+        self.settings
+            .lock()
+            .unwrap()
+            .insert(settings.user_id(), *settings);
+        Ok(*settings)
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let mut follows = self.follows.lock().unwrap();
+        follows.retain(|(follower_id, followee_id), _| {
+            *follower_id != user_id && *followee_id != user_id
+        });
+        let mut blocks = self.blocks.lock().unwrap();
+        blocks
+            .retain(|(blocker_id, blocked_id), _| *blocker_id != user_id && *blocked_id != user_id);
+        self.settings.lock().unwrap().remove(&user_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_block_ends_follows() {
+        let repository = InMemoryFollowRepository::new();
+        let (alice, bob) = (Uuid::now_v7(), Uuid::now_v7());
+        for (follower, followee) in [(alice, bob), (bob, alice)] {
+            repository
+                .save_follow(follower, followee, FollowStatus::Accepted, Utc::now())
+                .await
+                .unwrap();
+        }
+        assert_eq!(repository.find_followees(alice).await.unwrap(), vec![bob]);
+
+        repository.save_block(alice, bob, Utc::now()).await.unwrap();
+        assert!(repository.find_followees(alice).await.unwrap().is_empty());
+        assert!(repository.find_followees(bob).await.unwrap().is_empty());
+        assert!(matches!(
+            repository
+                .save_follow(bob, alice, FollowStatus::Accepted, Utc::now())
+                .await,
+            Err(FollowUserError::Blocked { .. })
+        ));
+
+        repository.delete_block(alice, bob).await.unwrap();
+        let follow = repository
+            .save_follow(bob, alice, FollowStatus::Pending, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(follow.status(), FollowStatus::Pending);
+        let declined = repository.answer_follow(bob, alice, false).await.unwrap();
+        assert_eq!(declined.status(), FollowStatus::Declined);
+        assert!(matches!(
+            repository.answer_follow(bob, alice, true).await,
+            Err(AnswerFollowRequestError::RequestDoesNotExist { .. })
+        ));
+    }
+}
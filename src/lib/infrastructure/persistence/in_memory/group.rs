@@ -0,0 +1,281 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    CreateGroupError, CreateGroupRequest, Cursor, FindGroupError, ForgetUserError, Group,
+    GroupInvitation, GroupMember, GroupRepository, GroupRole, InvitationStatus, InviteMemberError,
+    ListInvitationsError, Page, PageRequest, RespondToInvitationError, SharedWishlist,
+    SortDirection, SortValue, UpdateGroupError,
+};
+
+/// The [InMemoryGroupRepository] struct is an in-memory implementation of the
+/// [GroupRepository] trait. The groups are always locked before the invitations.
+pub struct InMemoryGroupRepository {
+    groups: Mutex<HashMap<Uuid, Group>>,
+    invitations: Mutex<HashMap<Uuid, GroupInvitation>>,
+}
+
+impl InMemoryGroupRepository {
+    pub fn new() -> Self {
+        Self {
+            groups: Mutex::new(HashMap::new()),
+            invitations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Applies `update` to a group.
+    fn update(
+        &self,
+        group_id: Uuid,
+        update: impl FnOnce(&mut Group) -> Result<(), UpdateGroupError>,
+    ) -> Result<Group, UpdateGroupError> {
+        let mut groups = self.groups.lock().unwrap();
+        let group = groups
+            .get_mut(&group_id)
+            .ok_or(UpdateGroupError::GroupDoesNotExist { id: group_id })?;
+        update(group)?;
+        Ok(group.clone())
+    }
+}
+
+impl Default for InMemoryGroupRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GroupRepository for InMemoryGroupRepository {
+    async fn save(&self, req: &CreateGroupRequest) -> Result<Group, CreateGroupError> {
+        let group = Group::new(Uuid::now_v7(), req.name().clone(), req.user_id());
+        self.groups
+            .lock()
+            .unwrap()
+            .insert(group.id(), group.clone());
+        Ok(group)
+    }
+
+    async fn find_group_by_id(&self, group_id: Uuid) -> Result<Option<Group>, FindGroupError> {
+        Ok(self.groups.lock().unwrap().get(&group_id).cloned())
+    }
+
+    async fn find_groups_by_member(&self, user_id: Uuid) -> Result<Vec<Group>, FindGroupError> {
+        let mut groups: Vec<Group> = self
+            .groups
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|group| group.is_member(user_id))
+            .cloned()
+            .collect();
+        groups.sort_by_key(Group::id);
+        Ok(groups)
+    }
+
+    async fn remove_member(
+        &self,
+        group_id: Uuid,
+        member_id: Uuid,
+    ) -> Result<Group, UpdateGroupError> {
+        self.update(group_id, |group| Ok(group.remove_member(member_id)?))
+    }
+
+    async fn set_member_role(
+        &self,
+        group_id: Uuid,
+        member_id: Uuid,
+        role: GroupRole,
+    ) -> Result<Group, UpdateGroupError> {
+        self.update(group_id, |group| Ok(group.set_role(member_id, role)?))
+    }
+
+    async fn share_wishlist(
+        &self,
+        group_id: Uuid,
+        shared: SharedWishlist,
+    ) -> Result<Group, UpdateGroupError> {
+        self.update(group_id, |group| {
+            group.share(shared);
+            Ok(())
+        })
+    }
+
+    async fn unshare_wishlist(
+        &self,
+        group_id: Uuid,
+        wishlist_id: Uuid,
+    ) -> Result<Group, UpdateGroupError> {
+        self.update(group_id, |group| {
+            group.unshare(wishlist_id);
+            Ok(())
+        })
+    }
+
+    async fn save_invitation(
+        &self,
+        group_id: Uuid,
+        invitee_id: Uuid,
+        invited_by: Uuid,
+        created_at: DateTime<Utc>,
+    ) -> Result<GroupInvitation, InviteMemberError> {
+        let groups = self.groups.lock().unwrap();
+        let group = groups
+            .get(&group_id)
+            .ok_or(InviteMemberError::GroupDoesNotExist { id: group_id })?;
+        if group.is_member(invitee_id) {
+            return Err(InviteMemberError::AlreadyMember { id: invitee_id });
+        }
+        let mut invitations = self.invitations.lock().unwrap();
+        if let Some(pending) = invitations.values().find(|invitation| {
+            invitation.group_id() == group_id
+                && invitation.invitee_id() == invitee_id
+                && invitation.status() == InvitationStatus::Pending
+        }) {
+            return Ok(pending.clone());
+        }
+        let invitation =
+            GroupInvitation::new(Uuid::now_v7(), group_id, invitee_id, invited_by, created_at);
+        invitations.insert(invitation.id(), invitation.clone());
+        Ok(invitation)
+    }
+
+    async fn find_pending_invitations(
+        &self,
+        user_id: Uuid,
+        page: &PageRequest,
+    ) -> Result<Page<GroupInvitation>, ListInvitationsError> {
+        let invitations: Vec<GroupInvitation> = self
+            .invitations
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|invitation| {
+                invitation.invitee_id() == user_id
+                    && invitation.status() == InvitationStatus::Pending
+            })
+            .cloned()
+            .collect();
+        Ok(
+            page.paginate(invitations, SortDirection::Ascending, |invitation| {
+                Cursor::new(
+                    Some(SortValue::Time(invitation.created_at())),
+                    invitation.id(),
+                )
+            }),
+        )
+    }
+
+    async fn answer_invitation(
+        &self,
+        invitation_id: Uuid,
+        user_id: Uuid,
+        accept: bool,
+    ) -> Result<GroupInvitation, RespondToInvitationError> {
+        let mut groups = self.groups.lock().unwrap();
+        let mut invitations = self.invitations.lock().unwrap();
+        let invitation = invitations
+            .get_mut(&invitation_id)
+            .filter(|invitation| invitation.invitee_id() == user_id)
+            .ok_or(RespondToInvitationError::InvitationDoesNotExist { id: invitation_id })?;
+        if invitation.status() != InvitationStatus::Pending {
+            return Err(RespondToInvitationError::AlreadyAnswered { id: invitation_id });
+        }
+        let group = groups.get_mut(&invitation.group_id()).ok_or(
+            RespondToInvitationError::GroupDoesNotExist {
+                id: invitation.group_id(),
+            },
+        )?;
+        if accept {
+            group.add_member(GroupMember::new(user_id, GroupRole::Member));
+            invitation.set_status(InvitationStatus::Accepted);
+        } else {
+            invitation.set_status(InvitationStatus::Declined);
+        }
+        Ok(invitation.clone())
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let mut groups = self.groups.lock().unwrap();
+        for group in groups.values_mut() {
+            group.forget(user_id);
+        }
+        groups.retain(|_, group| !group.members().is_empty());
+        let mut invitations = self.invitations.lock().unwrap();
+        invitations.retain(|_, invitation| {
+            invitation.invitee_id() != user_id
+                && invitation.invited_by() != user_id
+                && groups.contains_key(&invitation.group_id())
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::GroupMembershipError;
+
+    #[tokio::test]
+    async fn test_invitation_lifecycle() {
+        let repository = InMemoryGroupRepository::new();
+        let (admin, invitee) = (Uuid::now_v7(), Uuid::now_v7());
+        let group = repository
+            .save(&CreateGroupRequest::new(admin, "Family".into()))
+            .await
+            .unwrap();
+        let invitation = repository
+            .save_invitation(group.id(), invitee, admin, Utc::now())
+            .await
+            .unwrap();
+        let again = repository
+            .save_invitation(group.id(), invitee, admin, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(again, invitation);
+        assert!(matches!(
+            repository
+                .answer_invitation(invitation.id(), admin, true)
+                .await,
+            Err(RespondToInvitationError::InvitationDoesNotExist { .. })
+        ));
+
+        let accepted = repository
+            .answer_invitation(invitation.id(), invitee, true)
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), InvitationStatus::Accepted);
+        assert!(matches!(
+            repository
+                .answer_invitation(invitation.id(), invitee, false)
+                .await,
+            Err(RespondToInvitationError::AlreadyAnswered { .. })
+        ));
+        assert!(repository
+            .find_pending_invitations(invitee, &PageRequest::default())
+            .await
+            .unwrap()
+            .entries()
+            .is_empty());
+        assert_eq!(
+            repository
+                .find_groups_by_member(invitee)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(matches!(
+            repository
+                .save_invitation(group.id(), invitee, admin, Utc::now())
+                .await,
+            Err(InviteMemberError::AlreadyMember { .. })
+        ));
+        assert!(matches!(
+            repository.remove_member(group.id(), admin).await,
+            Err(UpdateGroupError::Membership(
+                GroupMembershipError::LastAdmin
+            ))
+        ));
+    }
+}
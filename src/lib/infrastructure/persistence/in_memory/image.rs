@@ -0,0 +1,53 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use uuid::Uuid;
+
+use crate::domain::{FindImageOwnerError, ForgetUserError, ImageRepository, SaveImageOwnerError};
+
+/// The [InMemoryImageRepository] struct is an in-memory implementation of the [ImageRepository]
+/// trait, mapping each image to the user who stored it.
+pub struct InMemoryImageRepository {
+    owners: Mutex<HashMap<Uuid, Uuid>>,
+}
+
+impl InMemoryImageRepository {
+    pub fn new() -> Self {
+        Self {
+            owners: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryImageRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImageRepository for InMemoryImageRepository {
+    async fn save_owner(&self, image_id: Uuid, owner_id: Uuid) -> Result<(), SaveImageOwnerError> {
+        self.owners.lock().unwrap().insert(image_id, owner_id);
+        Ok(())
+    }
+
+    async fn find_images_by_owner(&self, owner_id: Uuid) -> Result<Vec<Uuid>, FindImageOwnerError> {
+        let mut images: Vec<Uuid> = self
+            .owners
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, owner)| **owner == owner_id)
+            .map(|(image_id, _)| *image_id)
+            .collect();
+        images.sort();
+        Ok(images)
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        self.owners
+            .lock()
+            .unwrap()
+            .retain(|_, owner| *owner != user_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    Cursor, ForgetUserError, InboxMessage, InboxRepository, ListInboxError, ListInboxRequest,
+    Notification, Page, SaveInboxMessageError, SortDirection, SortValue,
+};
+
+/// The [InMemoryInboxRepository] struct is an in-memory implementation of the [InboxRepository]
+/// trait.
+pub struct InMemoryInboxRepository {
+    messages: Mutex<Vec<InboxMessage>>,
+}
+
+impl InMemoryInboxRepository {
+    pub fn new() -> Self {
+        Self {
+            messages: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for InMemoryInboxRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InboxRepository for InMemoryInboxRepository {
+    async fn save(
+        &self,
+        notification: &Notification,
+        created_at: DateTime<Utc>,
+    ) -> Result<InboxMessage, SaveInboxMessageError> {
+        let mut messages = self.messages.lock().unwrap();
+        let message = InboxMessage::new(Uuid::now_v7(), notification.clone(), created_at);
+        messages.push(message.clone());
+        Ok(message)
+    }
+
+    async fn find_messages(
+        &self,
+        req: &ListInboxRequest,
+    ) -> Result<Page<InboxMessage>, ListInboxError> {
+        let messages = self.messages.lock().unwrap();
+        let messages = messages
+            .iter()
+            .filter(|message| message.recipient_id() == req.user_id())
+            .cloned();
+        Ok(req
+            .page()
+            .paginate(messages, SortDirection::Descending, |message| {
+                Cursor::new(Some(SortValue::Time(message.created_at())), message.id())
+            }))
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let mut messages = self.messages.lock().unwrap();
+        messages.retain(|message| message.recipient_id() != user_id);
+        Ok(())
+    }
+}
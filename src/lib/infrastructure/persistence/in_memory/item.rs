@@ -3,10 +3,13 @@ use std::{collections::HashMap, sync::Mutex};
 use uuid::Uuid;
 
 use crate::domain::{
-    CreateItemError, CreateItemRequest, FindItemByIdError, FindItemByIdRequest, Item,
-    ItemRepository,
+    CreateItemError, CreateItemRequest, DeleteItemError, FindItemByIdError, FindItemByIdRequest,
+    FindItemsError, ForgetUserError, Item, ItemRepository, ListItemsRequest, Page,
+    ReserveItemError, TransferItemError, UpdateItemError, Wishlist,
 };
 
+/// The [InMemoryItemRepository] struct is an in-memory implementation of the [ItemRepository]
+/// trait.
 pub struct InMemoryItemRepository {
     items: Mutex<HashMap<Uuid, Item>>,
 }
@@ -19,14 +22,378 @@ impl InMemoryItemRepository {
     }
 }
 
+impl Default for InMemoryItemRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ItemRepository for InMemoryItemRepository {
-    async fn save(&self, _req: &CreateItemRequest) -> Result<Item, CreateItemError> {
-        todo!()
+    async fn save(&self, req: &CreateItemRequest) -> Result<Item, CreateItemError> {
+        let title = req.title().cloned().ok_or(CreateItemError::MissingTitle)?;
+        let mut items = self.items.lock().unwrap();
+        if items.values().any(|item| {
+            item.wishlist_id() == req.wishlist_id() && item.link_url() == req.link_url()
+        }) {
+            return Err(CreateItemError::Duplicate);
+        }
+        let id = Uuid::now_v7();
+        let mut item = Item::create(
+            id,
+            req.wishlist_id(),
+            title,
+            req.link_url().clone(),
+            req.image_url().cloned(),
+            req.price().cloned(),
+        );
+        item.set_image_id(req.image_id());
+        item.set_priority(req.priority());
+        item.set_notes(req.notes().cloned());
+        item.set_attributes(req.attributes().clone());
+        items.insert(id, item.clone());
+        Ok(item)
     }
+
     async fn find_item_by_id(
         &self,
-        _req: &FindItemByIdRequest,
+        req: &FindItemByIdRequest,
     ) -> Result<Option<Item>, FindItemByIdError> {
-        todo!()
+        let items = self.items.lock().unwrap();
+        Ok(items.get(req.id()).cloned())
+    }
+
+    async fn find_items(&self) -> Result<Vec<Item>, FindItemsError> {
+        let items = self.items.lock().unwrap();
+        Ok(items.values().cloned().collect())
+    }
+
+    async fn list_items(
+        &self,
+        req: &ListItemsRequest,
+        wishlist: &Wishlist,
+    ) -> Result<Page<Item>, FindItemsError> {
+        let items = self.items.lock().unwrap();
+        let sort = req.sort();
+        Ok(req.page().paginate(
+            items.values().filter(|item| req.matches(item)).cloned(),
+            sort.direction(),
+            |item| sort.position(item, wishlist),
+        ))
+    }
+
+    async fn find_items_reserved_by(&self, user_id: Uuid) -> Result<Vec<Item>, FindItemsError> {
+        let items = self.items.lock().unwrap();
+        Ok(items
+            .values()
+            .filter(|item| item.reserved_by() == Some(user_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_items_by_wishlists(
+        &self,
+        wishlist_ids: &[Uuid],
+    ) -> Result<Vec<Item>, FindItemsError> {
+        let items = self.items.lock().unwrap();
+        Ok(items
+            .values()
+            .filter(|item| wishlist_ids.contains(&item.wishlist_id()))
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, item: &Item) -> Result<Item, UpdateItemError> {
+        let mut items = self.items.lock().unwrap();
+        let stored = items
+            .get_mut(&item.id())
+            .ok_or(UpdateItemError::ItemDoesNotExist { id: item.id() })?;
+        stored.check_version(Some(item.version()))?;
+        let mut updated = item.clone();
+        updated.set_reserved_by(stored.reserved_by());
+        updated.next_version();
+        *stored = updated.clone();
+        Ok(updated)
+    }
+
+    async fn move_item(&self, item_id: Uuid, wishlist_id: Uuid) -> Result<Item, TransferItemError> {
+        let mut items = self.items.lock().unwrap();
+        let link_url = items
+            .get(&item_id)
+            .ok_or(TransferItemError::ItemDoesNotExist { id: item_id })?
+            .link_url()
+            .clone();
+        if items.values().any(|item| {
+            item.id() != item_id
+                && item.wishlist_id() == wishlist_id
+                && item.link_url() == &link_url
+        }) {
+            return Err(TransferItemError::Duplicate);
+        }
+        let item = items.get_mut(&item_id).unwrap();
+        item.set_wishlist_id(wishlist_id);
+        item.next_version();
+        Ok(item.clone())
+    }
+
+    async fn reserve_item(
+        &self,
+        item_id: Uuid,
+        user_id: Uuid,
+        reserved: bool,
+    ) -> Result<Item, ReserveItemError> {
+        let mut items = self.items.lock().unwrap();
+        let item = items
+            .get_mut(&item_id)
+            .ok_or(ReserveItemError::ItemDoesNotExist { id: item_id })?;
+        match (item.reserved_by(), reserved) {
+            (Some(reserver), true) if reserver != user_id => {
+                return Err(ReserveItemError::AlreadyReserved { id: item_id })
+            }
+            (reserver, false) if reserver != Some(user_id) => {
+                return Err(ReserveItemError::NotReserver { id: item_id })
+            }
+            _ => item.set_reserved_by(reserved.then_some(user_id)),
+        }
+        Ok(item.clone())
+    }
+
+    async fn delete(&self, item_id: Uuid) -> Result<(), DeleteItemError> {
+        let mut items = self.items.lock().unwrap();
+        items
+            .remove(&item_id)
+            .ok_or(DeleteItemError::ItemDoesNotExist { id: item_id })?;
+        Ok(())
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let mut items = self.items.lock().unwrap();
+        for item in items.values_mut() {
+            if item.reserved_by() == Some(user_id) {
+                item.set_reserved_by(None);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        ItemAttribute, ItemAttributes, ItemFilter, ItemPriority, ItemSort, ItemSortKey, PageLimit,
+        PageRequest, SortDirection, VersionMismatchError,
+    };
+
+    fn request(wishlist_id: Uuid) -> CreateItemRequest {
+        CreateItemRequest::new(
+            wishlist_id,
+            Uuid::now_v7(),
+            Some("Book".into()),
+            "https://shop.example/book".into(),
+            None,
+            Some(12.5.into()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_and_find_item() {
+        let repository = InMemoryItemRepository::new();
+        let item = repository.save(&request(Uuid::now_v7())).await.unwrap();
+
+        let found = repository
+            .find_item_by_id(&FindItemByIdRequest::new(item.id()))
+            .await
+            .unwrap();
+        assert_eq!(found, Some(item));
+    }
+
+    #[tokio::test]
+    async fn test_create_duplicate_item() {
+        let repository = InMemoryItemRepository::new();
+        let wishlist_id = Uuid::now_v7();
+        repository.save(&request(wishlist_id)).await.unwrap();
+
+        let result = repository.save(&request(wishlist_id)).await;
+        assert!(matches!(result, Err(CreateItemError::Duplicate)));
+
+        let result = repository.save(&request(Uuid::now_v7())).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_move_item() {
+        let repository = InMemoryItemRepository::new();
+        let (source, target) = (Uuid::now_v7(), Uuid::now_v7());
+        let item = repository.save(&request(source)).await.unwrap();
+
+        let moved = repository.move_item(item.id(), target).await.unwrap();
+        assert_eq!(moved.wishlist_id(), target);
+        assert_eq!(moved.id(), item.id());
+        assert_eq!(moved.version(), item.version() + 1);
+
+        repository.save(&request(source)).await.unwrap();
+        let result = repository.move_item(item.id(), source).await;
+        assert!(matches!(result, Err(TransferItemError::Duplicate)));
+        let result = repository.move_item(Uuid::now_v7(), source).await;
+        assert!(matches!(
+            result,
+            Err(TransferItemError::ItemDoesNotExist { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_item() {
+        let repository = InMemoryItemRepository::new();
+        let mut item = repository.save(&request(Uuid::now_v7())).await.unwrap();
+        item.set_price(Some(9.5.into()));
+
+        let updated = repository.update(&item).await.unwrap();
+        assert_eq!(updated.version(), item.version() + 1);
+        assert_eq!(updated.price(), item.price());
+        let items = repository.find_items().await.unwrap();
+        assert_eq!(items, vec![updated.clone()]);
+
+        let result = repository.update(&item).await;
+        assert!(matches!(
+            result,
+            Err(UpdateItemError::VersionMismatch(VersionMismatchError {
+                expected: 1,
+                actual: 2,
+                ..
+            }))
+        ));
+
+        let other = Item::create(
+            Uuid::now_v7(),
+            item.wishlist_id(),
+            "Other".into(),
+            "https://shop.example/other".into(),
+            None,
+            None,
+        );
+        let result = repository.update(&other).await;
+        assert!(matches!(
+            result,
+            Err(UpdateItemError::ItemDoesNotExist { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_persist_details_and_list_items() {
+        let repository = InMemoryItemRepository::new();
+        let wishlist_id = Uuid::now_v7();
+        let scarf = repository
+            .save(
+                &CreateItemRequest::new(
+                    wishlist_id,
+                    Uuid::now_v7(),
+                    Some("Scarf".into()),
+                    "https://shop.example/scarf".into(),
+                    None,
+                    None,
+                )
+                .with_priority(ItemPriority::MustHave)
+                .with_notes(Some("Navy, not black".into()))
+                .with_attributes(
+                    ItemAttributes::new(vec![ItemAttribute::new("color", "navy").unwrap()])
+                        .unwrap(),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(scarf.priority(), ItemPriority::MustHave);
+        assert_eq!(scarf.notes(), Some(&"Navy, not black".into()));
+        assert_eq!(scarf.attributes().get("color"), Some("navy"));
+        let book = repository.save(&request(wishlist_id)).await.unwrap();
+        repository.save(&request(Uuid::now_v7())).await.unwrap();
+
+        let wishlist = Wishlist::new(
+            wishlist_id,
+            Uuid::now_v7(),
+            "Winter".into(),
+            "Winter".into(),
+            false,
+        );
+        let list = |filter: ItemFilter, sort: ItemSort| {
+            let req = ListItemsRequest::new(wishlist_id, filter, sort, PageRequest::default());
+            let (repository, wishlist) = (&repository, &wishlist);
+            async move {
+                let page = repository.list_items(&req, wishlist).await.unwrap();
+                page.entries().to_vec()
+            }
+        };
+        let items = list(ItemFilter::default(), ItemSort::default()).await;
+        assert_eq!(items, vec![scarf.clone(), book.clone()]);
+        let items = list(
+            ItemFilter::default(),
+            ItemSort::new(ItemSortKey::Title, SortDirection::Ascending),
+        )
+        .await;
+        assert_eq!(items, vec![book.clone(), scarf.clone()]);
+        let items = list(
+            ItemFilter::default().with_attribute(Some(("color".into(), "navy".into()))),
+            ItemSort::default(),
+        )
+        .await;
+        assert_eq!(items, vec![scarf.clone()]);
+        let req = ListItemsRequest::new(
+            wishlist_id,
+            ItemFilter::default(),
+            ItemSort::default(),
+            PageRequest::new(None, PageLimit::new(1).unwrap()),
+        );
+        let first = repository.list_items(&req, &wishlist).await.unwrap();
+        assert_eq!(first.entries(), [scarf]);
+        let req = ListItemsRequest::new(
+            wishlist_id,
+            ItemFilter::default(),
+            ItemSort::default(),
+            PageRequest::new(first.next_cursor().cloned(), PageLimit::new(1).unwrap()),
+        );
+        let second = repository.list_items(&req, &wishlist).await.unwrap();
+        assert_eq!(second.entries(), [book]);
+        assert!(second.next_cursor().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reserve_item() {
+        let repository = InMemoryItemRepository::new();
+        let item = repository.save(&request(Uuid::now_v7())).await.unwrap();
+        let (alice, bob) = (Uuid::now_v7(), Uuid::now_v7());
+
+        let reserved = repository
+            .reserve_item(item.id(), alice, true)
+            .await
+            .unwrap();
+        assert_eq!(reserved.reserved_by(), Some(alice));
+        assert_eq!(reserved.version(), item.version());
+        let again = repository
+            .reserve_item(item.id(), alice, true)
+            .await
+            .unwrap();
+        assert_eq!(again.reserved_by(), Some(alice));
+        assert!(matches!(
+            repository.reserve_item(item.id(), bob, true).await,
+            Err(ReserveItemError::AlreadyReserved { .. })
+        ));
+        assert!(matches!(
+            repository.reserve_item(item.id(), bob, false).await,
+            Err(ReserveItemError::NotReserver { .. })
+        ));
+        assert_eq!(
+            repository.find_items_reserved_by(alice).await.unwrap(),
+            vec![reserved]
+        );
+
+        let cancelled = repository
+            .reserve_item(item.id(), alice, false)
+            .await
+            .unwrap();
+        assert_eq!(cancelled.reserved_by(), None);
+        assert!(repository
+            .find_items_reserved_by(alice)
+            .await
+            .unwrap()
+            .is_empty());
     }
 }
@@ -0,0 +1,132 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use uuid::Uuid;
+
+use crate::domain::{
+    FindPriceHistoryError, FindPriceHistoryRequest, FindPriceWatchesError, ForgetUserError,
+    PriceHistoryRepository, PricePoint, PriceWatch, RecordPriceError, WatchItemPriceError,
+    WatchItemPriceRequest,
+};
+
+/// The [InMemoryPriceHistoryRepository] struct is an in-memory implementation of the
+/// [PriceHistoryRepository] trait.
+pub struct InMemoryPriceHistoryRepository {
+    history: Mutex<HashMap<Uuid, Vec<PricePoint>>>,
+    watches: Mutex<HashMap<Uuid, PriceWatch>>,
+}
+
+impl InMemoryPriceHistoryRepository {
+    pub fn new() -> Self {
+        Self {
+            history: Mutex::new(HashMap::new()),
+            watches: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryPriceHistoryRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PriceHistoryRepository for InMemoryPriceHistoryRepository {
+    async fn record_price(
+        &self,
+        item_id: Uuid,
+        point: &PricePoint,
+    ) -> Result<(), RecordPriceError> {
+        let mut history = self.history.lock().unwrap();
+        history.entry(item_id).or_default().push(point.clone());
+        Ok(())
+    }
+
+    async fn find_price_history(
+        &self,
+        req: &FindPriceHistoryRequest,
+    ) -> Result<Vec<PricePoint>, FindPriceHistoryError> {
+        let history = self.history.lock().unwrap();
+        Ok(history.get(&req.item_id()).cloned().unwrap_or_default())
+    }
+
+    async fn save_price_watch(
+        &self,
+        req: &WatchItemPriceRequest,
+    ) -> Result<PriceWatch, WatchItemPriceError> {
+        let mut watches = self.watches.lock().unwrap();
+        let id = Uuid::now_v7();
+        let watch = PriceWatch::new(
+            id,
+            req.item_id(),
+            req.user_id(),
+            req.drop_percent(),
+            req.target_price().cloned(),
+        );
+        watches.insert(id, watch.clone());
+        Ok(watch)
+    }
+
+    async fn find_price_watches(
+        &self,
+        item_id: Uuid,
+    ) -> Result<Vec<PriceWatch>, FindPriceWatchesError> {
+        let watches = self.watches.lock().unwrap();
+        Ok(watches
+            .values()
+            .filter(|watch| watch.item_id() == item_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let mut watches = self.watches.lock().unwrap();
+        watches.retain(|_, watch| watch.user_id() != user_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_and_find_price_history() {
+        let repository = InMemoryPriceHistoryRepository::new();
+        let item_id = Uuid::now_v7();
+        let first = PricePoint::new(10.0.into(), Utc::now());
+        let second = PricePoint::new(8.0.into(), Utc::now());
+
+        repository.record_price(item_id, &first).await.unwrap();
+        repository.record_price(item_id, &second).await.unwrap();
+
+        let history = repository
+            .find_price_history(&FindPriceHistoryRequest::new(item_id))
+            .await
+            .unwrap();
+        assert_eq!(history, vec![first, second]);
+
+        let history = repository
+            .find_price_history(&FindPriceHistoryRequest::new(Uuid::now_v7()))
+            .await
+            .unwrap();
+        assert!(history.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_save_and_find_price_watches() {
+        let repository = InMemoryPriceHistoryRepository::new();
+        let item_id = Uuid::now_v7();
+        let req = WatchItemPriceRequest::new(item_id, Uuid::now_v7(), None, Some(5.0.into()));
+
+        let watch = repository.save_price_watch(&req).await.unwrap();
+        let watches = repository.find_price_watches(item_id).await.unwrap();
+        assert_eq!(watches, vec![watch]);
+        assert!(repository
+            .find_price_watches(Uuid::now_v7())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
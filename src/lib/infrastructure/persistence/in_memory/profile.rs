@@ -0,0 +1,82 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use uuid::Uuid;
+
+use crate::domain::{
+    FindProfileRepositoryError, ForgetUserError, Profile, ProfileRepository, SetProfileError,
+};
+
+/// The [InMemoryProfileRepository] struct is an in-memory implementation of the
+/// [ProfileRepository] trait.
+pub struct InMemoryProfileRepository {
+    profiles: Mutex<HashMap<Uuid, Profile>>,
+}
+
+impl InMemoryProfileRepository {
+    pub fn new() -> Self {
+        Self {
+            profiles: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryProfileRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProfileRepository for InMemoryProfileRepository {
+    async fn save_profile(&self, profile: &Profile) -> Result<Profile, SetProfileError> {
+        let mut profiles = self.profiles.lock().unwrap();
+        profiles.insert(profile.user_id(), profile.clone());
+        Ok(profile.clone())
+    }
+
+    async fn find_profile(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<Profile>, FindProfileRepositoryError> {
+        let profiles = self.profiles.lock().unwrap();
+        Ok(profiles.get(&user_id).cloned())
+    }
+
+    async fn find_profiles(
+        &self,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<Profile>, FindProfileRepositoryError> {
+        let profiles = self.profiles.lock().unwrap();
+        Ok(user_ids
+            .iter()
+            .filter_map(|user_id| profiles.get(user_id).cloned())
+            .collect())
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        self.profiles.lock().unwrap().remove(&user_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DisplayName;
+
+    #[tokio::test]
+    async fn test_save_and_replace_profile() {
+        let repository = InMemoryProfileRepository::new();
+        let user_id = Uuid::now_v7();
+        assert_eq!(repository.find_profile(user_id).await.unwrap(), None);
+
+        let profile = Profile::new(user_id).with_birthday(Some("1990-02-28".parse().unwrap()));
+        repository.save_profile(&profile).await.unwrap();
+        let renamed =
+            Profile::new(user_id).with_display_name(Some(DisplayName::new("Alice").unwrap()));
+        repository.save_profile(&renamed).await.unwrap();
+        assert_eq!(
+            repository.find_profile(user_id).await.unwrap(),
+            Some(renamed)
+        );
+    }
+}
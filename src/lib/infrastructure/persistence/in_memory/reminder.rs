@@ -0,0 +1,161 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::domain::{
+    FindReminderPreferencesError, FindRemindersError, ForgetUserError, MarkReminderSentError,
+    ReminderPreferences, ReminderRepository, ReminderSubscription, SetReminderPreferencesError,
+    SubscribeReminderError, SubscribeReminderRequest, UnsubscribeReminderError,
+    UnsubscribeReminderRequest,
+};
+
+/// The [InMemoryReminderRepository] struct is an in-memory implementation of the
+/// [ReminderRepository] trait.
+pub struct InMemoryReminderRepository {
+    subscriptions: Mutex<HashMap<Uuid, ReminderSubscription>>,
+    preferences: Mutex<HashMap<Uuid, ReminderPreferences>>,
+}
+
+impl InMemoryReminderRepository {
+    pub fn new() -> Self {
+        Self {
+            subscriptions: Mutex::new(HashMap::new()),
+            preferences: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryReminderRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReminderRepository for InMemoryReminderRepository {
+    async fn save_subscription(
+        &self,
+        req: &SubscribeReminderRequest,
+    ) -> Result<ReminderSubscription, SubscribeReminderError> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if let Some(existing) = subscriptions.values().find(|subscription| {
+            subscription.user_id() == req.user_id()
+                && subscription.wishlist_id() == req.wishlist_id()
+                && subscription.days_before() == req.days_before()
+        }) {
+            return Ok(existing.clone());
+        }
+        let subscription = ReminderSubscription::new(
+            Uuid::now_v7(),
+            req.user_id(),
+            req.wishlist_id(),
+            req.days_before(),
+        );
+        subscriptions.insert(subscription.id(), subscription.clone());
+        Ok(subscription)
+    }
+
+    async fn delete_subscription(
+        &self,
+        req: &UnsubscribeReminderRequest,
+    ) -> Result<ReminderSubscription, UnsubscribeReminderError> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let not_found = UnsubscribeReminderError::ReminderDoesNotExist {
+            id: req.reminder_id(),
+        };
+        match subscriptions.get(&req.reminder_id()) {
+            Some(subscription) if subscription.user_id() == req.user_id() => {}
+            _ => return Err(not_found),
+        }
+        subscriptions.remove(&req.reminder_id()).ok_or(not_found)
+    }
+
+    async fn find_subscriptions(&self) -> Result<Vec<ReminderSubscription>, FindRemindersError> {
+        let subscriptions = self.subscriptions.lock().unwrap();
+        let mut subscriptions: Vec<ReminderSubscription> =
+            subscriptions.values().cloned().collect();
+        subscriptions.sort_by_key(ReminderSubscription::id);
+        Ok(subscriptions)
+    }
+
+    async fn mark_sent(
+        &self,
+        subscription_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<(), MarkReminderSentError> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let subscription = subscriptions.get_mut(&subscription_id).ok_or(
+            MarkReminderSentError::ReminderDoesNotExist {
+                id: subscription_id,
+            },
+        )?;
+        subscription.set_last_sent_for(Some(date));
+        Ok(())
+    }
+
+    async fn save_preferences(
+        &self,
+        preferences: &ReminderPreferences,
+    ) -> Result<ReminderPreferences, SetReminderPreferencesError> {
+        let mut stored = self.preferences.lock().unwrap();
+        stored.insert(preferences.user_id(), preferences.clone());
+        Ok(preferences.clone())
+    }
+
+    async fn find_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<ReminderPreferences>, FindReminderPreferencesError> {
+        let preferences = self.preferences.lock().unwrap();
+        Ok(preferences.get(&user_id).cloned())
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        subscriptions.retain(|_, subscription| subscription.user_id() != user_id);
+        self.preferences.lock().unwrap().remove(&user_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribe_and_unsubscribe() {
+        let repository = InMemoryReminderRepository::new();
+        let (user_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let req = SubscribeReminderRequest::new(user_id, wishlist_id, 7);
+
+        let subscription = repository.save_subscription(&req).await.unwrap();
+        let again = repository.save_subscription(&req).await.unwrap();
+        assert_eq!(again, subscription);
+        repository
+            .mark_sent(subscription.id(), "2027-03-14".parse().unwrap())
+            .await
+            .unwrap();
+        let stored = repository.find_subscriptions().await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(
+            stored[0].last_sent_for(),
+            Some("2027-03-14".parse().unwrap())
+        );
+
+        let result = repository
+            .delete_subscription(&UnsubscribeReminderRequest::new(
+                subscription.id(),
+                Uuid::now_v7(),
+            ))
+            .await;
+        assert!(matches!(
+            result,
+            Err(UnsubscribeReminderError::ReminderDoesNotExist { .. })
+        ));
+        repository
+            .delete_subscription(&UnsubscribeReminderRequest::new(subscription.id(), user_id))
+            .await
+            .unwrap();
+        assert!(repository.find_subscriptions().await.unwrap().is_empty());
+    }
+}
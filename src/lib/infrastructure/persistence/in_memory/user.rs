@@ -2,7 +2,7 @@ use std::{collections::HashMap, sync::Mutex};
 
 use crate::domain::{
     CreateUserError, CreateUserRequest, FindUserByEmailError, FindUserByEmailRequest,
-    FindUserByIdError, FindUserByIdRequest, User, UserRepository,
+    FindUserByIdError, FindUserByIdRequest, ForgetUserError, User, UserRepository,
 };
 use uuid::Uuid;
 
@@ -21,6 +21,12 @@ impl InMemoryUserRepository {
     }
 }
 
+impl Default for InMemoryUserRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl UserRepository for InMemoryUserRepository {
     async fn save(&self, req: &CreateUserRequest) -> Result<User, CreateUserError> {
         let mut users = self.users.lock().unwrap();
@@ -49,9 +55,14 @@ impl UserRepository for InMemoryUserRepository {
         id: &FindUserByIdRequest,
     ) -> Result<Option<User>, FindUserByIdError> {
         let users = self.users.lock().unwrap();
-        let user = users.get(&id.id());
+        let user = users.get(id.id());
         Ok(user.cloned())
     }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        self.users.lock().unwrap().remove(&user_id);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -113,7 +124,7 @@ mod tests {
         let repository = InMemoryUserRepository::new();
 
         let user = repository.save(&req).await.unwrap();
-        let find_req = FindUserByIdRequest::new(user.id().clone());
+        let find_req = FindUserByIdRequest::new(*user.id());
 
         let result = repository.find_user_by_id(&find_req).await;
         assert!(result.is_ok());
@@ -0,0 +1,138 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    Cursor, DeleteWebhookError, FindWebhookDeliveriesError, FindWebhooksError, ForgetUserError,
+    ListWebhookDeliveriesError, ListWebhookDeliveriesRequest, Page, RegisterWebhookError,
+    SaveWebhookDeliveryError, SortDirection, SortValue, Webhook, WebhookDelivery,
+    WebhookRepository, WebhookRequest,
+};
+
+/// The [InMemoryWebhookRepository] struct is an in-memory implementation of the
+/// [WebhookRepository] trait.
+pub struct InMemoryWebhookRepository {
+    webhooks: Mutex<HashMap<Uuid, Webhook>>,
+    deliveries: Mutex<HashMap<Uuid, WebhookDelivery>>,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new() -> Self {
+        Self {
+            webhooks: Mutex::new(HashMap::new()),
+            deliveries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryWebhookRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebhookRepository for InMemoryWebhookRepository {
+    async fn save_webhook(&self, webhook: &Webhook) -> Result<Webhook, RegisterWebhookError> {
+        let mut webhooks = self.webhooks.lock().unwrap();
+        webhooks.insert(webhook.id(), webhook.clone());
+        Ok(webhook.clone())
+    }
+
+    async fn find_webhook_by_id(&self, id: Uuid) -> Result<Option<Webhook>, FindWebhooksError> {
+        let webhooks = self.webhooks.lock().unwrap();
+        Ok(webhooks.get(&id).cloned())
+    }
+
+    async fn find_webhooks_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<Webhook>, FindWebhooksError> {
+        let webhooks = self.webhooks.lock().unwrap();
+        let mut webhooks: Vec<Webhook> = webhooks
+            .values()
+            .filter(|webhook| webhook.user_id() == user_id)
+            .cloned()
+            .collect();
+        webhooks.sort_by_key(Webhook::id);
+        Ok(webhooks)
+    }
+
+    async fn find_webhooks_by_wishlist(
+        &self,
+        wishlist_id: Uuid,
+    ) -> Result<Vec<Webhook>, FindWebhooksError> {
+        let webhooks = self.webhooks.lock().unwrap();
+        let mut webhooks: Vec<Webhook> = webhooks
+            .values()
+            .filter(|webhook| webhook.wishlist_id() == wishlist_id)
+            .cloned()
+            .collect();
+        webhooks.sort_by_key(Webhook::id);
+        Ok(webhooks)
+    }
+
+    async fn delete_webhook(&self, req: &WebhookRequest) -> Result<Webhook, DeleteWebhookError> {
+        let mut webhooks = self.webhooks.lock().unwrap();
+        let not_found = DeleteWebhookError::WebhookDoesNotExist {
+            id: req.webhook_id(),
+        };
+        match webhooks.get(&req.webhook_id()) {
+            Some(webhook) if webhook.user_id() == req.user_id() => {}
+            _ => return Err(not_found),
+        }
+        let webhook = webhooks.remove(&req.webhook_id()).ok_or(not_found)?;
+        let mut deliveries = self.deliveries.lock().unwrap();
+        deliveries.retain(|_, delivery| delivery.webhook_id() != webhook.id());
+        Ok(webhook)
+    }
+
+    async fn save_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> Result<WebhookDelivery, SaveWebhookDeliveryError> {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        deliveries.insert(delivery.id(), delivery.clone());
+        Ok(delivery.clone())
+    }
+
+    async fn find_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>, FindWebhookDeliveriesError> {
+        let deliveries = self.deliveries.lock().unwrap();
+        let mut due: Vec<WebhookDelivery> = deliveries
+            .values()
+            .filter(|delivery| delivery.is_due(now))
+            .cloned()
+            .collect();
+        due.sort_by_key(|delivery| (delivery.next_attempt_at(), delivery.id()));
+        due.truncate(limit);
+        Ok(due)
+    }
+
+    async fn find_deliveries(
+        &self,
+        req: &ListWebhookDeliveriesRequest,
+    ) -> Result<Page<WebhookDelivery>, ListWebhookDeliveriesError> {
+        let deliveries = self.deliveries.lock().unwrap();
+        let deliveries = deliveries
+            .values()
+            .filter(|delivery| delivery.webhook_id() == req.webhook_id())
+            .cloned();
+        Ok(req
+            .page()
+            .paginate(deliveries, SortDirection::Descending, |delivery| {
+                Cursor::new(Some(SortValue::Time(delivery.created_at())), delivery.id())
+            }))
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let mut webhooks = self.webhooks.lock().unwrap();
+        webhooks.retain(|_, webhook| webhook.user_id() != user_id);
+        let mut deliveries = self.deliveries.lock().unwrap();
+        deliveries.retain(|_, delivery| delivery.user_id() != user_id);
+        Ok(())
+    }
+}
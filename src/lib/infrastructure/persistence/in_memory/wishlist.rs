@@ -1,23 +1,573 @@
-use std::{collections::HashMap, sync::Mutex};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::{CreateWishlistError, CreateWishlistRequest, Wishlist, WishlistRepository};
+use crate::domain::{
+    AddWishlistItemError, ArchiveWishlistError, Clock, CreateSectionError, CreateSectionRequest,
+    CreateWishlistError, CreateWishlistRequest, DeleteSectionError, DeleteSectionRequest,
+    FindWishlistByIdError, FindWishlistByIdRequest, FindWishlistsError, ForgetUserError,
+    MoveItemToSectionError, MoveItemToSectionRequest, Occasion, Page, RemoveWishlistItemError,
+    ReorderWishlistError, ReorderWishlistRequest, SetWishlistOccasionError,
+    SetWishlistTemplateError, ShareLinkError, ShareToken, Version, Wishlist, WishlistQuery,
+    WishlistRepository, WishlistSection, WishlistSlug,
+};
+use crate::infrastructure::clock::SystemClock;
 
+/// The [InMemoryWishlistRepository] struct is an in-memory implementation of the
+/// [WishlistRepository] trait. Wishlists are stamped with the time of their last change and moved
+/// to their next version.
 pub struct InMemoryWishlistRepository {
     wishlists: Mutex<HashMap<Uuid, Wishlist>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl InMemoryWishlistRepository {
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
         Self {
             wishlists: Mutex::new(HashMap::new()),
+            clock,
         }
     }
+
+    fn changed(&self, wishlist: &mut Wishlist) {
+        wishlist.touch(self.clock.now());
+        wishlist.next_version();
+    }
+}
+
+impl Default for InMemoryWishlistRepository {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl WishlistRepository for InMemoryWishlistRepository {
-    async fn save(&self, _req: &CreateWishlistRequest) -> Result<Wishlist, CreateWishlistError> {
-        todo!()
+    async fn save(&self, req: &CreateWishlistRequest) -> Result<Wishlist, CreateWishlistError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let id = Uuid::now_v7();
+        let mut wishlist = Wishlist::new(
+            id,
+            req.owner_id(),
+            req.name().clone(),
+            WishlistSlug::from(req.name().to_string().as_str()),
+            req.private(),
+        );
+        wishlist.touch(self.clock.now());
+        wishlists.insert(id, wishlist.clone());
+        Ok(wishlist)
+    }
+
+    async fn find_wishlist_by_id(
+        &self,
+        req: &FindWishlistByIdRequest,
+    ) -> Result<Option<Wishlist>, FindWishlistByIdError> {
+        let wishlists = self.wishlists.lock().unwrap();
+        Ok(wishlists.get(req.id()).cloned())
+    }
+
+    async fn find_wishlist_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Wishlist>, FindWishlistByIdError> {
+        let wishlists = self.wishlists.lock().unwrap();
+        Ok(wishlists
+            .values()
+            .find(|wishlist| wishlist.slug().to_string() == slug)
+            .cloned())
+    }
+
+    async fn find_wishlists_by_ids(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<Vec<Wishlist>, FindWishlistsError> {
+        let wishlists = self.wishlists.lock().unwrap();
+        Ok(ids
+            .iter()
+            .filter_map(|id| wishlists.get(id).cloned())
+            .collect())
+    }
+
+    async fn add_item(&self, wishlist_id: Uuid, item_id: Uuid) -> Result<(), AddWishlistItemError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists
+            .get_mut(&wishlist_id)
+            .ok_or(AddWishlistItemError::WishlistDoesNotExist { id: wishlist_id })?;
+        wishlist.add_item(item_id);
+        self.changed(wishlist);
+        Ok(())
+    }
+
+    async fn remove_item(
+        &self,
+        wishlist_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<(), RemoveWishlistItemError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists
+            .get_mut(&wishlist_id)
+            .ok_or(RemoveWishlistItemError::WishlistDoesNotExist { id: wishlist_id })?;
+        wishlist.remove_item(item_id);
+        self.changed(wishlist);
+        Ok(())
+    }
+
+    async fn create_section(
+        &self,
+        req: &CreateSectionRequest,
+    ) -> Result<WishlistSection, CreateSectionError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists.get_mut(&req.wishlist_id()).ok_or(
+            CreateSectionError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        let section = WishlistSection::new(Uuid::now_v7(), req.name().clone());
+        wishlist.add_section(section.clone())?;
+        self.changed(wishlist);
+        Ok(section)
+    }
+
+    async fn delete_section(
+        &self,
+        req: &DeleteSectionRequest,
+    ) -> Result<Wishlist, DeleteSectionError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists.get_mut(&req.wishlist_id()).ok_or(
+            DeleteSectionError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        wishlist.check_version(req.expected_version())?;
+        wishlist.remove_section(req.section_id())?;
+        self.changed(wishlist);
+        Ok(wishlist.clone())
+    }
+
+    async fn move_item_to_section(
+        &self,
+        req: &MoveItemToSectionRequest,
+    ) -> Result<Wishlist, MoveItemToSectionError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists.get_mut(&req.wishlist_id()).ok_or(
+            MoveItemToSectionError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        wishlist.check_version(req.expected_version())?;
+        wishlist.move_item_to_section(req.item_id(), req.section_id())?;
+        self.changed(wishlist);
+        Ok(wishlist.clone())
+    }
+
+    async fn reorder_wishlist(
+        &self,
+        req: &ReorderWishlistRequest,
+    ) -> Result<Wishlist, ReorderWishlistError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists.get_mut(&req.wishlist_id()).ok_or(
+            ReorderWishlistError::WishlistDoesNotExist {
+                id: req.wishlist_id(),
+            },
+        )?;
+        wishlist.check_version(req.expected_version())?;
+        wishlist.reorder(req.item_ids(), req.section_ids())?;
+        self.changed(wishlist);
+        Ok(wishlist.clone())
+    }
+
+    async fn set_template(
+        &self,
+        wishlist_id: Uuid,
+        template: bool,
+        expected_version: Option<Version>,
+    ) -> Result<Wishlist, SetWishlistTemplateError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists
+            .get_mut(&wishlist_id)
+            .ok_or(SetWishlistTemplateError::WishlistDoesNotExist { id: wishlist_id })?;
+        wishlist.check_version(expected_version)?;
+        wishlist.set_template(template);
+        self.changed(wishlist);
+        Ok(wishlist.clone())
+    }
+
+    async fn set_share_token(
+        &self,
+        wishlist_id: Uuid,
+        share_token: Option<ShareToken>,
+    ) -> Result<Wishlist, ShareLinkError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists
+            .get_mut(&wishlist_id)
+            .ok_or(ShareLinkError::WishlistDoesNotExist { id: wishlist_id })?;
+        wishlist.set_share_token(share_token);
+        self.changed(wishlist);
+        Ok(wishlist.clone())
+    }
+
+    async fn list_wishlists(
+        &self,
+        query: &WishlistQuery,
+    ) -> Result<Page<Wishlist>, FindWishlistsError> {
+        let wishlists = self.wishlists.lock().unwrap();
+        let sort = query.sort();
+        Ok(query.page().paginate(
+            wishlists
+                .values()
+                .filter(|wishlist| query.filter().matches(wishlist))
+                .cloned(),
+            sort.direction(),
+            |wishlist| sort.position(wishlist),
+        ))
+    }
+
+    async fn set_occasion(
+        &self,
+        wishlist_id: Uuid,
+        occasion: Option<Occasion>,
+        expected_version: Option<Version>,
+    ) -> Result<Wishlist, SetWishlistOccasionError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists
+            .get_mut(&wishlist_id)
+            .ok_or(SetWishlistOccasionError::WishlistDoesNotExist { id: wishlist_id })?;
+        wishlist.check_version(expected_version)?;
+        wishlist.set_occasion(occasion);
+        self.changed(wishlist);
+        Ok(wishlist.clone())
+    }
+
+    async fn set_archived(
+        &self,
+        wishlist_id: Uuid,
+        archived: bool,
+    ) -> Result<Wishlist, ArchiveWishlistError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        let wishlist = wishlists
+            .get_mut(&wishlist_id)
+            .ok_or(ArchiveWishlistError::WishlistDoesNotExist { id: wishlist_id })?;
+        wishlist.set_archived(archived);
+        self.changed(wishlist);
+        Ok(wishlist.clone())
+    }
+
+    async fn find_past_occasions(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Wishlist>, FindWishlistsError> {
+        let wishlists = self.wishlists.lock().unwrap();
+        let mut past: Vec<Wishlist> = wishlists
+            .values()
+            .filter(|wishlist| {
+                !wishlist.archived()
+                    && wishlist
+                        .occasion()
+                        .is_some_and(|occasion| occasion.is_over(now))
+            })
+            .cloned()
+            .collect();
+        past.sort_by_key(Wishlist::id);
+        Ok(past)
+    }
+
+    async fn find_wishlists_by_owners(
+        &self,
+        owner_ids: &[Uuid],
+    ) -> Result<Vec<Wishlist>, FindWishlistsError> {
+        let wishlists = self.wishlists.lock().unwrap();
+        let mut found: Vec<Wishlist> = wishlists
+            .values()
+            .filter(|wishlist| owner_ids.contains(&wishlist.owner_id()))
+            .cloned()
+            .collect();
+        found.sort_by_key(|wishlist| Reverse((wishlist.updated_at(), wishlist.id())));
+        Ok(found)
+    }
+
+    async fn forget_user(&self, user_id: Uuid) -> Result<(), ForgetUserError> {
+        let mut wishlists = self.wishlists.lock().unwrap();
+        wishlists.retain(|_, wishlist| wishlist.owner_id() != user_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        OccasionKind, PageLimit, PageRequest, SortDirection, VersionMismatchError, WishlistFilter,
+        WishlistSort, WishlistSortKey, FIRST_VERSION,
+    };
+
+    #[tokio::test]
+    async fn test_create_and_find_wishlist() {
+        let req = CreateWishlistRequest::new(Uuid::now_v7(), "Birthday".into(), false);
+        let repository = InMemoryWishlistRepository::new();
+
+        let wishlist = repository.save(&req).await.unwrap();
+        assert_eq!(wishlist.owner_id(), req.owner_id());
+        assert!(wishlist.slug().to_string().starts_with("birthday-"));
+
+        let found = repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlist.id()))
+            .await
+            .unwrap();
+        assert_eq!(found.unwrap().id(), wishlist.id());
+
+        let found = repository
+            .find_wishlist_by_slug(&wishlist.slug().to_string())
+            .await
+            .unwrap();
+        assert_eq!(found.unwrap().id(), wishlist.id());
+        let found = repository.find_wishlist_by_slug("birthday").await.unwrap();
+        assert!(found.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_add_item() {
+        let req = CreateWishlistRequest::new(Uuid::now_v7(), "Birthday".into(), false);
+        let repository = InMemoryWishlistRepository::new();
+        let wishlist = repository.save(&req).await.unwrap();
+        let item_id = Uuid::now_v7();
+
+        repository.add_item(wishlist.id(), item_id).await.unwrap();
+        let found = repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlist.id()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(found.contains_item(item_id));
+
+        repository
+            .remove_item(wishlist.id(), item_id)
+            .await
+            .unwrap();
+        let found = repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlist.id()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!found.contains_item(item_id));
+
+        let result = repository.add_item(Uuid::now_v7(), item_id).await;
+        assert!(matches!(
+            result,
+            Err(AddWishlistItemError::WishlistDoesNotExist { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sections_and_reorder() {
+        let req = CreateWishlistRequest::new(Uuid::now_v7(), "Birthday".into(), false);
+        let repository = InMemoryWishlistRepository::new();
+        let wishlist = repository.save(&req).await.unwrap();
+        let (first, second) = (Uuid::now_v7(), Uuid::now_v7());
+        repository.add_item(wishlist.id(), first).await.unwrap();
+        repository.add_item(wishlist.id(), second).await.unwrap();
+
+        let books = repository
+            .create_section(&CreateSectionRequest::new(
+                wishlist.id(),
+                wishlist.owner_id(),
+                "Books".into(),
+            ))
+            .await
+            .unwrap();
+        let moved = repository
+            .move_item_to_section(&MoveItemToSectionRequest::new(
+                wishlist.id(),
+                wishlist.owner_id(),
+                first,
+                Some(books.id()),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(moved.section_of(first), Some(&books));
+
+        let result = repository
+            .reorder_wishlist(&ReorderWishlistRequest::new(
+                wishlist.id(),
+                wishlist.owner_id(),
+                vec![second, Uuid::now_v7()],
+                vec![],
+            ))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ReorderWishlistError::ItemNotInWishlist { .. })
+        ));
+        let reordered = repository
+            .reorder_wishlist(&ReorderWishlistRequest::new(
+                wishlist.id(),
+                wishlist.owner_id(),
+                vec![second, first],
+                vec![],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(reordered.position_of(second), Some(0));
+
+        let deleted = repository
+            .delete_section(&DeleteSectionRequest::new(
+                wishlist.id(),
+                wishlist.owner_id(),
+                books.id(),
+            ))
+            .await
+            .unwrap();
+        assert!(deleted.sections().is_empty());
+        assert_eq!(deleted.section_of(first), None);
+    }
+
+    #[tokio::test]
+    async fn test_expected_version() {
+        let req = CreateWishlistRequest::new(Uuid::now_v7(), "Birthday".into(), false);
+        let repository = InMemoryWishlistRepository::new();
+        let wishlist = repository.save(&req).await.unwrap();
+        assert_eq!(wishlist.version(), FIRST_VERSION);
+
+        let template = repository
+            .set_template(wishlist.id(), true, Some(wishlist.version()))
+            .await
+            .unwrap();
+        assert_eq!(template.version(), wishlist.version() + 1);
+
+        let result = repository
+            .set_template(wishlist.id(), false, Some(wishlist.version()))
+            .await;
+        assert!(matches!(
+            result,
+            Err(SetWishlistTemplateError::VersionMismatch(
+                VersionMismatchError {
+                    expected: 1,
+                    actual: 2,
+                    ..
+                }
+            ))
+        ));
+        let result = repository
+            .reorder_wishlist(
+                &ReorderWishlistRequest::new(wishlist.id(), wishlist.owner_id(), vec![], vec![])
+                    .with_expected_version(Some(wishlist.version())),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ReorderWishlistError::VersionMismatch(_))
+        ));
+        let found = repository
+            .find_wishlist_by_id(&FindWishlistByIdRequest::new(wishlist.id()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(found.template());
+        assert_eq!(found.version(), template.version());
+    }
+
+    #[tokio::test]
+    async fn test_set_template_and_list_templates() {
+        let repository = InMemoryWishlistRepository::new();
+        let req = CreateWishlistRequest::new(Uuid::now_v7(), "Baby".into(), false);
+        let wishlist = repository.save(&req).await.unwrap();
+        repository.save(&req).await.unwrap();
+        let templates = WishlistQuery::default().narrow(|filter| filter.with_template(Some(true)));
+
+        let template = repository
+            .set_template(wishlist.id(), true, None)
+            .await
+            .unwrap();
+        assert!(template.template());
+        let page = repository.list_wishlists(&templates).await.unwrap();
+        assert_eq!(page.entries().len(), 1);
+        assert_eq!(page.entries()[0].id(), wishlist.id());
+
+        repository
+            .set_template(wishlist.id(), false, None)
+            .await
+            .unwrap();
+        let page = repository.list_wishlists(&templates).await.unwrap();
+        assert!(page.entries().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_wishlists_page_by_page() {
+        let repository = InMemoryWishlistRepository::new();
+        let owner_id = Uuid::now_v7();
+        for name in ["c", "a", "b"] {
+            let req = CreateWishlistRequest::new(owner_id, name.into(), false);
+            repository.save(&req).await.unwrap();
+        }
+        repository
+            .save(&CreateWishlistRequest::new(
+                Uuid::now_v7(),
+                "d".into(),
+                false,
+            ))
+            .await
+            .unwrap();
+        let filter = WishlistFilter::default().with_owners(vec![owner_id]);
+        let sort = WishlistSort::new(WishlistSortKey::Name, SortDirection::Ascending);
+        let query = |cursor| {
+            WishlistQuery::new(
+                filter.clone(),
+                sort,
+                PageRequest::new(cursor, PageLimit::new(2).unwrap()),
+            )
+        };
+
+        let first = repository.list_wishlists(&query(None)).await.unwrap();
+        let second = repository
+            .list_wishlists(&query(first.next_cursor().cloned()))
+            .await
+            .unwrap();
+        let names: Vec<String> = first
+            .entries()
+            .iter()
+            .chain(second.entries())
+            .map(|wishlist| wishlist.name().to_string())
+            .collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        assert!(second.next_cursor().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_find_past_occasions() {
+        let repository = InMemoryWishlistRepository::new();
+        let req = CreateWishlistRequest::new(Uuid::now_v7(), "Birthday".into(), false);
+        let birthday = repository.save(&req).await.unwrap();
+        repository.save(&req).await.unwrap();
+        let occasion = Occasion::new(
+            OccasionKind::Birthday,
+            "2027-03-14".parse().unwrap(),
+            chrono_tz::Europe::Paris,
+            None,
+        );
+        let now = "2027-03-15T12:00:00Z".parse().unwrap();
+
+        let updated = repository
+            .set_occasion(birthday.id(), Some(occasion.clone()), None)
+            .await
+            .unwrap();
+        assert_eq!(updated.occasion(), Some(&occasion));
+        let past = repository.find_past_occasions(now).await.unwrap();
+        assert_eq!(past.len(), 1);
+        assert_eq!(past[0].id(), birthday.id());
+
+        let archived = repository.set_archived(birthday.id(), true).await.unwrap();
+        assert!(archived.archived());
+        assert!(repository
+            .find_past_occasions(now)
+            .await
+            .unwrap()
+            .is_empty());
     }
 }
@@ -0,0 +1,50 @@
+use std::{future::Future, time::Duration};
+
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+
+/// Runs `task` every `period` on a background task, starting after the first period.
+///
+/// A run that takes longer than `period` delays the next one instead of stacking them up.
+pub fn spawn_periodic<F, Fut>(name: &'static str, period: Duration, mut task: F) -> JoinHandle<()>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            tracing::debug!("running scheduled job {}", name);
+            task().await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_spawn_periodic() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+        let handle = spawn_periodic("test", Duration::from_secs(60), move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        tokio::time::sleep(Duration::from_secs(100)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        handle.abort();
+    }
+}
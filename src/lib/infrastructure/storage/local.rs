@@ -0,0 +1,191 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use tokio::fs;
+use uuid::Uuid;
+
+use crate::domain::{Blob, BlobStore, BlobStoreError};
+
+/// Suffix of the file holding the content type next to each blob.
+const CONTENT_TYPE_SUFFIX: &str = ".content-type";
+
+/// The [LocalBlobStore] struct is a [BlobStore] keeping blobs as files under a root directory.
+///
+/// Keys are `/`-separated paths made of ASCII letters, digits, `-`, `_` and `.`, so that a key
+/// can never point outside of the root. Files are written to a temporary name and renamed, so
+/// readers never observe a partially written blob.
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, BlobStoreError> {
+        let valid = !key.ends_with(CONTENT_TYPE_SUFFIX)
+            && key.split('/').all(|segment| {
+                !segment.is_empty()
+                    && !segment.starts_with('.')
+                    && segment
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            });
+        if !valid {
+            return Err(BlobStoreError::InvalidKey {
+                key: key.to_string(),
+            });
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+fn content_type_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(CONTENT_TYPE_SUFFIX);
+    path.into()
+}
+
+/// Writes `contents` to `path` through a temporary file in the same directory.
+async fn write_atomically(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(format!(".{}.tmp", Uuid::new_v4()));
+    fs::write(&temporary, contents)
+        .await
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    if let Err(err) = fs::rename(&temporary, path).await {
+        let _ = fs::remove_file(&temporary).await;
+        return Err(err).with_context(|| format!("failed to write {}", path.display()));
+    }
+    Ok(())
+}
+
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, blob: &Blob) -> Result<(), BlobStoreError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        // The content type goes first: a blob is only visible once its data file exists.
+        write_atomically(&content_type_path(&path), blob.content_type().as_bytes()).await?;
+        write_atomically(&path, blob.bytes()).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Blob>, BlobStoreError> {
+        let path = self.path(key)?;
+        let bytes = match fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(anyhow::Error::new(err)
+                    .context(format!("failed to read {}", path.display()))
+                    .into())
+            }
+        };
+        let content_type = fs::read_to_string(content_type_path(&path))
+            .await
+            .with_context(|| format!("failed to read content type of {}", path.display()))?;
+        Ok(Some(Blob::new(&content_type, bytes)))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), BlobStoreError> {
+        let path = self.path(key)?;
+        // The data file goes first, mirroring put: a blob disappears as soon as it is gone.
+        for path in [path.clone(), content_type_path(&path)] {
+            match fs::remove_file(&path).await {
+                Ok(()) => {}
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(anyhow::Error::new(err)
+                        .context(format!("failed to delete {}", path.display()))
+                        .into())
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> LocalBlobStore {
+        LocalBlobStore::new(std::env::temp_dir().join(format!("blobs-{}", Uuid::new_v4())))
+    }
+
+    #[tokio::test]
+    async fn test_put_and_get() {
+        let store = store();
+        let blob = Blob::new("image/png", vec![1, 2, 3]);
+
+        store.put("images/1/original", &blob).await.unwrap();
+        assert_eq!(store.get("images/1/original").await.unwrap(), Some(blob));
+
+        let replaced = Blob::new("image/jpeg", vec![4]);
+        store.put("images/1/original", &replaced).await.unwrap();
+        assert_eq!(
+            store.get("images/1/original").await.unwrap(),
+            Some(replaced)
+        );
+
+        fs::remove_dir_all(&store.root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let store = store();
+        let blob = Blob::new("image/png", vec![1, 2, 3]);
+
+        store.put("images/3/original", &blob).await.unwrap();
+        store.delete("images/3/original").await.unwrap();
+        assert_eq!(store.get("images/3/original").await.unwrap(), None);
+        assert!(
+            !fs::try_exists(content_type_path(&store.root.join("images/3/original")))
+                .await
+                .unwrap()
+        );
+        // Deleting again is not an error
+        store.delete("images/3/original").await.unwrap();
+
+        fs::remove_dir_all(&store.root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_missing() {
+        let store = store();
+        assert_eq!(store.get("images/2/original").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_keys() {
+        let store = store();
+        let blob = Blob::new("text/plain", Vec::new());
+        for key in [
+            "",
+            "../escape",
+            "images/../../escape",
+            "/absolute",
+            "images//double",
+            "images/.hidden",
+            "images/1/original.content-type",
+            "images\\1",
+        ] {
+            assert!(
+                matches!(
+                    store.put(key, &blob).await,
+                    Err(BlobStoreError::InvalidKey { .. })
+                ),
+                "{} should be rejected",
+                key
+            );
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sha2::Sha256;
+
+use super::fetch::{pinned_public_client, FetchError};
+use crate::domain::{
+    Item, ItemChange, ItemPrice, SendWebhookError, Webhook, WebhookDelivery, WebhookEventKind,
+    WebhookSecret, WebhookSender,
+};
+
+const USER_AGENT: &str = concat!("wishlist-webhooks/", env!("CARGO_PKG_VERSION"));
+
+/// The ID of the delivery, the same on every attempt so that receivers can drop duplicates.
+pub const WEBHOOK_ID: &str = "webhook-id";
+/// When the payload was sent, in seconds since the Unix epoch.
+pub const WEBHOOK_TIMESTAMP: &str = "webhook-timestamp";
+/// The signature of the payload, see [sign].
+pub const WEBHOOK_SIGNATURE: &str = "webhook-signature";
+
+pub struct HttpWebhookSenderConfig {
+    /// Upper bound for a whole attempt, from connecting to the end of the response.
+    pub timeout: Duration,
+    /// Allows sending to loopback and private addresses. Only meant for tests and local setups.
+    pub allow_private_networks: bool,
+}
+
+/// The [HttpWebhookSender] struct posts webhook payloads as JSON over HTTP(S).
+///
+/// Every payload is signed with the secret of its webhook: the `webhook-signature` header is
+/// `v1=` followed by the hex HMAC-SHA256 of `{timestamp}.{body}`, where `timestamp` is the
+/// `webhook-timestamp` header. Receivers should check it, and reject old timestamps to stop
+/// replays. Like [HttpFetcher](super::fetch::HttpFetcher), it does not reach non-public
+/// addresses nor follow redirects.
+pub struct HttpWebhookSender {
+    config: HttpWebhookSenderConfig,
+}
+
+impl HttpWebhookSender {
+    pub fn new(config: HttpWebhookSenderConfig) -> Self {
+        Self { config }
+    }
+}
+
+/// Signs a payload sent at `timestamp` with `secret`, as the value of the `webhook-signature`
+/// header.
+pub fn sign(secret: &WebhookSecret, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_str().as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(body);
+    let signature: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("v1={}", signature)
+}
+
+/// The JSON body posted for a delivery.
+#[derive(Debug, Serialize)]
+struct WebhookPayload {
+    id: String,
+    event_id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    wishlist_id: String,
+    created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item: Option<ItemPayload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reserved: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+struct ItemPayload {
+    id: String,
+    title: String,
+    link_url: String,
+    image_url: Option<String>,
+    price: Option<Decimal>,
+    priority: String,
+    notes: Option<String>,
+    received: bool,
+}
+
+impl From<&Item> for ItemPayload {
+    fn from(item: &Item) -> Self {
+        Self {
+            id: item.id().to_string(),
+            title: item.title().to_string(),
+            link_url: item.link_url().to_string(),
+            image_url: item.image_url().map(ToString::to_string),
+            price: item.price().map(ItemPrice::value),
+            priority: item.priority().to_string(),
+            notes: item.notes().map(ToString::to_string),
+            received: item.received(),
+        }
+    }
+}
+
+impl From<&WebhookDelivery> for WebhookPayload {
+    fn from(delivery: &WebhookDelivery) -> Self {
+        let event = delivery.event();
+        let mut payload = Self {
+            id: delivery.id().to_string(),
+            event_id: event.id().to_string(),
+            kind: WebhookEventKind::of(event.change()).to_string(),
+            wishlist_id: event.wishlist_id().to_string(),
+            created_at: delivery.created_at(),
+            item: None,
+            item_id: None,
+            reserved: None,
+        };
+        match event.change() {
+            ItemChange::Added(item) | ItemChange::Edited(item) => payload.item = Some(item.into()),
+            ItemChange::Reserved { item_id, reserved } => {
+                payload.item_id = Some(item_id.to_string());
+                payload.reserved = Some(*reserved);
+            }
+            ItemChange::Removed { item_id } => payload.item_id = Some(item_id.to_string()),
+        }
+        payload
+    }
+}
+
+impl From<FetchError> for SendWebhookError {
+    fn from(err: FetchError) -> Self {
+        match err {
+            FetchError::Forbidden { url } => SendWebhookError::Forbidden { url },
+            FetchError::Timeout => SendWebhookError::Timeout,
+            FetchError::TooLarge { .. } | FetchError::Unknown(_) => {
+                SendWebhookError::Unknown(err.into())
+            }
+        }
+    }
+}
+
+impl WebhookSender for HttpWebhookSender {
+    async fn send(
+        &self,
+        webhook: &Webhook,
+        delivery: &WebhookDelivery,
+        sent_at: DateTime<Utc>,
+    ) -> Result<u16, SendWebhookError> {
+        let url = webhook.url().as_url();
+        let client = pinned_public_client(
+            url,
+            self.config.timeout,
+            self.config.allow_private_networks,
+            USER_AGENT,
+        )
+        .await?;
+
+        let body = serde_json::to_vec(&WebhookPayload::from(delivery))
+            .context("failed to serialize the payload")?;
+        let timestamp = sent_at.timestamp();
+        let response = client
+            .post(url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_ID, delivery.id().to_string())
+            .header(WEBHOOK_TIMESTAMP, timestamp.to_string())
+            .header(WEBHOOK_SIGNATURE, sign(webhook.secret(), timestamp, &body))
+            .body(body)
+            .send()
+            .await
+            .map_err(|err| {
+                if err.is_timeout() {
+                    SendWebhookError::Timeout
+                } else {
+                    SendWebhookError::Unknown(err.into())
+                }
+            })?;
+        Ok(response.status().as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        body::Bytes,
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Router,
+    };
+    use tokio::net::TcpListener;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::domain::{ItemEvent, WebhookUrl, Wishlist};
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    /// Records the requests posted to a local receiver and returns its base URL.
+    async fn spawn_receiver(received: Received) -> String {
+        let router = Router::new()
+            .route(
+                "/hooks",
+                post(
+                    |State(received): State<Received>, headers: HeaderMap, body: Bytes| async move {
+                        received.lock().unwrap().push((headers, body));
+                        StatusCode::NO_CONTENT
+                    },
+                ),
+            )
+            .route("/failing", post(|| async { StatusCode::BAD_GATEWAY }))
+            .route(
+                "/slow",
+                post(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    StatusCode::OK
+                }),
+            )
+            .with_state(received);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://{}", address)
+    }
+
+    fn sender(allow_private_networks: bool) -> HttpWebhookSender {
+        HttpWebhookSender::new(HttpWebhookSenderConfig {
+            timeout: Duration::from_millis(500),
+            allow_private_networks,
+        })
+    }
+
+    fn new_delivery(url: &str) -> (Webhook, WebhookDelivery) {
+        let now = Utc::now();
+        let wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        );
+        let webhook = Webhook::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            wishlist.id(),
+            WebhookUrl::new(url).unwrap(),
+            WebhookSecret::generate(),
+            WebhookEventKind::ALL.to_vec(),
+            now,
+        );
+        let item = Item::create(
+            Uuid::now_v7(),
+            wishlist.id(),
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        let event = ItemEvent::new(7, &wishlist, ItemChange::Added(item));
+        let delivery = webhook.delivery_for(&event, now).unwrap();
+        (webhook, delivery)
+    }
+
+    #[tokio::test]
+    async fn test_send_signed_payload() {
+        let received = Received::default();
+        let address = spawn_receiver(received.clone()).await;
+        let (webhook, delivery) = new_delivery(&format!("{}/hooks", address));
+        let sent_at: DateTime<Utc> = "2027-03-01T12:00:00Z".parse().unwrap();
+
+        let status = sender(true).send(&webhook, &delivery, sent_at).await;
+        assert_eq!(status.unwrap(), 204);
+        let received = received.lock().unwrap();
+        let (headers, body) = &received[0];
+        assert_eq!(headers[WEBHOOK_ID], delivery.id().to_string());
+        assert_eq!(headers[WEBHOOK_TIMESTAMP], sent_at.timestamp().to_string());
+        assert_eq!(
+            headers[WEBHOOK_SIGNATURE],
+            sign(webhook.secret(), sent_at.timestamp(), body)
+        );
+        assert_ne!(
+            headers[WEBHOOK_SIGNATURE],
+            sign(&"whsec_other".into(), sent_at.timestamp(), body)
+        );
+        let payload: serde_json::Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload["event_id"], "7");
+        assert_eq!(payload["type"], "item_added");
+        assert_eq!(payload["item"]["title"], "Book");
+        assert!(payload.get("reserved").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_send_reports_failures() {
+        let address = spawn_receiver(Received::default()).await;
+        let now = Utc::now();
+
+        let (webhook, delivery) = new_delivery(&format!("{}/failing", address));
+        let status = sender(true).send(&webhook, &delivery, now).await;
+        assert_eq!(status.unwrap(), 502);
+
+        let (webhook, delivery) = new_delivery(&format!("{}/slow", address));
+        let result = sender(true).send(&webhook, &delivery, now).await;
+        assert!(matches!(result, Err(SendWebhookError::Timeout)));
+
+        let (webhook, delivery) = new_delivery(&format!("{}/hooks", address));
+        let result = sender(false).send(&webhook, &delivery, now).await;
+        assert!(matches!(result, Err(SendWebhookError::Forbidden { .. })));
+    }
+
+    #[test]
+    fn test_sign() {
+        // HMAC-SHA256 of "1700000000.{}" with the key "whsec_test"
+        let signature = sign(&"whsec_test".into(), 1_700_000_000, b"{}");
+        assert_eq!(
+            signature,
+            "v1=35495024f4ef3f94e5a93e22221544c4b75e9a42300cd965ab81cb85cd994e91"
+        );
+        assert_ne!(signature, sign(&"whsec_test".into(), 1_700_000_001, b"{}"));
+    }
+}
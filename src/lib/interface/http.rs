@@ -1,21 +1,75 @@
+mod actor;
+mod conditional;
+mod graphql;
 mod handlers;
-mod responses;
+mod idempotency;
+mod openapi;
+mod pages;
+mod problem;
+mod query;
+mod rate_limit;
 
 use crate::application::UseCases;
+use crate::infrastructure::clock::SystemClock;
 use anyhow::Context;
-use axum::routing::get;
+use axum::{extract::DefaultBodyLimit, routing::get};
+use chrono::TimeDelta;
+use graphql::graphql_routes;
 use handlers::api_routes;
+use idempotency::{Idempotency, IdempotencyStore};
+use openapi::{ApiDoc, DOCS_PATH, OPENAPI_JSON_PATH};
+use pages::page_routes;
+use rate_limit::{InMemoryRateLimitStore, RateLimiter};
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+pub use graphql::GraphqlLimits;
+pub use rate_limit::{BucketRate, LockoutPolicy, RateLimitPolicy, RouteLimits};
 
 pub struct HttpServerConfig {
     pub host: String,
     pub port: u16,
+    /// Requests with a larger body are rejected with 413 Payload Too Large.
+    pub max_body_bytes: usize,
+    /// How long the response to a request with an `Idempotency-Key` is replayed to retries.
+    pub idempotency_ttl: Duration,
+    /// How many `Idempotency-Key`s are kept at most before the oldest responses are dropped.
+    pub idempotency_max_entries: usize,
+    /// Where the server is reached from outside, e.g. `https://example.com`, for the absolute
+    /// links of the wishlist pages.
+    pub public_url: String,
+    /// How many requests clients may send, per IP and per user, and when accounts are locked out.
+    pub rate_limits: RateLimitPolicy,
+    /// How deep and how costly queries to `/api/graphql` may be.
+    pub graphql: GraphqlLimits,
 }
 
 impl HttpServerConfig {
-    pub fn new(host: String, port: u16) -> Self {
-        Self { host, port }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        host: String,
+        port: u16,
+        max_body_bytes: usize,
+        idempotency_ttl: Duration,
+        idempotency_max_entries: usize,
+        public_url: String,
+        rate_limits: RateLimitPolicy,
+        graphql: GraphqlLimits,
+    ) -> Self {
+        Self {
+            host,
+            port,
+            max_body_bytes,
+            idempotency_ttl,
+            idempotency_max_entries,
+            public_url,
+            rate_limits,
+            graphql,
+        }
     }
 }
 
@@ -38,6 +92,24 @@ impl HttpServer {
             },
         );
 
+        let idempotency = Idempotency::new(
+            Arc::new(
+                IdempotencyStore::new(
+                    TimeDelta::from_std(config.idempotency_ttl)
+                        .context("Idempotency TTL is out of range")?,
+                    Arc::new(SystemClock),
+                )
+                .with_max_entries(config.idempotency_max_entries),
+            ),
+            config.max_body_bytes,
+        );
+        let rate_limiter = RateLimiter::new(
+            Arc::new(InMemoryRateLimitStore::new()),
+            config.rate_limits,
+            Arc::new(SystemClock),
+            config.max_body_bytes,
+        );
+
         let app_state = AppState {
             services: Arc::new(services),
         };
@@ -45,7 +117,28 @@ impl HttpServer {
         let router = axum::Router::new()
             .layer(trace_layer)
             .route("/health_check", get(|| async { "OK" }))
-            .nest("/api", api_routes())
+            .nest(
+                "/api",
+                api_routes()
+                    .merge(graphql_routes(config.graphql))
+                    .layer(axum::middleware::from_fn(conditional::not_modified))
+                    .layer(axum::middleware::from_fn_with_state(
+                        idempotency,
+                        idempotency::idempotent,
+                    ))
+                    .layer(axum::middleware::from_fn_with_state(
+                        rate_limiter.clone(),
+                        rate_limit::rate_limit::<InMemoryRateLimitStore>,
+                    )),
+            )
+            .merge(
+                page_routes(&config.public_url).layer(axum::middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit::rate_limit::<InMemoryRateLimitStore>,
+                )),
+            )
+            .merge(SwaggerUi::new(DOCS_PATH).url(OPENAPI_JSON_PATH, ApiDoc::openapi()))
+            .layer(DefaultBodyLimit::max(config.max_body_bytes))
             .with_state(app_state);
 
         let listener = net::TcpListener::bind((config.host.as_str(), config.port))
@@ -57,24 +150,69 @@ impl HttpServer {
 
     pub async fn run(self) -> anyhow::Result<()> {
         tracing::info!("listening on {}", self.listener.local_addr().unwrap());
-        axum::serve(self.listener, self.router)
-            .await
-            .context("received error from running server")?;
+        // Rate limits need the address of each client
+        axum::serve(
+            self.listener,
+            self.router
+                .into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .context("received error from running server")?;
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        application::Service,
-        domain::{MockUserService, MockWishlistService},
+    use crate::domain::{
+        Blob, CreateUserError, Item, ItemChange, ItemEvent, ItemEventStream, ItemEventSubscription,
+        ItemListing, MockImageService, MockItemService, MockProfileService, MockUserService,
+        MockWishlistService, Page, Profile, ReserveItemError, ShareToken, User,
+        VersionMismatchError, ViewSharedWishlistError, Wishlist, WishlistView,
     };
 
     use super::*;
+    use handlers::test_services;
+    use std::future;
     use std::net::{SocketAddr, TcpListener};
+    use uuid::Uuid;
 
     async fn spawn_app() -> String {
+        spawn_app_with(MockWishlistService::new(), MockItemService::new()).await
+    }
+
+    async fn spawn_app_with(
+        wish_service: MockWishlistService,
+        item_service: MockItemService,
+    ) -> String {
+        spawn_rate_limited_app(
+            MockUserService::new(),
+            wish_service,
+            item_service,
+            RateLimitPolicy::default(),
+        )
+        .await
+    }
+
+    async fn spawn_rate_limited_app(
+        user_service: MockUserService,
+        wish_service: MockWishlistService,
+        item_service: MockItemService,
+        rate_limits: RateLimitPolicy,
+    ) -> String {
+        let services = test_services()
+            .with_user(user_service)
+            .with_wishlist(wish_service)
+            .with_item(item_service)
+            .build();
+        spawn_server(services, rate_limits, GraphqlLimits::default()).await
+    }
+
+    async fn spawn_server(
+        services: impl UseCases,
+        rate_limits: RateLimitPolicy,
+        graphql: GraphqlLimits,
+    ) -> String {
         let local_addr = TcpListener::bind("127.0.0.1:0")
             .expect("Failed to bind a random port")
             .local_addr()
@@ -83,10 +221,13 @@ mod tests {
         let server_config = HttpServerConfig {
             port: local_addr.port(),
             host: local_addr.ip().to_string(),
+            max_body_bytes: 1024,
+            idempotency_ttl: Duration::from_secs(60),
+            idempotency_max_entries: 100,
+            public_url: "https://wishlist.example".to_string(),
+            rate_limits,
+            graphql,
         };
-        let user_service = MockUserService::new();
-        let wish_service = MockWishlistService::new();
-        let services = Service::new(user_service, wish_service);
         let http_server = HttpServer::new(services, server_config)
             .await
             .expect("Failed to create HttpServer");
@@ -100,7 +241,7 @@ mod tests {
         let client = reqwest::Client::new();
 
         let response = client
-            .get(&format!("{}/health_check", &address))
+            .get(format!("{}/health_check", &address))
             .send()
             .await
             .expect("Failed to execute request.");
@@ -108,4 +249,808 @@ mod tests {
         assert!(response.status().is_success());
         assert_eq!(Some(2), response.content_length());
     }
+
+    #[tokio::test]
+    async fn test_openapi_document() {
+        let address = spawn_app().await;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .get(format!("{}{}", &address, OPENAPI_JSON_PATH))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert!(response.status().is_success());
+        let document: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert!(document["paths"]["/wishlists"]["post"].is_object());
+
+        let response = client
+            .get(format!("{}{}/", &address, DOCS_PATH))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert!(response.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_request_is_a_problem() {
+        let address = spawn_app().await;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .post(format!("{}/api/wishlists", &address))
+            .header("content-type", "application/json")
+            .body(
+                serde_json::json!({ "owner_id": "nobody", "name": "", "private": false })
+                    .to_string(),
+            )
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert_eq!(response.status().as_u16(), 422);
+        assert_eq!(response.headers()["content-type"], problem::PROBLEM_JSON);
+        let problem: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert_eq!(problem["type"], "/problems/invalid-request");
+        assert_eq!(problem["status"], 422);
+        let pointers: Vec<_> = problem["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["pointer"].as_str().unwrap())
+            .collect();
+        assert_eq!(pointers, ["#/owner_id", "#/name"]);
+
+        let response = client
+            .post(format!("{}/api/wishlists", &address))
+            .header("content-type", "application/json")
+            .body(serde_json::json!({ "owner_id": 42 }).to_string())
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert_eq!(response.status().as_u16(), 422);
+        let problem: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert_eq!(problem["errors"][0]["pointer"], "#/owner_id");
+
+        let response = client
+            .post(format!("{}/api/wishlists", &address))
+            .header("content-type", "application/json")
+            .body("{")
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert_eq!(response.status().as_u16(), 400);
+        let problem: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert_eq!(problem["type"], "/problems/malformed-request");
+    }
+
+    #[tokio::test]
+    async fn test_conditional_requests() {
+        let wishlist_id = Uuid::now_v7();
+        let item = Item::create(
+            Uuid::now_v7(),
+            wishlist_id,
+            "Scarf".into(),
+            "https://shop.example/scarf".into(),
+            None,
+            None,
+        );
+        let mut wishlist = Wishlist::new(
+            wishlist_id,
+            Uuid::now_v7(),
+            "Birthday".into(),
+            "Birthday".into(),
+            false,
+        );
+        wishlist.add_item(item.id());
+        let mut item_service = MockItemService::new();
+        let listed = item.clone();
+        item_service
+            .expect_list_items()
+            .times(3)
+            .returning(move |_| {
+                let listing =
+                    ItemListing::new(wishlist.clone(), Page::new(vec![listed.clone()], None));
+                Box::pin(future::ready(Ok(listing)))
+            });
+        item_service
+            .expect_mark_item_received()
+            .withf(|req| req.expected_version() == Some(1))
+            .times(1)
+            .returning(|req| {
+                Box::pin(future::ready(Err(VersionMismatchError {
+                    id: req.item_id(),
+                    expected: 1,
+                    actual: 2,
+                }
+                .into())))
+            });
+        let address = spawn_app_with(MockWishlistService::new(), item_service).await;
+        let client = reqwest::Client::new();
+        let items_url = format!("{}/api/wishlists/{}/items", &address, wishlist_id);
+
+        let response = client.get(&items_url).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let etag = response.headers()["etag"].clone();
+        assert!(etag.to_str().unwrap().starts_with("W/\""));
+
+        let response = client
+            .get(&items_url)
+            .header("if-none-match", etag.clone())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 304);
+        assert_eq!(response.headers()["etag"], etag);
+        assert!(response.text().await.unwrap().is_empty());
+
+        let response = client
+            .get(&items_url)
+            .header("if-none-match", "W/\"stale\"")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        let received_url = format!("{}/api/items/{}/received", &address, item.id());
+        let body = serde_json::json!({ "user_id": Uuid::now_v7().to_string(), "received": true })
+            .to_string();
+        let response = client
+            .put(&received_url)
+            .header("if-match", "\"1\"")
+            .header("content-type", "application/json")
+            .body(body.clone())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 412);
+        let problem: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert_eq!(problem["type"], "/problems/version-mismatch");
+
+        let response = client
+            .put(&received_url)
+            .header("if-match", etag)
+            .header("content-type", "application/json")
+            .body(body.clone())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 412);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_image_requests() {
+        let mut image_service = MockImageService::new();
+        image_service.expect_find_image().times(2).returning(|_| {
+            let blob = Blob::new("image/png", vec![1, 2, 3]);
+            Box::pin(future::ready(Ok(Some(blob))))
+        });
+        let services = test_services().with_image(image_service).build();
+        let address = spawn_server(
+            services,
+            RateLimitPolicy::default(),
+            GraphqlLimits::default(),
+        )
+        .await;
+        let client = reqwest::Client::new();
+        let image_url = format!("{}/api/images/{}/small", &address, Uuid::now_v7());
+
+        let response = client.get(&image_url).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let etag = response.headers()["etag"].clone();
+        let cache_control = response.headers()["cache-control"].clone();
+
+        let response = client
+            .get(&image_url)
+            .header(
+                "if-none-match",
+                format!("\"other\", {}", etag.to_str().unwrap()),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 304);
+        assert_eq!(response.headers()["etag"], etag);
+        assert_eq!(response.headers()["cache-control"], cache_control);
+        assert!(response.text().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_retries() {
+        let mut wish_service = MockWishlistService::new();
+        wish_service
+            .expect_create_wishlist()
+            .times(1)
+            .returning(|req| {
+                let wishlist = Wishlist::new(
+                    Uuid::now_v7(),
+                    req.owner_id(),
+                    req.name().clone(),
+                    req.name().to_string().as_str().into(),
+                    req.private(),
+                );
+                Box::pin(future::ready(Ok(wishlist)))
+            });
+        let address = spawn_app_with(wish_service, MockItemService::new()).await;
+        let client = reqwest::Client::new();
+        let owner_id = Uuid::now_v7().to_string();
+        let create = |name: &str| {
+            client
+                .post(format!("{}/api/wishlists", &address))
+                .header("content-type", "application/json")
+                .header("idempotency-key", "create-birthday")
+                .body(
+                    serde_json::json!({ "owner_id": owner_id, "name": name, "private": false })
+                        .to_string(),
+                )
+                .send()
+        };
+
+        let first = create("Birthday").await.unwrap();
+        assert_eq!(first.status().as_u16(), 201);
+        assert!(first.headers().get("idempotent-replayed").is_none());
+        let first = first.text().await.unwrap();
+        let retry = create("Birthday").await.unwrap();
+        assert_eq!(retry.status().as_u16(), 201);
+        assert_eq!(retry.headers()["idempotent-replayed"], "true");
+        assert_eq!(retry.text().await.unwrap(), first);
+
+        let reused = create("Christmas").await.unwrap();
+        assert_eq!(reused.status().as_u16(), 422);
+        let problem: serde_json::Value =
+            serde_json::from_str(&reused.text().await.unwrap()).unwrap();
+        assert_eq!(problem["type"], "/problems/idempotency-key-reused");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_route() {
+        let mut user_service = MockUserService::new();
+        user_service.expect_create_user().times(2).returning(|req| {
+            let user = User::new(Uuid::now_v7(), req.email().clone(), req.password().clone());
+            Box::pin(future::ready(Ok(user)))
+        });
+        let rate_limits = RateLimitPolicy::default().with_route(
+            "POST",
+            "/api/authors",
+            RouteLimits::new(Some(BucketRate::new(2, 1)), None),
+        );
+        let address = spawn_rate_limited_app(
+            user_service,
+            MockWishlistService::new(),
+            MockItemService::new(),
+            rate_limits,
+        )
+        .await;
+        let client = reqwest::Client::new();
+        let create = |email: &str| {
+            client
+                .post(format!("{}/api/authors", &address))
+                .header("content-type", "application/json")
+                .body(serde_json::json!({ "email": email, "password": "secret" }).to_string())
+                .send()
+        };
+
+        assert_eq!(
+            create("alice@example.com").await.unwrap().status().as_u16(),
+            201
+        );
+        assert_eq!(
+            create("bob@example.com").await.unwrap().status().as_u16(),
+            201
+        );
+        let limited = create("carol@example.com").await.unwrap();
+        assert_eq!(limited.status().as_u16(), 429);
+        assert_eq!(limited.headers()["retry-after"], "60");
+        let problem: serde_json::Value =
+            serde_json::from_str(&limited.text().await.unwrap()).unwrap();
+        assert_eq!(problem["type"], "/problems/rate-limited");
+
+        // Other routes have their own buckets
+        let response = client
+            .get(format!("{}/health_check", &address))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_locked_out_author() {
+        let mut user_service = MockUserService::new();
+        user_service.expect_create_user().times(3).returning(|req| {
+            if req.email().to_string().to_lowercase() == "alice@example.com" {
+                let email = req.email().clone();
+                return Box::pin(future::ready(Err(CreateUserError::Duplicate { email })));
+            }
+            let user = User::new(Uuid::now_v7(), req.email().clone(), req.password().clone());
+            Box::pin(future::ready(Ok(user)))
+        });
+        let rate_limits = RateLimitPolicy::new(
+            RouteLimits::default(),
+            LockoutPolicy::new(2, TimeDelta::seconds(30), TimeDelta::hours(1)),
+        )
+        .with_route(
+            "POST",
+            "/api/authors",
+            RouteLimits::default().with_lockout(true),
+        );
+        let address = spawn_rate_limited_app(
+            user_service,
+            MockWishlistService::new(),
+            MockItemService::new(),
+            rate_limits,
+        )
+        .await;
+        let client = reqwest::Client::new();
+        let create = |email: &str| {
+            client
+                .post(format!("{}/api/authors", &address))
+                .header("content-type", "application/json")
+                .body(serde_json::json!({ "email": email, "password": "secret" }).to_string())
+                .send()
+        };
+
+        assert_eq!(
+            create("alice@example.com").await.unwrap().status().as_u16(),
+            422
+        );
+        assert_eq!(
+            create("Alice@Example.com").await.unwrap().status().as_u16(),
+            422
+        );
+        let locked = create("alice@example.com").await.unwrap();
+        assert_eq!(locked.status().as_u16(), 429);
+        assert_eq!(locked.headers()["retry-after"], "30");
+        let problem: serde_json::Value =
+            serde_json::from_str(&locked.text().await.unwrap()).unwrap();
+        assert_eq!(problem["type"], "/problems/account-locked-out");
+
+        // Other accounts are not locked out
+        assert_eq!(
+            create("bob@example.com").await.unwrap().status().as_u16(),
+            201
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watch_items_over_websocket() {
+        use futures_util::StreamExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        let (owner_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let wishlist = Wishlist::new(
+            wishlist_id,
+            owner_id,
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        );
+        let item = Item::create(
+            Uuid::now_v7(),
+            wishlist_id,
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        let missed = vec![
+            ItemEvent::new(
+                4,
+                &wishlist,
+                ItemChange::Reserved {
+                    item_id: item.id(),
+                    reserved: true,
+                },
+            ),
+            ItemEvent::new(5, &wishlist, ItemChange::Edited(item.clone())),
+        ];
+        let mut item_service = MockItemService::new();
+        item_service
+            .expect_watch_items()
+            .withf(move |req| req.user_id() == owner_id && req.last_event_id() == Some(3))
+            .return_once(move |req| {
+                let live = tokio::sync::broadcast::channel(1).1;
+                let events =
+                    ItemEventStream::new(req.user_id(), ItemEventSubscription::new(missed, live));
+                Box::pin(future::ready(Ok(events)))
+            });
+        let address = spawn_app_with(MockWishlistService::new(), item_service).await;
+        let url = format!(
+            "{}/api/wishlists/{}/events/ws?user_id={}&last_event_id=3",
+            address.replacen("http", "ws", 1),
+            wishlist_id,
+            owner_id
+        );
+
+        let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        // The owner is not told about the reservation
+        let message = socket.next().await.unwrap().unwrap();
+        let data: serde_json::Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+        assert_eq!(data["id"], "5");
+        assert_eq!(data["type"], "item_edited");
+        assert_eq!(data["item"]["id"], item.id().to_string());
+        assert!(matches!(
+            socket.next().await.unwrap().unwrap(),
+            Message::Close(_)
+        ));
+    }
+
+    async fn post_graphql(address: &str, query: &str) -> serde_json::Value {
+        let response = reqwest::Client::new()
+            .post(format!("{}/api/graphql", address))
+            .header("content-type", "application/json")
+            .body(serde_json::json!({ "query": query }).to_string())
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert_eq!(response.status().as_u16(), 200);
+        serde_json::from_str(&response.text().await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_graphql_batches_lookups() {
+        let (viewer_id, friend_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let own = Wishlist::new(
+            Uuid::now_v7(),
+            viewer_id,
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        );
+        let friends = Wishlist::new(
+            Uuid::now_v7(),
+            friend_id,
+            "Wedding".into(),
+            "wedding".into(),
+            false,
+        );
+        let scarf = Item::create(
+            Uuid::now_v7(),
+            friends.id(),
+            "Scarf".into(),
+            "https://shop.example/scarf".into(),
+            None,
+            None,
+        );
+        let mut item_service = MockItemService::new();
+        let views = vec![
+            WishlistView::new(viewer_id, own.clone(), Vec::new()),
+            WishlistView::new(viewer_id, friends.clone(), vec![scarf]),
+        ];
+        item_service
+            .expect_view_wishlists()
+            .withf(move |req| req.user_id() == viewer_id && req.wishlist_ids().len() == 3)
+            .times(1)
+            .return_once(move |_| Box::pin(future::ready(Ok(views))));
+        let mut profile_service = MockProfileService::new();
+        profile_service
+            .expect_find_profiles()
+            .withf(move |req| req.user_id() == viewer_id && req.profile_ids().len() == 2)
+            .times(1)
+            .returning(|req| {
+                let profiles = req
+                    .profile_ids()
+                    .iter()
+                    .copied()
+                    .map(Profile::new)
+                    .collect();
+                Box::pin(future::ready(Ok(profiles)))
+            });
+        let services = test_services()
+            .with_item(item_service)
+            .with_profile(profile_service)
+            .build();
+        let address = spawn_server(
+            services,
+            RateLimitPolicy::default(),
+            GraphqlLimits::default(),
+        )
+        .await;
+
+        let query = format!(
+            r#"{{ wishlists(userId: "{}", ids: ["{}", "{}", "{}"]) {{
+                name owner {{ userId }} items {{ title }} reservationCount
+            }} }}"#,
+            viewer_id,
+            friends.id(),
+            Uuid::now_v7(),
+            own.id()
+        );
+        let body = post_graphql(&address, &query).await;
+        assert_eq!(body["errors"], serde_json::Value::Null);
+        assert_eq!(
+            body["data"]["wishlists"],
+            serde_json::json!([
+                {
+                    "name": "Wedding",
+                    "owner": { "userId": friend_id.to_string() },
+                    "items": [{ "title": "Scarf" }],
+                    "reservationCount": 0,
+                },
+                {
+                    "name": "Birthday",
+                    "owner": { "userId": viewer_id.to_string() },
+                    "items": [],
+                    "reservationCount": null,
+                },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graphql_limits() {
+        let services = test_services().build();
+        let limits = GraphqlLimits {
+            max_depth: 2,
+            max_complexity: 4,
+            max_batch: 2,
+        };
+        let address = spawn_server(services, RateLimitPolicy::default(), limits).await;
+        let user_id = Uuid::now_v7();
+
+        let query = format!(
+            r#"{{ wishlist(userId: "{0}", id: "{0}") {{ owner {{ userId }} }} }}"#,
+            user_id
+        );
+        let body = post_graphql(&address, &query).await;
+        assert_eq!(body["data"], serde_json::Value::Null);
+        assert_eq!(body["errors"][0]["message"], "Query is nested too deep.");
+
+        let query = format!(
+            r#"{{ wishlist(userId: "{0}", id: "{0}") {{ id name slug private template }} }}"#,
+            user_id
+        );
+        let body = post_graphql(&address, &query).await;
+        assert_eq!(body["data"], serde_json::Value::Null);
+        assert_eq!(body["errors"][0]["message"], "Query is too complex.");
+
+        let batch = |size| {
+            let queries = vec![serde_json::json!({ "query": "{ __typename }" }); size];
+            reqwest::Client::new()
+                .post(format!("{}/api/graphql", address))
+                .header("content-type", "application/json")
+                .body(serde_json::Value::from(queries).to_string())
+                .send()
+        };
+        let response = batch(2).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert_eq!(body.as_array().unwrap().len(), 2);
+        let response = batch(3).await.unwrap();
+        assert_eq!(response.status().as_u16(), 413);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert_eq!(body["type"], "/problems/batch-too-large");
+    }
+
+    #[tokio::test]
+    async fn test_graphql_errors_are_problems() {
+        let address = spawn_app().await;
+
+        let body = post_graphql(
+            &address,
+            r#"mutation { createWishlist(input: { ownerId: "nobody", name: "", private: false }) { id } }"#,
+        )
+        .await;
+        let error = &body["errors"][0];
+        assert_eq!(error["path"], serde_json::json!(["createWishlist"]));
+        assert_eq!(error["extensions"]["type"], "/problems/invalid-request");
+        assert_eq!(error["extensions"]["status"], 422);
+        let pointers: Vec<_> = error["extensions"]["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["pointer"].as_str().unwrap())
+            .collect();
+        assert_eq!(pointers, ["#/owner_id", "#/name"]);
+
+        let body = post_graphql(
+            &address,
+            r#"{ followFeed(userId: "nobody") { nextCursor } }"#,
+        )
+        .await;
+        assert_eq!(
+            body["errors"][0]["extensions"]["errors"][0]["parameter"],
+            "user_id"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wishlist_page() {
+        let guest_id = Uuid::now_v7();
+        let wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            "Grandma's <3 list".into(),
+            "Grandma's <3 list".into(),
+            true,
+        );
+        // Slugs keep the quotes and brackets of names, so links to the page encode them
+        let suffix = wishlist.slug().to_string().split_off(18);
+        let path = format!("/w/grandma%27s-%3C3-list-{}", suffix);
+        let mut scarf = Item::create(
+            Uuid::now_v7(),
+            wishlist.id(),
+            "Scarf".into(),
+            "https://shop.example/scarf".into(),
+            None,
+            None,
+        );
+        let image_id = Uuid::now_v7();
+        scarf.set_image_id(Some(image_id));
+        let mut book = Item::create(
+            Uuid::now_v7(),
+            wishlist.id(),
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        book.set_reserved_by(Some(guest_id));
+        let view = WishlistView::new(guest_id, wishlist, vec![scarf.clone(), book]);
+        let mut item_service = MockItemService::new();
+        item_service
+            .expect_view_shared_wishlist()
+            .withf(|req| req.share_token() == Some(&ShareToken::from("secret")))
+            .times(2)
+            .returning(move |_| Box::pin(future::ready(Ok(view.clone()))));
+        item_service.expect_view_shared_wishlist().returning(|req| {
+            let slug = req.slug().to_string();
+            Box::pin(future::ready(Err(
+                ViewSharedWishlistError::WishlistDoesNotExist { slug },
+            )))
+        });
+        let address = spawn_app_with(MockWishlistService::new(), item_service).await;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .get(format!("{}{}?token=secret", &address, path))
+            .header("cookie", format!("guest_id={}", guest_id))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert!(response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html"));
+        assert!(response.headers().get("set-cookie").is_none());
+        let html = response.text().await.unwrap();
+        assert!(html.contains(r#"<meta property="og:title" content="Grandma&#39;s &#60;3 list">"#));
+        assert!(html.contains(&format!(
+            r#"<meta property="og:image" content="https://wishlist.example/api/images/{}/medium">"#,
+            image_id
+        )));
+        assert!(html.contains(&format!(
+            r#"<form method="post" action="{}/items/{}/reservation">"#,
+            path,
+            scarf.id()
+        )));
+        assert!(html.contains(r#"<input type="hidden" name="token" value="secret">"#));
+        assert!(html.contains("Reserved by you"));
+
+        // A new visitor is given a guest ID, for whom the reservation is someone else's
+        let response = client
+            .get(format!("{}{}?token=secret", &address, path))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let cookie = response.headers()["set-cookie"].to_str().unwrap();
+        assert!(cookie.starts_with("guest_id="));
+        assert!(!cookie.contains(&guest_id.to_string()));
+        let html = response.text().await.unwrap();
+        assert!(html.contains("Reserved by someone else"));
+
+        // Without the token, a private wishlist does not exist
+        let response = client
+            .get(format!("{}{}", &address, path))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 404);
+        assert!(response
+            .text()
+            .await
+            .unwrap()
+            .contains("<h1>The wishlist does not exist</h1>"));
+    }
+
+    #[tokio::test]
+    async fn test_reserve_from_page() {
+        let (guest_id, item_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut item_service = MockItemService::new();
+        item_service
+            .expect_reserve_item()
+            .withf(move |req| {
+                req.item_id() == item_id
+                    && req.user_id() == guest_id
+                    && req.reserved()
+                    && req.share_token() == Some(&ShareToken::from("secret"))
+            })
+            .times(1)
+            .return_once(move |req| {
+                let mut item = Item::create(
+                    req.item_id(),
+                    Uuid::now_v7(),
+                    "Scarf".into(),
+                    "https://shop.example/scarf".into(),
+                    None,
+                    None,
+                );
+                item.set_reserved_by(Some(req.user_id()));
+                Box::pin(future::ready(Ok(item)))
+            });
+        let address = spawn_app_with(MockWishlistService::new(), item_service).await;
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+
+        let response = client
+            .post(format!(
+                "{}/w/grandma-3-list-1a2b3c4d/items/{}/reservation",
+                &address, item_id
+            ))
+            .header("cookie", format!("guest_id={}", guest_id))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body("token=secret&reserved=true")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 303);
+        assert_eq!(
+            response.headers()["location"],
+            "/w/grandma-3-list-1a2b3c4d?token=secret"
+        );
+
+        let response = client
+            .post(format!(
+                "{}/w/grandma-3-list-1a2b3c4d/items/{}/reservation",
+                &address, item_id
+            ))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body("reserved=maybe")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_reserve_from_archived_page() {
+        let (wishlist_id, item_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut item_service = MockItemService::new();
+        item_service
+            .expect_reserve_item()
+            .times(1)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(ReserveItemError::WishlistArchived {
+                    id: wishlist_id,
+                })))
+            });
+        let address = spawn_app_with(MockWishlistService::new(), item_service).await;
+
+        let response = reqwest::Client::new()
+            .post(format!(
+                "{}/w/grandma-3-list-1a2b3c4d/items/{}/reservation",
+                &address, item_id
+            ))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body("reserved=true")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 409);
+        assert!(response
+            .text()
+            .await
+            .unwrap()
+            .contains("<h1>The wishlist is archived</h1>"));
+    }
 }
@@ -0,0 +1,74 @@
+/*
+Module `actor` tells who a request acts for, for the middlewares that apply per user or per
+account. The API has no sessions: handlers read the acting user from the `user_id` or `owner_id`
+member of the body, or from the `user_id` query parameter, and so does this module.
+*/
+
+use axum::body::{to_bytes, Body, Bytes};
+
+use super::handlers::ApiError;
+use super::problem::{Problem, ProblemType};
+
+/// Reads a whole request body to look into it, rejecting one larger than `max_bytes` with 413
+/// Payload Too Large.
+pub async fn buffer_body(body: Body, max_bytes: usize) -> Result<Bytes, ApiError> {
+    to_bytes(body, max_bytes).await.map_err(|_| {
+        ApiError::PayloadTooLarge(Problem::new(
+            ProblemType::PayloadTooLarge,
+            format!("The request body is larger than {} bytes", max_bytes),
+        ))
+    })
+}
+
+/// Reads the acting user from the `user_id` or `owner_id` member of a JSON body, or else from
+/// the `user_id` query parameter.
+pub fn acting_user(query: Option<&str>, body: &[u8]) -> Option<String> {
+    let from_body = || {
+        let body: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(body).ok()?;
+        ["user_id", "owner_id"]
+            .iter()
+            .find_map(|member| body.get(*member)?.as_str().map(ToString::to_string))
+    };
+    let from_query = || {
+        url::form_urlencoded::parse(query?.as_bytes())
+            .find(|(name, _)| name == "user_id")
+            .map(|(_, user_id)| user_id.into_owned())
+    };
+    from_body().or_else(from_query)
+}
+
+/// Reads the account a login is for from the `email` member of a JSON body.
+pub fn login_account(body: &[u8]) -> Option<String> {
+    let body: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(body).ok()?;
+    body.get("email")?
+        .as_str()
+        .map(|email| email.trim().to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acting_user() {
+        assert_eq!(
+            acting_user(None, br#"{"owner_id": "alice", "name": "Birthday"}"#),
+            Some("alice".to_string())
+        );
+        assert_eq!(
+            acting_user(Some("limit=5&user_id=bob"), b""),
+            Some("bob".to_string())
+        );
+        assert_eq!(acting_user(None, br#"{"name": "Alice"}"#), None);
+        assert_eq!(acting_user(Some("limit=5"), b"not json"), None);
+    }
+
+    #[test]
+    fn test_login_account() {
+        assert_eq!(
+            login_account(br#"{"email": " Alice@Example.com", "password": "secret"}"#),
+            Some("alice@example.com".to_string())
+        );
+        assert_eq!(login_account(br#"{"password": "secret"}"#), None);
+    }
+}
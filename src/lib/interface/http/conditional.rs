@@ -0,0 +1,294 @@
+/*
+Module `conditional` implements conditional requests (RFC 9110 section 13) on versioned resources.
+Responses carrying a wishlist, an item or an image are tagged with an `ETag`. Updates may send the tag they
+last read in `If-Match` to fail with 412 Precondition Failed rather than overwrite someone else's
+change, and reads may send it in `If-None-Match` to get a bodiless 304 Not Modified when nothing
+changed.
+*/
+
+use std::fmt::{self, Display};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use axum::extract::{FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::domain::{Version, VersionMismatchError};
+
+use super::handlers::ApiError;
+use super::problem::{Problem, ProblemType};
+
+/// The tag of a representation of a resource, sent in the `ETag` header.
+///
+/// A strong tag is the [Version] of a single wishlist or item, so that a client can send it back
+/// to update exactly that version, or the name of a resource that never changes. A weak tag is a
+/// digest of a listing, which only tells whether the listing changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityTag {
+    weak: bool,
+    opaque: String,
+}
+
+impl EntityTag {
+    /// The strong tag of a resource at `version`.
+    pub fn version(version: Version) -> Self {
+        Self {
+            weak: false,
+            opaque: version.to_string(),
+        }
+    }
+
+    /// The strong tag of a resource that never changes, such as a stored image, named by `name`.
+    pub fn immutable(name: impl Display) -> Self {
+        Self {
+            weak: false,
+            opaque: name.to_string(),
+        }
+    }
+
+    /// The weak tag of a listing, a digest of what identifies its content.
+    pub fn digest(content: impl Hash) -> Self {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        Self {
+            weak: true,
+            opaque: format!("{:016x}", hasher.finish()),
+        }
+    }
+
+    /// Returns the version the tag names, if it is a strong tag produced by
+    /// [EntityTag::version].
+    pub fn as_version(&self) -> Option<Version> {
+        if self.weak {
+            return None;
+        }
+        self.opaque.parse().ok()
+    }
+
+    /// Compares the tags regardless of their weakness, as `If-None-Match` does.
+    pub fn weak_eq(&self, other: &Self) -> bool {
+        self.opaque == other.opaque
+    }
+
+    fn parse(tag: &str) -> Option<Self> {
+        let (weak, quoted) = match tag.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, tag),
+        };
+        let opaque = quoted.strip_prefix('"')?.strip_suffix('"')?;
+        if opaque.contains('"') {
+            return None;
+        }
+        Some(Self {
+            weak,
+            opaque: opaque.to_string(),
+        })
+    }
+}
+
+impl Display for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/")?;
+        }
+        write!(f, "\"{}\"", self.opaque)
+    }
+}
+
+impl From<&EntityTag> for HeaderValue {
+    fn from(tag: &EntityTag) -> Self {
+        HeaderValue::from_str(&tag.to_string()).expect("entity tags are visible ASCII")
+    }
+}
+
+/// The value of an `If-Match` or `If-None-Match` header: any current representation, or one of
+/// a list of tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Precondition {
+    Any,
+    Tags(Vec<EntityTag>),
+}
+
+impl Precondition {
+    /// Reads the header `name`, if present. Repeated headers are combined into one list.
+    fn from_headers(headers: &HeaderMap, name: &header::HeaderName) -> Result<Option<Self>, ()> {
+        let mut values = headers.get_all(name).iter().peekable();
+        if values.peek().is_none() {
+            return Ok(None);
+        }
+        let mut tags = Vec::new();
+        for value in values {
+            let value = value.to_str().map_err(|_| ())?;
+            for tag in value
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+            {
+                if tag == "*" {
+                    return Ok(Some(Self::Any));
+                }
+                tags.push(EntityTag::parse(tag).ok_or(())?);
+            }
+        }
+        if tags.is_empty() {
+            return Err(());
+        }
+        Ok(Some(Self::Tags(tags)))
+    }
+}
+
+/// The version an update expects the resource to be at, read from the `If-Match` header.
+///
+/// Without the header, or with `If-Match: *`, the update applies to whatever version is current.
+/// A tag that is not the version of a wishlist or an item can never match, so the request fails
+/// with 412 Precondition Failed at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IfMatch(pub Option<Version>);
+
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let tags = match Precondition::from_headers(&parts.headers, &header::IF_MATCH) {
+            Ok(None) | Ok(Some(Precondition::Any)) => return Ok(Self(None)),
+            Ok(Some(Precondition::Tags(tags))) => tags,
+            Err(()) => {
+                return Err(ApiError::BadRequest(Problem::new(
+                    ProblemType::MalformedRequest,
+                    "If-Match is not a list of entity tags",
+                )))
+            }
+        };
+        match tags.as_slice() {
+            [tag] => tag
+                .as_version()
+                .map(|version| Self(Some(version)))
+                .ok_or_else(|| {
+                    ApiError::PreconditionFailed(Problem::new(
+                        ProblemType::VersionMismatch,
+                        format!("If-Match {} is not the version of a resource", tag),
+                    ))
+                }),
+            _ => Err(ApiError::BadRequest(Problem::new(
+                ProblemType::MalformedRequest,
+                "If-Match must name a single version",
+            ))),
+        }
+    }
+}
+
+impl From<VersionMismatchError> for ApiError {
+    fn from(e: VersionMismatchError) -> Self {
+        Self::PreconditionFailed(Problem::new(
+            ProblemType::VersionMismatch,
+            format!(
+                "ID {} is at version {}, not {}; read it again before updating it",
+                e.id, e.actual, e.expected
+            ),
+        ))
+    }
+}
+
+/// Answers a read with 304 Not Modified, keeping only its `ETag` and `Cache-Control`, when the
+/// client already has the current representation according to `If-None-Match`.
+pub async fn not_modified(request: Request, next: Next) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+    let precondition = Precondition::from_headers(request.headers(), &header::IF_NONE_MATCH);
+    let response = next.run(request).await;
+    let Ok(Some(precondition)) = precondition else {
+        return response;
+    };
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+    let Some(etag) = response.headers().get(header::ETAG).cloned() else {
+        return response;
+    };
+    let matches = match &precondition {
+        Precondition::Any => true,
+        Precondition::Tags(tags) => etag
+            .to_str()
+            .ok()
+            .and_then(EntityTag::parse)
+            .is_some_and(|current| tags.iter().any(|tag| tag.weak_eq(&current))),
+    };
+    if !matches {
+        return response;
+    }
+    let mut not_modified = (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+    if let Some(cache_control) = response.headers().get(header::CACHE_CONTROL) {
+        not_modified
+            .headers_mut()
+            .insert(header::CACHE_CONTROL, cache_control.clone());
+    }
+    not_modified
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Request as HttpRequest;
+
+    use super::*;
+
+    async fn if_match(values: &[&str]) -> Result<IfMatch, ApiError> {
+        let mut request = HttpRequest::builder();
+        for value in values {
+            request = request.header(header::IF_MATCH, *value);
+        }
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        IfMatch::from_request_parts(&mut parts, &()).await
+    }
+
+    #[test]
+    fn test_entity_tags() {
+        assert_eq!(EntityTag::version(3).to_string(), "\"3\"");
+        assert_eq!(EntityTag::parse("\"3\""), Some(EntityTag::version(3)));
+        assert_eq!(EntityTag::parse("\"3\"").unwrap().as_version(), Some(3));
+
+        let digest = EntityTag::digest(("listing", 3));
+        assert_eq!(digest, EntityTag::digest(("listing", 3)));
+        assert_ne!(digest, EntityTag::digest(("listing", 4)));
+        assert!(digest.to_string().starts_with("W/\""));
+        assert_eq!(EntityTag::parse(&digest.to_string()), Some(digest.clone()));
+        assert_eq!(digest.as_version(), None);
+
+        let image = EntityTag::immutable("image-small");
+        assert_eq!(image.to_string(), "\"image-small\"");
+        assert_eq!(image.as_version(), None);
+
+        assert_eq!(EntityTag::parse("3"), None);
+        assert_eq!(EntityTag::parse("\"3"), None);
+    }
+
+    #[tokio::test]
+    async fn test_if_match() {
+        assert_eq!(if_match(&[]).await, Ok(IfMatch(None)));
+        assert_eq!(if_match(&["*"]).await, Ok(IfMatch(None)));
+        assert_eq!(if_match(&["\"7\""]).await, Ok(IfMatch(Some(7))));
+
+        assert!(matches!(
+            if_match(&["W/\"7\""]).await,
+            Err(ApiError::PreconditionFailed(_))
+        ));
+        assert!(matches!(
+            if_match(&["\"seven\""]).await,
+            Err(ApiError::PreconditionFailed(_))
+        ));
+        assert!(matches!(
+            if_match(&["\"7\", \"8\""]).await,
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            if_match(&["\"7\"", "\"8\""]).await,
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            if_match(&["7"]).await,
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,141 @@
+/*
+Module `graphql` specifies the GraphQL endpoint of the API, which lets clients fetch wishlists with
+their items, owners and reservation counts in one round-trip. The schema sits over the same
+[UseCases] as the REST handlers, parses arguments with the same HTTP request types, and reports
+errors with the same problem details, in the `extensions` of each GraphQL error.
+*/
+
+mod loader;
+mod mutation;
+mod object;
+mod query;
+
+use std::sync::Arc;
+
+use async_graphql::dataloader::{DataLoader, HashMapCache};
+use async_graphql::{BatchRequest, EmptySubscription, Schema, ID};
+use async_graphql_axum::{GraphQLBatchRequest, GraphQLResponse};
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Extension, Router};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+
+use super::handlers::ApiError;
+use super::problem::{FieldErrors, Problem, ProblemType};
+use super::AppState;
+
+use loader::{ProfileLoader, WishlistViewLoader};
+use mutation::Mutation;
+use query::Query;
+
+pub type ApiSchema<UC> = Schema<Query<UC>, Mutation<UC>, EmptySubscription>;
+
+/// How deep and how costly a GraphQL query may be, and how many queries a batch may hold. Queries
+/// over either limit are rejected before any field is resolved, and larger batches before any
+/// query runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphqlLimits {
+    pub max_depth: usize,
+    pub max_complexity: usize,
+    pub max_batch: usize,
+}
+
+impl Default for GraphqlLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 10,
+            max_complexity: 500,
+            max_batch: 10,
+        }
+    }
+}
+
+/// The routes of the GraphQL endpoint, to nest along the REST routes.
+pub fn graphql_routes<UC: UseCases>(limits: GraphqlLimits) -> Router<AppState<UC>> {
+    let schema: ApiSchema<UC> =
+        Schema::build(Query::default(), Mutation::default(), EmptySubscription)
+            .limit_depth(limits.max_depth)
+            .limit_complexity(limits.max_complexity)
+            .finish();
+    Router::new()
+        .route("/graphql", post(graphql::<UC>))
+        .layer(Extension(schema))
+        .layer(Extension(limits))
+}
+
+/// Executes a GraphQL query, or a batch of them. A batch of more than
+/// [GraphqlLimits::max_batch] queries is rejected with 413 Payload Too Large. Each request gets
+/// its own data loaders, so lookups are batched and cached within the request but never shared
+/// between users.
+async fn graphql<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    Extension(schema): Extension<ApiSchema<UC>>,
+    Extension(limits): Extension<GraphqlLimits>,
+    req: GraphQLBatchRequest,
+) -> Result<GraphQLResponse, ApiError> {
+    if let BatchRequest::Batch(queries) = &req.0 {
+        if queries.len() > limits.max_batch {
+            return Err(ApiError::PayloadTooLarge(Problem::new(
+                ProblemType::BatchTooLarge,
+                format!(
+                    "The batch has {} queries, more than {}",
+                    queries.len(),
+                    limits.max_batch
+                ),
+            )));
+        }
+    }
+    let services = state.services;
+    let wishlist_views = DataLoader::with_cache(
+        WishlistViewLoader::new(services.clone()),
+        tokio::spawn,
+        HashMapCache::default(),
+    );
+    let profiles = DataLoader::with_cache(
+        ProfileLoader::new(services.clone()),
+        tokio::spawn,
+        HashMapCache::default(),
+    );
+    let req = req
+        .into_inner()
+        .data(services)
+        .data(Arc::new(wishlist_views))
+        .data(Arc::new(profiles));
+    Ok(schema.execute_batch(req).await.into())
+}
+
+/// Turns an [ApiError] into a GraphQL error whose message is the detail of the problem, and whose
+/// extensions hold the rest of the problem details.
+fn problem(e: impl Into<ApiError>) -> async_graphql::Error {
+    let (_, details) = e.into().into_problem_details();
+    let mut error = async_graphql::Error::new(details.detail());
+    if let Ok(serde_json::Value::Object(members)) = serde_json::to_value(&details) {
+        let extensions = error.extensions.get_or_insert_with(Default::default);
+        for (name, value) in members.into_iter().filter(|(name, _)| name != "detail") {
+            if let Ok(value) = async_graphql::Value::from_json(value) {
+                extensions.set(name, value);
+            }
+        }
+    }
+    error
+}
+
+#[derive(Debug, Clone, Error)]
+enum ParseGraphqlArgumentError {
+    #[error("id {0} is invalid")]
+    Id(String),
+}
+
+/// Parses the ID given as the argument `name`.
+fn parse_id(name: &str, id: &ID) -> Result<Uuid, async_graphql::Error> {
+    let mut errors = FieldErrors::<ParseGraphqlArgumentError>::new();
+    errors
+        .check_parameter(
+            name,
+            Uuid::parse_str(id).map_err(|_| ParseGraphqlArgumentError::Id(id.to_string())),
+        )
+        .ok_or_else(|| problem(errors))
+}
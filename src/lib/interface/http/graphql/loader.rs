@@ -0,0 +1,118 @@
+/*
+Module `loader` specifies the data loaders of the GraphQL schema. Each collects the keys the
+fields of a query ask for, and looks them all up with one use case call per viewer, so a list of
+wishlists costs the same few repository lookups as a single one.
+*/
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_graphql::dataloader::{DataLoader, HashMapCache, Loader};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    FindProfilesError, FindProfilesRequest, Profile, ViewWishlistsError, ViewWishlistsRequest,
+    WishlistView,
+};
+use crate::interface::http::handlers::ApiError;
+
+use super::problem;
+
+impl From<ViewWishlistsError> for ApiError {
+    fn from(e: ViewWishlistsError) -> Self {
+        match e {
+            ViewWishlistsError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+impl From<FindProfilesError> for ApiError {
+    fn from(e: FindProfilesError) -> Self {
+        match e {
+            FindProfilesError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The wishlist views of a request, cached for its lifetime.
+pub type WishlistViews<UC> = DataLoader<WishlistViewLoader<UC>, HashMapCache>;
+
+/// The profiles of a request, cached for its lifetime.
+pub type Profiles<UC> = DataLoader<ProfileLoader<UC>, HashMapCache>;
+
+/// Groups `(viewer ID, ID)` keys by viewer, since every lookup is made on behalf of one user.
+fn by_viewer(keys: &[(Uuid, Uuid)]) -> HashMap<Uuid, Vec<Uuid>> {
+    let mut ids: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for &(viewer_id, id) in keys {
+        ids.entry(viewer_id).or_default().push(id);
+    }
+    ids
+}
+
+/// Loads wishlists with their items as a viewer sees them, keyed by `(viewer ID, wishlist ID)`.
+/// Wishlists that do not exist or that the viewer may not see are left out.
+pub struct WishlistViewLoader<UC: UseCases> {
+    services: Arc<UC>,
+}
+
+impl<UC: UseCases> WishlistViewLoader<UC> {
+    pub fn new(services: Arc<UC>) -> Self {
+        Self { services }
+    }
+}
+
+impl<UC: UseCases> Loader<(Uuid, Uuid)> for WishlistViewLoader<UC> {
+    type Value = WishlistView;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        keys: &[(Uuid, Uuid)],
+    ) -> Result<HashMap<(Uuid, Uuid), WishlistView>, Self::Error> {
+        let mut views = HashMap::new();
+        for (viewer_id, wishlist_ids) in by_viewer(keys) {
+            let req = ViewWishlistsRequest::new(viewer_id, wishlist_ids);
+            for view in self.services.view_wishlists(&req).await.map_err(problem)? {
+                views.insert((viewer_id, view.wishlist().id()), view);
+            }
+        }
+        Ok(views)
+    }
+}
+
+/// Loads profiles as a viewer sees them, keyed by `(viewer ID, user ID)`.
+pub struct ProfileLoader<UC: UseCases> {
+    services: Arc<UC>,
+}
+
+impl<UC: UseCases> ProfileLoader<UC> {
+    pub fn new(services: Arc<UC>) -> Self {
+        Self { services }
+    }
+}
+
+impl<UC: UseCases> Loader<(Uuid, Uuid)> for ProfileLoader<UC> {
+    type Value = Profile;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        keys: &[(Uuid, Uuid)],
+    ) -> Result<HashMap<(Uuid, Uuid), Profile>, Self::Error> {
+        let mut profiles = HashMap::new();
+        for (viewer_id, user_ids) in by_viewer(keys) {
+            let req = FindProfilesRequest::new(viewer_id, user_ids);
+            for profile in self.services.find_profiles(&req).await.map_err(problem)? {
+                profiles.insert((viewer_id, profile.user_id()), profile);
+            }
+        }
+        Ok(profiles)
+    }
+}
@@ -0,0 +1,262 @@
+/*
+Module `mutation` specifies the root mutation type of the GraphQL schema. Each mutation takes the
+body of the matching REST creation request as its input, and validates it the same way: invalid
+members are listed in the `errors` extension, with pointers into the input.
+*/
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_graphql::{Context, InputObject, Object, ID};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::interface::http::handlers::create_exchange::CreateExchangeHttpRequestBody;
+use crate::interface::http::handlers::create_group::CreateGroupHttpRequestBody;
+use crate::interface::http::handlers::create_item::{CreateItemHttpRequestBody, ItemAttributeData};
+use crate::interface::http::handlers::create_section::CreateSectionHttpRequestBody;
+use crate::interface::http::handlers::create_user::CreateUserHttpRequestBody;
+use crate::interface::http::handlers::create_wishlist::CreateWishlistHttpRequestBody;
+
+use super::object::{Exchange, Group, Item, Section, User, Wishlist};
+use super::{parse_id, problem};
+
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateUserInput {
+    email: String,
+    password: String,
+}
+
+impl From<CreateUserInput> for CreateUserHttpRequestBody {
+    fn from(input: CreateUserInput) -> Self {
+        Self {
+            email: input.email,
+            password: input.password,
+        }
+    }
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateWishlistInput {
+    name: String,
+    owner_id: ID,
+    private: bool,
+}
+
+impl From<CreateWishlistInput> for CreateWishlistHttpRequestBody {
+    fn from(input: CreateWishlistInput) -> Self {
+        Self {
+            name: input.name,
+            owner_id: input.owner_id.to_string(),
+            private: input.private,
+        }
+    }
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct ItemAttributeInput {
+    key: String,
+    value: String,
+}
+
+/// Besides the `userId` of the owner, only `linkUrl` is required: missing fields are pre-filled
+/// from the linked page.
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateItemInput {
+    user_id: ID,
+    title: Option<String>,
+    link_url: String,
+    image_url: Option<String>,
+    image_id: Option<Uuid>,
+    price: Option<Decimal>,
+    priority: Option<String>,
+    notes: Option<String>,
+    #[graphql(default)]
+    attributes: Vec<ItemAttributeInput>,
+}
+
+impl From<CreateItemInput> for CreateItemHttpRequestBody {
+    fn from(input: CreateItemInput) -> Self {
+        Self {
+            user_id: input.user_id.to_string(),
+            title: input.title,
+            link_url: input.link_url,
+            image_url: input.image_url,
+            image_id: input.image_id,
+            price: input.price,
+            priority: input.priority,
+            notes: input.notes,
+            attributes: input
+                .attributes
+                .into_iter()
+                .map(|attribute| ItemAttributeData {
+                    key: attribute.key,
+                    value: attribute.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateSectionInput {
+    user_id: ID,
+    name: String,
+}
+
+impl From<CreateSectionInput> for CreateSectionHttpRequestBody {
+    fn from(input: CreateSectionInput) -> Self {
+        Self {
+            user_id: input.user_id.to_string(),
+            name: input.name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateExchangeInput {
+    user_id: ID,
+    name: String,
+}
+
+impl From<CreateExchangeInput> for CreateExchangeHttpRequestBody {
+    fn from(input: CreateExchangeInput) -> Self {
+        Self {
+            user_id: input.user_id.to_string(),
+            name: input.name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateGroupInput {
+    user_id: ID,
+    name: String,
+}
+
+impl From<CreateGroupInput> for CreateGroupHttpRequestBody {
+    fn from(input: CreateGroupInput) -> Self {
+        Self {
+            user_id: input.user_id.to_string(),
+            name: input.name,
+        }
+    }
+}
+
+pub struct Mutation<UC: UseCases>(PhantomData<fn() -> UC>);
+
+impl<UC: UseCases> Default for Mutation<UC> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[Object]
+impl<UC: UseCases> Mutation<UC> {
+    /// Like `POST /api/authors`.
+    async fn create_user(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateUserInput,
+    ) -> async_graphql::Result<User> {
+        let req = CreateUserHttpRequestBody::from(input)
+            .try_into_domain()
+            .map_err(problem)?;
+        let user = ctx
+            .data_unchecked::<Arc<UC>>()
+            .create_user(&req)
+            .await
+            .map_err(problem)?;
+        Ok((&user).into())
+    }
+
+    /// Like `POST /api/wishlists`. The new wishlist is seen as its owner sees it.
+    async fn create_wishlist(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateWishlistInput,
+    ) -> async_graphql::Result<Wishlist<UC>> {
+        let req = CreateWishlistHttpRequestBody::from(input)
+            .try_into_domain()
+            .map_err(problem)?;
+        let wishlist = ctx
+            .data_unchecked::<Arc<UC>>()
+            .create_wishlist(&req)
+            .await
+            .map_err(problem)?;
+        Ok(Wishlist::new(wishlist.owner_id(), &wishlist))
+    }
+
+    /// Like `POST /api/wishlists/{wishlist_id}/items`.
+    async fn create_item(
+        &self,
+        ctx: &Context<'_>,
+        wishlist_id: ID,
+        input: CreateItemInput,
+    ) -> async_graphql::Result<Item> {
+        let wishlist_id = parse_id("wishlistId", &wishlist_id)?;
+        let req = CreateItemHttpRequestBody::from(input)
+            .try_into_domain(wishlist_id)
+            .map_err(problem)?;
+        let item = ctx
+            .data_unchecked::<Arc<UC>>()
+            .create_item(&req)
+            .await
+            .map_err(problem)?;
+        Ok((&item).into())
+    }
+
+    /// Like `POST /api/wishlists/{wishlist_id}/sections`.
+    async fn create_section(
+        &self,
+        ctx: &Context<'_>,
+        wishlist_id: ID,
+        input: CreateSectionInput,
+    ) -> async_graphql::Result<Section> {
+        let wishlist_id = parse_id("wishlistId", &wishlist_id)?;
+        let req = CreateSectionHttpRequestBody::from(input)
+            .try_into_domain(wishlist_id)
+            .map_err(problem)?;
+        let section = ctx
+            .data_unchecked::<Arc<UC>>()
+            .create_section(&req)
+            .await
+            .map_err(problem)?;
+        Ok((&section).into())
+    }
+
+    /// Like `POST /api/exchanges`.
+    async fn create_exchange(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateExchangeInput,
+    ) -> async_graphql::Result<Exchange> {
+        let req = CreateExchangeHttpRequestBody::from(input)
+            .try_into_domain()
+            .map_err(problem)?;
+        let exchange = ctx
+            .data_unchecked::<Arc<UC>>()
+            .create_exchange(&req)
+            .await
+            .map_err(problem)?;
+        Ok((&exchange).into())
+    }
+
+    /// Like `POST /api/groups`.
+    async fn create_group(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateGroupInput,
+    ) -> async_graphql::Result<Group> {
+        let req = CreateGroupHttpRequestBody::from(input)
+            .try_into_domain()
+            .map_err(problem)?;
+        let group = ctx
+            .data_unchecked::<Arc<UC>>()
+            .create_group(&req)
+            .await
+            .map_err(problem)?;
+        Ok((&group).into())
+    }
+}
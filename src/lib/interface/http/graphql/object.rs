@@ -0,0 +1,285 @@
+/*
+Module `object` specifies the output types of the GraphQL schema. Scalar fields are read the way
+the REST handlers present them; the items, owner and reservation count of a wishlist go through
+the data loaders of the request.
+*/
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_graphql::{Context, Object, SimpleObject, ID};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain;
+use crate::interface::http::handlers::create_item::ItemResponseData;
+use crate::interface::http::handlers::duplicate_wishlist::WishlistResponseData;
+use crate::interface::http::handlers::ApiError;
+use crate::interface::http::problem::{Problem, ProblemType};
+
+use super::loader::{Profiles, WishlistViews};
+use super::problem;
+
+/// A wishlist as a user sees it.
+pub struct Wishlist<UC: UseCases> {
+    viewer_id: Uuid,
+    id: Uuid,
+    owner_id: Uuid,
+    data: WishlistResponseData,
+    services: PhantomData<fn() -> UC>,
+}
+
+impl<UC: UseCases> Wishlist<UC> {
+    pub fn new(viewer_id: Uuid, wishlist: &domain::Wishlist) -> Self {
+        Self {
+            viewer_id,
+            id: wishlist.id(),
+            owner_id: wishlist.owner_id(),
+            data: wishlist.into(),
+            services: PhantomData,
+        }
+    }
+
+    async fn view(&self, ctx: &Context<'_>) -> async_graphql::Result<domain::WishlistView> {
+        ctx.data_unchecked::<Arc<WishlistViews<UC>>>()
+            .load_one((self.viewer_id, self.id))
+            .await?
+            .ok_or_else(|| {
+                problem(ApiError::NotFound(Problem::new(
+                    ProblemType::WishlistNotFound,
+                    format!("Wishlist ID {} does not exist", self.id),
+                )))
+            })
+    }
+}
+
+#[Object]
+impl<UC: UseCases> Wishlist<UC> {
+    async fn id(&self) -> ID {
+        self.data.id.clone().into()
+    }
+
+    async fn owner_id(&self) -> ID {
+        self.data.owner_id.clone().into()
+    }
+
+    async fn name(&self) -> &str {
+        &self.data.name
+    }
+
+    async fn slug(&self) -> &str {
+        &self.data.slug
+    }
+
+    async fn private(&self) -> bool {
+        self.data.private
+    }
+
+    async fn template(&self) -> bool {
+        self.data.template
+    }
+
+    async fn archived(&self) -> bool {
+        self.data.archived
+    }
+
+    async fn item_count(&self) -> usize {
+        self.data.item_count
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.data.updated_at
+    }
+
+    async fn version(&self) -> u64 {
+        self.data.version
+    }
+
+    /// The profile of the owner, with the fields they keep from the user left out.
+    async fn owner(&self, ctx: &Context<'_>) -> async_graphql::Result<Profile> {
+        let profile = ctx
+            .data_unchecked::<Arc<Profiles<UC>>>()
+            .load_one((self.viewer_id, self.owner_id))
+            .await?
+            .unwrap_or_else(|| domain::Profile::new(self.owner_id));
+        Ok((&profile).into())
+    }
+
+    /// Every item, in the owner's order.
+    async fn items(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Item>> {
+        Ok(self
+            .view(ctx)
+            .await?
+            .items()
+            .iter()
+            .map(Item::from)
+            .collect())
+    }
+
+    /// How many items are reserved, or null for the owner, from whom reservations are kept.
+    async fn reservation_count(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<usize>> {
+        Ok(self.view(ctx).await?.reservation_count())
+    }
+}
+
+/// A page of wishlists, and the cursor of the next page if there is one.
+pub struct WishlistPage<UC: UseCases> {
+    wishlists: Vec<Wishlist<UC>>,
+    next_cursor: Option<String>,
+}
+
+impl<UC: UseCases> WishlistPage<UC> {
+    pub fn new(viewer_id: Uuid, page: &domain::Page<domain::Wishlist>) -> Self {
+        Self {
+            wishlists: page
+                .entries()
+                .iter()
+                .map(|wishlist| Wishlist::new(viewer_id, wishlist))
+                .collect(),
+            next_cursor: page.next_cursor().map(|cursor| cursor.to_string()),
+        }
+    }
+}
+
+#[Object]
+impl<UC: UseCases> WishlistPage<UC> {
+    async fn wishlists(&self) -> &[Wishlist<UC>] {
+        &self.wishlists
+    }
+
+    async fn next_cursor(&self) -> Option<&str> {
+        self.next_cursor.as_deref()
+    }
+}
+
+/// A variant attribute of an item, e.g. `{ key: "size", value: "M" }`.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ItemAttribute {
+    key: String,
+    value: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Item {
+    id: ID,
+    wishlist_id: ID,
+    title: String,
+    link_url: String,
+    image_url: Option<String>,
+    image_id: Option<ID>,
+    price: Option<Decimal>,
+    priority: String,
+    notes: Option<String>,
+    attributes: Vec<ItemAttribute>,
+    received: bool,
+    version: u64,
+}
+
+impl From<&domain::Item> for Item {
+    fn from(item: &domain::Item) -> Self {
+        let data = ItemResponseData::from(item);
+        Self {
+            id: data.id.into(),
+            wishlist_id: data.wishlist_id.into(),
+            title: data.title,
+            link_url: data.link_url,
+            image_url: data.image_url,
+            image_id: data.image_id.map(ID::from),
+            price: data.price,
+            priority: data.priority,
+            notes: data.notes,
+            attributes: data
+                .attributes
+                .into_iter()
+                .map(|attribute| ItemAttribute {
+                    key: attribute.key,
+                    value: attribute.value,
+                })
+                .collect(),
+            received: data.received,
+            version: data.version,
+        }
+    }
+}
+
+/// The public part of a profile: the fields its owner shows to the user.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Profile {
+    user_id: ID,
+    display_name: Option<String>,
+    avatar_id: Option<ID>,
+    birthday: Option<NaiveDate>,
+}
+
+impl From<&domain::Profile> for Profile {
+    fn from(profile: &domain::Profile) -> Self {
+        Self {
+            user_id: profile.user_id().into(),
+            display_name: profile.display_name().map(ToString::to_string),
+            avatar_id: profile.avatar_id().map(ID::from),
+            birthday: profile.birthday(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct User {
+    id: ID,
+}
+
+impl From<&domain::User> for User {
+    fn from(user: &domain::User) -> Self {
+        Self {
+            id: (*user.id()).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Section {
+    id: ID,
+    name: String,
+}
+
+impl From<&domain::WishlistSection> for Section {
+    fn from(section: &domain::WishlistSection) -> Self {
+        Self {
+            id: section.id().into(),
+            name: section.name().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Exchange {
+    id: ID,
+    organizer_id: ID,
+    name: String,
+}
+
+impl From<&domain::Exchange> for Exchange {
+    fn from(exchange: &domain::Exchange) -> Self {
+        Self {
+            id: exchange.id().into(),
+            organizer_id: exchange.organizer_id().into(),
+            name: exchange.name().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Group {
+    id: ID,
+    name: String,
+}
+
+impl From<&domain::Group> for Group {
+    fn from(group: &domain::Group) -> Self {
+        Self {
+            id: group.id().into(),
+            name: group.name().to_string(),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+/*
+Module `query` specifies the root query type of the GraphQL schema. Like the REST routes, every
+field is asked on behalf of the user given as `userId`, and sees only what they may see.
+*/
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_graphql::{Context, Object, ID};
+
+use crate::application::UseCases;
+use crate::interface::http::handlers::follow_feed::FollowFeedHttpQuery;
+use crate::interface::http::handlers::list_templates::ListTemplatesHttpQuery;
+
+use super::loader::WishlistViews;
+use super::object::{Wishlist, WishlistPage};
+use super::{parse_id, problem};
+
+pub struct Query<UC: UseCases>(PhantomData<fn() -> UC>);
+
+impl<UC: UseCases> Default for Query<UC> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[Object]
+impl<UC: UseCases> Query<UC> {
+    /// A wishlist, or null if it does not exist or the user may not see it.
+    async fn wishlist(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        id: ID,
+    ) -> async_graphql::Result<Option<Wishlist<UC>>> {
+        let viewer_id = parse_id("userId", &user_id)?;
+        let wishlist_id = parse_id("id", &id)?;
+        let view = ctx
+            .data_unchecked::<Arc<WishlistViews<UC>>>()
+            .load_one((viewer_id, wishlist_id))
+            .await?;
+        Ok(view.map(|view| Wishlist::new(viewer_id, view.wishlist())))
+    }
+
+    /// The wishlists among `ids` that exist and that the user may see, in the order asked.
+    async fn wishlists(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        ids: Vec<ID>,
+    ) -> async_graphql::Result<Vec<Wishlist<UC>>> {
+        let viewer_id = parse_id("userId", &user_id)?;
+        let keys = ids
+            .iter()
+            .map(|id| Ok((viewer_id, parse_id("ids", id)?)))
+            .collect::<async_graphql::Result<Vec<_>>>()?;
+        let mut views = ctx
+            .data_unchecked::<Arc<WishlistViews<UC>>>()
+            .load_many(keys.iter().copied())
+            .await?;
+        Ok(keys
+            .iter()
+            .filter_map(|key| views.remove(key))
+            .map(|view| Wishlist::new(viewer_id, view.wishlist()))
+            .collect())
+    }
+
+    /// A page of the public templates, like `GET /api/wishlists/templates`.
+    #[allow(clippy::too_many_arguments)]
+    async fn templates(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        visibility: Option<String>,
+        occasion: Option<String>,
+        sort: Option<String>,
+        order: Option<String>,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<WishlistPage<UC>> {
+        let viewer_id = parse_id("userId", &user_id)?;
+        let query = ListTemplatesHttpQuery {
+            visibility,
+            occasion,
+            sort,
+            order,
+            cursor,
+            limit,
+        }
+        .try_into_domain()
+        .map_err(problem)?;
+        let page = ctx
+            .data_unchecked::<Arc<UC>>()
+            .list_templates(&query)
+            .await
+            .map_err(problem)?;
+        Ok(WishlistPage::new(viewer_id, &page))
+    }
+
+    /// A page of the wishlists of the users the user follows, like `GET /api/feed`.
+    #[allow(clippy::too_many_arguments)]
+    async fn follow_feed(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        visibility: Option<String>,
+        occasion: Option<String>,
+        sort: Option<String>,
+        order: Option<String>,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<WishlistPage<UC>> {
+        let req = FollowFeedHttpQuery {
+            user_id: user_id.to_string(),
+            visibility,
+            occasion,
+            sort,
+            order,
+            cursor,
+            limit,
+        }
+        .try_into_domain()
+        .map_err(problem)?;
+        let page = ctx
+            .data_unchecked::<Arc<UC>>()
+            .follow_feed(&req)
+            .await
+            .map_err(problem)?;
+        Ok(WishlistPage::new(req.user_id(), &page))
+    }
+}
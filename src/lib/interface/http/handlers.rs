@@ -1,46 +1,198 @@
+pub mod add_exclusion;
+pub mod add_participant;
+pub mod answer_follow_request;
+pub mod audit_draw;
+pub mod block_user;
+pub mod cancel_account_deletion;
+pub mod copy_item;
+pub mod create_exchange;
+pub mod create_group;
+pub mod create_item;
+pub mod create_section;
+pub mod create_share_link;
 pub mod create_user;
 pub mod create_wishlist;
+pub mod delete_account;
+pub mod delete_section;
+pub mod delete_webhook;
+pub mod draw_exchange;
+pub mod duplicate_wishlist;
+pub mod export_account;
+pub mod find_image;
+pub mod find_price_history;
+pub mod find_profile;
+pub mod follow_feed;
+pub mod follow_user;
+pub mod group_feed;
+pub mod invite_member;
+pub mod list_follow_requests;
+pub mod list_inbox;
+pub mod list_invitations;
+pub mod list_items;
+pub mod list_templates;
+pub mod list_webhook_deliveries;
+pub mod list_webhooks;
+pub mod mark_item_received;
+pub mod move_item;
+pub mod move_item_to_section;
+pub mod register_webhook;
+pub mod remove_member;
+pub mod reorder_wishlist;
+pub mod request_account_confirmation;
+pub mod reserve_item;
+pub mod respond_to_invitation;
+pub mod reveal_recipient;
+pub mod revoke_share_link;
+pub mod set_follow_settings;
+pub mod set_member_role;
+pub mod set_profile;
+pub mod set_reminder_preferences;
+pub mod set_wishlist_occasion;
+pub mod set_wishlist_template;
+pub mod share_wishlist;
+pub mod subscribe_reminder;
+pub mod suggest_occasions;
+pub mod unblock_user;
+pub mod unfollow_user;
+pub mod unshare_wishlist;
+pub mod unsubscribe_reminder;
+pub mod upload_image;
+pub mod watch_item_price;
+pub mod watch_items;
 
-use axum::{routing::post, Json, Router};
+use add_exclusion::add_exclusion;
+use add_participant::add_participant;
+use answer_follow_request::answer_follow_request;
+use audit_draw::audit_draw;
+use axum::{
+    routing::{delete, get, post, put},
+    Json, Router,
+};
+use block_user::block_user;
+use cancel_account_deletion::cancel_account_deletion;
+use copy_item::copy_item;
+use create_exchange::create_exchange;
+use create_group::create_group;
+use create_item::create_item;
+use create_section::create_section;
+use create_share_link::create_share_link;
 use create_user::create_user;
 use create_wishlist::create_wishlist;
+use delete_account::delete_account;
+use delete_section::delete_section;
+use delete_webhook::delete_webhook;
+use draw_exchange::draw_exchange;
+use duplicate_wishlist::duplicate_wishlist;
+use export_account::export_account;
+use find_image::find_image;
+use find_price_history::find_price_history;
+use find_profile::find_profile;
+use follow_feed::follow_feed;
+use follow_user::follow_user;
+use group_feed::group_feed;
+use invite_member::invite_member;
+use list_follow_requests::list_follow_requests;
+use list_inbox::list_inbox;
+use list_invitations::list_invitations;
+use list_items::list_items;
+use list_templates::list_templates;
+use list_webhook_deliveries::list_webhook_deliveries;
+use list_webhooks::list_webhooks;
+use mark_item_received::mark_item_received;
+use move_item::move_item;
+use move_item_to_section::move_item_to_section;
+use register_webhook::register_webhook;
+use remove_member::remove_member;
+use reorder_wishlist::reorder_wishlist;
+use request_account_confirmation::request_account_confirmation;
+use reserve_item::reserve_item;
+use respond_to_invitation::respond_to_invitation;
+use reveal_recipient::reveal_recipient;
+use revoke_share_link::revoke_share_link;
 use serde::Serialize;
+use set_follow_settings::set_follow_settings;
+use set_member_role::set_member_role;
+use set_profile::set_profile;
+use set_reminder_preferences::set_reminder_preferences;
+use set_wishlist_occasion::set_wishlist_occasion;
+use set_wishlist_template::set_wishlist_template;
+use share_wishlist::share_wishlist;
+use subscribe_reminder::subscribe_reminder;
+use suggest_occasions::suggest_occasions;
+use unblock_user::unblock_user;
+use unfollow_user::unfollow_user;
+use unshare_wishlist::unshare_wishlist;
+use unsubscribe_reminder::unsubscribe_reminder;
+use upload_image::upload_image;
+use utoipa::ToSchema;
+use watch_item_price::watch_item_price;
+use watch_items::{watch_items, watch_items_socket};
 
-use axum::http::StatusCode;
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::extract::{FromRequest, FromRequestParts};
+use axum::http::{header, HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
+use std::fmt::Display;
 
 use crate::application::UseCases;
 
+use super::conditional::EntityTag;
+use super::problem::{
+    FieldErrors, InvalidField, Problem, ProblemDetails, ProblemType, PROBLEM_JSON,
+};
 use super::AppState;
 
 #[derive(Debug, Clone)]
-pub struct ApiSuccess<T: Serialize + PartialEq>(StatusCode, Json<ApiResponseBody<T>>);
+pub struct ApiSuccess<T: Serialize + PartialEq>(
+    StatusCode,
+    Json<ApiResponseBody<T>>,
+    Option<EntityTag>,
+);
 
 impl<T> PartialEq for ApiSuccess<T>
 where
     T: Serialize + PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0 && self.1 .0 == other.1 .0
+        self.0 == other.0 && self.1 .0 == other.1 .0 && self.2 == other.2
     }
 }
 
 impl<T: Serialize + PartialEq> ApiSuccess<T> {
     fn new(status: StatusCode, data: T) -> Self {
-        ApiSuccess(status, Json(ApiResponseBody::new(status, data)))
+        ApiSuccess(status, Json(ApiResponseBody::new(status, data)), None)
+    }
+
+    /// Tags the response with the `ETag` of the representation it carries.
+    fn with_etag(self, etag: EntityTag) -> Self {
+        ApiSuccess(self.0, self.1, Some(etag))
     }
 }
 
 impl<T: Serialize + PartialEq> IntoResponse for ApiSuccess<T> {
     fn into_response(self) -> Response {
-        (self.0, self.1).into_response()
+        match self.2 {
+            Some(etag) => {
+                (self.0, [(header::ETAG, HeaderValue::from(&etag))], self.1).into_response()
+            }
+            None => (self.0, self.1).into_response(),
+        }
     }
 }
 
+/// An error response. Every error but internal ones carries the [Problem] reported to the client.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ApiError {
     InternalServerError(String),
-    UnprocessableEntity(String),
+    BadRequest(Problem),
+    Forbidden(Problem),
+    NotFound(Problem),
+    Conflict(Problem),
+    PreconditionFailed(Problem),
+    PayloadTooLarge(Problem),
+    UnsupportedMediaType(Problem),
+    UnprocessableEntity(Problem),
+    TooManyRequests(Problem),
 }
 
 impl From<anyhow::Error> for ApiError {
@@ -49,8 +201,106 @@ impl From<anyhow::Error> for ApiError {
     }
 }
 
-/// Generic response structure shared by all API responses.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+impl<E: Display> From<FieldErrors<E>> for ApiError {
+    fn from(errors: FieldErrors<E>) -> Self {
+        Self::UnprocessableEntity(Problem::invalid_fields(errors.into_invalid_fields()))
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::JsonDataError(err) => {
+                let invalid_field =
+                    match find_source::<serde_path_to_error::Error<serde_json::Error>>(&err) {
+                        Some(err) => {
+                            InvalidField::body(json_pointer(err.path()), err.inner().to_string())
+                        }
+                        None => InvalidField::body("", err.body_text()),
+                    };
+                Self::UnprocessableEntity(Problem::invalid_fields(vec![invalid_field]))
+            }
+            JsonRejection::MissingJsonContentType(err) => Self::UnsupportedMediaType(Problem::new(
+                ProblemType::UnsupportedMediaType,
+                err.body_text(),
+            )),
+            rejection if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                Self::PayloadTooLarge(Problem::new(
+                    ProblemType::PayloadTooLarge,
+                    rejection.body_text(),
+                ))
+            }
+            rejection => Self::BadRequest(Problem::new(
+                ProblemType::MalformedRequest,
+                rejection.body_text(),
+            )),
+        }
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::BadRequest(Problem::new(
+            ProblemType::MalformedRequest,
+            rejection.body_text(),
+        ))
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        Self::BadRequest(Problem::new(
+            ProblemType::MalformedRequest,
+            rejection.body_text(),
+        ))
+    }
+}
+
+/// Finds an error of type `T` in the chain of sources of `err`.
+fn find_source<'a, T: std::error::Error + 'static>(
+    err: &'a (dyn std::error::Error + 'static),
+) -> Option<&'a T> {
+    let mut source = err.source();
+    while let Some(err) = source {
+        if let Some(found) = err.downcast_ref::<T>() {
+            return Some(found);
+        }
+        source = err.source();
+    }
+    None
+}
+
+/// Renders the location of a deserialization error as a JSON pointer (RFC 6901).
+fn json_pointer(path: &serde_path_to_error::Path) -> String {
+    use serde_path_to_error::Segment;
+
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(key.replace('~', "~0").replace('/', "~1")),
+            Segment::Enum { .. } | Segment::Unknown => None,
+        })
+        .map(|token| format!("/{}", token))
+        .collect()
+}
+
+/// [axum::Json], rejecting unreadable bodies with problem details.
+#[derive(Debug, Clone, FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct ApiJson<T>(pub T);
+
+/// [axum::extract::Query], rejecting unreadable query strings with problem details.
+#[derive(Debug, Clone, FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct ApiQuery<T>(pub T);
+
+/// [axum::extract::Path], rejecting unreadable path parameters with problem details.
+#[derive(Debug, Clone, FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(ApiError))]
+pub struct ApiPath<T>(pub T);
+
+/// Generic response structure shared by all successful API responses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 pub struct ApiResponseBody<T: Serialize + PartialEq> {
     status_code: u16,
     data: T,
@@ -65,46 +315,43 @@ impl<T: Serialize + PartialEq> ApiResponseBody<T> {
     }
 }
 
-impl ApiResponseBody<ApiErrorData> {
-    pub fn new_error(status_code: StatusCode, message: String) -> Self {
-        Self {
-            status_code: status_code.as_u16(),
-            data: ApiErrorData { message },
-        }
-    }
-}
-
-/// The response data format for all error responses.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
-pub struct ApiErrorData {
-    pub message: String,
-}
-
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
+impl ApiError {
+    /// The status and the problem details of the error, as sent to clients. The cause of an
+    /// internal error is logged rather than sent.
+    pub fn into_problem_details(self) -> (StatusCode, ProblemDetails) {
         use ApiError::*;
 
-        match self {
+        let (status, problem) = match self {
             InternalServerError(e) => {
                 tracing::error!("{}", e);
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponseBody::new_error(
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        "Internal server error".to_string(),
-                    )),
+                    Problem::new(ProblemType::Internal, "Internal server error"),
                 )
-                    .into_response()
             }
-            UnprocessableEntity(message) => (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                Json(ApiResponseBody::new_error(
-                    StatusCode::UNPROCESSABLE_ENTITY,
-                    message,
-                )),
-            )
-                .into_response(),
-        }
+            BadRequest(problem) => (StatusCode::BAD_REQUEST, problem),
+            Forbidden(problem) => (StatusCode::FORBIDDEN, problem),
+            NotFound(problem) => (StatusCode::NOT_FOUND, problem),
+            Conflict(problem) => (StatusCode::CONFLICT, problem),
+            PreconditionFailed(problem) => (StatusCode::PRECONDITION_FAILED, problem),
+            PayloadTooLarge(problem) => (StatusCode::PAYLOAD_TOO_LARGE, problem),
+            UnsupportedMediaType(problem) => (StatusCode::UNSUPPORTED_MEDIA_TYPE, problem),
+            UnprocessableEntity(problem) => (StatusCode::UNPROCESSABLE_ENTITY, problem),
+            TooManyRequests(problem) => (StatusCode::TOO_MANY_REQUESTS, problem),
+        };
+        (status, ProblemDetails::new(status.as_u16(), problem))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, details) = self.into_problem_details();
+        (
+            status,
+            [(header::CONTENT_TYPE, PROBLEM_JSON)],
+            Json(details),
+        )
+            .into_response()
     }
 }
 
@@ -112,4 +359,270 @@ pub fn api_routes<UC: UseCases>() -> Router<AppState<UC>> {
     Router::new()
         .route("/authors", post(create_user::<UC>))
         .route("/wishlists", post(create_wishlist::<UC>))
+        .route("/wishlists/templates", get(list_templates::<UC>))
+        .route(
+            "/wishlists/{wishlist_id}/duplicate",
+            post(duplicate_wishlist::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/template",
+            put(set_wishlist_template::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/share-link",
+            post(create_share_link::<UC>).delete(revoke_share_link::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/occasion",
+            put(set_wishlist_occasion::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/items",
+            get(list_items::<UC>).post(create_item::<UC>),
+        )
+        .route("/wishlists/{wishlist_id}/events", get(watch_items::<UC>))
+        .route(
+            "/wishlists/{wishlist_id}/events/ws",
+            get(watch_items_socket::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/webhooks",
+            post(register_webhook::<UC>),
+        )
+        .route("/webhooks", get(list_webhooks::<UC>))
+        .route("/webhooks/{webhook_id}", delete(delete_webhook::<UC>))
+        .route(
+            "/webhooks/{webhook_id}/deliveries",
+            get(list_webhook_deliveries::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/order",
+            put(reorder_wishlist::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/sections",
+            post(create_section::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/sections/{section_id}",
+            delete(delete_section::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/items/{item_id}/section",
+            put(move_item_to_section::<UC>),
+        )
+        .route("/items/{item_id}/move", post(move_item::<UC>))
+        .route("/items/{item_id}/copy", post(copy_item::<UC>))
+        .route("/items/{item_id}/received", put(mark_item_received::<UC>))
+        .route("/items/{item_id}/reservation", put(reserve_item::<UC>))
+        .route(
+            "/items/{item_id}/price-history",
+            get(find_price_history::<UC>),
+        )
+        .route(
+            "/items/{item_id}/price-watches",
+            post(watch_item_price::<UC>),
+        )
+        .route(
+            "/wishlists/{wishlist_id}/reminders",
+            post(subscribe_reminder::<UC>),
+        )
+        .route(
+            "/reminders/{reminder_id}",
+            delete(unsubscribe_reminder::<UC>),
+        )
+        .route("/reminder-preferences", put(set_reminder_preferences::<UC>))
+        .route("/inbox", get(list_inbox::<UC>))
+        .route("/exchanges", post(create_exchange::<UC>))
+        .route(
+            "/exchanges/{exchange_id}/participants",
+            post(add_participant::<UC>),
+        )
+        .route(
+            "/exchanges/{exchange_id}/exclusions",
+            post(add_exclusion::<UC>),
+        )
+        .route(
+            "/exchanges/{exchange_id}/draw",
+            post(draw_exchange::<UC>).get(audit_draw::<UC>),
+        )
+        .route(
+            "/exchanges/{exchange_id}/recipient",
+            get(reveal_recipient::<UC>),
+        )
+        .route("/groups", post(create_group::<UC>))
+        .route("/groups/{group_id}/invitations", post(invite_member::<UC>))
+        .route("/invitations", get(list_invitations::<UC>))
+        .route(
+            "/invitations/{invitation_id}",
+            put(respond_to_invitation::<UC>),
+        )
+        .route(
+            "/groups/{group_id}/members/{member_id}",
+            delete(remove_member::<UC>),
+        )
+        .route(
+            "/groups/{group_id}/members/{member_id}/role",
+            put(set_member_role::<UC>),
+        )
+        .route("/groups/{group_id}/wishlists", post(share_wishlist::<UC>))
+        .route(
+            "/groups/{group_id}/wishlists/{wishlist_id}",
+            delete(unshare_wishlist::<UC>),
+        )
+        .route("/groups/{group_id}/feed", get(group_feed::<UC>))
+        .route("/follow-settings", put(set_follow_settings::<UC>))
+        .route("/follows", post(follow_user::<UC>))
+        .route("/follows/{followee_id}", delete(unfollow_user::<UC>))
+        .route("/follow-requests", get(list_follow_requests::<UC>))
+        .route(
+            "/follow-requests/{follower_id}",
+            put(answer_follow_request::<UC>),
+        )
+        .route("/blocks", post(block_user::<UC>))
+        .route("/blocks/{blocked_id}", delete(unblock_user::<UC>))
+        .route("/feed", get(follow_feed::<UC>))
+        .route("/profile", put(set_profile::<UC>))
+        .route(
+            "/profile/occasion-suggestions",
+            get(suggest_occasions::<UC>),
+        )
+        .route("/profiles/{profile_id}", get(find_profile::<UC>))
+        .route("/me", delete(delete_account::<UC>))
+        .route(
+            "/me/confirmations",
+            post(request_account_confirmation::<UC>),
+        )
+        .route("/me/deletion", delete(cancel_account_deletion::<UC>))
+        .route("/me/export", get(export_account::<UC>))
+        .route("/images", post(upload_image::<UC>))
+        .route("/images/{image_id}/{size}", get(find_image::<UC>))
+}
+
+#[cfg(test)]
+pub use test_support::test_services;
+
+/// Handler tests call handlers with mock services.
+#[cfg(test)]
+pub mod test_support {
+    use std::sync::Arc;
+
+    use axum::extract::State;
+
+    use crate::application::Service;
+    use crate::domain::{
+        MockAccountService, MockExchangeService, MockFollowService, MockGroupService,
+        MockImageService, MockItemService, MockNotificationService, MockProfileService,
+        MockUserService, MockWebhookService, MockWishlistService,
+    };
+
+    use super::AppState;
+
+    /// The services of a handler test: every one a mock expecting no call, unless replaced.
+    pub type TestServices = Service<
+        MockUserService,
+        MockWishlistService,
+        MockItemService,
+        MockImageService,
+        MockNotificationService,
+        MockExchangeService,
+        MockGroupService,
+        MockFollowService,
+        MockProfileService,
+        MockAccountService,
+        MockWebhookService,
+    >;
+
+    /// Builds [TestServices], so that a test only sets up the mocks it exercises, e.g.
+    /// `test_services().with_item(mock_item_service).into_state()`.
+    pub fn test_services() -> TestServicesBuilder {
+        TestServicesBuilder::default()
+    }
+
+    #[derive(Default)]
+    pub struct TestServicesBuilder {
+        user: MockUserService,
+        wishlist: MockWishlistService,
+        item: MockItemService,
+        image: MockImageService,
+        notification: MockNotificationService,
+        exchange: MockExchangeService,
+        group: MockGroupService,
+        follow: MockFollowService,
+        profile: MockProfileService,
+        account: MockAccountService,
+        webhook: MockWebhookService,
+    }
+
+    impl TestServicesBuilder {
+        pub fn with_user(self, user: MockUserService) -> Self {
+            Self { user, ..self }
+        }
+
+        pub fn with_wishlist(self, wishlist: MockWishlistService) -> Self {
+            Self { wishlist, ..self }
+        }
+
+        pub fn with_item(self, item: MockItemService) -> Self {
+            Self { item, ..self }
+        }
+
+        pub fn with_image(self, image: MockImageService) -> Self {
+            Self { image, ..self }
+        }
+
+        pub fn with_notification(self, notification: MockNotificationService) -> Self {
+            Self {
+                notification,
+                ..self
+            }
+        }
+
+        pub fn with_exchange(self, exchange: MockExchangeService) -> Self {
+            Self { exchange, ..self }
+        }
+
+        pub fn with_group(self, group: MockGroupService) -> Self {
+            Self { group, ..self }
+        }
+
+        pub fn with_follow(self, follow: MockFollowService) -> Self {
+            Self { follow, ..self }
+        }
+
+        pub fn with_profile(self, profile: MockProfileService) -> Self {
+            Self { profile, ..self }
+        }
+
+        pub fn with_account(self, account: MockAccountService) -> Self {
+            Self { account, ..self }
+        }
+
+        pub fn with_webhook(self, webhook: MockWebhookService) -> Self {
+            Self { webhook, ..self }
+        }
+
+        pub fn build(self) -> TestServices {
+            Service::new(
+                self.user,
+                self.wishlist,
+                self.item,
+                self.image,
+                self.notification,
+                self.exchange,
+                self.group,
+                self.follow,
+                self.profile,
+                self.account,
+                self.webhook,
+            )
+        }
+
+        /// The state handlers are called with.
+        pub fn into_state(self) -> State<AppState<TestServices>> {
+            State(AppState {
+                services: Arc::new(self.build()),
+            })
+        }
+    }
 }
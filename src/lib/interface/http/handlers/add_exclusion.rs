@@ -0,0 +1,172 @@
+/*
+Module `add_exclusion` specifies an HTTP handler for excluding a pair of participants from the
+draw of a gift [Exchange](crate::domain::Exchange), and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{AddExclusionError, AddExclusionRequest, Exclusion};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_exchange::ExchangeResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<AddExclusionError> for ApiError {
+    fn from(e: AddExclusionError) -> Self {
+        match e {
+            AddExclusionError::ExchangeDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ExchangeNotFound,
+                format!("Exchange ID {} does not exist", id),
+            )),
+            AddExclusionError::NotOrganizer { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotExchangeOrganizer,
+                format!(
+                    "Only the organizer of exchange ID {} can add exclusions",
+                    id
+                ),
+            )),
+            AddExclusionError::NotParticipant { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::NotExchangeParticipant,
+                format!("User ID {} does not participate in the exchange", id),
+            )),
+            AddExclusionError::SameParticipant => Self::UnprocessableEntity(Problem::new(
+                ProblemType::SelfExclusion,
+                "a participant cannot be excluded from themselves",
+            )),
+            AddExclusionError::AlreadyDrawn { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ExchangeAlreadyDrawn,
+                format!("Exchange ID {} has already been drawn", id),
+            )),
+            AddExclusionError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of a request excluding `giver_id` from giving to `recipient_id`, and the other way
+/// around when `mutual`, e.g. for couples.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct AddExclusionHttpRequestBody {
+    pub user_id: String,
+    pub giver_id: String,
+    pub recipient_id: String,
+    #[serde(default)]
+    pub mutual: bool,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseAddExclusionHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl AddExclusionHttpRequestBody {
+    /// Converts the HTTP request body into a domain [AddExclusionRequest].
+    pub fn try_into_domain(
+        self,
+        exchange_id: Uuid,
+    ) -> Result<AddExclusionRequest, FieldErrors<ParseAddExclusionHttpRequestError>> {
+        let parse_user = |id: &str| {
+            Uuid::parse_str(id)
+                .map_err(|_| ParseAddExclusionHttpRequestError::UserId(id.to_string()))
+        };
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field("/user_id", parse_user(&self.user_id));
+        let giver_id = errors.check_field("/giver_id", parse_user(&self.giver_id));
+        let recipient_id = errors.check_field("/recipient_id", parse_user(&self.recipient_id));
+        let (Some(user_id), Some(giver_id), Some(recipient_id)) = (user_id, giver_id, recipient_id)
+        else {
+            return Err(errors);
+        };
+        Ok(AddExclusionRequest::new(
+            exchange_id,
+            user_id,
+            Exclusion::new(giver_id, recipient_id),
+            self.mutual,
+        ))
+    }
+}
+
+/// Exclude a pair of participants from the draw of an [Exchange](crate::domain::Exchange), such
+/// as a couple or last year's pair.
+///
+/// # Responses
+///
+/// - 200 OK: the updated exchange.
+/// - 403 Forbidden: the user is not the organizer.
+/// - 404 Not found: the exchange does not exist.
+/// - 422 Unprocessable entity: an ID is invalid, a user does not participate, both users are the
+///   same, or the exchange has already been drawn.
+#[utoipa::path(
+    post,
+    path = "/exchanges/{exchange_id}/exclusions",
+    tag = "exchanges",
+    params(
+        ("exchange_id" = Uuid, Path, description = "The ID of the exchange."),
+    ),
+    request_body = AddExclusionHttpRequestBody,
+    responses(
+        (status = 200, description = "The updated exchange.", body = ApiResponseBody<ExchangeResponseData>),
+        (status = 403, description = "The user is not the organizer.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The exchange does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "An ID is invalid, a user does not participate, both users are the same, or the exchange has already been drawn.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn add_exclusion<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(exchange_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<AddExclusionHttpRequestBody>,
+) -> Result<ApiSuccess<ExchangeResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(exchange_id)?;
+    state
+        .services
+        .add_exclusion(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref exchange| ApiSuccess::new(StatusCode::OK, exchange.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockExchangeService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_add_exclusion_not_organizer() {
+        let exchange_id = Uuid::now_v7();
+        let mut mock_exchange_service = MockExchangeService::new();
+        mock_exchange_service
+            .expect_add_exclusion()
+            .withf(|req| req.mutual() && req.exclusions().len() == 2)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(AddExclusionError::NotOrganizer {
+                    id: exchange_id,
+                })))
+            });
+        let state = test_services()
+            .with_exchange(mock_exchange_service)
+            .into_state();
+        let body = ApiJson(AddExclusionHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            giver_id: Uuid::now_v7().to_string(),
+            recipient_id: Uuid::now_v7().to_string(),
+            mutual: true,
+        });
+
+        let actual = add_exclusion(state, ApiPath(exchange_id), body).await;
+        assert!(matches!(actual, Err(ApiError::Forbidden(_))));
+    }
+}
@@ -0,0 +1,189 @@
+/*
+Module `add_participant` specifies an HTTP handler for adding a participant to a gift
+[Exchange](crate::domain::Exchange), and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{AddParticipantError, AddParticipantRequest, Participant};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_exchange::ExchangeResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<AddParticipantError> for ApiError {
+    fn from(e: AddParticipantError) -> Self {
+        match e {
+            AddParticipantError::ExchangeDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ExchangeNotFound,
+                format!("Exchange ID {} does not exist", id),
+            )),
+            AddParticipantError::UserDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::UserNotFound,
+                    format!("User ID {} does not exist", id),
+                ))
+            }
+            AddParticipantError::WishlistDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::WishlistNotFound,
+                    format!("Wishlist ID {} does not exist", id),
+                ))
+            }
+            AddParticipantError::NotOrganizer { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotExchangeOrganizer,
+                format!(
+                    "Only the organizer of exchange ID {} can add other participants",
+                    id
+                ),
+            )),
+            AddParticipantError::AlreadyDrawn { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ExchangeAlreadyDrawn,
+                format!("Exchange ID {} has already been drawn", id),
+            )),
+            AddParticipantError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of a request adding `participant_id` to an exchange, on behalf of `user_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct AddParticipantHttpRequestBody {
+    pub user_id: String,
+    pub participant_id: String,
+    pub wishlist_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseAddParticipantHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("wishlist id {0} is invalid")]
+    WishlistId(String),
+}
+
+impl AddParticipantHttpRequestBody {
+    /// Converts the HTTP request body into a domain [AddParticipantRequest].
+    pub fn try_into_domain(
+        self,
+        exchange_id: Uuid,
+    ) -> Result<AddParticipantRequest, FieldErrors<ParseAddParticipantHttpRequestError>> {
+        let parse_user = |id: &str| {
+            Uuid::parse_str(id)
+                .map_err(|_| ParseAddParticipantHttpRequestError::UserId(id.to_string()))
+        };
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field("/user_id", parse_user(&self.user_id));
+        let participant_id =
+            errors.check_field("/participant_id", parse_user(&self.participant_id));
+        let wishlist_id = errors.check_field(
+            "/wishlist_id",
+            self.wishlist_id
+                .map(|id| {
+                    Uuid::parse_str(&id)
+                        .map_err(|_| ParseAddParticipantHttpRequestError::WishlistId(id))
+                })
+                .transpose(),
+        );
+        let (Some(user_id), Some(participant_id), Some(wishlist_id)) =
+            (user_id, participant_id, wishlist_id)
+        else {
+            return Err(errors);
+        };
+        Ok(AddParticipantRequest::new(
+            exchange_id,
+            user_id,
+            Participant::new(participant_id, wishlist_id),
+        ))
+    }
+}
+
+/// Add a participant to an [Exchange](crate::domain::Exchange), with one of their wishlists for
+/// their giver to see. The organizer adds anyone, other users only add themselves. Adding a
+/// participant again replaces their wishlist.
+///
+/// # Responses
+///
+/// - 200 OK: the updated exchange.
+/// - 403 Forbidden: the user is not the organizer and adds someone else.
+/// - 404 Not found: the exchange does not exist.
+/// - 422 Unprocessable entity: an ID is invalid, the participant or their wishlist does not
+///   exist, or the exchange has already been drawn.
+#[utoipa::path(
+    post,
+    path = "/exchanges/{exchange_id}/participants",
+    tag = "exchanges",
+    params(
+        ("exchange_id" = Uuid, Path, description = "The ID of the exchange."),
+    ),
+    request_body = AddParticipantHttpRequestBody,
+    responses(
+        (status = 200, description = "The updated exchange.", body = ApiResponseBody<ExchangeResponseData>),
+        (status = 403, description = "The user is not the organizer and adds someone else.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The exchange does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "An ID is invalid, the participant or their wishlist does not exist, or the exchange has already been drawn.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn add_participant<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(exchange_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<AddParticipantHttpRequestBody>,
+) -> Result<ApiSuccess<ExchangeResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(exchange_id)?;
+    state
+        .services
+        .add_participant(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref exchange| ApiSuccess::new(StatusCode::OK, exchange.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{Exchange, MockExchangeService};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_add_participant_success() {
+        let organizer_id = Uuid::now_v7();
+        let participant = Participant::new(Uuid::now_v7(), Some(Uuid::now_v7()));
+        let mut exchange = Exchange::new(Uuid::now_v7(), organizer_id, "Office".into());
+        exchange.add_participant(participant);
+        let expected = ApiSuccess::new(StatusCode::OK, ExchangeResponseData::from(&exchange));
+        let exchange_id = exchange.id();
+        let mut mock_exchange_service = MockExchangeService::new();
+        mock_exchange_service
+            .expect_add_participant()
+            .withf(move |req| {
+                req.exchange_id() == exchange_id
+                    && req.user_id() == organizer_id
+                    && req.participant() == participant
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(exchange))));
+        let state = test_services()
+            .with_exchange(mock_exchange_service)
+            .into_state();
+        let body = ApiJson(AddParticipantHttpRequestBody {
+            user_id: organizer_id.to_string(),
+            participant_id: participant.user_id().to_string(),
+            wishlist_id: participant.wishlist_id().map(|id| id.to_string()),
+        });
+
+        let actual = add_participant(state, ApiPath(exchange_id), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,138 @@
+/*
+Module `answer_follow_request` specifies an HTTP handler for accepting or declining a request
+to follow a user, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{AnswerFollowRequest, AnswerFollowRequestError};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::follow_user::FollowResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<AnswerFollowRequestError> for ApiError {
+    fn from(e: AnswerFollowRequestError) -> Self {
+        match e {
+            AnswerFollowRequestError::RequestDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::FollowRequestNotFound,
+                format!("User ID {} has no pending request to follow the user", id),
+            )),
+            AnswerFollowRequestError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of the answer of `user_id` to a request to follow them.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct AnswerFollowRequestHttpRequestBody {
+    pub user_id: String,
+    pub accept: bool,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseAnswerFollowRequestHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl AnswerFollowRequestHttpRequestBody {
+    /// Converts the HTTP request body into a domain [AnswerFollowRequest].
+    pub fn try_into_domain(
+        self,
+        follower_id: Uuid,
+    ) -> Result<AnswerFollowRequest, FieldErrors<ParseAnswerFollowRequestHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id).map_err(|_| {
+                ParseAnswerFollowRequestHttpRequestError::UserId(self.user_id.clone())
+            }),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(AnswerFollowRequest::new(user_id, follower_id, self.accept))
+    }
+}
+
+/// Accept or decline the request of a user to follow the user. Declined requests are deleted.
+///
+/// # Responses
+///
+/// - 200 OK: the [Follow](crate::domain::Follow), either `accepted` or `declined`.
+/// - 404 Not found: the follower has no pending request to follow the user.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    put,
+    path = "/follow-requests/{follower_id}",
+    tag = "follows",
+    params(
+        ("follower_id" = Uuid, Path, description = "The ID of the follower."),
+    ),
+    request_body = AnswerFollowRequestHttpRequestBody,
+    responses(
+        (status = 200, description = "The Follow(crate::domain::Follow), either 'accepted' or 'declined'.", body = ApiResponseBody<FollowResponseData>),
+        (status = 404, description = "The follower has no pending request to follow the user.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn answer_follow_request<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(follower_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<AnswerFollowRequestHttpRequestBody>,
+) -> Result<ApiSuccess<FollowResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(follower_id)?;
+    state
+        .services
+        .answer_follow_request(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref follow| ApiSuccess::new(StatusCode::OK, follow.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use chrono::Utc;
+
+    use crate::domain::{Follow, FollowStatus, MockFollowService};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accept_follow_request() {
+        let (user_id, follower_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let follow = Follow::new(follower_id, user_id, FollowStatus::Accepted, Utc::now());
+        let expected = ApiSuccess::new(StatusCode::OK, FollowResponseData::from(&follow));
+        let mut mock_follow_service = MockFollowService::new();
+        mock_follow_service
+            .expect_answer_follow_request()
+            .withf(move |req| {
+                req.user_id() == user_id && req.follower_id() == follower_id && req.accept()
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(follow))));
+        let state = test_services()
+            .with_follow(mock_follow_service)
+            .into_state();
+        let body = ApiJson(AnswerFollowRequestHttpRequestBody {
+            user_id: user_id.to_string(),
+            accept: true,
+        });
+
+        let actual = answer_follow_request(state, ApiPath(follower_id), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
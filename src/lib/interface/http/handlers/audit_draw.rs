@@ -0,0 +1,164 @@
+/*
+Module `audit_draw` specifies an HTTP handler for auditing the draw of a gift
+[Exchange](crate::domain::Exchange) from its stored seed, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{AuditDrawError, DrawAudit, ExchangeActionRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::draw_exchange::ParseExchangeActionHttpRequestError;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<AuditDrawError> for ApiError {
+    fn from(e: AuditDrawError) -> Self {
+        match e {
+            AuditDrawError::ExchangeDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ExchangeNotFound,
+                format!("Exchange ID {} does not exist", id),
+            )),
+            AuditDrawError::NotOrganizer { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotExchangeOrganizer,
+                format!(
+                    "Only the organizer of exchange ID {} can audit its draw",
+                    id
+                ),
+            )),
+            AuditDrawError::NotDrawn { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ExchangeNotDrawn,
+                format!("Exchange ID {} has not been drawn yet", id),
+            )),
+            AuditDrawError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [DrawAudit]. The seed is a string, as it does not fit in
+/// a JSON number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct DrawAuditResponseData {
+    pub seed: String,
+    pub drawn_at: DateTime<Utc>,
+    pub reproducible: bool,
+}
+
+impl From<&DrawAudit> for DrawAuditResponseData {
+    fn from(audit: &DrawAudit) -> Self {
+        Self {
+            seed: audit.seed().to_string(),
+            drawn_at: audit.drawn_at(),
+            reproducible: audit.reproducible(),
+        }
+    }
+}
+
+/// The query string of a request by a user about an [Exchange](crate::domain::Exchange).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExchangeActionHttpQuery {
+    pub user_id: String,
+}
+
+impl ExchangeActionHttpQuery {
+    /// Converts the HTTP query into a domain [ExchangeActionRequest].
+    pub fn try_into_domain(
+        self,
+        exchange_id: Uuid,
+    ) -> Result<ExchangeActionRequest, FieldErrors<ParseExchangeActionHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseExchangeActionHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(ExchangeActionRequest::new(exchange_id, user_id))
+    }
+}
+
+/// Audit the draw of an [Exchange](crate::domain::Exchange): drawing again from the stored seed
+/// must give the stored draw. The audit does not tell who gives to whom.
+///
+/// # Responses
+///
+/// - 200 OK: the seed of the draw, and whether it reproduces the draw.
+/// - 403 Forbidden: the user is not the organizer.
+/// - 404 Not found: the exchange does not exist.
+/// - 422 Unprocessable entity: the user ID is invalid or the exchange has not been drawn yet.
+#[utoipa::path(
+    get,
+    path = "/exchanges/{exchange_id}/draw",
+    tag = "exchanges",
+    params(
+        ("exchange_id" = Uuid, Path, description = "The ID of the exchange."),
+        ExchangeActionHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The seed of the draw, and whether it reproduces the draw.", body = ApiResponseBody<DrawAuditResponseData>),
+        (status = 403, description = "The user is not the organizer.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The exchange does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid or the exchange has not been drawn yet.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn audit_draw<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(exchange_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<ExchangeActionHttpQuery>,
+) -> Result<ApiSuccess<DrawAuditResponseData>, ApiError> {
+    let domain_req = query.try_into_domain(exchange_id)?;
+    state
+        .services
+        .audit_draw(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref audit| ApiSuccess::new(StatusCode::OK, audit.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockExchangeService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_audit_draw_success() {
+        let audit = DrawAudit::new(u64::MAX, Utc::now(), true);
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            DrawAuditResponseData {
+                seed: "18446744073709551615".into(),
+                drawn_at: audit.drawn_at(),
+                reproducible: true,
+            },
+        );
+        let mut mock_exchange_service = MockExchangeService::new();
+        mock_exchange_service
+            .expect_audit_draw()
+            .return_once(move |_| Box::pin(future::ready(Ok(audit))));
+        let state = test_services()
+            .with_exchange(mock_exchange_service)
+            .into_state();
+        let query = ApiQuery(ExchangeActionHttpQuery {
+            user_id: Uuid::now_v7().to_string(),
+        });
+
+        let actual = audit_draw(state, ApiPath(Uuid::now_v7()), query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
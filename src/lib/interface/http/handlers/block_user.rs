@@ -0,0 +1,155 @@
+/*
+Module `block_user` specifies an HTTP handler for blocking a user, and the associated data
+structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{Block, BlockUserError, BlockUserRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
+
+impl From<BlockUserError> for ApiError {
+    fn from(e: BlockUserError) -> Self {
+        match e {
+            BlockUserError::UserDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::UserNotFound,
+                format!("User ID {} does not exist", id),
+            )),
+            BlockUserError::SelfBlock => Self::UnprocessableEntity(Problem::new(
+                ProblemType::SelfBlock,
+                "users cannot block themselves",
+            )),
+            BlockUserError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [Block].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct BlockResponseData {
+    pub blocker_id: String,
+    pub blocked_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&Block> for BlockResponseData {
+    fn from(block: &Block) -> Self {
+        Self {
+            blocker_id: block.blocker_id().to_string(),
+            blocked_id: block.blocked_id().to_string(),
+            created_at: block.created_at(),
+        }
+    }
+}
+
+/// The body of a request by `user_id` to block `blocked_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct BlockUserHttpRequestBody {
+    pub user_id: String,
+    pub blocked_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseBlockUserHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl BlockUserHttpRequestBody {
+    /// Converts the HTTP request body into a domain [BlockUserRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<BlockUserRequest, FieldErrors<ParseBlockUserHttpRequestError>> {
+        let parse_user = |id: &str| {
+            Uuid::parse_str(id).map_err(|_| ParseBlockUserHttpRequestError::UserId(id.to_string()))
+        };
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field("/user_id", parse_user(&self.user_id));
+        let blocked_id = errors.check_field("/blocked_id", parse_user(&self.blocked_id));
+        let (Some(user_id), Some(blocked_id)) = (user_id, blocked_id) else {
+            return Err(errors);
+        };
+        Ok(BlockUserRequest::new(user_id, blocked_id))
+    }
+}
+
+/// Block a user. Any follow between the two users is removed, and neither can follow the other
+/// until the block is lifted.
+///
+/// # Responses
+///
+/// - 201 Created: the [Block].
+/// - 404 Not found: the blocked user does not exist.
+/// - 422 Unprocessable entity: a user ID is invalid, or the user blocks themselves.
+#[utoipa::path(
+    post,
+    path = "/blocks",
+    tag = "follows",
+    request_body = BlockUserHttpRequestBody,
+    responses(
+        (status = 201, description = "The Block.", body = ApiResponseBody<BlockResponseData>),
+        (status = 404, description = "The blocked user does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "A user ID is invalid, or the user blocks themselves.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn block_user<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiJson(body): ApiJson<BlockUserHttpRequestBody>,
+) -> Result<ApiSuccess<BlockResponseData>, ApiError> {
+    let domain_req = body.try_into_domain()?;
+    state
+        .services
+        .block_user(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref block| ApiSuccess::new(StatusCode::CREATED, block.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockFollowService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_block_self() {
+        let user_id = Uuid::now_v7();
+        let mut mock_follow_service = MockFollowService::new();
+        mock_follow_service
+            .expect_block_user()
+            .withf(move |req| req.user_id() == user_id && req.blocked_id() == user_id)
+            .return_once(move |_| Box::pin(future::ready(Err(BlockUserError::SelfBlock))));
+        let state = test_services()
+            .with_follow(mock_follow_service)
+            .into_state();
+        let body = ApiJson(BlockUserHttpRequestBody {
+            user_id: user_id.to_string(),
+            blocked_id: user_id.to_string(),
+        });
+
+        let actual = block_user(state, body).await;
+        assert_eq!(
+            actual,
+            Err(ApiError::UnprocessableEntity(Problem::new(
+                ProblemType::SelfBlock,
+                "users cannot block themselves".to_string()
+            )))
+        );
+    }
+}
@@ -0,0 +1,138 @@
+/*
+Module `cancel_account_deletion` specifies an HTTP handler for cancelling the deletion of the
+account of a user during its grace period.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+
+use crate::application::UseCases;
+use crate::domain::CancelAccountDeletionError;
+use crate::interface::http::problem::{Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::delete_account::{AccountDeletionResponseData, ConfirmedAccountHttpQuery};
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<CancelAccountDeletionError> for ApiError {
+    fn from(e: CancelAccountDeletionError) -> Self {
+        match e {
+            CancelAccountDeletionError::InvalidConfirmation { id } => {
+                Self::Forbidden(Problem::new(
+                    ProblemType::InvalidConfirmationToken,
+                    format!(
+                        "The confirmation token of user ID {} is invalid or expired",
+                        id
+                    ),
+                ))
+            }
+            CancelAccountDeletionError::NotScheduled { id } => Self::NotFound(Problem::new(
+                ProblemType::AccountDeletionNotScheduled,
+                format!("The account of user ID {} is not being deleted", id),
+            )),
+            CancelAccountDeletionError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// Cancel the pending deletion of the account of a user, which keeps all of their data. Confirmed
+/// with a token emailed to them, like the deletion itself.
+///
+/// # Responses
+///
+/// - 200 OK: the cancelled deletion.
+/// - 403 Forbidden: the confirmation token is invalid or expired.
+/// - 404 Not found: the account is not being deleted, or the grace period is over.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    delete,
+    path = "/me/deletion",
+    tag = "account",
+    params(
+        ConfirmedAccountHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The cancelled deletion.", body = ApiResponseBody<AccountDeletionResponseData>),
+        (status = 403, description = "The confirmation token is invalid or expired.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The account is not being deleted, or the grace period is over.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn cancel_account_deletion<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<ConfirmedAccountHttpQuery>,
+) -> Result<ApiSuccess<AccountDeletionResponseData>, ApiError> {
+    let domain_req = query.try_into_domain()?;
+    state
+        .services
+        .cancel_account_deletion(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref deletion| ApiSuccess::new(StatusCode::OK, deletion.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use uuid::Uuid;
+
+    use crate::domain::MockAccountService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancel_unscheduled_deletion() {
+        let user_id = Uuid::now_v7();
+        let expected = ApiError::NotFound(Problem::new(
+            ProblemType::AccountDeletionNotScheduled,
+            format!("The account of user ID {} is not being deleted", user_id),
+        ));
+        let mut mock_account_service = MockAccountService::new();
+        mock_account_service
+            .expect_cancel_account_deletion()
+            .withf(move |req| req.user_id() == user_id && req.token().as_str() == "secret")
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(
+                    CancelAccountDeletionError::NotScheduled { id: user_id },
+                )))
+            });
+        let state = test_services()
+            .with_account(mock_account_service)
+            .into_state();
+        let query = ApiQuery(ConfirmedAccountHttpQuery {
+            user_id: user_id.to_string(),
+            token: "secret".into(),
+        });
+
+        let actual = cancel_account_deletion(state, query).await;
+        assert_eq!(actual, Err(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancel_deletion_invalid_confirmation() {
+        let user_id = Uuid::now_v7();
+        let mut mock_account_service = MockAccountService::new();
+        mock_account_service
+            .expect_cancel_account_deletion()
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(
+                    CancelAccountDeletionError::InvalidConfirmation { id: user_id },
+                )))
+            });
+        let state = test_services()
+            .with_account(mock_account_service)
+            .into_state();
+        let query = ApiQuery(ConfirmedAccountHttpQuery {
+            user_id: user_id.to_string(),
+            token: "guess".into(),
+        });
+
+        let actual = cancel_account_deletion(state, query).await;
+        assert!(matches!(actual, Err(ApiError::Forbidden(_))));
+    }
+}
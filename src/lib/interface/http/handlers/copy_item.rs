@@ -0,0 +1,81 @@
+/*
+Module `copy_item` specifies an HTTP handler for copying an [Item] into another [Wishlist].
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::Item;
+use crate::interface::http::problem::ProblemDetails;
+use crate::interface::http::AppState;
+
+use super::create_item::ItemResponseData;
+use super::move_item::TransferItemHttpRequestBody;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+/// Copy an [Item], with its details and image, into another [Wishlist] of the same owner. The
+/// copy starts without the price history or watches of the original.
+///
+/// # Responses
+///
+/// - 201 Created: the copy of the [Item].
+/// - 403 Forbidden: the user does not own both [Wishlist]s.
+/// - 404 Not found: the [Item] does not exist.
+/// - 422 Unprocessable entity: the target [Wishlist] does not exist, is archived, or already
+///   has an [Item] with the same link.
+#[utoipa::path(
+    post,
+    path = "/items/{item_id}/copy",
+    tag = "items",
+    params(
+        ("item_id" = Uuid, Path, description = "The ID of the item."),
+    ),
+    request_body = TransferItemHttpRequestBody,
+    responses(
+        (status = 201, description = "The copy of the Item.", body = ApiResponseBody<ItemResponseData>),
+        (status = 403, description = "The user does not own both Wishlists.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Item does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The target Wishlist does not exist, is archived, or already has an Item with the same link.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn copy_item<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<TransferItemHttpRequestBody>,
+) -> Result<ApiSuccess<ItemResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(item_id)?;
+    state
+        .services
+        .copy_item(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref item: Item| ApiSuccess::new(StatusCode::CREATED, item.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{MockItemService, TransferItemError};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_copy_item_duplicate() {
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service
+            .expect_copy_item()
+            .return_once(|_| Box::pin(future::ready(Err(TransferItemError::Duplicate))));
+        let state = test_services().with_item(mock_item_service).into_state();
+        let body = ApiJson(TransferItemHttpRequestBody {
+            wishlist_id: Uuid::now_v7().to_string(),
+            user_id: Uuid::now_v7().to_string(),
+        });
+
+        let actual = copy_item(state, ApiPath(Uuid::now_v7()), body).await;
+        assert!(matches!(actual, Err(ApiError::UnprocessableEntity(_))));
+    }
+}
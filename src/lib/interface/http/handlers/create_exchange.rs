@@ -0,0 +1,196 @@
+/*
+Module `create_exchange` specifies an HTTP handler for creating a gift [Exchange], and the
+exchange data structures shared with the other [Exchange] handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    CreateExchangeError, CreateExchangeRequest, Exchange, ExchangeName, ExchangeNameInvalidError,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
+
+impl From<CreateExchangeError> for ApiError {
+    fn from(e: CreateExchangeError) -> Self {
+        match e {
+            CreateExchangeError::OrganizerDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::UserNotFound,
+                    format!("User ID {} does not exist", id),
+                ))
+            }
+            CreateExchangeError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for an [Exchange]. It never includes who gives to whom.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ExchangeResponseData {
+    pub id: String,
+    pub organizer_id: String,
+    pub name: String,
+    pub participants: Vec<ParticipantResponseData>,
+    pub exclusions: Vec<ExclusionResponseData>,
+    pub drawn_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ParticipantResponseData {
+    pub user_id: String,
+    pub wishlist_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ExclusionResponseData {
+    pub giver_id: String,
+    pub recipient_id: String,
+}
+
+impl From<&Exchange> for ExchangeResponseData {
+    fn from(exchange: &Exchange) -> Self {
+        Self {
+            id: exchange.id().to_string(),
+            organizer_id: exchange.organizer_id().to_string(),
+            name: exchange.name().to_string(),
+            participants: exchange
+                .participants()
+                .iter()
+                .map(|participant| ParticipantResponseData {
+                    user_id: participant.user_id().to_string(),
+                    wishlist_id: participant.wishlist_id().map(|id| id.to_string()),
+                })
+                .collect(),
+            exclusions: exchange
+                .exclusions()
+                .iter()
+                .map(|exclusion| ExclusionResponseData {
+                    giver_id: exclusion.giver_id().to_string(),
+                    recipient_id: exclusion.recipient_id().to_string(),
+                })
+                .collect(),
+            drawn_at: exchange.draw().map(|draw| draw.drawn_at()),
+        }
+    }
+}
+
+/// The body of an [Exchange] creation request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct CreateExchangeHttpRequestBody {
+    pub user_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseCreateExchangeHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("name is invalid")]
+    Name(#[from] ExchangeNameInvalidError),
+}
+
+impl CreateExchangeHttpRequestBody {
+    /// Converts the HTTP request body into a domain [CreateExchangeRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<CreateExchangeRequest, FieldErrors<ParseCreateExchangeHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseCreateExchangeHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let name = errors.check_field("/name", ExchangeName::new(&self.name));
+        let (Some(user_id), Some(name)) = (user_id, name) else {
+            return Err(errors);
+        };
+        Ok(CreateExchangeRequest::new(user_id, name))
+    }
+}
+
+/// Create a gift [Exchange] organized by the user, such as a Secret Santa.
+///
+/// # Responses
+///
+/// - 201 Created: the [Exchange], without participants.
+/// - 422 Unprocessable entity: the user ID is invalid or does not exist, or the name is empty.
+#[utoipa::path(
+    post,
+    path = "/exchanges",
+    tag = "exchanges",
+    request_body = CreateExchangeHttpRequestBody,
+    responses(
+        (status = 201, description = "The Exchange, without participants.", body = ApiResponseBody<ExchangeResponseData>),
+        (status = 422, description = "The user ID is invalid or does not exist, or the name is empty.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn create_exchange<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiJson(body): ApiJson<CreateExchangeHttpRequestBody>,
+) -> Result<ApiSuccess<ExchangeResponseData>, ApiError> {
+    let domain_req = body.try_into_domain()?;
+    state
+        .services
+        .create_exchange(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref exchange| ApiSuccess::new(StatusCode::CREATED, exchange.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockExchangeService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+    use crate::interface::http::problem::InvalidField;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_exchange_success() {
+        let user_id = Uuid::now_v7();
+        let exchange = Exchange::new(Uuid::now_v7(), user_id, "Office".into());
+        let expected = ApiSuccess::new(StatusCode::CREATED, ExchangeResponseData::from(&exchange));
+        let mut mock_exchange_service = MockExchangeService::new();
+        mock_exchange_service
+            .expect_create_exchange()
+            .withf(move |req| req.organizer_id() == user_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(exchange))));
+        let state = test_services()
+            .with_exchange(mock_exchange_service)
+            .into_state();
+        let body = ApiJson(CreateExchangeHttpRequestBody {
+            user_id: user_id.to_string(),
+            name: "Office".into(),
+        });
+
+        let actual = create_exchange(state, body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_empty_name() {
+        let body = CreateExchangeHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            name: " ".into(),
+        };
+        assert_eq!(
+            body.try_into_domain().unwrap_err().into_invalid_fields(),
+            vec![InvalidField::body("/name", "name is invalid")]
+        );
+    }
+}
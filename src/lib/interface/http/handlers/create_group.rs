@@ -0,0 +1,205 @@
+/*
+Module `create_group` specifies an HTTP handler for creating a [Group], and the group data
+structures shared with the other [Group] handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    CreateGroupError, CreateGroupRequest, Group, GroupName, GroupNameInvalidError,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
+
+impl From<CreateGroupError> for ApiError {
+    fn from(e: CreateGroupError) -> Self {
+        match e {
+            CreateGroupError::UserDoesNotExist { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::UserNotFound,
+                format!("User ID {} does not exist", id),
+            )),
+            CreateGroupError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [Group].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct GroupResponseData {
+    pub id: String,
+    pub name: String,
+    pub members: Vec<GroupMemberResponseData>,
+    pub wishlists: Vec<SharedWishlistResponseData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct GroupMemberResponseData {
+    pub user_id: String,
+    pub role: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct SharedWishlistResponseData {
+    pub wishlist_id: String,
+    pub shared_by: String,
+    pub shared_at: DateTime<Utc>,
+}
+
+impl From<&Group> for GroupResponseData {
+    fn from(group: &Group) -> Self {
+        Self {
+            id: group.id().to_string(),
+            name: group.name().to_string(),
+            members: group
+                .members()
+                .iter()
+                .map(|member| GroupMemberResponseData {
+                    user_id: member.user_id().to_string(),
+                    role: member.role().to_string(),
+                })
+                .collect(),
+            wishlists: group
+                .visible_wishlists()
+                .map(|shared| SharedWishlistResponseData {
+                    wishlist_id: shared.wishlist_id().to_string(),
+                    shared_by: shared.shared_by().to_string(),
+                    shared_at: shared.shared_at(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The body of a [Group] creation request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct CreateGroupHttpRequestBody {
+    pub user_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseCreateGroupHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("name is invalid")]
+    Name(#[from] GroupNameInvalidError),
+}
+
+impl CreateGroupHttpRequestBody {
+    /// Converts the HTTP request body into a domain [CreateGroupRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<CreateGroupRequest, FieldErrors<ParseCreateGroupHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseCreateGroupHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let name = errors.check_field("/name", GroupName::new(&self.name));
+        let (Some(user_id), Some(name)) = (user_id, name) else {
+            return Err(errors);
+        };
+        Ok(CreateGroupRequest::new(user_id, name))
+    }
+}
+
+/// Create a [Group], such as a family, with the user as its admin.
+///
+/// # Responses
+///
+/// - 201 Created: the [Group].
+/// - 422 Unprocessable entity: the user ID is invalid or does not exist, or the name is empty.
+#[utoipa::path(
+    post,
+    path = "/groups",
+    tag = "groups",
+    request_body = CreateGroupHttpRequestBody,
+    responses(
+        (status = 201, description = "The Group.", body = ApiResponseBody<GroupResponseData>),
+        (status = 422, description = "The user ID is invalid or does not exist, or the name is empty.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn create_group<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiJson(body): ApiJson<CreateGroupHttpRequestBody>,
+) -> Result<ApiSuccess<GroupResponseData>, ApiError> {
+    let domain_req = body.try_into_domain()?;
+    state
+        .services
+        .create_group(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref group| ApiSuccess::new(StatusCode::CREATED, group.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockGroupService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+    use crate::interface::http::problem::InvalidField;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_group_success() {
+        let user_id = Uuid::now_v7();
+        let group = Group::new(Uuid::now_v7(), "Family".into(), user_id);
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            GroupResponseData {
+                id: group.id().to_string(),
+                name: "Family".to_string(),
+                members: vec![GroupMemberResponseData {
+                    user_id: user_id.to_string(),
+                    role: "admin".to_string(),
+                }],
+                wishlists: vec![],
+            },
+        );
+        let mut mock_group_service = MockGroupService::new();
+        mock_group_service
+            .expect_create_group()
+            .withf(move |req| req.user_id() == user_id && req.name() == &"Family".into())
+            .return_once(move |_| Box::pin(future::ready(Ok(group))));
+        let state = test_services().with_group(mock_group_service).into_state();
+        let body = ApiJson(CreateGroupHttpRequestBody {
+            user_id: user_id.to_string(),
+            name: "Family".to_string(),
+        });
+
+        let actual = create_group(state, body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_group_blank_name() {
+        let state = test_services().into_state();
+        let body = ApiJson(CreateGroupHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            name: " ".to_string(),
+        });
+
+        let actual = create_group(state, body).await;
+        assert_eq!(
+            actual,
+            Err(ApiError::UnprocessableEntity(Problem::invalid_fields(
+                vec![InvalidField::body("/name", "name is invalid")]
+            )))
+        );
+    }
+}
@@ -0,0 +1,465 @@
+/*
+Module `create_item` specifies an HTTP handler for adding a new [Item] to a [Wishlist], and the
+associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    CreateItemError, CreateItemRequest, Item, ItemAttribute, ItemAttributeInvalidError,
+    ItemAttributes, ItemImageUrl, ItemImageUrlInvalidError, ItemLinkUrl, ItemLinkUrlInvalidError,
+    ItemNotes, ItemNotesInvalidError, ItemPrice, ItemPriceInvalidError, ItemPriority,
+    ItemPriorityInvalidError, ItemTitle, ItemTitleInvalidError,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<CreateItemError> for ApiError {
+    fn from(e: CreateItemError) -> Self {
+        match e {
+            CreateItemError::Duplicate => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ItemAlreadyInWishlist,
+                "An item with the same link already exists in this wishlist",
+            )),
+            CreateItemError::WishlistDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::WishlistNotFound,
+                    format!("Wishlist ID {} does not exist", id),
+                ))
+            }
+            CreateItemError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            CreateItemError::WishlistArchived { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::WishlistArchived,
+                format!("Wishlist ID {} is archived", id),
+            )),
+            CreateItemError::MissingTitle => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ItemTitleMissing,
+                "title is required when it cannot be found on the linked page",
+            )),
+            CreateItemError::ImageDoesNotExist { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ImageNotFound,
+                format!("Image ID {} does not exist", id),
+            )),
+            CreateItemError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// A variant attribute of an [Item], e.g. `{ "key": "size", "value": "M" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ItemAttributeData {
+    pub key: String,
+    pub value: String,
+}
+
+/// The response data describing an [Item].
+///
+/// After creation, details that were not part of the request are returned as found on the
+/// linked page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ItemResponseData {
+    pub id: String,
+    pub wishlist_id: String,
+    pub title: String,
+    pub link_url: String,
+    pub image_url: Option<String>,
+    pub image_id: Option<String>,
+    pub price: Option<Decimal>,
+    pub priority: String,
+    pub notes: Option<String>,
+    pub attributes: Vec<ItemAttributeData>,
+    pub received: bool,
+    pub version: u64,
+}
+
+impl From<&Item> for ItemResponseData {
+    fn from(item: &Item) -> Self {
+        Self {
+            id: item.id().to_string(),
+            wishlist_id: item.wishlist_id().to_string(),
+            title: item.title().to_string(),
+            link_url: item.link_url().to_string(),
+            image_url: item.image_url().map(ToString::to_string),
+            image_id: item.image_id().map(|id| id.to_string()),
+            price: item.price().map(ItemPrice::value),
+            priority: item.priority().to_string(),
+            notes: item.notes().map(ToString::to_string),
+            attributes: item
+                .attributes()
+                .iter()
+                .map(|attribute| ItemAttributeData {
+                    key: attribute.key().to_string(),
+                    value: attribute.value().to_string(),
+                })
+                .collect(),
+            received: item.received(),
+            version: item.version(),
+        }
+    }
+}
+
+/// The body of an [Item] creation request.
+///
+/// Besides the `user_id` of the owner, only `link_url` is required: missing fields are pre-filled
+/// from the linked page. `image_id` refers to an image previously uploaded to `/api/images`.
+/// `priority` defaults to `normal`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct CreateItemHttpRequestBody {
+    pub user_id: String,
+    pub title: Option<String>,
+    pub link_url: String,
+    pub image_url: Option<String>,
+    pub image_id: Option<Uuid>,
+    pub price: Option<Decimal>,
+    pub priority: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub attributes: Vec<ItemAttributeData>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseCreateItemHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("title is invalid")]
+    Title(#[from] ItemTitleInvalidError),
+    #[error("link url {} is invalid", .0.invalid_url)]
+    LinkUrl(#[from] ItemLinkUrlInvalidError),
+    #[error("image url {} is invalid", .0.invalid_url)]
+    ImageUrl(#[from] ItemImageUrlInvalidError),
+    #[error("price {} is invalid", .0.invalid_price)]
+    Price(#[from] ItemPriceInvalidError),
+    #[error("priority {} is invalid, expected one of nice_to_have, normal, must_have", .0.invalid_priority)]
+    Priority(#[from] ItemPriorityInvalidError),
+    #[error(transparent)]
+    Notes(#[from] ItemNotesInvalidError),
+    #[error(transparent)]
+    Attribute(#[from] ItemAttributeInvalidError),
+}
+
+impl CreateItemHttpRequestBody {
+    /// Converts the HTTP request body into a domain [CreateItemRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<CreateItemRequest, FieldErrors<ParseCreateItemHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseCreateItemHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let title = errors.check_field(
+            "/title",
+            self.title.as_deref().map(ItemTitle::new).transpose(),
+        );
+        let link_url = errors.check_field("/link_url", ItemLinkUrl::new(&self.link_url));
+        let image_url = errors.check_field(
+            "/image_url",
+            self.image_url.as_deref().map(ItemImageUrl::new).transpose(),
+        );
+        let price = errors.check_field("/price", self.price.map(ItemPrice::new).transpose());
+        let priority = errors.check_field(
+            "/priority",
+            self.priority
+                .as_deref()
+                .map(str::parse::<ItemPriority>)
+                .transpose(),
+        );
+        let notes = errors.check_field(
+            "/notes",
+            self.notes.as_deref().map(ItemNotes::new).transpose(),
+        );
+        let attributes = self
+            .attributes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, attribute)| {
+                errors.check_field(
+                    format!("/attributes/{}", index),
+                    ItemAttribute::new(&attribute.key, &attribute.value),
+                )
+            })
+            .collect::<Vec<_>>();
+        let attributes = if errors.is_empty() {
+            errors.check_field("/attributes", ItemAttributes::new(attributes))
+        } else {
+            None
+        };
+        let (
+            Some(user_id),
+            Some(title),
+            Some(link_url),
+            Some(image_url),
+            Some(price),
+            Some(priority),
+            Some(notes),
+            Some(attributes),
+        ) = (
+            user_id, title, link_url, image_url, price, priority, notes, attributes,
+        )
+        else {
+            return Err(errors);
+        };
+        Ok(
+            CreateItemRequest::new(wishlist_id, user_id, title, link_url, image_url, price)
+                .with_image_id(self.image_id)
+                .with_priority(priority.unwrap_or_default())
+                .with_notes(notes)
+                .with_attributes(attributes),
+        )
+    }
+}
+
+/// Add a new [Item] to a [Wishlist].
+///
+/// # Responses
+///
+/// - 201 Created: the [Item] was successfully created.
+/// - 403 Forbidden: the user does not own the [Wishlist].
+/// - 422 Unprocessable entity: the user ID is invalid, the [Wishlist] or the uploaded image does
+///   not exist, the
+///   [Wishlist] is archived, the link is already in the [Wishlist], or no title was given nor
+///   found on the linked page.
+#[utoipa::path(
+    post,
+    path = "/wishlists/{wishlist_id}/items",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+    ),
+    request_body = CreateItemHttpRequestBody,
+    responses(
+        (status = 201, description = "The Item was successfully created.", body = ApiResponseBody<ItemResponseData>),
+        (status = 403, description = "The user does not own the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid, the Wishlist or the uploaded image does not exist, the Wishlist is archived, the link is already in the Wishlist, or no title was given nor found on the linked page.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn create_item<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<CreateItemHttpRequestBody>,
+) -> Result<ApiSuccess<ItemResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(wishlist_id)?;
+    state
+        .services
+        .create_item(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref item: Item| ApiSuccess::new(StatusCode::CREATED, item.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, str::FromStr, sync::Arc};
+
+    use crate::domain::MockItemService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+    use crate::interface::http::problem::InvalidField;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_item_success() {
+        let id = Uuid::now_v7();
+        let wishlist_id = Uuid::now_v7();
+
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service
+            .expect_create_item()
+            .return_once(move |req| {
+                let mut item = Item::create(
+                    id,
+                    req.wishlist_id(),
+                    "Found on page".into(),
+                    req.link_url().clone(),
+                    None,
+                    req.price().cloned(),
+                );
+                item.set_priority(req.priority());
+                item.set_notes(req.notes().cloned());
+                item.set_attributes(req.attributes().clone());
+                Box::pin(future::ready(Ok(item)))
+            });
+        let service = test_services().with_item(mock_item_service).build();
+        let state = axum::extract::State(AppState {
+            services: Arc::new(service),
+        });
+        let body = ApiJson(CreateItemHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            title: None,
+            link_url: "https://shop.example/p/1".to_string(),
+            image_url: None,
+            image_id: None,
+            price: Some(Decimal::from_str("12.50").unwrap()),
+            priority: Some("must_have".to_string()),
+            notes: Some(" Navy ".to_string()),
+            attributes: vec![ItemAttributeData {
+                key: "size".to_string(),
+                value: "M".to_string(),
+            }],
+        });
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            ItemResponseData {
+                id: id.to_string(),
+                wishlist_id: wishlist_id.to_string(),
+                title: "Found on page".to_string(),
+                link_url: "https://shop.example/p/1".to_string(),
+                image_url: None,
+                image_id: None,
+                price: Some(Decimal::from_str("12.5").unwrap()),
+                priority: "must_have".to_string(),
+                notes: Some("Navy".to_string()),
+                attributes: vec![ItemAttributeData {
+                    key: "size".to_string(),
+                    value: "M".to_string(),
+                }],
+                received: false,
+                version: 1,
+            },
+        );
+
+        let actual = create_item(state, ApiPath(wishlist_id), body).await;
+        assert!(
+            actual.is_ok(),
+            "expected create_item to succeed, but got {:?}",
+            actual
+        );
+
+        let actual = actual.unwrap();
+        assert_eq!(
+            actual, expected,
+            "expected ApiSuccess {:?}, but got {:?}",
+            expected, actual
+        )
+    }
+
+    #[test]
+    fn test_parse_invalid_link_url() {
+        let body = CreateItemHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            title: None,
+            link_url: "ftp://shop.example/p/1".to_string(),
+            image_url: None,
+            image_id: None,
+            price: None,
+            priority: None,
+            notes: None,
+            attributes: Vec::new(),
+        };
+        let result = body.try_into_domain(Uuid::now_v7());
+        assert_eq!(
+            result.unwrap_err().into_invalid_fields(),
+            vec![InvalidField::body(
+                "/link_url",
+                "link url ftp://shop.example/p/1 is invalid"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_details() {
+        let body = |priority: &str, notes: &str, keys: &[&str]| CreateItemHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            title: None,
+            link_url: "https://shop.example/p/1".to_string(),
+            image_url: None,
+            image_id: None,
+            price: None,
+            priority: Some(priority.to_string()),
+            notes: Some(notes.to_string()),
+            attributes: keys
+                .iter()
+                .map(|key| ItemAttributeData {
+                    key: key.to_string(),
+                    value: "x".to_string(),
+                })
+                .collect(),
+        };
+        assert!(body("normal", "ok", &["size"])
+            .try_into_domain(Uuid::now_v7())
+            .is_ok());
+        assert_eq!(
+            body("urgent", "ok", &[])
+                .try_into_domain(Uuid::now_v7())
+                .unwrap_err()
+                .into_invalid_fields(),
+            vec![InvalidField::body(
+                "/priority",
+                "priority urgent is invalid, expected one of nice_to_have, normal, must_have"
+            )]
+        );
+        assert_eq!(
+            body("normal", &"a".repeat(ItemNotes::MAX_LENGTH + 1), &[])
+                .try_into_domain(Uuid::now_v7())
+                .unwrap_err()
+                .into_invalid_fields(),
+            vec![InvalidField::body(
+                "/notes",
+                format!(
+                    "Notes must be between 1 and {} characters long",
+                    ItemNotes::MAX_LENGTH
+                )
+            )]
+        );
+        assert_eq!(
+            body("normal", "ok", &["size", "Size"])
+                .try_into_domain(Uuid::now_v7())
+                .unwrap_err()
+                .into_invalid_fields(),
+            vec![InvalidField::body(
+                "/attributes",
+                "Attribute Size is given more than once"
+            )]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_item_not_owner() {
+        let (user_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let expected = ApiError::Forbidden(Problem::new(
+            ProblemType::NotWishlistOwner,
+            format!("Wishlist ID {} is not yours", wishlist_id),
+        ));
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service
+            .expect_create_item()
+            .withf(move |req| req.wishlist_id() == wishlist_id && req.user_id() == user_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(CreateItemError::NotWishlistOwner {
+                    id: wishlist_id,
+                })))
+            });
+        let state = test_services().with_item(mock_item_service).into_state();
+        let body = ApiJson(CreateItemHttpRequestBody {
+            user_id: user_id.to_string(),
+            title: None,
+            link_url: "https://shop.example/p/1".to_string(),
+            image_url: None,
+            image_id: None,
+            price: None,
+            priority: None,
+            notes: None,
+            attributes: Vec::new(),
+        });
+
+        let actual = create_item(state, ApiPath(wishlist_id), body).await;
+        assert_eq!(actual, Err(expected));
+    }
+}
@@ -0,0 +1,220 @@
+/*
+Module `create_section` specifies an HTTP handler for adding a named section to a [Wishlist], and
+the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    CreateSectionError, CreateSectionRequest, SectionName, SectionNameInvalidError, WishlistSection,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<CreateSectionError> for ApiError {
+    fn from(e: CreateSectionError) -> Self {
+        match e {
+            CreateSectionError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            CreateSectionError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            CreateSectionError::DuplicateName { name } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::SectionAlreadyExists,
+                format!("Section {} already exists", name),
+            )),
+            CreateSectionError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [WishlistSection].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct SectionResponseData {
+    pub id: String,
+    pub name: String,
+}
+
+impl From<&WishlistSection> for SectionResponseData {
+    fn from(section: &WishlistSection) -> Self {
+        Self {
+            id: section.id().to_string(),
+            name: section.name().to_string(),
+        }
+    }
+}
+
+/// The body of a section creation request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct CreateSectionHttpRequestBody {
+    pub user_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseCreateSectionHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Name(#[from] SectionNameInvalidError),
+}
+
+impl CreateSectionHttpRequestBody {
+    /// Converts the HTTP request body into a domain [CreateSectionRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<CreateSectionRequest, FieldErrors<ParseCreateSectionHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseCreateSectionHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let name = errors.check_field("/name", SectionName::new(&self.name));
+        let (Some(user_id), Some(name)) = (user_id, name) else {
+            return Err(errors);
+        };
+        Ok(CreateSectionRequest::new(wishlist_id, user_id, name))
+    }
+}
+
+/// Add a named section to a [Wishlist], after its existing sections.
+///
+/// # Responses
+///
+/// - 201 Created: the section was successfully created.
+/// - 403 Forbidden: the user does not own the [Wishlist].
+/// - 404 Not found: the [Wishlist] does not exist.
+/// - 422 Unprocessable entity: the user ID or the name is invalid, or the name is already used
+///   in the [Wishlist].
+#[utoipa::path(
+    post,
+    path = "/wishlists/{wishlist_id}/sections",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+    ),
+    request_body = CreateSectionHttpRequestBody,
+    responses(
+        (status = 201, description = "The section was successfully created.", body = ApiResponseBody<SectionResponseData>),
+        (status = 403, description = "The user does not own the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Wishlist does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID or the name is invalid, or the name is already used in the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn create_section<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<CreateSectionHttpRequestBody>,
+) -> Result<ApiSuccess<SectionResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(wishlist_id)?;
+    state
+        .services
+        .create_section(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref section| ApiSuccess::new(StatusCode::CREATED, section.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::MockWishlistService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    fn state(wish_service: MockWishlistService) -> State<AppState<impl UseCases>> {
+        let service = test_services().with_wishlist(wish_service).build();
+        State(AppState {
+            services: Arc::new(service),
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_section_success() {
+        let id = Uuid::now_v7();
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_create_section()
+            .return_once(move |req| {
+                let section = WishlistSection::new(id, req.name().clone());
+                Box::pin(future::ready(Ok(section)))
+            });
+        let body = ApiJson(CreateSectionHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            name: " Books ".to_string(),
+        });
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            SectionResponseData {
+                id: id.to_string(),
+                name: "Books".to_string(),
+            },
+        );
+
+        let actual = create_section(state(mock_wish_service), ApiPath(Uuid::now_v7()), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_section_duplicate() {
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_create_section()
+            .return_once(|req| {
+                let name = req.name().clone();
+                Box::pin(future::ready(Err(CreateSectionError::DuplicateName {
+                    name,
+                })))
+            });
+        let body = ApiJson(CreateSectionHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            name: "Books".to_string(),
+        });
+
+        let actual = create_section(state(mock_wish_service), ApiPath(Uuid::now_v7()), body).await;
+        assert!(matches!(actual, Err(ApiError::UnprocessableEntity(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_section_not_owner() {
+        let (user_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let expected = ApiError::Forbidden(Problem::new(
+            ProblemType::NotWishlistOwner,
+            format!("Wishlist ID {} is not yours", wishlist_id),
+        ));
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_create_section()
+            .withf(move |req| req.wishlist_id() == wishlist_id && req.user_id() == user_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(CreateSectionError::NotWishlistOwner {
+                    id: wishlist_id,
+                })))
+            });
+        let body = ApiJson(CreateSectionHttpRequestBody {
+            user_id: user_id.to_string(),
+            name: "Books".to_string(),
+        });
+
+        let actual = create_section(state(mock_wish_service), ApiPath(wishlist_id), body).await;
+        assert_eq!(actual, Err(expected));
+    }
+}
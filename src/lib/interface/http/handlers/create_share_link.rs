@@ -0,0 +1,183 @@
+/*
+Module `create_share_link` specifies an HTTP handler for creating the share link of a [Wishlist],
+and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{ShareLinkError, ShareLinkRequest, Wishlist};
+use crate::interface::http::pages::page_path;
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<ShareLinkError> for ApiError {
+    fn from(e: ShareLinkError) -> Self {
+        match e {
+            ShareLinkError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            ShareLinkError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            ShareLinkError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for the share link of a [Wishlist].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ShareLinkResponseData {
+    pub wishlist_id: String,
+    pub token: String,
+    /// The path of the page of the [Wishlist], with the token, e.g. `/w/{slug}?token=...`. The
+    /// slug is percent-encoded.
+    pub path: String,
+}
+
+impl TryFrom<&Wishlist> for ShareLinkResponseData {
+    type Error = ApiError;
+
+    fn try_from(wishlist: &Wishlist) -> Result<Self, Self::Error> {
+        let token = wishlist.share_token().ok_or_else(|| {
+            ApiError::InternalServerError(format!(
+                "Wishlist ID {} has no share link after creating one",
+                wishlist.id()
+            ))
+        })?;
+        Ok(Self {
+            wishlist_id: wishlist.id().to_string(),
+            token: token.to_string(),
+            path: page_path(&wishlist.slug().to_string(), Some(token.as_str())),
+        })
+    }
+}
+
+/// The body of a request on the share link of a [Wishlist].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct CreateShareLinkHttpRequestBody {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseShareLinkHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl CreateShareLinkHttpRequestBody {
+    /// Converts the HTTP request body into a domain [ShareLinkRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<ShareLinkRequest, FieldErrors<ParseShareLinkHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseShareLinkHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(ShareLinkRequest::new(wishlist_id, user_id))
+    }
+}
+
+/// Create the share link of a [Wishlist], replacing the previous one. Whoever has the link may
+/// see the page of the [Wishlist], even a private one, and reserve its items without an account.
+///
+/// # Responses
+///
+/// - 201 Created: the new share link.
+/// - 403 Forbidden: the user does not own the [Wishlist].
+/// - 404 Not found: the [Wishlist] does not exist.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    post,
+    path = "/wishlists/{wishlist_id}/share-link",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+    ),
+    request_body = CreateShareLinkHttpRequestBody,
+    responses(
+        (status = 201, description = "The new share link.", body = ApiResponseBody<ShareLinkResponseData>),
+        (status = 403, description = "The user does not own the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Wishlist does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn create_share_link<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<CreateShareLinkHttpRequestBody>,
+) -> Result<ApiSuccess<ShareLinkResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(wishlist_id)?;
+    let wishlist = state.services.create_share_link(&domain_req).await?;
+    Ok(ApiSuccess::new(
+        StatusCode::CREATED,
+        ShareLinkResponseData::try_from(&wishlist)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{MockWishlistService, ShareToken, WishlistName, WishlistSlug};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_share_link() {
+        let user_id = Uuid::now_v7();
+        let mut wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            user_id,
+            WishlistName::from("Mom & Dad?"),
+            WishlistSlug::from("Mom & Dad?"),
+            true,
+        );
+        wishlist.set_share_token(Some(ShareToken::from("secret")));
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            ShareLinkResponseData {
+                wishlist_id: wishlist.id().to_string(),
+                token: "secret".to_string(),
+                path: format!(
+                    "/w/mom-%26-dad%3F-{}?token=secret",
+                    &wishlist.slug().to_string()[11..]
+                ),
+            },
+        );
+        let wishlist_id = wishlist.id();
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_create_share_link()
+            .withf(move |req| req.wishlist_id() == wishlist_id && req.user_id() == user_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(wishlist))));
+        let state = test_services()
+            .with_wishlist(mock_wish_service)
+            .into_state();
+        let body = ApiJson(CreateShareLinkHttpRequestBody {
+            user_id: user_id.to_string(),
+        });
+
+        let actual = create_share_link(state, ApiPath(wishlist_id), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -5,25 +5,27 @@ associated data structures.
 
 use axum::extract::State;
 use axum::http::StatusCode;
-use axum::Json;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use utoipa::ToSchema;
 
 use crate::application::UseCases;
 use crate::domain::{
     CreateUserError, CreateUserRequest, User, UserEmail, UserEmailInvalidError, UserPassword,
     UserPasswordInvalidError,
 };
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
 use crate::interface::http::AppState;
 
-use super::{ApiError, ApiSuccess};
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
 
 impl From<CreateUserError> for ApiError {
     fn from(e: CreateUserError) -> Self {
         match e {
-            CreateUserError::Duplicate { email } => {
-                Self::UnprocessableEntity(format!("User with email {} already exists", email))
-            }
+            CreateUserError::Duplicate { email } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::UserAlreadyExists,
+                format!("User with email {} already exists", email),
+            )),
             CreateUserError::Unknown(cause) => {
                 tracing::error!("{:?}\n{}", cause, cause.backtrace());
                 Self::InternalServerError("Internal server error".to_string())
@@ -32,23 +34,8 @@ impl From<CreateUserError> for ApiError {
     }
 }
 
-impl From<ParseCreateUserHttpRequestError> for ApiError {
-    fn from(e: ParseCreateUserHttpRequestError) -> Self {
-        let message = match e {
-            ParseCreateUserHttpRequestError::EmailAddress(cause) => {
-                format!("email address {} is invalid", cause.invalid_email)
-            }
-            ParseCreateUserHttpRequestError::Password(_) => {
-                format!("password is invalid")
-            }
-        };
-
-        Self::UnprocessableEntity(message)
-    }
-}
-
 /// The response body data field for successful [User] creation.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 pub struct CreateUserResponseData {
     id: String,
 }
@@ -62,25 +49,31 @@ impl From<&User> for CreateUserResponseData {
 }
 
 /// The body of an [User] creation request.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
 pub struct CreateUserHttpRequestBody {
-    email: String,
-    password: String,
+    pub email: String,
+    pub password: String,
 }
 
 #[derive(Debug, Clone, Error)]
-enum ParseCreateUserHttpRequestError {
-    #[error(transparent)]
+pub enum ParseCreateUserHttpRequestError {
+    #[error("email address {} is invalid", .0.invalid_email)]
     EmailAddress(#[from] UserEmailInvalidError),
-    #[error(transparent)]
+    #[error("password is invalid")]
     Password(#[from] UserPasswordInvalidError),
 }
 
 impl CreateUserHttpRequestBody {
     /// Converts the HTTP request body into a domain request.
-    fn try_into_domain(self) -> Result<CreateUserRequest, ParseCreateUserHttpRequestError> {
-        let email = UserEmail::new(&self.email)?;
-        let password = UserPassword::new(&self.password)?;
+    pub fn try_into_domain(
+        self,
+    ) -> Result<CreateUserRequest, FieldErrors<ParseCreateUserHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let email = errors.check_field("/email", UserEmail::new(&self.email));
+        let password = errors.check_field("/password", UserPassword::new(&self.password));
+        let (Some(email), Some(password)) = (email, password) else {
+            return Err(errors);
+        };
         Ok(CreateUserRequest::new(email, password))
     }
 }
@@ -90,10 +83,28 @@ impl CreateUserHttpRequestBody {
 /// # Responses
 ///
 /// - 201 Created: the [User] was successfully created.
-/// - 422 Unprocessable entity: An [User] with the same name already exists.
+/// - 422 Unprocessable entity: An [User] with the same name already exists, or the
+///   `Idempotency-Key` was used for another request.
+/// - 429 Too Many Requests: too many authors were created from the client IP; retry after the
+///   seconds in `Retry-After`.
+#[utoipa::path(
+    post,
+    path = "/authors",
+    tag = "users",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "A key unique to this creation, so that retries with the same key replay its response rather than create a duplicate."),
+    ),
+    request_body = CreateUserHttpRequestBody,
+    responses(
+        (status = 201, description = "The User was successfully created.", body = ApiResponseBody<CreateUserResponseData>),
+        (status = 422, description = "An User with the same name already exists, or the Idempotency-Key was used for another request.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 429, description = "Too many authors were created from the client IP.", body = ProblemDetails, content_type = "application/problem+json",
+            headers(("Retry-After" = u64, description = "How many seconds to wait before retrying."))),
+    )
+)]
 pub async fn create_user<UC: UseCases>(
     State(state): State<AppState<UC>>,
-    Json(body): Json<CreateUserHttpRequestBody>,
+    ApiJson(body): ApiJson<CreateUserHttpRequestBody>,
 ) -> Result<ApiSuccess<CreateUserResponseData>, ApiError> {
     let domain_req = body.try_into_domain()?;
     state
@@ -110,10 +121,8 @@ mod tests {
 
     use uuid::Uuid;
 
-    use crate::{
-        application::Service,
-        domain::{MockUserService, MockWishlistService},
-    };
+    use crate::domain::{MockUserService, MockWishlistService};
+    use crate::interface::http::handlers::test_services;
 
     use super::*;
 
@@ -131,11 +140,14 @@ mod tests {
                 Box::pin(future::ready(Ok(user)))
             });
         let mock_wish_service = MockWishlistService::new();
-        let service = Service::new(mock_user_service, mock_wish_service);
+        let service = test_services()
+            .with_user(mock_user_service)
+            .with_wishlist(mock_wish_service)
+            .build();
         let state = axum::extract::State(AppState {
             services: Arc::new(service),
         });
-        let body = axum::extract::Json(CreateUserHttpRequestBody {
+        let body = ApiJson(CreateUserHttpRequestBody {
             email: email.to_string(),
             password: password.to_string(),
         });
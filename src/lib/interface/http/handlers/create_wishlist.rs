@@ -3,25 +3,30 @@ Module `create_wishlist` specifies an HTTP handler for creating a new [Wishlist]
 associated data structures.
 */
 
+use axum::extract::State;
 use axum::http::StatusCode;
-use axum::{extract::State, Json};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::domain::{
     CreateWishlistError, CreateWishlistRequest, OwnerIdInvalidError, WishlistName,
     WishlistNameInvalidError,
 };
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
 use crate::{application::UseCases, domain::Wishlist, interface::http::AppState};
 
-use super::{ApiError, ApiSuccess};
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
 
 impl From<CreateWishlistError> for ApiError {
     fn from(e: CreateWishlistError) -> Self {
         match e {
             CreateWishlistError::OwnerIdDoesNotExist { id } => {
-                Self::UnprocessableEntity(format!("Owner ID {} does not exist", id))
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::UserNotFound,
+                    format!("Owner ID {} does not exist", id),
+                ))
             }
             CreateWishlistError::Unknown(cause) => {
                 tracing::error!("{:?}\n{}", cause, cause.backtrace());
@@ -31,23 +36,8 @@ impl From<CreateWishlistError> for ApiError {
     }
 }
 
-impl From<ParseCreateWishlistHttpRequestError> for ApiError {
-    fn from(e: ParseCreateWishlistHttpRequestError) -> Self {
-        let message = match e {
-            ParseCreateWishlistHttpRequestError::InvalidOwnerId(cause) => {
-                format!("owner id {} is invalid", cause.invalid_owner_id)
-            }
-            ParseCreateWishlistHttpRequestError::InvalidName(_) => {
-                format!("name is invalid")
-            }
-        };
-
-        Self::UnprocessableEntity(message)
-    }
-}
-
 /// The response body data field for successful [Wishlist] creation.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 pub struct CreateWishlistResponseData {
     pub id: String,
 }
@@ -61,7 +51,7 @@ impl From<&Wishlist> for CreateWishlistResponseData {
 }
 
 /// The body of an [Wishlist] creation request.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
 pub struct CreateWishlistHttpRequestBody {
     pub name: String,
     pub owner_id: String,
@@ -70,9 +60,9 @@ pub struct CreateWishlistHttpRequestBody {
 
 #[derive(Debug, Clone, Error)]
 pub enum ParseCreateWishlistHttpRequestError {
-    #[error(transparent)]
+    #[error("owner id {} is invalid", .0.invalid_owner_id)]
     InvalidOwnerId(#[from] OwnerIdInvalidError),
-    #[error(transparent)]
+    #[error("name is invalid")]
     InvalidName(#[from] WishlistNameInvalidError),
 }
 
@@ -80,13 +70,19 @@ impl CreateWishlistHttpRequestBody {
     /// Converts the HTTP request body into a domain [CreateWishlistRequest].
     pub fn try_into_domain(
         self,
-    ) -> Result<CreateWishlistRequest, ParseCreateWishlistHttpRequestError> {
-        let owner_id = Uuid::parse_str(&self.owner_id).map_err(|_| OwnerIdInvalidError {
-            invalid_owner_id: Uuid::parse_str(&self.owner_id).unwrap_or_default(),
-        })?;
-        let name = WishlistName::new(&self.name)?;
-        let private = self.private;
-        Ok(CreateWishlistRequest::new(owner_id, name, private))
+    ) -> Result<CreateWishlistRequest, FieldErrors<ParseCreateWishlistHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let owner_id = errors.check_field(
+            "/owner_id",
+            Uuid::parse_str(&self.owner_id).map_err(|_| OwnerIdInvalidError {
+                invalid_owner_id: Uuid::parse_str(&self.owner_id).unwrap_or_default(),
+            }),
+        );
+        let name = errors.check_field("/name", WishlistName::new(&self.name));
+        let (Some(owner_id), Some(name)) = (owner_id, name) else {
+            return Err(errors);
+        };
+        Ok(CreateWishlistRequest::new(owner_id, name, self.private))
     }
 }
 
@@ -95,10 +91,24 @@ impl CreateWishlistHttpRequestBody {
 /// # Response
 ///
 /// - 201 Created: the [Wishlist] was successfully created.
-/// - 422 Unprocessable entity: An [Wishlist] with the same name already exists.
+/// - 422 Unprocessable entity: An [Wishlist] with the same name already exists, or the
+///   `Idempotency-Key` was used for another request.
+#[utoipa::path(
+    post,
+    path = "/wishlists",
+    tag = "wishlists",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "A key unique to this creation, so that retries with the same key replay its response rather than create a duplicate."),
+    ),
+    request_body = CreateWishlistHttpRequestBody,
+    responses(
+        (status = 201, description = "The Wishlist was successfully created.", body = ApiResponseBody<CreateWishlistResponseData>),
+        (status = 422, description = "An Wishlist with the same name already exists, or the Idempotency-Key was used for another request.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
 pub async fn create_wishlist<UC: UseCases>(
     State(state): State<AppState<UC>>,
-    Json(body): Json<CreateWishlistHttpRequestBody>,
+    ApiJson(body): ApiJson<CreateWishlistHttpRequestBody>,
 ) -> Result<ApiSuccess<CreateWishlistResponseData>, ApiError> {
     let domain_req = body.try_into_domain()?;
     state
@@ -113,10 +123,8 @@ pub async fn create_wishlist<UC: UseCases>(
 mod tests {
     use std::{future, sync::Arc};
 
-    use crate::{
-        application::Service,
-        domain::{MockUserService, MockWishlistService, WishlistSlug},
-    };
+    use crate::domain::{MockUserService, MockWishlistService, WishlistSlug};
+    use crate::interface::http::handlers::test_services;
 
     use super::*;
 
@@ -141,11 +149,14 @@ mod tests {
             });
 
         let mock_user_service = MockUserService::new();
-        let service = Service::new(mock_user_service, mock_wish_service);
+        let service = test_services()
+            .with_user(mock_user_service)
+            .with_wishlist(mock_wish_service)
+            .build();
         let state = axum::extract::State(AppState {
             services: Arc::new(service),
         });
-        let body = axum::extract::Json(CreateWishlistHttpRequestBody {
+        let body = ApiJson(CreateWishlistHttpRequestBody {
             name: name.to_string(),
             owner_id: id.to_string(),
             private,
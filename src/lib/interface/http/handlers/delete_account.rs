@@ -0,0 +1,207 @@
+/*
+Module `delete_account` specifies an HTTP handler for deleting the account of a user after a
+grace period, and the data structures shared with the other account handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    AccountDeletion, ConfirmationToken, ConfirmedAccountRequest, DeleteAccountError,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<DeleteAccountError> for ApiError {
+    fn from(e: DeleteAccountError) -> Self {
+        match e {
+            DeleteAccountError::UserDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::UserNotFound,
+                format!("User ID {} does not exist", id),
+            )),
+            DeleteAccountError::InvalidConfirmation { id } => Self::Forbidden(Problem::new(
+                ProblemType::InvalidConfirmationToken,
+                format!(
+                    "The confirmation token of user ID {} is invalid or expired",
+                    id
+                ),
+            )),
+            DeleteAccountError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a pending [AccountDeletion].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct AccountDeletionResponseData {
+    pub user_id: String,
+    pub requested_at: DateTime<Utc>,
+    pub purge_at: DateTime<Utc>,
+}
+
+impl From<&AccountDeletion> for AccountDeletionResponseData {
+    fn from(deletion: &AccountDeletion) -> Self {
+        Self {
+            user_id: deletion.user_id().to_string(),
+            requested_at: deletion.requested_at(),
+            purge_at: deletion.purge_at(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseAccountHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+/// The query string of a request of a user about their own account, with the token they were
+/// emailed to confirm it, e.g. `?user_id=...&token=...`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConfirmedAccountHttpQuery {
+    pub user_id: String,
+    pub token: String,
+}
+
+impl ConfirmedAccountHttpQuery {
+    /// Converts the HTTP query into a domain [ConfirmedAccountRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<ConfirmedAccountRequest, FieldErrors<ParseAccountHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseAccountHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(ConfirmedAccountRequest::new(
+            user_id,
+            ConfirmationToken::from(self.token.as_str()),
+        ))
+    }
+}
+
+/// Delete the account of a user, confirmed with a token emailed to them. Their data is kept during
+/// a grace period, in which the deletion can be cancelled, then all of it is deleted or
+/// anonymised.
+///
+/// # Responses
+///
+/// - 202 Accepted: the pending deletion, with the time the data is purged at.
+/// - 403 Forbidden: the confirmation token is invalid or expired.
+/// - 404 Not found: the user does not exist.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    delete,
+    path = "/me",
+    tag = "account",
+    params(
+        ConfirmedAccountHttpQuery,
+    ),
+    responses(
+        (status = 202, description = "The pending deletion, with the time the data is purged at.", body = ApiResponseBody<AccountDeletionResponseData>),
+        (status = 403, description = "The confirmation token is invalid or expired.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The user does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn delete_account<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<ConfirmedAccountHttpQuery>,
+) -> Result<ApiSuccess<AccountDeletionResponseData>, ApiError> {
+    let domain_req = query.try_into_domain()?;
+    state
+        .services
+        .delete_account(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref deletion| ApiSuccess::new(StatusCode::ACCEPTED, deletion.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use chrono::Duration;
+
+    use crate::domain::MockAccountService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_delete_account_success() {
+        let user_id = Uuid::now_v7();
+        let requested_at: DateTime<Utc> = "2027-01-01T09:00:00Z".parse().unwrap();
+        let deletion = AccountDeletion::new(user_id, requested_at, Duration::days(30));
+        let expected = ApiSuccess::new(
+            StatusCode::ACCEPTED,
+            AccountDeletionResponseData {
+                user_id: user_id.to_string(),
+                requested_at,
+                purge_at: "2027-01-31T09:00:00Z".parse().unwrap(),
+            },
+        );
+        let mut mock_account_service = MockAccountService::new();
+        mock_account_service
+            .expect_delete_account()
+            .withf(move |req| req.user_id() == user_id && req.token().as_str() == "secret")
+            .return_once(move |_| Box::pin(future::ready(Ok(deletion))));
+        let state = test_services()
+            .with_account(mock_account_service)
+            .into_state();
+        let query = ApiQuery(ConfirmedAccountHttpQuery {
+            user_id: user_id.to_string(),
+            token: "secret".into(),
+        });
+
+        let actual = delete_account(state, query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_delete_account_invalid_confirmation() {
+        let user_id = Uuid::now_v7();
+        let expected = ApiError::Forbidden(Problem::new(
+            ProblemType::InvalidConfirmationToken,
+            format!(
+                "The confirmation token of user ID {} is invalid or expired",
+                user_id
+            ),
+        ));
+        let mut mock_account_service = MockAccountService::new();
+        mock_account_service
+            .expect_delete_account()
+            .withf(move |req| req.user_id() == user_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(
+                    DeleteAccountError::InvalidConfirmation { id: user_id },
+                )))
+            });
+        let state = test_services()
+            .with_account(mock_account_service)
+            .into_state();
+        let query = ApiQuery(ConfirmedAccountHttpQuery {
+            user_id: user_id.to_string(),
+            token: "guess".into(),
+        });
+
+        let actual = delete_account(state, query).await;
+        assert_eq!(actual, Err(expected));
+    }
+}
@@ -0,0 +1,185 @@
+/*
+Module `delete_section` specifies an HTTP handler for removing a section from a [Wishlist].
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{DeleteSectionError, DeleteSectionRequest};
+use crate::interface::http::conditional::{EntityTag, IfMatch};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::reorder_wishlist::{ParseWishlistLayoutHttpRequestError, WishlistLayoutResponseData};
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<DeleteSectionError> for ApiError {
+    fn from(e: DeleteSectionError) -> Self {
+        match e {
+            DeleteSectionError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            DeleteSectionError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            DeleteSectionError::SectionDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::SectionNotFound,
+                format!("Section ID {} does not exist", id),
+            )),
+            DeleteSectionError::VersionMismatch(err) => err.into(),
+            DeleteSectionError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The query string of a request removing a section from a [Wishlist], e.g. `?user_id=...`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteSectionHttpQuery {
+    pub user_id: String,
+}
+
+impl DeleteSectionHttpQuery {
+    /// Converts the HTTP query string into a domain [DeleteSectionRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+        section_id: Uuid,
+    ) -> Result<DeleteSectionRequest, FieldErrors<ParseWishlistLayoutHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        errors
+            .check_parameter(
+                "user_id",
+                Uuid::parse_str(&self.user_id)
+                    .map_err(|_| ParseWishlistLayoutHttpRequestError::UserId(self.user_id.clone())),
+            )
+            .map(|user_id| DeleteSectionRequest::new(wishlist_id, user_id, section_id))
+            .ok_or(errors)
+    }
+}
+
+/// Remove a section from a [Wishlist]. Its items stay in place, outside any section.
+///
+/// # Responses
+///
+/// - 200 OK: the new layout of the [Wishlist].
+/// - 403 Forbidden: the user does not own the [Wishlist].
+/// - 404 Not found: the [Wishlist] or the section does not exist.
+/// - 412 Precondition failed: the [Wishlist] changed since the version in `If-Match`.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    delete,
+    path = "/wishlists/{wishlist_id}/sections/{section_id}",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        ("section_id" = Uuid, Path, description = "The ID of the section."),
+        ("If-Match" = Option<String>, Header, description = "The ETag of the Wishlist as last read; the change only applies to that version."),
+        DeleteSectionHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The new layout of the Wishlist.", body = ApiResponseBody<WishlistLayoutResponseData>),
+        (status = 403, description = "The user does not own the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Wishlist or the section does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "The Wishlist changed since the version in If-Match.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn delete_section<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath((wishlist_id, section_id)): ApiPath<(Uuid, Uuid)>,
+    IfMatch(expected_version): IfMatch,
+    ApiQuery(query): ApiQuery<DeleteSectionHttpQuery>,
+) -> Result<ApiSuccess<WishlistLayoutResponseData>, ApiError> {
+    let domain_req = query
+        .try_into_domain(wishlist_id, section_id)?
+        .with_expected_version(expected_version);
+    state
+        .services
+        .delete_section(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref wishlist| {
+            ApiSuccess::new(StatusCode::OK, wishlist.into())
+                .with_etag(EntityTag::version(wishlist.version()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockWishlistService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_delete_section_not_found() {
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_delete_section()
+            .return_once(|req| {
+                let id = req.section_id();
+                Box::pin(future::ready(Err(
+                    DeleteSectionError::SectionDoesNotExist { id },
+                )))
+            });
+        let state = test_services()
+            .with_wishlist(mock_wish_service)
+            .into_state();
+
+        let actual = delete_section(
+            state,
+            ApiPath((Uuid::now_v7(), Uuid::now_v7())),
+            IfMatch::default(),
+            ApiQuery(DeleteSectionHttpQuery {
+                user_id: Uuid::now_v7().to_string(),
+            }),
+        )
+        .await;
+        assert!(matches!(actual, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_delete_section_not_owner() {
+        let (user_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let expected = ApiError::Forbidden(Problem::new(
+            ProblemType::NotWishlistOwner,
+            format!("Wishlist ID {} is not yours", wishlist_id),
+        ));
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_delete_section()
+            .withf(move |req| req.wishlist_id() == wishlist_id && req.user_id() == user_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(DeleteSectionError::NotWishlistOwner {
+                    id: wishlist_id,
+                })))
+            });
+        let state = test_services()
+            .with_wishlist(mock_wish_service)
+            .into_state();
+        let query = ApiQuery(DeleteSectionHttpQuery {
+            user_id: user_id.to_string(),
+        });
+
+        let actual = delete_section(
+            state,
+            ApiPath((wishlist_id, Uuid::now_v7())),
+            IfMatch::default(),
+            query,
+        )
+        .await;
+        assert_eq!(actual, Err(expected));
+    }
+}
@@ -0,0 +1,103 @@
+/*
+Module `delete_webhook` specifies an HTTP handler for deleting a webhook of a user.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{DeleteWebhookError, WebhookRequest};
+use crate::interface::http::problem::{Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::list_webhooks::WebhookHttpQuery;
+use super::register_webhook::WebhookResponseData;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<DeleteWebhookError> for ApiError {
+    fn from(e: DeleteWebhookError) -> Self {
+        match e {
+            DeleteWebhookError::WebhookDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WebhookNotFound,
+                format!("Webhook ID {} does not exist", id),
+            )),
+            DeleteWebhookError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// Delete a webhook of a user, with its delivery log. Pending deliveries are dropped.
+///
+/// # Responses
+///
+/// - 200 OK: the deleted [Webhook](crate::domain::Webhook).
+/// - 404 Not found: the user has no such webhook.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = Uuid, Path, description = "The ID of the webhook."),
+        WebhookHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The deleted Webhook(crate::domain::Webhook).", body = ApiResponseBody<WebhookResponseData>),
+        (status = 404, description = "The user has no such webhook.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn delete_webhook<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(webhook_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<WebhookHttpQuery>,
+) -> Result<ApiSuccess<WebhookResponseData>, ApiError> {
+    let domain_req = WebhookRequest::new(webhook_id, query.user_id()?);
+    state
+        .services
+        .delete_webhook(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref webhook| ApiSuccess::new(StatusCode::OK, webhook.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockWebhookService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_delete_webhook_not_found() {
+        let (user_id, webhook_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let expected = ApiError::NotFound(Problem::new(
+            ProblemType::WebhookNotFound,
+            format!("Webhook ID {} does not exist", webhook_id),
+        ));
+        let mut mock_webhook_service = MockWebhookService::new();
+        mock_webhook_service
+            .expect_delete_webhook()
+            .withf(move |req| req.webhook_id() == webhook_id && req.user_id() == user_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(
+                    DeleteWebhookError::WebhookDoesNotExist { id: webhook_id },
+                )))
+            });
+        let state = test_services()
+            .with_webhook(mock_webhook_service)
+            .into_state();
+        let query = ApiQuery(WebhookHttpQuery {
+            user_id: user_id.to_string(),
+        });
+
+        let actual = delete_webhook(state, ApiPath(webhook_id), query).await;
+        assert_eq!(actual, Err(expected));
+    }
+}
@@ -0,0 +1,161 @@
+/*
+Module `draw_exchange` specifies an HTTP handler for drawing a gift
+[Exchange](crate::domain::Exchange), and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{DrawError, DrawExchangeError, ExchangeActionRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_exchange::ExchangeResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<DrawExchangeError> for ApiError {
+    fn from(e: DrawExchangeError) -> Self {
+        match e {
+            DrawExchangeError::ExchangeDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ExchangeNotFound,
+                format!("Exchange ID {} does not exist", id),
+            )),
+            DrawExchangeError::NotOrganizer { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotExchangeOrganizer,
+                format!("Only the organizer of exchange ID {} can draw it", id),
+            )),
+            DrawExchangeError::AlreadyDrawn { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ExchangeAlreadyDrawn,
+                format!("Exchange ID {} has already been drawn", id),
+            )),
+            DrawExchangeError::Invalid(DrawError::TooFewParticipants) => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::TooFewParticipants,
+                    "an exchange needs at least two participants",
+                ))
+            }
+            DrawExchangeError::Invalid(DrawError::NoValidDraw) => Self::UnprocessableEntity(
+                Problem::new(ProblemType::NoValidDraw, "no draw satisfies the exclusions"),
+            ),
+            DrawExchangeError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of a request by a user about an [Exchange](crate::domain::Exchange).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct ExchangeActionHttpRequest {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseExchangeActionHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl ExchangeActionHttpRequest {
+    /// Converts the HTTP request into a domain [ExchangeActionRequest].
+    pub fn try_into_domain(
+        self,
+        exchange_id: Uuid,
+    ) -> Result<ExchangeActionRequest, FieldErrors<ParseExchangeActionHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseExchangeActionHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(ExchangeActionRequest::new(exchange_id, user_id))
+    }
+}
+
+/// Draw an [Exchange](crate::domain::Exchange): every participant is given another one to give
+/// a gift to, respecting the exclusions. The seed of the draw is stored so that it can be
+/// audited, and the response does not tell who gives to whom.
+///
+/// # Responses
+///
+/// - 200 OK: the drawn exchange.
+/// - 403 Forbidden: the user is not the organizer.
+/// - 404 Not found: the exchange does not exist.
+/// - 422 Unprocessable entity: the user ID is invalid, the exchange has already been drawn, has
+///   too few participants or no draw satisfies its exclusions.
+#[utoipa::path(
+    post,
+    path = "/exchanges/{exchange_id}/draw",
+    tag = "exchanges",
+    params(
+        ("exchange_id" = Uuid, Path, description = "The ID of the exchange."),
+    ),
+    request_body = ExchangeActionHttpRequest,
+    responses(
+        (status = 200, description = "The drawn exchange.", body = ApiResponseBody<ExchangeResponseData>),
+        (status = 403, description = "The user is not the organizer.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The exchange does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid, the exchange has already been drawn, has too few participants or no draw satisfies its exclusions.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn draw_exchange<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(exchange_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<ExchangeActionHttpRequest>,
+) -> Result<ApiSuccess<ExchangeResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(exchange_id)?;
+    state
+        .services
+        .draw_exchange(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref exchange| ApiSuccess::new(StatusCode::OK, exchange.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockExchangeService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_draw_exchange_without_valid_draw() {
+        let exchange_id = Uuid::now_v7();
+        let mut mock_exchange_service = MockExchangeService::new();
+        mock_exchange_service
+            .expect_draw_exchange()
+            .withf(move |req| req.exchange_id() == exchange_id)
+            .return_once(|_| {
+                Box::pin(future::ready(Err(DrawExchangeError::Invalid(
+                    DrawError::NoValidDraw,
+                ))))
+            });
+        let state = test_services()
+            .with_exchange(mock_exchange_service)
+            .into_state();
+        let body = ApiJson(ExchangeActionHttpRequest {
+            user_id: Uuid::now_v7().to_string(),
+        });
+
+        let actual = draw_exchange(state, ApiPath(exchange_id), body).await;
+        assert_eq!(
+            actual,
+            Err(ApiError::UnprocessableEntity(Problem::new(
+                ProblemType::NoValidDraw,
+                "no draw satisfies the exclusions".to_string()
+            )))
+        );
+    }
+}
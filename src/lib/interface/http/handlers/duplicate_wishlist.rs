@@ -0,0 +1,236 @@
+/*
+Module `duplicate_wishlist` specifies an HTTP handler for starting a new [Wishlist] from a copy of
+an existing one, and the [Wishlist] response data shared with the template handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    DuplicateWishlistError, DuplicateWishlistRequest, Wishlist, WishlistName,
+    WishlistNameInvalidError,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::set_wishlist_occasion::OccasionResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<DuplicateWishlistError> for ApiError {
+    fn from(e: DuplicateWishlistError) -> Self {
+        match e {
+            DuplicateWishlistError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            DuplicateWishlistError::UserDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::UserNotFound,
+                    format!("User ID {} does not exist", id),
+                ))
+            }
+            DuplicateWishlistError::NotAllowed { id } => Self::Forbidden(Problem::new(
+                ProblemType::WishlistNotVisible,
+                format!("Wishlist ID {} is neither yours nor a template", id),
+            )),
+            DuplicateWishlistError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [Wishlist].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WishlistResponseData {
+    pub id: String,
+    pub owner_id: String,
+    pub name: String,
+    pub slug: String,
+    pub private: bool,
+    pub template: bool,
+    pub archived: bool,
+    pub occasion: Option<OccasionResponseData>,
+    pub item_count: usize,
+    pub updated_at: DateTime<Utc>,
+    pub version: u64,
+}
+
+impl From<&Wishlist> for WishlistResponseData {
+    fn from(wishlist: &Wishlist) -> Self {
+        Self {
+            id: wishlist.id().to_string(),
+            owner_id: wishlist.owner_id().to_string(),
+            name: wishlist.name().to_string(),
+            slug: wishlist.slug().to_string(),
+            private: wishlist.private(),
+            template: wishlist.template(),
+            archived: wishlist.archived(),
+            occasion: wishlist.occasion().map(OccasionResponseData::from),
+            item_count: wishlist.items().len(),
+            updated_at: wishlist.updated_at(),
+            version: wishlist.version(),
+        }
+    }
+}
+
+/// The body of a [Wishlist] duplication request. The copy keeps the name and visibility of the
+/// original unless given.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct DuplicateWishlistHttpRequestBody {
+    pub user_id: String,
+    pub name: Option<String>,
+    pub private: Option<bool>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseDuplicateWishlistHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("name is invalid")]
+    Name(#[from] WishlistNameInvalidError),
+}
+
+impl DuplicateWishlistHttpRequestBody {
+    /// Converts the HTTP request body into a domain [DuplicateWishlistRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<DuplicateWishlistRequest, FieldErrors<ParseDuplicateWishlistHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseDuplicateWishlistHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let name = errors.check_field(
+            "/name",
+            self.name.as_deref().map(WishlistName::new).transpose(),
+        );
+        let (Some(user_id), Some(name)) = (user_id, name) else {
+            return Err(errors);
+        };
+        Ok(DuplicateWishlistRequest::new(wishlist_id, user_id)
+            .with_name(name)
+            .with_private(self.private))
+    }
+}
+
+/// Create a new [Wishlist] for the user, with a fresh slug and copies of the sections and items
+/// of one of their wishlists or of a template. Reservations are not copied.
+///
+/// # Responses
+///
+/// - 201 Created: the new [Wishlist].
+/// - 403 Forbidden: the [Wishlist] is neither the user's nor a template.
+/// - 404 Not found: the [Wishlist] does not exist.
+/// - 422 Unprocessable entity: the user does not exist, or the user ID or name is invalid.
+#[utoipa::path(
+    post,
+    path = "/wishlists/{wishlist_id}/duplicate",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+    ),
+    request_body = DuplicateWishlistHttpRequestBody,
+    responses(
+        (status = 201, description = "The new Wishlist.", body = ApiResponseBody<WishlistResponseData>),
+        (status = 403, description = "The Wishlist is neither the user's nor a template.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Wishlist does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user does not exist, or the user ID or name is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn duplicate_wishlist<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<DuplicateWishlistHttpRequestBody>,
+) -> Result<ApiSuccess<WishlistResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(wishlist_id)?;
+    state
+        .services
+        .duplicate_wishlist(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref wishlist| ApiSuccess::new(StatusCode::CREATED, wishlist.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::MockWishlistService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    fn state(wish_service: MockWishlistService) -> State<AppState<impl UseCases>> {
+        let service = test_services().with_wishlist(wish_service).build();
+        State(AppState {
+            services: Arc::new(service),
+        })
+    }
+
+    fn body(name: Option<&str>) -> ApiJson<DuplicateWishlistHttpRequestBody> {
+        ApiJson(DuplicateWishlistHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            name: name.map(str::to_string),
+            private: Some(true),
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_duplicate_wishlist_success() {
+        let id = Uuid::now_v7();
+        let mut mock_wish_service = MockWishlistService::new();
+        let copy = Wishlist::new(
+            id,
+            id,
+            "Christmas 2027".into(),
+            "Christmas 2027".into(),
+            true,
+        );
+        let expected = ApiSuccess::new(StatusCode::CREATED, WishlistResponseData::from(&copy));
+        mock_wish_service
+            .expect_duplicate_wishlist()
+            .withf(|req| {
+                req.name() == Some(&"Christmas 2027".into()) && req.private() == Some(true)
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(copy))));
+
+        let actual = duplicate_wishlist(
+            state(mock_wish_service),
+            ApiPath(Uuid::now_v7()),
+            body(Some("Christmas 2027")),
+        )
+        .await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_duplicate_wishlist_not_allowed() {
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_duplicate_wishlist()
+            .return_once(|req| {
+                let id = req.wishlist_id();
+                Box::pin(future::ready(Err(DuplicateWishlistError::NotAllowed {
+                    id,
+                })))
+            });
+
+        let actual = duplicate_wishlist(
+            state(mock_wish_service),
+            ApiPath(Uuid::now_v7()),
+            body(None),
+        )
+        .await;
+        assert!(matches!(actual, Err(ApiError::Forbidden(_))));
+    }
+}
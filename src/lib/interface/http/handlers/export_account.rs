@@ -0,0 +1,217 @@
+/*
+Module `export_account` specifies an HTTP handler for exporting the personal data of a user, and
+the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::{header, HeaderName, StatusCode};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::application::UseCases;
+use crate::domain::{AccountExport, ExportAccountError, User};
+use crate::interface::http::problem::{Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_item::ItemResponseData;
+use super::delete_account::{AccountDeletionResponseData, ConfirmedAccountHttpQuery};
+use super::duplicate_wishlist::WishlistResponseData;
+use super::set_profile::ProfileResponseData;
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+/// The version of the export format, bumped whenever a field changes meaning or goes away.
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+impl From<ExportAccountError> for ApiError {
+    fn from(e: ExportAccountError) -> Self {
+        match e {
+            ExportAccountError::UserDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::UserNotFound,
+                format!("User ID {} does not exist", id),
+            )),
+            ExportAccountError::InvalidConfirmation { id } => Self::Forbidden(Problem::new(
+                ProblemType::InvalidConfirmationToken,
+                format!(
+                    "The confirmation token of user ID {} is invalid or expired",
+                    id
+                ),
+            )),
+            ExportAccountError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The account of the user in an export. The password is never exported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ExportedUserData {
+    pub id: String,
+    pub email: String,
+    pub anonymous: bool,
+}
+
+impl From<&User> for ExportedUserData {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id().to_string(),
+            email: user.email().to_string(),
+            anonymous: user.anonymous(),
+        }
+    }
+}
+
+/// The response body data field for an [AccountExport].
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct AccountExportResponseData {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub user: ExportedUserData,
+    pub profile: ProfileResponseData,
+    pub wishlists: Vec<WishlistResponseData>,
+    pub items: Vec<ItemResponseData>,
+    pub reservations: Vec<ItemResponseData>,
+    pub deletion: Option<AccountDeletionResponseData>,
+}
+
+impl From<&AccountExport> for AccountExportResponseData {
+    fn from(export: &AccountExport) -> Self {
+        Self {
+            format_version: EXPORT_FORMAT_VERSION,
+            exported_at: export.exported_at(),
+            user: export.user().into(),
+            profile: ProfileResponseData::for_user(export.profile(), *export.user().id()),
+            wishlists: export.wishlists().iter().map(Into::into).collect(),
+            items: export.items().iter().map(Into::into).collect(),
+            reservations: export.reservations().iter().map(Into::into).collect(),
+            deletion: export.deletion().map(Into::into),
+        }
+    }
+}
+
+/// Export all the personal data of a user as a JSON file: their account, profile, wishlists
+/// and their items, and the items they reserved on the wishlists of others. The export is
+/// confirmed with a token emailed to the user.
+///
+/// # Responses
+///
+/// - 200 OK: the export, as an attachment.
+/// - 403 Forbidden: the confirmation token is invalid or expired.
+/// - 404 Not found: the user does not exist.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    get,
+    path = "/me/export",
+    tag = "account",
+    params(
+        ConfirmedAccountHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The export, as an attachment.", body = ApiResponseBody<AccountExportResponseData>),
+        (status = 403, description = "The confirmation token is invalid or expired.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The user does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn export_account<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<ConfirmedAccountHttpQuery>,
+) -> Result<
+    (
+        [(HeaderName, String); 1],
+        ApiSuccess<AccountExportResponseData>,
+    ),
+    ApiError,
+> {
+    let domain_req = query.try_into_domain()?;
+    state
+        .services
+        .export_account(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref export| {
+            let disposition = format!(
+                "attachment; filename=\"account-{}.json\"",
+                domain_req.user_id()
+            );
+            (
+                [(header::CONTENT_DISPOSITION, disposition)],
+                ApiSuccess::new(StatusCode::OK, export.into()),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use uuid::Uuid;
+
+    use crate::domain::{MockAccountService, Profile, Wishlist};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_export_account_success() {
+        let user_id = Uuid::now_v7();
+        let user = User::new(user_id, "a@example.com".into(), "secret".into());
+        let wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            user_id,
+            "Gifts".into(),
+            "gifts".into(),
+            false,
+        );
+        let exported_at: DateTime<Utc> = "2027-01-01T09:00:00Z".parse().unwrap();
+        let export = AccountExport::new(
+            user.clone(),
+            Profile::new(user_id),
+            vec![wishlist.clone()],
+            Vec::new(),
+            Vec::new(),
+            None,
+            exported_at,
+        );
+        let expected = (
+            [(
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"account-{}.json\"", user_id),
+            )],
+            ApiSuccess::new(
+                StatusCode::OK,
+                AccountExportResponseData {
+                    format_version: EXPORT_FORMAT_VERSION,
+                    exported_at,
+                    user: ExportedUserData {
+                        id: user_id.to_string(),
+                        email: "a@example.com".into(),
+                        anonymous: false,
+                    },
+                    profile: ProfileResponseData::for_user(&Profile::new(user_id), user_id),
+                    wishlists: vec![(&wishlist).into()],
+                    items: Vec::new(),
+                    reservations: Vec::new(),
+                    deletion: None,
+                },
+            ),
+        );
+        let mut mock_account_service = MockAccountService::new();
+        mock_account_service
+            .expect_export_account()
+            .withf(move |req| req.user_id() == user_id && req.token().as_str() == "secret")
+            .return_once(move |_| Box::pin(future::ready(Ok(export))));
+        let state = test_services()
+            .with_account(mock_account_service)
+            .into_state();
+        let query = ApiQuery(ConfirmedAccountHttpQuery {
+            user_id: user_id.to_string(),
+            token: "secret".into(),
+        });
+
+        let actual = export_account(state, query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,159 @@
+/*
+Module `find_image` specifies an HTTP handler for serving a stored image.
+*/
+
+use axum::extract::State;
+use axum::http::{header, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{FindImageError, FindImageRequest, ImageSize};
+use crate::interface::http::conditional::EntityTag;
+use crate::interface::http::problem::{Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiPath};
+
+/// Stored images never change: a new upload always gets a new ID.
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+impl From<FindImageError> for ApiError {
+    fn from(e: FindImageError) -> Self {
+        match e {
+            FindImageError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// Serve a stored image in one of its sizes: `original`, `large`, `medium` or `small`.
+///
+/// # Responses
+///
+/// - 200 OK: the image content, with long-lived cache headers.
+/// - 304 Not modified: the client already has the image, per `If-None-Match`.
+/// - 404 Not found: the image or the size does not exist.
+#[utoipa::path(
+    get,
+    path = "/images/{image_id}/{size}",
+    tag = "images",
+    params(
+        ("image_id" = Uuid, Path, description = "The ID of the image."),
+        ("size" = String, Path, description = "The size: 'original', 'large', 'medium' or 'small'."),
+    ),
+    responses(
+        (status = 200, description = "The image content, with long-lived cache headers.", content_type = "image/*", body = [u8]),
+        (status = 304, description = "The client already has the image, per 'If-None-Match'."),
+        (status = 404, description = "The image or the size does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn find_image<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath((image_id, size)): ApiPath<(Uuid, String)>,
+) -> Result<Response, ApiError> {
+    let not_found = || {
+        ApiError::NotFound(Problem::new(
+            ProblemType::ImageNotFound,
+            format!("Image {}/{} does not exist.", image_id, size),
+        ))
+    };
+    let size = size.parse::<ImageSize>().map_err(|_| not_found())?;
+    let blob = state
+        .services
+        .find_image(&FindImageRequest::new(image_id, size))
+        .await?
+        .ok_or_else(not_found)?;
+
+    // `If-None-Match` is answered by the conditional::not_modified middleware
+    let etag = EntityTag::immutable(format!("{}-{}", image_id, size));
+    let content_type = HeaderValue::from_str(blob.content_type())
+        .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+    Ok((
+        [
+            (header::ETAG, HeaderValue::from(&etag)),
+            (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static(CACHE_CONTROL),
+            ),
+            (header::CONTENT_TYPE, content_type),
+            (
+                header::X_CONTENT_TYPE_OPTIONS,
+                HeaderValue::from_static("nosniff"),
+            ),
+        ],
+        blob.into_bytes(),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::{Blob, MockImageService};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    fn state(image_service: MockImageService) -> State<AppState<impl UseCases>> {
+        let service = test_services().with_image(image_service).build();
+        State(AppState {
+            services: Arc::new(service),
+        })
+    }
+
+    fn stored_image() -> MockImageService {
+        let mut mock_image_service = MockImageService::new();
+        mock_image_service.expect_find_image().returning(|req| {
+            assert_eq!(req.size(), ImageSize::Small);
+            let blob = Blob::new("image/png", vec![1, 2, 3]);
+            Box::pin(future::ready(Ok(Some(blob))))
+        });
+        mock_image_service
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_find_image_success() {
+        let id = Uuid::now_v7();
+
+        let response = find_image(state(stored_image()), ApiPath((id, "small".to_string())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers[header::CONTENT_TYPE], "image/png");
+        assert_eq!(headers[header::CACHE_CONTROL], CACHE_CONTROL);
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(headers[header::ETAG], format!("\"{}-small\"", id).as_str());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), &[1, 2, 3]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_find_image_not_found() {
+        let id = Uuid::now_v7();
+        let mut mock_image_service = MockImageService::new();
+        mock_image_service
+            .expect_find_image()
+            .return_once(|_| Box::pin(future::ready(Ok(None))));
+
+        let actual = find_image(
+            state(mock_image_service),
+            ApiPath((id, "small".to_string())),
+        )
+        .await;
+        assert!(matches!(actual, Err(ApiError::NotFound(_))));
+
+        let actual = find_image(
+            state(MockImageService::new()),
+            ApiPath((id, "huge".to_string())),
+        )
+        .await;
+        assert!(matches!(actual, Err(ApiError::NotFound(_))));
+    }
+}
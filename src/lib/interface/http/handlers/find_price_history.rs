@@ -0,0 +1,253 @@
+/*
+Module `find_price_history` specifies an HTTP handler for reading the price history of an
+[Item], and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{FindPriceHistoryError, FindPriceHistoryRequest, PricePoint, ShareToken};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<FindPriceHistoryError> for ApiError {
+    fn from(e: FindPriceHistoryError) -> Self {
+        match e {
+            FindPriceHistoryError::ItemDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ItemNotFound,
+                format!("Item ID {} does not exist", id),
+            )),
+            FindPriceHistoryError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// A single observation of an [Item]'s price.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct PricePointData {
+    pub price: Decimal,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl From<&PricePoint> for PricePointData {
+    fn from(point: &PricePoint) -> Self {
+        Self {
+            price: point.price().value(),
+            recorded_at: point.recorded_at(),
+        }
+    }
+}
+
+/// The response body data field for an [Item]'s price history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FindPriceHistoryResponseData {
+    pub item_id: String,
+    pub prices: Vec<PricePointData>,
+}
+
+/// The query string of a request for the price history of an [Item], e.g. `?user_id=...`. It
+/// names a `user_id` who may see the wishlist of the [Item], or the `token` of its share link.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FindPriceHistoryHttpQuery {
+    pub user_id: Option<String>,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseFindPriceHistoryHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("a user id or a share token is required")]
+    Viewer,
+}
+
+impl FindPriceHistoryHttpQuery {
+    /// Converts the HTTP query into a domain [FindPriceHistoryRequest].
+    pub fn try_into_domain(
+        self,
+        item_id: Uuid,
+    ) -> Result<FindPriceHistoryRequest, FieldErrors<ParseFindPriceHistoryHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let viewer_id = errors.check_parameter(
+            "user_id",
+            match (self.user_id, &self.token) {
+                (None, None) => Err(ParseFindPriceHistoryHttpRequestError::Viewer),
+                (user_id, _) => user_id
+                    .map(|id| {
+                        Uuid::parse_str(&id)
+                            .map_err(|_| ParseFindPriceHistoryHttpRequestError::UserId(id))
+                    })
+                    .transpose(),
+            },
+        );
+        let Some(viewer_id) = viewer_id else {
+            return Err(errors);
+        };
+        Ok(FindPriceHistoryRequest::new(item_id)
+            .with_viewer(viewer_id)
+            .with_share_token(self.token.as_deref().map(ShareToken::from)))
+    }
+}
+
+/// Get the price history of an [Item], oldest observation first, for a user who may see its
+/// wishlist or with the token of its share link.
+///
+/// # Responses
+///
+/// - 200 OK: the price history of the [Item].
+/// - 404 Not found: the [Item] does not exist or the user may not see it.
+/// - 422 Unprocessable entity: the user ID is invalid, or neither it nor a token was given.
+#[utoipa::path(
+    get,
+    path = "/items/{item_id}/price-history",
+    tag = "items",
+    params(
+        ("item_id" = Uuid, Path, description = "The ID of the item."),
+        FindPriceHistoryHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The price history of the Item.", body = ApiResponseBody<FindPriceHistoryResponseData>),
+        (status = 404, description = "The Item does not exist or the user may not see it.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid, or neither it nor a token was given.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn find_price_history<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<FindPriceHistoryHttpQuery>,
+) -> Result<ApiSuccess<FindPriceHistoryResponseData>, ApiError> {
+    let domain_req = query.try_into_domain(item_id)?;
+    state
+        .services
+        .find_price_history(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|prices| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                FindPriceHistoryResponseData {
+                    item_id: item_id.to_string(),
+                    prices: prices.iter().map(PricePointData::from).collect(),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::MockItemService;
+    use crate::interface::http::handlers::test_services;
+    use crate::interface::http::problem::InvalidField;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_find_price_history_success() {
+        let (item_id, user_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let recorded_at = Utc::now();
+
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service
+            .expect_find_price_history()
+            .withf(move |req| req.item_id() == item_id && req.viewer_id() == Some(user_id))
+            .return_once(move |_| {
+                Box::pin(future::ready(Ok(vec![PricePoint::new(
+                    10.0.into(),
+                    recorded_at,
+                )])))
+            });
+        let service = test_services().with_item(mock_item_service).build();
+        let state = axum::extract::State(AppState {
+            services: Arc::new(service),
+        });
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            FindPriceHistoryResponseData {
+                item_id: item_id.to_string(),
+                prices: vec![PricePointData {
+                    price: Decimal::TEN,
+                    recorded_at,
+                }],
+            },
+        );
+
+        let query = ApiQuery(FindPriceHistoryHttpQuery {
+            user_id: Some(user_id.to_string()),
+            token: None,
+        });
+
+        let actual = find_price_history(state, ApiPath(item_id), query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_find_price_history_not_found() {
+        let item_id = Uuid::now_v7();
+
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service
+            .expect_find_price_history()
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(
+                    FindPriceHistoryError::ItemDoesNotExist { id: item_id },
+                )))
+            });
+        let service = test_services().with_item(mock_item_service).build();
+        let state = axum::extract::State(AppState {
+            services: Arc::new(service),
+        });
+
+        let query = ApiQuery(FindPriceHistoryHttpQuery {
+            user_id: None,
+            token: Some("secret".into()),
+        });
+
+        let actual = find_price_history(state, ApiPath(item_id), query).await;
+        assert_eq!(
+            actual,
+            Err(ApiError::NotFound(Problem::new(
+                ProblemType::ItemNotFound,
+                format!("Item ID {} does not exist", item_id)
+            )))
+        );
+    }
+
+    #[test]
+    fn test_parse_find_price_history_query() {
+        let item_id = Uuid::now_v7();
+        let req = FindPriceHistoryHttpQuery {
+            user_id: None,
+            token: Some("secret".into()),
+        }
+        .try_into_domain(item_id)
+        .unwrap();
+        assert_eq!(req.viewer_id(), None);
+        assert_eq!(req.share_token(), Some(&ShareToken::from("secret")));
+
+        assert_eq!(
+            FindPriceHistoryHttpQuery::default()
+                .try_into_domain(item_id)
+                .unwrap_err()
+                .into_invalid_fields(),
+            vec![InvalidField::parameter(
+                "user_id",
+                "a user id or a share token is required"
+            )]
+        );
+    }
+}
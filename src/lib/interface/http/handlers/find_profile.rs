@@ -0,0 +1,144 @@
+/*
+Module `find_profile` specifies an HTTP handler for reading the [Profile](crate::domain::Profile)
+of a user.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{FindProfileError, FindProfileRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::set_profile::ProfileResponseData;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<FindProfileError> for ApiError {
+    fn from(e: FindProfileError) -> Self {
+        match e {
+            FindProfileError::UserDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::UserNotFound,
+                format!("User ID {} does not exist", id),
+            )),
+            FindProfileError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The query string of a request by a user about a profile, e.g. `?user_id=...`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProfileHttpQuery {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseProfileHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl ProfileHttpQuery {
+    /// Parses the ID of the user making the request.
+    pub fn user_id(&self) -> Result<Uuid, FieldErrors<ParseProfileHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        errors
+            .check_parameter(
+                "user_id",
+                Uuid::parse_str(&self.user_id)
+                    .map_err(|_| ParseProfileHttpRequestError::UserId(self.user_id.clone())),
+            )
+            .ok_or(errors)
+    }
+}
+
+/// Read the profile of a user. Each field is only shown to the users its visibility allows,
+/// and the shipping address only to the users who reserved one of the owner's items.
+///
+/// # Responses
+///
+/// - 200 OK: the profile, without the fields the user may not see.
+/// - 404 Not found: the profile owner does not exist.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    get,
+    path = "/profiles/{profile_id}",
+    tag = "profiles",
+    params(
+        ("profile_id" = Uuid, Path, description = "The ID of the profile."),
+        ProfileHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The profile, without the fields the user may not see.", body = ApiResponseBody<ProfileResponseData>),
+        (status = 404, description = "The profile owner does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn find_profile<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(profile_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<ProfileHttpQuery>,
+) -> Result<ApiSuccess<ProfileResponseData>, ApiError> {
+    let user_id = query.user_id()?;
+    state
+        .services
+        .find_profile(&FindProfileRequest::new(user_id, profile_id))
+        .await
+        .map_err(ApiError::from)
+        .map(|ref profile| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                ProfileResponseData::for_user(profile, user_id),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{DisplayName, MockProfileService, Profile};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_find_profile_of_someone_else() {
+        let (user_id, profile_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let profile =
+            Profile::new(profile_id).with_display_name(Some(DisplayName::new("Alice").unwrap()));
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ProfileResponseData {
+                user_id: profile_id.to_string(),
+                display_name: Some("Alice".into()),
+                avatar: None,
+                birthday: None,
+                shipping_address: None,
+                visibility: None,
+            },
+        );
+        let mut mock_profile_service = MockProfileService::new();
+        mock_profile_service
+            .expect_find_profile()
+            .withf(move |req| req.user_id() == user_id && req.profile_id() == profile_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(profile))));
+        let state = test_services()
+            .with_profile(mock_profile_service)
+            .into_state();
+        let query = ApiQuery(ProfileHttpQuery {
+            user_id: user_id.to_string(),
+        });
+
+        let actual = find_profile(state, ApiPath(profile_id), query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
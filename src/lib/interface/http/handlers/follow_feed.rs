@@ -0,0 +1,189 @@
+/*
+Module `follow_feed` specifies an HTTP handler for reading the recently updated wishlists of the
+users a user follows, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{FollowFeedError, FollowFeedRequest, WishlistQuery};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::query::{
+    parse_page, parse_wishlist_filter, parse_wishlist_sort, ParseListingQueryError,
+};
+use crate::interface::http::AppState;
+
+use super::duplicate_wishlist::WishlistResponseData;
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<FollowFeedError> for ApiError {
+    fn from(e: FollowFeedError) -> Self {
+        match e {
+            FollowFeedError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a page of the feed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FollowFeedResponseData {
+    pub wishlists: Vec<WishlistResponseData>,
+    pub next_cursor: Option<String>,
+}
+
+/// The query string of a feed page, e.g. `?user_id=...&occasion=wedding&cursor=...&limit=20`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FollowFeedHttpQuery {
+    pub user_id: String,
+    pub visibility: Option<String>,
+    pub occasion: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseFollowFeedHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Listing(#[from] ParseListingQueryError),
+}
+
+impl FollowFeedHttpQuery {
+    /// Converts the HTTP query into a domain [FollowFeedRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<FollowFeedRequest, FieldErrors<ParseFollowFeedHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseFollowFeedHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let filter = parse_wishlist_filter(
+            &mut errors,
+            self.visibility.as_deref(),
+            self.occasion.as_deref(),
+        );
+        let sort = parse_wishlist_sort(&mut errors, self.sort.as_deref(), self.order.as_deref());
+        let page = parse_page(&mut errors, self.cursor.as_deref(), self.limit);
+        let (Some(user_id), Some(filter), Some(sort), Some(page)) = (user_id, filter, sort, page)
+        else {
+            return Err(errors);
+        };
+        Ok(FollowFeedRequest::new(
+            user_id,
+            WishlistQuery::new(filter, sort, page),
+        ))
+    }
+}
+
+/// List a page of the wishlists of the users the user follows, optionally filtered by visibility
+/// or occasion. Wishlists come most recently updated first unless sorted by creation or name.
+/// Only the wishlists the user may see are listed; pass `next_cursor` as `cursor` to read the
+/// next page.
+///
+/// # Responses
+///
+/// - 200 OK: the wishlists of the page, and the cursor of the next page if there is one.
+/// - 422 Unprocessable entity: the user ID, a filter, sort or page parameter is invalid.
+#[utoipa::path(
+    get,
+    path = "/feed",
+    tag = "follows",
+    params(
+        FollowFeedHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The wishlists of the page, and the cursor of the next page if there is one.", body = ApiResponseBody<FollowFeedResponseData>),
+        (status = 422, description = "The user ID, a filter, sort or page parameter is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn follow_feed<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<FollowFeedHttpQuery>,
+) -> Result<ApiSuccess<FollowFeedResponseData>, ApiError> {
+    let domain_req = query.try_into_domain()?;
+    state
+        .services
+        .follow_feed(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|page| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                FollowFeedResponseData {
+                    wishlists: page
+                        .entries()
+                        .iter()
+                        .map(WishlistResponseData::from)
+                        .collect(),
+                    next_cursor: page.next_cursor().map(|cursor| cursor.to_string()),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{
+        MockFollowService, Page, SortDirection, Wishlist, WishlistSort, WishlistSortKey,
+    };
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_follow_feed_next_page() {
+        let user_id = Uuid::now_v7();
+        let id = Uuid::now_v7();
+        let wishlist = Wishlist::new(id, id, "Birthday".into(), "Birthday".into(), false);
+        let sort = WishlistSort::new(WishlistSortKey::Created, SortDirection::Descending);
+        let cursor = sort.position(&wishlist);
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            FollowFeedResponseData {
+                wishlists: vec![WishlistResponseData::from(&wishlist)],
+                next_cursor: Some(cursor.to_string()),
+            },
+        );
+        let mut mock_follow_service = MockFollowService::new();
+        mock_follow_service
+            .expect_follow_feed()
+            .withf(move |req| {
+                req.user_id() == user_id
+                    && req.query().sort() == &sort
+                    && req.query().page().cursor().is_none()
+                    && req.query().page().limit().get() == 1
+            })
+            .return_once(move |_| {
+                Box::pin(future::ready(Ok(Page::new(vec![wishlist], Some(cursor)))))
+            });
+        let state = test_services()
+            .with_follow(mock_follow_service)
+            .into_state();
+        let query = ApiQuery(FollowFeedHttpQuery {
+            user_id: user_id.to_string(),
+            sort: Some("created".to_string()),
+            order: Some("desc".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        });
+
+        let actual = follow_feed(state, query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
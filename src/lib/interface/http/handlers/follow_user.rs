@@ -0,0 +1,167 @@
+/*
+Module `follow_user` specifies an HTTP handler for following a user, and the follow data
+structures shared with the other follow handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{Follow, FollowUserError, FollowUserRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
+
+impl From<FollowUserError> for ApiError {
+    fn from(e: FollowUserError) -> Self {
+        match e {
+            FollowUserError::UserDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::UserNotFound,
+                format!("User ID {} does not exist", id),
+            )),
+            FollowUserError::SelfFollow => Self::UnprocessableEntity(Problem::new(
+                ProblemType::SelfFollow,
+                "users cannot follow themselves",
+            )),
+            FollowUserError::Blocked { id } => Self::Forbidden(Problem::new(
+                ProblemType::FollowBlocked,
+                format!("User ID {} cannot be followed", id),
+            )),
+            FollowUserError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [Follow].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FollowResponseData {
+    pub follower_id: String,
+    pub followee_id: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&Follow> for FollowResponseData {
+    fn from(follow: &Follow) -> Self {
+        Self {
+            follower_id: follow.follower_id().to_string(),
+            followee_id: follow.followee_id().to_string(),
+            status: follow.status().to_string(),
+            created_at: follow.created_at(),
+        }
+    }
+}
+
+/// The body of a request by `user_id` to follow `followee_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct FollowUserHttpRequestBody {
+    pub user_id: String,
+    pub followee_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseFollowUserHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl FollowUserHttpRequestBody {
+    /// Converts the HTTP request body into a domain [FollowUserRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<FollowUserRequest, FieldErrors<ParseFollowUserHttpRequestError>> {
+        let parse_user = |id: &str| {
+            Uuid::parse_str(id).map_err(|_| ParseFollowUserHttpRequestError::UserId(id.to_string()))
+        };
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field("/user_id", parse_user(&self.user_id));
+        let followee_id = errors.check_field("/followee_id", parse_user(&self.followee_id));
+        let (Some(user_id), Some(followee_id)) = (user_id, followee_id) else {
+            return Err(errors);
+        };
+        Ok(FollowUserRequest::new(user_id, followee_id))
+    }
+}
+
+/// Follow a user. Following a private account is pending until its owner accepts, and
+/// following a user again returns the existing follow.
+///
+/// # Responses
+///
+/// - 201 Created: the [Follow], either `accepted` or `pending`.
+/// - 403 Forbidden: either user has blocked the other.
+/// - 404 Not found: the followee does not exist.
+/// - 422 Unprocessable entity: a user ID is invalid, or the user follows themselves.
+#[utoipa::path(
+    post,
+    path = "/follows",
+    tag = "follows",
+    request_body = FollowUserHttpRequestBody,
+    responses(
+        (status = 201, description = "The Follow, either 'accepted' or 'pending'.", body = ApiResponseBody<FollowResponseData>),
+        (status = 403, description = "Either user has blocked the other.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The followee does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "A user ID is invalid, or the user follows themselves.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn follow_user<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiJson(body): ApiJson<FollowUserHttpRequestBody>,
+) -> Result<ApiSuccess<FollowResponseData>, ApiError> {
+    let domain_req = body.try_into_domain()?;
+    state
+        .services
+        .follow_user(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref follow| ApiSuccess::new(StatusCode::CREATED, follow.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{FollowStatus, MockFollowService};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_follow_private_account() {
+        let (user_id, followee_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let follow = Follow::new(user_id, followee_id, FollowStatus::Pending, Utc::now());
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            FollowResponseData {
+                follower_id: user_id.to_string(),
+                followee_id: followee_id.to_string(),
+                status: "pending".to_string(),
+                created_at: follow.created_at(),
+            },
+        );
+        let mut mock_follow_service = MockFollowService::new();
+        mock_follow_service
+            .expect_follow_user()
+            .withf(move |req| req.user_id() == user_id && req.followee_id() == followee_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(follow))));
+        let state = test_services()
+            .with_follow(mock_follow_service)
+            .into_state();
+        let body = ApiJson(FollowUserHttpRequestBody {
+            user_id: user_id.to_string(),
+            followee_id: followee_id.to_string(),
+        });
+
+        let actual = follow_user(state, body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
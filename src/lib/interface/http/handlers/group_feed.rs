@@ -0,0 +1,245 @@
+/*
+Module `group_feed` specifies an HTTP handler for the feed of the wishlists shared with a
+[Group](crate::domain::Group), and the query string shared with the other group handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{GroupFeedEntry, GroupFeedError, GroupFeedRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::query::{parse_page, parse_wishlist_filter, ParseListingQueryError};
+use crate::interface::http::AppState;
+
+use super::duplicate_wishlist::WishlistResponseData;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<GroupFeedError> for ApiError {
+    fn from(e: GroupFeedError) -> Self {
+        match e {
+            GroupFeedError::GroupDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::GroupNotFound,
+                format!("Group ID {} does not exist", id),
+            )),
+            GroupFeedError::NotMember { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotGroupMember,
+                format!("User ID {} is not a member of the group", id),
+            )),
+            GroupFeedError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [GroupFeedEntry].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct GroupFeedEntryResponseData {
+    pub wishlist: WishlistResponseData,
+    pub shared_by: String,
+    pub shared_at: DateTime<Utc>,
+}
+
+impl From<&GroupFeedEntry> for GroupFeedEntryResponseData {
+    fn from(entry: &GroupFeedEntry) -> Self {
+        Self {
+            wishlist: entry.wishlist().into(),
+            shared_by: entry.shared_by().to_string(),
+            shared_at: entry.shared_at(),
+        }
+    }
+}
+
+/// The response body data field for the feed of a group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct GroupFeedResponseData {
+    pub entries: Vec<GroupFeedEntryResponseData>,
+    pub next_cursor: Option<String>,
+}
+
+/// The query string of a request by a user about a [Group](crate::domain::Group), e.g.
+/// `?user_id=...`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GroupActionHttpQuery {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseGroupActionHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl GroupActionHttpQuery {
+    /// Parses the ID of the user making the request.
+    pub fn user_id(&self) -> Result<Uuid, FieldErrors<ParseGroupActionHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        errors
+            .check_parameter(
+                "user_id",
+                Uuid::parse_str(&self.user_id)
+                    .map_err(|_| ParseGroupActionHttpRequestError::UserId(self.user_id.clone())),
+            )
+            .ok_or(errors)
+    }
+}
+
+/// The query string of a group feed page, e.g. `?user_id=...&occasion=birthday&limit=20`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GroupFeedHttpQuery {
+    pub user_id: String,
+    pub visibility: Option<String>,
+    pub occasion: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseGroupFeedHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Listing(#[from] ParseListingQueryError),
+}
+
+impl GroupFeedHttpQuery {
+    /// Converts the HTTP query into a domain [GroupFeedRequest].
+    pub fn try_into_domain(
+        self,
+        group_id: Uuid,
+    ) -> Result<GroupFeedRequest, FieldErrors<ParseGroupFeedHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseGroupFeedHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let filter = parse_wishlist_filter(
+            &mut errors,
+            self.visibility.as_deref(),
+            self.occasion.as_deref(),
+        );
+        let page = parse_page(&mut errors, self.cursor.as_deref(), self.limit);
+        let (Some(user_id), Some(filter), Some(page)) = (user_id, filter, page) else {
+            return Err(errors);
+        };
+        Ok(GroupFeedRequest::new(group_id, user_id, filter, page))
+    }
+}
+
+/// List a page of the wishlists shared with a [Group](crate::domain::Group) by its current
+/// members, most recently shared first, optionally filtered by visibility or occasion. Pass
+/// `next_cursor` as `cursor` to read the next page.
+///
+/// # Responses
+///
+/// - 200 OK: the feed entries of the page, and the cursor of the next page if there is one.
+/// - 403 Forbidden: the user is not a member of the group.
+/// - 404 Not found: the group does not exist.
+/// - 422 Unprocessable entity: the user ID, a filter or a page parameter is invalid.
+#[utoipa::path(
+    get,
+    path = "/groups/{group_id}/feed",
+    tag = "groups",
+    params(
+        ("group_id" = Uuid, Path, description = "The ID of the group."),
+        GroupFeedHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The feed entries of the page, and the cursor of the next page if there is one.", body = ApiResponseBody<GroupFeedResponseData>),
+        (status = 403, description = "The user is not a member of the group.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The group does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID, a filter or a page parameter is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn group_feed<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(group_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<GroupFeedHttpQuery>,
+) -> Result<ApiSuccess<GroupFeedResponseData>, ApiError> {
+    let domain_req = query.try_into_domain(group_id)?;
+    state
+        .services
+        .group_feed(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|page| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                GroupFeedResponseData {
+                    entries: page
+                        .entries()
+                        .iter()
+                        .map(GroupFeedEntryResponseData::from)
+                        .collect(),
+                    next_cursor: page.next_cursor().map(|cursor| cursor.to_string()),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{MockGroupService, OccasionKind, Page, Wishlist};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_group_feed_success() {
+        let (group_id, user_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            user_id,
+            "Birthday".into(),
+            "Birthday".into(),
+            true,
+        );
+        let entry = GroupFeedEntry::new(wishlist, user_id, Utc::now());
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            GroupFeedResponseData {
+                entries: vec![GroupFeedEntryResponseData::from(&entry)],
+                next_cursor: None,
+            },
+        );
+        let mut mock_group_service = MockGroupService::new();
+        mock_group_service
+            .expect_group_feed()
+            .withf(move |req| {
+                req.group_id() == group_id
+                    && req.user_id() == user_id
+                    // A wishlist without an occasion is filtered out.
+                    && !req.filter().matches(&Wishlist::new(
+                        Uuid::now_v7(),
+                        user_id,
+                        "Party".into(),
+                        "Party".into(),
+                        false,
+                    ))
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(Page::new(vec![entry], None)))));
+        let state = test_services().with_group(mock_group_service).into_state();
+        let query = ApiQuery(GroupFeedHttpQuery {
+            user_id: user_id.to_string(),
+            visibility: None,
+            occasion: Some(OccasionKind::Birthday.to_string()),
+            cursor: None,
+            limit: None,
+        });
+
+        let actual = group_feed(state, ApiPath(group_id), query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,178 @@
+/*
+Module `invite_member` specifies an HTTP handler for inviting a user into a
+[Group](crate::domain::Group), and the invitation data structures shared with the other
+invitation handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{GroupInvitation, InviteMemberError, InviteMemberRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<InviteMemberError> for ApiError {
+    fn from(e: InviteMemberError) -> Self {
+        match e {
+            InviteMemberError::GroupDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::GroupNotFound,
+                format!("Group ID {} does not exist", id),
+            )),
+            InviteMemberError::NotAdmin { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotGroupAdmin,
+                format!("Only admins of group ID {} can invite members", id),
+            )),
+            InviteMemberError::UserDoesNotExist { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::UserNotFound,
+                format!("User ID {} does not exist", id),
+            )),
+            InviteMemberError::AlreadyMember { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::AlreadyGroupMember,
+                format!("User ID {} is already a member of the group", id),
+            )),
+            InviteMemberError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [GroupInvitation].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct InvitationResponseData {
+    pub id: String,
+    pub group_id: String,
+    pub invitee_id: String,
+    pub invited_by: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&GroupInvitation> for InvitationResponseData {
+    fn from(invitation: &GroupInvitation) -> Self {
+        Self {
+            id: invitation.id().to_string(),
+            group_id: invitation.group_id().to_string(),
+            invitee_id: invitation.invitee_id().to_string(),
+            invited_by: invitation.invited_by().to_string(),
+            status: invitation.status().to_string(),
+            created_at: invitation.created_at(),
+        }
+    }
+}
+
+/// The body of a request by the admin `user_id` inviting `invitee_id` into a group.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct InviteMemberHttpRequestBody {
+    pub user_id: String,
+    pub invitee_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseInviteMemberHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl InviteMemberHttpRequestBody {
+    /// Converts the HTTP request body into a domain [InviteMemberRequest].
+    pub fn try_into_domain(
+        self,
+        group_id: Uuid,
+    ) -> Result<InviteMemberRequest, FieldErrors<ParseInviteMemberHttpRequestError>> {
+        let parse_user = |id: &str| {
+            Uuid::parse_str(id)
+                .map_err(|_| ParseInviteMemberHttpRequestError::UserId(id.to_string()))
+        };
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field("/user_id", parse_user(&self.user_id));
+        let invitee_id = errors.check_field("/invitee_id", parse_user(&self.invitee_id));
+        let (Some(user_id), Some(invitee_id)) = (user_id, invitee_id) else {
+            return Err(errors);
+        };
+        Ok(InviteMemberRequest::new(group_id, user_id, invitee_id))
+    }
+}
+
+/// Invite a user into a [Group](crate::domain::Group). Inviting them again while the invitation
+/// is pending returns that invitation.
+///
+/// # Responses
+///
+/// - 201 Created: the pending [GroupInvitation].
+/// - 403 Forbidden: the user is not an admin of the group.
+/// - 404 Not found: the group does not exist.
+/// - 422 Unprocessable entity: an ID is invalid, the invitee does not exist or is already a
+///   member.
+#[utoipa::path(
+    post,
+    path = "/groups/{group_id}/invitations",
+    tag = "groups",
+    params(
+        ("group_id" = Uuid, Path, description = "The ID of the group."),
+    ),
+    request_body = InviteMemberHttpRequestBody,
+    responses(
+        (status = 201, description = "The pending GroupInvitation.", body = ApiResponseBody<InvitationResponseData>),
+        (status = 403, description = "The user is not an admin of the group.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The group does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "An ID is invalid, the invitee does not exist or is already a member.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn invite_member<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(group_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<InviteMemberHttpRequestBody>,
+) -> Result<ApiSuccess<InvitationResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(group_id)?;
+    state
+        .services
+        .invite_member(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref invitation| ApiSuccess::new(StatusCode::CREATED, invitation.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockGroupService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_invite_member_success() {
+        let (group_id, admin, invitee) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let invitation = GroupInvitation::new(Uuid::now_v7(), group_id, invitee, admin, Utc::now());
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            InvitationResponseData::from(&invitation),
+        );
+        let mut mock_group_service = MockGroupService::new();
+        mock_group_service
+            .expect_invite_member()
+            .withf(move |req| {
+                req.group_id() == group_id && req.user_id() == admin && req.invitee_id() == invitee
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(invitation))));
+        let state = test_services().with_group(mock_group_service).into_state();
+        let body = ApiJson(InviteMemberHttpRequestBody {
+            user_id: admin.to_string(),
+            invitee_id: invitee.to_string(),
+        });
+
+        let actual = invite_member(state, ApiPath(group_id), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
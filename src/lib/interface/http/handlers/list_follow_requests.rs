@@ -0,0 +1,160 @@
+/*
+Module `list_follow_requests` specifies an HTTP handler for listing the pending requests to
+follow a user, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{ListFollowRequestsError, ListFollowRequestsRequest};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::query::{parse_page, ParseListingQueryError};
+use crate::interface::http::AppState;
+
+use super::follow_user::FollowResponseData;
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<ListFollowRequestsError> for ApiError {
+    fn from(e: ListFollowRequestsError) -> Self {
+        match e {
+            ListFollowRequestsError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for the pending requests to follow a user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ListFollowRequestsResponseData {
+    pub requests: Vec<FollowResponseData>,
+    pub next_cursor: Option<String>,
+}
+
+/// The query string of a follow request listing, e.g. `?user_id=...&limit=20`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListFollowRequestsHttpQuery {
+    pub user_id: String,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseListFollowRequestsHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Listing(#[from] ParseListingQueryError),
+}
+
+impl ListFollowRequestsHttpQuery {
+    /// Converts the HTTP query into a domain [ListFollowRequestsRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<ListFollowRequestsRequest, FieldErrors<ParseListFollowRequestsHttpRequestError>>
+    {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseListFollowRequestsHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let page = parse_page(&mut errors, self.cursor.as_deref(), self.limit);
+        let (Some(user_id), Some(page)) = (user_id, page) else {
+            return Err(errors);
+        };
+        Ok(ListFollowRequestsRequest::new(user_id, page))
+    }
+}
+
+/// List a page of the pending requests to follow the user, oldest first. Pass `next_cursor` as
+/// `cursor` to read the next page.
+///
+/// # Responses
+///
+/// - 200 OK: the pending follows of the page, and the cursor of the next page if there is one.
+/// - 422 Unprocessable entity: the user ID, the cursor or the limit is invalid.
+#[utoipa::path(
+    get,
+    path = "/follow-requests",
+    tag = "follows",
+    params(
+        ListFollowRequestsHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The pending follows of the page, and the cursor of the next page if there is one.", body = ApiResponseBody<ListFollowRequestsResponseData>),
+        (status = 422, description = "The user ID, the cursor or the limit is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn list_follow_requests<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<ListFollowRequestsHttpQuery>,
+) -> Result<ApiSuccess<ListFollowRequestsResponseData>, ApiError> {
+    let domain_req = query.try_into_domain()?;
+    state
+        .services
+        .list_follow_requests(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|page| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                ListFollowRequestsResponseData {
+                    requests: page
+                        .entries()
+                        .iter()
+                        .map(FollowResponseData::from)
+                        .collect(),
+                    next_cursor: page.next_cursor().map(|cursor| cursor.to_string()),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use chrono::Utc;
+
+    use crate::domain::{Follow, FollowStatus, MockFollowService, Page};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_list_follow_requests_success() {
+        let user_id = Uuid::now_v7();
+        let follow = Follow::new(Uuid::now_v7(), user_id, FollowStatus::Pending, Utc::now());
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ListFollowRequestsResponseData {
+                requests: vec![FollowResponseData::from(&follow)],
+                next_cursor: None,
+            },
+        );
+        let mut mock_follow_service = MockFollowService::new();
+        mock_follow_service
+            .expect_list_follow_requests()
+            .withf(move |req| req.user_id() == user_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(Page::new(vec![follow], None)))));
+        let state = test_services()
+            .with_follow(mock_follow_service)
+            .into_state();
+        let query = ApiQuery(ListFollowRequestsHttpQuery {
+            user_id: user_id.to_string(),
+            cursor: None,
+            limit: None,
+        });
+
+        let actual = list_follow_requests(state, query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,209 @@
+/*
+Module `list_inbox` specifies an HTTP handler for reading the in-app inbox of a user, and the
+associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{InboxMessage, ListInboxError, ListInboxRequest, NotificationKind};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::query::{parse_page, ParseListingQueryError};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<ListInboxError> for ApiError {
+    fn from(e: ListInboxError) -> Self {
+        match e {
+            ListInboxError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for an [InboxMessage].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct InboxMessageResponseData {
+    pub id: String,
+    pub kind: String,
+    pub subject: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&InboxMessage> for InboxMessageResponseData {
+    fn from(message: &InboxMessage) -> Self {
+        let kind = match message.kind() {
+            NotificationKind::PriceDrop { .. } => "price_drop",
+            NotificationKind::OccasionReminder { .. } => "occasion_reminder",
+        };
+        Self {
+            id: message.id().to_string(),
+            kind: kind.to_string(),
+            subject: message.kind().subject(),
+            message: message.kind().message(),
+            created_at: message.created_at(),
+        }
+    }
+}
+
+/// The response body data field for an inbox.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ListInboxResponseData {
+    pub messages: Vec<InboxMessageResponseData>,
+    pub next_cursor: Option<String>,
+}
+
+/// The query string of an inbox listing, e.g. `?user_id=...&limit=20`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListInboxHttpQuery {
+    pub user_id: String,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseListInboxHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Listing(#[from] ParseListingQueryError),
+}
+
+impl ListInboxHttpQuery {
+    /// Converts the HTTP query into a domain [ListInboxRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<ListInboxRequest, FieldErrors<ParseListInboxHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseListInboxHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let page = parse_page(&mut errors, self.cursor.as_deref(), self.limit);
+        let (Some(user_id), Some(page)) = (user_id, page) else {
+            return Err(errors);
+        };
+        Ok(ListInboxRequest::new(user_id, page))
+    }
+}
+
+/// List a page of the notifications in the inbox of a user, newest first. Pass `next_cursor` as
+/// `cursor` to read the next page.
+///
+/// # Responses
+///
+/// - 200 OK: the [InboxMessage]s of the page, and the cursor of the next page if there is one.
+/// - 422 Unprocessable entity: the user ID, the cursor or the limit is invalid.
+#[utoipa::path(
+    get,
+    path = "/inbox",
+    tag = "notifications",
+    params(
+        ListInboxHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The InboxMessages of the page, and the cursor of the next page if there is one.", body = ApiResponseBody<ListInboxResponseData>),
+        (status = 422, description = "The user ID, the cursor or the limit is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn list_inbox<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<ListInboxHttpQuery>,
+) -> Result<ApiSuccess<ListInboxResponseData>, ApiError> {
+    let domain_req = query.try_into_domain()?;
+    state
+        .services
+        .list_inbox(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|page| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                ListInboxResponseData {
+                    messages: page
+                        .entries()
+                        .iter()
+                        .map(InboxMessageResponseData::from)
+                        .collect(),
+                    next_cursor: page.next_cursor().map(|cursor| cursor.to_string()),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{
+        Cursor, MockNotificationService, Notification, OccasionKind, Page, SortValue,
+    };
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_list_inbox_success() {
+        let user_id = Uuid::now_v7();
+        let message = InboxMessage::new(
+            Uuid::now_v7(),
+            Notification::new(
+                user_id,
+                NotificationKind::OccasionReminder {
+                    wishlist_id: Uuid::now_v7(),
+                    wishlist_name: "Alice's birthday".into(),
+                    occasion: OccasionKind::Birthday,
+                    date: "2027-03-14".parse().unwrap(),
+                    days_left: 3,
+                },
+            ),
+            Utc::now(),
+        );
+        let cursor = Cursor::new(Some(SortValue::Time(message.created_at())), message.id());
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ListInboxResponseData {
+                messages: vec![InboxMessageResponseData {
+                    id: message.id().to_string(),
+                    kind: "occasion_reminder".into(),
+                    subject: "Alice's birthday is in 3 days".into(),
+                    message:
+                        "Reminder: the wishlist Alice's birthday is for a birthday on 2027-03-14."
+                            .into(),
+                    created_at: message.created_at(),
+                }],
+                next_cursor: Some(cursor.to_string()),
+            },
+        );
+        let mut mock_notification_service = MockNotificationService::new();
+        mock_notification_service
+            .expect_list_inbox()
+            .withf(move |req| req.user_id() == user_id && req.page().limit().get() == 1)
+            .return_once(move |_| {
+                Box::pin(future::ready(Ok(Page::new(vec![message], Some(cursor)))))
+            });
+        let state = test_services()
+            .with_notification(mock_notification_service)
+            .into_state();
+        let query = ApiQuery(ListInboxHttpQuery {
+            user_id: user_id.to_string(),
+            cursor: None,
+            limit: Some(1),
+        });
+
+        let actual = list_inbox(state, query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,165 @@
+/*
+Module `list_invitations` specifies an HTTP handler for listing the pending
+[GroupInvitation](crate::domain::GroupInvitation)s of a user, and the associated data
+structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{ListInvitationsError, ListInvitationsRequest};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::query::{parse_page, ParseListingQueryError};
+use crate::interface::http::AppState;
+
+use super::invite_member::InvitationResponseData;
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<ListInvitationsError> for ApiError {
+    fn from(e: ListInvitationsError) -> Self {
+        match e {
+            ListInvitationsError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for the pending invitations of a user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ListInvitationsResponseData {
+    pub invitations: Vec<InvitationResponseData>,
+    pub next_cursor: Option<String>,
+}
+
+/// The query string of an invitation listing, e.g. `?user_id=...&limit=20`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListInvitationsHttpQuery {
+    pub user_id: String,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseListInvitationsHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Listing(#[from] ParseListingQueryError),
+}
+
+impl ListInvitationsHttpQuery {
+    /// Converts the HTTP query into a domain [ListInvitationsRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<ListInvitationsRequest, FieldErrors<ParseListInvitationsHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseListInvitationsHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let page = parse_page(&mut errors, self.cursor.as_deref(), self.limit);
+        let (Some(user_id), Some(page)) = (user_id, page) else {
+            return Err(errors);
+        };
+        Ok(ListInvitationsRequest::new(user_id, page))
+    }
+}
+
+/// List a page of the pending invitations of a user into groups, oldest first. Pass
+/// `next_cursor` as `cursor` to read the next page.
+///
+/// # Responses
+///
+/// - 200 OK: the pending invitations of the page, and the cursor of the next page if there is
+///   one.
+/// - 422 Unprocessable entity: the user ID, the cursor or the limit is invalid.
+#[utoipa::path(
+    get,
+    path = "/invitations",
+    tag = "groups",
+    params(
+        ListInvitationsHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The pending invitations of the page, and the cursor of the next page if there is one.", body = ApiResponseBody<ListInvitationsResponseData>),
+        (status = 422, description = "The user ID, the cursor or the limit is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn list_invitations<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<ListInvitationsHttpQuery>,
+) -> Result<ApiSuccess<ListInvitationsResponseData>, ApiError> {
+    let domain_req = query.try_into_domain()?;
+    state
+        .services
+        .list_invitations(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|page| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                ListInvitationsResponseData {
+                    invitations: page
+                        .entries()
+                        .iter()
+                        .map(InvitationResponseData::from)
+                        .collect(),
+                    next_cursor: page.next_cursor().map(|cursor| cursor.to_string()),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use chrono::Utc;
+
+    use crate::domain::{GroupInvitation, MockGroupService, Page};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_list_invitations_success() {
+        let user_id = Uuid::now_v7();
+        let invitation = GroupInvitation::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            user_id,
+            Uuid::now_v7(),
+            Utc::now(),
+        );
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ListInvitationsResponseData {
+                invitations: vec![InvitationResponseData::from(&invitation)],
+                next_cursor: None,
+            },
+        );
+        let mut mock_group_service = MockGroupService::new();
+        mock_group_service
+            .expect_list_invitations()
+            .withf(move |req| req.user_id() == user_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(Page::new(vec![invitation], None)))));
+        let state = test_services().with_group(mock_group_service).into_state();
+        let query = ApiQuery(ListInvitationsHttpQuery {
+            user_id: user_id.to_string(),
+            cursor: None,
+            limit: None,
+        });
+
+        let actual = list_invitations(state, query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,446 @@
+/*
+Module `list_items` specifies an HTTP handler for listing the [Item]s of a [Wishlist], and the
+associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    ItemFilter, ItemListing, ItemPriority, ItemPriorityInvalidError, ItemSort,
+    ItemSortInvalidError, ItemSortKey, ListItemsError, ListItemsRequest, ShareToken, SortDirection,
+};
+use crate::interface::http::conditional::EntityTag;
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::query::{
+    parse_order, parse_page, parse_price_range, ParseListingQueryError,
+};
+use crate::interface::http::AppState;
+
+use super::create_item::ItemResponseData;
+use super::create_section::SectionResponseData;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<ListItemsError> for ApiError {
+    fn from(e: ListItemsError) -> Self {
+        match e {
+            ListItemsError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            ListItemsError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for an [Item] listing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ListItemsResponseData {
+    pub wishlist_id: String,
+    pub wishlist_version: u64,
+    pub sections: Vec<SectionResponseData>,
+    pub items: Vec<ListedItemResponseData>,
+    pub next_cursor: Option<String>,
+}
+
+/// A listed [Item], along with its place in the owner's order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ListedItemResponseData {
+    #[serde(flatten)]
+    pub item: ItemResponseData,
+    pub position: Option<usize>,
+    pub section_id: Option<String>,
+}
+
+impl From<&ItemListing> for ListItemsResponseData {
+    fn from(listing: &ItemListing) -> Self {
+        let wishlist = listing.wishlist();
+        Self {
+            wishlist_id: wishlist.id().to_string(),
+            wishlist_version: wishlist.version(),
+            sections: wishlist
+                .sections()
+                .iter()
+                .map(SectionResponseData::from)
+                .collect(),
+            items: listing
+                .items()
+                .iter()
+                .map(|item| ListedItemResponseData {
+                    item: item.into(),
+                    position: wishlist.position_of(item.id()),
+                    section_id: wishlist
+                        .section_of(item.id())
+                        .map(|section| section.id().to_string()),
+                })
+                .collect(),
+            next_cursor: listing.next_cursor().map(|cursor| cursor.to_string()),
+        }
+    }
+}
+
+/// The weak tag of an [Item] listing, which changes along with the [Wishlist] or any [Item] of the
+/// page.
+fn listing_etag(listing: &ItemListing) -> EntityTag {
+    let wishlist = listing.wishlist();
+    let items: Vec<_> = listing
+        .items()
+        .iter()
+        .map(|item| (item.id(), item.version()))
+        .collect();
+    EntityTag::digest((
+        wishlist.id(),
+        wishlist.version(),
+        items,
+        listing.next_cursor().map(ToString::to_string),
+    ))
+}
+
+/// The query string of an [Item] listing, e.g.
+/// `?min_priority=normal&attribute=size:M&max_price=50&sort=price&order=desc&limit=20`. A private
+/// [Wishlist] is only listed for a `user_id` who may see it or with the `token` of its share link.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListItemsHttpQuery {
+    pub user_id: Option<String>,
+    pub token: Option<String>,
+    pub priority: Option<String>,
+    pub min_priority: Option<String>,
+    pub attribute: Option<String>,
+    pub min_price: Option<String>,
+    pub max_price: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseListItemsHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("priority {} is invalid", .0.invalid_priority)]
+    Priority(#[from] ItemPriorityInvalidError),
+    #[error(
+        "sort {} is invalid, expected position, created, title, price, priority or attribute:<key>",
+        .0.invalid_sort
+    )]
+    Sort(#[from] ItemSortInvalidError),
+    #[error("attribute {0} is invalid, expected <key>:<value>")]
+    Attribute(String),
+    #[error(transparent)]
+    Listing(#[from] ParseListingQueryError),
+}
+
+impl ListItemsHttpQuery {
+    /// Converts the HTTP query into a domain [ListItemsRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<ListItemsRequest, FieldErrors<ParseListItemsHttpRequestError>> {
+        let priority =
+            |value: Option<String>| value.as_deref().map(str::parse::<ItemPriority>).transpose();
+        let mut errors = FieldErrors::new();
+        let viewer_id = errors.check_parameter(
+            "user_id",
+            self.user_id
+                .map(|id| {
+                    Uuid::parse_str(&id).map_err(|_| ParseListItemsHttpRequestError::UserId(id))
+                })
+                .transpose(),
+        );
+        let exact_priority = errors.check_parameter("priority", priority(self.priority));
+        let min_priority = errors.check_parameter("min_priority", priority(self.min_priority));
+        let attribute = errors.check_parameter(
+            "attribute",
+            self.attribute
+                .map(|attribute| match attribute.split_once(':') {
+                    Some((key, value)) if !key.trim().is_empty() => {
+                        Ok((key.trim().to_string(), value.trim().to_string()))
+                    }
+                    _ => Err(ParseListItemsHttpRequestError::Attribute(attribute)),
+                })
+                .transpose(),
+        );
+        let key = errors.check_parameter(
+            "sort",
+            self.sort
+                .as_deref()
+                .map(str::parse::<ItemSortKey>)
+                .transpose(),
+        );
+        let price_range = parse_price_range(
+            &mut errors,
+            self.min_price.as_deref(),
+            self.max_price.as_deref(),
+        );
+        let direction = parse_order(&mut errors, self.order.as_deref());
+        let page = parse_page(&mut errors, self.cursor.as_deref(), self.limit);
+        let (
+            Some(viewer_id),
+            Some(exact_priority),
+            Some(min_priority),
+            Some(attribute),
+            Some(price_range),
+            Some(key),
+            Some(direction),
+            Some(page),
+        ) = (
+            viewer_id,
+            exact_priority,
+            min_priority,
+            attribute,
+            price_range,
+            key,
+            direction,
+            page,
+        )
+        else {
+            return Err(errors);
+        };
+        let filter = ItemFilter::default()
+            .with_priority(exact_priority)
+            .with_min_priority(min_priority)
+            .with_attribute(attribute)
+            .with_price_range(price_range);
+        Ok(ListItemsRequest::new(
+            wishlist_id,
+            filter,
+            ItemSort::new(
+                key.unwrap_or(ItemSortKey::Position),
+                direction.unwrap_or(SortDirection::Ascending),
+            ),
+            page,
+        )
+        .with_viewer(viewer_id)
+        .with_share_token(self.token.as_deref().map(ShareToken::from)))
+    }
+}
+
+/// List a page of the [Item]s of a [Wishlist] along with its sections, optionally filtered by
+/// priority, variant attribute or price. Items come in the owner's order unless sorted by
+/// creation, title, price, priority or attribute; pass `next_cursor` as `cursor` to read the next
+/// page.
+///
+/// # Responses
+///
+/// - 200 OK: the matching [Item]s of the page, in the requested order, and the cursor of the next
+///   page if there is one, tagged with an `ETag`.
+/// - 304 Not modified: the listing has not changed since the `ETag` in `If-None-Match`.
+/// - 404 Not found: the [Wishlist] does not exist or is private to the user.
+/// - 422 Unprocessable entity: the user ID or a filter, sort or page parameter is invalid.
+#[utoipa::path(
+    get,
+    path = "/wishlists/{wishlist_id}/items",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        ListItemsHttpQuery,
+        ("If-None-Match" = Option<String>, Header, description = "The ETag of the listing as last read, to skip it if it has not changed."),
+    ),
+    responses(
+        (status = 200, description = "The matching Items of the page, in the requested order, and the cursor of the next page if there is one.", body = ApiResponseBody<ListItemsResponseData>, headers(("ETag" = String, description = "The weak tag of the listing."))),
+        (status = 304, description = "The listing has not changed since the ETag in If-None-Match."),
+        (status = 404, description = "The Wishlist does not exist or is private to the user.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID or a filter, sort or page parameter is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn list_items<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<ListItemsHttpQuery>,
+) -> Result<ApiSuccess<ListItemsResponseData>, ApiError> {
+    let domain_req = query.try_into_domain(wishlist_id)?;
+    state
+        .services
+        .list_items(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref listing| {
+            ApiSuccess::new(StatusCode::OK, listing.into()).with_etag(listing_etag(listing))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::{Item, MockItemService, Page, PageRequest, Wishlist, WishlistSection};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+    use crate::interface::http::problem::InvalidField;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_list_items_success() {
+        let wishlist_id = Uuid::now_v7();
+        let item = Item::create(
+            Uuid::now_v7(),
+            wishlist_id,
+            "Scarf".into(),
+            "https://shop.example/scarf".into(),
+            None,
+            None,
+        );
+
+        let section = WishlistSection::new(Uuid::now_v7(), "Winter".into());
+        let mut wishlist = Wishlist::new(
+            wishlist_id,
+            Uuid::now_v7(),
+            "Birthday".into(),
+            "Birthday".into(),
+            false,
+        );
+        wishlist.add_item(item.id());
+        wishlist.add_section(section.clone()).unwrap();
+        wishlist
+            .move_item_to_section(item.id(), Some(section.id()))
+            .unwrap();
+
+        let mut mock_item_service = MockItemService::new();
+        let listing = ItemListing::new(wishlist, Page::new(vec![item.clone()], None));
+        let etag = listing_etag(&listing);
+        mock_item_service
+            .expect_list_items()
+            .withf(|req| {
+                req.sort() == &ItemSort::new(ItemSortKey::Priority, SortDirection::Descending)
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(listing))));
+        let service = test_services().with_item(mock_item_service).build();
+        let state = axum::extract::State(AppState {
+            services: Arc::new(service),
+        });
+        let query = ApiQuery(ListItemsHttpQuery {
+            sort: Some("priority".to_string()),
+            order: Some("desc".to_string()),
+            ..Default::default()
+        });
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ListItemsResponseData {
+                wishlist_id: wishlist_id.to_string(),
+                wishlist_version: 1,
+                sections: vec![SectionResponseData::from(&section)],
+                items: vec![ListedItemResponseData {
+                    item: ItemResponseData::from(&item),
+                    position: Some(0),
+                    section_id: Some(section.id().to_string()),
+                }],
+                next_cursor: None,
+            },
+        )
+        .with_etag(etag);
+
+        let actual = list_items(state, ApiPath(wishlist_id), query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_default_query() {
+        let req = ListItemsHttpQuery::default()
+            .try_into_domain(Uuid::now_v7())
+            .unwrap();
+        assert_eq!(req.sort(), &ItemSort::default());
+        assert_eq!(req.sort().key(), &ItemSortKey::Position);
+        assert_eq!(req.page(), &PageRequest::default());
+        assert_eq!(req.viewer_id(), None);
+        assert_eq!(req.share_token(), None);
+    }
+
+    #[test]
+    fn test_parse_viewer_query() {
+        let user_id = Uuid::now_v7();
+        let req = ListItemsHttpQuery {
+            user_id: Some(user_id.to_string()),
+            token: Some("secret".to_string()),
+            ..Default::default()
+        }
+        .try_into_domain(Uuid::now_v7())
+        .unwrap();
+        assert_eq!(req.viewer_id(), Some(user_id));
+        assert_eq!(req.share_token(), Some(&ShareToken::from("secret")));
+    }
+
+    #[test]
+    fn test_parse_invalid_query() {
+        let parse = |query: ListItemsHttpQuery| query.try_into_domain(Uuid::now_v7());
+        assert_eq!(
+            parse(ListItemsHttpQuery {
+                sort: Some("colour".to_string()),
+                ..Default::default()
+            })
+                .unwrap_err()
+                .into_invalid_fields(),
+            vec![InvalidField::parameter("sort", "sort colour is invalid, expected position, created, title, price, priority or attribute:<key>")]
+        );
+        assert_eq!(
+            parse(ListItemsHttpQuery {
+                user_id: Some("alice".to_string()),
+                ..Default::default()
+            })
+            .unwrap_err()
+            .into_invalid_fields(),
+            vec![InvalidField::parameter(
+                "user_id",
+                "user id alice is invalid"
+            )]
+        );
+        assert_eq!(
+            parse(ListItemsHttpQuery {
+                order: Some("up".to_string()),
+                ..Default::default()
+            })
+            .unwrap_err()
+            .into_invalid_fields(),
+            vec![InvalidField::parameter(
+                "order",
+                "order up is invalid, expected asc or desc"
+            )]
+        );
+        assert_eq!(
+            parse(ListItemsHttpQuery {
+                attribute: Some("size".to_string()),
+                ..Default::default()
+            })
+            .unwrap_err()
+            .into_invalid_fields(),
+            vec![InvalidField::parameter(
+                "attribute",
+                "attribute size is invalid, expected <key>:<value>"
+            )]
+        );
+        assert_eq!(
+            parse(ListItemsHttpQuery {
+                max_price: Some("cheap".to_string()),
+                limit: Some(500),
+                ..Default::default()
+            })
+            .unwrap_err()
+            .into_invalid_fields(),
+            vec![
+                InvalidField::parameter("max_price", "price cheap is invalid"),
+                InvalidField::parameter("limit", "Limit 500 is not between 1 and 100"),
+            ]
+        );
+        assert_eq!(
+            parse(ListItemsHttpQuery {
+                min_priority: Some("urgent".to_string()),
+                ..Default::default()
+            })
+            .unwrap_err()
+            .into_invalid_fields(),
+            vec![InvalidField::parameter(
+                "min_priority",
+                "priority urgent is invalid"
+            )]
+        );
+    }
+}
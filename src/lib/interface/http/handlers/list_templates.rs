@@ -0,0 +1,200 @@
+/*
+Module `list_templates` specifies an HTTP handler for listing the [Wishlist] templates.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::application::UseCases;
+use crate::domain::{FindWishlistsError, WishlistQuery};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::query::{
+    parse_page, parse_wishlist_filter, parse_wishlist_sort, ParseListingQueryError,
+};
+use crate::interface::http::AppState;
+
+use super::duplicate_wishlist::WishlistResponseData;
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<FindWishlistsError> for ApiError {
+    fn from(e: FindWishlistsError) -> Self {
+        match e {
+            FindWishlistsError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a page of templates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ListTemplatesResponseData {
+    pub templates: Vec<WishlistResponseData>,
+    pub next_cursor: Option<String>,
+}
+
+/// The query string of a template listing, e.g. `?occasion=baby_shower&sort=name&limit=20`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListTemplatesHttpQuery {
+    pub visibility: Option<String>,
+    pub occasion: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseListTemplatesHttpRequestError {
+    #[error(transparent)]
+    Listing(#[from] ParseListingQueryError),
+}
+
+impl ListTemplatesHttpQuery {
+    /// Converts the HTTP query into a domain [WishlistQuery].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<WishlistQuery, FieldErrors<ParseListTemplatesHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let filter = parse_wishlist_filter(
+            &mut errors,
+            self.visibility.as_deref(),
+            self.occasion.as_deref(),
+        );
+        let sort = parse_wishlist_sort(&mut errors, self.sort.as_deref(), self.order.as_deref());
+        let page = parse_page(&mut errors, self.cursor.as_deref(), self.limit);
+        let (Some(filter), Some(sort), Some(page)) = (filter, sort, page) else {
+            return Err(errors);
+        };
+        Ok(WishlistQuery::new(filter, sort, page))
+    }
+}
+
+/// List a page of the [Wishlist]s marked as templates, which anyone can duplicate, optionally
+/// filtered by visibility or occasion. Templates come most recently updated first unless sorted
+/// by creation or name; pass `next_cursor` as `cursor` to read the next page.
+///
+/// # Responses
+///
+/// - 200 OK: the templates of the page, and the cursor of the next page if there is one.
+/// - 422 Unprocessable entity: a filter, sort or page parameter is invalid.
+#[utoipa::path(
+    get,
+    path = "/wishlists/templates",
+    tag = "wishlists",
+    params(
+        ListTemplatesHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The templates of the page, and the cursor of the next page if there is one.", body = ApiResponseBody<ListTemplatesResponseData>),
+        (status = 422, description = "A filter, sort or page parameter is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn list_templates<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<ListTemplatesHttpQuery>,
+) -> Result<ApiSuccess<ListTemplatesResponseData>, ApiError> {
+    let domain_query = query.try_into_domain()?;
+    state
+        .services
+        .list_templates(&domain_query)
+        .await
+        .map_err(ApiError::from)
+        .map(|page| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                ListTemplatesResponseData {
+                    templates: page
+                        .entries()
+                        .iter()
+                        .map(WishlistResponseData::from)
+                        .collect(),
+                    next_cursor: page.next_cursor().map(|cursor| cursor.to_string()),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use uuid::Uuid;
+
+    use crate::domain::{MockWishlistService, OccasionKind, Page, Wishlist, WishlistSortKey};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+    use crate::interface::http::problem::InvalidField;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_list_templates_success() {
+        let id = Uuid::now_v7();
+        let mut template = Wishlist::new(id, id, "Baby".into(), "Baby".into(), false);
+        template.set_template(true);
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ListTemplatesResponseData {
+                templates: vec![WishlistResponseData::from(&template)],
+                next_cursor: None,
+            },
+        );
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_list_templates()
+            .withf(|query| {
+                query.sort().key() == WishlistSortKey::Name
+                    && query.page().limit().get() == 10
+                    && query.filter().matches(&Wishlist::new(
+                        Uuid::now_v7(),
+                        Uuid::now_v7(),
+                        "Baby".into(),
+                        "Baby".into(),
+                        false,
+                    ))
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(Page::new(vec![template], None)))));
+        let state = test_services()
+            .with_wishlist(mock_wish_service)
+            .into_state();
+
+        let query = ApiQuery(ListTemplatesHttpQuery {
+            visibility: Some("public".to_string()),
+            sort: Some("name".to_string()),
+            limit: Some(10),
+            ..Default::default()
+        });
+
+        let actual = list_templates(state, query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_invalid_query() {
+        let query = ListTemplatesHttpQuery {
+            occasion: Some("housewarming".to_string()),
+            sort: Some("price".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            query.try_into_domain().unwrap_err().into_invalid_fields(),
+            vec![
+                InvalidField::parameter("occasion", "Occasion kind housewarming is invalid"),
+                InvalidField::parameter(
+                    "sort",
+                    "sort price is invalid, expected updated, created or name"
+                ),
+            ]
+        );
+        let query = ListTemplatesHttpQuery {
+            occasion: Some(OccasionKind::BabyShower.to_string()),
+            ..Default::default()
+        };
+        assert!(query.try_into_domain().is_ok());
+    }
+}
@@ -0,0 +1,278 @@
+/*
+Module `list_webhook_deliveries` specifies an HTTP handler for reading the delivery log of a
+webhook, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    AttemptOutcome, DeliveryAttempt, ListWebhookDeliveriesError, ListWebhookDeliveriesRequest,
+    WebhookDelivery, WebhookEventKind,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::query::{parse_page, ParseListingQueryError};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<ListWebhookDeliveriesError> for ApiError {
+    fn from(e: ListWebhookDeliveriesError) -> Self {
+        match e {
+            ListWebhookDeliveriesError::WebhookDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WebhookNotFound,
+                format!("Webhook ID {} does not exist", id),
+            )),
+            ListWebhookDeliveriesError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [DeliveryAttempt]. It has the status the receiver
+/// responded with, or the error if it could not be reached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct DeliveryAttemptResponseData {
+    pub attempted_at: DateTime<Utc>,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl From<&DeliveryAttempt> for DeliveryAttemptResponseData {
+    fn from(attempt: &DeliveryAttempt) -> Self {
+        let (status, error) = match attempt.outcome() {
+            AttemptOutcome::Responded { status } => (Some(*status), None),
+            AttemptOutcome::Failed { reason } => (None, Some(reason.clone())),
+        };
+        Self {
+            attempted_at: attempt.attempted_at(),
+            status,
+            error,
+        }
+    }
+}
+
+/// The response body data field for a [WebhookDelivery].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WebhookDeliveryResponseData {
+    pub id: String,
+    pub event_id: String,
+    pub event: String,
+    pub status: String,
+    pub attempts: Vec<DeliveryAttemptResponseData>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&WebhookDelivery> for WebhookDeliveryResponseData {
+    fn from(delivery: &WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id().to_string(),
+            event_id: delivery.event().id().to_string(),
+            event: WebhookEventKind::of(delivery.event().change()).to_string(),
+            status: delivery.status().to_string(),
+            attempts: delivery
+                .attempts()
+                .iter()
+                .map(DeliveryAttemptResponseData::from)
+                .collect(),
+            next_attempt_at: delivery.next_attempt_at(),
+            created_at: delivery.created_at(),
+        }
+    }
+}
+
+/// The response body data field for a delivery log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ListWebhookDeliveriesResponseData {
+    pub deliveries: Vec<WebhookDeliveryResponseData>,
+    pub next_cursor: Option<String>,
+}
+
+/// The query string of a delivery log listing, e.g. `?user_id=...&limit=20`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListWebhookDeliveriesHttpQuery {
+    pub user_id: String,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseListWebhookDeliveriesHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Listing(#[from] ParseListingQueryError),
+}
+
+impl ListWebhookDeliveriesHttpQuery {
+    /// Converts the HTTP query into a domain [ListWebhookDeliveriesRequest].
+    pub fn try_into_domain(
+        self,
+        webhook_id: Uuid,
+    ) -> Result<ListWebhookDeliveriesRequest, FieldErrors<ParseListWebhookDeliveriesHttpRequestError>>
+    {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id).map_err(|_| {
+                ParseListWebhookDeliveriesHttpRequestError::UserId(self.user_id.clone())
+            }),
+        );
+        let page = parse_page(&mut errors, self.cursor.as_deref(), self.limit);
+        let (Some(user_id), Some(page)) = (user_id, page) else {
+            return Err(errors);
+        };
+        Ok(ListWebhookDeliveriesRequest::new(webhook_id, user_id, page))
+    }
+}
+
+/// List a page of the delivery log of a webhook of the user, newest first, with every attempt.
+/// Deliveries are `pending` until the receiver responds with a 2xx status, and `dead_lettered`
+/// once every attempt failed. Pass `next_cursor` as `cursor` to read the next page.
+///
+/// # Responses
+///
+/// - 200 OK: the [WebhookDelivery]s of the page, and the cursor of the next page if there is one.
+/// - 404 Not found: the user has no such webhook.
+/// - 422 Unprocessable entity: the user ID, the cursor or the limit is invalid.
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}/deliveries",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = Uuid, Path, description = "The ID of the webhook."),
+        ListWebhookDeliveriesHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The WebhookDeliverys of the page, and the cursor of the next page if there is one.", body = ApiResponseBody<ListWebhookDeliveriesResponseData>),
+        (status = 404, description = "The user has no such webhook.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID, the cursor or the limit is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn list_webhook_deliveries<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(webhook_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<ListWebhookDeliveriesHttpQuery>,
+) -> Result<ApiSuccess<ListWebhookDeliveriesResponseData>, ApiError> {
+    let domain_req = query.try_into_domain(webhook_id)?;
+    state
+        .services
+        .list_webhook_deliveries(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|page| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                ListWebhookDeliveriesResponseData {
+                    deliveries: page
+                        .entries()
+                        .iter()
+                        .map(WebhookDeliveryResponseData::from)
+                        .collect(),
+                    next_cursor: page.next_cursor().map(|cursor| cursor.to_string()),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use chrono::TimeDelta;
+
+    use crate::domain::{
+        Item, ItemChange, ItemEvent, MockWebhookService, Page, RetryPolicy, Webhook, WebhookSecret,
+        WebhookUrl, Wishlist,
+    };
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_list_webhook_deliveries_success() {
+        let user_id = Uuid::now_v7();
+        let now = Utc::now();
+        let wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            user_id,
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        );
+        let webhook = Webhook::new(
+            Uuid::now_v7(),
+            user_id,
+            wishlist.id(),
+            WebhookUrl::new("https://home.example/hooks").unwrap(),
+            WebhookSecret::generate(),
+            WebhookEventKind::ALL.to_vec(),
+            now,
+        );
+        let item = Item::create(
+            Uuid::now_v7(),
+            wishlist.id(),
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        let event = ItemEvent::new(3, &wishlist, ItemChange::Edited(item));
+        let mut delivery = webhook.delivery_for(&event, now).unwrap();
+        delivery.record_attempt(
+            DeliveryAttempt::new(
+                now,
+                AttemptOutcome::Failed {
+                    reason: "Webhook timed out".into(),
+                },
+            ),
+            &RetryPolicy::default(),
+        );
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ListWebhookDeliveriesResponseData {
+                deliveries: vec![WebhookDeliveryResponseData {
+                    id: delivery.id().to_string(),
+                    event_id: "3".into(),
+                    event: "item_edited".into(),
+                    status: "pending".into(),
+                    attempts: vec![DeliveryAttemptResponseData {
+                        attempted_at: now,
+                        status: None,
+                        error: Some("Webhook timed out".into()),
+                    }],
+                    next_attempt_at: Some(now + TimeDelta::seconds(30)),
+                    created_at: now,
+                }],
+                next_cursor: None,
+            },
+        );
+        let webhook_id = webhook.id();
+        let mut mock_webhook_service = MockWebhookService::new();
+        mock_webhook_service
+            .expect_list_webhook_deliveries()
+            .withf(move |req| req.webhook_id() == webhook_id && req.user_id() == user_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(Page::new(vec![delivery], None)))));
+        let state = test_services()
+            .with_webhook(mock_webhook_service)
+            .into_state();
+        let query = ApiQuery(ListWebhookDeliveriesHttpQuery {
+            user_id: user_id.to_string(),
+            cursor: None,
+            limit: None,
+        });
+
+        let actual = list_webhook_deliveries(state, ApiPath(webhook_id), query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
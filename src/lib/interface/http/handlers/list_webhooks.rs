@@ -0,0 +1,214 @@
+/*
+Module `list_webhooks` specifies an HTTP handler for listing the webhooks of a user, and the
+associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{ListWebhooksError, ListWebhooksRequest};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::query::{parse_page, ParseListingQueryError};
+use crate::interface::http::AppState;
+
+use super::register_webhook::WebhookResponseData;
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<ListWebhooksError> for ApiError {
+    fn from(e: ListWebhooksError) -> Self {
+        match e {
+            ListWebhooksError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a page of the webhooks of a user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ListWebhooksResponseData {
+    pub webhooks: Vec<WebhookResponseData>,
+    pub next_cursor: Option<String>,
+}
+
+/// The query string of the requests on the webhooks of a user, e.g. `?user_id=...`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WebhookHttpQuery {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseWebhookHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Listing(#[from] ParseListingQueryError),
+}
+
+impl WebhookHttpQuery {
+    /// Parses the ID of the user making the request.
+    pub fn user_id(&self) -> Result<Uuid, FieldErrors<ParseWebhookHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        errors
+            .check_parameter(
+                "user_id",
+                Uuid::parse_str(&self.user_id)
+                    .map_err(|_| ParseWebhookHttpRequestError::UserId(self.user_id.clone())),
+            )
+            .ok_or(errors)
+    }
+}
+
+/// The query string of a listing of the webhooks of a user, e.g. `?user_id=...&limit=20`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListWebhooksHttpQuery {
+    pub user_id: String,
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl ListWebhooksHttpQuery {
+    /// Converts the HTTP query into a domain [ListWebhooksRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<ListWebhooksRequest, FieldErrors<ParseWebhookHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseWebhookHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let page = parse_page(&mut errors, self.cursor.as_deref(), self.limit);
+        let (Some(user_id), Some(page)) = (user_id, page) else {
+            return Err(errors);
+        };
+        Ok(ListWebhooksRequest::new(user_id, page))
+    }
+}
+
+/// List a page of the webhooks of a user, oldest first. Their secrets are not included. Pass
+/// `next_cursor` as `cursor` to read the next page.
+///
+/// # Responses
+///
+/// - 200 OK: the [Webhook](crate::domain::Webhook)s of the page, and the cursor of the next page
+///   if there is one.
+/// - 422 Unprocessable entity: the user ID, the cursor or the limit is invalid.
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    params(
+        ListWebhooksHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The Webhooks of the page, and the cursor of the next page if there is one.", body = ApiResponseBody<ListWebhooksResponseData>),
+        (status = 422, description = "The user ID, the cursor or the limit is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn list_webhooks<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<ListWebhooksHttpQuery>,
+) -> Result<ApiSuccess<ListWebhooksResponseData>, ApiError> {
+    let domain_req = query.try_into_domain()?;
+    state
+        .services
+        .list_webhooks(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|page| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                ListWebhooksResponseData {
+                    webhooks: page
+                        .entries()
+                        .iter()
+                        .map(WebhookResponseData::from)
+                        .collect(),
+                    next_cursor: page.next_cursor().map(|cursor| cursor.to_string()),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use chrono::Utc;
+
+    use crate::domain::{
+        Cursor, MockWebhookService, Page, SortValue, Webhook, WebhookEventKind, WebhookSecret,
+        WebhookUrl,
+    };
+    use crate::interface::http::handlers::test_services;
+    use crate::interface::http::problem::InvalidField;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_list_webhooks_success() {
+        let user_id = Uuid::now_v7();
+        let webhook = Webhook::new(
+            Uuid::now_v7(),
+            user_id,
+            Uuid::now_v7(),
+            WebhookUrl::new("https://chat.example/hooks").unwrap(),
+            WebhookSecret::generate(),
+            WebhookEventKind::ALL.to_vec(),
+            Utc::now(),
+        );
+        let next = Cursor::new(Some(SortValue::Time(webhook.created_at())), webhook.id());
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ListWebhooksResponseData {
+                webhooks: vec![WebhookResponseData::from(&webhook)],
+                next_cursor: Some(next.to_string()),
+            },
+        );
+        let mut mock_webhook_service = MockWebhookService::new();
+        mock_webhook_service
+            .expect_list_webhooks()
+            .withf(move |req| req.user_id() == user_id && req.page().limit().get() == 1)
+            .return_once(move |_| {
+                Box::pin(future::ready(Ok(Page::new(vec![webhook], Some(next)))))
+            });
+        let state = test_services()
+            .with_webhook(mock_webhook_service)
+            .into_state();
+        let query = ApiQuery(ListWebhooksHttpQuery {
+            user_id: user_id.to_string(),
+            cursor: None,
+            limit: Some(1),
+        });
+
+        let actual = list_webhooks(state, query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_list_webhooks_query() {
+        assert_eq!(
+            ListWebhooksHttpQuery {
+                user_id: Uuid::now_v7().to_string(),
+                cursor: None,
+                limit: Some(500),
+            }
+            .try_into_domain()
+            .unwrap_err()
+            .into_invalid_fields(),
+            vec![InvalidField::parameter(
+                "limit",
+                "Limit 500 is not between 1 and 100"
+            )]
+        );
+    }
+}
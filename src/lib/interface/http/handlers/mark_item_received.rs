@@ -0,0 +1,162 @@
+/*
+Module `mark_item_received` specifies an HTTP handler for marking an [Item] as received by the
+owner of its [Wishlist], and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{Item, MarkItemReceivedError, MarkItemReceivedRequest};
+use crate::interface::http::conditional::{EntityTag, IfMatch};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_item::ItemResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<MarkItemReceivedError> for ApiError {
+    fn from(e: MarkItemReceivedError) -> Self {
+        match e {
+            MarkItemReceivedError::ItemDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ItemNotFound,
+                format!("Item ID {} does not exist", id),
+            )),
+            MarkItemReceivedError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            MarkItemReceivedError::VersionMismatch(err) => err.into(),
+            MarkItemReceivedError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of a request marking an [Item] as received, or not.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct MarkItemReceivedHttpRequestBody {
+    pub user_id: String,
+    pub received: bool,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseMarkItemReceivedHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl MarkItemReceivedHttpRequestBody {
+    /// Converts the HTTP request body into a domain [MarkItemReceivedRequest].
+    pub fn try_into_domain(
+        self,
+        item_id: Uuid,
+    ) -> Result<MarkItemReceivedRequest, FieldErrors<ParseMarkItemReceivedHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseMarkItemReceivedHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(MarkItemReceivedRequest::new(
+            item_id,
+            user_id,
+            self.received,
+        ))
+    }
+}
+
+/// Mark an [Item] as received by the owner of its [Wishlist], or not. Received items leave
+/// recurring [Wishlist]s when they reopen for the next occasion.
+///
+/// # Responses
+///
+/// - 200 OK: the updated [Item].
+/// - 403 Forbidden: the user does not own the [Wishlist] of the [Item].
+/// - 404 Not found: the [Item] does not exist.
+/// - 412 Precondition failed: the [Item] changed since the version in `If-Match`.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    put,
+    path = "/items/{item_id}/received",
+    tag = "items",
+    params(
+        ("item_id" = Uuid, Path, description = "The ID of the item."),
+        ("If-Match" = Option<String>, Header, description = "The ETag of the Item as last read; the change only applies to that version."),
+    ),
+    request_body = MarkItemReceivedHttpRequestBody,
+    responses(
+        (status = 200, description = "The updated Item.", body = ApiResponseBody<ItemResponseData>),
+        (status = 403, description = "The user does not own the Wishlist of the Item.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Item does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "The Item changed since the version in If-Match.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn mark_item_received<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(body): ApiJson<MarkItemReceivedHttpRequestBody>,
+) -> Result<ApiSuccess<ItemResponseData>, ApiError> {
+    let domain_req = body
+        .try_into_domain(item_id)?
+        .with_expected_version(expected_version);
+    state
+        .services
+        .mark_item_received(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref item: Item| {
+            ApiSuccess::new(StatusCode::OK, item.into())
+                .with_etag(EntityTag::version(item.version()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockItemService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mark_item_received_success() {
+        let id = Uuid::now_v7();
+        let mut item = Item::create(
+            id,
+            id,
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        item.set_received(true);
+        let expected = ApiSuccess::new(StatusCode::OK, ItemResponseData::from(&item))
+            .with_etag(EntityTag::version(item.version()));
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service
+            .expect_mark_item_received()
+            .withf(|req| req.received())
+            .return_once(move |_| Box::pin(future::ready(Ok(item))));
+        let state = test_services().with_item(mock_item_service).into_state();
+        let body = ApiJson(MarkItemReceivedHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            received: true,
+        });
+
+        let actual = mark_item_received(state, ApiPath(id), IfMatch::default(), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,203 @@
+/*
+Module `move_item` specifies an HTTP handler for moving an [Item] to another [Wishlist], and the
+data structures shared with the `copy_item` handler.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{Item, TransferItemError, TransferItemRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_item::ItemResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<TransferItemError> for ApiError {
+    fn from(e: TransferItemError) -> Self {
+        match e {
+            TransferItemError::ItemDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ItemNotFound,
+                format!("Item ID {} does not exist", id),
+            )),
+            TransferItemError::WishlistDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::WishlistNotFound,
+                    format!("Wishlist ID {} does not exist", id),
+                ))
+            }
+            TransferItemError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            TransferItemError::WishlistArchived { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::WishlistArchived,
+                format!("Wishlist ID {} is archived", id),
+            )),
+            TransferItemError::Duplicate => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ItemAlreadyInWishlist,
+                "An item with the same link already exists in the target wishlist",
+            )),
+            TransferItemError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of a request moving or copying an [Item] into the [Wishlist] `wishlist_id`, on
+/// behalf of the user `user_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct TransferItemHttpRequestBody {
+    pub wishlist_id: String,
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseTransferItemHttpRequestError {
+    #[error("wishlist id {0} is invalid")]
+    WishlistId(String),
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl TransferItemHttpRequestBody {
+    /// Converts the HTTP request body into a domain [TransferItemRequest].
+    pub fn try_into_domain(
+        self,
+        item_id: Uuid,
+    ) -> Result<TransferItemRequest, FieldErrors<ParseTransferItemHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let wishlist_id = errors.check_field(
+            "/wishlist_id",
+            Uuid::parse_str(&self.wishlist_id).map_err(|_| {
+                ParseTransferItemHttpRequestError::WishlistId(self.wishlist_id.clone())
+            }),
+        );
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseTransferItemHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let (Some(wishlist_id), Some(user_id)) = (wishlist_id, user_id) else {
+            return Err(errors);
+        };
+        Ok(TransferItemRequest::new(item_id, wishlist_id, user_id))
+    }
+}
+
+/// Move an [Item] to another [Wishlist] of the same owner, with everything attached to it.
+///
+/// # Responses
+///
+/// - 200 OK: the moved [Item].
+/// - 403 Forbidden: the user does not own both [Wishlist]s.
+/// - 404 Not found: the [Item] does not exist.
+/// - 422 Unprocessable entity: the target [Wishlist] does not exist, is archived, or already
+///   has an [Item] with the same link.
+#[utoipa::path(
+    post,
+    path = "/items/{item_id}/move",
+    tag = "items",
+    params(
+        ("item_id" = Uuid, Path, description = "The ID of the item."),
+    ),
+    request_body = TransferItemHttpRequestBody,
+    responses(
+        (status = 200, description = "The moved Item.", body = ApiResponseBody<ItemResponseData>),
+        (status = 403, description = "The user does not own both Wishlists.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Item does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The target Wishlist does not exist, is archived, or already has an Item with the same link.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn move_item<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<TransferItemHttpRequestBody>,
+) -> Result<ApiSuccess<ItemResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(item_id)?;
+    state
+        .services
+        .move_item(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref item: Item| ApiSuccess::new(StatusCode::OK, item.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::MockItemService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    fn state(item_service: MockItemService) -> State<AppState<impl UseCases>> {
+        let service = test_services().with_item(item_service).build();
+        State(AppState {
+            services: Arc::new(service),
+        })
+    }
+
+    fn body(wishlist_id: Uuid) -> ApiJson<TransferItemHttpRequestBody> {
+        ApiJson(TransferItemHttpRequestBody {
+            wishlist_id: wishlist_id.to_string(),
+            user_id: Uuid::now_v7().to_string(),
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_move_item_success() {
+        let item_id = Uuid::now_v7();
+        let wishlist_id = Uuid::now_v7();
+        let item = Item::create(
+            item_id,
+            wishlist_id,
+            "Scarf".into(),
+            "https://shop.example/scarf".into(),
+            None,
+            None,
+        );
+        let mut mock_item_service = MockItemService::new();
+        let moved = item.clone();
+        mock_item_service
+            .expect_move_item()
+            .withf(move |req| req.item_id() == item_id && req.wishlist_id() == wishlist_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(moved))));
+        let expected = ApiSuccess::new(StatusCode::OK, ItemResponseData::from(&item));
+
+        let actual = move_item(
+            state(mock_item_service),
+            ApiPath(item_id),
+            body(wishlist_id),
+        )
+        .await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_move_item_not_owner() {
+        let wishlist_id = Uuid::now_v7();
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service.expect_move_item().return_once(move |_| {
+            Box::pin(future::ready(Err(TransferItemError::NotWishlistOwner {
+                id: wishlist_id,
+            })))
+        });
+
+        let actual = move_item(
+            state(mock_item_service),
+            ApiPath(Uuid::now_v7()),
+            body(wishlist_id),
+        )
+        .await;
+        assert!(matches!(actual, Err(ApiError::Forbidden(_))));
+    }
+}
@@ -0,0 +1,232 @@
+/*
+Module `move_item_to_section` specifies an HTTP handler for moving an [Item] between the sections
+of its [Wishlist], and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{MoveItemToSectionError, MoveItemToSectionRequest};
+use crate::interface::http::conditional::{EntityTag, IfMatch};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::reorder_wishlist::{ParseWishlistLayoutHttpRequestError, WishlistLayoutResponseData};
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<MoveItemToSectionError> for ApiError {
+    fn from(e: MoveItemToSectionError) -> Self {
+        match e {
+            MoveItemToSectionError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            MoveItemToSectionError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            MoveItemToSectionError::ItemNotInWishlist { id } => Self::NotFound(Problem::new(
+                ProblemType::ItemNotInWishlist,
+                format!("Item ID {} is not in the wishlist", id),
+            )),
+            MoveItemToSectionError::SectionDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::SectionNotFound,
+                    format!("Section ID {} is not in the wishlist", id),
+                ))
+            }
+            MoveItemToSectionError::VersionMismatch(err) => err.into(),
+            MoveItemToSectionError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of a request moving an [Item] into a section, or out of any section when
+/// `section_id` is null.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct MoveItemToSectionHttpRequestBody {
+    pub user_id: String,
+    pub section_id: Option<Uuid>,
+}
+
+impl MoveItemToSectionHttpRequestBody {
+    /// Converts the HTTP request body into a domain [MoveItemToSectionRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<MoveItemToSectionRequest, FieldErrors<ParseWishlistLayoutHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        errors
+            .check_field(
+                "/user_id",
+                Uuid::parse_str(&self.user_id)
+                    .map_err(|_| ParseWishlistLayoutHttpRequestError::UserId(self.user_id.clone())),
+            )
+            .map(|user_id| {
+                MoveItemToSectionRequest::new(wishlist_id, user_id, item_id, self.section_id)
+            })
+            .ok_or(errors)
+    }
+}
+
+/// Move an [Item] into a section of its [Wishlist], or out of any section. The [Item] keeps its
+/// position.
+///
+/// # Responses
+///
+/// - 200 OK: the new layout of the [Wishlist].
+/// - 403 Forbidden: the user does not own the [Wishlist].
+/// - 404 Not found: the [Wishlist] does not exist or the [Item] is not in it.
+/// - 412 Precondition failed: the [Wishlist] changed since the version in `If-Match`.
+/// - 422 Unprocessable entity: the user ID is invalid or the section is not in the [Wishlist].
+#[utoipa::path(
+    put,
+    path = "/wishlists/{wishlist_id}/items/{item_id}/section",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        ("item_id" = Uuid, Path, description = "The ID of the item."),
+        ("If-Match" = Option<String>, Header, description = "The ETag of the Wishlist as last read; the change only applies to that version."),
+    ),
+    request_body = MoveItemToSectionHttpRequestBody,
+    responses(
+        (status = 200, description = "The new layout of the Wishlist.", body = ApiResponseBody<WishlistLayoutResponseData>),
+        (status = 403, description = "The user does not own the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Wishlist does not exist or the Item is not in it.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "The Wishlist changed since the version in If-Match.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid or the section is not in the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn move_item_to_section<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath((wishlist_id, item_id)): ApiPath<(Uuid, Uuid)>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(body): ApiJson<MoveItemToSectionHttpRequestBody>,
+) -> Result<ApiSuccess<WishlistLayoutResponseData>, ApiError> {
+    let domain_req = body
+        .try_into_domain(wishlist_id, item_id)?
+        .with_expected_version(expected_version);
+    state
+        .services
+        .move_item_to_section(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref wishlist| {
+            ApiSuccess::new(StatusCode::OK, wishlist.into())
+                .with_etag(EntityTag::version(wishlist.version()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{MockWishlistService, Wishlist, WishlistSection};
+    use crate::interface::http::handlers::test_services;
+    use crate::interface::http::handlers::{
+        create_section::SectionResponseData, reorder_wishlist::WishlistEntryResponseData,
+    };
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_move_item_to_section_success() {
+        let wishlist_id = Uuid::now_v7();
+        let item_id = Uuid::now_v7();
+        let section = WishlistSection::new(Uuid::now_v7(), "Books".into());
+        let section_id = section.id();
+
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_move_item_to_section()
+            .return_once(move |req| {
+                let mut wishlist = Wishlist::new(
+                    req.wishlist_id(),
+                    Uuid::now_v7(),
+                    "Birthday".into(),
+                    "Birthday".into(),
+                    false,
+                );
+                wishlist.add_item(req.item_id());
+                wishlist.add_section(section).unwrap();
+                let result = wishlist
+                    .move_item_to_section(req.item_id(), req.section_id())
+                    .map(|_| wishlist);
+                Box::pin(future::ready(result))
+            });
+        let state = test_services()
+            .with_wishlist(mock_wish_service)
+            .into_state();
+        let body = ApiJson(MoveItemToSectionHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            section_id: Some(section_id),
+        });
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            WishlistLayoutResponseData {
+                wishlist_id: wishlist_id.to_string(),
+                version: 1,
+                sections: vec![SectionResponseData {
+                    id: section_id.to_string(),
+                    name: "Books".to_string(),
+                }],
+                items: vec![WishlistEntryResponseData {
+                    item_id: item_id.to_string(),
+                    section_id: Some(section_id.to_string()),
+                }],
+            },
+        )
+        .with_etag(EntityTag::version(1));
+
+        let actual = move_item_to_section(
+            state,
+            ApiPath((wishlist_id, item_id)),
+            IfMatch::default(),
+            body,
+        )
+        .await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_move_item_to_section_not_owner() {
+        let (user_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let expected = ApiError::Forbidden(Problem::new(
+            ProblemType::NotWishlistOwner,
+            format!("Wishlist ID {} is not yours", wishlist_id),
+        ));
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_move_item_to_section()
+            .withf(move |req| req.wishlist_id() == wishlist_id && req.user_id() == user_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(
+                    MoveItemToSectionError::NotWishlistOwner { id: wishlist_id },
+                )))
+            });
+        let state = test_services()
+            .with_wishlist(mock_wish_service)
+            .into_state();
+        let body = ApiJson(MoveItemToSectionHttpRequestBody {
+            user_id: user_id.to_string(),
+            section_id: None,
+        });
+
+        let actual = move_item_to_section(
+            state,
+            ApiPath((wishlist_id, Uuid::now_v7())),
+            IfMatch::default(),
+            body,
+        )
+        .await;
+        assert_eq!(actual, Err(expected));
+    }
+}
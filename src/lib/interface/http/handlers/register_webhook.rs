@@ -0,0 +1,249 @@
+/*
+Module `register_webhook` specifies an HTTP handler for registering a [Webhook] notified of the
+events of a wishlist, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    RegisterWebhookError, RegisterWebhookRequest, Webhook, WebhookEventKind,
+    WebhookEventKindInvalidError, WebhookUrl, WebhookUrlInvalidError,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<RegisterWebhookError> for ApiError {
+    fn from(e: RegisterWebhookError) -> Self {
+        match e {
+            RegisterWebhookError::UserDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::UserNotFound,
+                    format!("User ID {} does not exist", id),
+                ))
+            }
+            RegisterWebhookError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            RegisterWebhookError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [Webhook]. Its secret is only returned on registration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WebhookResponseData {
+    pub id: String,
+    pub wishlist_id: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&Webhook> for WebhookResponseData {
+    fn from(webhook: &Webhook) -> Self {
+        Self {
+            id: webhook.id().to_string(),
+            wishlist_id: webhook.wishlist_id().to_string(),
+            url: webhook.url().to_string(),
+            events: webhook.events().iter().map(ToString::to_string).collect(),
+            created_at: webhook.created_at(),
+        }
+    }
+}
+
+/// The response body data field for a registered [Webhook], with the secret signing its
+/// payloads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RegisterWebhookResponseData {
+    pub webhook: WebhookResponseData,
+    pub secret: String,
+}
+
+impl From<&Webhook> for RegisterWebhookResponseData {
+    fn from(webhook: &Webhook) -> Self {
+        Self {
+            webhook: webhook.into(),
+            secret: webhook.secret().as_str().to_string(),
+        }
+    }
+}
+
+/// The body of a webhook registration request. Without events, the webhook is notified of every
+/// kind of event.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct RegisterWebhookHttpRequestBody {
+    pub user_id: String,
+    pub url: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseRegisterWebhookHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Url(#[from] WebhookUrlInvalidError),
+    #[error(transparent)]
+    Event(#[from] WebhookEventKindInvalidError),
+}
+
+impl RegisterWebhookHttpRequestBody {
+    /// Converts the HTTP request body into a domain [RegisterWebhookRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<RegisterWebhookRequest, FieldErrors<ParseRegisterWebhookHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseRegisterWebhookHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let url = errors.check_field("/url", WebhookUrl::new(&self.url));
+        let events: Vec<Option<WebhookEventKind>> = self
+            .events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| errors.check_field(format!("/events/{}", i), event.parse()))
+            .collect();
+        let (Some(user_id), Some(url), Some(events)) =
+            (user_id, url, events.into_iter().collect::<Option<Vec<_>>>())
+        else {
+            return Err(errors);
+        };
+        Ok(RegisterWebhookRequest::new(
+            user_id,
+            wishlist_id,
+            url,
+            events,
+        ))
+    }
+}
+
+/// Register a webhook notified of the events of a wishlist the user may see. Events are posted as
+/// JSON, signed with the returned secret, and retried with backoff until the receiver responds
+/// with a 2xx status. The secret is not shown again.
+///
+/// # Responses
+///
+/// - 201 Created: the [Webhook] and its secret.
+/// - 404 Not found: the wishlist does not exist, or the user may not see it.
+/// - 422 Unprocessable entity: the user does not exist, or the user ID, URL or an event is
+///   invalid.
+#[utoipa::path(
+    post,
+    path = "/wishlists/{wishlist_id}/webhooks",
+    tag = "webhooks",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+    ),
+    request_body = RegisterWebhookHttpRequestBody,
+    responses(
+        (status = 201, description = "The Webhook and its secret.", body = ApiResponseBody<RegisterWebhookResponseData>),
+        (status = 404, description = "The wishlist does not exist, or the user may not see it.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user does not exist, or the user ID, URL or an event is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn register_webhook<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<RegisterWebhookHttpRequestBody>,
+) -> Result<ApiSuccess<RegisterWebhookResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(wishlist_id)?;
+    state
+        .services
+        .register_webhook(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref webhook| ApiSuccess::new(StatusCode::CREATED, webhook.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{MockWebhookService, WebhookSecret};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+    use crate::interface::http::problem::InvalidField;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_register_webhook_success() {
+        let (user_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let webhook = Webhook::new(
+            Uuid::now_v7(),
+            user_id,
+            wishlist_id,
+            WebhookUrl::new("https://home.example/hooks").unwrap(),
+            WebhookSecret::generate(),
+            vec![WebhookEventKind::ItemReserved],
+            Utc::now(),
+        );
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            RegisterWebhookResponseData {
+                webhook: WebhookResponseData {
+                    id: webhook.id().to_string(),
+                    wishlist_id: wishlist_id.to_string(),
+                    url: "https://home.example/hooks".into(),
+                    events: vec!["item_reserved".into()],
+                    created_at: webhook.created_at(),
+                },
+                secret: webhook.secret().as_str().to_string(),
+            },
+        );
+        let mut mock_webhook_service = MockWebhookService::new();
+        mock_webhook_service
+            .expect_register_webhook()
+            .withf(move |req| {
+                req.user_id() == user_id
+                    && req.wishlist_id() == wishlist_id
+                    && req.events() == [WebhookEventKind::ItemReserved]
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(webhook))));
+        let state = test_services()
+            .with_webhook(mock_webhook_service)
+            .into_state();
+        let body = ApiJson(RegisterWebhookHttpRequestBody {
+            user_id: user_id.to_string(),
+            url: "https://home.example/hooks".into(),
+            events: vec!["item_reserved".into()],
+        });
+
+        let actual = register_webhook(state, ApiPath(wishlist_id), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_invalid_url_and_event() {
+        let body = RegisterWebhookHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            url: "ftp://home.example/hooks".into(),
+            events: vec!["item_added".into(), "item_deleted".into()],
+        };
+        assert_eq!(
+            body.try_into_domain(Uuid::now_v7())
+                .unwrap_err()
+                .into_invalid_fields(),
+            vec![
+                InvalidField::body("/url", "Webhook URL ftp://home.example/hooks is invalid"),
+                InvalidField::body("/events/1", "Webhook event item_deleted is invalid"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+/*
+Module `remove_member` specifies an HTTP handler for removing a member from a
+[Group](crate::domain::Group), or leaving it.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{GroupMembershipError, ManageMemberError, ManageMemberRequest};
+use crate::interface::http::problem::{Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_group::GroupResponseData;
+use super::group_feed::GroupActionHttpQuery;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<ManageMemberError> for ApiError {
+    fn from(e: ManageMemberError) -> Self {
+        match e {
+            ManageMemberError::GroupDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::GroupNotFound,
+                format!("Group ID {} does not exist", id),
+            )),
+            ManageMemberError::NotAdmin { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotGroupAdmin,
+                format!("Only admins of group ID {} can manage other members", id),
+            )),
+            ManageMemberError::Membership(GroupMembershipError::NotMember { id }) => {
+                Self::NotFound(Problem::new(
+                    ProblemType::NotGroupMember,
+                    format!("User ID {} is not a member of the group", id),
+                ))
+            }
+            ManageMemberError::Membership(GroupMembershipError::LastAdmin) => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::LastGroupAdmin,
+                    "a group needs at least one admin",
+                ))
+            }
+            ManageMemberError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// Remove a member from a [Group](crate::domain::Group): admins remove anyone, and members
+/// remove themselves to leave. The member loses access to the wishlists shared with the group,
+/// and the group to theirs.
+///
+/// # Responses
+///
+/// - 200 OK: the updated group.
+/// - 403 Forbidden: the user is not an admin and removes someone else.
+/// - 404 Not found: the group does not exist or the user is not a member of it.
+/// - 422 Unprocessable entity: the user ID is invalid or the member is the last admin.
+#[utoipa::path(
+    delete,
+    path = "/groups/{group_id}/members/{member_id}",
+    tag = "groups",
+    params(
+        ("group_id" = Uuid, Path, description = "The ID of the group."),
+        ("member_id" = Uuid, Path, description = "The ID of the member."),
+        GroupActionHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The updated group.", body = ApiResponseBody<GroupResponseData>),
+        (status = 403, description = "The user is not an admin and removes someone else.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The group does not exist or the user is not a member of it.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid or the member is the last admin.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn remove_member<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath((group_id, member_id)): ApiPath<(Uuid, Uuid)>,
+    ApiQuery(query): ApiQuery<GroupActionHttpQuery>,
+) -> Result<ApiSuccess<GroupResponseData>, ApiError> {
+    let domain_req = ManageMemberRequest::new(group_id, query.user_id()?, member_id);
+    state
+        .services
+        .remove_member(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref group| ApiSuccess::new(StatusCode::OK, group.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockGroupService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_remove_last_admin() {
+        let (group_id, user_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut mock_group_service = MockGroupService::new();
+        mock_group_service
+            .expect_remove_member()
+            .withf(move |req| req.user_id() == user_id && req.member_id() == user_id)
+            .return_once(|_| {
+                Box::pin(future::ready(Err(ManageMemberError::Membership(
+                    GroupMembershipError::LastAdmin,
+                ))))
+            });
+        let state = test_services().with_group(mock_group_service).into_state();
+        let query = ApiQuery(GroupActionHttpQuery {
+            user_id: user_id.to_string(),
+        });
+
+        let actual = remove_member(state, ApiPath((group_id, user_id)), query).await;
+        assert_eq!(
+            actual,
+            Err(ApiError::UnprocessableEntity(Problem::new(
+                ProblemType::LastGroupAdmin,
+                "a group needs at least one admin".to_string()
+            )))
+        );
+    }
+}
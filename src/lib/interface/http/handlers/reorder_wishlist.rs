@@ -0,0 +1,317 @@
+/*
+Module `reorder_wishlist` specifies an HTTP handler for rearranging the items and sections of a
+[Wishlist], and the layout data structures shared by the handlers changing it.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{ReorderWishlistError, ReorderWishlistRequest, Wishlist};
+use crate::interface::http::conditional::{EntityTag, IfMatch};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_section::SectionResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<ReorderWishlistError> for ApiError {
+    fn from(e: ReorderWishlistError) -> Self {
+        match e {
+            ReorderWishlistError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            ReorderWishlistError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            ReorderWishlistError::ItemNotInWishlist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::ItemNotInWishlist,
+                    format!("Item ID {} is not in the wishlist", id),
+                ))
+            }
+            ReorderWishlistError::SectionDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::SectionNotFound,
+                    format!("Section ID {} is not in the wishlist", id),
+                ))
+            }
+            ReorderWishlistError::Duplicate { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::DuplicateOrderEntry,
+                format!("ID {} is listed more than once", id),
+            )),
+            ReorderWishlistError::VersionMismatch(err) => err.into(),
+            ReorderWishlistError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for the layout of a [Wishlist]: its sections and items, in the
+/// owner's order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WishlistLayoutResponseData {
+    pub wishlist_id: String,
+    pub version: u64,
+    pub sections: Vec<SectionResponseData>,
+    pub items: Vec<WishlistEntryResponseData>,
+}
+
+/// The place of an item in a [Wishlist] layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WishlistEntryResponseData {
+    pub item_id: String,
+    pub section_id: Option<String>,
+}
+
+impl From<&Wishlist> for WishlistLayoutResponseData {
+    fn from(wishlist: &Wishlist) -> Self {
+        Self {
+            wishlist_id: wishlist.id().to_string(),
+            version: wishlist.version(),
+            sections: wishlist
+                .sections()
+                .iter()
+                .map(SectionResponseData::from)
+                .collect(),
+            items: wishlist
+                .items()
+                .iter()
+                .map(|entry| WishlistEntryResponseData {
+                    item_id: entry.item_id().to_string(),
+                    section_id: entry.section_id().map(|id| id.to_string()),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The errors of the requests changing the layout of a [Wishlist].
+#[derive(Debug, Clone, Error)]
+pub enum ParseWishlistLayoutHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+/// The body of a reorder request. Each ordering may list all or only some of the items or
+/// sections; omitted ones keep their position.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct ReorderWishlistHttpRequestBody {
+    pub user_id: String,
+    #[serde(default)]
+    pub item_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub section_ids: Vec<Uuid>,
+}
+
+impl ReorderWishlistHttpRequestBody {
+    /// Converts the HTTP request body into a domain [ReorderWishlistRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<ReorderWishlistRequest, FieldErrors<ParseWishlistLayoutHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        errors
+            .check_field(
+                "/user_id",
+                Uuid::parse_str(&self.user_id)
+                    .map_err(|_| ParseWishlistLayoutHttpRequestError::UserId(self.user_id.clone())),
+            )
+            .map(|user_id| {
+                ReorderWishlistRequest::new(wishlist_id, user_id, self.item_ids, self.section_ids)
+            })
+            .ok_or(errors)
+    }
+}
+
+/// Reorder the items and sections of a [Wishlist] at once.
+///
+/// A partial ordering rearranges the listed items among the positions they occupy: reordering
+/// `[a, b, c, d]` with `[d, b]` gives `[a, d, c, b]`.
+///
+/// # Responses
+///
+/// - 200 OK: the new layout of the [Wishlist].
+/// - 403 Forbidden: the user does not own the [Wishlist].
+/// - 404 Not found: the [Wishlist] does not exist.
+/// - 412 Precondition failed: the [Wishlist] changed since the version in `If-Match`.
+/// - 422 Unprocessable entity: the user ID is invalid, or an item or section is not in the
+///   [Wishlist] or is listed twice; nothing was reordered.
+#[utoipa::path(
+    put,
+    path = "/wishlists/{wishlist_id}/order",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        ("If-Match" = Option<String>, Header, description = "The ETag of the Wishlist as last read; the change only applies to that version."),
+    ),
+    request_body = ReorderWishlistHttpRequestBody,
+    responses(
+        (status = 200, description = "The new layout of the Wishlist.", body = ApiResponseBody<WishlistLayoutResponseData>),
+        (status = 403, description = "The user does not own the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Wishlist does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "The Wishlist changed since the version in If-Match.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid, or an item or section is not in the Wishlist or is listed twice; nothing was reordered.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn reorder_wishlist<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(body): ApiJson<ReorderWishlistHttpRequestBody>,
+) -> Result<ApiSuccess<WishlistLayoutResponseData>, ApiError> {
+    let domain_req = body
+        .try_into_domain(wishlist_id)?
+        .with_expected_version(expected_version);
+    state
+        .services
+        .reorder_wishlist(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref wishlist| {
+            ApiSuccess::new(StatusCode::OK, wishlist.into())
+                .with_etag(EntityTag::version(wishlist.version()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::MockWishlistService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    fn state(wish_service: MockWishlistService) -> State<AppState<impl UseCases>> {
+        let service = test_services().with_wishlist(wish_service).build();
+        State(AppState {
+            services: Arc::new(service),
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reorder_wishlist_success() {
+        let wishlist_id = Uuid::now_v7();
+        let (first, second) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_reorder_wishlist()
+            .withf(|req| req.expected_version() == Some(1))
+            .return_once(move |req| {
+                let mut wishlist = Wishlist::new(
+                    req.wishlist_id(),
+                    Uuid::now_v7(),
+                    "Birthday".into(),
+                    "Birthday".into(),
+                    false,
+                );
+                wishlist.add_item(first);
+                wishlist.add_item(second);
+                let result = wishlist
+                    .reorder(req.item_ids(), req.section_ids())
+                    .map(|_| {
+                        wishlist.next_version();
+                        wishlist
+                    });
+                Box::pin(future::ready(result))
+            });
+        let body = ApiJson(ReorderWishlistHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            item_ids: vec![second, first],
+            ..Default::default()
+        });
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            WishlistLayoutResponseData {
+                wishlist_id: wishlist_id.to_string(),
+                version: 2,
+                sections: vec![],
+                items: vec![
+                    WishlistEntryResponseData {
+                        item_id: second.to_string(),
+                        section_id: None,
+                    },
+                    WishlistEntryResponseData {
+                        item_id: first.to_string(),
+                        section_id: None,
+                    },
+                ],
+            },
+        )
+        .with_etag(EntityTag::version(2));
+
+        let actual = reorder_wishlist(
+            state(mock_wish_service),
+            ApiPath(wishlist_id),
+            IfMatch(Some(1)),
+            body,
+        )
+        .await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reorder_wishlist_invalid() {
+        let id = Uuid::now_v7();
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_reorder_wishlist()
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(ReorderWishlistError::Duplicate { id })))
+            });
+        let body = ApiJson(ReorderWishlistHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            item_ids: vec![id, id],
+            ..Default::default()
+        });
+
+        let actual = reorder_wishlist(
+            state(mock_wish_service),
+            ApiPath(Uuid::now_v7()),
+            IfMatch::default(),
+            body,
+        )
+        .await;
+        assert!(matches!(actual, Err(ApiError::UnprocessableEntity(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reorder_wishlist_not_owner() {
+        let (user_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let expected = ApiError::Forbidden(Problem::new(
+            ProblemType::NotWishlistOwner,
+            format!("Wishlist ID {} is not yours", wishlist_id),
+        ));
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_reorder_wishlist()
+            .withf(move |req| req.wishlist_id() == wishlist_id && req.user_id() == user_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(ReorderWishlistError::NotWishlistOwner {
+                    id: wishlist_id,
+                })))
+            });
+        let body = ApiJson(ReorderWishlistHttpRequestBody {
+            user_id: user_id.to_string(),
+            ..Default::default()
+        });
+
+        let actual = reorder_wishlist(
+            state(mock_wish_service),
+            ApiPath(wishlist_id),
+            IfMatch::default(),
+            body,
+        )
+        .await;
+        assert_eq!(actual, Err(expected));
+    }
+}
@@ -0,0 +1,180 @@
+/*
+Module `request_account_confirmation` specifies an HTTP handler for emailing a user the token
+confirming an export or a deletion of their account, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    AccountAction, AccountActionInvalidError, AccountConfirmation, AccountConfirmationRequest,
+    RequestAccountConfirmationError,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
+
+impl From<RequestAccountConfirmationError> for ApiError {
+    fn from(e: RequestAccountConfirmationError) -> Self {
+        match e {
+            RequestAccountConfirmationError::UserDoesNotExist { id } => {
+                Self::NotFound(Problem::new(
+                    ProblemType::UserNotFound,
+                    format!("User ID {} does not exist", id),
+                ))
+            }
+            RequestAccountConfirmationError::NoEmail { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::UserHasNoEmail,
+                    format!("User ID {} has no email address", id),
+                ))
+            }
+            RequestAccountConfirmationError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for an [AccountConfirmation]. The token is only emailed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct AccountConfirmationResponseData {
+    pub user_id: String,
+    pub action: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<&AccountConfirmation> for AccountConfirmationResponseData {
+    fn from(confirmation: &AccountConfirmation) -> Self {
+        Self {
+            user_id: confirmation.user_id().to_string(),
+            action: confirmation.action().to_string(),
+            expires_at: confirmation.expires_at(),
+        }
+    }
+}
+
+/// The body of a request of the user `user_id` to confirm an action on their account, either
+/// `export`, `delete` or `cancel_deletion`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct AccountConfirmationHttpRequestBody {
+    pub user_id: String,
+    pub action: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseAccountConfirmationHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("action {} is invalid", .0 .0)]
+    Action(#[from] AccountActionInvalidError),
+}
+
+impl AccountConfirmationHttpRequestBody {
+    /// Converts the HTTP request body into a domain [AccountConfirmationRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<AccountConfirmationRequest, FieldErrors<ParseAccountConfirmationHttpRequestError>>
+    {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id).map_err(|_| {
+                ParseAccountConfirmationHttpRequestError::UserId(self.user_id.clone())
+            }),
+        );
+        let action = errors.check_field("/action", self.action.parse::<AccountAction>());
+        let (Some(user_id), Some(action)) = (user_id, action) else {
+            return Err(errors);
+        };
+        Ok(AccountConfirmationRequest::new(user_id, action))
+    }
+}
+
+/// Email a user the token confirming an export or a deletion of their account, or the
+/// cancellation of that deletion, to pass to `GET /me/export`, `DELETE /me` or
+/// `DELETE /me/deletion`. The token can be used once, for a limited time; asking again replaces
+/// it.
+///
+/// # Responses
+///
+/// - 202 Accepted: the token was emailed, and expires at the returned time.
+/// - 404 Not found: the user does not exist.
+/// - 422 Unprocessable entity: the user ID or the action is invalid, or the user has no email
+///   address.
+#[utoipa::path(
+    post,
+    path = "/me/confirmations",
+    tag = "account",
+    request_body = AccountConfirmationHttpRequestBody,
+    responses(
+        (status = 202, description = "The token was emailed, and expires at the returned time.", body = ApiResponseBody<AccountConfirmationResponseData>),
+        (status = 404, description = "The user does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID or the action is invalid, or the user has no email address.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn request_account_confirmation<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiJson(body): ApiJson<AccountConfirmationHttpRequestBody>,
+) -> Result<ApiSuccess<AccountConfirmationResponseData>, ApiError> {
+    let domain_req = body.try_into_domain()?;
+    state
+        .services
+        .request_account_confirmation(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref confirmation| ApiSuccess::new(StatusCode::ACCEPTED, confirmation.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{ConfirmationToken, MockAccountService};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_request_account_confirmation_success() {
+        let user_id = Uuid::now_v7();
+        let expires_at: DateTime<Utc> = "2027-01-01T09:30:00Z".parse().unwrap();
+        let confirmation = AccountConfirmation::new(
+            user_id,
+            AccountAction::Delete,
+            &ConfirmationToken::generate(),
+            expires_at,
+        );
+        let expected = ApiSuccess::new(
+            StatusCode::ACCEPTED,
+            AccountConfirmationResponseData {
+                user_id: user_id.to_string(),
+                action: "delete".into(),
+                expires_at,
+            },
+        );
+        let mut mock_account_service = MockAccountService::new();
+        mock_account_service
+            .expect_request_account_confirmation()
+            .withf(move |req| req.user_id() == user_id && req.action() == AccountAction::Delete)
+            .return_once(move |_| Box::pin(future::ready(Ok(confirmation))));
+        let state = test_services()
+            .with_account(mock_account_service)
+            .into_state();
+        let body = ApiJson(AccountConfirmationHttpRequestBody {
+            user_id: user_id.to_string(),
+            action: "delete".into(),
+        });
+
+        let actual = request_account_confirmation(state, body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
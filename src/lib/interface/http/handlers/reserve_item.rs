@@ -0,0 +1,184 @@
+/*
+Module `reserve_item` specifies an HTTP handler for reserving an [Item] to offer it, and the
+associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{Item, ReserveItemError, ReserveItemRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<ReserveItemError> for ApiError {
+    fn from(e: ReserveItemError) -> Self {
+        match e {
+            ReserveItemError::ItemDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ItemNotFound,
+                format!("Item ID {} does not exist", id),
+            )),
+            ReserveItemError::OwnItem => Self::UnprocessableEntity(Problem::new(
+                ProblemType::OwnItemReservation,
+                "owners cannot reserve their own items",
+            )),
+            ReserveItemError::WishlistArchived { id } => Self::Conflict(Problem::new(
+                ProblemType::WishlistArchived,
+                format!("Wishlist ID {} is archived", id),
+            )),
+            ReserveItemError::AlreadyReserved { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ItemAlreadyReserved,
+                format!("Item ID {} is already reserved", id),
+            )),
+            ReserveItemError::NotReserver { id } => Self::NotFound(Problem::new(
+                ProblemType::NotItemReserver,
+                format!("Item ID {} is not reserved by the user", id),
+            )),
+            ReserveItemError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for the reservation of an [Item]. Only the reserver gets it:
+/// reservations are kept from the owner of the [Item].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ReservationResponseData {
+    pub item_id: String,
+    pub reserved_by: Option<String>,
+}
+
+impl From<&Item> for ReservationResponseData {
+    fn from(item: &Item) -> Self {
+        Self {
+            item_id: item.id().to_string(),
+            reserved_by: item.reserved_by().map(|id| id.to_string()),
+        }
+    }
+}
+
+/// The body of a request reserving an [Item], or cancelling the reservation.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct ReserveItemHttpRequestBody {
+    pub user_id: String,
+    pub reserved: bool,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseReserveItemHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl ReserveItemHttpRequestBody {
+    /// Converts the HTTP request body into a domain [ReserveItemRequest].
+    pub fn try_into_domain(
+        self,
+        item_id: Uuid,
+    ) -> Result<ReserveItemRequest, FieldErrors<ParseReserveItemHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseReserveItemHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(ReserveItemRequest::new(item_id, user_id, self.reserved))
+    }
+}
+
+/// Reserve an [Item] of a [Wishlist](crate::domain::Wishlist) the user may see, so no one else
+/// offers it, or cancel the user's reservation. Reserving an [Item] again keeps the reservation.
+///
+/// # Responses
+///
+/// - 200 OK: the reservation of the [Item].
+/// - 404 Not found: the [Item] does not exist, or the user cancels a reservation that is not
+///   theirs.
+/// - 409 Conflict: the [Wishlist](crate::domain::Wishlist) is archived.
+/// - 422 Unprocessable entity: the user ID is invalid, the user owns the [Item], or someone else
+///   reserved it.
+#[utoipa::path(
+    put,
+    path = "/items/{item_id}/reservation",
+    tag = "items",
+    params(
+        ("item_id" = Uuid, Path, description = "The ID of the item."),
+    ),
+    request_body = ReserveItemHttpRequestBody,
+    responses(
+        (status = 200, description = "The reservation of the Item.", body = ApiResponseBody<ReservationResponseData>),
+        (status = 404, description = "The Item does not exist, or the user cancels a reservation that is not theirs.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "The Wishlist is archived.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid, the user owns the Item, or someone else reserved it.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn reserve_item<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<ReserveItemHttpRequestBody>,
+) -> Result<ApiSuccess<ReservationResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(item_id)?;
+    state
+        .services
+        .reserve_item(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref item| ApiSuccess::new(StatusCode::OK, item.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockItemService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reserve_item_success() {
+        let (item_id, user_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut item = Item::create(
+            item_id,
+            Uuid::now_v7(),
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        item.set_reserved_by(Some(user_id));
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ReservationResponseData {
+                item_id: item_id.to_string(),
+                reserved_by: Some(user_id.to_string()),
+            },
+        );
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service
+            .expect_reserve_item()
+            .withf(move |req| {
+                req.item_id() == item_id && req.user_id() == user_id && req.reserved()
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(item))));
+        let state = test_services().with_item(mock_item_service).into_state();
+        let body = ApiJson(ReserveItemHttpRequestBody {
+            user_id: user_id.to_string(),
+            reserved: true,
+        });
+
+        let actual = reserve_item(state, ApiPath(item_id), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,159 @@
+/*
+Module `respond_to_invitation` specifies an HTTP handler for accepting or declining a
+[GroupInvitation](crate::domain::GroupInvitation), and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{RespondToInvitationError, RespondToInvitationRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::invite_member::InvitationResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<RespondToInvitationError> for ApiError {
+    fn from(e: RespondToInvitationError) -> Self {
+        match e {
+            RespondToInvitationError::InvitationDoesNotExist { id } => {
+                Self::NotFound(Problem::new(
+                    ProblemType::InvitationNotFound,
+                    format!("Invitation ID {} does not exist", id),
+                ))
+            }
+            RespondToInvitationError::AlreadyAnswered { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::InvitationAlreadyAnswered,
+                    format!("Invitation ID {} has already been answered", id),
+                ))
+            }
+            RespondToInvitationError::GroupDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::GroupNotFound,
+                format!("Group ID {} does not exist", id),
+            )),
+            RespondToInvitationError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of the answer of `user_id` to an invitation.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct RespondToInvitationHttpRequestBody {
+    pub user_id: String,
+    pub accept: bool,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseRespondToInvitationHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl RespondToInvitationHttpRequestBody {
+    /// Converts the HTTP request body into a domain [RespondToInvitationRequest].
+    pub fn try_into_domain(
+        self,
+        invitation_id: Uuid,
+    ) -> Result<RespondToInvitationRequest, FieldErrors<ParseRespondToInvitationHttpRequestError>>
+    {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id).map_err(|_| {
+                ParseRespondToInvitationHttpRequestError::UserId(self.user_id.clone())
+            }),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(RespondToInvitationRequest::new(
+            invitation_id,
+            user_id,
+            self.accept,
+        ))
+    }
+}
+
+/// Accept or decline an invitation into a [Group](crate::domain::Group). Accepting it makes the
+/// user a member, who then sees the wishlists shared with the group.
+///
+/// # Responses
+///
+/// - 200 OK: the answered invitation.
+/// - 404 Not found: the user has no invitation with that ID, or its group no longer exists.
+/// - 422 Unprocessable entity: the user ID is invalid or the invitation has already been
+///   answered.
+#[utoipa::path(
+    put,
+    path = "/invitations/{invitation_id}",
+    tag = "groups",
+    params(
+        ("invitation_id" = Uuid, Path, description = "The ID of the invitation."),
+    ),
+    request_body = RespondToInvitationHttpRequestBody,
+    responses(
+        (status = 200, description = "The answered invitation.", body = ApiResponseBody<InvitationResponseData>),
+        (status = 404, description = "The user has no invitation with that ID, or its group no longer exists.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid or the invitation has already been answered.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn respond_to_invitation<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(invitation_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<RespondToInvitationHttpRequestBody>,
+) -> Result<ApiSuccess<InvitationResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(invitation_id)?;
+    state
+        .services
+        .respond_to_invitation(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref invitation| ApiSuccess::new(StatusCode::OK, invitation.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockGroupService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_respond_to_answered_invitation() {
+        let invitation_id = Uuid::now_v7();
+        let mut mock_group_service = MockGroupService::new();
+        mock_group_service
+            .expect_respond_to_invitation()
+            .withf(move |req| req.invitation_id() == invitation_id && !req.accept())
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(
+                    RespondToInvitationError::AlreadyAnswered { id: invitation_id },
+                )))
+            });
+        let state = test_services().with_group(mock_group_service).into_state();
+        let body = ApiJson(RespondToInvitationHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            accept: false,
+        });
+
+        let actual = respond_to_invitation(state, ApiPath(invitation_id), body).await;
+        assert_eq!(
+            actual,
+            Err(ApiError::UnprocessableEntity(Problem::new(
+                ProblemType::InvitationAlreadyAnswered,
+                format!("Invitation ID {} has already been answered", invitation_id)
+            )))
+        );
+    }
+}
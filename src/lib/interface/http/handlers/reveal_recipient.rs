@@ -0,0 +1,136 @@
+/*
+Module `reveal_recipient` specifies an HTTP handler for revealing to a participant of a drawn
+gift [Exchange](crate::domain::Exchange) who they give to, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{Reveal, RevealRecipientError};
+use crate::interface::http::problem::{Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::audit_draw::ExchangeActionHttpQuery;
+use super::duplicate_wishlist::WishlistResponseData;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<RevealRecipientError> for ApiError {
+    fn from(e: RevealRecipientError) -> Self {
+        match e {
+            RevealRecipientError::ExchangeDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ExchangeNotFound,
+                format!("Exchange ID {} does not exist", id),
+            )),
+            RevealRecipientError::NotParticipant { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotExchangeParticipant,
+                format!("User ID {} does not participate in the exchange", id),
+            )),
+            RevealRecipientError::NotDrawn { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ExchangeNotDrawn,
+                format!("Exchange ID {} has not been drawn yet", id),
+            )),
+            RevealRecipientError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [Reveal].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RevealResponseData {
+    pub recipient_id: String,
+    pub wishlist: Option<WishlistResponseData>,
+}
+
+impl From<&Reveal> for RevealResponseData {
+    fn from(reveal: &Reveal) -> Self {
+        Self {
+            recipient_id: reveal.recipient_id().to_string(),
+            wishlist: reveal.wishlist().map(WishlistResponseData::from),
+        }
+    }
+}
+
+/// Reveal to a participant who they give a gift to in a drawn
+/// [Exchange](crate::domain::Exchange), along with the wishlist of their recipient. Nobody sees
+/// anyone else's recipient.
+///
+/// # Responses
+///
+/// - 200 OK: the recipient of the user.
+/// - 403 Forbidden: the user does not participate in the exchange.
+/// - 404 Not found: the exchange does not exist.
+/// - 422 Unprocessable entity: the user ID is invalid or the exchange has not been drawn yet.
+#[utoipa::path(
+    get,
+    path = "/exchanges/{exchange_id}/recipient",
+    tag = "exchanges",
+    params(
+        ("exchange_id" = Uuid, Path, description = "The ID of the exchange."),
+        ExchangeActionHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The recipient of the user.", body = ApiResponseBody<RevealResponseData>),
+        (status = 403, description = "The user does not participate in the exchange.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The exchange does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid or the exchange has not been drawn yet.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn reveal_recipient<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(exchange_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<ExchangeActionHttpQuery>,
+) -> Result<ApiSuccess<RevealResponseData>, ApiError> {
+    let domain_req = query.try_into_domain(exchange_id)?;
+    state
+        .services
+        .reveal_recipient(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref reveal| ApiSuccess::new(StatusCode::OK, reveal.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{MockExchangeService, Wishlist, WishlistSlug};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reveal_recipient_success() {
+        let user_id = Uuid::now_v7();
+        let recipient_id = Uuid::now_v7();
+        let wishlist = Wishlist::new(
+            Uuid::now_v7(),
+            recipient_id,
+            "Books".into(),
+            WishlistSlug::from("books"),
+            true,
+        );
+        let reveal = Reveal::new(recipient_id, Some(wishlist));
+        let expected = ApiSuccess::new(StatusCode::OK, RevealResponseData::from(&reveal));
+        let mut mock_exchange_service = MockExchangeService::new();
+        mock_exchange_service
+            .expect_reveal_recipient()
+            .withf(move |req| req.user_id() == user_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(reveal))));
+        let state = test_services()
+            .with_exchange(mock_exchange_service)
+            .into_state();
+        let query = ApiQuery(ExchangeActionHttpQuery {
+            user_id: user_id.to_string(),
+        });
+
+        let actual = reveal_recipient(state, ApiPath(Uuid::now_v7()), query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,122 @@
+/*
+Module `revoke_share_link` specifies an HTTP handler for revoking the share link of a [Wishlist],
+and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::ShareLinkRequest;
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::AppState;
+
+use super::create_share_link::ParseShareLinkHttpRequestError;
+use super::duplicate_wishlist::WishlistResponseData;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+/// The query string of a request revoking the share link of a [Wishlist], e.g. `?user_id=...`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RevokeShareLinkHttpQuery {
+    pub user_id: String,
+}
+
+impl RevokeShareLinkHttpQuery {
+    /// Converts the HTTP query string into a domain [ShareLinkRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<ShareLinkRequest, FieldErrors<ParseShareLinkHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        errors
+            .check_parameter(
+                "user_id",
+                Uuid::parse_str(&self.user_id)
+                    .map_err(|_| ParseShareLinkHttpRequestError::UserId(self.user_id.clone())),
+            )
+            .map(|user_id| ShareLinkRequest::new(wishlist_id, user_id))
+            .ok_or(errors)
+    }
+}
+
+/// Revoke the share link of a [Wishlist]: its page is no longer shown to those who have the link,
+/// unless the [Wishlist] is public.
+///
+/// # Responses
+///
+/// - 200 OK: the updated [Wishlist].
+/// - 403 Forbidden: the user does not own the [Wishlist].
+/// - 404 Not found: the [Wishlist] does not exist.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    delete,
+    path = "/wishlists/{wishlist_id}/share-link",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        RevokeShareLinkHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The updated Wishlist.", body = ApiResponseBody<WishlistResponseData>),
+        (status = 403, description = "The user does not own the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Wishlist does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn revoke_share_link<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<RevokeShareLinkHttpQuery>,
+) -> Result<ApiSuccess<WishlistResponseData>, ApiError> {
+    let domain_req = query.try_into_domain(wishlist_id)?;
+    state
+        .services
+        .revoke_share_link(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref wishlist| ApiSuccess::new(StatusCode::OK, wishlist.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::interface::http::handlers::test_services;
+    use crate::{
+        domain::{MockWishlistService, ShareLinkError},
+        interface::http::problem::{Problem, ProblemType},
+    };
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_revoke_share_link_not_owner() {
+        let (user_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let expected = ApiError::Forbidden(Problem::new(
+            ProblemType::NotWishlistOwner,
+            format!("Wishlist ID {} is not yours", wishlist_id),
+        ));
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_revoke_share_link()
+            .withf(move |req| req.wishlist_id() == wishlist_id && req.user_id() == user_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(ShareLinkError::NotWishlistOwner {
+                    id: wishlist_id,
+                })))
+            });
+        let state = test_services()
+            .with_wishlist(mock_wish_service)
+            .into_state();
+        let query = ApiQuery(RevokeShareLinkHttpQuery {
+            user_id: user_id.to_string(),
+        });
+
+        let actual = revoke_share_link(state, ApiPath(wishlist_id), query).await;
+        assert_eq!(actual, Err(expected));
+    }
+}
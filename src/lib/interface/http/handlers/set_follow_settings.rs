@@ -0,0 +1,143 @@
+/*
+Module `set_follow_settings` specifies an HTTP handler for choosing whether following a user
+needs their approval, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{FollowSettings, SetFollowSettingsError};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
+
+impl From<SetFollowSettingsError> for ApiError {
+    fn from(e: SetFollowSettingsError) -> Self {
+        match e {
+            SetFollowSettingsError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for [FollowSettings].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FollowSettingsResponseData {
+    pub user_id: String,
+    pub private_account: bool,
+}
+
+impl From<&FollowSettings> for FollowSettingsResponseData {
+    fn from(settings: &FollowSettings) -> Self {
+        Self {
+            user_id: settings.user_id().to_string(),
+            private_account: settings.private_account(),
+        }
+    }
+}
+
+/// The body of the follow settings of `user_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct SetFollowSettingsHttpRequestBody {
+    pub user_id: String,
+    pub private_account: bool,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseSetFollowSettingsHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl SetFollowSettingsHttpRequestBody {
+    /// Converts the HTTP request body into domain [FollowSettings].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<FollowSettings, FieldErrors<ParseSetFollowSettingsHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseSetFollowSettingsHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(FollowSettings::new(user_id, self.private_account))
+    }
+}
+
+/// Set the follow settings of a user. Following a private account needs the approval of its
+/// owner; making an account public does not approve the pending requests.
+///
+/// # Responses
+///
+/// - 200 OK: the [FollowSettings].
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    put,
+    path = "/follow-settings",
+    tag = "follows",
+    request_body = SetFollowSettingsHttpRequestBody,
+    responses(
+        (status = 200, description = "The FollowSettings.", body = ApiResponseBody<FollowSettingsResponseData>),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn set_follow_settings<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiJson(body): ApiJson<SetFollowSettingsHttpRequestBody>,
+) -> Result<ApiSuccess<FollowSettingsResponseData>, ApiError> {
+    let settings = body.try_into_domain()?;
+    state
+        .services
+        .set_follow_settings(&settings)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref settings| ApiSuccess::new(StatusCode::OK, settings.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockFollowService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_private_account() {
+        let user_id = Uuid::now_v7();
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            FollowSettingsResponseData {
+                user_id: user_id.to_string(),
+                private_account: true,
+            },
+        );
+        let mut mock_follow_service = MockFollowService::new();
+        mock_follow_service
+            .expect_set_follow_settings()
+            .withf(move |settings| settings.user_id() == user_id && settings.private_account())
+            .return_once(|settings| Box::pin(future::ready(Ok(*settings))));
+        let state = test_services()
+            .with_follow(mock_follow_service)
+            .into_state();
+        let body = ApiJson(SetFollowSettingsHttpRequestBody {
+            user_id: user_id.to_string(),
+            private_account: true,
+        });
+
+        let actual = set_follow_settings(state, body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
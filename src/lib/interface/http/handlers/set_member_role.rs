@@ -0,0 +1,124 @@
+/*
+Module `set_member_role` specifies an HTTP handler for changing the role of a member of a
+[Group](crate::domain::Group), and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{GroupRole, GroupRoleInvalidError, ManageMemberRequest, SetMemberRoleRequest};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::AppState;
+
+use super::create_group::GroupResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+/// The body of a request by the admin `user_id` changing the role of a member, either `admin`
+/// or `member`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct SetMemberRoleHttpRequestBody {
+    pub user_id: String,
+    pub role: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseSetMemberRoleHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("role {} is invalid", .0 .0)]
+    Role(#[from] GroupRoleInvalidError),
+}
+
+impl SetMemberRoleHttpRequestBody {
+    /// Converts the HTTP request body into a domain [SetMemberRoleRequest].
+    pub fn try_into_domain(
+        self,
+        group_id: Uuid,
+        member_id: Uuid,
+    ) -> Result<SetMemberRoleRequest, FieldErrors<ParseSetMemberRoleHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseSetMemberRoleHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let role = errors.check_field("/role", self.role.parse::<GroupRole>());
+        let (Some(user_id), Some(role)) = (user_id, role) else {
+            return Err(errors);
+        };
+        Ok(SetMemberRoleRequest::new(
+            ManageMemberRequest::new(group_id, user_id, member_id),
+            role,
+        ))
+    }
+}
+
+/// Change the role of a member of a [Group](crate::domain::Group). A group always keeps at least
+/// one admin.
+///
+/// # Responses
+///
+/// - 200 OK: the updated group.
+/// - 403 Forbidden: the user is not an admin of the group.
+/// - 404 Not found: the group does not exist or the member does not belong to it.
+/// - 422 Unprocessable entity: the user ID or the role is invalid, or the member is the last
+///   admin.
+#[utoipa::path(
+    put,
+    path = "/groups/{group_id}/members/{member_id}/role",
+    tag = "groups",
+    params(
+        ("group_id" = Uuid, Path, description = "The ID of the group."),
+        ("member_id" = Uuid, Path, description = "The ID of the member."),
+    ),
+    request_body = SetMemberRoleHttpRequestBody,
+    responses(
+        (status = 200, description = "The updated group.", body = ApiResponseBody<GroupResponseData>),
+        (status = 403, description = "The user is not an admin of the group.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The group does not exist or the member does not belong to it.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID or the role is invalid, or the member is the last admin.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn set_member_role<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath((group_id, member_id)): ApiPath<(Uuid, Uuid)>,
+    ApiJson(body): ApiJson<SetMemberRoleHttpRequestBody>,
+) -> Result<ApiSuccess<GroupResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(group_id, member_id)?;
+    state
+        .services
+        .set_member_role(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref group| ApiSuccess::new(StatusCode::OK, group.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+    use crate::interface::http::problem::{InvalidField, Problem};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_member_role_invalid_role() {
+        let state = test_services().into_state();
+        let body = ApiJson(SetMemberRoleHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            role: "owner".to_string(),
+        });
+
+        let actual = set_member_role(state, ApiPath((Uuid::now_v7(), Uuid::now_v7())), body).await;
+        assert_eq!(
+            actual,
+            Err(ApiError::UnprocessableEntity(Problem::invalid_fields(
+                vec![InvalidField::body("/role", "role owner is invalid")]
+            )))
+        );
+    }
+}
@@ -0,0 +1,352 @@
+/*
+Module `set_profile` specifies an HTTP handler for setting the [Profile] of a user, and the
+profile data structures shared with the other profile handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    DisplayName, DisplayNameInvalidError, Profile, ProfileVisibility,
+    ProfileVisibilityInvalidError, SetProfileError, ShippingAddress, ShippingAddressInvalidError,
+    StoredImage,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::upload_image::UploadImageResponseData;
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
+
+impl From<SetProfileError> for ApiError {
+    fn from(e: SetProfileError) -> Self {
+        match e {
+            SetProfileError::UserDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::UserNotFound,
+                format!("User ID {} does not exist", id),
+            )),
+            SetProfileError::AvatarDoesNotExist { id } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::ImageNotFound,
+                format!("Image ID {} does not exist", id),
+            )),
+            SetProfileError::BirthdayInFuture { date } => Self::UnprocessableEntity(Problem::new(
+                ProblemType::BirthdayInFuture,
+                format!("birthday {} is in the future", date),
+            )),
+            SetProfileError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// A shipping address, in requests and responses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ShippingAddressData {
+    pub recipient: String,
+    pub line1: String,
+    pub line2: Option<String>,
+    pub city: String,
+    pub postal_code: String,
+    pub country: String,
+}
+
+impl From<&ShippingAddress> for ShippingAddressData {
+    fn from(address: &ShippingAddress) -> Self {
+        Self {
+            recipient: address.recipient().to_string(),
+            line1: address.line1().to_string(),
+            line2: address.line2().map(ToString::to_string),
+            city: address.city().to_string(),
+            postal_code: address.postal_code().to_string(),
+            country: address.country().to_string(),
+        }
+    }
+}
+
+impl ShippingAddressData {
+    fn try_into_domain(self) -> Result<ShippingAddress, ShippingAddressInvalidError> {
+        ShippingAddress::new(
+            &self.recipient,
+            &self.line1,
+            self.line2.as_deref(),
+            &self.city,
+            &self.postal_code,
+            &self.country,
+        )
+    }
+}
+
+/// Who may see each field of a [Profile]: `everyone`, `followers`, `reservers` or `nobody`.
+/// The shipping address is only ever visible to `reservers` or `nobody`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ProfileVisibilityData {
+    pub display_name: String,
+    pub avatar: String,
+    pub birthday: String,
+    pub shipping_address: String,
+}
+
+impl From<ProfileVisibility> for ProfileVisibilityData {
+    fn from(visibility: ProfileVisibility) -> Self {
+        Self {
+            display_name: visibility.display_name().to_string(),
+            avatar: visibility.avatar().to_string(),
+            birthday: visibility.birthday().to_string(),
+            shipping_address: visibility.shipping_address().to_string(),
+        }
+    }
+}
+
+impl ProfileVisibilityData {
+    /// Converts the visibilities into a domain [ProfileVisibility], recording the invalid ones
+    /// in `errors`.
+    fn try_into_domain(
+        self,
+        errors: &mut FieldErrors<ParseSetProfileHttpRequestError>,
+    ) -> Option<ProfileVisibility> {
+        let display_name =
+            errors.check_field("/visibility/display_name", self.display_name.parse());
+        let avatar = errors.check_field("/visibility/avatar", self.avatar.parse());
+        let birthday = errors.check_field("/visibility/birthday", self.birthday.parse());
+        let shipping_address = errors.check_field(
+            "/visibility/shipping_address",
+            self.shipping_address.parse(),
+        );
+        let (Some(display_name), Some(avatar), Some(birthday), Some(shipping_address)) =
+            (display_name, avatar, birthday, shipping_address)
+        else {
+            return None;
+        };
+        errors.check_field(
+            "/visibility/shipping_address",
+            ProfileVisibility::new(display_name, avatar, birthday, shipping_address),
+        )
+    }
+}
+
+/// The response body data field for a [Profile]. The fields the caller may not see are `null`,
+/// and `visibility` is only given to the owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ProfileResponseData {
+    pub user_id: String,
+    pub display_name: Option<String>,
+    pub avatar: Option<UploadImageResponseData>,
+    pub birthday: Option<NaiveDate>,
+    pub shipping_address: Option<ShippingAddressData>,
+    pub visibility: Option<ProfileVisibilityData>,
+}
+
+impl ProfileResponseData {
+    /// Builds the response data for `profile` as seen by `user_id`.
+    pub fn for_user(profile: &Profile, user_id: Uuid) -> Self {
+        Self {
+            user_id: profile.user_id().to_string(),
+            display_name: profile.display_name().map(ToString::to_string),
+            avatar: profile
+                .avatar_id()
+                .map(|id| UploadImageResponseData::from(&StoredImage::new(id))),
+            birthday: profile.birthday(),
+            shipping_address: profile.shipping_address().map(ShippingAddressData::from),
+            visibility: (profile.user_id() == user_id).then(|| profile.visibility().into()),
+        }
+    }
+}
+
+/// The body of the [Profile] of `user_id`. Omitted fields are cleared, and `avatar_id` refers to
+/// an image previously uploaded to `/api/images`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct SetProfileHttpRequestBody {
+    pub user_id: String,
+    pub display_name: Option<String>,
+    pub avatar_id: Option<Uuid>,
+    pub birthday: Option<String>,
+    pub shipping_address: Option<ShippingAddressData>,
+    pub visibility: Option<ProfileVisibilityData>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseSetProfileHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("birthday {0} is invalid")]
+    Birthday(String),
+    #[error(transparent)]
+    DisplayName(#[from] DisplayNameInvalidError),
+    #[error(transparent)]
+    ShippingAddress(#[from] ShippingAddressInvalidError),
+    #[error(transparent)]
+    Visibility(#[from] ProfileVisibilityInvalidError),
+}
+
+impl SetProfileHttpRequestBody {
+    /// Converts the HTTP request body into a domain [Profile].
+    pub fn try_into_domain(self) -> Result<Profile, FieldErrors<ParseSetProfileHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseSetProfileHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let display_name = errors.check_field(
+            "/display_name",
+            self.display_name
+                .as_deref()
+                .map(DisplayName::new)
+                .transpose(),
+        );
+        let birthday = errors.check_field(
+            "/birthday",
+            self.birthday
+                .as_deref()
+                .map(|birthday| {
+                    birthday.parse::<NaiveDate>().map_err(|_| {
+                        ParseSetProfileHttpRequestError::Birthday(birthday.to_string())
+                    })
+                })
+                .transpose(),
+        );
+        let shipping_address = match self
+            .shipping_address
+            .map(ShippingAddressData::try_into_domain)
+            .transpose()
+        {
+            Ok(address) => Some(address),
+            Err(err) => errors.check_field(format!("/shipping_address/{}", err.0), Err(err)),
+        };
+        let visibility = match self.visibility {
+            Some(visibility) => visibility.try_into_domain(&mut errors),
+            None => Some(ProfileVisibility::default()),
+        };
+        let (
+            Some(user_id),
+            Some(display_name),
+            Some(birthday),
+            Some(shipping_address),
+            Some(visibility),
+        ) = (
+            user_id,
+            display_name,
+            birthday,
+            shipping_address,
+            visibility,
+        )
+        else {
+            return Err(errors);
+        };
+        Ok(Profile::new(user_id)
+            .with_display_name(display_name)
+            .with_avatar_id(self.avatar_id)
+            .with_birthday(birthday)
+            .with_shipping_address(shipping_address)
+            .with_visibility(visibility))
+    }
+}
+
+/// Set the [Profile] of a user, replacing the previous one. Its birthday feeds the occasions
+/// suggested to the user, and its shipping address is only shown to the users who reserved one
+/// of their items.
+///
+/// # Responses
+///
+/// - 200 OK: the [Profile], with its visibility.
+/// - 404 Not found: the user does not exist.
+/// - 422 Unprocessable entity: a field is invalid, the avatar is not a stored image, or the
+///   birthday is in the future.
+#[utoipa::path(
+    put,
+    path = "/profile",
+    tag = "profiles",
+    request_body = SetProfileHttpRequestBody,
+    responses(
+        (status = 200, description = "The Profile, with its visibility.", body = ApiResponseBody<ProfileResponseData>),
+        (status = 404, description = "The user does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "A field is invalid, the avatar is not a stored image, or the birthday is in the future.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn set_profile<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiJson(body): ApiJson<SetProfileHttpRequestBody>,
+) -> Result<ApiSuccess<ProfileResponseData>, ApiError> {
+    let profile = body.try_into_domain()?;
+    state
+        .services
+        .set_profile(&profile)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref profile| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                ProfileResponseData::for_user(profile, profile.user_id()),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{FieldVisibility, MockProfileService};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_profile_success() {
+        let (user_id, avatar_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let address = ShippingAddressData {
+            recipient: "Alice".into(),
+            line1: "1 Main St".into(),
+            line2: None,
+            city: "Paris".into(),
+            postal_code: "75001".into(),
+            country: "FR".into(),
+        };
+        let visibility = ProfileVisibilityData {
+            display_name: "everyone".into(),
+            avatar: "followers".into(),
+            birthday: "nobody".into(),
+            shipping_address: "reservers".into(),
+        };
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ProfileResponseData {
+                user_id: user_id.to_string(),
+                display_name: Some("Alice".into()),
+                avatar: Some(UploadImageResponseData::from(&StoredImage::new(avatar_id))),
+                birthday: Some("1990-03-14".parse().unwrap()),
+                shipping_address: Some(address.clone()),
+                visibility: Some(visibility.clone()),
+            },
+        );
+        let mut mock_profile_service = MockProfileService::new();
+        mock_profile_service
+            .expect_set_profile()
+            .withf(move |profile| {
+                profile.user_id() == user_id
+                    && profile.avatar_id() == Some(avatar_id)
+                    && profile.visibility().avatar() == FieldVisibility::Followers
+            })
+            .return_once(|profile| Box::pin(future::ready(Ok(profile.clone()))));
+        let state = test_services()
+            .with_profile(mock_profile_service)
+            .into_state();
+        let body = ApiJson(SetProfileHttpRequestBody {
+            user_id: user_id.to_string(),
+            display_name: Some(" Alice ".into()),
+            avatar_id: Some(avatar_id),
+            birthday: Some("1990-03-14".into()),
+            shipping_address: Some(address),
+            visibility: Some(visibility),
+        });
+
+        let actual = set_profile(state, body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,232 @@
+/*
+Module `set_reminder_preferences` specifies an HTTP handler for setting the time zone and quiet
+hours in which a user receives reminders, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::NaiveTime;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    QuietHours, QuietHoursInvalidError, ReminderPreferences, SetReminderPreferencesError,
+};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiResponseBody, ApiSuccess};
+
+impl From<SetReminderPreferencesError> for ApiError {
+    fn from(e: SetReminderPreferencesError) -> Self {
+        match e {
+            SetReminderPreferencesError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// Quiet hours in local time, e.g. `{"start": "22:00", "end": "08:00"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct QuietHoursData {
+    pub start: String,
+    pub end: String,
+}
+
+/// The response body data field for [ReminderPreferences].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ReminderPreferencesResponseData {
+    pub user_id: String,
+    pub time_zone: String,
+    pub quiet_hours: Option<QuietHoursData>,
+}
+
+impl From<&ReminderPreferences> for ReminderPreferencesResponseData {
+    fn from(preferences: &ReminderPreferences) -> Self {
+        Self {
+            user_id: preferences.user_id().to_string(),
+            time_zone: preferences.time_zone().name().to_string(),
+            quiet_hours: preferences.quiet_hours().map(|quiet_hours| QuietHoursData {
+                start: quiet_hours.start().format("%H:%M").to_string(),
+                end: quiet_hours.end().format("%H:%M").to_string(),
+            }),
+        }
+    }
+}
+
+/// The body of a request setting the reminder preferences of a user.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct SetReminderPreferencesHttpRequestBody {
+    pub user_id: String,
+    pub time_zone: String,
+    pub quiet_hours: Option<QuietHoursData>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseSetReminderPreferencesHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("time zone {0} is invalid")]
+    TimeZone(String),
+    #[error("time {0} is invalid")]
+    Time(String),
+    #[error("quiet hours from {} to {} are invalid", .0.start, .0.end)]
+    QuietHours(#[from] QuietHoursInvalidError),
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, ParseSetReminderPreferencesHttpRequestError> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .map_err(|_| ParseSetReminderPreferencesHttpRequestError::Time(value.to_string()))
+}
+
+impl SetReminderPreferencesHttpRequestBody {
+    /// Converts the HTTP request body into domain [ReminderPreferences].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<ReminderPreferences, FieldErrors<ParseSetReminderPreferencesHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id).map_err(|_| {
+                ParseSetReminderPreferencesHttpRequestError::UserId(self.user_id.clone())
+            }),
+        );
+        let time_zone = errors.check_field(
+            "/time_zone",
+            self.time_zone.parse::<Tz>().map_err(|_| {
+                ParseSetReminderPreferencesHttpRequestError::TimeZone(self.time_zone.clone())
+            }),
+        );
+        let quiet_hours = match self.quiet_hours {
+            Some(quiet_hours) => {
+                let start =
+                    errors.check_field("/quiet_hours/start", parse_time(&quiet_hours.start));
+                let end = errors.check_field("/quiet_hours/end", parse_time(&quiet_hours.end));
+                match (start, end) {
+                    (Some(start), Some(end)) => errors
+                        .check_field("/quiet_hours", QuietHours::new(start, end))
+                        .map(Some),
+                    _ => None,
+                }
+            }
+            None => Some(None),
+        };
+        let (Some(user_id), Some(time_zone), Some(quiet_hours)) = (user_id, time_zone, quiet_hours)
+        else {
+            return Err(errors);
+        };
+        Ok(ReminderPreferences::new(user_id, time_zone, quiet_hours))
+    }
+}
+
+/// Set the time zone in which reminders are scheduled for a user, and the quiet hours during
+/// which they are held back. Users without preferences are reminded at midnight UTC.
+///
+/// # Responses
+///
+/// - 200 OK: the saved [ReminderPreferences].
+/// - 422 Unprocessable entity: the user ID, time zone or quiet hours are invalid.
+#[utoipa::path(
+    put,
+    path = "/reminder-preferences",
+    tag = "notifications",
+    request_body = SetReminderPreferencesHttpRequestBody,
+    responses(
+        (status = 200, description = "The saved ReminderPreferences.", body = ApiResponseBody<ReminderPreferencesResponseData>),
+        (status = 422, description = "The user ID, time zone or quiet hours are invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn set_reminder_preferences<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiJson(body): ApiJson<SetReminderPreferencesHttpRequestBody>,
+) -> Result<ApiSuccess<ReminderPreferencesResponseData>, ApiError> {
+    let preferences = body.try_into_domain()?;
+    state
+        .services
+        .set_reminder_preferences(&preferences)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref preferences| ApiSuccess::new(StatusCode::OK, preferences.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockNotificationService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+    use crate::interface::http::problem::InvalidField;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_reminder_preferences_success() {
+        let user_id = Uuid::now_v7();
+        let body = SetReminderPreferencesHttpRequestBody {
+            user_id: user_id.to_string(),
+            time_zone: "Asia/Tokyo".into(),
+            quiet_hours: Some(QuietHoursData {
+                start: "22:00".into(),
+                end: "08:00".into(),
+            }),
+        };
+        let expected = ApiSuccess::new(
+            StatusCode::OK,
+            ReminderPreferencesResponseData {
+                user_id: user_id.to_string(),
+                time_zone: "Asia/Tokyo".into(),
+                quiet_hours: body.quiet_hours.clone(),
+            },
+        );
+        let mut mock_notification_service = MockNotificationService::new();
+        mock_notification_service
+            .expect_set_reminder_preferences()
+            .withf(|preferences| preferences.time_zone() == chrono_tz::Asia::Tokyo)
+            .return_once(|preferences| Box::pin(future::ready(Ok(preferences.clone()))));
+        let state = test_services()
+            .with_notification(mock_notification_service)
+            .into_state();
+
+        let actual = set_reminder_preferences(state, ApiJson(body)).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_invalid_quiet_hours() {
+        let body = |start: &str, end: &str| SetReminderPreferencesHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            time_zone: "Europe/Paris".into(),
+            quiet_hours: Some(QuietHoursData {
+                start: start.into(),
+                end: end.into(),
+            }),
+        };
+        assert_eq!(
+            body("22:00", "22:00")
+                .try_into_domain()
+                .unwrap_err()
+                .into_invalid_fields(),
+            vec![InvalidField::body(
+                "/quiet_hours",
+                "quiet hours from 22:00:00 to 22:00:00 are invalid"
+            )]
+        );
+        assert_eq!(
+            body("25:00", "08:00")
+                .try_into_domain()
+                .unwrap_err()
+                .into_invalid_fields(),
+            vec![InvalidField::body(
+                "/quiet_hours/start",
+                "time 25:00 is invalid"
+            )]
+        );
+    }
+}
@@ -0,0 +1,318 @@
+/*
+Module `set_wishlist_occasion` specifies an HTTP handler for setting the occasion a [Wishlist] is
+for, and the occasion data structures shared with the other [Wishlist] handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    Occasion, OccasionInvalidError, OccasionKind, Recurrence, SetWishlistOccasionError,
+    SetWishlistOccasionRequest,
+};
+use crate::interface::http::conditional::{EntityTag, IfMatch};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::duplicate_wishlist::WishlistResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<SetWishlistOccasionError> for ApiError {
+    fn from(e: SetWishlistOccasionError) -> Self {
+        match e {
+            SetWishlistOccasionError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            SetWishlistOccasionError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            SetWishlistOccasionError::VersionMismatch(err) => err.into(),
+            SetWishlistOccasionError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for an [Occasion], with a countdown to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct OccasionResponseData {
+    pub kind: String,
+    pub date: NaiveDate,
+    pub time_zone: String,
+    pub recurrence: Option<String>,
+    pub countdown: CountdownResponseData,
+}
+
+/// How far away an [Occasion] is: `days_remaining` is 0 on the day and negative once it passed,
+/// counted in the time zone of the occasion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct CountdownResponseData {
+    pub days_remaining: i64,
+    pub ends_at: DateTime<Utc>,
+    pub passed: bool,
+}
+
+impl OccasionResponseData {
+    /// Builds the response data for `occasion`, counting down from `now`.
+    pub fn at(occasion: &Occasion, now: DateTime<Utc>) -> Self {
+        Self {
+            kind: occasion.kind().to_string(),
+            date: occasion.date(),
+            time_zone: occasion.time_zone().name().to_string(),
+            recurrence: occasion
+                .recurrence()
+                .map(|recurrence| recurrence.to_string()),
+            countdown: CountdownResponseData {
+                days_remaining: occasion.days_until(now),
+                ends_at: occasion.ends_at(),
+                passed: occasion.is_over(now),
+            },
+        }
+    }
+}
+
+impl From<&Occasion> for OccasionResponseData {
+    fn from(occasion: &Occasion) -> Self {
+        Self::at(occasion, Utc::now())
+    }
+}
+
+/// The occasion in the body of a [SetWishlistOccasionHttpRequestBody], e.g.
+/// `{"kind": "birthday", "date": "2027-03-14", "time_zone": "Europe/Paris", "recurrence": "yearly"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct OccasionHttpRequestBody {
+    pub kind: String,
+    pub date: String,
+    pub time_zone: String,
+    pub recurrence: Option<String>,
+}
+
+impl OccasionHttpRequestBody {
+    /// Converts the occasion into a domain [Occasion], recording its invalid fields in `errors`.
+    fn try_into_domain(
+        self,
+        errors: &mut FieldErrors<ParseSetWishlistOccasionHttpRequestError>,
+    ) -> Option<Occasion> {
+        let kind = errors.check_field("/occasion/kind", self.kind.parse::<OccasionKind>());
+        let date = errors.check_field(
+            "/occasion/date",
+            self.date
+                .parse::<NaiveDate>()
+                .map_err(|_| OccasionInvalidError::Date(self.date.clone())),
+        );
+        let time_zone = errors.check_field(
+            "/occasion/time_zone",
+            self.time_zone
+                .parse::<Tz>()
+                .map_err(|_| OccasionInvalidError::TimeZone(self.time_zone.clone())),
+        );
+        let recurrence = errors.check_field(
+            "/occasion/recurrence",
+            self.recurrence
+                .as_deref()
+                .map(str::parse::<Recurrence>)
+                .transpose(),
+        );
+        let (Some(kind), Some(date), Some(time_zone), Some(recurrence)) =
+            (kind, date, time_zone, recurrence)
+        else {
+            return None;
+        };
+        Some(Occasion::new(kind, date, time_zone, recurrence))
+    }
+}
+
+/// The body of a request setting the occasion of a [Wishlist]; a `null` occasion clears it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct SetWishlistOccasionHttpRequestBody {
+    pub user_id: String,
+    pub occasion: Option<OccasionHttpRequestBody>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseSetWishlistOccasionHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error(transparent)]
+    Occasion(#[from] OccasionInvalidError),
+}
+
+impl SetWishlistOccasionHttpRequestBody {
+    /// Converts the HTTP request body into a domain [SetWishlistOccasionRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<SetWishlistOccasionRequest, FieldErrors<ParseSetWishlistOccasionHttpRequestError>>
+    {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id).map_err(|_| {
+                ParseSetWishlistOccasionHttpRequestError::UserId(self.user_id.clone())
+            }),
+        );
+        let occasion = match self.occasion {
+            Some(occasion) => occasion.try_into_domain(&mut errors).map(Some),
+            None => Some(None),
+        };
+        let (Some(user_id), Some(occasion)) = (user_id, occasion) else {
+            return Err(errors);
+        };
+        Ok(SetWishlistOccasionRequest::new(
+            wishlist_id,
+            user_id,
+            occasion,
+        ))
+    }
+}
+
+/// Set the occasion a [Wishlist] is for, or clear it. Once the occasion is over, the [Wishlist]
+/// is archived or, if the occasion recurs, reopened for the next one without the received items.
+/// Setting an occasion reopens an archived [Wishlist].
+///
+/// # Responses
+///
+/// - 200 OK: the updated [Wishlist], with a countdown to its occasion.
+/// - 403 Forbidden: the user does not own the [Wishlist].
+/// - 404 Not found: the [Wishlist] does not exist.
+/// - 412 Precondition failed: the [Wishlist] changed since the version in `If-Match`.
+/// - 422 Unprocessable entity: the user ID or the occasion is invalid.
+#[utoipa::path(
+    put,
+    path = "/wishlists/{wishlist_id}/occasion",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        ("If-Match" = Option<String>, Header, description = "The ETag of the Wishlist as last read; the change only applies to that version."),
+    ),
+    request_body = SetWishlistOccasionHttpRequestBody,
+    responses(
+        (status = 200, description = "The updated Wishlist, with a countdown to its occasion.", body = ApiResponseBody<WishlistResponseData>),
+        (status = 403, description = "The user does not own the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Wishlist does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "The Wishlist changed since the version in If-Match.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID or the occasion is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn set_wishlist_occasion<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(body): ApiJson<SetWishlistOccasionHttpRequestBody>,
+) -> Result<ApiSuccess<WishlistResponseData>, ApiError> {
+    let domain_req = body
+        .try_into_domain(wishlist_id)?
+        .with_expected_version(expected_version);
+    state
+        .services
+        .set_wishlist_occasion(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref wishlist| {
+            ApiSuccess::new(StatusCode::OK, wishlist.into())
+                .with_etag(EntityTag::version(wishlist.version()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::{MockWishlistService, OccasionKind, Recurrence, Wishlist};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    fn state(wish_service: MockWishlistService) -> State<AppState<impl UseCases>> {
+        let service = test_services().with_wishlist(wish_service).build();
+        State(AppState {
+            services: Arc::new(service),
+        })
+    }
+
+    fn body(time_zone: &str) -> ApiJson<SetWishlistOccasionHttpRequestBody> {
+        ApiJson(SetWishlistOccasionHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            occasion: Some(OccasionHttpRequestBody {
+                kind: "birthday".to_string(),
+                date: "2027-03-14".to_string(),
+                time_zone: time_zone.to_string(),
+                recurrence: Some("yearly".to_string()),
+            }),
+        })
+    }
+
+    #[test]
+    fn test_occasion_countdown() {
+        let occasion = Occasion::new(
+            OccasionKind::Birthday,
+            "2027-03-14".parse().unwrap(),
+            chrono_tz::Asia::Tokyo,
+            None,
+        );
+        let data = OccasionResponseData::at(&occasion, "2027-03-10T16:00:00Z".parse().unwrap());
+        assert_eq!(data.time_zone, "Asia/Tokyo");
+        assert_eq!(data.countdown.days_remaining, 3);
+        assert_eq!(
+            data.countdown.ends_at,
+            "2027-03-14T15:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert!(!data.countdown.passed);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_wishlist_occasion_success() {
+        let id = Uuid::now_v7();
+        let mut wishlist = Wishlist::new(id, id, "Birthday".into(), "Birthday".into(), false);
+        wishlist.set_occasion(Some(Occasion::new(
+            OccasionKind::Birthday,
+            "2027-03-14".parse().unwrap(),
+            chrono_tz::Europe::Paris,
+            Some(Recurrence::Yearly),
+        )));
+        let expected = ApiSuccess::new(StatusCode::OK, WishlistResponseData::from(&wishlist))
+            .with_etag(EntityTag::version(wishlist.version()));
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_set_wishlist_occasion()
+            .withf(|req| {
+                req.occasion().is_some_and(|occasion| {
+                    occasion.time_zone() == chrono_tz::Europe::Paris
+                        && occasion.recurrence() == Some(Recurrence::Yearly)
+                })
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(wishlist))));
+
+        let actual = set_wishlist_occasion(
+            state(mock_wish_service),
+            ApiPath(Uuid::now_v7()),
+            IfMatch::default(),
+            body("Europe/Paris"),
+        )
+        .await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_wishlist_occasion_invalid_time_zone() {
+        let actual = set_wishlist_occasion(
+            state(MockWishlistService::new()),
+            ApiPath(Uuid::now_v7()),
+            IfMatch::default(),
+            body("Europe/Atlantis"),
+        )
+        .await;
+        assert!(matches!(actual, Err(ApiError::UnprocessableEntity(_))));
+    }
+}
@@ -0,0 +1,186 @@
+/*
+Module `set_wishlist_template` specifies an HTTP handler for marking a [Wishlist] as a template,
+and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{SetWishlistTemplateError, SetWishlistTemplateRequest};
+use crate::interface::http::conditional::{EntityTag, IfMatch};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::duplicate_wishlist::WishlistResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<SetWishlistTemplateError> for ApiError {
+    fn from(e: SetWishlistTemplateError) -> Self {
+        match e {
+            SetWishlistTemplateError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            SetWishlistTemplateError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} is not yours", id),
+            )),
+            SetWishlistTemplateError::VersionMismatch(err) => err.into(),
+            SetWishlistTemplateError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of a request marking a [Wishlist] as a template, or unmarking it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct SetWishlistTemplateHttpRequestBody {
+    pub user_id: String,
+    pub template: bool,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseSetWishlistTemplateHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl SetWishlistTemplateHttpRequestBody {
+    /// Converts the HTTP request body into a domain [SetWishlistTemplateRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<SetWishlistTemplateRequest, FieldErrors<ParseSetWishlistTemplateHttpRequestError>>
+    {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id).map_err(|_| {
+                ParseSetWishlistTemplateHttpRequestError::UserId(self.user_id.clone())
+            }),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(SetWishlistTemplateRequest::new(
+            wishlist_id,
+            user_id,
+            self.template,
+        ))
+    }
+}
+
+/// Mark a [Wishlist] as a template anyone can start a [Wishlist] from, or unmark it.
+///
+/// # Responses
+///
+/// - 200 OK: the updated [Wishlist].
+/// - 403 Forbidden: the user does not own the [Wishlist].
+/// - 404 Not found: the [Wishlist] does not exist.
+/// - 412 Precondition failed: the [Wishlist] changed since the version in `If-Match`.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    put,
+    path = "/wishlists/{wishlist_id}/template",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        ("If-Match" = Option<String>, Header, description = "The ETag of the Wishlist as last read; the change only applies to that version."),
+    ),
+    request_body = SetWishlistTemplateHttpRequestBody,
+    responses(
+        (status = 200, description = "The updated Wishlist.", body = ApiResponseBody<WishlistResponseData>),
+        (status = 403, description = "The user does not own the Wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The Wishlist does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "The Wishlist changed since the version in If-Match.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn set_wishlist_template<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    IfMatch(expected_version): IfMatch,
+    ApiJson(body): ApiJson<SetWishlistTemplateHttpRequestBody>,
+) -> Result<ApiSuccess<WishlistResponseData>, ApiError> {
+    let domain_req = body
+        .try_into_domain(wishlist_id)?
+        .with_expected_version(expected_version);
+    state
+        .services
+        .set_wishlist_template(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref wishlist| {
+            ApiSuccess::new(StatusCode::OK, wishlist.into())
+                .with_etag(EntityTag::version(wishlist.version()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{MockWishlistService, VersionMismatchError};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_wishlist_template_not_owner() {
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_set_wishlist_template()
+            .return_once(|req| {
+                let id = req.wishlist_id();
+                Box::pin(future::ready(Err(
+                    SetWishlistTemplateError::NotWishlistOwner { id },
+                )))
+            });
+        let state = test_services()
+            .with_wishlist(mock_wish_service)
+            .into_state();
+        let body = ApiJson(SetWishlistTemplateHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            template: true,
+        });
+
+        let actual =
+            set_wishlist_template(state, ApiPath(Uuid::now_v7()), IfMatch::default(), body).await;
+        assert!(matches!(actual, Err(ApiError::Forbidden(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_set_wishlist_template_version_mismatch() {
+        let mut mock_wish_service = MockWishlistService::new();
+        mock_wish_service
+            .expect_set_wishlist_template()
+            .withf(|req| req.expected_version() == Some(3))
+            .return_once(|req| {
+                let id = req.wishlist_id();
+                Box::pin(future::ready(Err(VersionMismatchError {
+                    id,
+                    expected: 3,
+                    actual: 4,
+                }
+                .into())))
+            });
+        let state = test_services()
+            .with_wishlist(mock_wish_service)
+            .into_state();
+        let body = ApiJson(SetWishlistTemplateHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            template: true,
+        });
+
+        let actual =
+            set_wishlist_template(state, ApiPath(Uuid::now_v7()), IfMatch(Some(3)), body).await;
+        assert!(matches!(actual, Err(ApiError::PreconditionFailed(_))));
+    }
+}
@@ -0,0 +1,168 @@
+/*
+Module `share_wishlist` specifies an HTTP handler for sharing a wishlist with a
+[Group](crate::domain::Group), and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{ShareWishlistError, ShareWishlistRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_group::GroupResponseData;
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<ShareWishlistError> for ApiError {
+    fn from(e: ShareWishlistError) -> Self {
+        match e {
+            ShareWishlistError::GroupDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::GroupNotFound,
+                format!("Group ID {} does not exist", id),
+            )),
+            ShareWishlistError::NotMember { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotGroupMember,
+                format!("User ID {} is not a member of the group", id),
+            )),
+            ShareWishlistError::WishlistDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::WishlistNotFound,
+                    format!("Wishlist ID {} does not exist", id),
+                ))
+            }
+            ShareWishlistError::NotWishlistOwner { id } => Self::Forbidden(Problem::new(
+                ProblemType::NotWishlistOwner,
+                format!("Wishlist ID {} does not belong to the user", id),
+            )),
+            ShareWishlistError::NotShared { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotShared,
+                format!("Wishlist ID {} is not shared with the group", id),
+            )),
+            ShareWishlistError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The body of a request by `user_id` sharing one of their wishlists with a group.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct ShareWishlistHttpRequestBody {
+    pub user_id: String,
+    pub wishlist_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseShareWishlistHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("wishlist id {0} is invalid")]
+    WishlistId(String),
+}
+
+impl ShareWishlistHttpRequestBody {
+    /// Converts the HTTP request body into a domain [ShareWishlistRequest].
+    pub fn try_into_domain(
+        self,
+        group_id: Uuid,
+    ) -> Result<ShareWishlistRequest, FieldErrors<ParseShareWishlistHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseShareWishlistHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let wishlist_id = errors.check_field(
+            "/wishlist_id",
+            Uuid::parse_str(&self.wishlist_id).map_err(|_| {
+                ParseShareWishlistHttpRequestError::WishlistId(self.wishlist_id.clone())
+            }),
+        );
+        let (Some(user_id), Some(wishlist_id)) = (user_id, wishlist_id) else {
+            return Err(errors);
+        };
+        Ok(ShareWishlistRequest::new(group_id, user_id, wishlist_id))
+    }
+}
+
+/// Share a wishlist with a [Group](crate::domain::Group). Every member sees it, including those
+/// who join later, for as long as its owner belongs to the group.
+///
+/// # Responses
+///
+/// - 200 OK: the updated group.
+/// - 403 Forbidden: the user is not a member of the group or does not own the wishlist.
+/// - 404 Not found: the group does not exist.
+/// - 422 Unprocessable entity: an ID is invalid or the wishlist does not exist.
+#[utoipa::path(
+    post,
+    path = "/groups/{group_id}/wishlists",
+    tag = "groups",
+    params(
+        ("group_id" = Uuid, Path, description = "The ID of the group."),
+    ),
+    request_body = ShareWishlistHttpRequestBody,
+    responses(
+        (status = 200, description = "The updated group.", body = ApiResponseBody<GroupResponseData>),
+        (status = 403, description = "The user is not a member of the group or does not own the wishlist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The group does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "An ID is invalid or the wishlist does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn share_wishlist<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(group_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<ShareWishlistHttpRequestBody>,
+) -> Result<ApiSuccess<GroupResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(group_id)?;
+    state
+        .services
+        .share_wishlist(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref group| ApiSuccess::new(StatusCode::OK, group.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use chrono::Utc;
+
+    use crate::domain::{Group, MockGroupService, SharedWishlist};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_share_wishlist_success() {
+        let (user_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut group = Group::new(Uuid::now_v7(), "Family".into(), user_id);
+        group.share(SharedWishlist::new(wishlist_id, user_id, Utc::now()));
+        let expected = ApiSuccess::new(StatusCode::OK, GroupResponseData::from(&group));
+        let group_id = group.id();
+        let mut mock_group_service = MockGroupService::new();
+        mock_group_service
+            .expect_share_wishlist()
+            .withf(move |req| {
+                req.group_id() == group_id
+                    && req.user_id() == user_id
+                    && req.wishlist_id() == wishlist_id
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(group))));
+        let state = test_services().with_group(mock_group_service).into_state();
+        let body = ApiJson(ShareWishlistHttpRequestBody {
+            user_id: user_id.to_string(),
+            wishlist_id: wishlist_id.to_string(),
+        });
+
+        let actual = share_wishlist(state, ApiPath(group_id), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
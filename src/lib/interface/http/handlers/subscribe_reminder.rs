@@ -0,0 +1,197 @@
+/*
+Module `subscribe_reminder` specifies an HTTP handler for subscribing to reminders of the
+occasion of a [Wishlist], and the reminder data structures shared with the other reminder
+handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{ReminderSubscription, SubscribeReminderError, SubscribeReminderRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<SubscribeReminderError> for ApiError {
+    fn from(e: SubscribeReminderError) -> Self {
+        match e {
+            SubscribeReminderError::UserDoesNotExist { id } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::UserNotFound,
+                    format!("User ID {} does not exist", id),
+                ))
+            }
+            SubscribeReminderError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            SubscribeReminderError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a [ReminderSubscription].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ReminderResponseData {
+    pub id: String,
+    pub user_id: String,
+    pub wishlist_id: String,
+    pub days_before: u32,
+}
+
+impl From<&ReminderSubscription> for ReminderResponseData {
+    fn from(subscription: &ReminderSubscription) -> Self {
+        Self {
+            id: subscription.id().to_string(),
+            user_id: subscription.user_id().to_string(),
+            wishlist_id: subscription.wishlist_id().to_string(),
+            days_before: subscription.days_before(),
+        }
+    }
+}
+
+/// The body of a request subscribing to reminders of a [Wishlist]'s occasion.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct SubscribeReminderHttpRequestBody {
+    pub user_id: String,
+    pub days_before: u32,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseSubscribeReminderHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl SubscribeReminderHttpRequestBody {
+    /// Converts the HTTP request body into a domain [SubscribeReminderRequest].
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+    ) -> Result<SubscribeReminderRequest, FieldErrors<ParseSubscribeReminderHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseSubscribeReminderHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(SubscribeReminderRequest::new(
+            user_id,
+            wishlist_id,
+            self.days_before,
+        ))
+    }
+}
+
+/// Subscribe to a reminder `days_before` days ahead of the occasion of a [Wishlist]. Reminders
+/// arrive by email and in the inbox, once per occasion. Subscribing twice with the same number
+/// of days returns the existing subscription.
+///
+/// # Responses
+///
+/// - 201 Created: the [ReminderSubscription].
+/// - 404 Not found: the [Wishlist] does not exist or is private to someone else.
+/// - 422 Unprocessable entity: the user ID is invalid or does not exist.
+#[utoipa::path(
+    post,
+    path = "/wishlists/{wishlist_id}/reminders",
+    tag = "wishlists",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+    ),
+    request_body = SubscribeReminderHttpRequestBody,
+    responses(
+        (status = 201, description = "The ReminderSubscription.", body = ApiResponseBody<ReminderResponseData>),
+        (status = 404, description = "The Wishlist does not exist or is private to someone else.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid or does not exist.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn subscribe_reminder<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<SubscribeReminderHttpRequestBody>,
+) -> Result<ApiSuccess<ReminderResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(wishlist_id)?;
+    state
+        .services
+        .subscribe_reminder(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref subscription| ApiSuccess::new(StatusCode::CREATED, subscription.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::MockNotificationService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    fn state(notification_service: MockNotificationService) -> State<AppState<impl UseCases>> {
+        let service = test_services()
+            .with_notification(notification_service)
+            .build();
+        State(AppState {
+            services: Arc::new(service),
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_subscribe_reminder_success() {
+        let user_id = Uuid::now_v7();
+        let wishlist_id = Uuid::now_v7();
+        let subscription = ReminderSubscription::new(Uuid::now_v7(), user_id, wishlist_id, 7);
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            ReminderResponseData::from(&subscription),
+        );
+        let mut mock_notification_service = MockNotificationService::new();
+        mock_notification_service
+            .expect_subscribe_reminder()
+            .withf(move |req| req.wishlist_id() == wishlist_id && req.days_before() == 7)
+            .return_once(move |_| Box::pin(future::ready(Ok(subscription))));
+        let body = ApiJson(SubscribeReminderHttpRequestBody {
+            user_id: user_id.to_string(),
+            days_before: 7,
+        });
+
+        let actual =
+            subscribe_reminder(state(mock_notification_service), ApiPath(wishlist_id), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_subscribe_reminder_private_wishlist() {
+        let wishlist_id = Uuid::now_v7();
+        let mut mock_notification_service = MockNotificationService::new();
+        mock_notification_service
+            .expect_subscribe_reminder()
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(
+                    SubscribeReminderError::WishlistDoesNotExist { id: wishlist_id },
+                )))
+            });
+        let body = ApiJson(SubscribeReminderHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            days_before: 7,
+        });
+
+        let actual =
+            subscribe_reminder(state(mock_notification_service), ApiPath(wishlist_id), body).await;
+        assert!(matches!(actual, Err(ApiError::NotFound(_))));
+    }
+}
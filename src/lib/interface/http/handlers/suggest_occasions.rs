@@ -0,0 +1,163 @@
+/*
+Module `suggest_occasions` specifies an HTTP handler for suggesting the occasions a user could
+make a wishlist for, and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{SuggestOccasionsError, SuggestOccasionsRequest};
+use crate::interface::http::problem::{FieldErrors, ProblemDetails};
+use crate::interface::http::AppState;
+
+use super::set_wishlist_occasion::OccasionResponseData;
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<SuggestOccasionsError> for ApiError {
+    fn from(e: SuggestOccasionsError) -> Self {
+        match e {
+            SuggestOccasionsError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for the suggested occasions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct SuggestOccasionsResponseData {
+    pub occasions: Vec<OccasionResponseData>,
+}
+
+/// The query string of a request for occasion suggestions, e.g.
+/// `?user_id=...&time_zone=Europe/Paris`. The time zone defaults to UTC.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SuggestOccasionsHttpQuery {
+    pub user_id: String,
+    pub time_zone: Option<String>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseSuggestOccasionsHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("time zone {0} is invalid")]
+    TimeZone(String),
+}
+
+impl SuggestOccasionsHttpQuery {
+    /// Converts the HTTP query into a domain [SuggestOccasionsRequest].
+    pub fn try_into_domain(
+        self,
+    ) -> Result<SuggestOccasionsRequest, FieldErrors<ParseSuggestOccasionsHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseSuggestOccasionsHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let time_zone = errors.check_parameter(
+            "time_zone",
+            match self.time_zone {
+                Some(time_zone) => time_zone
+                    .parse()
+                    .map_err(|_| ParseSuggestOccasionsHttpRequestError::TimeZone(time_zone)),
+                None => Ok(Tz::UTC),
+            },
+        );
+        let (Some(user_id), Some(time_zone)) = (user_id, time_zone) else {
+            return Err(errors);
+        };
+        Ok(SuggestOccasionsRequest::new(user_id, time_zone))
+    }
+}
+
+/// Suggest the occasions the user could make a wishlist for, e.g. their next birthday from their
+/// profile, ready to be set as the occasion of a wishlist.
+///
+/// # Responses
+///
+/// - 200 OK: the suggested occasions, with a countdown to each.
+/// - 422 Unprocessable entity: the user ID or the time zone is invalid.
+#[utoipa::path(
+    get,
+    path = "/profile/occasion-suggestions",
+    tag = "profiles",
+    params(
+        SuggestOccasionsHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The suggested occasions, with a countdown to each.", body = ApiResponseBody<SuggestOccasionsResponseData>),
+        (status = 422, description = "The user ID or the time zone is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn suggest_occasions<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<SuggestOccasionsHttpQuery>,
+) -> Result<ApiSuccess<SuggestOccasionsResponseData>, ApiError> {
+    let domain_req = query.try_into_domain()?;
+    state
+        .services
+        .suggest_occasions(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|occasions| {
+            ApiSuccess::new(
+                StatusCode::OK,
+                SuggestOccasionsResponseData {
+                    occasions: occasions.iter().map(OccasionResponseData::from).collect(),
+                },
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{MockProfileService, Occasion, OccasionKind, Recurrence};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_suggest_birthday() {
+        let user_id = Uuid::now_v7();
+        let birthday = Occasion::new(
+            OccasionKind::Birthday,
+            "2027-03-14".parse().unwrap(),
+            chrono_tz::Europe::Paris,
+            Some(Recurrence::Yearly),
+        );
+        let mut mock_profile_service = MockProfileService::new();
+        mock_profile_service
+            .expect_suggest_occasions()
+            .withf(move |req| {
+                req.user_id() == user_id && req.time_zone() == chrono_tz::Europe::Paris
+            })
+            .return_once(move |_| Box::pin(future::ready(Ok(vec![birthday]))));
+        let state = test_services()
+            .with_profile(mock_profile_service)
+            .into_state();
+        let query = ApiQuery(SuggestOccasionsHttpQuery {
+            user_id: user_id.to_string(),
+            time_zone: Some("Europe/Paris".into()),
+        });
+
+        let actual = suggest_occasions(state, query).await.unwrap();
+        let ApiSuccess(status, body, _) = actual;
+        assert_eq!(status, StatusCode::OK);
+        let occasions = &body.0.data.occasions;
+        assert_eq!(occasions.len(), 1);
+        assert_eq!(occasions[0].kind, "birthday");
+        assert_eq!(occasions[0].recurrence.as_deref(), Some("yearly"));
+    }
+}
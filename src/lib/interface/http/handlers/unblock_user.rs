@@ -0,0 +1,99 @@
+/*
+Module `unblock_user` specifies an HTTP handler for lifting the block of a user.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{BlockUserRequest, UnblockUserError};
+use crate::interface::http::problem::{Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::block_user::BlockResponseData;
+use super::unfollow_user::FollowActionHttpQuery;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<UnblockUserError> for ApiError {
+    fn from(e: UnblockUserError) -> Self {
+        match e {
+            UnblockUserError::NotBlocked { id } => Self::NotFound(Problem::new(
+                ProblemType::NotBlocked,
+                format!("User has not blocked user ID {}", id),
+            )),
+            UnblockUserError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// Lift the block of a user. Previous follows are not restored.
+///
+/// # Responses
+///
+/// - 200 OK: the deleted [Block](crate::domain::Block).
+/// - 404 Not found: the user has not blocked the other user.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    delete,
+    path = "/blocks/{blocked_id}",
+    tag = "follows",
+    params(
+        ("blocked_id" = Uuid, Path, description = "The ID of the blocked."),
+        FollowActionHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The deleted Block(crate::domain::Block).", body = ApiResponseBody<BlockResponseData>),
+        (status = 404, description = "The user has not blocked the other user.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn unblock_user<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(blocked_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<FollowActionHttpQuery>,
+) -> Result<ApiSuccess<BlockResponseData>, ApiError> {
+    let domain_req = BlockUserRequest::new(query.user_id()?, blocked_id);
+    state
+        .services
+        .unblock_user(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref block| ApiSuccess::new(StatusCode::OK, block.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use chrono::Utc;
+
+    use crate::domain::{Block, MockFollowService};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unblock_user_success() {
+        let (user_id, blocked_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let block = Block::new(user_id, blocked_id, Utc::now());
+        let expected = ApiSuccess::new(StatusCode::OK, BlockResponseData::from(&block));
+        let mut mock_follow_service = MockFollowService::new();
+        mock_follow_service
+            .expect_unblock_user()
+            .withf(move |req| req.user_id() == user_id && req.blocked_id() == blocked_id)
+            .return_once(move |_| Box::pin(future::ready(Ok(block))));
+        let state = test_services()
+            .with_follow(mock_follow_service)
+            .into_state();
+        let query = ApiQuery(FollowActionHttpQuery {
+            user_id: user_id.to_string(),
+        });
+
+        let actual = unblock_user(state, ApiPath(blocked_id), query).await;
+        assert_eq!(actual, Ok(expected));
+    }
+}
@@ -0,0 +1,129 @@
+/*
+Module `unfollow_user` specifies an HTTP handler for no longer following a user, and the query
+string shared with the other follow handlers.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{FollowUserRequest, UnfollowUserError};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::follow_user::FollowResponseData;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<UnfollowUserError> for ApiError {
+    fn from(e: UnfollowUserError) -> Self {
+        match e {
+            UnfollowUserError::NotFollowing { id } => Self::NotFound(Problem::new(
+                ProblemType::NotFollowing,
+                format!("User does not follow user ID {}", id),
+            )),
+            UnfollowUserError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The query string of a request by a user about another user, e.g. `?user_id=...`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FollowActionHttpQuery {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseFollowActionHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl FollowActionHttpQuery {
+    /// Parses the ID of the user making the request.
+    pub fn user_id(&self) -> Result<Uuid, FieldErrors<ParseFollowActionHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        errors
+            .check_parameter(
+                "user_id",
+                Uuid::parse_str(&self.user_id)
+                    .map_err(|_| ParseFollowActionHttpRequestError::UserId(self.user_id.clone())),
+            )
+            .ok_or(errors)
+    }
+}
+
+/// Stop following a user, or cancel a pending request to follow them.
+///
+/// # Responses
+///
+/// - 200 OK: the deleted [Follow](crate::domain::Follow).
+/// - 404 Not found: the user does not follow the followee.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    delete,
+    path = "/follows/{followee_id}",
+    tag = "follows",
+    params(
+        ("followee_id" = Uuid, Path, description = "The ID of the followee."),
+        FollowActionHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The deleted Follow(crate::domain::Follow).", body = ApiResponseBody<FollowResponseData>),
+        (status = 404, description = "The user does not follow the followee.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn unfollow_user<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(followee_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<FollowActionHttpQuery>,
+) -> Result<ApiSuccess<FollowResponseData>, ApiError> {
+    let domain_req = FollowUserRequest::new(query.user_id()?, followee_id);
+    state
+        .services
+        .unfollow_user(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref follow| ApiSuccess::new(StatusCode::OK, follow.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockFollowService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unfollow_user_not_following() {
+        let followee_id = Uuid::now_v7();
+        let mut mock_follow_service = MockFollowService::new();
+        mock_follow_service
+            .expect_unfollow_user()
+            .withf(move |req| req.followee_id() == followee_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(UnfollowUserError::NotFollowing {
+                    id: followee_id,
+                })))
+            });
+        let state = test_services()
+            .with_follow(mock_follow_service)
+            .into_state();
+        let query = ApiQuery(FollowActionHttpQuery {
+            user_id: Uuid::now_v7().to_string(),
+        });
+
+        let actual = unfollow_user(state, ApiPath(followee_id), query).await;
+        assert!(matches!(actual, Err(ApiError::NotFound(_))));
+    }
+}
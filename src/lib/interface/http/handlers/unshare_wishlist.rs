@@ -0,0 +1,87 @@
+/*
+Module `unshare_wishlist` specifies an HTTP handler for no longer sharing a wishlist with a
+[Group](crate::domain::Group).
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::ShareWishlistRequest;
+use crate::interface::http::problem::ProblemDetails;
+use crate::interface::http::AppState;
+
+use super::create_group::GroupResponseData;
+use super::group_feed::GroupActionHttpQuery;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+/// Stop sharing a wishlist with a [Group](crate::domain::Group). Its owner and the admins of the
+/// group can do so.
+///
+/// # Responses
+///
+/// - 200 OK: the updated group.
+/// - 403 Forbidden: the user is not a member, or neither the owner nor an admin.
+/// - 404 Not found: the group does not exist or the wishlist is not shared with it.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    delete,
+    path = "/groups/{group_id}/wishlists/{wishlist_id}",
+    tag = "groups",
+    params(
+        ("group_id" = Uuid, Path, description = "The ID of the group."),
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        GroupActionHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The updated group.", body = ApiResponseBody<GroupResponseData>),
+        (status = 403, description = "The user is not a member, or neither the owner nor an admin.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "The group does not exist or the wishlist is not shared with it.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn unshare_wishlist<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath((group_id, wishlist_id)): ApiPath<(Uuid, Uuid)>,
+    ApiQuery(query): ApiQuery<GroupActionHttpQuery>,
+) -> Result<ApiSuccess<GroupResponseData>, ApiError> {
+    let domain_req = ShareWishlistRequest::new(group_id, query.user_id()?, wishlist_id);
+    state
+        .services
+        .unshare_wishlist(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref group| ApiSuccess::new(StatusCode::OK, group.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::{MockGroupService, ShareWishlistError};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unshare_wishlist_not_shared() {
+        let (group_id, wishlist_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut mock_group_service = MockGroupService::new();
+        mock_group_service
+            .expect_unshare_wishlist()
+            .withf(move |req| req.group_id() == group_id && req.wishlist_id() == wishlist_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(ShareWishlistError::NotShared {
+                    id: wishlist_id,
+                })))
+            });
+        let state = test_services().with_group(mock_group_service).into_state();
+        let query = ApiQuery(GroupActionHttpQuery {
+            user_id: Uuid::now_v7().to_string(),
+        });
+
+        let actual = unshare_wishlist(state, ApiPath((group_id, wishlist_id)), query).await;
+        assert!(matches!(actual, Err(ApiError::NotFound(_))));
+    }
+}
@@ -0,0 +1,142 @@
+/*
+Module `unsubscribe_reminder` specifies an HTTP handler for cancelling a
+[ReminderSubscription], and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{UnsubscribeReminderError, UnsubscribeReminderRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::subscribe_reminder::ReminderResponseData;
+use super::{ApiError, ApiPath, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<UnsubscribeReminderError> for ApiError {
+    fn from(e: UnsubscribeReminderError) -> Self {
+        match e {
+            UnsubscribeReminderError::ReminderDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ReminderNotFound,
+                format!("Reminder ID {} does not exist", id),
+            )),
+            UnsubscribeReminderError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The query string of a request cancelling a [ReminderSubscription], e.g. `?user_id=...`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UnsubscribeReminderHttpQuery {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseUnsubscribeReminderHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl UnsubscribeReminderHttpQuery {
+    /// Converts the HTTP query into a domain [UnsubscribeReminderRequest].
+    pub fn try_into_domain(
+        self,
+        reminder_id: Uuid,
+    ) -> Result<UnsubscribeReminderRequest, FieldErrors<ParseUnsubscribeReminderHttpRequestError>>
+    {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id).map_err(|_| {
+                ParseUnsubscribeReminderHttpRequestError::UserId(self.user_id.clone())
+            }),
+        );
+        let Some(user_id) = user_id else {
+            return Err(errors);
+        };
+        Ok(UnsubscribeReminderRequest::new(reminder_id, user_id))
+    }
+}
+
+/// Cancel a [ReminderSubscription](crate::domain::ReminderSubscription) of the user.
+///
+/// # Responses
+///
+/// - 200 OK: the cancelled subscription.
+/// - 404 Not found: the user has no subscription with that ID.
+/// - 422 Unprocessable entity: the user ID is invalid.
+#[utoipa::path(
+    delete,
+    path = "/reminders/{reminder_id}",
+    tag = "notifications",
+    params(
+        ("reminder_id" = Uuid, Path, description = "The ID of the reminder."),
+        UnsubscribeReminderHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The cancelled subscription.", body = ApiResponseBody<ReminderResponseData>),
+        (status = 404, description = "The user has no subscription with that ID.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn unsubscribe_reminder<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(reminder_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<UnsubscribeReminderHttpQuery>,
+) -> Result<ApiSuccess<ReminderResponseData>, ApiError> {
+    let domain_req = query.try_into_domain(reminder_id)?;
+    state
+        .services
+        .unsubscribe_reminder(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref subscription| ApiSuccess::new(StatusCode::OK, subscription.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use crate::domain::MockNotificationService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unsubscribe_reminder_not_found() {
+        let reminder_id = Uuid::now_v7();
+        let mut mock_notification_service = MockNotificationService::new();
+        mock_notification_service
+            .expect_unsubscribe_reminder()
+            .withf(move |req| req.reminder_id() == reminder_id)
+            .return_once(move |_| {
+                Box::pin(future::ready(Err(
+                    UnsubscribeReminderError::ReminderDoesNotExist { id: reminder_id },
+                )))
+            });
+        let state = test_services()
+            .with_notification(mock_notification_service)
+            .into_state();
+        let query = ApiQuery(UnsubscribeReminderHttpQuery {
+            user_id: Uuid::now_v7().to_string(),
+        });
+
+        let actual = unsubscribe_reminder(state, ApiPath(reminder_id), query).await;
+        assert_eq!(
+            actual,
+            Err(ApiError::NotFound(Problem::new(
+                ProblemType::ReminderNotFound,
+                format!("Reminder ID {} does not exist", reminder_id)
+            )))
+        );
+    }
+}
@@ -0,0 +1,240 @@
+/*
+Module `upload_image` specifies an HTTP handler for storing an image uploaded by a user, and the
+associated data structures.
+*/
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{ImageSize, StoreImageError, StoredImage, UploadImageRequest};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiQuery, ApiResponseBody, ApiSuccess};
+
+impl From<StoreImageError> for ApiError {
+    fn from(e: StoreImageError) -> Self {
+        match e {
+            StoreImageError::TooLarge { limit } => Self::PayloadTooLarge(Problem::new(
+                ProblemType::ImageTooLarge,
+                format!("Image must not be larger than {} bytes", limit),
+            )),
+            StoreImageError::DimensionsTooLarge { limit } => {
+                Self::UnprocessableEntity(Problem::new(
+                    ProblemType::ImageDimensionsTooLarge,
+                    format!(
+                        "Image must not be larger than {} pixels wide or high",
+                        limit
+                    ),
+                ))
+            }
+            StoreImageError::Unsupported => Self::UnsupportedMediaType(Problem::new(
+                ProblemType::UnsupportedImageFormat,
+                "Image must be a PNG, JPEG, GIF or WebP",
+            )),
+            StoreImageError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The URLs a stored image is served from, one per size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ImageUrlsData {
+    pub original: String,
+    pub large: String,
+    pub medium: String,
+    pub small: String,
+}
+
+/// The response body data field for a successful image upload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct UploadImageResponseData {
+    pub id: String,
+    pub urls: ImageUrlsData,
+}
+
+impl From<&StoredImage> for UploadImageResponseData {
+    fn from(image: &StoredImage) -> Self {
+        let url = |size: ImageSize| format!("/api/images/{}/{}", image.id(), size);
+        Self {
+            id: image.id().to_string(),
+            urls: ImageUrlsData {
+                original: url(ImageSize::Original),
+                large: url(ImageSize::Large),
+                medium: url(ImageSize::Medium),
+                small: url(ImageSize::Small),
+            },
+        }
+    }
+}
+
+/// The query string of an image upload, e.g. `?user_id=...`. The user becomes the owner of the
+/// image.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UploadImageHttpQuery {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseUploadImageHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+}
+
+impl UploadImageHttpQuery {
+    /// Converts the HTTP query string and the uploaded bytes into a domain [UploadImageRequest].
+    pub fn try_into_domain(
+        self,
+        bytes: Vec<u8>,
+    ) -> Result<UploadImageRequest, FieldErrors<ParseUploadImageHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        errors
+            .check_parameter(
+                "user_id",
+                Uuid::parse_str(&self.user_id)
+                    .map_err(|_| ParseUploadImageHttpRequestError::UserId(self.user_id.clone())),
+            )
+            .map(|user_id| UploadImageRequest::new(user_id, bytes))
+            .ok_or(errors)
+    }
+}
+
+/// Store an image uploaded as the raw request body, along with its thumbnails.
+///
+/// The format is detected from the content; the request `Content-Type` is ignored. The uploading
+/// user is recorded as the owner of the image.
+///
+/// # Responses
+///
+/// - 201 Created: the image was stored.
+/// - 413 Payload too large: the image exceeds the upload size limit.
+/// - 415 Unsupported media type: the body is not a PNG, JPEG, GIF or WebP image.
+/// - 422 Unprocessable entity: the user ID is invalid, or the image dimensions exceed the limit.
+#[utoipa::path(
+    post,
+    path = "/images",
+    tag = "images",
+    params(UploadImageHttpQuery),
+    request_body(
+        content = [u8],
+        content_type = "application/octet-stream",
+        description = "The image, as PNG, JPEG, GIF or WebP."
+    ),
+    responses(
+        (status = 201, description = "The image was stored.", body = ApiResponseBody<UploadImageResponseData>),
+        (status = 413, description = "The image exceeds the upload size limit.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 415, description = "The body is not a PNG, JPEG, GIF or WebP image.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID is invalid, or the image dimensions exceed the limit.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn upload_image<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiQuery(query): ApiQuery<UploadImageHttpQuery>,
+    body: Bytes,
+) -> Result<ApiSuccess<UploadImageResponseData>, ApiError> {
+    let domain_req = query.try_into_domain(body.to_vec())?;
+    state
+        .services
+        .upload_image(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref image| ApiSuccess::new(StatusCode::CREATED, image.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use uuid::Uuid;
+
+    use crate::domain::MockImageService;
+    use crate::interface::http::handlers::test_services;
+    use crate::interface::http::problem::InvalidField;
+
+    use super::*;
+
+    fn state(image_service: MockImageService) -> State<AppState<impl UseCases>> {
+        let service = test_services().with_image(image_service).build();
+        State(AppState {
+            services: Arc::new(service),
+        })
+    }
+
+    fn query(user_id: Uuid) -> ApiQuery<UploadImageHttpQuery> {
+        ApiQuery(UploadImageHttpQuery {
+            user_id: user_id.to_string(),
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_upload_image_success() {
+        let (id, user_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut mock_image_service = MockImageService::new();
+        mock_image_service
+            .expect_upload_image()
+            .withf(move |req| req.user_id() == user_id && req.bytes() == b"png")
+            .return_once(move |_| Box::pin(future::ready(Ok(StoredImage::new(id)))));
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            UploadImageResponseData {
+                id: id.to_string(),
+                urls: ImageUrlsData {
+                    original: format!("/api/images/{}/original", id),
+                    large: format!("/api/images/{}/large", id),
+                    medium: format!("/api/images/{}/medium", id),
+                    small: format!("/api/images/{}/small", id),
+                },
+            },
+        );
+
+        let actual = upload_image(
+            state(mock_image_service),
+            query(user_id),
+            Bytes::from_static(b"png"),
+        )
+        .await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_upload_image_unsupported() {
+        let mut mock_image_service = MockImageService::new();
+        mock_image_service
+            .expect_upload_image()
+            .return_once(|_| Box::pin(future::ready(Err(StoreImageError::Unsupported))));
+
+        let actual = upload_image(
+            state(mock_image_service),
+            query(Uuid::now_v7()),
+            Bytes::from_static(b"<svg/>"),
+        )
+        .await;
+        assert!(matches!(actual, Err(ApiError::UnsupportedMediaType(_))));
+    }
+
+    #[test]
+    fn test_parse_upload_image_query() {
+        assert_eq!(
+            UploadImageHttpQuery {
+                user_id: "nope".into(),
+            }
+            .try_into_domain(vec![1])
+            .unwrap_err()
+            .into_invalid_fields(),
+            vec![InvalidField::parameter(
+                "user_id",
+                "user id nope is invalid"
+            )]
+        );
+    }
+}
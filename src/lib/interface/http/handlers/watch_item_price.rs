@@ -0,0 +1,210 @@
+/*
+Module `watch_item_price` specifies an HTTP handler for subscribing to the price drops of an
+[Item], and the associated data structures.
+*/
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    ItemPrice, ItemPriceInvalidError, PriceWatch, ShareToken, WatchItemPriceError,
+    WatchItemPriceRequest,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::{ApiError, ApiJson, ApiPath, ApiResponseBody, ApiSuccess};
+
+impl From<WatchItemPriceError> for ApiError {
+    fn from(e: WatchItemPriceError) -> Self {
+        match e {
+            WatchItemPriceError::ItemDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::ItemNotFound,
+                format!("Item ID {} does not exist", id),
+            )),
+            WatchItemPriceError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The response body data field for a successful price watch subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WatchItemPriceResponseData {
+    pub id: String,
+}
+
+impl From<&PriceWatch> for WatchItemPriceResponseData {
+    fn from(watch: &PriceWatch) -> Self {
+        Self {
+            id: watch.id().to_string(),
+        }
+    }
+}
+
+/// The body of a price watch subscription request. The items of a private wishlist can only be
+/// watched by a user who may see it, or with the `token` of its share link.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct WatchItemPriceHttpRequestBody {
+    pub user_id: String,
+    pub drop_percent: Option<Decimal>,
+    pub target_price: Option<Decimal>,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseWatchItemPriceHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("drop percent {0} must be between 0 and 100")]
+    DropPercent(Decimal),
+    #[error("target price {} is invalid", .0.invalid_price)]
+    TargetPrice(#[from] ItemPriceInvalidError),
+}
+
+impl WatchItemPriceHttpRequestBody {
+    /// Converts the HTTP request body into a domain [WatchItemPriceRequest].
+    pub fn try_into_domain(
+        self,
+        item_id: Uuid,
+    ) -> Result<WatchItemPriceRequest, FieldErrors<ParseWatchItemPriceHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_field(
+            "/user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseWatchItemPriceHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let drop_percent = errors.check_field(
+            "/drop_percent",
+            match self.drop_percent {
+                Some(percent) if percent <= Decimal::ZERO || percent > Decimal::ONE_HUNDRED => {
+                    Err(ParseWatchItemPriceHttpRequestError::DropPercent(percent))
+                }
+                drop_percent => Ok(drop_percent),
+            },
+        );
+        let target_price = errors.check_field(
+            "/target_price",
+            self.target_price.map(ItemPrice::new).transpose(),
+        );
+        let (Some(user_id), Some(drop_percent), Some(target_price)) =
+            (user_id, drop_percent, target_price)
+        else {
+            return Err(errors);
+        };
+        Ok(
+            WatchItemPriceRequest::new(item_id, user_id, drop_percent, target_price)
+                .with_share_token(self.token.as_deref().map(ShareToken::from)),
+        )
+    }
+}
+
+/// Subscribe to the price drops of an [Item] of a wishlist the user may see.
+///
+/// # Responses
+///
+/// - 201 Created: the subscription was successfully created.
+/// - 404 Not found: the [Item] does not exist or the user may not see it.
+/// - 422 Unprocessable entity: the user ID, drop percent or target price is invalid.
+#[utoipa::path(
+    post,
+    path = "/items/{item_id}/price-watches",
+    tag = "items",
+    params(
+        ("item_id" = Uuid, Path, description = "The ID of the item."),
+    ),
+    request_body = WatchItemPriceHttpRequestBody,
+    responses(
+        (status = 201, description = "The subscription was successfully created.", body = ApiResponseBody<WatchItemPriceResponseData>),
+        (status = 404, description = "The Item does not exist or the user may not see it.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID, drop percent or target price is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn watch_item_price<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<WatchItemPriceHttpRequestBody>,
+) -> Result<ApiSuccess<WatchItemPriceResponseData>, ApiError> {
+    let domain_req = body.try_into_domain(item_id)?;
+    state
+        .services
+        .watch_item_price(&domain_req)
+        .await
+        .map_err(ApiError::from)
+        .map(|ref watch: PriceWatch| ApiSuccess::new(StatusCode::CREATED, watch.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, sync::Arc};
+
+    use crate::domain::MockItemService;
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+    use crate::interface::http::problem::InvalidField;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_watch_item_price_success() {
+        let id = Uuid::now_v7();
+        let item_id = Uuid::now_v7();
+        let user_id = Uuid::now_v7();
+
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service
+            .expect_watch_item_price()
+            .return_once(move |req| {
+                let watch = PriceWatch::new(
+                    id,
+                    req.item_id(),
+                    req.user_id(),
+                    req.drop_percent(),
+                    req.target_price().cloned(),
+                );
+                Box::pin(future::ready(Ok(watch)))
+            });
+        let service = test_services().with_item(mock_item_service).build();
+        let state = axum::extract::State(AppState {
+            services: Arc::new(service),
+        });
+        let body = ApiJson(WatchItemPriceHttpRequestBody {
+            user_id: user_id.to_string(),
+            drop_percent: Some(Decimal::TEN),
+            target_price: None,
+            token: None,
+        });
+        let expected = ApiSuccess::new(
+            StatusCode::CREATED,
+            WatchItemPriceResponseData { id: id.to_string() },
+        );
+
+        let actual = watch_item_price(state, ApiPath(item_id), body).await;
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_invalid_drop_percent() {
+        let body = WatchItemPriceHttpRequestBody {
+            user_id: Uuid::now_v7().to_string(),
+            drop_percent: Some(Decimal::ONE_THOUSAND),
+            target_price: None,
+            token: None,
+        };
+        let result = body.try_into_domain(Uuid::now_v7());
+        assert_eq!(
+            result.unwrap_err().into_invalid_fields(),
+            vec![InvalidField::body(
+                "/drop_percent",
+                "drop percent 1000 must be between 0 and 100"
+            )]
+        );
+    }
+}
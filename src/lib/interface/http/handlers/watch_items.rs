@@ -0,0 +1,391 @@
+/*
+Module `watch_items` specifies the HTTP handlers pushing the changes of the [Item]s of a
+[Wishlist](crate::domain::Wishlist) to its viewers live, as Server-Sent Events or over a WebSocket,
+and the associated data structures.
+
+Each event carries its ID. A viewer who lost their connection resumes after the last event they
+received: EventSource clients send it back in `Last-Event-ID` on their own, WebSocket clients pass
+it as `last_event_id` in the query string.
+*/
+
+use std::convert::Infallible;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderName};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::Response;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    EventId, ItemChange, ItemEvent, ItemEventStream, WatchItemsError, WatchItemsRequest,
+};
+use crate::interface::http::problem::{FieldErrors, Problem, ProblemDetails, ProblemType};
+use crate::interface::http::AppState;
+
+use super::create_item::ItemResponseData;
+use super::{ApiError, ApiPath, ApiQuery};
+
+/// The request header carrying the ID of the last event an EventSource client received.
+const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+impl From<WatchItemsError> for ApiError {
+    fn from(e: WatchItemsError) -> Self {
+        match e {
+            WatchItemsError::WishlistDoesNotExist { id } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist ID {} does not exist", id),
+            )),
+            WatchItemsError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// The data of an [ItemEvent], sent as the data of a Server-Sent Event or as a WebSocket text
+/// message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ItemEventResponseData {
+    pub id: String,
+    pub wishlist_id: String,
+    #[serde(flatten)]
+    pub change: ItemChangeResponseData,
+}
+
+/// What happened to an [Item], named by `type`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "type")]
+pub enum ItemChangeResponseData {
+    #[serde(rename = "item_added")]
+    Added { item: ItemResponseData },
+    #[serde(rename = "item_edited")]
+    Edited { item: ItemResponseData },
+    #[serde(rename = "item_reserved")]
+    Reserved { item_id: String, reserved: bool },
+    #[serde(rename = "item_removed")]
+    Removed { item_id: String },
+}
+
+impl ItemChangeResponseData {
+    /// The `type` of the change, also the name of its Server-Sent Event.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Added { .. } => "item_added",
+            Self::Edited { .. } => "item_edited",
+            Self::Reserved { .. } => "item_reserved",
+            Self::Removed { .. } => "item_removed",
+        }
+    }
+}
+
+impl From<&ItemEvent> for ItemEventResponseData {
+    fn from(event: &ItemEvent) -> Self {
+        Self {
+            id: event.id().to_string(),
+            wishlist_id: event.wishlist_id().to_string(),
+            change: match event.change() {
+                ItemChange::Added(item) => ItemChangeResponseData::Added { item: item.into() },
+                ItemChange::Edited(item) => ItemChangeResponseData::Edited { item: item.into() },
+                ItemChange::Reserved { item_id, reserved } => ItemChangeResponseData::Reserved {
+                    item_id: item_id.to_string(),
+                    reserved: *reserved,
+                },
+                ItemChange::Removed { item_id } => ItemChangeResponseData::Removed {
+                    item_id: item_id.to_string(),
+                },
+            },
+        }
+    }
+}
+
+/// The query string of a request following the [Item]s of a wishlist, e.g.
+/// `?user_id=...&last_event_id=42`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WatchItemsHttpQuery {
+    pub user_id: String,
+    pub last_event_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseWatchItemsHttpRequestError {
+    #[error("user id {0} is invalid")]
+    UserId(String),
+    #[error("event id {0} is invalid")]
+    EventId(String),
+}
+
+impl WatchItemsHttpQuery {
+    /// Converts the HTTP query into a domain [WatchItemsRequest]. A `Last-Event-ID` header takes
+    /// precedence over the `last_event_id` parameter.
+    pub fn try_into_domain(
+        self,
+        wishlist_id: Uuid,
+        headers: &HeaderMap,
+    ) -> Result<WatchItemsRequest, FieldErrors<ParseWatchItemsHttpRequestError>> {
+        let mut errors = FieldErrors::new();
+        let user_id = errors.check_parameter(
+            "user_id",
+            Uuid::parse_str(&self.user_id)
+                .map_err(|_| ParseWatchItemsHttpRequestError::UserId(self.user_id.clone())),
+        );
+        let header = headers
+            .get(LAST_EVENT_ID)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned());
+        let last_event_id = match header {
+            Some(header) => errors
+                .check_header("Last-Event-ID", parse_event_id(&header))
+                .map(Some),
+            None => errors.check_parameter(
+                "last_event_id",
+                self.last_event_id
+                    .as_deref()
+                    .map(parse_event_id)
+                    .transpose(),
+            ),
+        };
+        let (Some(user_id), Some(last_event_id)) = (user_id, last_event_id) else {
+            return Err(errors);
+        };
+        Ok(WatchItemsRequest::new(wishlist_id, user_id).with_last_event_id(last_event_id))
+    }
+}
+
+fn parse_event_id(id: &str) -> Result<EventId, ParseWatchItemsHttpRequestError> {
+    id.trim()
+        .parse()
+        .map_err(|_| ParseWatchItemsHttpRequestError::EventId(id.to_string()))
+}
+
+/// Follow the changes of the [Item]s of a wishlist as Server-Sent Events: `item_added`,
+/// `item_edited`, `item_reserved` and `item_removed`, each with its ID and an
+/// [ItemEventResponseData] as data.
+/// The owner of the wishlist never gets `item_reserved`, nor learns of reservations otherwise.
+///
+/// # Responses
+///
+/// - 200 OK: the `text/event-stream` of the changes, until the client disconnects, falls too
+///   far behind, or may no longer see the wishlist; it then reconnects with `Last-Event-ID`.
+/// - 404 Not found: the wishlist does not exist or the user may not see it.
+/// - 422 Unprocessable entity: the user ID or the last event ID is invalid.
+#[utoipa::path(
+    get,
+    path = "/wishlists/{wishlist_id}/events",
+    tag = "items",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        ("Last-Event-ID" = Option<String>, Header, description = "The ID of the last event received, to resume after it."),
+        WatchItemsHttpQuery,
+    ),
+    responses(
+        (status = 200, description = "The stream of the changes of the items.", body = ItemEventResponseData, content_type = "text/event-stream"),
+        (status = 404, description = "The wishlist does not exist or the user may not see it.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID or the last event ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn watch_items<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<WatchItemsHttpQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let domain_req = query.try_into_domain(wishlist_id, &headers)?;
+    let events = state.services.watch_items(&domain_req).await?;
+    let stream = futures_util::stream::unfold(events, |mut events| async move {
+        let event = events.next().await?;
+        Some((Ok(server_sent_event(&event)), events))
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+fn server_sent_event(event: &ItemEvent) -> Event {
+    let data = ItemEventResponseData::from(event);
+    Event::default()
+        .id(data.id.clone())
+        .event(data.change.kind())
+        .data(serde_json::to_string(&data).expect("event data serializes to JSON"))
+}
+
+/// Follow the changes of the [Item]s of a wishlist over a WebSocket: each change is a text
+/// message holding an [ItemEventResponseData]. Messages from the client are ignored. The socket
+/// closes once the user may no longer see the wishlist.
+///
+/// # Responses
+///
+/// - 101 Switching Protocols: the WebSocket is open.
+/// - 404 Not found: the wishlist does not exist or the user may not see it.
+/// - 422 Unprocessable entity: the user ID or the last event ID is invalid.
+#[utoipa::path(
+    get,
+    path = "/wishlists/{wishlist_id}/events/ws",
+    tag = "items",
+    params(
+        ("wishlist_id" = Uuid, Path, description = "The ID of the wishlist."),
+        WatchItemsHttpQuery,
+    ),
+    responses(
+        (status = 101, description = "The WebSocket is open; each text message is an ItemEventResponseData."),
+        (status = 404, description = "The wishlist does not exist or the user may not see it.", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "The user ID or the last event ID is invalid.", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub async fn watch_items_socket<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    ApiPath(wishlist_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<WatchItemsHttpQuery>,
+    headers: HeaderMap,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let domain_req = query.try_into_domain(wishlist_id, &headers)?;
+    // Viewers are checked before the upgrade, so that they get a problem rather than a socket
+    let events = state.services.watch_items(&domain_req).await?;
+    Ok(upgrade.on_upgrade(move |socket| push_events(socket, events)))
+}
+
+async fn push_events(mut socket: WebSocket, mut events: ItemEventStream) {
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else {
+                    // The viewer fell behind, or lost access: they reconnect after the last event
+                    // they received, if they still may
+                    let _ = socket.send(Message::Close(None)).await;
+                    return;
+                };
+                let data = serde_json::to_string(&ItemEventResponseData::from(&event))
+                    .expect("event data serializes to JSON");
+                if socket.send(Message::Text(data.into())).await.is_err() {
+                    return;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use axum::body::to_bytes;
+    use axum::response::IntoResponse;
+    use tokio::sync::broadcast;
+
+    use crate::domain::{Item, ItemEventSubscription, MockItemService, Wishlist};
+    use crate::interface::http::handlers::test_services;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_watch_items_as_server_sent_events() {
+        let (wishlist_id, user_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let wishlist = Wishlist::new(
+            wishlist_id,
+            Uuid::now_v7(),
+            "Birthday".into(),
+            "birthday".into(),
+            false,
+        );
+        let item = Item::create(
+            Uuid::now_v7(),
+            wishlist_id,
+            "Book".into(),
+            "https://shop.example/book".into(),
+            None,
+            None,
+        );
+        let missed = vec![
+            ItemEvent::new(8, &wishlist, ItemChange::Added(item.clone())),
+            ItemEvent::new(
+                9,
+                &wishlist,
+                ItemChange::Reserved {
+                    item_id: item.id(),
+                    reserved: true,
+                },
+            ),
+        ];
+        let mut mock_item_service = MockItemService::new();
+        mock_item_service
+            .expect_watch_items()
+            .withf(move |req| {
+                req.wishlist_id() == wishlist_id
+                    && req.user_id() == user_id
+                    && req.last_event_id() == Some(7)
+            })
+            .return_once(move |req| {
+                // The bus is gone, so the stream ends after the missed events
+                let live = broadcast::channel(1).1;
+                let events =
+                    ItemEventStream::new(req.user_id(), ItemEventSubscription::new(missed, live));
+                Box::pin(future::ready(Ok(events)))
+            });
+        let state = test_services().with_item(mock_item_service).into_state();
+        let query = ApiQuery(WatchItemsHttpQuery {
+            user_id: user_id.to_string(),
+            last_event_id: Some("3".to_string()),
+        });
+        let mut headers = HeaderMap::new();
+        headers.insert(LAST_EVENT_ID, "7".parse().unwrap());
+
+        let response = watch_items(state, ApiPath(wishlist_id), query, headers)
+            .await
+            .unwrap()
+            .into_response();
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        let events: Vec<_> = body.split("\n\n").filter(|e| !e.is_empty()).collect();
+        assert_eq!(events.len(), 2);
+        assert!(events[0].starts_with("id: 8\nevent: item_added\ndata: "));
+        assert!(events[1].starts_with("id: 9\nevent: item_reserved\ndata: "));
+        let data: serde_json::Value =
+            serde_json::from_str(events[1].split("data: ").nth(1).unwrap()).unwrap();
+        assert_eq!(
+            data,
+            serde_json::json!({
+                "id": "9",
+                "wishlist_id": wishlist_id.to_string(),
+                "type": "item_reserved",
+                "item_id": item.id().to_string(),
+                "reserved": true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_last_event_id() {
+        let wishlist_id = Uuid::now_v7();
+        let query = |last_event_id: Option<&str>| WatchItemsHttpQuery {
+            user_id: Uuid::now_v7().to_string(),
+            last_event_id: last_event_id.map(ToString::to_string),
+        };
+        let req = query(Some("12"))
+            .try_into_domain(wishlist_id, &HeaderMap::new())
+            .unwrap();
+        assert_eq!(req.last_event_id(), Some(12));
+        let req = query(None)
+            .try_into_domain(wishlist_id, &HeaderMap::new())
+            .unwrap();
+        assert_eq!(req.last_event_id(), None);
+
+        assert!(query(Some("latest"))
+            .try_into_domain(wishlist_id, &HeaderMap::new())
+            .is_err());
+        let mut headers = HeaderMap::new();
+        headers.insert(LAST_EVENT_ID, "latest".parse().unwrap());
+        assert!(query(Some("12"))
+            .try_into_domain(wishlist_id, &headers)
+            .is_err());
+    }
+}
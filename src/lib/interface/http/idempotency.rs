@@ -0,0 +1,458 @@
+/*
+Module `idempotency` lets clients retry a POST safely by sending an `Idempotency-Key` header. The
+first response to a key is kept for a while and replayed on retries instead of running the request
+again, so a retried creation does not create a duplicate.
+
+Keys are scoped to the acting user, as told by the `actor` module, and the route. Requests that
+act for no user, such as the creation of an author, share an anonymous scope.
+*/
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::body::{to_bytes, Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, TimeDelta, Utc};
+use sha2::{Digest, Sha256};
+use tokio::sync::watch;
+
+use crate::domain::Clock;
+
+use super::actor::{acting_user, buffer_body};
+use super::handlers::ApiError;
+use super::problem::{Problem, ProblemType};
+
+/// The request header carrying the idempotency key chosen by the client.
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// The response header marking a response replayed from an earlier request.
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// The longest key accepted, e.g. enough for a UUID with some prefix.
+const MAX_KEY_LENGTH: usize = 255;
+
+/// How many keys the store keeps when no limit is configured.
+pub const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
+/// The SHA-256 digest of a request, see [fingerprint].
+type Fingerprint = [u8; 32];
+
+/// What a key is unique within: the acting user and the route, e.g. `POST /api/wishlists`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Scope {
+    user: Option<String>,
+    key: String,
+    route: String,
+}
+
+/// A response kept to be replayed.
+#[derive(Debug, Clone)]
+struct StoredResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl StoredResponse {
+    fn to_response(&self) -> Response {
+        let mut response = (self.status, self.body.clone()).into_response();
+        *response.headers_mut() = self.headers.clone();
+        response
+    }
+
+    fn replay(&self) -> Response {
+        let mut response = self.to_response();
+        response
+            .headers_mut()
+            .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+#[derive(Debug)]
+enum Entry {
+    /// The first request with the key is still running; the receiver is closed once it is over.
+    InFlight {
+        fingerprint: Fingerprint,
+        done: watch::Receiver<()>,
+    },
+    Completed {
+        fingerprint: Fingerprint,
+        response: StoredResponse,
+        expires_at: DateTime<Utc>,
+    },
+}
+
+/// The outcome of a request claiming its key.
+enum Claim {
+    /// The request is the first with the key: it runs, and its response is kept.
+    Run(RunningRequest),
+    /// The request is a retry of a completed one.
+    Replay(StoredResponse),
+    /// The request is a retry of one that is still running, which it must wait for.
+    Wait(watch::Receiver<()>),
+    /// The key was used for a different request.
+    Mismatch,
+}
+
+/// The [IdempotencyStore] struct keeps the responses to idempotent requests in memory, each for
+/// the same time to live. Past its maximum number of keys, the responses closest to expiring are
+/// dropped for new keys; the keys of requests still running are always kept.
+pub struct IdempotencyStore {
+    entries: Mutex<HashMap<Scope, Entry>>,
+    ttl: TimeDelta,
+    max_entries: usize,
+    clock: Arc<dyn Clock>,
+}
+
+impl IdempotencyStore {
+    pub fn new(ttl: TimeDelta, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            clock,
+        }
+    }
+
+    /// Sets how many keys are kept at most, at least one.
+    pub fn with_max_entries(self, max_entries: usize) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            ..self
+        }
+    }
+
+    fn claim(self: &Arc<Self>, scope: &Scope, fingerprint: Fingerprint) -> Claim {
+        let now = self.clock.now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(
+            |_, entry| !matches!(entry, Entry::Completed { expires_at, .. } if *expires_at <= now),
+        );
+        if !entries.contains_key(scope) {
+            while entries.len() >= self.max_entries {
+                let oldest = entries
+                    .iter()
+                    .filter_map(|(scope, entry)| match entry {
+                        Entry::Completed { expires_at, .. } => Some((*expires_at, scope)),
+                        Entry::InFlight { .. } => None,
+                    })
+                    .min_by_key(|(expires_at, _)| *expires_at)
+                    .map(|(_, scope)| scope.clone());
+                let Some(oldest) = oldest else {
+                    break;
+                };
+                entries.remove(&oldest);
+            }
+        }
+        match entries.get(scope) {
+            Some(Entry::InFlight {
+                fingerprint: claimed,
+                ..
+            })
+            | Some(Entry::Completed {
+                fingerprint: claimed,
+                ..
+            }) if *claimed != fingerprint => Claim::Mismatch,
+            Some(Entry::InFlight { done, .. }) => Claim::Wait(done.clone()),
+            Some(Entry::Completed { response, .. }) => Claim::Replay(response.clone()),
+            None => {
+                let (sender, done) = watch::channel(());
+                entries.insert(scope.clone(), Entry::InFlight { fingerprint, done });
+                Claim::Run(RunningRequest {
+                    store: self.clone(),
+                    scope: scope.clone(),
+                    fingerprint,
+                    _done: sender,
+                    completed: false,
+                })
+            }
+        }
+    }
+}
+
+/// The first request with a key, while it runs. Unless it completes, e.g. if the client hangs up
+/// or the server fails, the key is released when it is dropped so that a retry runs again.
+struct RunningRequest {
+    store: Arc<IdempotencyStore>,
+    scope: Scope,
+    fingerprint: Fingerprint,
+    _done: watch::Sender<()>,
+    completed: bool,
+}
+
+impl RunningRequest {
+    /// Keeps the response to replay it to retries, and wakes up those waiting for it.
+    fn complete(mut self, response: StoredResponse) {
+        let expires_at = self.store.clock.now() + self.store.ttl;
+        self.store.entries.lock().unwrap().insert(
+            self.scope.clone(),
+            Entry::Completed {
+                fingerprint: self.fingerprint,
+                response,
+                expires_at,
+            },
+        );
+        self.completed = true;
+    }
+}
+
+impl Drop for RunningRequest {
+    fn drop(&mut self) {
+        if !self.completed {
+            self.store.entries.lock().unwrap().remove(&self.scope);
+        }
+    }
+}
+
+/// The state of the [idempotent] middleware.
+#[derive(Clone)]
+pub struct Idempotency {
+    store: Arc<IdempotencyStore>,
+    max_body_bytes: usize,
+}
+
+impl Idempotency {
+    pub fn new(store: Arc<IdempotencyStore>, max_body_bytes: usize) -> Self {
+        Self {
+            store,
+            max_body_bytes,
+        }
+    }
+}
+
+/// Runs a POST with an `Idempotency-Key` at most once per key: retries get the first response
+/// again, retries of a request still running wait for its response, and a key reused for another
+/// request is rejected with 422 Unprocessable Entity. Server errors are not kept, so that a retry
+/// may succeed.
+pub async fn idempotent(
+    State(idempotency): State<Idempotency>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY) else {
+        return next.run(request).await;
+    };
+    let key = match parse_key(key) {
+        Ok(key) => key,
+        Err(err) => return err.into_response(),
+    };
+    let (parts, body) = request.into_parts();
+    let body = match buffer_body(body, idempotency.max_body_bytes).await {
+        Ok(body) => body,
+        Err(err) => return err.into_response(),
+    };
+    let scope = Scope {
+        user: acting_user(parts.uri.query(), &body),
+        key,
+        route: format!("{} {}", parts.method, parts.uri.path()),
+    };
+    let fingerprint = fingerprint(parts.uri.query(), &body);
+
+    let running = loop {
+        match idempotency.store.claim(&scope, fingerprint) {
+            Claim::Run(running) => break running,
+            Claim::Replay(response) => return response.replay(),
+            Claim::Wait(mut done) => {
+                // Either way the first request is over: its response is kept or its key released
+                let _ = done.changed().await;
+            }
+            Claim::Mismatch => {
+                return ApiError::UnprocessableEntity(Problem::new(
+                    ProblemType::IdempotencyKeyReused,
+                    format!(
+                        "Idempotency-Key {} was already used for a different request",
+                        scope.key
+                    ),
+                ))
+                .into_response()
+            }
+        }
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if response.status().is_server_error() {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(err) => return ApiError::InternalServerError(err.to_string()).into_response(),
+    };
+    let response = StoredResponse {
+        status: parts.status,
+        headers: parts.headers,
+        body,
+    };
+    running.complete(response.clone());
+    response.to_response()
+}
+
+fn parse_key(key: &HeaderValue) -> Result<String, ApiError> {
+    key.to_str()
+        .ok()
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LENGTH)
+        .map(ToString::to_string)
+        .ok_or_else(|| {
+            ApiError::BadRequest(Problem::new(
+                ProblemType::MalformedRequest,
+                format!(
+                    "Idempotency-Key must be between 1 and {} visible ASCII characters",
+                    MAX_KEY_LENGTH
+                ),
+            ))
+        })
+}
+
+/// Identifies a request within its [Scope], to tell retries from another use of the same key.
+/// The query is prefixed with its length, so that it cannot run into the body.
+fn fingerprint(query: Option<&str>, body: &[u8]) -> Fingerprint {
+    let mut hasher = Sha256::new();
+    match query {
+        Some(query) => {
+            hasher.update([1]);
+            hasher.update((query.len() as u64).to_be_bytes());
+            hasher.update(query.as_bytes());
+        }
+        None => hasher.update([0]),
+    }
+    hasher.update(body);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::clock::ManualClock;
+
+    fn scope(key: &str) -> Scope {
+        Scope {
+            user: Some("alice".to_string()),
+            key: key.to_string(),
+            route: "POST /api/wishlists".to_string(),
+        }
+    }
+
+    fn request(body: &str) -> Fingerprint {
+        fingerprint(None, body.as_bytes())
+    }
+
+    fn response(body: &'static str) -> StoredResponse {
+        StoredResponse {
+            status: StatusCode::CREATED,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_replay_until_expired() {
+        let clock = Arc::new(ManualClock::new("2027-03-14T08:00:00Z".parse().unwrap()));
+        let store = Arc::new(IdempotencyStore::new(TimeDelta::hours(1), clock.clone()));
+
+        let Claim::Run(running) = store.claim(&scope("a"), request("1")) else {
+            panic!("expected the first request to run");
+        };
+        running.complete(response("created"));
+        let Claim::Replay(replayed) = store.claim(&scope("a"), request("1")) else {
+            panic!("expected a retry to be replayed");
+        };
+        assert_eq!(replayed.body, "created");
+        assert!(matches!(
+            store.claim(&scope("a"), request("2")),
+            Claim::Mismatch
+        ));
+        assert!(matches!(
+            store.claim(&scope("b"), request("2")),
+            Claim::Run(_)
+        ));
+
+        clock.advance(TimeDelta::hours(1));
+        assert!(matches!(
+            store.claim(&scope("a"), request("2")),
+            Claim::Run(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_request_in_flight() {
+        let clock = Arc::new(ManualClock::new("2027-03-14T08:00:00Z".parse().unwrap()));
+        let store = Arc::new(IdempotencyStore::new(TimeDelta::hours(1), clock));
+
+        let Claim::Run(running) = store.claim(&scope("a"), request("1")) else {
+            panic!("expected the first request to run");
+        };
+        let Claim::Wait(mut done) = store.claim(&scope("a"), request("1")) else {
+            panic!("expected a concurrent retry to wait");
+        };
+        assert!(matches!(
+            store.claim(&scope("a"), request("2")),
+            Claim::Mismatch
+        ));
+        let waiting = tokio::spawn(async move { done.changed().await });
+        running.complete(response("created"));
+        assert!(waiting.await.unwrap().is_err());
+        assert!(matches!(
+            store.claim(&scope("a"), request("1")),
+            Claim::Replay(_)
+        ));
+
+        // A request that never completes releases its key
+        let Claim::Run(running) = store.claim(&scope("b"), request("1")) else {
+            panic!("expected the first request to run");
+        };
+        drop(running);
+        assert!(matches!(
+            store.claim(&scope("b"), request("1")),
+            Claim::Run(_)
+        ));
+    }
+
+    #[test]
+    fn test_max_entries() {
+        let clock = Arc::new(ManualClock::new("2027-03-14T08:00:00Z".parse().unwrap()));
+        let store =
+            Arc::new(IdempotencyStore::new(TimeDelta::hours(1), clock.clone()).with_max_entries(2));
+
+        for key in ["a", "b"] {
+            let Claim::Run(running) = store.claim(&scope(key), request("1")) else {
+                panic!("expected the first request to run");
+            };
+            running.complete(response("created"));
+            clock.advance(TimeDelta::minutes(1));
+        }
+        let Claim::Run(_running) = store.claim(&scope("c"), request("1")) else {
+            panic!("expected the first request to run");
+        };
+        assert_eq!(store.entries.lock().unwrap().len(), 2);
+        // The response closest to expiring was dropped, the others are kept
+        assert!(matches!(
+            store.claim(&scope("b"), request("1")),
+            Claim::Replay(_)
+        ));
+        assert!(matches!(
+            store.claim(&scope("a"), request("1")),
+            Claim::Run(_)
+        ));
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(
+            fingerprint(Some("a=1"), b"{}"),
+            fingerprint(Some("a=1"), b"{}")
+        );
+        assert_ne!(
+            fingerprint(Some("a=1"), b"{}"),
+            fingerprint(Some("a=2"), b"{}")
+        );
+        assert_ne!(fingerprint(Some("a"), b"b"), fingerprint(Some("ab"), b""));
+        assert_ne!(fingerprint(None, b""), fingerprint(Some(""), b""));
+    }
+}
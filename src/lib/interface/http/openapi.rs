@@ -0,0 +1,164 @@
+/*
+Module `openapi` generates the OpenAPI document of the API from the annotated handlers and their
+request and response types.
+*/
+
+use utoipa::OpenApi;
+
+use super::handlers::{
+    add_exclusion, add_participant, answer_follow_request, audit_draw, block_user,
+    cancel_account_deletion, copy_item, create_exchange, create_group, create_item, create_section,
+    create_share_link, create_user, create_wishlist, delete_account, delete_section,
+    delete_webhook, draw_exchange, duplicate_wishlist, export_account, find_image,
+    find_price_history, find_profile, follow_feed, follow_user, group_feed, invite_member,
+    list_follow_requests, list_inbox, list_invitations, list_items, list_templates,
+    list_webhook_deliveries, list_webhooks, mark_item_received, move_item, move_item_to_section,
+    register_webhook, remove_member, reorder_wishlist, request_account_confirmation, reserve_item,
+    respond_to_invitation, reveal_recipient, revoke_share_link, set_follow_settings,
+    set_member_role, set_profile, set_reminder_preferences, set_wishlist_occasion,
+    set_wishlist_template, share_wishlist, subscribe_reminder, suggest_occasions, unblock_user,
+    unfollow_user, unshare_wishlist, unsubscribe_reminder, upload_image, watch_item_price,
+    watch_items,
+};
+
+/// Where the OpenAPI document is served.
+pub const OPENAPI_JSON_PATH: &str = "/api/openapi.json";
+
+/// Where the browsable documentation is served.
+pub const DOCS_PATH: &str = "/api/docs";
+
+/// The OpenAPI document of the routes in [api_routes](super::handlers::api_routes). Successful
+/// responses are wrapped in an `ApiResponseBody`, errors are `application/problem+json`.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Wishlist API", description = "Wishlists, their items and the people who offer them."),
+    servers((url = "/api")),
+    paths(
+        create_user::create_user,
+        create_wishlist::create_wishlist,
+        list_templates::list_templates,
+        duplicate_wishlist::duplicate_wishlist,
+        set_wishlist_template::set_wishlist_template,
+        set_wishlist_occasion::set_wishlist_occasion,
+        create_share_link::create_share_link,
+        revoke_share_link::revoke_share_link,
+        list_items::list_items,
+        create_item::create_item,
+        reorder_wishlist::reorder_wishlist,
+        create_section::create_section,
+        delete_section::delete_section,
+        move_item_to_section::move_item_to_section,
+        move_item::move_item,
+        copy_item::copy_item,
+        mark_item_received::mark_item_received,
+        reserve_item::reserve_item,
+        find_price_history::find_price_history,
+        watch_item_price::watch_item_price,
+        watch_items::watch_items,
+        watch_items::watch_items_socket,
+        register_webhook::register_webhook,
+        list_webhooks::list_webhooks,
+        delete_webhook::delete_webhook,
+        list_webhook_deliveries::list_webhook_deliveries,
+        subscribe_reminder::subscribe_reminder,
+        unsubscribe_reminder::unsubscribe_reminder,
+        set_reminder_preferences::set_reminder_preferences,
+        list_inbox::list_inbox,
+        create_exchange::create_exchange,
+        add_participant::add_participant,
+        add_exclusion::add_exclusion,
+        draw_exchange::draw_exchange,
+        audit_draw::audit_draw,
+        reveal_recipient::reveal_recipient,
+        create_group::create_group,
+        invite_member::invite_member,
+        list_invitations::list_invitations,
+        respond_to_invitation::respond_to_invitation,
+        remove_member::remove_member,
+        set_member_role::set_member_role,
+        share_wishlist::share_wishlist,
+        unshare_wishlist::unshare_wishlist,
+        group_feed::group_feed,
+        set_follow_settings::set_follow_settings,
+        follow_user::follow_user,
+        unfollow_user::unfollow_user,
+        list_follow_requests::list_follow_requests,
+        answer_follow_request::answer_follow_request,
+        block_user::block_user,
+        unblock_user::unblock_user,
+        follow_feed::follow_feed,
+        set_profile::set_profile,
+        suggest_occasions::suggest_occasions,
+        find_profile::find_profile,
+        request_account_confirmation::request_account_confirmation,
+        delete_account::delete_account,
+        cancel_account_deletion::cancel_account_deletion,
+        export_account::export_account,
+        upload_image::upload_image,
+        find_image::find_image,
+    )
+)]
+pub struct ApiDoc;
+
+#[cfg(test)]
+mod tests {
+    use utoipa::openapi::HttpMethod;
+
+    use super::*;
+
+    /// The `(method, path)` of every `.route(...)` in `api_routes`, read from its source since
+    /// an axum router cannot list its routes.
+    fn api_routes() -> Vec<(String, String)> {
+        let source = include_str!("handlers.rs");
+        let start = source
+            .find("pub fn api_routes")
+            .expect("api_routes is defined in handlers.rs");
+        let mut routes = Vec::new();
+        for route in source[start..].split(".route(").skip(1) {
+            let path = route.split('"').nth(1).expect("route has a path");
+            for method in ["get", "post", "put", "delete", "patch"] {
+                let call = format!("{}(", method);
+                let calls = route.match_indices(&call).filter(|(index, _)| {
+                    !route[..*index].ends_with(|c: char| c.is_alphanumeric() || c == '_')
+                });
+                for _ in calls {
+                    routes.push((method.to_string(), path.to_string()));
+                }
+            }
+        }
+        routes
+    }
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let spec = ApiDoc::openapi();
+        let routes = api_routes();
+        assert!(routes.len() > 50, "api_routes could not be read");
+        let missing: Vec<String> = routes
+            .iter()
+            .filter(|(method, path)| {
+                let method = match method.as_str() {
+                    "get" => HttpMethod::Get,
+                    "post" => HttpMethod::Post,
+                    "put" => HttpMethod::Put,
+                    "delete" => HttpMethod::Delete,
+                    _ => HttpMethod::Patch,
+                };
+                let item = spec.paths.paths.get(path.as_str());
+                item.is_none_or(|item| match method {
+                    HttpMethod::Get => item.get.is_none(),
+                    HttpMethod::Post => item.post.is_none(),
+                    HttpMethod::Put => item.put.is_none(),
+                    HttpMethod::Delete => item.delete.is_none(),
+                    _ => item.patch.is_none(),
+                })
+            })
+            .map(|(method, path)| format!("{} {}", method.to_uppercase(), path))
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "routes missing from the OpenAPI document: {:?}",
+            missing
+        );
+    }
+}
@@ -0,0 +1,368 @@
+/*
+Module `pages` specifies the HTML pages of wishlists, for the people a wishlist is shared with who
+do not use the app. A page shows a public wishlist, or a private one to whoever has its share link,
+at `/w/{slug}`. Visitors have no account: a random guest ID kept in a cookie stands for them, so
+they can reserve items with plain forms and find their reservations when they come back.
+*/
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::extract::rejection::{FormRejection, QueryRejection};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Extension, Form, Router};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::application::UseCases;
+use crate::domain::{
+    Item, ReserveItemRequest, ShareToken, ViewSharedWishlistError, ViewSharedWishlistRequest,
+    WishlistView,
+};
+
+use super::handlers::ApiError;
+use super::problem::{Problem, ProblemType};
+use super::AppState;
+
+/// The cookie holding the guest ID of a visitor.
+const GUEST_COOKIE: &str = "guest_id";
+
+/// How long a visitor keeps their guest ID, and so their reservations: 400 days, the longest
+/// browsers allow.
+const GUEST_COOKIE_MAX_AGE_SECS: u64 = 400 * 24 * 60 * 60;
+
+impl From<ViewSharedWishlistError> for ApiError {
+    fn from(e: ViewSharedWishlistError) -> Self {
+        match e {
+            ViewSharedWishlistError::WishlistDoesNotExist { slug } => Self::NotFound(Problem::new(
+                ProblemType::WishlistNotFound,
+                format!("Wishlist {} does not exist", slug),
+            )),
+            ViewSharedWishlistError::Unkown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+                Self::InternalServerError("Internal server error".to_string())
+            }
+        }
+    }
+}
+
+/// Where the server is reached from outside, for the absolute links of link previews.
+#[derive(Debug, Clone)]
+struct PublicUrl(Arc<str>);
+
+/// The routes of the wishlist pages, to merge at the root, outside `/api`.
+pub fn page_routes<UC: UseCases>(public_url: &str) -> Router<AppState<UC>> {
+    Router::new()
+        .route("/w/{slug}", get(wishlist_page::<UC>))
+        .route(
+            "/w/{slug}/items/{item_id}/reservation",
+            post(reserve_from_page::<UC>),
+        )
+        .layer(Extension(PublicUrl(
+            public_url.trim_end_matches('/').into(),
+        )))
+}
+
+/// An error shown as an HTML page, with the status and the problem the API would report.
+#[derive(Debug)]
+struct PageError(ApiError);
+
+impl<E: Into<ApiError>> From<E> for PageError {
+    fn from(e: E) -> Self {
+        Self(e.into())
+    }
+}
+
+#[derive(Template)]
+#[template(path = "error.html")]
+struct ErrorTemplate<'a> {
+    title: &'a str,
+    detail: &'a str,
+}
+
+impl IntoResponse for PageError {
+    fn into_response(self) -> Response {
+        let (status, details) = self.0.into_problem_details();
+        let page = ErrorTemplate {
+            title: details.title(),
+            detail: details.detail(),
+        };
+        match page.render() {
+            Ok(html) => (status, Html(html)).into_response(),
+            Err(err) => {
+                tracing::error!("{}", err);
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+impl From<askama::Error> for ApiError {
+    fn from(e: askama::Error) -> Self {
+        Self::InternalServerError(e.to_string())
+    }
+}
+
+/// The query string of a page, with the token of the share link if the page was reached by one.
+#[derive(Debug, Clone, Deserialize)]
+struct PageQuery {
+    token: Option<String>,
+}
+
+/// The form of the reservation buttons.
+#[derive(Debug, Clone, Deserialize)]
+struct ReservationForm {
+    token: Option<String>,
+    reserved: bool,
+}
+
+/// How an item stands for the visitor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reservation {
+    Available,
+    ReservedByViewer,
+    ReservedByOther,
+    Received,
+}
+
+struct ItemCard {
+    id: Uuid,
+    title: String,
+    link_url: String,
+    image: Option<String>,
+    price: Option<String>,
+    notes: Option<String>,
+    reservation: Reservation,
+    reservation_path: String,
+}
+
+impl ItemCard {
+    fn new(item: &Item, slug: &str, viewer_id: Uuid) -> Self {
+        let reservation = match item.reserved_by() {
+            _ if item.received() => Reservation::Received,
+            Some(reserver) if reserver == viewer_id => Reservation::ReservedByViewer,
+            Some(_) => Reservation::ReservedByOther,
+            None => Reservation::Available,
+        };
+        Self {
+            id: item.id(),
+            title: item.title().to_string(),
+            link_url: item.link_url().to_string(),
+            image: image_path(item),
+            price: item.price().map(ToString::to_string),
+            notes: item.notes().map(ToString::to_string),
+            reservation,
+            reservation_path: format!("{}/items/{}/reservation", page_path(slug, None), item.id()),
+        }
+    }
+}
+
+/// The image of an item: the copy kept by the server if there is one, or else the remote one.
+fn image_path(item: &Item) -> Option<String> {
+    match (item.image_id(), item.image_url()) {
+        (Some(image_id), _) => Some(format!("/api/images/{}/medium", image_id)),
+        (None, Some(image_url)) => Some(image_url.to_string()),
+        (None, None) => None,
+    }
+}
+
+#[derive(Template)]
+#[template(path = "wishlist.html")]
+struct WishlistTemplate {
+    name: String,
+    description: String,
+    url: String,
+    image: Option<String>,
+    archived: bool,
+    token: Option<String>,
+    items: Vec<ItemCard>,
+}
+
+impl WishlistTemplate {
+    fn new(view: &WishlistView, viewer_id: Uuid, token: Option<&str>, public_url: &str) -> Self {
+        let wishlist = view.wishlist();
+        let slug = wishlist.slug().to_string();
+        let items: Vec<ItemCard> = view
+            .items()
+            .iter()
+            .map(|item| ItemCard::new(item, &slug, viewer_id))
+            .collect();
+        let available = items
+            .iter()
+            .filter(|item| item.reservation == Reservation::Available)
+            .count();
+        Self {
+            name: wishlist.name().to_string(),
+            description: format!(
+                "{} {}, {} still available",
+                items.len(),
+                if items.len() == 1 { "wish" } else { "wishes" },
+                available
+            ),
+            url: format!("{}{}", public_url, page_path(&slug, token)),
+            image: items
+                .iter()
+                .find_map(|item| item.image.as_deref())
+                .map(|image| absolute_url(public_url, image)),
+            archived: wishlist.archived(),
+            token: token.map(ToString::to_string),
+            items,
+        }
+    }
+}
+
+/// The path of the page of a wishlist, with the token of its share link if any. Slugs keep every
+/// character of the name but spaces, so they are percent-encoded.
+pub(crate) fn page_path(slug: &str, token: Option<&str>) -> String {
+    let path = format!(
+        "/w/{}",
+        url::form_urlencoded::byte_serialize(slug.as_bytes()).collect::<String>()
+    );
+    match token {
+        Some(token) => format!(
+            "{}?{}",
+            path,
+            url::form_urlencoded::Serializer::new(String::new())
+                .append_pair("token", token)
+                .finish()
+        ),
+        None => path,
+    }
+}
+
+/// Link previews need absolute URLs: paths on this server are made absolute.
+fn absolute_url(public_url: &str, url: &str) -> String {
+    if url.starts_with('/') {
+        format!("{}{}", public_url, url)
+    } else {
+        url.to_string()
+    }
+}
+
+/// The guest ID of the visitor, from their cookie, or a new one if they have none. The second
+/// member is the `Set-Cookie` header to send when the guest ID is new.
+fn guest_id(headers: &HeaderMap) -> (Uuid, Option<HeaderValue>) {
+    let existing = headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == GUEST_COOKIE)
+        .and_then(|(_, value)| Uuid::parse_str(value).ok());
+    match existing {
+        Some(guest_id) => (guest_id, None),
+        None => {
+            let guest_id = Uuid::new_v4();
+            let cookie = format!(
+                "{}={}; Path=/w; Max-Age={}; HttpOnly; SameSite=Lax",
+                GUEST_COOKIE, guest_id, GUEST_COOKIE_MAX_AGE_SECS
+            );
+            (guest_id, HeaderValue::from_str(&cookie).ok())
+        }
+    }
+}
+
+/// Adds the `Set-Cookie` header of a new guest ID to a response.
+fn with_guest_cookie(response: impl IntoResponse, cookie: Option<HeaderValue>) -> Response {
+    let mut response = response.into_response();
+    if let Some(cookie) = cookie {
+        response.headers_mut().insert(header::SET_COOKIE, cookie);
+    }
+    response
+}
+
+/// Shows the page of a wishlist: its items, with their images and prices, and a button to reserve
+/// each available item, or to cancel the reservations of the visitor.
+async fn wishlist_page<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    Extension(PublicUrl(public_url)): Extension<PublicUrl>,
+    Path(slug): Path<String>,
+    query: Result<Query<PageQuery>, QueryRejection>,
+    headers: HeaderMap,
+) -> Result<Response, PageError> {
+    let Query(query) = query?;
+    let (viewer_id, cookie) = guest_id(&headers);
+    let req = ViewSharedWishlistRequest::new(slug, viewer_id)
+        .with_share_token(query.token.as_deref().map(ShareToken::from));
+    let view = state.services.view_shared_wishlist(&req).await?;
+    let page = WishlistTemplate::new(&view, viewer_id, query.token.as_deref(), &public_url);
+    Ok(with_guest_cookie(Html(page.render()?), cookie))
+}
+
+/// Reserves an item for the visitor, or cancels their reservation, then goes back to the page.
+async fn reserve_from_page<UC: UseCases>(
+    State(state): State<AppState<UC>>,
+    Path((slug, item_id)): Path<(String, String)>,
+    headers: HeaderMap,
+    form: Result<Form<ReservationForm>, FormRejection>,
+) -> Result<Response, PageError> {
+    let Ok(item_id) = Uuid::parse_str(&item_id) else {
+        return Err(PageError(ApiError::NotFound(Problem::new(
+            ProblemType::ItemNotFound,
+            format!("Item ID {} does not exist", item_id),
+        ))));
+    };
+    let Form(form) = form.map_err(|rejection| {
+        ApiError::BadRequest(Problem::new(
+            ProblemType::MalformedRequest,
+            rejection.body_text(),
+        ))
+    })?;
+    let (guest_id, cookie) = guest_id(&headers);
+    let req = ReserveItemRequest::new(item_id, guest_id, form.reserved)
+        .with_share_token(form.token.as_deref().map(ShareToken::from));
+    state.services.reserve_item(&req).await?;
+    Ok(with_guest_cookie(
+        Redirect::to(&page_path(&slug, form.token.as_deref())),
+        cookie,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guest_id() {
+        let guest = Uuid::new_v4();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(&format!("theme=dark; guest_id={}", guest)).unwrap(),
+        );
+        assert_eq!(guest_id(&headers), (guest, None));
+
+        headers.insert(header::COOKIE, HeaderValue::from_static("guest_id=forged"));
+        let (new_guest, cookie) = guest_id(&headers);
+        assert_ne!(new_guest, guest);
+        let cookie = cookie.unwrap();
+        let cookie = cookie.to_str().unwrap();
+        assert!(cookie.starts_with(&format!("guest_id={};", new_guest)));
+        assert!(cookie.contains("HttpOnly"));
+    }
+
+    #[test]
+    fn test_page_links() {
+        assert_eq!(page_path("birthday-1a2b3c4d", None), "/w/birthday-1a2b3c4d");
+        assert_eq!(
+            page_path("mom's-50th?-1a2b3c4d", None),
+            "/w/mom%27s-50th%3F-1a2b3c4d"
+        );
+        assert_eq!(
+            page_path("birthday-1a2b3c4d", Some("a&b")),
+            "/w/birthday-1a2b3c4d?token=a%26b"
+        );
+        assert_eq!(
+            absolute_url("https://example.com", "/api/images/1/medium"),
+            "https://example.com/api/images/1/medium"
+        );
+        assert_eq!(
+            absolute_url("https://example.com", "https://shop.example/scarf.png"),
+            "https://shop.example/scarf.png"
+        );
+    }
+}
@@ -0,0 +1,447 @@
+/*
+Module `problem` describes how errors are reported to API clients: as problem details
+(RFC 9457) served with the `application/problem+json` media type.
+*/
+
+use std::fmt::Display;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// The media type of error responses.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// The prefix of every problem type URI.
+const PROBLEM_TYPE_PREFIX: &str = "/problems/";
+
+/// The kinds of problem reported by the API.
+///
+/// Each kind is identified by a stable type URI that clients can match on, while the `detail` of
+/// a problem describes the specific occurrence and may change between releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProblemType {
+    Internal,
+    MalformedRequest,
+    InvalidRequest,
+    PayloadTooLarge,
+    BatchTooLarge,
+    UnsupportedMediaType,
+    VersionMismatch,
+    IdempotencyKeyReused,
+    RateLimited,
+    AccountLockedOut,
+    UserNotFound,
+    UserAlreadyExists,
+    WishlistNotFound,
+    WishlistArchived,
+    WishlistNotVisible,
+    WishlistNotShared,
+    NotWishlistOwner,
+    ItemNotFound,
+    ItemAlreadyInWishlist,
+    ItemNotInWishlist,
+    ItemTitleMissing,
+    DuplicateOrderEntry,
+    ItemAlreadyReserved,
+    OwnItemReservation,
+    NotItemReserver,
+    SectionNotFound,
+    SectionAlreadyExists,
+    ImageNotFound,
+    ImageTooLarge,
+    ImageDimensionsTooLarge,
+    UnsupportedImageFormat,
+    ReminderNotFound,
+    ExchangeNotFound,
+    ExchangeAlreadyDrawn,
+    ExchangeNotDrawn,
+    NotExchangeOrganizer,
+    NotExchangeParticipant,
+    SelfExclusion,
+    TooFewParticipants,
+    NoValidDraw,
+    GroupNotFound,
+    NotGroupMember,
+    NotGroupAdmin,
+    AlreadyGroupMember,
+    LastGroupAdmin,
+    InvitationNotFound,
+    InvitationAlreadyAnswered,
+    FollowRequestNotFound,
+    SelfFollow,
+    FollowBlocked,
+    NotFollowing,
+    SelfBlock,
+    NotBlocked,
+    BirthdayInFuture,
+    AccountDeletionNotScheduled,
+    InvalidConfirmationToken,
+    UserHasNoEmail,
+    WebhookNotFound,
+}
+
+impl ProblemType {
+    /// The last segment of the type URI. Slugs are never renamed once released.
+    pub fn slug(self) -> &'static str {
+        use ProblemType::*;
+
+        match self {
+            Internal => "internal-error",
+            MalformedRequest => "malformed-request",
+            InvalidRequest => "invalid-request",
+            PayloadTooLarge => "payload-too-large",
+            BatchTooLarge => "batch-too-large",
+            UnsupportedMediaType => "unsupported-media-type",
+            VersionMismatch => "version-mismatch",
+            IdempotencyKeyReused => "idempotency-key-reused",
+            RateLimited => "rate-limited",
+            AccountLockedOut => "account-locked-out",
+            UserNotFound => "user-not-found",
+            UserAlreadyExists => "user-already-exists",
+            WishlistNotFound => "wishlist-not-found",
+            WishlistArchived => "wishlist-archived",
+            WishlistNotVisible => "wishlist-not-visible",
+            WishlistNotShared => "wishlist-not-shared",
+            NotWishlistOwner => "not-wishlist-owner",
+            ItemNotFound => "item-not-found",
+            ItemAlreadyInWishlist => "item-already-in-wishlist",
+            ItemNotInWishlist => "item-not-in-wishlist",
+            ItemTitleMissing => "item-title-missing",
+            DuplicateOrderEntry => "duplicate-order-entry",
+            ItemAlreadyReserved => "item-already-reserved",
+            OwnItemReservation => "own-item-reservation",
+            NotItemReserver => "not-item-reserver",
+            SectionNotFound => "section-not-found",
+            SectionAlreadyExists => "section-already-exists",
+            ImageNotFound => "image-not-found",
+            ImageTooLarge => "image-too-large",
+            ImageDimensionsTooLarge => "image-dimensions-too-large",
+            UnsupportedImageFormat => "unsupported-image-format",
+            ReminderNotFound => "reminder-not-found",
+            ExchangeNotFound => "exchange-not-found",
+            ExchangeAlreadyDrawn => "exchange-already-drawn",
+            ExchangeNotDrawn => "exchange-not-drawn",
+            NotExchangeOrganizer => "not-exchange-organizer",
+            NotExchangeParticipant => "not-exchange-participant",
+            SelfExclusion => "self-exclusion",
+            TooFewParticipants => "too-few-participants",
+            NoValidDraw => "no-valid-draw",
+            GroupNotFound => "group-not-found",
+            NotGroupMember => "not-group-member",
+            NotGroupAdmin => "not-group-admin",
+            AlreadyGroupMember => "already-group-member",
+            LastGroupAdmin => "last-group-admin",
+            InvitationNotFound => "invitation-not-found",
+            InvitationAlreadyAnswered => "invitation-already-answered",
+            FollowRequestNotFound => "follow-request-not-found",
+            SelfFollow => "self-follow",
+            FollowBlocked => "follow-blocked",
+            NotFollowing => "not-following",
+            SelfBlock => "self-block",
+            NotBlocked => "not-blocked",
+            BirthdayInFuture => "birthday-in-future",
+            AccountDeletionNotScheduled => "account-deletion-not-scheduled",
+            InvalidConfirmationToken => "invalid-confirmation-token",
+            UserHasNoEmail => "user-has-no-email",
+            WebhookNotFound => "webhook-not-found",
+        }
+    }
+
+    /// A short summary of the problem, the same for every occurrence.
+    pub fn title(self) -> &'static str {
+        use ProblemType::*;
+
+        match self {
+            Internal => "Internal server error",
+            MalformedRequest => "The request could not be read",
+            InvalidRequest => "The request has invalid fields",
+            PayloadTooLarge => "The request body is too large",
+            BatchTooLarge => "The batch holds too many queries",
+            UnsupportedMediaType => "The request body has an unsupported media type",
+            VersionMismatch => "The resource changed since the client read it",
+            IdempotencyKeyReused => "The idempotency key was used for another request",
+            RateLimited => "Too many requests were sent",
+            AccountLockedOut => "The account is locked out after too many failed logins",
+            UserNotFound => "The user does not exist",
+            UserAlreadyExists => "The user already exists",
+            WishlistNotFound => "The wishlist does not exist",
+            WishlistArchived => "The wishlist is archived",
+            WishlistNotVisible => "The wishlist is not visible to the user",
+            WishlistNotShared => "The wishlist is not shared with the group",
+            NotWishlistOwner => "The wishlist belongs to someone else",
+            ItemNotFound => "The item does not exist",
+            ItemAlreadyInWishlist => "The wishlist already has an item with the same link",
+            ItemNotInWishlist => "The item is not in the wishlist",
+            ItemTitleMissing => "The item has no title",
+            DuplicateOrderEntry => "The order lists an entry more than once",
+            ItemAlreadyReserved => "The item is already reserved",
+            OwnItemReservation => "Owners cannot reserve their own items",
+            NotItemReserver => "The item is not reserved by the user",
+            SectionNotFound => "The section does not exist",
+            SectionAlreadyExists => "The section already exists",
+            ImageNotFound => "The image does not exist",
+            ImageTooLarge => "The image is too large",
+            ImageDimensionsTooLarge => "The image dimensions are too large",
+            UnsupportedImageFormat => "The image format is not supported",
+            ReminderNotFound => "The reminder does not exist",
+            ExchangeNotFound => "The exchange does not exist",
+            ExchangeAlreadyDrawn => "The exchange has already been drawn",
+            ExchangeNotDrawn => "The exchange has not been drawn yet",
+            NotExchangeOrganizer => "The user does not organize the exchange",
+            NotExchangeParticipant => "The user does not participate in the exchange",
+            SelfExclusion => "Participants cannot be excluded from themselves",
+            TooFewParticipants => "The exchange has too few participants",
+            NoValidDraw => "No draw satisfies the exclusions",
+            GroupNotFound => "The group does not exist",
+            NotGroupMember => "The user is not a member of the group",
+            NotGroupAdmin => "The user is not an admin of the group",
+            AlreadyGroupMember => "The user is already a member of the group",
+            LastGroupAdmin => "The group needs at least one admin",
+            InvitationNotFound => "The invitation does not exist",
+            InvitationAlreadyAnswered => "The invitation has already been answered",
+            FollowRequestNotFound => "The follow request does not exist",
+            SelfFollow => "Users cannot follow themselves",
+            FollowBlocked => "The user cannot be followed",
+            NotFollowing => "The user is not followed",
+            SelfBlock => "Users cannot block themselves",
+            NotBlocked => "The user is not blocked",
+            BirthdayInFuture => "The birthday is in the future",
+            AccountDeletionNotScheduled => "The account is not scheduled for deletion",
+            InvalidConfirmationToken => "The confirmation token is invalid or expired",
+            UserHasNoEmail => "The user has no email address",
+            WebhookNotFound => "The webhook does not exist",
+        }
+    }
+
+    /// The type URI of the problem, relative to the root of the server.
+    pub fn uri(self) -> String {
+        format!("{}{}", PROBLEM_TYPE_PREFIX, self.slug())
+    }
+}
+
+/// A single occurrence of a problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    problem_type: ProblemType,
+    detail: String,
+    errors: Vec<InvalidField>,
+}
+
+impl Problem {
+    pub fn new(problem_type: ProblemType, detail: impl Into<String>) -> Self {
+        Self {
+            problem_type,
+            detail: detail.into(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// A request with invalid fields. The detail sums up every invalid field.
+    pub fn invalid_fields(errors: Vec<InvalidField>) -> Self {
+        let detail = errors
+            .iter()
+            .map(|error| error.detail.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        Self {
+            problem_type: ProblemType::InvalidRequest,
+            detail,
+            errors,
+        }
+    }
+}
+
+/// A field of a request that could not be parsed, located by a JSON pointer into the request
+/// body, by the name of a query parameter or by the name of a header.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct InvalidField {
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pointer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    header: Option<String>,
+}
+
+impl InvalidField {
+    /// An invalid member of the request body. `pointer` is a JSON pointer (RFC 6901) such as
+    /// `/attributes/0/key`, rendered as a URI fragment as in RFC 9457.
+    pub fn body(pointer: impl Display, detail: impl Into<String>) -> Self {
+        Self {
+            detail: detail.into(),
+            pointer: Some(format!("#{}", pointer)),
+            parameter: None,
+            header: None,
+        }
+    }
+
+    /// An invalid query parameter.
+    pub fn parameter(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            detail: detail.into(),
+            pointer: None,
+            parameter: Some(name.into()),
+            header: None,
+        }
+    }
+
+    /// An invalid request header.
+    pub fn header(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            detail: detail.into(),
+            pointer: None,
+            parameter: None,
+            header: Some(name.into()),
+        }
+    }
+}
+
+/// Collects the errors of every invalid field of a request, so that they are reported at once
+/// rather than one request at a time.
+#[derive(Debug, Clone)]
+pub struct FieldErrors<E> {
+    errors: Vec<(FieldLocation, E)>,
+}
+
+#[derive(Debug, Clone)]
+enum FieldLocation {
+    Body(String),
+    Parameter(String),
+    Header(String),
+}
+
+impl<E> Default for FieldErrors<E> {
+    fn default() -> Self {
+        Self { errors: Vec::new() }
+    }
+}
+
+impl<E> FieldErrors<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value parsed from the body member at `pointer`, or records its error.
+    pub fn check_field<T, F: Into<E>>(
+        &mut self,
+        pointer: impl Into<String>,
+        result: Result<T, F>,
+    ) -> Option<T> {
+        result
+            .map_err(|err| {
+                self.errors
+                    .push((FieldLocation::Body(pointer.into()), err.into()))
+            })
+            .ok()
+    }
+
+    /// Returns the value parsed from the query parameter `name`, or records its error.
+    pub fn check_parameter<T, F: Into<E>>(
+        &mut self,
+        name: impl Into<String>,
+        result: Result<T, F>,
+    ) -> Option<T> {
+        result
+            .map_err(|err| {
+                self.errors
+                    .push((FieldLocation::Parameter(name.into()), err.into()))
+            })
+            .ok()
+    }
+
+    /// Returns the value parsed from the header `name`, or records its error.
+    pub fn check_header<T, F: Into<E>>(
+        &mut self,
+        name: impl Into<String>,
+        result: Result<T, F>,
+    ) -> Option<T> {
+        result
+            .map_err(|err| {
+                self.errors
+                    .push((FieldLocation::Header(name.into()), err.into()))
+            })
+            .ok()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl<E: Display> FieldErrors<E> {
+    pub fn into_invalid_fields(self) -> Vec<InvalidField> {
+        self.errors
+            .into_iter()
+            .map(|(location, err)| match location {
+                FieldLocation::Body(pointer) => InvalidField::body(pointer, err.to_string()),
+                FieldLocation::Parameter(name) => InvalidField::parameter(name, err.to_string()),
+                FieldLocation::Header(name) => InvalidField::header(name, err.to_string()),
+            })
+            .collect()
+    }
+}
+
+/// The body of every error response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ProblemDetails {
+    /// The stable URI identifying the kind of problem, e.g. `/problems/user-not-found`.
+    #[serde(rename = "type")]
+    problem_type: String,
+    title: String,
+    status: u16,
+    detail: String,
+    /// Every invalid field of the request, when the request could not be parsed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<InvalidField>,
+}
+
+impl ProblemDetails {
+    pub fn new(status: u16, problem: Problem) -> Self {
+        Self {
+            problem_type: problem.problem_type.uri(),
+            title: problem.problem_type.title().to_string(),
+            status,
+            detail: problem.detail,
+            errors: problem.errors,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_errors_collect_every_invalid_field() {
+        let mut errors = FieldErrors::<String>::new();
+        let email = errors.check_field("/email", Err::<(), _>("email is invalid"));
+        let name = errors.check_field("/name", Ok::<_, String>("name"));
+        let limit = errors.check_parameter("limit", Err::<(), _>("limit is invalid"));
+        assert_eq!((email, name, limit), (None, Some("name"), None));
+
+        let problem = Problem::invalid_fields(errors.into_invalid_fields());
+        let details = serde_json::to_value(ProblemDetails::new(422, problem)).unwrap();
+        assert_eq!(
+            details,
+            serde_json::json!({
+                "type": "/problems/invalid-request",
+                "title": "The request has invalid fields",
+                "status": 422,
+                "detail": "email is invalid; limit is invalid",
+                "errors": [
+                    { "detail": "email is invalid", "pointer": "#/email" },
+                    { "detail": "limit is invalid", "parameter": "limit" },
+                ],
+            })
+        );
+    }
+}
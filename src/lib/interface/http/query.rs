@@ -0,0 +1,243 @@
+/*
+Module `query` parses the query string parameters shared by the listing routes: the page to read,
+the sort order, and the wishlist and price filters. Handlers list these parameters in their own
+query structs and parse them here, so every listing reports the same errors for them.
+*/
+
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::domain::{
+    Cursor, CursorInvalidError, OccasionInvalidError, OccasionKind, PageLimit,
+    PageLimitInvalidError, PageRequest, PriceRange, PriceRangeInvalidError, SortDirection,
+    WishlistFilter, WishlistSort, WishlistSortInvalidError, WishlistSortKey, WishlistVisibility,
+};
+
+use super::problem::FieldErrors;
+
+#[derive(Debug, Clone, Error)]
+pub enum ParseListingQueryError {
+    #[error(transparent)]
+    Cursor(#[from] CursorInvalidError),
+    #[error(transparent)]
+    Limit(#[from] PageLimitInvalidError),
+    #[error("order {0} is invalid, expected asc or desc")]
+    Order(String),
+    #[error("visibility {0} is invalid, expected public or private")]
+    Visibility(String),
+    #[error(transparent)]
+    Occasion(#[from] OccasionInvalidError),
+    #[error("price {0} is invalid")]
+    Price(String),
+    #[error(transparent)]
+    PriceRange(#[from] PriceRangeInvalidError),
+    #[error("sort {} is invalid, expected updated, created or name", .0.invalid_sort)]
+    WishlistSort(#[from] WishlistSortInvalidError),
+}
+
+/// Parses the `cursor` and `limit` parameters into the page to read, the first one by default.
+pub fn parse_page<E: From<ParseListingQueryError>>(
+    errors: &mut FieldErrors<E>,
+    cursor: Option<&str>,
+    limit: Option<usize>,
+) -> Option<PageRequest> {
+    let cursor = errors.check_parameter(
+        "cursor",
+        cursor
+            .map(str::parse::<Cursor>)
+            .transpose()
+            .map_err(ParseListingQueryError::from),
+    );
+    let limit = errors.check_parameter(
+        "limit",
+        limit
+            .map(PageLimit::new)
+            .transpose()
+            .map_err(ParseListingQueryError::from),
+    );
+    Some(PageRequest::new(cursor?, limit?.unwrap_or_default()))
+}
+
+/// Parses the `order` parameter, `asc` or `desc`.
+pub fn parse_order<E: From<ParseListingQueryError>>(
+    errors: &mut FieldErrors<E>,
+    order: Option<&str>,
+) -> Option<Option<SortDirection>> {
+    errors.check_parameter(
+        "order",
+        match order {
+            None => Ok(None),
+            Some("asc") => Ok(Some(SortDirection::Ascending)),
+            Some("desc") => Ok(Some(SortDirection::Descending)),
+            Some(order) => Err(ParseListingQueryError::Order(order.to_string())),
+        },
+    )
+}
+
+/// Parses the `visibility` and `occasion` parameters into a filter on wishlists.
+pub fn parse_wishlist_filter<E: From<ParseListingQueryError>>(
+    errors: &mut FieldErrors<E>,
+    visibility: Option<&str>,
+    occasion: Option<&str>,
+) -> Option<WishlistFilter> {
+    let visibility = errors.check_parameter(
+        "visibility",
+        visibility
+            .map(|visibility| {
+                visibility
+                    .parse::<WishlistVisibility>()
+                    .map_err(|err| ParseListingQueryError::Visibility(err.0))
+            })
+            .transpose(),
+    );
+    let occasion = errors.check_parameter(
+        "occasion",
+        occasion
+            .map(str::parse::<OccasionKind>)
+            .transpose()
+            .map_err(ParseListingQueryError::from),
+    );
+    Some(
+        WishlistFilter::default()
+            .with_visibility(visibility?)
+            .with_occasion(occasion?),
+    )
+}
+
+/// Parses the `sort` and `order` parameters of a wishlist listing. Wishlists are sorted by their
+/// last update by default, and each key has its own default order.
+pub fn parse_wishlist_sort<E: From<ParseListingQueryError>>(
+    errors: &mut FieldErrors<E>,
+    sort: Option<&str>,
+    order: Option<&str>,
+) -> Option<WishlistSort> {
+    let key = errors.check_parameter(
+        "sort",
+        sort.map(str::parse::<WishlistSortKey>)
+            .transpose()
+            .map_err(ParseListingQueryError::from),
+    );
+    let direction = parse_order(errors, order);
+    let key = key?.unwrap_or(WishlistSortKey::Updated);
+    Some(WishlistSort::new(
+        key,
+        direction?.unwrap_or(key.default_direction()),
+    ))
+}
+
+/// Parses the `min_price` and `max_price` parameters into a price range.
+pub fn parse_price_range<E: From<ParseListingQueryError>>(
+    errors: &mut FieldErrors<E>,
+    min_price: Option<&str>,
+    max_price: Option<&str>,
+) -> Option<PriceRange> {
+    let price = |price: Option<&str>| {
+        price
+            .map(|price| {
+                price
+                    .parse::<Decimal>()
+                    .map_err(|_| ParseListingQueryError::Price(price.to_string()))
+            })
+            .transpose()
+    };
+    let min = errors.check_parameter("min_price", price(min_price));
+    let max = errors.check_parameter("max_price", price(max_price));
+    let (min, max) = (min?, max?);
+    errors.check_parameter(
+        "min_price",
+        PriceRange::new(min, max).map_err(ParseListingQueryError::from),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::interface::http::problem::InvalidField;
+
+    type Errors = FieldErrors<ParseListingQueryError>;
+
+    #[test]
+    fn test_parse_page() {
+        let mut errors = Errors::new();
+        assert_eq!(
+            parse_page(&mut errors, None, None),
+            Some(PageRequest::default())
+        );
+        let cursor = Cursor::new(None, Uuid::now_v7());
+        let page = parse_page(&mut errors, Some(&cursor.to_string()), Some(5)).unwrap();
+        assert_eq!(page.cursor(), Some(&cursor));
+        assert_eq!(page.limit().get(), 5);
+        assert!(errors.is_empty());
+
+        assert!(parse_page(&mut errors, Some("nope"), Some(0)).is_none());
+        assert_eq!(
+            errors.into_invalid_fields(),
+            vec![
+                InvalidField::parameter("cursor", "Cursor nope is invalid"),
+                InvalidField::parameter("limit", "Limit 0 is not between 1 and 100"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_wishlist_sort() {
+        let mut errors = Errors::new();
+        assert_eq!(
+            parse_wishlist_sort(&mut errors, None, None),
+            Some(WishlistSort::default())
+        );
+        assert_eq!(
+            parse_wishlist_sort(&mut errors, Some("name"), None),
+            Some(WishlistSort::new(
+                WishlistSortKey::Name,
+                SortDirection::Ascending
+            ))
+        );
+        assert_eq!(
+            parse_wishlist_sort(&mut errors, Some("name"), Some("desc")),
+            Some(WishlistSort::new(
+                WishlistSortKey::Name,
+                SortDirection::Descending
+            ))
+        );
+        assert!(errors.is_empty());
+
+        assert!(parse_wishlist_sort(&mut errors, Some("colour"), Some("up")).is_none());
+        assert_eq!(
+            errors.into_invalid_fields(),
+            vec![
+                InvalidField::parameter(
+                    "sort",
+                    "sort colour is invalid, expected updated, created or name"
+                ),
+                InvalidField::parameter("order", "order up is invalid, expected asc or desc"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_filters() {
+        let mut errors = Errors::new();
+        assert!(parse_wishlist_filter(&mut errors, Some("public"), Some("birthday")).is_some());
+        let range = parse_price_range(&mut errors, Some("10"), Some("25.50")).unwrap();
+        assert_eq!(range.min(), Some(Decimal::from(10)));
+        assert!(errors.is_empty());
+
+        assert!(parse_wishlist_filter(&mut errors, Some("secret"), None).is_none());
+        assert!(parse_price_range(&mut errors, Some("cheap"), None).is_none());
+        assert!(parse_price_range(&mut errors, Some("30"), Some("20")).is_none());
+        assert_eq!(
+            errors.into_invalid_fields(),
+            vec![
+                InvalidField::parameter(
+                    "visibility",
+                    "visibility secret is invalid, expected public or private"
+                ),
+                InvalidField::parameter("min_price", "price cheap is invalid"),
+                InvalidField::parameter("min_price", "Minimum price 30 is above maximum price 20"),
+            ]
+        );
+    }
+}
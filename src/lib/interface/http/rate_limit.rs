@@ -0,0 +1,630 @@
+/*
+Module `rate_limit` throttles clients hammering the API. Every request takes a token from a bucket
+of its client IP and, when it acts for a user, from a bucket of that user. Buckets refill at a
+steady rate up to their capacity, so short bursts are allowed but sustained floods are not; a
+request finding a bucket empty is rejected with 429 Too Many Requests and told in `Retry-After`
+when to try again. Routes may have their own buckets, e.g. a small one for creating authors.
+
+The API has no sessions, so the user a request acts for is the one it claims, as for the handlers.
+A client may empty the bucket of another user that way, but only up to its own IP limits.
+
+Logins, and the creation of authors, are also protected from guessing: an account whose requests
+fail too many times in a row is locked out, for twice as long after every further failure.
+*/
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::body::Body;
+use axum::extract::{ConnectInfo, MatchedPath, Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::domain::Clock;
+
+use super::actor::{acting_user, buffer_body, login_account};
+use super::handlers::ApiError;
+use super::problem::{Problem, ProblemType};
+
+/// How many buckets, and accounts with failed logins, the in-memory store keeps at most. Past it,
+/// the least recently used one is dropped for a new one.
+const MAX_BUCKETS: usize = 100_000;
+
+/// The size of a token bucket and how fast it refills.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketRate {
+    capacity: u32,
+    refill_per_minute: u32,
+}
+
+impl BucketRate {
+    /// A bucket of `capacity` tokens, refilled with `refill_per_minute` tokens a minute, at least
+    /// one.
+    pub fn new(capacity: u32, refill_per_minute: u32) -> Self {
+        Self {
+            capacity,
+            refill_per_minute: refill_per_minute.max(1),
+        }
+    }
+
+    fn refill_per_second(&self) -> f64 {
+        f64::from(self.refill_per_minute) / 60.0
+    }
+}
+
+/// The limits of a route. Without a bucket, requests are not limited.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteLimits {
+    per_ip: Option<BucketRate>,
+    per_user: Option<BucketRate>,
+    lockout: bool,
+}
+
+impl RouteLimits {
+    pub fn new(per_ip: Option<BucketRate>, per_user: Option<BucketRate>) -> Self {
+        Self {
+            per_ip,
+            per_user,
+            lockout: false,
+        }
+    }
+
+    /// Marks the route as a login, whose failures lock the account out. A request fails with a
+    /// client error, e.g. 401 Unauthorized for a wrong password or 409 Conflict for a taken email.
+    pub fn with_lockout(self, lockout: bool) -> Self {
+        Self { lockout, ..self }
+    }
+}
+
+/// How long an account is locked out after failing to log in too many times in a row.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LockoutPolicy {
+    max_failures: u32,
+    base: TimeDelta,
+    max: TimeDelta,
+}
+
+impl LockoutPolicy {
+    /// Locks an account out for `base` after `max_failures` failures in a row, then twice as
+    /// long after every further failure, up to `max`.
+    pub fn new(max_failures: u32, base: TimeDelta, max: TimeDelta) -> Self {
+        Self {
+            max_failures: max_failures.max(1),
+            base,
+            max,
+        }
+    }
+
+    /// Returns how long an account is locked out after `failures` failures in a row, if it is.
+    pub fn lockout(&self, failures: u32) -> Option<TimeDelta> {
+        let doublings = failures.checked_sub(self.max_failures)?.min(30);
+        Some((self.base * (1 << doublings)).min(self.max))
+    }
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self::new(5, TimeDelta::seconds(30), TimeDelta::hours(1))
+    }
+}
+
+/// The limits of every route: the default ones, shared by the routes without their own.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitPolicy {
+    default: RouteLimits,
+    routes: HashMap<String, RouteLimits>,
+    lockout: LockoutPolicy,
+}
+
+impl RateLimitPolicy {
+    pub fn new(default: RouteLimits, lockout: LockoutPolicy) -> Self {
+        Self {
+            default,
+            routes: HashMap::new(),
+            lockout,
+        }
+    }
+
+    /// Gives the route `method path`, e.g. `POST /api/authors`, its own limits and buckets.
+    /// Path parameters are written as in the router, e.g. `/api/wishlists/{wishlist_id}/items`.
+    pub fn with_route(mut self, method: &str, path: &str, limits: RouteLimits) -> Self {
+        self.routes.insert(route(method, path), limits);
+        self
+    }
+
+    /// Returns the limits of `route` and the name of its buckets.
+    fn limits<'a>(&'a self, route: &'a str) -> (&'a str, &'a RouteLimits) {
+        match self.routes.get(route) {
+            Some(limits) => (route, limits),
+            None => ("*", &self.default),
+        }
+    }
+}
+
+fn route(method: &str, path: &str) -> String {
+    format!("{} {}", method.to_uppercase(), path)
+}
+
+/// The [RateLimitStore] trait defines where token buckets and login failures are kept, so that
+/// several servers may share them.
+pub trait RateLimitStore: Send + Sync + 'static {
+    /// Takes a token from the bucket `bucket` filling at `rate`, which starts full.
+    ///
+    /// # Errors
+    /// How long until the bucket has a token again, if it is empty.
+    fn take_token(
+        &self,
+        bucket: &str,
+        rate: BucketRate,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), TimeDelta>> + Send;
+    /// Returns until when `account` is locked out, if it is.
+    fn locked_until(
+        &self,
+        account: &str,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Option<DateTime<Utc>>> + Send;
+    /// Records a failed login of `account`, locking it out as `policy` says.
+    fn record_login_failure(
+        &self,
+        account: &str,
+        policy: LockoutPolicy,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = ()> + Send;
+    /// Records a successful login of `account`, which forgets its failures.
+    fn record_login_success(&self, account: &str) -> impl Future<Output = ()> + Send;
+}
+
+#[derive(Debug, Clone)]
+struct Bucket {
+    rate: BucketRate,
+    tokens: f64,
+    updated_at: DateTime<Utc>,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: DateTime<Utc>) {
+        let elapsed = (now - self.updated_at).as_seconds_f64().max(0.0);
+        self.tokens = (self.tokens + elapsed * self.rate.refill_per_second())
+            .min(f64::from(self.rate.capacity));
+        self.updated_at = now;
+    }
+}
+
+/// Entries of the [InMemoryRateLimitStore], with the order they were last used in.
+#[derive(Debug)]
+struct Recent<V> {
+    entries: HashMap<String, (V, u64)>,
+    recency: BTreeMap<u64, String>,
+    uses: u64,
+    max: usize,
+}
+
+impl<V> Recent<V> {
+    fn new(max: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            uses: 0,
+            max: max.max(1),
+        }
+    }
+
+    fn peek(&self, name: &str) -> Option<&V> {
+        self.entries.get(name).map(|(value, _)| value)
+    }
+
+    /// Returns the entry `name`, created with `new` if there is none, and marks it as the most
+    /// recently used. The least recently used entry is dropped for a new one if there are too many.
+    fn get(&mut self, name: &str, new: impl FnOnce() -> V) -> &mut V {
+        self.uses += 1;
+        let used = self.uses;
+        if let Some((_, last_used)) = self.entries.get(name) {
+            self.recency.remove(last_used);
+        } else {
+            while self.entries.len() >= self.max {
+                let Some((_, oldest)) = self.recency.pop_first() else {
+                    break;
+                };
+                self.entries.remove(&oldest);
+            }
+        }
+        self.recency.insert(used, name.to_string());
+        let (value, last_used) = self
+            .entries
+            .entry(name.to_string())
+            .or_insert_with(|| (new(), used));
+        *last_used = used;
+        value
+    }
+
+    fn remove(&mut self, name: &str) {
+        if let Some((_, used)) = self.entries.remove(name) {
+            self.recency.remove(&used);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct LoginFailures {
+    count: u32,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+/// The [InMemoryRateLimitStore] struct is an in-memory implementation of the [RateLimitStore]
+/// trait, for a single server.
+pub struct InMemoryRateLimitStore {
+    buckets: Mutex<Recent<Bucket>>,
+    failures: Mutex<Recent<LoginFailures>>,
+}
+
+impl InMemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self::with_max_buckets(MAX_BUCKETS)
+    }
+
+    /// A store keeping at most `max` buckets, and `max` accounts with failed logins, at least one.
+    pub fn with_max_buckets(max: usize) -> Self {
+        Self {
+            buckets: Mutex::new(Recent::new(max)),
+            failures: Mutex::new(Recent::new(max)),
+        }
+    }
+}
+
+impl Default for InMemoryRateLimitStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn take_token(
+        &self,
+        bucket: &str,
+        rate: BucketRate,
+        now: DateTime<Utc>,
+    ) -> Result<(), TimeDelta> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.get(bucket, || Bucket {
+            rate,
+            tokens: f64::from(rate.capacity),
+            updated_at: now,
+        });
+        bucket.rate = rate;
+        bucket.refill(now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        let wait = (1.0 - bucket.tokens) / rate.refill_per_second();
+        Err(TimeDelta::milliseconds((wait * 1000.0).ceil() as i64))
+    }
+
+    async fn locked_until(&self, account: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let failures = self.failures.lock().unwrap();
+        failures
+            .peek(account)
+            .and_then(|failures| failures.locked_until)
+            .filter(|until| *until > now)
+    }
+
+    async fn record_login_failure(&self, account: &str, policy: LockoutPolicy, now: DateTime<Utc>) {
+        let mut failures = self.failures.lock().unwrap();
+        let failures = failures.get(account, LoginFailures::default);
+        failures.count += 1;
+        failures.locked_until = policy.lockout(failures.count).map(|lockout| now + lockout);
+    }
+
+    async fn record_login_success(&self, account: &str) {
+        self.failures.lock().unwrap().remove(account);
+    }
+}
+
+/// The state of the [rate_limit] middleware.
+pub struct RateLimiter<S> {
+    store: Arc<S>,
+    policy: Arc<RateLimitPolicy>,
+    clock: Arc<dyn Clock>,
+    max_body_bytes: usize,
+}
+
+impl<S> RateLimiter<S> {
+    pub fn new(
+        store: Arc<S>,
+        policy: RateLimitPolicy,
+        clock: Arc<dyn Clock>,
+        max_body_bytes: usize,
+    ) -> Self {
+        Self {
+            store,
+            policy: Arc::new(policy),
+            clock,
+            max_body_bytes,
+        }
+    }
+}
+
+impl<S> Clone for RateLimiter<S> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            policy: self.policy.clone(),
+            clock: self.clock.clone(),
+            max_body_bytes: self.max_body_bytes,
+        }
+    }
+}
+
+/// Rejects a request with 429 Too Many Requests if a bucket of its client IP or acting user is
+/// empty, or if it logs into a locked out account. The outcome of a login is recorded to lock the
+/// account out after too many failures, which are client errors.
+pub async fn rate_limit<S: RateLimitStore>(
+    State(limiter): State<RateLimiter<S>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let now = limiter.clock.now();
+    let path = match request.extensions().get::<MatchedPath>() {
+        Some(path) => path.as_str(),
+        None => request.uri().path(),
+    };
+    let route = route(request.method().as_str(), path);
+    let (buckets, limits) = limiter.policy.limits(&route);
+    let client_ip = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip());
+    if let (Some(rate), Some(ip)) = (limits.per_ip, client_ip) {
+        let bucket = format!("ip {} {}", ip, buckets);
+        if let Err(retry_after) = limiter.store.take_token(&bucket, rate, now).await {
+            return too_many_requests(
+                retry_after,
+                Problem::new(
+                    ProblemType::RateLimited,
+                    format!("Too many requests from {}", ip),
+                ),
+            );
+        }
+    }
+    if limits.per_user.is_none() && !limits.lockout {
+        return next.run(request).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    let body = match buffer_body(body, limiter.max_body_bytes).await {
+        Ok(body) => body,
+        Err(err) => return err.into_response(),
+    };
+    let user = limits
+        .per_user
+        .and_then(|rate| Some((rate, acting_user(parts.uri.query(), &body)?)));
+    if let Some((rate, user)) = user {
+        let bucket = format!("user {} {}", user, buckets);
+        if let Err(retry_after) = limiter.store.take_token(&bucket, rate, now).await {
+            return too_many_requests(
+                retry_after,
+                Problem::new(
+                    ProblemType::RateLimited,
+                    format!("Too many requests for user {}", user),
+                ),
+            );
+        }
+    }
+    let account = limits.lockout.then(|| login_account(&body)).flatten();
+    if let Some(account) = &account {
+        if let Some(until) = limiter.store.locked_until(account, now).await {
+            return too_many_requests(
+                until - now,
+                Problem::new(
+                    ProblemType::AccountLockedOut,
+                    format!("Account {} is locked out until {}", account, until),
+                ),
+            );
+        }
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if let Some(account) = &account {
+        let status = response.status();
+        if status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS {
+            let policy = limiter.policy.lockout;
+            limiter
+                .store
+                .record_login_failure(account, policy, limiter.clock.now())
+                .await;
+        } else if status.is_success() {
+            limiter.store.record_login_success(account).await;
+        }
+    }
+    response
+}
+
+/// A 429 Too Many Requests response telling the client to retry after `retry_after`, in whole
+/// seconds.
+fn too_many_requests(retry_after: TimeDelta, problem: Problem) -> Response {
+    let seconds = (retry_after.num_milliseconds().max(0) as u64)
+        .div_ceil(1000)
+        .max(1);
+    (
+        [(header::RETRY_AFTER, HeaderValue::from(seconds))],
+        ApiError::TooManyRequests(problem),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::routing::post;
+    use axum::{Json, Router};
+
+    use super::*;
+    use crate::infrastructure::clock::ManualClock;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        time.parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_token_bucket() {
+        let store = InMemoryRateLimitStore::new();
+        let rate = BucketRate::new(2, 30);
+        let now = at("2027-03-14T08:00:00Z");
+
+        assert_eq!(store.take_token("a", rate, now).await, Ok(()));
+        assert_eq!(store.take_token("a", rate, now).await, Ok(()));
+        assert_eq!(
+            store.take_token("a", rate, now).await,
+            Err(TimeDelta::seconds(2))
+        );
+        assert_eq!(store.take_token("b", rate, now).await, Ok(()));
+
+        let later = now + TimeDelta::seconds(2);
+        assert_eq!(store.take_token("a", rate, later).await, Ok(()));
+        assert!(store.take_token("a", rate, later).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_max_buckets() {
+        let store = InMemoryRateLimitStore::with_max_buckets(2);
+        let rate = BucketRate::new(1, 1);
+        let now = at("2027-03-14T08:00:00Z");
+
+        assert_eq!(store.take_token("a", rate, now).await, Ok(()));
+        assert_eq!(store.take_token("b", rate, now).await, Ok(()));
+        assert!(store.take_token("a", rate, now).await.is_err());
+        // "b" is the least recently used bucket, dropped for "c" although it is empty.
+        assert_eq!(store.take_token("c", rate, now).await, Ok(()));
+        assert_eq!(store.buckets.lock().unwrap().entries.len(), 2);
+        assert!(store.take_token("a", rate, now).await.is_err());
+        assert_eq!(store.take_token("b", rate, now).await, Ok(()));
+    }
+
+    #[test]
+    fn test_lockout_policy() {
+        let policy = LockoutPolicy::new(3, TimeDelta::seconds(30), TimeDelta::minutes(2));
+        assert_eq!(policy.lockout(2), None);
+        assert_eq!(policy.lockout(3), Some(TimeDelta::seconds(30)));
+        assert_eq!(policy.lockout(4), Some(TimeDelta::seconds(60)));
+        assert_eq!(policy.lockout(5), Some(TimeDelta::seconds(120)));
+        assert_eq!(policy.lockout(40), Some(TimeDelta::seconds(120)));
+    }
+
+    /// Serves `router` behind the [rate_limit] middleware with `policy`, returning its address.
+    async fn serve(router: Router, policy: RateLimitPolicy, clock: Arc<ManualClock>) -> SocketAddr {
+        let limiter =
+            RateLimiter::new(Arc::new(InMemoryRateLimitStore::new()), policy, clock, 1024);
+        let router = router.layer(axum::middleware::from_fn_with_state(
+            limiter,
+            rate_limit::<InMemoryRateLimitStore>,
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn test_per_user_buckets() {
+        let clock = Arc::new(ManualClock::new(at("2027-03-14T08:00:00Z")));
+        let policy = RateLimitPolicy::new(
+            RouteLimits::new(None, Some(BucketRate::new(2, 1))),
+            LockoutPolicy::default(),
+        );
+        let router = Router::new().route("/items", post(|| async { StatusCode::CREATED }));
+        let address = serve(router, policy, clock).await;
+        let client = reqwest::Client::new();
+        let create = |user: &str| {
+            client
+                .post(format!("http://{}/items", address))
+                .header("content-type", "application/json")
+                .body(serde_json::json!({ "user_id": user }).to_string())
+                .send()
+        };
+
+        assert_eq!(create("alice").await.unwrap().status().as_u16(), 201);
+        assert_eq!(create("alice").await.unwrap().status().as_u16(), 201);
+        let limited = create("alice").await.unwrap();
+        assert_eq!(limited.status().as_u16(), 429);
+        assert_eq!(limited.headers()["retry-after"], "60");
+        assert_eq!(create("bob").await.unwrap().status().as_u16(), 201);
+    }
+
+    /// A login accepting the password `right` only.
+    async fn login(Json(body): Json<serde_json::Value>) -> StatusCode {
+        if body["password"] == "right" {
+            StatusCode::OK
+        } else {
+            StatusCode::UNAUTHORIZED
+        }
+    }
+
+    #[tokio::test]
+    async fn test_progressive_lockout() {
+        let clock = Arc::new(ManualClock::new(at("2027-03-14T08:00:00Z")));
+        let policy = RateLimitPolicy::new(
+            RouteLimits::default(),
+            LockoutPolicy::new(2, TimeDelta::seconds(30), TimeDelta::hours(1)),
+        )
+        .with_route("post", "/login", RouteLimits::default().with_lockout(true));
+        let router = Router::new().route("/login", post(login));
+        let address = serve(router, policy, clock.clone()).await;
+        let client = reqwest::Client::new();
+        let attempt = |password: &str| {
+            client
+                .post(format!("http://{}/login", address))
+                .header("content-type", "application/json")
+                .body(
+                    serde_json::json!({ "email": "alice@example.com", "password": password })
+                        .to_string(),
+                )
+                .send()
+        };
+
+        assert_eq!(attempt("wrong").await.unwrap().status().as_u16(), 401);
+        assert_eq!(attempt("wrong").await.unwrap().status().as_u16(), 401);
+        let locked = attempt("right").await.unwrap();
+        assert_eq!(locked.status().as_u16(), 429);
+        assert_eq!(locked.headers()["retry-after"], "30");
+
+        clock.advance(TimeDelta::seconds(30));
+        assert_eq!(attempt("wrong").await.unwrap().status().as_u16(), 401);
+        let locked = attempt("right").await.unwrap();
+        assert_eq!(locked.headers()["retry-after"], "60");
+
+        clock.advance(TimeDelta::seconds(60));
+        assert_eq!(attempt("right").await.unwrap().status().as_u16(), 200);
+        assert_eq!(attempt("wrong").await.unwrap().status().as_u16(), 401);
+        assert_eq!(attempt("right").await.unwrap().status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_max_login_failures() {
+        let store = InMemoryRateLimitStore::with_max_buckets(2);
+        let policy = LockoutPolicy::new(1, TimeDelta::seconds(30), TimeDelta::hours(1));
+        let now = at("2027-03-14T08:00:00Z");
+
+        store.record_login_failure("a", policy, now).await;
+        store.record_login_failure("b", policy, now).await;
+        store.record_login_failure("c", policy, now).await;
+        assert_eq!(store.failures.lock().unwrap().entries.len(), 2);
+        assert_eq!(store.locked_until("a", now).await, None);
+        assert_eq!(
+            store.locked_until("c", now).await,
+            Some(now + TimeDelta::seconds(30))
+        );
+
+        store.record_login_success("c").await;
+        assert_eq!(store.locked_until("c", now).await, None);
+    }
+}